@@ -0,0 +1,6 @@
+-- Create settings table to persist app preferences as key-value pairs
+CREATE TABLE settings (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
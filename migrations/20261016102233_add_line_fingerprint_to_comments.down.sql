@@ -0,0 +1,2 @@
+-- Remove line fingerprint column from comments table
+ALTER TABLE comments DROP COLUMN line_fingerprint;
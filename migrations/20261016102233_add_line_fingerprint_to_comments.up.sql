@@ -0,0 +1,2 @@
+-- Add line fingerprint column to comments table to relocate line comments when the diff changes
+ALTER TABLE comments ADD COLUMN line_fingerprint TEXT;
@@ -0,0 +1,23 @@
+-- Full-text index over comment content, kept in sync with the comments table by triggers
+CREATE VIRTUAL TABLE comments_fts USING fts5(
+    content,
+    content = 'comments',
+    content_rowid = 'rowid'
+);
+
+-- Index existing comments
+INSERT INTO comments_fts (rowid, content)
+SELECT rowid, content FROM comments;
+
+CREATE TRIGGER comments_fts_after_insert AFTER INSERT ON comments BEGIN
+    INSERT INTO comments_fts (rowid, content) VALUES (new.rowid, new.content);
+END;
+
+CREATE TRIGGER comments_fts_after_delete AFTER DELETE ON comments BEGIN
+    INSERT INTO comments_fts (comments_fts, rowid, content) VALUES ('delete', old.rowid, old.content);
+END;
+
+CREATE TRIGGER comments_fts_after_update AFTER UPDATE OF content ON comments BEGIN
+    INSERT INTO comments_fts (comments_fts, rowid, content) VALUES ('delete', old.rowid, old.content);
+    INSERT INTO comments_fts (rowid, content) VALUES (new.rowid, new.content);
+END;
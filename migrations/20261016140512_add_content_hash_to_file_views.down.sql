@@ -0,0 +1,2 @@
+-- Remove content hash column from file_views table
+ALTER TABLE file_views DROP COLUMN content_hash;
@@ -0,0 +1,2 @@
+-- Add content hash column to file_views table to reset the viewed status when the file changes
+ALTER TABLE file_views ADD COLUMN content_hash TEXT;
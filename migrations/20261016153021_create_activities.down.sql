@@ -0,0 +1,3 @@
+-- Drop activities table and its index
+DROP INDEX IF EXISTS idx_activities_review_id;
+DROP TABLE IF EXISTS activities;
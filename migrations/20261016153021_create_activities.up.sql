@@ -0,0 +1,12 @@
+-- Create activities table to keep a timeline of the actions on each review
+CREATE TABLE activities (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    review_id TEXT NOT NULL,
+    activity_type TEXT NOT NULL,
+    detail TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (review_id) REFERENCES reviews(id) ON DELETE CASCADE
+);
+
+-- Create index for efficient lookups
+CREATE INDEX idx_activities_review_id ON activities(review_id);
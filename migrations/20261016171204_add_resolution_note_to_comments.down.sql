@@ -0,0 +1,2 @@
+-- Remove resolution note column from comments table
+ALTER TABLE comments DROP COLUMN resolution_note;
@@ -0,0 +1,2 @@
+-- Add resolution note column to comments table to record how a comment was addressed
+ALTER TABLE comments ADD COLUMN resolution_note TEXT;
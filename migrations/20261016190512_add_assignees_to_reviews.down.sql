@@ -0,0 +1,2 @@
+-- Remove assignees column from reviews table
+ALTER TABLE reviews DROP COLUMN assignees;
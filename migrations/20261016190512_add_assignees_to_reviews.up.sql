@@ -0,0 +1,2 @@
+-- Add comma-separated assignees column to reviews table to track who should look at a review
+ALTER TABLE reviews ADD COLUMN assignees TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,2 @@
+-- Remove priority column from comments table
+ALTER TABLE comments DROP COLUMN priority;
@@ -0,0 +1,2 @@
+-- Add priority column to comments table to triage which comments to address first
+ALTER TABLE comments ADD COLUMN priority TEXT NOT NULL DEFAULT 'normal';
@@ -0,0 +1,2 @@
+-- Remove archived column from reviews table
+ALTER TABLE reviews DROP COLUMN archived;
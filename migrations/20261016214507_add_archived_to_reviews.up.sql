@@ -0,0 +1,2 @@
+-- Add archived column to reviews table to exclude old reviews from the review list
+ALTER TABLE reviews ADD COLUMN archived BOOLEAN NOT NULL DEFAULT FALSE;
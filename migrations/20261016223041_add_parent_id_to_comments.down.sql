@@ -0,0 +1,3 @@
+-- Remove parent_id column from comments table
+DROP INDEX idx_comments_parent_id;
+ALTER TABLE comments DROP COLUMN parent_id;
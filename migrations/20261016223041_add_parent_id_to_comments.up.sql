@@ -0,0 +1,5 @@
+-- Add parent_id column to comments table so that replies are threaded under their comment
+ALTER TABLE comments ADD COLUMN parent_id TEXT REFERENCES comments (id) ON DELETE CASCADE;
+
+-- Create index for efficient lookups of the replies of a comment
+CREATE INDEX idx_comments_parent_id ON comments (parent_id);
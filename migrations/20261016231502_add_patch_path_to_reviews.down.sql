@@ -0,0 +1,2 @@
+-- Remove patch_path column from reviews table
+ALTER TABLE reviews DROP COLUMN patch_path;
@@ -0,0 +1,2 @@
+-- Add patch_path column to reviews table for reviews of a patch file instead of two revisions
+ALTER TABLE reviews ADD COLUMN patch_path TEXT;
@@ -0,0 +1,2 @@
+-- Remove pinned column from reviews table
+ALTER TABLE reviews DROP COLUMN pinned;
@@ -0,0 +1,2 @@
+-- Add pinned column to reviews table to list the reviews in progress first
+ALTER TABLE reviews ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,2 @@
+-- Remove status column from comments table
+ALTER TABLE comments DROP COLUMN status;
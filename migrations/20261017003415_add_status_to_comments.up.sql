@@ -0,0 +1,2 @@
+-- Add status column to comments table to acknowledge comments without resolving them
+ALTER TABLE comments ADD COLUMN status TEXT NOT NULL DEFAULT 'open';
@@ -0,0 +1,3 @@
+-- Drop checklist_items table and its index
+DROP INDEX IF EXISTS idx_checklist_items_review_id;
+DROP TABLE IF EXISTS checklist_items;
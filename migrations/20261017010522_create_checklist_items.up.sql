@@ -0,0 +1,12 @@
+-- Create checklist_items table for the checklists reviewers follow besides commenting
+CREATE TABLE checklist_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    review_id TEXT NOT NULL,
+    text TEXT NOT NULL,
+    checked BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (review_id) REFERENCES reviews(id) ON DELETE CASCADE
+);
+
+-- Create index for efficient lookups
+CREATE INDEX idx_checklist_items_review_id ON checklist_items(review_id);
@@ -0,0 +1,2 @@
+-- Remove skipped flag from file_views table
+ALTER TABLE file_views DROP COLUMN skipped;
@@ -0,0 +1,2 @@
+-- Add skipped flag to file_views table for files the reviewer consciously doesn't review
+ALTER TABLE file_views ADD COLUMN skipped BOOLEAN NOT NULL DEFAULT FALSE;
@@ -10,12 +10,12 @@ CREATE TABLE reviews (
     id TEXT PRIMARY KEY,
     created_at TEXT NOT NULL,
     updated_at TEXT NOT NULL
-, base_branch TEXT NOT NULL, target_branch TEXT NOT NULL, base_sha TEXT, target_sha TEXT, base_sha_changed TEXT, target_sha_changed TEXT, base_branch_exists BOOLEAN, target_branch_exists BOOLEAN);
+, base_branch TEXT NOT NULL, target_branch TEXT NOT NULL, base_sha TEXT, target_sha TEXT, base_sha_changed TEXT, target_sha_changed TEXT, base_branch_exists BOOLEAN, target_branch_exists BOOLEAN, assignees TEXT NOT NULL DEFAULT '', archived BOOLEAN NOT NULL DEFAULT FALSE, patch_path TEXT, pinned BOOLEAN NOT NULL DEFAULT FALSE);
 CREATE TABLE file_views (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     review_id TEXT NOT NULL,
     file_path TEXT NOT NULL,
-    created_at TEXT NOT NULL,
+    created_at TEXT NOT NULL, content_hash TEXT, skipped BOOLEAN NOT NULL DEFAULT FALSE,
     FOREIGN KEY (review_id) REFERENCES reviews(id) ON DELETE CASCADE,
     UNIQUE(review_id, file_path)
 );
@@ -28,7 +28,7 @@ CREATE TABLE comments (
     file_path TEXT NOT NULL,
     line_number INTEGER,  -- NULL for file-level comments
     content TEXT NOT NULL,
-    created_at TEXT NOT NULL, resolved BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TEXT NOT NULL, resolved BOOLEAN NOT NULL DEFAULT FALSE, line_fingerprint TEXT, resolution_note TEXT, priority TEXT NOT NULL DEFAULT 'normal', parent_id TEXT REFERENCES comments (id) ON DELETE CASCADE, status TEXT NOT NULL DEFAULT 'open',
     FOREIGN KEY (review_id) REFERENCES reviews (id) ON DELETE CASCADE
 );
 CREATE INDEX idx_comments_review_id ON comments (review_id);
@@ -37,3 +37,47 @@ CREATE INDEX idx_comments_line ON comments (review_id, file_path, line_number);
 CREATE INDEX idx_comments_created_at ON comments (created_at DESC);
 CREATE INDEX idx_comments_resolved ON comments(resolved);
 CREATE INDEX idx_comments_review_resolved ON comments(review_id, resolved);
+CREATE TABLE settings (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE VIRTUAL TABLE comments_fts USING fts5(
+    content,
+    content = 'comments',
+    content_rowid = 'rowid'
+)
+/* comments_fts(content) */;
+CREATE TABLE IF NOT EXISTS 'comments_fts_data'(id INTEGER PRIMARY KEY, block BLOB);
+CREATE TABLE IF NOT EXISTS 'comments_fts_idx'(segid, term, pgno, PRIMARY KEY(segid, term)) WITHOUT ROWID;
+CREATE TABLE IF NOT EXISTS 'comments_fts_docsize'(id INTEGER PRIMARY KEY, sz BLOB);
+CREATE TABLE IF NOT EXISTS 'comments_fts_config'(k PRIMARY KEY, v) WITHOUT ROWID;
+CREATE TRIGGER comments_fts_after_insert AFTER INSERT ON comments BEGIN
+    INSERT INTO comments_fts (rowid, content) VALUES (new.rowid, new.content);
+END;
+CREATE TRIGGER comments_fts_after_delete AFTER DELETE ON comments BEGIN
+    INSERT INTO comments_fts (comments_fts, rowid, content) VALUES ('delete', old.rowid, old.content);
+END;
+CREATE TRIGGER comments_fts_after_update AFTER UPDATE OF content ON comments BEGIN
+    INSERT INTO comments_fts (comments_fts, rowid, content) VALUES ('delete', old.rowid, old.content);
+    INSERT INTO comments_fts (rowid, content) VALUES (new.rowid, new.content);
+END;
+CREATE TABLE activities (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    review_id TEXT NOT NULL,
+    activity_type TEXT NOT NULL,
+    detail TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (review_id) REFERENCES reviews(id) ON DELETE CASCADE
+);
+CREATE INDEX idx_activities_review_id ON activities(review_id);
+CREATE INDEX idx_comments_parent_id ON comments (parent_id);
+CREATE TABLE checklist_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    review_id TEXT NOT NULL,
+    text TEXT NOT NULL,
+    checked BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (review_id) REFERENCES reviews(id) ON DELETE CASCADE
+);
+CREATE INDEX idx_checklist_items_review_id ON checklist_items(review_id);
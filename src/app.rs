@@ -1,10 +1,22 @@
-use ratatui::{DefaultTerminal, crossterm::event::KeyEvent};
+use std::{collections::HashMap, process::Stdio, sync::Arc};
+
+use ratatui::{
+    DefaultTerminal,
+    crossterm::{
+        event::KeyEvent,
+        execute,
+        terminal::{EnterAlternateScreen, enable_raw_mode},
+    },
+};
 
 use crate::{
     database::Database,
-    event::{AppEvent, EventHandler},
+    event::{AppEvent, Event, EventHandler},
     event_handler::EventProcessor,
-    views::{MainView, ViewHandler},
+    models::{SessionState, Settings},
+    services::{DifftoolCommand, GitService},
+    theme::Theme,
+    views::{CommentTarget, MainView, ViewHandler},
 };
 
 /// Application.
@@ -17,8 +29,15 @@ pub struct App {
     pub database: Database,
     /// Current view stack.
     pub view_stack: Vec<Box<dyn ViewHandler>>,
-    /// Path to the Git repository being reviewed.
+    /// Root of the Git repository being reviewed.
     pub repo_path: String,
+    /// Persistent app settings.
+    pub settings: Settings,
+    /// Visual theme.
+    pub theme: Theme,
+    /// Unsubmitted comment texts of the comments views that were closed, restored when the
+    /// comments of the same target are opened again. Kept in memory only.
+    pub comment_drafts: HashMap<CommentTarget, String>,
 }
 
 impl Default for App {
@@ -31,6 +50,7 @@ impl App {
     /// Constructs a new instance of [`App`].
     pub async fn new() -> color_eyre::Result<Self> {
         let database = Database::new().await?;
+        let settings = Settings::load(database.pool()).await?;
 
         Ok(Self {
             running: true,
@@ -38,12 +58,21 @@ impl App {
             database,
             view_stack: vec![Box::new(MainView::new())],
             repo_path: String::new(),
+            settings,
+            theme: Theme::default(),
+            comment_drafts: HashMap::new(),
         })
     }
 
-    /// Sets the repository path.
+    /// Sets the repository path to the root of the repository that contains it, so that the
+    /// tool works from any subdirectory. Paths outside of a repository are kept as they are.
     pub fn set_repo_path(&mut self, repo_path: String) {
-        self.repo_path = repo_path;
+        self.repo_path = GitService::discover_repo_root(&repo_path).unwrap_or(repo_path);
+    }
+
+    /// Sets the visual theme.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
     }
 
     /// Run the application's main loop.
@@ -54,7 +83,88 @@ impl App {
         while self.running {
             terminal.draw(|frame| frame.render_widget(&self, frame.area()))?;
             let event = self.events.next().await?;
-            EventProcessor::process_event(&mut self, event).await?;
+            EventProcessor::process_event(&mut self, Arc::clone(&event)).await?;
+
+            // External programs and the clipboard need the terminal, which only the main loop has
+            match event.as_ref() {
+                Event::App(AppEvent::DifftoolRun(command)) => {
+                    self.run_difftool(&mut terminal, command)?;
+                }
+                Event::App(AppEvent::ClipboardCopy(text)) => {
+                    if let Err(error) = crate::clipboard::copy(text) {
+                        log::warn!("Failed to copy to clipboard: {error}");
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.shutdown().await
+    }
+
+    /// Process the app events that are still queued after quitting, e.g. a comment that was
+    /// submitted right before, so that their database writes complete before exiting. Then
+    /// checkpoint the database.
+    pub async fn shutdown(&mut self) -> color_eyre::Result<()> {
+        while let Some(event) = self.events.try_recv() {
+            // Input and ticks don't matter anymore
+            if matches!(event.as_ref(), Event::App(_)) {
+                EventProcessor::process_event(self, event).await?;
+            }
+        }
+
+        // Write everything to the database file, a killed terminal can't lose anything then
+        if let Err(error) = self.database.checkpoint().await {
+            log::warn!("Failed to checkpoint the database on exit: {error}");
+        }
+        Ok(())
+    }
+
+    /// Run the difftool. GUI tools are started in the background, terminal tools take over the
+    /// terminal until they exit.
+    fn run_difftool(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        difftool_command: &DifftoolCommand,
+    ) -> color_eyre::Result<()> {
+        let mut command = difftool_command.command();
+        log::info!("Running difftool: {command:?}");
+
+        if difftool_command.tool.gui {
+            let result = command
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            match result {
+                // Reap the process once the tool is closed
+                Ok(mut child) => drop(std::thread::spawn(move || child.wait())),
+                Err(error) => self.events.send(AppEvent::DifftoolError(Arc::from(format!(
+                    "Failed to start difftool {}: {error}",
+                    difftool_command.tool.name
+                )))),
+            }
+            return Ok(());
+        }
+
+        self.events.suspend_input();
+        ratatui::restore();
+        let result = command.status();
+        execute!(std::io::stdout(), EnterAlternateScreen)?;
+        enable_raw_mode()?;
+        terminal.clear()?;
+        self.events.resume_input();
+
+        match result {
+            Ok(status) if status.success() => {}
+            Ok(status) => self.events.send(AppEvent::DifftoolError(Arc::from(format!(
+                "Difftool {} exited with {status}",
+                difftool_command.tool.name
+            )))),
+            Err(error) => self.events.send(AppEvent::DifftoolError(Arc::from(format!(
+                "Failed to start difftool {}: {error}",
+                difftool_command.tool.name
+            )))),
         }
         Ok(())
     }
@@ -97,11 +207,21 @@ impl App {
         }
     }
 
+    /// Navigation state of the topmost view that shows a review, to resume it on the next launch
+    pub fn session_state(&self) -> Option<SessionState> {
+        self.view_stack
+            .iter()
+            .rev()
+            .find_map(|view| view.session_state())
+    }
+
     /// Handles the tick event of the terminal.
     ///
     /// The tick event is where you can update the state of your application with any logic that
     /// needs to be updated at a fixed frame rate. E.g. polling a server, updating an animation.
-    pub fn tick(&self) {}
+    pub fn tick(&self) {
+        self.database.checkpoint_periodically();
+    }
 
     /// Set running to false to quit the application.
     pub fn quit(&mut self) {
@@ -119,7 +239,7 @@ mod tests {
 
     use crate::{
         event::{AppEvent, Event},
-        models::Review,
+        models::{Comment, CommentPriority, Review},
         services::ReviewsLoadingState,
         views::{MainView, ReviewCreateView, ViewType},
     };
@@ -136,6 +256,9 @@ mod tests {
             database,
             view_stack: vec![Box::new(MainView::new())],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         }
     }
 
@@ -146,7 +269,10 @@ mod tests {
 
         assert!(app.running);
         assert_eq!(app.view_stack.len(), 1);
-        assert_eq!(app.repo_path, ".");
+        assert_eq!(
+            app.repo_path,
+            GitService::discover_repo_root(".").unwrap_or_else(|| ".".to_string())
+        );
     }
 
     #[tokio::test]
@@ -159,6 +285,37 @@ mod tests {
         assert!(!app.running);
     }
 
+    #[tokio::test]
+    async fn test_shutdown_completes_queued_writes() {
+        let mut app = create_test_app().await;
+        let review = Review::builder().build();
+        review.save(app.database.pool()).await.unwrap();
+
+        app.events.send(AppEvent::Quit);
+        app.events.send(AppEvent::CommentCreate {
+            review_id: review.id.clone().into(),
+            file_path: "src/main.rs".into(),
+            line_number: Some(3),
+            content: "Written before exiting".into(),
+            priority: CommentPriority::Normal,
+            parent_id: None,
+        });
+        let event = app.events.try_recv().unwrap();
+        EventProcessor::process_event(&mut app, event)
+            .await
+            .unwrap();
+        assert!(!app.running);
+
+        app.shutdown().await.unwrap();
+
+        let comments = Comment::find_for_review(app.database.pool(), &review.id)
+            .await
+            .unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].content, "Written before exiting");
+        assert!(!app.events.has_pending_events());
+    }
+
     #[tokio::test]
     async fn test_push_view() {
         let mut app = create_test_app().await;
@@ -281,6 +438,13 @@ mod tests {
             base_branch_index: 0,
             target_branch_index: 2,
             current_field: crate::views::review_create_view::InputField::BaseBranch,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: String::new(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
         };
 
         // Add it to the stack
@@ -332,11 +496,13 @@ mod tests {
             database: Database::from_pool(pool),
             view_stack: vec![Box::new(MainView::new())],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
 
-        // Tick should not change anything
+        // Tick should not change anything, the database checkpoint is not due yet
         app.tick();
-        // Since tick() is a no-op, there's nothing to assert
     }
 
     #[tokio::test]
@@ -358,6 +524,7 @@ mod tests {
         // Call handle_app_events with ReviewsLoadingState event
         app.handle_app_events(&AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
             reviews.into(),
+            Default::default(),
         )));
 
         // Verify MainView now has the first review selected
@@ -391,6 +558,7 @@ mod tests {
         // Call handle_app_events with ReviewsLoadingState::Loaded event
         app.handle_app_events(&AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
             reviews.into(),
+            Default::default(),
         )));
 
         // Verify MainView now has the first review selected (all views should have received the event)
@@ -426,6 +594,7 @@ mod tests {
         // Call handle_app_events
         app.handle_app_events(&AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
             Arc::new([]),
+            Default::default(),
         )));
 
         // Verify view stack order is preserved
@@ -0,0 +1,31 @@
+use std::io::Write;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+/// Copy text to the system clipboard through the terminal using the OSC 52 escape sequence.
+///
+/// This works without any clipboard dependencies and also over SSH, as long as the terminal
+/// supports OSC 52.
+pub fn copy(text: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()
+}
+
+/// Build the OSC 52 escape sequence that sets the clipboard to the given text
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(
+            osc52_sequence("https://github.com/owner/repo"),
+            "\x1b]52;c;aHR0cHM6Ly9naXRodWIuY29tL293bmVyL3JlcG8=\x07"
+        );
+    }
+}
@@ -1,37 +1,258 @@
-use sqlx::{SqlitePool, migrate, sqlite::SqliteConnectOptions};
+use std::{
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use sqlx::{
+    SqlitePool, migrate,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode},
+};
+
+/// Interval of the WAL checkpoints while the app is running
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long SQLite retries a query while another instance of the app holds the lock, before
+/// it fails with "database is locked". SQLite's busy handler retries the single query with a
+/// growing delay on the connection's worker thread, so nothing that already ran is repeated.
+/// Kept short because the event loop waits for the query meanwhile.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(2);
 
 pub struct Database {
     pool: SqlitePool,
+    /// Time of the last WAL checkpoint
+    last_checkpoint: Mutex<Instant>,
 }
 
 impl Database {
     pub async fn new() -> color_eyre::Result<Self> {
         std::fs::create_dir_all("tmp")?;
 
-        let options = SqliteConnectOptions::new()
-            .filename("tmp/reviews.db")
-            .create_if_missing(true);
-
-        let pool = SqlitePool::connect_with(options).await?;
+        let pool =
+            SqlitePool::connect_with(Self::connect_options(Path::new("tmp/reviews.db"))).await?;
 
         migrate!().run(&pool).await?;
 
         log::info!("Database initialized at tmp/reviews.db with migrations");
 
-        Ok(Self { pool })
+        Ok(Self::from_pool(pool))
+    }
+
+    fn connect_options(filename: &Path) -> SqliteConnectOptions {
+        SqliteConnectOptions::new()
+            .filename(filename)
+            .create_if_missing(true)
+            .foreign_keys(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(BUSY_TIMEOUT)
+    }
+
+    /// Is the error caused by another connection holding the database (`SQLITE_BUSY` or
+    /// `SQLITE_LOCKED`), e.g. another instance of the app writing at the same time?
+    pub fn is_locked_error(error: &color_eyre::Report) -> bool {
+        let Some(sqlx::Error::Database(database_error)) = error.downcast_ref::<sqlx::Error>()
+        else {
+            return false;
+        };
+        // The extended result codes keep the primary code in the lowest byte
+        database_error
+            .code()
+            .and_then(|code| code.parse::<i32>().ok())
+            .is_some_and(|code| matches!(code & 0xff, 5 | 6))
     }
 
-    #[cfg(test)]
     pub fn from_pool(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            last_checkpoint: Mutex::new(Instant::now()),
+        }
     }
 
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
 
+    /// Checkpoint the WAL in the background when the last checkpoint is older than
+    /// `CHECKPOINT_INTERVAL`, so that the written state survives the terminal being killed.
+    /// Called on every tick. The passive checkpoint does not wait for other connections.
+    pub fn checkpoint_periodically(&self) {
+        let Ok(mut last_checkpoint) = self.last_checkpoint.lock() else {
+            return;
+        };
+        if last_checkpoint.elapsed() < CHECKPOINT_INTERVAL {
+            return;
+        }
+        *last_checkpoint = Instant::now();
+
+        let pool = self.pool.clone();
+        tokio::spawn(async move {
+            if let Err(error) = Self::wal_checkpoint(&pool, "PASSIVE").await {
+                log::warn!("Failed to checkpoint the database: {error}");
+            }
+        });
+    }
+
+    /// Checkpoint the whole WAL into the database file and truncate it, used on exit
+    pub async fn checkpoint(&self) -> color_eyre::Result<()> {
+        Self::wal_checkpoint(&self.pool, "TRUNCATE").await?;
+        if let Ok(mut last_checkpoint) = self.last_checkpoint.lock() {
+            *last_checkpoint = Instant::now();
+        }
+        Ok(())
+    }
+
+    async fn wal_checkpoint(pool: &SqlitePool, mode: &str) -> color_eyre::Result<()> {
+        sqlx::query(&format!("PRAGMA wal_checkpoint({mode})"))
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
     pub async fn close(self) -> color_eyre::Result<()> {
         self.pool.close().await;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_checkpoint_in_memory() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let database = Database::from_pool(pool);
+
+        database.checkpoint().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = SqliteConnectOptions::new()
+            .filename(temp_dir.path().join("reviews.db"))
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        let pool = SqlitePool::connect_with(options).await.unwrap();
+        migrate!().run(&pool).await.unwrap();
+        let database = Database::from_pool(pool);
+
+        // The periodic checkpoint succeeds next to the open connections
+        Database::wal_checkpoint(database.pool(), "PASSIVE")
+            .await
+            .unwrap();
+        database.checkpoint().await.unwrap();
+
+        // Everything was written to the database file, the WAL is truncated
+        let wal_path = temp_dir.path().join("reviews.db-wal");
+        assert_eq!(std::fs::metadata(wal_path).unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_busy_timeout_is_set() {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = SqlitePool::connect_with(Database::connect_options(
+            &temp_dir.path().join("reviews.db"),
+        ))
+        .await
+        .unwrap();
+
+        let busy_timeout: i64 = sqlx::query_scalar("PRAGMA busy_timeout")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(busy_timeout, 2000);
+    }
+
+    #[tokio::test]
+    async fn test_transient_lock_is_retried() {
+        let temp_dir = TempDir::new().unwrap();
+        let filename = temp_dir.path().join("reviews.db");
+        let pool = SqlitePool::connect_with(Database::connect_options(&filename))
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE items (name TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        // Another instance of the app
+        let other_pool = SqlitePool::connect_with(Database::connect_options(&filename))
+            .await
+            .unwrap();
+
+        let mut lock = pool.begin().await.unwrap();
+        sqlx::query("INSERT INTO items (name) VALUES ('locking')")
+            .execute(&mut *lock)
+            .await
+            .unwrap();
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            lock.commit().await.unwrap();
+        });
+
+        // The query waits for the lock instead of failing
+        sqlx::query("INSERT INTO items (name) VALUES ('retried')")
+            .execute(&other_pool)
+            .await
+            .unwrap();
+        release.await.unwrap();
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items")
+            .fetch_one(&other_pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_is_locked_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let filename = temp_dir.path().join("reviews.db");
+        let pool = SqlitePool::connect_with(Database::connect_options(&filename))
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE items (name TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        // Another instance, failing right away instead of waiting for the lock
+        let other_pool = SqlitePool::connect_with(
+            Database::connect_options(&filename).busy_timeout(Duration::ZERO),
+        )
+        .await
+        .unwrap();
+
+        let mut lock = pool.begin().await.unwrap();
+        sqlx::query("INSERT INTO items (name) VALUES ('locking')")
+            .execute(&mut *lock)
+            .await
+            .unwrap();
+        let error: color_eyre::Report = sqlx::query("INSERT INTO items (name) VALUES ('locked')")
+            .execute(&other_pool)
+            .await
+            .unwrap_err()
+            .into();
+
+        assert!(Database::is_locked_error(&error));
+        assert!(!Database::is_locked_error(&color_eyre::eyre::eyre!(
+            "database is locked"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_periodically_waits_for_the_interval() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let database = Database::from_pool(pool);
+        let created_at = *database.last_checkpoint.lock().unwrap();
+
+        database.checkpoint_periodically();
+        assert_eq!(*database.last_checkpoint.lock().unwrap(), created_at);
+
+        let overdue = created_at.checked_sub(CHECKPOINT_INTERVAL).unwrap();
+        *database.last_checkpoint.lock().unwrap() = overdue;
+        database.checkpoint_periodically();
+        assert!(*database.last_checkpoint.lock().unwrap() > overdue);
+    }
+}
@@ -1,17 +1,23 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use color_eyre::eyre::OptionExt;
 use futures::{FutureExt, StreamExt};
 use ratatui::crossterm::event::{Event as CrosstermEvent, KeyEvent};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
+#[cfg(feature = "watch-refs")]
+use crate::ref_watcher::RefWatcher;
 use crate::{
-    models::{Comment, Review},
+    models::{
+        Activity, ChecklistItem, Comment, CommentPriority, CommentStatus, FileViewStatus, Review,
+        SessionState, Settings,
+    },
     services::{
-        CommentsLoadParams, CommentsLoadingState, GitBranchesLoadingState, GitDiffLoadingState,
-        ReviewCreateData, ReviewLoadingState, ReviewsLoadingState,
+        CommentsLoadParams, CommentsLoadingState, DiffContextRequest, DiffPreview, DifftoolCommand,
+        GitBranchesLoadingState, GitDiffLoadingState, HunkBlameRequest, ReviewComparison,
+        ReviewCreateData, ReviewLoadingState, ReviewUpdateData, ReviewsLoadingState, SinceLastTag,
     },
-    views::{KeyBinding, ReviewRefreshOptions},
+    views::{KeyBinding, ReviewRefreshOptions, ReviewSummary},
 };
 
 /// Type alias for review identifiers to make event signatures more descriptive.
@@ -48,8 +54,16 @@ pub enum AppEvent {
     Init,
     /// Check all reviews against current Git repository state.
     ReviewsBranchStatusCheck,
+    /// Refs of the repository changed, e.g. a branch moved by a commit or a fetch.
+    #[cfg(feature = "watch-refs")]
+    RefsChanged,
+    /// Refs of the repository changed while the details of a review are the active view.
+    #[cfg(feature = "watch-refs")]
+    ActiveReviewRefsChanged,
     /// Quit the application.
     Quit,
+    /// Ask before quitting the application.
+    QuitConfirm,
     /// Close the current view.
     ViewClose,
 
@@ -70,7 +84,7 @@ pub enum AppEvent {
     /// Error occurred while loading a review.
     ReviewLoadError(Arc<ReviewId>),
     /// Inform that a review has been created.
-    ReviewCreated(Review),
+    ReviewCreated(Arc<Review>),
     /// Error occurred while creating a review.
     ReviewCreatedError(Arc<str>),
     /// Delete the selected review.
@@ -79,6 +93,13 @@ pub enum AppEvent {
     ReviewDeleted,
     /// Error occurred while deleting a review.
     ReviewDeletedError(Arc<ReviewId>),
+    /// Archive the done reviews that were not updated in the last `archive_after_days` days.
+    ReviewsArchive,
+    /// Pin the review to the top of the review list or unpin it.
+    ReviewPinToggle(Arc<ReviewId>),
+    /// More reviews than the review limit of the settings are not archived, carrying their
+    /// number.
+    ReviewLimitExceeded(i64),
 
     /// Open help modal with keybindings.
     HelpOpen(Arc<[KeyBinding]>),
@@ -87,14 +108,48 @@ pub enum AppEvent {
 
     /// Open the review creation view.
     ReviewCreateOpen,
+    /// Open the branch selection for a quick review of two branches, nothing is saved.
+    QuickCompareOpen,
     /// Submit the review creation form.
     ReviewCreateSubmit(Arc<ReviewCreateData>),
+    /// Ask whether to create a review without changes, the data is submitted when confirmed.
+    ReviewCreateEmptyConfirm(Arc<ReviewCreateData>),
+    /// Creating a review without changes was cancelled, the creation form stays open.
+    ReviewCreateEmptyCancelled,
+    /// Open the review creation view to change the branches of an existing review.
+    ReviewEditOpen(Arc<ReviewUpdateData>),
+    /// Change the branches of a review and re-resolve its SHAs.
+    ReviewUpdate(Arc<ReviewUpdateData>),
+    /// Review branches were updated successfully.
+    ReviewUpdated(Arc<Review>),
+    /// Error occurred while updating the review branches.
+    ReviewUpdateError(Arc<str>),
 
     /// Open delete confirmation dialog for selected review.
     ReviewDeleteConfirm(Arc<ReviewId>),
+    /// Open the confirmation dialog for archiving the done reviews.
+    ReviewsArchiveConfirm,
 
     /// Open review details view.
     ReviewDetailsOpen(Arc<ReviewId>),
+    /// Open the review details of a quick review that is not saved, limited to the files
+    /// matching `pathspec` if given
+    QuickReviewOpen {
+        review: Arc<Review>,
+        pathspec: Option<Arc<str>>,
+    },
+    /// Open review details view positioned at a file and optionally a line of its diff.
+    ReviewFileOpen {
+        review_id: Arc<ReviewId>,
+        file_path: Arc<str>,
+        line_number: Option<i64>,
+    },
+    /// Jump to a line of the diff in the open review details of the review.
+    ReviewDiffJump {
+        review_id: Arc<ReviewId>,
+        file_path: Arc<str>,
+        line_number: i64,
+    },
     /// Open refresh review chooser.
     ReviewRefreshOpen {
         review_id: Arc<ReviewId>,
@@ -109,44 +164,127 @@ pub enum AppEvent {
     /// Duplicate a review using current branch heads, copying comments.
     ReviewDuplicate { review_id: Arc<ReviewId> },
 
+    /// Open a file of a diff in the git difftool configured by the user.
+    DifftoolOpen {
+        base_sha: Arc<str>,
+        target_sha: Arc<str>,
+        file_path: Arc<str>,
+    },
+    /// Run the resolved difftool. The app suspends the terminal while a terminal difftool runs.
+    DifftoolRun(Arc<DifftoolCommand>),
+    /// Error occurred while opening the difftool, e.g. because none is configured.
+    DifftoolError(Arc<str>),
+
+    /// Copy the web compare URL (GitHub/GitLab) of the `origin` remote between two revisions.
+    CompareUrlCopy { base: Arc<str>, target: Arc<str> },
+    /// Error occurred while building the compare URL, e.g. because there is no `origin` remote.
+    CompareUrlError(Arc<str>),
+    /// Copy text to the system clipboard. The app writes it to the terminal as it owns stdout.
+    ClipboardCopy(Arc<str>),
+
     /// Trigger loading of Git branches.
     GitBranchesLoad,
     /// Load the Git branches
     GitBranchesLoading,
     /// Propagates the current loading state of Git branches.
     GitBranchesLoadingState(GitBranchesLoadingState),
+    /// The changes since the last tag that can be reviewed, loaded with the branches. `None`
+    /// when `HEAD` is not on a branch or there is no tag before it.
+    GitSinceLastTagLoaded(Option<Arc<SinceLastTag>>),
 
     /// Trigger loading of Git diff between two SHAs.
+    /// The diff is limited to the files matching `pathspec` if given.
     GitDiffLoad {
         base_sha: Arc<str>,
         target_sha: Arc<str>,
+        pathspec: Option<Arc<str>>,
     },
     /// Load the Git diff
     GitDiffLoading {
         base_sha: Arc<str>,
         target_sha: Arc<str>,
+        pathspec: Option<Arc<str>>,
+    },
+    /// Propagates the current loading state of the Git diff between two SHAs.
+    /// The SHAs let views ignore results of loads that were superseded in the meantime.
+    GitDiffLoadingState {
+        base_sha: Arc<str>,
+        target_sha: Arc<str>,
+        state: GitDiffLoadingState,
+    },
+    /// Progress of parsing the Git diff between two SHAs, sent throttled while it loads.
+    GitDiffProgress {
+        base_sha: Arc<str>,
+        target_sha: Arc<str>,
+        files_done: usize,
+        files_total: usize,
+    },
+    /// Trigger loading of the diff of a patch file.
+    PatchDiffLoad(Arc<str>),
+    /// Propagates the loading state of the diff of a patch file.
+    PatchDiffLoadingState {
+        patch_path: Arc<str>,
+        state: GitDiffLoadingState,
     },
-    /// Propagates the current loading state of Git diff.
-    GitDiffLoadingState(GitDiffLoadingState),
+    /// Load unchanged lines of a file around a hunk to expand the diff context.
+    DiffContextExpand(Arc<DiffContextRequest>),
+    /// Context lines were loaded successfully.
+    DiffContextExpanded {
+        request: Arc<DiffContextRequest>,
+        lines: Arc<[String]>,
+    },
+    /// Error occurred while loading context lines.
+    DiffContextExpandError(Arc<str>),
+    /// Blame the hunks of a file to show who last touched the lines they replace.
+    HunkBlameLoad(Arc<HunkBlameRequest>),
+    /// Blame summaries keyed by the index of the hunk header line.
+    HunkBlameLoaded {
+        request: Arc<HunkBlameRequest>,
+        summaries: Arc<HashMap<usize, Arc<str>>>,
+    },
+    /// Error occurred while blaming the hunks of a file.
+    HunkBlameLoadError(Arc<str>),
+    /// List the files of the tree at a SHA, to list the unchanged files of a review as well.
+    TreeFilesLoad(Arc<str>),
+    /// Paths of all files of the tree at the SHA.
+    TreeFilesLoaded { sha: Arc<str>, paths: Arc<[String]> },
+    /// Error occurred while listing the files of a tree.
+    TreeFilesLoadError(Arc<str>),
+    /// Summarize the diff between two branches before creating a review.
+    DiffPreviewLoad {
+        base_branch: Arc<str>,
+        target_branch: Arc<str>,
+    },
+    /// The diff preview was computed successfully.
+    DiffPreviewLoaded(Arc<DiffPreview>),
+    /// Error occurred while computing the diff preview, e.g. because a branch is gone.
+    DiffPreviewError(Arc<str>),
 
     /// Toggle the view status of a file for a review.
     FileViewToggle {
         review_id: Arc<ReviewId>,
         file_path: Arc<str>,
+        /// Hash of the diff content of the file, stored when it is marked as viewed
+        content_hash: Option<Arc<str>>,
+    },
+    /// Toggle the skipped status of a file for a review.
+    FileSkipToggle {
+        review_id: Arc<ReviewId>,
+        file_path: Arc<str>,
     },
-    /// File view status was toggled successfully.
+    /// The viewed or skipped status of a file was toggled successfully.
     FileViewToggled {
         review_id: Arc<ReviewId>,
         file_path: Arc<str>,
-        is_viewed: bool,
+        status: FileViewStatus,
     },
-    /// Error occurred while toggling file view status.
+    /// Error occurred while toggling the viewed or skipped status of a file.
     FileViewToggleError {
         review_id: Arc<ReviewId>,
         file_path: Arc<str>,
         error: Arc<str>,
     },
-    /// Load the viewed files for a review.
+    /// Load the viewed and skipped files for a review.
     FileViewsLoad { review_id: Arc<ReviewId> },
     /// File views are being loaded.
     FileViewsLoading { review_id: Arc<ReviewId> },
@@ -154,6 +292,14 @@ pub enum AppEvent {
     FileViewsLoaded {
         review_id: Arc<ReviewId>,
         viewed_files: Arc<Vec<String>>,
+        skipped_files: Arc<Vec<String>>,
+        /// Diff content hashes of the viewed files at the time they were marked as viewed
+        content_hashes: Arc<HashMap<String, String>>,
+    },
+    /// Mark files as not viewed because they changed since they were marked as viewed.
+    FileViewsReset {
+        review_id: Arc<ReviewId>,
+        file_paths: Arc<[String]>,
     },
     /// Error occurred while loading file views.
     FileViewsLoadError {
@@ -166,6 +312,18 @@ pub enum AppEvent {
         review_id: Arc<ReviewId>,
         file_path: Arc<str>,
         line_number: Option<i64>,
+        /// Path of the file before it was renamed, shown next to its path in the title
+        old_file_path: Option<Arc<str>>,
+    },
+    /// Close the comments docked beside the diff of the review details.
+    CommentsDockedClose,
+    /// Warn that the review diff is outdated before opening comments for a line,
+    /// offering to refresh the review or to comment anyway.
+    CommentsOpenOutdatedDiff {
+        review_id: Arc<ReviewId>,
+        file_path: Arc<str>,
+        line_number: Option<i64>,
+        options: ReviewRefreshOptions,
     },
     /// Load comments for a review, file or line.
     CommentsLoad(CommentsLoadParams),
@@ -182,13 +340,19 @@ pub enum AppEvent {
         file_path: Arc<str>,
         line_number: Option<i64>,
         content: Arc<str>,
+        priority: CommentPriority,
+        /// Comment the new comment replies to
+        parent_id: Option<Arc<str>>,
     },
     /// Comment was created successfully.
     CommentCreated(Arc<Comment>),
     /// Error occurred while creating a comment.
     CommentCreateError(Arc<str>),
-    /// Mark a comment as resolved.
-    CommentMarkResolved { comment_id: Arc<str> },
+    /// Mark a comment as resolved, with an optional note on how it was addressed.
+    CommentMarkResolved {
+        comment_id: Arc<str>,
+        resolution_note: Option<Arc<str>>,
+    },
     /// Comment was marked as resolved successfully.
     CommentMarkedResolved { comment_id: Arc<str> },
     /// Error occurred while marking comment as resolved.
@@ -208,6 +372,18 @@ pub enum AppEvent {
         comment_id: Arc<str>,
         error: Arc<str>,
     },
+    /// Cycle a comment's status to the next one.
+    CommentCycleStatus { comment_id: Arc<str> },
+    /// Comment's status was changed successfully.
+    CommentStatusChanged {
+        comment_id: Arc<str>,
+        status: CommentStatus,
+    },
+    /// Error occurred while changing comment status.
+    CommentCycleStatusError {
+        comment_id: Arc<str>,
+        error: Arc<str>,
+    },
     /// Mark all comments as resolved for a specific target.
     CommentsMarkAllResolved {
         review_id: Arc<ReviewId>,
@@ -248,6 +424,105 @@ pub enum AppEvent {
         line_number: Option<i64>,
         error: Arc<str>,
     },
+
+    /// Open the activity log of a review.
+    ActivityOpen { review_id: Arc<ReviewId> },
+    /// Load the activity log of a review.
+    ActivitiesLoad { review_id: Arc<ReviewId> },
+    /// Activity log was loaded successfully, newest first.
+    ActivitiesLoaded {
+        review_id: Arc<ReviewId>,
+        activities: Arc<[Activity]>,
+    },
+    /// Error occurred while loading the activity log.
+    ActivitiesLoadError {
+        review_id: Arc<ReviewId>,
+        error: Arc<str>,
+    },
+
+    /// Open the checklist of a review.
+    ChecklistOpen { review_id: Arc<ReviewId> },
+    /// Load the checklist items of a review.
+    ChecklistLoad { review_id: Arc<ReviewId> },
+    /// Checklist items were loaded successfully, in the order they were added.
+    ChecklistLoaded {
+        review_id: Arc<ReviewId>,
+        items: Arc<[ChecklistItem]>,
+    },
+    /// Error occurred while loading the checklist items.
+    ChecklistLoadError {
+        review_id: Arc<ReviewId>,
+        error: Arc<str>,
+    },
+    /// Add an item to the checklist of a review.
+    ChecklistItemCreate {
+        review_id: Arc<ReviewId>,
+        text: Arc<str>,
+    },
+    /// Check or uncheck a checklist item.
+    ChecklistItemToggle {
+        review_id: Arc<ReviewId>,
+        item_id: i64,
+    },
+    /// Change the text of a checklist item.
+    ChecklistItemUpdate {
+        review_id: Arc<ReviewId>,
+        item_id: i64,
+        text: Arc<str>,
+    },
+    /// Remove an item from the checklist of a review.
+    ChecklistItemDelete {
+        review_id: Arc<ReviewId>,
+        item_id: i64,
+    },
+    /// Add the items of the checklist template that the checklist of a review doesn't have yet.
+    ChecklistSeed { review_id: Arc<ReviewId> },
+    /// The checklist of a review was changed successfully.
+    ChecklistChanged { review_id: Arc<ReviewId> },
+    /// Error occurred while changing the checklist of a review.
+    ChecklistChangeError {
+        review_id: Arc<ReviewId>,
+        error: Arc<str>,
+    },
+
+    /// Open the comparison of the diffs of two reviews.
+    ReviewCompareOpen {
+        review_a_id: Arc<ReviewId>,
+        review_b_id: Arc<ReviewId>,
+    },
+    /// Load two reviews and compare their diffs.
+    ReviewCompareLoad {
+        review_a_id: Arc<ReviewId>,
+        review_b_id: Arc<ReviewId>,
+    },
+    /// Comparison of two reviews was computed successfully.
+    ReviewCompareLoaded(Arc<ReviewComparison>),
+    /// Error occurred while comparing two reviews.
+    ReviewCompareLoadError(Arc<str>),
+
+    /// Open the summary of a review after its details were closed.
+    ReviewSummaryOpen(Arc<ReviewSummary>),
+
+    /// Load the navigation state saved on the last exit to offer resuming it.
+    SessionLoad,
+    /// A saved navigation state of an existing review was loaded.
+    SessionLoaded {
+        session_state: Arc<SessionState>,
+        review: Arc<Review>,
+    },
+    /// Reopen the review of a saved navigation state.
+    SessionResume(Arc<SessionState>),
+
+    /// (Re)load the app settings from the database.
+    SettingsLoad,
+    /// App settings were loaded successfully.
+    SettingsLoaded(Arc<Settings>),
+    /// Error occurred while loading the app settings.
+    SettingsLoadError(Arc<str>),
+    /// Persist changed app settings.
+    SettingsSave(Arc<Settings>),
+    /// Error occurred while persisting the app settings.
+    SettingsSaveError(Arc<str>),
 }
 
 /// Terminal event handler.
@@ -257,6 +532,8 @@ pub struct EventHandler {
     sender: mpsc::UnboundedSender<Arc<Event>>,
     /// Event receiver channel.
     receiver: mpsc::UnboundedReceiver<Arc<Event>>,
+    /// Whether reading terminal input is suspended, e.g. while an external program runs.
+    input_suspended: watch::Sender<bool>,
 }
 
 impl Default for EventHandler {
@@ -269,9 +546,14 @@ impl EventHandler {
     /// Constructs a new instance of [`EventHandler`] and spawns a new thread to handle events.
     pub fn new() -> Self {
         let (sender, receiver) = mpsc::unbounded_channel();
-        let actor = EventTask::new(sender.clone());
+        let (input_suspended, input_suspended_receiver) = watch::channel(false);
+        let actor = EventTask::new(sender.clone(), input_suspended_receiver);
         tokio::spawn(async { actor.run().await });
-        Self { sender, receiver }
+        Self {
+            sender,
+            receiver,
+            input_suspended,
+        }
     }
 
     /// Constructs a new instance of [`EventHandler`] for testing without spawning the event task.
@@ -279,7 +561,12 @@ impl EventHandler {
     #[cfg(test)]
     pub fn new_for_test() -> Self {
         let (sender, receiver) = mpsc::unbounded_channel();
-        Self { sender, receiver }
+        let (input_suspended, _) = watch::channel(false);
+        Self {
+            sender,
+            receiver,
+            input_suspended,
+        }
     }
 
     /// Receives an event from the sender.
@@ -317,6 +604,23 @@ impl EventHandler {
         let _ = self.sender.send(Event::Crossterm(crossterm_event).into());
     }
 
+    /// Stop reading terminal input so that an external program can take over the terminal.
+    pub fn suspend_input(&self) {
+        self.input_suspended.send_replace(true);
+    }
+
+    /// Watch the refs of the repository and send [`AppEvent::RefsChanged`] when they change,
+    /// until the returned watcher is dropped.
+    #[cfg(feature = "watch-refs")]
+    pub fn watch_refs(&self, repo_path: &str) -> color_eyre::Result<RefWatcher> {
+        RefWatcher::start(repo_path, self.sender.clone())
+    }
+
+    /// Resume reading terminal input after [`EventHandler::suspend_input`].
+    pub fn resume_input(&self) {
+        self.input_suspended.send_replace(false);
+    }
+
     /// Check if there are any pending events in the queue.
     /// This is useful for testing to verify that events have been sent.
     #[cfg(test)]
@@ -326,8 +630,8 @@ impl EventHandler {
 
     /// Try to receive an event without blocking.
     /// Returns None if no events are available.
-    /// This is useful for testing to check what events have been sent.
-    #[cfg(test)]
+    /// This is used to drain the queue on shutdown and in tests to check what events have been
+    /// sent.
     pub fn try_recv(&mut self) -> Option<Arc<Event>> {
         self.receiver.try_recv().ok()
     }
@@ -337,35 +641,61 @@ impl EventHandler {
 struct EventTask {
     /// Event sender channel.
     sender: mpsc::UnboundedSender<Arc<Event>>,
+    /// Whether reading terminal input is suspended.
+    input_suspended: watch::Receiver<bool>,
 }
 
 impl EventTask {
     /// Constructs a new instance of [`Event`].
-    fn new(sender: mpsc::UnboundedSender<Arc<Event>>) -> Self {
-        Self { sender }
+    fn new(
+        sender: mpsc::UnboundedSender<Arc<Event>>,
+        input_suspended: watch::Receiver<bool>,
+    ) -> Self {
+        Self {
+            sender,
+            input_suspended,
+        }
     }
 
     /// Runs the event thread.
     ///
     /// This function emits tick events at a fixed rate and polls for crossterm events in between.
-    async fn run(self) -> color_eyre::Result<()> {
+    /// While input is suspended, the crossterm event stream is dropped so that no input is read
+    /// away from an external program running in the terminal.
+    async fn run(mut self) -> color_eyre::Result<()> {
         let tick_rate = Duration::from_secs_f64(1.0 / TICK_FPS);
-        let mut reader = crossterm::event::EventStream::new();
         let mut tick = tokio::time::interval(tick_rate);
         loop {
-            let tick_delay = tick.tick();
-            let crossterm_event = reader.next().fuse();
-            tokio::select! {
-              _ = self.sender.closed() => {
+            if self
+                .input_suspended
+                .wait_for(|suspended| !suspended)
+                .await
+                .is_err()
+            {
                 break;
-              }
-              _ = tick_delay => {
-                self.send(Event::Tick.into());
-              }
-              Some(Ok(event)) = crossterm_event => {
-                self.send(Event::Crossterm(event).into());
-              }
-            };
+            }
+
+            let mut reader = crossterm::event::EventStream::new();
+            loop {
+                let tick_delay = tick.tick();
+                let crossterm_event = reader.next().fuse();
+                tokio::select! {
+                  _ = self.sender.closed() => {
+                    return Ok(());
+                  }
+                  _ = self.input_suspended.changed() => {
+                    if *self.input_suspended.borrow() {
+                        break;
+                    }
+                  }
+                  _ = tick_delay => {
+                    self.send(Event::Tick.into());
+                  }
+                  Some(Ok(event)) = crossterm_event => {
+                    self.send(Event::Crossterm(event).into());
+                  }
+                };
+            }
         }
         Ok(())
     }
@@ -4,14 +4,19 @@ use ratatui::crossterm::event::KeyEvent;
 
 use crate::{
     app::App,
-    event::{AppEvent, Event},
+    database::Database,
+    event::{AppEvent, Event, ReviewId},
+    models::{Review, SessionState},
     services::{
-        BranchStatusService, CommentService, CommentsLoadParams, FileViewService, GitService,
-        ReviewService, ServiceContext, ServiceHandler,
+        ActivityService, BranchStatusService, ChecklistService, CommentService, CommentsLoadParams,
+        DifftoolService, FileViewService, GitService, ReviewCreateData, ReviewLoadingState,
+        ReviewService, ReviewUpdateData, ServiceContext, ServiceHandler, SessionService,
+        SettingsService,
     },
     views::{
-        CommentsView, ConfirmationDialogView, HelpModalView, KeyBinding, ReviewCreateView,
-        ReviewDetailsView, ReviewRefreshDialogView,
+        ActivityView, ChecklistView, CommentsView, ConfirmationDialogView, HelpModalView,
+        KeyBinding, ReviewCompareView, ReviewCreateView, ReviewDetailsView,
+        ReviewRefreshDialogView, ReviewSummary, ReviewSummaryView, ViewType,
     },
 };
 
@@ -38,25 +43,80 @@ impl EventProcessor {
                 // Finally handle app events globally
                 match *app_event {
                     AppEvent::Init => Self::init(app),
-                    AppEvent::Quit => app.quit(),
+                    AppEvent::Quit => Self::quit(app).await,
+                    AppEvent::QuitConfirm => Self::quit_confirm(app),
                     AppEvent::ViewClose => app.pop_view(),
+                    AppEvent::SettingsLoaded(ref settings) => {
+                        app.settings = settings.as_ref().clone();
+                    }
+                    #[cfg(feature = "watch-refs")]
+                    AppEvent::RefsChanged => Self::refs_changed(app),
                     // Events that open views
                     AppEvent::ReviewCreateOpen => Self::review_create_open(app),
+                    AppEvent::QuickCompareOpen => Self::quick_compare_open(app),
+                    AppEvent::ReviewEditOpen(ref data) => Self::review_edit_open(app, data),
+                    AppEvent::ReviewCreateEmptyConfirm(ref data) => {
+                        Self::review_create_empty_confirm(app, data)
+                    }
                     AppEvent::ReviewDeleteConfirm(ref review_id) => {
                         Self::review_delete_confirm(app, review_id)
                     }
+                    AppEvent::ReviewsArchiveConfirm => Self::reviews_archive_confirm(app, None),
+                    AppEvent::ReviewLimitExceeded(review_count) => {
+                        Self::review_limit_exceeded(app, review_count)
+                    }
                     AppEvent::CommentsOpen {
                         ref review_id,
                         ref file_path,
                         ref line_number,
-                    } => Self::comments_open(app, review_id, file_path, line_number),
+                        ref old_file_path,
+                    } => Self::comments_open(app, review_id, file_path, line_number, old_file_path),
+                    AppEvent::CommentsOpenOutdatedDiff {
+                        ref review_id,
+                        ref file_path,
+                        ref line_number,
+                        ref options,
+                    } => Self::comments_open_outdated_diff(
+                        app,
+                        review_id,
+                        file_path,
+                        line_number,
+                        options,
+                    ),
+                    AppEvent::SessionLoaded {
+                        ref session_state,
+                        ref review,
+                    } => Self::session_resume_confirm(app, session_state, review),
+                    AppEvent::SessionResume(ref session_state) => {
+                        Self::session_resume(app, session_state)
+                    }
                     AppEvent::HelpOpen(ref keybindings) => Self::help_open(app, keybindings),
+                    AppEvent::ActivityOpen { ref review_id } => Self::activity_open(app, review_id),
+                    AppEvent::ChecklistOpen { ref review_id } => {
+                        Self::checklist_open(app, review_id)
+                    }
+                    AppEvent::ReviewCompareOpen {
+                        ref review_a_id,
+                        ref review_b_id,
+                    } => Self::review_compare_open(app, review_a_id, review_b_id),
+                    AppEvent::ReviewSummaryOpen(ref summary) => {
+                        Self::review_summary_open(app, summary)
+                    }
                     AppEvent::HelpKeySelected(ref key_event) => {
                         Self::help_key_selected(app, key_event)
                     }
                     AppEvent::ReviewDetailsOpen(ref review_id) => {
                         Self::review_details_open(app, review_id)
                     }
+                    AppEvent::QuickReviewOpen {
+                        ref review,
+                        ref pathspec,
+                    } => Self::quick_review_open(app, review, pathspec),
+                    AppEvent::ReviewFileOpen {
+                        ref review_id,
+                        ref file_path,
+                        ref line_number,
+                    } => Self::review_file_open(app, review_id, file_path, line_number),
                     AppEvent::ReviewRefreshOpen {
                         ref review_id,
                         ref options,
@@ -78,14 +138,38 @@ impl EventProcessor {
         app.events.send(AppEvent::ReviewsBranchStatusCheck);
     }
 
+    /// Save the navigation state to offer resuming it on the next launch, then quit
+    async fn quit(app: &mut App) {
+        let session_state = app.session_state();
+        if let Err(error) = SessionState::save(app.database.pool(), session_state.as_ref()).await {
+            log::error!("Failed to save the session state: {error}");
+        }
+        app.quit();
+    }
+
+    /// Ask before quitting, for users who enabled the confirmation
+    fn quit_confirm(app: &mut App) {
+        let confirmation_dialog = ConfirmationDialogView::new(
+            "Quit git-local-review?".to_string(),
+            AppEvent::Quit,
+            AppEvent::ViewClose,
+        );
+        app.push_view(Box::new(confirmation_dialog));
+    }
+
     /// Handle app events through services
     async fn handle_services(app: &mut App, event: &AppEvent) -> color_eyre::Result<()> {
         let services = vec![
+            ActivityService::handle_app_event,
             BranchStatusService::handle_app_event,
+            ChecklistService::handle_app_event,
             CommentService::handle_app_event,
+            DifftoolService::handle_app_event,
             ReviewService::handle_app_event,
+            SessionService::handle_app_event,
             GitService::handle_app_event,
             FileViewService::handle_app_event,
+            SettingsService::handle_app_event,
         ];
 
         for handler in services {
@@ -94,12 +178,31 @@ impl EventProcessor {
                 repo_path: &app.repo_path,
                 events: &mut app.events,
             };
-            handler(event, context).await?;
+            if let Err(error) = handler(event, context).await {
+                // The query was retried for the busy timeout already, the handler is not run
+                // again so that nothing it did before the query happens twice
+                if !Database::is_locked_error(&error) {
+                    return Err(error);
+                }
+                log::error!("Database is still locked, giving up: {error}");
+                Self::database_locked_confirm(app);
+            }
         }
 
         Ok(())
     }
 
+    /// Tell that a change was not saved because the database stayed locked by another instance
+    /// instead of exiting, and offer to quit this one
+    fn database_locked_confirm(app: &mut App) {
+        let confirmation_dialog = ConfirmationDialogView::new(
+            "The database is locked by another git-local-review instance, the last change was not saved — quit?".to_string(),
+            AppEvent::Quit,
+            AppEvent::ViewClose,
+        );
+        app.push_view(Box::new(confirmation_dialog));
+    }
+
     /// Open the review creation view
     fn review_create_open(app: &mut App) {
         app.push_view(Box::new(ReviewCreateView::default()));
@@ -107,6 +210,18 @@ impl EventProcessor {
         app.events.send(AppEvent::GitBranchesLoad);
     }
 
+    /// Open the branch selection for a quick review that is not saved
+    fn quick_compare_open(app: &mut App) {
+        app.push_view(Box::new(ReviewCreateView::for_quick_compare()));
+        app.events.send(AppEvent::GitBranchesLoad);
+    }
+
+    /// Open the branch selection for changing the branches of an existing review
+    fn review_edit_open(app: &mut App, data: &ReviewUpdateData) {
+        app.push_view(Box::new(ReviewCreateView::for_review(data)));
+        app.events.send(AppEvent::GitBranchesLoad);
+    }
+
     /// Open delete confirmation dialog
     fn review_delete_confirm(app: &mut App, review_id: &str) {
         // Create a generic confirmation dialog without the specific review title
@@ -121,6 +236,76 @@ impl EventProcessor {
         app.push_view(Box::new(confirmation_dialog));
     }
 
+    /// Ask before archiving the done reviews, explaining the suggestion when it's made because
+    /// of the number of reviews
+    fn reviews_archive_confirm(app: &mut App, review_count: Option<i64>) {
+        let question = format!(
+            "Archive the done reviews not updated in the last {} days?",
+            app.settings.archive_after_days
+        );
+        let message = match review_count {
+            Some(review_count) => {
+                format!("{review_count} reviews slow down the review list. {question}")
+            }
+            None => question,
+        };
+        let confirmation_dialog =
+            ConfirmationDialogView::new(message, AppEvent::ReviewsArchive, AppEvent::ViewClose);
+        app.push_view(Box::new(confirmation_dialog));
+    }
+
+    /// Suggest archiving the done reviews once, when there are more reviews than the limit
+    fn review_limit_exceeded(app: &mut App, review_count: i64) {
+        app.settings.review_limit_notified = true;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
+        Self::reviews_archive_confirm(app, Some(review_count));
+    }
+
+    /// Ask before creating a review without changes. Cancelling only closes the dialog, so
+    /// that the branches can be changed in the creation form below it.
+    fn review_create_empty_confirm(app: &mut App, data: &Arc<ReviewCreateData>) {
+        let confirmation_dialog = ConfirmationDialogView::new(
+            "This review has no changes — create anyway?".to_string(),
+            AppEvent::ReviewCreateSubmit(Arc::clone(data)),
+            AppEvent::ReviewCreateEmptyCancelled,
+        );
+        app.push_view(Box::new(confirmation_dialog));
+    }
+
+    /// Offer to reopen the review that was open on the last exit
+    fn session_resume_confirm(app: &mut App, session_state: &Arc<SessionState>, review: &Review) {
+        let location = session_state
+            .file_path
+            .as_ref()
+            .map(|file_path| format!(" at {file_path}"))
+            .unwrap_or_default();
+        let message = format!(
+            "Resume the review {} -> {}{location}?",
+            review.base_branch, review.target_branch
+        );
+        let confirmation_dialog = ConfirmationDialogView::new(
+            message,
+            AppEvent::SessionResume(Arc::clone(session_state)),
+            AppEvent::ViewClose,
+        );
+        app.push_view(Box::new(confirmation_dialog));
+    }
+
+    /// Reopen the review of the session state. The view is opened through another event so
+    /// that the confirmation dialog is closed before.
+    fn session_resume(app: &mut App, session_state: &SessionState) {
+        let review_id = Arc::from(session_state.review_id.as_str());
+        match &session_state.file_path {
+            Some(file_path) => app.events.send(AppEvent::ReviewFileOpen {
+                review_id,
+                file_path: Arc::from(file_path.as_str()),
+                line_number: session_state.line_number,
+            }),
+            None => app.events.send(AppEvent::ReviewDetailsOpen(review_id)),
+        }
+    }
+
     /// Open comments view for a specific review and file and optionally a line number
     /// Triggers loading of comments for the specified target
     fn comments_open(
@@ -128,6 +313,7 @@ impl EventProcessor {
         review_id: &Arc<str>,
         file_path: &Arc<str>,
         line_number: &Option<i64>,
+        old_file_path: &Option<Arc<str>>,
     ) {
         // Trigger loading of comments for the specified target
         app.events.send(AppEvent::CommentsLoad(CommentsLoadParams {
@@ -136,22 +322,95 @@ impl EventProcessor {
             line_number: Arc::from(*line_number),
         }));
 
-        let comments_view = if let Some(line) = line_number {
+        if line_number.is_none() {
+            // Load the comments of the whole review to know the other files with comments
+            app.events.send(AppEvent::CommentsLoad(CommentsLoadParams {
+                review_id: review_id.clone(),
+                file_path: Arc::from(None),
+                line_number: Arc::from(None),
+            }));
+        }
+
+        let mut comments_view = if let Some(line) = line_number {
             log::info!("Opening comments for review {review_id} at {file_path}:{line}");
             CommentsView::new_for_line(review_id.to_string(), file_path.to_string(), *line)
         } else {
             log::info!("Opening comments for review {review_id} at {file_path}");
             CommentsView::new_for_file(review_id.to_string(), file_path.to_string())
-        };
+        }
+        .with_show_resolved(!app.settings.hide_resolved_comments)
+        .with_max_comment_length(app.settings.max_comment_length)
+        .with_old_file_path(old_file_path.as_deref().map(str::to_string))
+        .with_comment_drafts(&app.comment_drafts);
+
+        if app.settings.dock_comments
+            && let Some(view) = app.view_stack.last_mut()
+        {
+            match view.dock_comments(comments_view) {
+                Some(undocked_view) => comments_view = undocked_view,
+                None => return,
+            }
+        }
         app.push_view(Box::new(comments_view));
     }
 
+    /// Open the refresh chooser as a warning that the review diff is outdated,
+    /// with the option to comment anyway
+    fn comments_open_outdated_diff(
+        app: &mut App,
+        review_id: &str,
+        file_path: &Arc<str>,
+        line_number: &Option<i64>,
+        options: &crate::views::ReviewRefreshOptions,
+    ) {
+        log::info!("Review {review_id} diff is outdated, asking to refresh before commenting");
+        let refresh_dialog = ReviewRefreshDialogView::new(Arc::from(review_id), *options)
+            .with_pending_comment(Arc::clone(file_path), *line_number);
+        app.push_view(Box::new(refresh_dialog));
+    }
+
     /// Open help modal with provided keybindings
     fn help_open(app: &mut App, keybindings: &Arc<[KeyBinding]>) {
         let help_modal = HelpModalView::new(Arc::clone(keybindings));
         app.push_view(Box::new(help_modal));
     }
 
+    /// Open the activity log of a review and trigger loading it
+    fn activity_open(app: &mut App, review_id: &Arc<ReviewId>) {
+        app.push_view(Box::new(ActivityView::new(Arc::clone(review_id))));
+        app.events.send(AppEvent::ActivitiesLoad {
+            review_id: Arc::clone(review_id),
+        });
+    }
+
+    /// Open the checklist of a review and trigger loading it
+    fn checklist_open(app: &mut App, review_id: &Arc<ReviewId>) {
+        app.push_view(Box::new(ChecklistView::new(Arc::clone(review_id))));
+        app.events.send(AppEvent::ChecklistLoad {
+            review_id: Arc::clone(review_id),
+        });
+    }
+
+    /// Open the comparison of two reviews and trigger computing it
+    fn review_compare_open(
+        app: &mut App,
+        review_a_id: &Arc<ReviewId>,
+        review_b_id: &Arc<ReviewId>,
+    ) {
+        app.push_view(Box::new(ReviewCompareView::new(
+            Arc::clone(review_a_id),
+            Arc::clone(review_b_id),
+        )));
+        app.events.send(AppEvent::ReviewCompareLoad {
+            review_a_id: Arc::clone(review_a_id),
+            review_b_id: Arc::clone(review_b_id),
+        });
+    }
+
+    fn review_summary_open(app: &mut App, summary: &Arc<ReviewSummary>) {
+        app.push_view(Box::new(ReviewSummaryView::new(Arc::clone(summary))));
+    }
+
     /// Handle key selected from help modal
     fn help_key_selected(app: &mut App, key_event: &KeyEvent) {
         // First close the help modal
@@ -163,7 +422,37 @@ impl EventProcessor {
     /// Open review details view
     fn review_details_open(app: &mut App, review_id: &str) {
         // Create an empty ReviewDetailsView and trigger loading
-        app.push_view(Box::new(ReviewDetailsView::new_loading()));
+        app.push_view(Box::new(
+            ReviewDetailsView::new_loading().with_scroll_mode(app.settings.scroll_mode),
+        ));
+        app.events.send(AppEvent::ReviewLoad(Arc::from(review_id)));
+    }
+
+    /// Open the review details of a quick review. It is not saved, so it is passed on as
+    /// loaded directly instead of being loaded from the database.
+    fn quick_review_open(app: &mut App, review: &Arc<Review>, pathspec: &Option<Arc<str>>) {
+        app.push_view(Box::new(
+            ReviewDetailsView::new_loading()
+                .with_scroll_mode(app.settings.scroll_mode)
+                .with_quick_review(pathspec.clone()),
+        ));
+        app.events
+            .send(AppEvent::ReviewLoadingState(ReviewLoadingState::Loaded(
+                Arc::clone(review),
+            )));
+    }
+
+    /// Open review details view and jump to the given file (and line) once the diff is loaded
+    fn review_file_open(
+        app: &mut App,
+        review_id: &str,
+        file_path: &Arc<str>,
+        line_number: &Option<i64>,
+    ) {
+        app.push_view(Box::new(
+            ReviewDetailsView::new_loading_with_file_jump(Arc::clone(file_path), *line_number)
+                .with_scroll_mode(app.settings.scroll_mode),
+        ));
         app.events.send(AppEvent::ReviewLoad(Arc::from(review_id)));
     }
 
@@ -173,9 +462,31 @@ impl EventProcessor {
         review_id: &str,
         options: &crate::views::ReviewRefreshOptions,
     ) {
+        // The refs can move again while the dialog is open
+        if app
+            .view_stack
+            .last()
+            .is_some_and(|view| view.view_type() == ViewType::ReviewRefreshDialog)
+        {
+            return;
+        }
         let refresh_dialog = ReviewRefreshDialogView::new(Arc::from(review_id), *options);
         app.push_view(Box::new(refresh_dialog));
     }
+
+    /// Keep the branch status of the review list current and let the open review offer to
+    /// refresh, only if it is the active view and not below a dialog or another view
+    #[cfg(feature = "watch-refs")]
+    fn refs_changed(app: &mut App) {
+        app.events.send(AppEvent::ReviewsBranchStatusCheck);
+        if app
+            .view_stack
+            .last()
+            .is_some_and(|view| view.view_type() == ViewType::ReviewDetails)
+        {
+            app.events.send(AppEvent::ActiveReviewRefsChanged);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -186,8 +497,9 @@ mod tests {
 
     use crate::{
         database::Database,
-        models::Review,
-        views::{MainView, ViewType},
+        models::{Review, Settings},
+        services::GitBranchesLoadingState,
+        views::MainView,
     };
 
     async fn create_test_app() -> App {
@@ -202,6 +514,9 @@ mod tests {
             database,
             view_stack: vec![Box::new(MainView::new())],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         }
     }
 
@@ -217,6 +532,132 @@ mod tests {
         assert!(!app.running);
     }
 
+    #[tokio::test]
+    async fn test_quit_asks_for_confirmation_when_enabled() {
+        let mut app = create_test_app().await;
+        app.settings.confirm_quit = true;
+
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Char('q')).await;
+        assert!(app.running);
+        assert_eq!(app.view_stack.len(), 2);
+        assert_eq!(
+            app.view_stack.last().unwrap().debug_state(),
+            "ConfirmationDialogView(message: \"Quit git-local-review?\")"
+        );
+
+        // Cancelling keeps the app running on the review list
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Char('n')).await;
+        assert!(app.running);
+        assert_eq!(app.view_stack.len(), 1);
+        assert_eq!(app.view_stack[0].view_type(), ViewType::Main);
+
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Char('q')).await;
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Char('y')).await;
+        assert!(!app.running);
+    }
+
+    #[tokio::test]
+    async fn test_quit_without_confirmation_by_default() {
+        let mut app = create_test_app().await;
+
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Char('q')).await;
+
+        assert!(!app.running);
+    }
+
+    #[tokio::test]
+    async fn test_ctrl_c_quits_without_confirmation() {
+        let mut app = create_test_app().await;
+        app.settings.confirm_quit = true;
+
+        let key_event = KeyEvent::new(
+            ratatui::crossterm::event::KeyCode::Char('c'),
+            ratatui::crossterm::event::KeyModifiers::CONTROL,
+        );
+        app.handle_key_events(&key_event).unwrap();
+        process_pending_events(&mut app).await;
+
+        assert!(!app.running);
+    }
+
+    #[tokio::test]
+    async fn test_process_quit_event_saves_session_state() {
+        let mut app = create_test_app().await;
+        let review = Review::builder().build();
+        app.push_view(Box::new(ReviewDetailsView::new(review.clone())));
+
+        EventProcessor::process_event(&mut app, Event::App(AppEvent::Quit).into())
+            .await
+            .unwrap();
+
+        let session_state = SessionState::load(app.database.pool()).await.unwrap();
+        assert_eq!(
+            session_state,
+            Some(SessionState {
+                review_id: review.id,
+                file_path: None,
+                line_number: None,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_quit_event_clears_session_state_without_review() {
+        let mut app = create_test_app().await;
+        let session_state = SessionState {
+            review_id: "review-1".to_string(),
+            file_path: None,
+            line_number: None,
+        };
+        SessionState::save(app.database.pool(), Some(&session_state))
+            .await
+            .unwrap();
+
+        EventProcessor::process_event(&mut app, Event::App(AppEvent::Quit).into())
+            .await
+            .unwrap();
+
+        assert_eq!(SessionState::load(app.database.pool()).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_session_resume_confirm_and_resume() {
+        let mut app = create_test_app().await;
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature")
+            .build();
+        let session_state = Arc::new(SessionState {
+            review_id: review.id.clone(),
+            file_path: Some("src/main.rs".to_string()),
+            line_number: Some(3),
+        });
+
+        EventProcessor::session_resume_confirm(&mut app, &session_state, &review);
+
+        assert_eq!(app.view_stack.len(), 2);
+        assert_eq!(
+            app.view_stack.last().unwrap().debug_state(),
+            "ConfirmationDialogView(message: \"Resume the review main -> feature at src/main.rs?\")"
+        );
+
+        EventProcessor::session_resume(&mut app, &session_state);
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewFileOpen {
+                review_id,
+                file_path,
+                line_number,
+            }) => {
+                assert_eq!(review_id.as_ref(), review.id);
+                assert_eq!(file_path.as_ref(), "src/main.rs");
+                assert_eq!(*line_number, Some(3));
+            }
+            _ => panic!("Expected ReviewFileOpen event, got: {event:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_process_review_create_open_event() {
         let mut app = create_test_app().await;
@@ -233,6 +674,128 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_process_review_edit_open_event() {
+        let mut app = create_test_app().await;
+
+        EventProcessor::process_event(
+            &mut app,
+            Event::App(AppEvent::ReviewEditOpen(Arc::new(ReviewUpdateData {
+                review_id: "review-1".to_string(),
+                base_branch: "main".to_string(),
+                target_branch: "feature".to_string(),
+                assignees: String::new(),
+            })))
+            .into(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(app.view_stack.len(), 2);
+        let view = app
+            .view_stack
+            .last()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ReviewCreateView>()
+            .unwrap();
+        assert_eq!(
+            view.edited_review.as_ref().unwrap().review_id,
+            "review-1".to_string()
+        );
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::GitBranchesLoad)));
+    }
+
+    /// Open the review creation form and submit it with the same base and target SHA
+    async fn submit_empty_review(app: &mut App) {
+        EventProcessor::process_event(app, Event::App(AppEvent::ReviewCreateOpen).into())
+            .await
+            .unwrap();
+        let data = ReviewCreateData {
+            base_branch: "main".to_string(),
+            target_branch: "feature".to_string(),
+            base_sha: Some("abc123".to_string()),
+            target_sha: Some("abc123".to_string()),
+            assignees: String::new(),
+            allow_empty_diff: false,
+        };
+        app.events
+            .send(AppEvent::ReviewCreateSubmit(Arc::new(data)));
+        process_pending_events(app).await;
+    }
+
+    async fn process_pending_events(app: &mut App) {
+        while let Some(event) = app.events.try_recv() {
+            EventProcessor::process_event(app, event).await.unwrap();
+        }
+    }
+
+    async fn press_key(app: &mut App, code: ratatui::crossterm::event::KeyCode) {
+        let key_event = KeyEvent::new(code, ratatui::crossterm::event::KeyModifiers::NONE);
+        app.handle_key_events(&key_event).unwrap();
+        process_pending_events(app).await;
+    }
+
+    #[tokio::test]
+    async fn test_review_create_empty_diff_asks_for_confirmation() {
+        let mut app = create_test_app().await;
+
+        submit_empty_review(&mut app).await;
+
+        assert_eq!(app.view_stack.len(), 3);
+        assert_eq!(
+            app.view_stack.last().unwrap().debug_state(),
+            "ConfirmationDialogView(message: \"This review has no changes — create anyway?\")"
+        );
+        assert_eq!(Review::list_all(app.database.pool()).await.unwrap(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_review_create_empty_diff_confirmed() {
+        let mut app = create_test_app().await;
+        submit_empty_review(&mut app).await;
+
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Char('y')).await;
+
+        let reviews = Review::list_all(app.database.pool()).await.unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].base_sha.as_deref(), Some("abc123"));
+        assert_eq!(app.view_stack.len(), 1);
+        assert_eq!(app.view_stack.last().unwrap().view_type(), ViewType::Main);
+    }
+
+    #[tokio::test]
+    async fn test_review_create_opens_review_details() {
+        let mut app = create_test_app().await;
+        app.settings.open_review_after_create = true;
+        submit_empty_review(&mut app).await;
+
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Char('y')).await;
+
+        let reviews = Review::list_all(app.database.pool()).await.unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(app.view_stack.len(), 2);
+        let view = app.view_stack.last().unwrap();
+        assert_eq!(view.view_type(), ViewType::ReviewDetails);
+        assert!(view.debug_state().contains(&reviews[0].id.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_review_create_empty_diff_cancelled() {
+        let mut app = create_test_app().await;
+        submit_empty_review(&mut app).await;
+
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Char('n')).await;
+
+        assert_eq!(Review::list_all(app.database.pool()).await.unwrap(), vec![]);
+        assert_eq!(app.view_stack.len(), 2);
+        assert_eq!(
+            app.view_stack.last().unwrap().view_type(),
+            ViewType::ReviewCreate
+        );
+    }
+
     #[tokio::test]
     async fn test_process_review_refresh_open_event() {
         let mut app = create_test_app().await;
@@ -260,6 +823,75 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_process_review_refresh_open_event_with_open_refresh_dialog() {
+        let mut app = create_test_app().await;
+        let event = || {
+            Event::App(AppEvent::ReviewRefreshOpen {
+                review_id: Arc::from("review-1"),
+                options: crate::views::ReviewRefreshOptions {
+                    can_refresh_base: true,
+                    can_refresh_target: false,
+                    can_duplicate: true,
+                },
+            })
+            .into()
+        };
+
+        EventProcessor::process_event(&mut app, event())
+            .await
+            .unwrap();
+        EventProcessor::process_event(&mut app, event())
+            .await
+            .unwrap();
+
+        assert_eq!(app.view_stack.len(), 2);
+    }
+
+    #[cfg(feature = "watch-refs")]
+    #[tokio::test]
+    async fn test_process_refs_changed_event() {
+        let mut app = create_test_app().await;
+        let is_active_review_refs_changed = |event: Option<Arc<Event>>| {
+            matches!(
+                event.as_deref(),
+                Some(Event::App(AppEvent::ActiveReviewRefsChanged))
+            )
+        };
+
+        // Without an open review only the branch status of the review list is checked
+        EventProcessor::process_event(&mut app, Event::App(AppEvent::RefsChanged).into())
+            .await
+            .unwrap();
+        assert!(matches!(
+            app.events.try_recv().as_deref(),
+            Some(Event::App(AppEvent::ReviewsBranchStatusCheck))
+        ));
+        assert!(!app.events.has_pending_events());
+
+        app.push_view(Box::new(ReviewDetailsView::new(Review::builder().build())));
+        EventProcessor::process_event(&mut app, Event::App(AppEvent::RefsChanged).into())
+            .await
+            .unwrap();
+        app.events.try_recv();
+        assert!(is_active_review_refs_changed(app.events.try_recv()));
+
+        // The open review is not the active view below the refresh dialog
+        app.push_view(Box::new(ReviewRefreshDialogView::new(
+            Arc::from("review-1"),
+            crate::views::ReviewRefreshOptions {
+                can_refresh_base: true,
+                can_refresh_target: false,
+                can_duplicate: true,
+            },
+        )));
+        EventProcessor::process_event(&mut app, Event::App(AppEvent::RefsChanged).into())
+            .await
+            .unwrap();
+        app.events.try_recv();
+        assert!(!app.events.has_pending_events());
+    }
+
     #[tokio::test]
     async fn test_process_view_close_event() {
         let mut app = create_test_app().await;
@@ -341,6 +973,31 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_review_limit_exceeded_suggests_archiving_once() {
+        let mut app = create_test_app().await;
+
+        EventProcessor::process_event(
+            &mut app,
+            Event::App(AppEvent::ReviewLimitExceeded(250)).into(),
+        )
+        .await
+        .unwrap();
+
+        assert!(app.settings.review_limit_notified);
+        assert_eq!(
+            app.view_stack.last().unwrap().debug_state(),
+            "ConfirmationDialogView(message: \"250 reviews slow down the review list. Archive the done reviews not updated in the last 30 days?\")"
+        );
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SettingsSave(settings)) => {
+                assert!(settings.review_limit_notified);
+            }
+            _ => panic!("Expected SettingsSave event, got: {event:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_process_review_delete_confirm_event_no_selection() {
         let mut app = create_test_app().await;
@@ -494,6 +1151,93 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_database_locked_confirm_offers_to_quit() {
+        let mut app = create_test_app().await;
+
+        EventProcessor::database_locked_confirm(&mut app);
+        assert_eq!(
+            app.view_stack.last().unwrap().debug_state(),
+            "ConfirmationDialogView(message: \"The database is locked by another git-local-review instance, the last change was not saved — quit?\")"
+        );
+
+        let key_event = KeyEvent::new(
+            ratatui::crossterm::event::KeyCode::Char('y'),
+            ratatui::crossterm::event::KeyModifiers::NONE,
+        );
+        app.handle_key_events(&key_event).unwrap();
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::Quit)));
+    }
+
+    #[tokio::test]
+    async fn test_checklist_open_function() {
+        let mut app = create_test_app().await;
+
+        EventProcessor::checklist_open(&mut app, &Arc::from("review-1"));
+
+        assert_eq!(app.view_stack.len(), 2);
+        assert_eq!(
+            app.view_stack.last().unwrap().view_type(),
+            ViewType::Checklist
+        );
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ChecklistLoad { review_id }) => {
+                assert_eq!(review_id.as_ref(), "review-1");
+            }
+            _ => panic!("Expected ChecklistLoad event"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_activity_open_function() {
+        let mut app = create_test_app().await;
+
+        EventProcessor::activity_open(&mut app, &Arc::from("review-1"));
+
+        assert_eq!(app.view_stack.len(), 2);
+        assert_eq!(
+            app.view_stack.last().unwrap().view_type(),
+            ViewType::Activity
+        );
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ActivitiesLoad { review_id }) => {
+                assert_eq!(review_id.as_ref(), "review-1");
+            }
+            _ => panic!("Expected ActivitiesLoad event"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_compare_open_function() {
+        let mut app = create_test_app().await;
+
+        EventProcessor::review_compare_open(
+            &mut app,
+            &Arc::from("review-a"),
+            &Arc::from("review-b"),
+        );
+
+        assert_eq!(app.view_stack.len(), 2);
+        assert_eq!(
+            app.view_stack.last().unwrap().view_type(),
+            ViewType::ReviewCompare
+        );
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewCompareLoad {
+                review_a_id,
+                review_b_id,
+            }) => {
+                assert_eq!(review_a_id.as_ref(), "review-a");
+                assert_eq!(review_b_id.as_ref(), "review-b");
+            }
+            _ => panic!("Expected ReviewCompareLoad event"),
+        }
+    }
+
     #[tokio::test]
     async fn test_help_key_selected_function() {
         let mut app = create_test_app().await;
@@ -572,6 +1316,62 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_process_settings_loaded_event() {
+        let mut app = create_test_app().await;
+        assert!(!app.settings.collapse_viewed_list);
+
+        let settings = Settings {
+            collapse_viewed_list: true,
+            ..Default::default()
+        };
+        EventProcessor::process_event(
+            &mut app,
+            Event::App(AppEvent::SettingsLoaded(Arc::new(settings.clone()))).into(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(app.settings, settings);
+    }
+
+    #[tokio::test]
+    async fn test_process_review_file_open_event() {
+        let mut app = create_test_app().await;
+        assert_eq!(app.view_stack.len(), 1); // Only MainView
+        assert!(!app.events.has_pending_events());
+
+        let review_id = "test-review-id";
+        EventProcessor::process_event(
+            &mut app,
+            Event::App(AppEvent::ReviewFileOpen {
+                review_id: review_id.into(),
+                file_path: "src/main.rs".into(),
+                line_number: Some(3),
+            })
+            .into(),
+        )
+        .await
+        .unwrap();
+
+        // Should have added a ReviewDetailsView to the stack
+        assert_eq!(app.view_stack.len(), 2);
+        assert_eq!(
+            app.view_stack.last().unwrap().view_type(),
+            ViewType::ReviewDetails
+        );
+
+        // Should have sent a ReviewLoad event
+        assert!(app.events.has_pending_events());
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewLoad(event_review_id)) => {
+                assert_eq!(event_review_id.as_ref(), review_id);
+            }
+            _ => panic!("Expected ReviewLoad event, got: {event:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_review_details_open_function() {
         let mut app = create_test_app().await;
@@ -599,6 +1399,106 @@ mod tests {
         }
     }
 
+    /// Commit the given files on top of HEAD of the repository
+    fn commit_files(repo: &git2::Repository, files: &[(&str, &str)]) {
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let mut tree_builder = repo.treebuilder(None).unwrap();
+        for (path, content) in files {
+            let blob_id = repo.blob(content.as_bytes()).unwrap();
+            tree_builder.insert(path, blob_id, 0o100644).unwrap();
+        }
+        let tree = repo.find_tree(tree_builder.write().unwrap()).unwrap();
+        let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Commit",
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_quick_review_opens_diff_between_revspecs_limited_to_pathspec() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        commit_files(&repo, &[("notes.txt", "notes\n")]);
+        commit_files(
+            &repo,
+            &[("notes.txt", "more notes\n"), ("main.rs", "fn main() {}\n")],
+        );
+        let mut app = create_test_app().await;
+        app.repo_path = temp_dir.path().to_string_lossy().to_string();
+        let review = ReviewService::quick_review(&app.repo_path, "HEAD~1", "HEAD").unwrap();
+
+        EventProcessor::process_event(
+            &mut app,
+            Event::App(AppEvent::QuickReviewOpen {
+                review: Arc::new(review.clone()),
+                pathspec: Some(Arc::from("*.rs")),
+            })
+            .into(),
+        )
+        .await
+        .unwrap();
+        while let Some(event) = app.events.try_recv() {
+            EventProcessor::process_event(&mut app, event)
+                .await
+                .unwrap();
+        }
+
+        // The quick review is not saved
+        assert_eq!(Review::list_all(app.database.pool()).await.unwrap(), vec![]);
+        let view = app.view_stack.last().unwrap();
+        assert_eq!(view.view_type(), ViewType::ReviewDetails);
+        assert_eq!(view.session_state(), None);
+        let debug_state = view.debug_state();
+        assert!(debug_state.contains(&format!("base_sha: Some({:?})", review.base_sha.unwrap())));
+        assert!(debug_state.contains("path: \"main.rs\""));
+        assert!(!debug_state.contains("notes.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_quick_compare_opens_quick_review_without_saving_it() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        commit_files(&repo, &[("notes.txt", "notes\n")]);
+        let first_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &first_commit, false).unwrap();
+        commit_files(&repo, &[("notes.txt", "more notes\n")]);
+        let mut app = create_test_app().await;
+        app.repo_path = temp_dir.path().to_string_lossy().to_string();
+
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Char('C')).await;
+        let view = app
+            .view_stack
+            .last()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ReviewCreateView>()
+            .unwrap();
+        assert!(view.quick_compare);
+        assert!(matches!(
+            view.branches_state,
+            GitBranchesLoadingState::Loaded(_)
+        ));
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Enter).await;
+
+        assert_eq!(app.view_stack.len(), 2);
+        let view = app.view_stack.last().unwrap();
+        assert_eq!(view.view_type(), ViewType::ReviewDetails);
+        assert_eq!(view.session_state(), None);
+        assert_eq!(Review::list_all(app.database.pool()).await.unwrap(), vec![]);
+
+        // Closing the quick review returns to the review list
+        press_key(&mut app, ratatui::crossterm::event::KeyCode::Esc).await;
+        assert_eq!(app.view_stack.len(), 1);
+        assert_eq!(app.view_stack[0].view_type(), ViewType::Main);
+    }
+
     #[tokio::test]
     async fn test_process_comments_open_for_file_event() {
         let mut app = create_test_app().await;
@@ -615,6 +1515,7 @@ mod tests {
                 review_id: review_id.clone(),
                 file_path: file_path.clone(),
                 line_number,
+                old_file_path: None,
             })
             .into(),
         )
@@ -657,6 +1558,7 @@ mod tests {
                 review_id: review_id.clone(),
                 file_path: file_path.clone(),
                 line_number,
+                old_file_path: None,
             })
             .into(),
         )
@@ -693,7 +1595,7 @@ mod tests {
         let file_path: Arc<str> = Arc::from("src/utils.rs");
         let line_number = None;
 
-        EventProcessor::comments_open(&mut app, &review_id, &file_path, &line_number);
+        EventProcessor::comments_open(&mut app, &review_id, &file_path, &line_number, &None);
 
         // Should have added a CommentsView to the stack
         assert_eq!(app.view_stack.len(), 2);
@@ -725,7 +1627,7 @@ mod tests {
         let file_path: Arc<str> = Arc::from("src/models/comment.rs");
         let line_number = Some(123);
 
-        EventProcessor::comments_open(&mut app, &review_id, &file_path, &line_number);
+        EventProcessor::comments_open(&mut app, &review_id, &file_path, &line_number, &None);
 
         // Should have added a CommentsView to the stack
         assert_eq!(app.view_stack.len(), 2);
@@ -755,7 +1657,7 @@ mod tests {
         let file_path: Arc<str> = Arc::from("src/test.rs");
         let line_number = Some(99);
 
-        EventProcessor::comments_open(&mut app, &review_id, &file_path, &line_number);
+        EventProcessor::comments_open(&mut app, &review_id, &file_path, &line_number, &None);
 
         // Verify the view was created with correct state
         assert_eq!(app.view_stack.len(), 2);
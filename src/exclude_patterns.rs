@@ -0,0 +1,164 @@
+//! Glob patterns of the files that are left out of reviews, e.g. lockfiles, vendored
+//! dependencies or generated code. They are read from the `.glr-ignore` file in the root of the
+//! repository, one pattern per line like in a `.gitignore` file.
+
+use std::path::Path;
+
+/// Name of the file with the exclude patterns in the root of the repository
+pub const EXCLUDE_FILE_NAME: &str = ".glr-ignore";
+
+/// Glob patterns matched against the paths of the diff files:
+///
+/// - `*` matches any characters except `/`, `?` matches one of them and `**` matches any number
+///   of directories.
+/// - Patterns without a `/` match the name of a file or directory at any depth (`*.lock`,
+///   `node_modules`), the others are relative to the root of the repository (`src/generated/*`).
+/// - Patterns ending with `/` only match directories (`vendor/`).
+/// - Files in a matching directory are excluded too. Empty lines and lines starting with `#`
+///   are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExcludePatterns {
+    patterns: Vec<ExcludePattern>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ExcludePattern {
+    /// Path segments of the pattern, starting with `**` when it matches at any depth
+    segments: Vec<String>,
+    /// Whether the pattern only matches directories
+    directory_only: bool,
+}
+
+impl ExcludePatterns {
+    /// Parse the lines of an exclude file
+    pub fn parse(text: &str) -> Self {
+        let patterns = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let directory_only = line.ends_with('/');
+                let pattern = line.trim_end_matches('/');
+                let is_anchored = pattern.contains('/');
+                let mut segments: Vec<String> = if is_anchored {
+                    vec![]
+                } else {
+                    vec!["**".to_string()]
+                };
+                segments.extend(
+                    pattern
+                        .trim_start_matches('/')
+                        .split('/')
+                        .map(str::to_string),
+                );
+                ExcludePattern {
+                    segments,
+                    directory_only,
+                }
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    /// Read the exclude file of the repository, no patterns if it doesn't exist
+    pub fn load(repo_root: &Path) -> Self {
+        match std::fs::read_to_string(repo_root.join(EXCLUDE_FILE_NAME)) {
+            Ok(text) => Self::parse(&text),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether the file or one of its directories matches a pattern
+    pub fn is_excluded(&self, path: &str) -> bool {
+        let components: Vec<&str> = path.split('/').collect();
+        self.patterns.iter().any(|pattern| {
+            (1..=components.len()).any(|length| {
+                let is_directory = length < components.len();
+                (is_directory || !pattern.directory_only)
+                    && segments_match(&pattern.segments, &components[..length])
+            })
+        })
+    }
+}
+
+/// Match path components against pattern segments, `**` matches any number of components
+fn segments_match(segments: &[String], components: &[&str]) -> bool {
+    match segments.split_first() {
+        None => components.is_empty(),
+        Some((segment, rest)) if segment == "**" => {
+            (0..=components.len()).any(|skipped| segments_match(rest, &components[skipped..]))
+        }
+        Some((segment, rest)) => match components.split_first() {
+            Some((component, remaining)) => {
+                wildcard_match(segment.as_bytes(), component.as_bytes())
+                    && segments_match(rest, remaining)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Match a single path component against a pattern with `*` and `?` wildcards
+fn wildcard_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => {
+            (0..=text.len()).any(|skipped| wildcard_match(rest, &text[skipped..]))
+        }
+        Some((b'?', rest)) => !text.is_empty() && wildcard_match(rest, &text[1..]),
+        Some((byte, rest)) => text.first() == Some(byte) && wildcard_match(rest, &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name_patterns_match_at_any_depth() {
+        let patterns = ExcludePatterns::parse("# Lockfiles\n*.lock\n\npackage-lock.json\n");
+
+        assert!(patterns.is_excluded("Cargo.lock"));
+        assert!(patterns.is_excluded("web/yarn.lock"));
+        assert!(patterns.is_excluded("web/package-lock.json"));
+        assert!(!patterns.is_excluded("src/lock.rs"));
+        assert!(!patterns.is_excluded("Cargo.lock.bak"));
+    }
+
+    #[test]
+    fn test_directory_patterns_exclude_their_files() {
+        let patterns = ExcludePatterns::parse("node_modules\nvendor/\n/src/generated/*.rs\n");
+
+        assert!(patterns.is_excluded("node_modules/left-pad/index.js"));
+        assert!(patterns.is_excluded("web/node_modules/left-pad/index.js"));
+        assert!(patterns.is_excluded("vendor/lib/mod.go"));
+        // Directory patterns don't match files
+        assert!(!patterns.is_excluded("docs/vendor"));
+        assert!(patterns.is_excluded("src/generated/schema.rs"));
+        assert!(!patterns.is_excluded("lib/src/generated/schema.rs"));
+        assert!(!patterns.is_excluded("src/generated/README.md"));
+    }
+
+    #[test]
+    fn test_double_star_matches_any_number_of_directories() {
+        let patterns = ExcludePatterns::parse("docs/**/*.g?.md");
+
+        assert!(!patterns.is_excluded("docs/api.gen.md"));
+        assert!(patterns.is_excluded("docs/api.g1.md"));
+        assert!(patterns.is_excluded("docs/a/b/api.g1.md"));
+        assert!(!patterns.is_excluded("src/docs/api.g1.md"));
+    }
+
+    #[test]
+    fn test_no_patterns() {
+        let patterns = ExcludePatterns::parse("\n# Nothing\n");
+
+        assert!(patterns.is_empty());
+        assert!(!patterns.is_excluded("Cargo.lock"));
+        assert!(ExcludePatterns::load(Path::new("/nonexistent")).is_empty());
+    }
+}
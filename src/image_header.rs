@@ -0,0 +1,163 @@
+//! Dimensions of PNG and JPEG images read from their headers, to describe changes of binary
+//! image files without decoding them.
+
+/// Signature at the start of every PNG file
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Width and height of an image in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageDimensions {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Read the dimensions of a PNG or JPEG image, `None` for other formats or broken headers
+pub fn dimensions(data: &[u8]) -> Option<ImageDimensions> {
+    png_dimensions(data).or_else(|| jpeg_dimensions(data))
+}
+
+/// The IHDR chunk directly follows the signature: length (4), type (4), width (4), height (4)
+fn png_dimensions(data: &[u8]) -> Option<ImageDimensions> {
+    let chunk = data.strip_prefix(PNG_SIGNATURE)?;
+    if chunk.get(4..8)? != b"IHDR" {
+        return None;
+    }
+    Some(ImageDimensions {
+        width: u32::from_be_bytes(chunk.get(8..12)?.try_into().ok()?),
+        height: u32::from_be_bytes(chunk.get(12..16)?.try_into().ok()?),
+    })
+}
+
+/// Walk the segments after the start of image marker up to the first start of frame segment,
+/// which holds the dimensions: length (2), precision (1), height (2), width (2)
+fn jpeg_dimensions(data: &[u8]) -> Option<ImageDimensions> {
+    let mut rest = data.strip_prefix(&[0xFF, 0xD8])?;
+    loop {
+        let (&[0xFF, marker], segment) = rest.split_first_chunk::<2>()? else {
+            return None;
+        };
+        match marker {
+            // Fill byte before a marker
+            0xFF => rest = &rest[1..],
+            // Markers without a segment
+            0x01 | 0xD0..=0xD7 => rest = segment,
+            // Start of frame, except for the DHT, JPG and DAC markers in the same range
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                let read_u16 = |index: usize| -> Option<u16> {
+                    Some(u16::from_be_bytes(
+                        segment.get(index..index + 2)?.try_into().ok()?,
+                    ))
+                };
+                return Some(ImageDimensions {
+                    width: read_u16(5)?.into(),
+                    height: read_u16(3)?.into(),
+                });
+            }
+            _ => {
+                let length = u16::from_be_bytes(segment.get(..2)?.try_into().ok()?);
+                rest = segment.get(usize::from(length)..)?;
+            }
+        }
+    }
+}
+
+/// Format a size in bytes with binary units, e.g. `512 B`, `45 KiB` or `1.5 MiB`
+pub fn format_size(bytes: usize) -> String {
+    const KIB: usize = 1024;
+    const MIB: usize = 1024 * KIB;
+    if bytes < KIB {
+        format!("{bytes} B")
+    } else if bytes < MIB {
+        format!("{} KiB", bytes.div_ceil(KIB))
+    } else {
+        format!("{:.1} MiB", bytes as f64 / MIB as f64)
+    }
+}
+
+/// Describe the change of an image, e.g. `image: 800x600 (45 KiB) → 1024x768 (80 KiB)`. The
+/// missing side of an added or deleted image is `none`. Returns `None` when an image header
+/// can't be parsed.
+pub fn describe_change(old: Option<&[u8]>, new: Option<&[u8]>) -> Option<String> {
+    let describe = |data: Option<&[u8]>| -> Option<String> {
+        let Some(data) = data else {
+            return Some("none".to_string());
+        };
+        let ImageDimensions { width, height } = dimensions(data)?;
+        Some(format!("{width}x{height} ({})", format_size(data.len())))
+    };
+    if old.is_none() && new.is_none() {
+        return None;
+    }
+    Some(format!("image: {} → {}", describe(old)?, describe(new)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Header of a PNG with the given dimensions, up to the end of the IHDR chunk data
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend_from_slice(&13u32.to_be_bytes());
+        data.extend_from_slice(b"IHDR");
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&[8, 6, 0, 0, 0]);
+        data
+    }
+
+    #[test]
+    fn test_png_dimensions() {
+        assert_eq!(
+            dimensions(&png_header(800, 600)),
+            Some(ImageDimensions {
+                width: 800,
+                height: 600
+            })
+        );
+        assert_eq!(dimensions(&png_header(800, 600)[..20]), None);
+        assert_eq!(dimensions(b"not an image"), None);
+    }
+
+    #[test]
+    fn test_jpeg_dimensions() {
+        let jpeg = [
+            0xFF, 0xD8, // Start of image
+            0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, // APP0 segment
+            0xFF, 0xC0, 0x00, 0x11, 0x08, 0x02, 0x58, 0x03, 0x20, // Start of frame, 800x600
+        ];
+        assert_eq!(
+            dimensions(&jpeg),
+            Some(ImageDimensions {
+                width: 800,
+                height: 600
+            })
+        );
+        assert_eq!(dimensions(&jpeg[..12]), None);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(45 * 1024), "45 KiB");
+        assert_eq!(format_size(45 * 1024 + 1), "46 KiB");
+        assert_eq!(format_size(1536 * 1024), "1.5 MiB");
+    }
+
+    #[test]
+    fn test_describe_change() {
+        let old = png_header(800, 600);
+        let new = png_header(1024, 768);
+
+        assert_eq!(
+            describe_change(Some(&old), Some(&new)).as_deref(),
+            Some("image: 800x600 (29 B) → 1024x768 (29 B)")
+        );
+        assert_eq!(
+            describe_change(None, Some(&new)).as_deref(),
+            Some("image: none → 1024x768 (29 B)")
+        );
+        assert_eq!(describe_change(Some(&old), Some(b"broken")), None);
+        assert_eq!(describe_change(None, None), None);
+    }
+}
@@ -1,6 +1,13 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
-use crate::app::App;
+use crate::{
+    app::App,
+    database::Database,
+    event::AppEvent,
+    models::{Density, ProgressMetric, Settings},
+    services::ReviewService,
+    theme::Theme,
+};
 
 #[derive(Parser)]
 #[command(name = "git-local-review")]
@@ -12,20 +19,156 @@ struct Cli {
     /// Path to the Git repository to review
     #[arg(long, default_value = ".")]
     repo_path: String,
+
+    /// Render without colors (also enabled by the NO_COLOR environment variable)
+    #[arg(long)]
+    no_color: bool,
+
+    /// Don't offer to resume the review that was open when the app was last quit
+    #[arg(long)]
+    no_resume: bool,
+
+    /// Maximum number of characters of a comment, remembered for the next launches
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    max_comment_length: Option<u64>,
+
+    /// Number of columns between the tab stops in the diff, remembered for the next launches
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..=16))]
+    tab_width: Option<u64>,
+
+    /// Number of days the `t` filter of the review list shows reviews for, remembered for the
+    /// next launches
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    recent_days: Option<u64>,
+
+    /// Number of reviews above which archiving the done reviews is suggested once, remembered
+    /// for the next launches
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    review_limit: Option<u64>,
+
+    /// Number of days after their last update that `A` in the review list archives done
+    /// reviews, remembered for the next launches
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    archive_after_days: Option<u64>,
+
+    /// Show a summary of the viewed files and comments when closing a review, remembered for
+    /// the next launches
+    #[arg(long)]
+    review_summary: Option<bool>,
+
+    /// Ask before quitting with `q` in the review list (`Ctrl+C` always quits right away),
+    /// remembered for the next launches
+    #[arg(long)]
+    confirm_quit: Option<bool>,
+
+    /// Show the comments of a review beside its diff instead of over it, remembered for the next
+    /// launches
+    #[arg(long)]
+    dock_comments: Option<bool>,
+
+    /// Spacing of the comments list and the diff gutter, `normal` or `compact` without blank
+    /// lines between the comments, remembered for the next launches
+    #[arg(long)]
+    density: Option<Density>,
+
+    /// Open the details of a review right after creating it, remembered for the next launches
+    #[arg(long)]
+    open_review_after_create: Option<bool>,
+
+    /// Base branch preselected when creating a review instead of main/master, an empty name
+    /// removes it, remembered for the next launches
+    #[arg(long)]
+    default_base_branch: Option<String>,
+
+    /// Watch the repository for branches that move, e.g. by a commit or a fetch, and offer to
+    /// refresh the open review
+    #[cfg(feature = "watch-refs")]
+    #[arg(long)]
+    watch_refs: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Export all reviews and their comments as JSON
+    Export {
+        /// File to write the JSON to
+        #[arg(long)]
+        file: String,
+    },
+    /// Import reviews and comments from a JSON export, skipping the ones that already exist
+    Import {
+        /// File to read the JSON from
+        #[arg(long)]
+        file: String,
+    },
+    /// List the reviews of the review list with their progress
+    List {
+        /// Print the reviews and their counts as JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Search the comments of all reviews
+    Search {
+        /// Words the comments contain in this order, the last one may be the start of a word
+        #[arg(long)]
+        query: String,
+    },
+    /// Open the diff between two revisions in a quick review that is not saved
+    Quick {
+        /// Base revision (branch, tag, SHA or any other revspec like HEAD~2)
+        #[arg(long)]
+        base: String,
+        /// Target revision (branch, tag, SHA or any other revspec like HEAD~2)
+        #[arg(long)]
+        target: String,
+        /// Limit the diff to the files matching this pathspec
+        #[arg(long)]
+        path: Option<String>,
+    },
+    /// Create a review of a patch file, e.g. the output of an external diff command, and open it
+    Patch {
+        /// Unified diff to review, like a .diff or .patch file
+        #[arg(long)]
+        file: String,
+    },
+    /// Open the review details of a saved review, optionally at a file and line of its diff
+    Open {
+        /// ID of the review
+        #[arg(long)]
+        review: String,
+        /// File of the diff to select
+        #[arg(long)]
+        file: Option<String>,
+        /// Line of the file's diff to select, as shown next to the file path in the activity log
+        #[arg(long, requires = "file")]
+        line: Option<i64>,
+    },
 }
 
 pub mod app;
+pub mod clipboard;
 pub mod database;
 pub mod event;
 pub mod event_handler;
+pub mod exclude_patterns;
+#[cfg(feature = "image-diff")]
+pub mod image_header;
 pub mod logging;
 pub mod models;
+#[cfg(feature = "watch-refs")]
+pub mod ref_watcher;
 pub mod services;
 #[cfg(test)]
 pub mod test_utils;
+pub mod theme;
 pub mod time_provider;
 pub mod ui;
+pub mod unified_diff;
 pub mod views;
+pub mod word_diff;
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
@@ -35,14 +178,403 @@ async fn main() -> color_eyre::Result<()> {
 
     // Parse command line arguments
     let cli = Cli::parse();
+    if apply_settings_flags(&cli, &mut app.settings)? {
+        app.settings.save(app.database.pool()).await?;
+    }
     app.set_repo_path(cli.repo_path);
+    app.set_theme(Theme::from_flag_and_env(cli.no_color));
+
+    let open_event = match cli.command {
+        Some(Command::Quick { base, target, path }) => Some(quick_review_open_event(
+            &app.repo_path,
+            &base,
+            &target,
+            path,
+        )?),
+        Some(Command::Open { review, file, line }) => {
+            Some(review_open_event(&app.database, &review, file, line).await?)
+        }
+        Some(Command::Patch { file }) => {
+            let review = ReviewService::create_patch_review(&app.database, &file).await?;
+            Some(AppEvent::ReviewDetailsOpen(std::sync::Arc::from(review.id)))
+        }
+        Some(command) => return run_command(command, &app.database, &app.repo_path).await,
+        None => None,
+    };
 
     crate::logging::setup_logging();
     log::info!("Starting application");
 
+    #[cfg(feature = "watch-refs")]
+    let _ref_watcher = cli
+        .watch_refs
+        .then(|| app.events.watch_refs(&app.repo_path))
+        .transpose()?;
+
+    if let Some(event) = open_event {
+        app.events.send(event);
+    } else if !cli.no_resume {
+        app.events.send(AppEvent::SessionLoad);
+    }
+
     color_eyre::install()?;
+    install_panic_hook(ratatui::restore);
     let terminal = ratatui::init();
     let result = app.run(terminal).await;
     ratatui::restore();
     result
 }
+
+/// Run a CLI command instead of the TUI
+async fn run_command(
+    command: Command,
+    database: &Database,
+    repo_path: &str,
+) -> color_eyre::Result<()> {
+    match command {
+        Command::Export { file } => {
+            let export = ReviewService::export_json(database).await?;
+            std::fs::write(&file, serde_json::to_string_pretty(&export)?)?;
+            println!("Exported reviews to {file}");
+        }
+        Command::Import { file } => {
+            let value = serde_json::from_str(&std::fs::read_to_string(&file)?)?;
+            let summary = ReviewService::import_json(database, value).await?;
+            println!(
+                "Imported {} reviews and {} comments ({} reviews and {} comments already existed)",
+                summary.reviews_imported,
+                summary.comments_imported,
+                summary.reviews_skipped,
+                summary.comments_skipped
+            );
+        }
+        Command::List { json } => {
+            let review_list = ReviewService::review_list(database, repo_path).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&review_list)?);
+            } else {
+                for entry in &review_list.reviews {
+                    println!("{}  {}", entry.review.id, review_list_line(entry));
+                }
+            }
+        }
+        Command::Search { query } => {
+            for comment in crate::models::Comment::search_fts(database.pool(), &query).await? {
+                println!("{}", comment_search_line(&comment));
+            }
+        }
+        Command::Quick { .. } | Command::Patch { .. } | Command::Open { .. } => {
+            unreachable!("The quick, patch and open commands open the TUI")
+        }
+    }
+    Ok(())
+}
+
+/// Title and progress of a review as listed by the `list` command
+fn review_list_line(entry: &crate::models::ReviewListEntry) -> String {
+    let mut line = entry.title.clone();
+    for metric in [
+        ProgressMetric::ViewedFiles,
+        ProgressMetric::ResolvedComments,
+    ] {
+        if let Some(progress) = entry.progress.describe(metric) {
+            line.push_str(&format!(" [{progress}]"));
+        }
+    }
+    line
+}
+
+/// Review, location and first line of a comment as listed by the `search` command
+fn comment_search_line(comment: &crate::models::Comment) -> String {
+    let location = match comment.line_number {
+        Some(line_number) => format!("{}:{line_number}", comment.file_path),
+        None => comment.file_path.clone(),
+    };
+    let content = comment.content.lines().next().unwrap_or_default();
+    format!("{}  {location}  {content}", comment.review_id)
+}
+
+/// Apply the settings given as flags, which are saved for the next starts. Returns whether any
+/// was given, so that the settings are saved once.
+fn apply_settings_flags(cli: &Cli, settings: &mut Settings) -> color_eyre::Result<bool> {
+    let mut given = false;
+    if let Some(max_comment_length) = cli.max_comment_length {
+        settings.max_comment_length = usize::try_from(max_comment_length)?;
+        given = true;
+    }
+    if let Some(tab_width) = cli.tab_width {
+        settings.tab_width = usize::try_from(tab_width)?;
+        given = true;
+    }
+    if let Some(recent_days) = cli.recent_days {
+        settings.recent_review_days = usize::try_from(recent_days)?;
+        given = true;
+    }
+    if let Some(review_limit) = cli.review_limit {
+        settings.review_limit = usize::try_from(review_limit)?;
+        given = true;
+    }
+    if let Some(archive_after_days) = cli.archive_after_days {
+        settings.archive_after_days = usize::try_from(archive_after_days)?;
+        given = true;
+    }
+    if let Some(review_summary) = cli.review_summary {
+        settings.show_review_summary = review_summary;
+        given = true;
+    }
+    if let Some(confirm_quit) = cli.confirm_quit {
+        settings.confirm_quit = confirm_quit;
+        given = true;
+    }
+    if let Some(dock_comments) = cli.dock_comments {
+        settings.dock_comments = dock_comments;
+        given = true;
+    }
+    if let Some(density) = cli.density {
+        settings.density = density;
+        given = true;
+    }
+    if let Some(open_review_after_create) = cli.open_review_after_create {
+        settings.open_review_after_create = open_review_after_create;
+        given = true;
+    }
+    if let Some(default_base_branch) = &cli.default_base_branch {
+        settings.default_base_branch =
+            Some(default_base_branch.clone()).filter(|name| !name.is_empty());
+        given = true;
+    }
+    Ok(given)
+}
+
+/// Resolve the revisions of a quick review before starting the TUI, so that invalid ones are
+/// reported on the command line
+fn quick_review_open_event(
+    repo_path: &str,
+    base: &str,
+    target: &str,
+    path: Option<String>,
+) -> color_eyre::Result<AppEvent> {
+    let review = ReviewService::quick_review(repo_path, base, target)?;
+    Ok(AppEvent::QuickReviewOpen {
+        review: std::sync::Arc::new(review),
+        pathspec: path.map(std::sync::Arc::from),
+    })
+}
+
+/// Check that the review exists before starting the TUI, so that an unknown ID is reported on
+/// the command line
+async fn review_open_event(
+    database: &Database,
+    review_id: &str,
+    file: Option<String>,
+    line: Option<i64>,
+) -> color_eyre::Result<AppEvent> {
+    if crate::models::Review::find_by_id(database.pool(), review_id)
+        .await?
+        .is_none()
+    {
+        return Err(color_eyre::eyre::eyre!("Review {review_id} not found"));
+    }
+
+    let review_id = std::sync::Arc::from(review_id);
+    Ok(match file {
+        Some(file_path) => AppEvent::ReviewFileOpen {
+            review_id,
+            file_path: std::sync::Arc::from(file_path),
+            line_number: line,
+        },
+        None => AppEvent::ReviewDetailsOpen(review_id),
+    })
+}
+
+/// Restore the terminal before the previous panic hook (color_eyre) prints the report, so that a
+/// panic does not leave the terminal in raw mode
+fn install_panic_hook(restore: impl Fn() + Send + Sync + 'static) {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore();
+        previous_hook(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    #[tokio::test]
+    async fn test_run_command_export_and_import() {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        let database = Database::from_pool(pool);
+        crate::models::Review::builder()
+            .build()
+            .save(database.pool())
+            .await
+            .unwrap();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir
+            .path()
+            .join("state.json")
+            .to_string_lossy()
+            .to_string();
+
+        run_command(Command::Export { file: file.clone() }, &database, ".")
+            .await
+            .unwrap();
+        run_command(Command::Import { file }, &database, ".")
+            .await
+            .unwrap();
+
+        let reviews = crate::models::Review::list_all(database.pool())
+            .await
+            .unwrap();
+        assert_eq!(reviews.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_command_search() {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        let database = Database::from_pool(pool);
+
+        run_command(
+            Command::Search {
+                query: "fix".to_string(),
+            },
+            &database,
+            ".",
+        )
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn test_comment_search_line() {
+        let mut comment =
+            crate::models::Comment::new("review-1", "src/main.rs", Some(3), "Fix this\nPlease");
+        assert_eq!(
+            comment_search_line(&comment),
+            "review-1  src/main.rs:3  Fix this"
+        );
+
+        comment.line_number = None;
+        assert_eq!(
+            comment_search_line(&comment),
+            "review-1  src/main.rs  Fix this"
+        );
+    }
+
+    #[test]
+    fn test_apply_settings_flags() {
+        let mut settings = Settings::default();
+        let cli = Cli::try_parse_from(["git-local-review"]).unwrap();
+        assert!(!apply_settings_flags(&cli, &mut settings).unwrap());
+        assert_eq!(settings, Settings::default());
+
+        let cli = Cli::try_parse_from([
+            "git-local-review",
+            "--tab-width",
+            "8",
+            "--confirm-quit",
+            "true",
+            "--default-base-branch",
+            "develop",
+        ])
+        .unwrap();
+        assert!(apply_settings_flags(&cli, &mut settings).unwrap());
+        assert_eq!(settings.tab_width, 8);
+        assert!(settings.confirm_quit);
+        assert_eq!(settings.default_base_branch.as_deref(), Some("develop"));
+    }
+
+    #[test]
+    fn test_parse_open_command() {
+        let cli = Cli::try_parse_from([
+            "git-local-review",
+            "open",
+            "--review",
+            "review-1",
+            "--file",
+            "src/main.rs",
+            "--line",
+            "3",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Open { review, file, line })
+                if review == "review-1" && file.as_deref() == Some("src/main.rs") && line == Some(3)
+        ));
+
+        // A line needs a file
+        assert!(
+            Cli::try_parse_from([
+                "git-local-review",
+                "open",
+                "--review",
+                "review-1",
+                "--line",
+                "3"
+            ])
+            .is_err()
+        );
+        assert!(Cli::try_parse_from(["git-local-review", "open"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_review_open_event() {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        let database = Database::from_pool(pool);
+        let review = crate::models::Review::builder().build();
+        review.save(database.pool()).await.unwrap();
+
+        let event = review_open_event(&database, &review.id, None, None)
+            .await
+            .unwrap();
+        assert!(
+            matches!(event, AppEvent::ReviewDetailsOpen(ref review_id) if **review_id == review.id)
+        );
+
+        let event = review_open_event(&database, &review.id, Some("src/main.rs".into()), Some(3))
+            .await
+            .unwrap();
+        assert!(matches!(
+            event,
+            AppEvent::ReviewFileOpen { ref file_path, line_number, .. }
+                if file_path.as_ref() == "src/main.rs" && line_number == Some(3)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_review_open_event_with_unknown_review() {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        let database = Database::from_pool(pool);
+
+        let error = review_open_event(&database, "unknown", None, None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "Review unknown not found");
+    }
+
+    #[test]
+    fn test_install_panic_hook_restores_terminal_on_panic() {
+        let restored = Arc::new(AtomicBool::new(false));
+        let restored_in_hook = Arc::clone(&restored);
+        install_panic_hook(move || restored_in_hook.store(true, Ordering::SeqCst));
+
+        let result = std::panic::catch_unwind(|| panic!("simulated panic"));
+        // Reset to the default hook so that other tests are not affected
+        let _ = std::panic::take_hook();
+
+        assert!(result.is_err());
+        assert!(restored.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,252 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::{
+    models::ReviewId,
+    time_provider::{SystemTimeProvider, TimeProvider},
+};
+
+/// Kind of action that was taken on a review
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActivityType {
+    ReviewCreated,
+    BranchesChanged,
+    FileViewed,
+    FileUnviewed,
+    FileSkipped,
+    CommentAdded,
+    CommentResolved,
+    CommentUnresolved,
+}
+
+impl ActivityType {
+    /// Human readable label for the activity view
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ReviewCreated => "Review created",
+            Self::BranchesChanged => "Branches changed",
+            Self::FileViewed => "File viewed",
+            Self::FileUnviewed => "File unviewed",
+            Self::FileSkipped => "File skipped",
+            Self::CommentAdded => "Comment added",
+            Self::CommentResolved => "Comment resolved",
+            Self::CommentUnresolved => "Comment unresolved",
+        }
+    }
+}
+
+impl fmt::Display for ActivityType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Self::ReviewCreated => "review_created",
+            Self::BranchesChanged => "branches_changed",
+            Self::FileViewed => "file_viewed",
+            Self::FileUnviewed => "file_unviewed",
+            Self::FileSkipped => "file_skipped",
+            Self::CommentAdded => "comment_added",
+            Self::CommentResolved => "comment_resolved",
+            Self::CommentUnresolved => "comment_unresolved",
+        };
+        write!(f, "{value}")
+    }
+}
+
+impl FromStr for ActivityType {
+    type Err = color_eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "review_created" => Ok(Self::ReviewCreated),
+            "branches_changed" => Ok(Self::BranchesChanged),
+            "file_viewed" => Ok(Self::FileViewed),
+            "file_unviewed" => Ok(Self::FileUnviewed),
+            "file_skipped" => Ok(Self::FileSkipped),
+            "comment_added" => Ok(Self::CommentAdded),
+            "comment_resolved" => Ok(Self::CommentResolved),
+            "comment_unresolved" => Ok(Self::CommentUnresolved),
+            _ => Err(color_eyre::eyre::eyre!("Unknown activity type: {value}")),
+        }
+    }
+}
+
+/// Entry of the timeline of actions taken on a review
+#[derive(Debug, Clone, PartialEq)]
+pub struct Activity {
+    pub id: i64,
+    pub review_id: ReviewId,
+    pub activity_type: ActivityType,
+    /// What the action was taken on, e.g. the file path or the branches
+    pub detail: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Activity {
+    pub fn new(review_id: &str, activity_type: ActivityType, detail: &str) -> Self {
+        Self::new_with_time_provider(review_id, activity_type, detail, &SystemTimeProvider)
+    }
+
+    pub fn new_with_time_provider(
+        review_id: &str,
+        activity_type: ActivityType,
+        detail: &str,
+        time_provider: &dyn TimeProvider,
+    ) -> Self {
+        Self {
+            id: 0, // Will be set by database
+            review_id: review_id.to_string(),
+            activity_type,
+            detail: detail.to_string(),
+            created_at: time_provider.now(),
+        }
+    }
+
+    pub async fn create(&self, pool: &SqlitePool) -> color_eyre::Result<()> {
+        let activity_type = self.activity_type.to_string();
+        let created_at = self.created_at.to_rfc3339();
+        sqlx::query!(
+            r#"
+            INSERT INTO activities (review_id, activity_type, detail, created_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+            self.review_id,
+            activity_type,
+            self.detail,
+            created_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Find the activities of a review, newest first
+    pub async fn find_for_review(
+        pool: &SqlitePool,
+        review_id: &str,
+    ) -> color_eyre::Result<Vec<Activity>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id as "id!", review_id, activity_type, detail, created_at
+            FROM activities
+            WHERE review_id = ?1
+            ORDER BY created_at DESC, id DESC
+            "#,
+            review_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let created_at = DateTime::parse_from_rfc3339(&row.created_at)
+                    .map_err(|e| color_eyre::eyre::eyre!("Failed to parse created_at: {}", e))?
+                    .with_timezone(&Utc);
+                Ok(Activity {
+                    id: row.id,
+                    review_id: row.review_id,
+                    activity_type: row.activity_type.parse()?,
+                    detail: row.detail,
+                    created_at,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn delete_for_review(pool: &SqlitePool, review_id: &str) -> color_eyre::Result<()> {
+        sqlx::query!("DELETE FROM activities WHERE review_id = ?", review_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    use crate::{models::Review, test_utils::fixed_time, time_provider::MockTimeProvider};
+
+    async fn create_test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        pool
+    }
+
+    #[test]
+    fn test_activity_type_round_trip() {
+        for activity_type in [
+            ActivityType::ReviewCreated,
+            ActivityType::BranchesChanged,
+            ActivityType::FileViewed,
+            ActivityType::FileUnviewed,
+            ActivityType::CommentAdded,
+            ActivityType::CommentResolved,
+            ActivityType::CommentUnresolved,
+        ] {
+            assert_eq!(
+                activity_type.to_string().parse::<ActivityType>().unwrap(),
+                activity_type
+            );
+        }
+        assert!("unknown".parse::<ActivityType>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_and_find_for_review_newest_first() {
+        let pool = create_test_pool().await;
+        let review = Review::builder().build();
+        review.save(&pool).await.unwrap();
+        let other_review = Review::builder().build();
+        other_review.save(&pool).await.unwrap();
+
+        let older = Activity::new_with_time_provider(
+            &review.id,
+            ActivityType::FileViewed,
+            "src/main.rs",
+            &MockTimeProvider::new(fixed_time()),
+        );
+        let newer = Activity::new_with_time_provider(
+            &review.id,
+            ActivityType::CommentAdded,
+            "src/main.rs:3",
+            &MockTimeProvider::new(fixed_time() + Duration::minutes(1)),
+        );
+        older.create(&pool).await.unwrap();
+        newer.create(&pool).await.unwrap();
+        Activity::new(
+            &other_review.id,
+            ActivityType::ReviewCreated,
+            "main..feature",
+        )
+        .create(&pool)
+        .await
+        .unwrap();
+
+        let activities = Activity::find_for_review(&pool, &review.id).await.unwrap();
+        assert_eq!(activities.len(), 2);
+        assert_eq!(activities[0].activity_type, ActivityType::CommentAdded);
+        assert_eq!(activities[0].detail, "src/main.rs:3");
+        assert_eq!(activities[0].created_at, newer.created_at);
+        assert_eq!(activities[1].activity_type, ActivityType::FileViewed);
+
+        Activity::delete_for_review(&pool, &review.id)
+            .await
+            .unwrap();
+        assert!(
+            Activity::find_for_review(&pool, &review.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            Activity::find_for_review(&pool, &other_review.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use std::{collections::HashMap, path::Path};
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::{
+    models::ReviewId,
+    time_provider::{SystemTimeProvider, TimeProvider},
+};
+
+/// Name of the file in the root of the repository whose lines seed the checklists, e.g.
+/// "Tests cover the change" or "Docs are updated"
+pub const CHECKLIST_TEMPLATE_FILE_NAME: &str = ".glr-checklist";
+
+/// Item of the checklist a reviewer follows besides commenting, e.g. security or docs
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChecklistItem {
+    pub id: i64,
+    pub review_id: ReviewId,
+    pub text: String,
+    pub checked: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ChecklistItem {
+    pub fn new(review_id: &str, text: &str) -> Self {
+        Self::new_with_time_provider(review_id, text, &SystemTimeProvider)
+    }
+
+    pub fn new_with_time_provider(
+        review_id: &str,
+        text: &str,
+        time_provider: &dyn TimeProvider,
+    ) -> Self {
+        Self {
+            id: 0, // Will be set by database
+            review_id: review_id.to_string(),
+            text: text.to_string(),
+            checked: false,
+            created_at: time_provider.now(),
+        }
+    }
+
+    /// Items of a checklist template, one per line. Empty lines and lines starting with `#` are
+    /// ignored.
+    pub fn parse_template(text: &str) -> Vec<String> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Read the checklist template of the repository, no items if it doesn't exist
+    pub fn load_template(repo_root: &Path) -> Vec<String> {
+        match std::fs::read_to_string(repo_root.join(CHECKLIST_TEMPLATE_FILE_NAME)) {
+            Ok(text) => Self::parse_template(&text),
+            Err(_) => vec![],
+        }
+    }
+
+    pub async fn create(&self, pool: &SqlitePool) -> color_eyre::Result<()> {
+        let created_at = self.created_at.to_rfc3339();
+        sqlx::query!(
+            r#"
+            INSERT INTO checklist_items (review_id, text, checked, created_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+            self.review_id,
+            self.text,
+            self.checked,
+            created_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Find the checklist items of a review in the order they were added
+    pub async fn find_for_review(
+        pool: &SqlitePool,
+        review_id: &str,
+    ) -> color_eyre::Result<Vec<ChecklistItem>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id as "id!", review_id, text, checked as "checked!: bool", created_at
+            FROM checklist_items
+            WHERE review_id = ?1
+            ORDER BY id
+            "#,
+            review_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let created_at = DateTime::parse_from_rfc3339(&row.created_at)
+                    .map_err(|e| color_eyre::eyre::eyre!("Failed to parse created_at: {}", e))?
+                    .with_timezone(&Utc);
+                Ok(ChecklistItem {
+                    id: row.id,
+                    review_id: row.review_id,
+                    text: row.text,
+                    checked: row.checked,
+                    created_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Count the checked and total checklist items of every review that has a checklist
+    pub async fn counts_by_review(
+        pool: &SqlitePool,
+    ) -> color_eyre::Result<HashMap<ReviewId, (i64, i64)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                review_id,
+                SUM(CASE WHEN checked THEN 1 ELSE 0 END) as "checked!: i64",
+                COUNT(*) as "total!: i64"
+            FROM checklist_items
+            GROUP BY review_id
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.review_id, (row.checked, row.total)))
+            .collect())
+    }
+
+    pub async fn toggle_checked(pool: &SqlitePool, item_id: i64) -> color_eyre::Result<()> {
+        sqlx::query!(
+            "UPDATE checklist_items SET checked = NOT checked WHERE id = ?",
+            item_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_text(
+        pool: &SqlitePool,
+        item_id: i64,
+        text: &str,
+    ) -> color_eyre::Result<()> {
+        sqlx::query!(
+            "UPDATE checklist_items SET text = ? WHERE id = ?",
+            text,
+            item_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, item_id: i64) -> color_eyre::Result<()> {
+        sqlx::query!("DELETE FROM checklist_items WHERE id = ?", item_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_for_review(pool: &SqlitePool, review_id: &str) -> color_eyre::Result<()> {
+        sqlx::query!("DELETE FROM checklist_items WHERE review_id = ?", review_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    use crate::{models::Review, test_utils::fixed_time, time_provider::MockTimeProvider};
+
+    async fn create_test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        pool
+    }
+
+    #[test]
+    fn test_parse_template() {
+        assert_eq!(
+            ChecklistItem::parse_template("# Checklist\nSecurity reviewed\n\n  Tests added  \n"),
+            vec!["Security reviewed".to_string(), "Tests added".to_string()]
+        );
+        assert!(ChecklistItem::load_template(Path::new("/nonexistent")).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_check_and_find_for_review() {
+        let pool = create_test_pool().await;
+        let review = Review::builder().build();
+        review.save(&pool).await.unwrap();
+        let other_review = Review::builder().build();
+        other_review.save(&pool).await.unwrap();
+
+        ChecklistItem::new_with_time_provider(
+            &review.id,
+            "Security reviewed",
+            &MockTimeProvider::new(fixed_time()),
+        )
+        .create(&pool)
+        .await
+        .unwrap();
+        ChecklistItem::new_with_time_provider(
+            &review.id,
+            "Tests added",
+            &MockTimeProvider::new(fixed_time() + Duration::minutes(1)),
+        )
+        .create(&pool)
+        .await
+        .unwrap();
+        ChecklistItem::new(&other_review.id, "Docs updated")
+            .create(&pool)
+            .await
+            .unwrap();
+
+        let items = ChecklistItem::find_for_review(&pool, &review.id)
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].text, "Security reviewed");
+        assert_eq!(items[0].created_at, fixed_time());
+        assert!(!items[0].checked);
+        assert_eq!(items[1].text, "Tests added");
+
+        ChecklistItem::toggle_checked(&pool, items[1].id)
+            .await
+            .unwrap();
+        ChecklistItem::update_text(&pool, items[0].id, "Security and privacy reviewed")
+            .await
+            .unwrap();
+        let items = ChecklistItem::find_for_review(&pool, &review.id)
+            .await
+            .unwrap();
+        assert_eq!(items[0].text, "Security and privacy reviewed");
+        assert!(!items[0].checked);
+        assert!(items[1].checked);
+
+        let counts = ChecklistItem::counts_by_review(&pool).await.unwrap();
+        assert_eq!(counts.get(&review.id), Some(&(1, 2)));
+        assert_eq!(counts.get(&other_review.id), Some(&(0, 1)));
+
+        ChecklistItem::delete(&pool, items[0].id).await.unwrap();
+        assert_eq!(
+            ChecklistItem::find_for_review(&pool, &review.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        ChecklistItem::delete_for_review(&pool, &review.id)
+            .await
+            .unwrap();
+        assert!(
+            ChecklistItem::find_for_review(&pool, &review.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            ChecklistItem::find_for_review(&pool, &other_review.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
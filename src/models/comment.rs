@@ -1,21 +1,154 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use uuid::Uuid;
 
 use crate::time_provider::{SystemTimeProvider, TimeProvider};
 
-use super::review::ReviewId;
+use super::{diff::Diff, review::ReviewId};
 
 pub type CommentId = String;
 
+/// Number of lines above and below the commented line that are part of its fingerprint
+const FINGERPRINT_CONTEXT_LINES: usize = 1;
+
+/// How urgently a comment should be addressed, chosen when it is created
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    sqlx::Type,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum CommentPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl CommentPriority {
+    /// Next priority when cycling through them while writing a comment
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::Normal => Self::High,
+            Self::High => Self::Low,
+            Self::Low => Self::Normal,
+        }
+    }
+}
+
+impl fmt::Display for CommentPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Normal => write!(f, "normal"),
+            Self::High => write!(f, "high"),
+        }
+    }
+}
+
+impl FromStr for CommentPriority {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "low" => Ok(Self::Low),
+            "normal" => Ok(Self::Normal),
+            "high" => Ok(Self::High),
+            _ => Err(format!("Unknown comment priority: {value}")),
+        }
+    }
+}
+
+/// Acknowledgement of a comment short of resolving it, cycled in the comments
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum CommentStatus {
+    #[default]
+    Open,
+    Addressed,
+    WontFix,
+    Question,
+}
+
+impl CommentStatus {
+    /// Next status when cycling through them in the comments list
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::Open => Self::Addressed,
+            Self::Addressed => Self::WontFix,
+            Self::WontFix => Self::Question,
+            Self::Question => Self::Open,
+        }
+    }
+
+    /// Label shown next to the comment
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Addressed => "addressed",
+            Self::WontFix => "won't fix",
+            Self::Question => "question",
+        }
+    }
+}
+
+impl fmt::Display for CommentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => write!(f, "open"),
+            Self::Addressed => write!(f, "addressed"),
+            Self::WontFix => write!(f, "wont_fix"),
+            Self::Question => write!(f, "question"),
+        }
+    }
+}
+
+impl FromStr for CommentStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "open" => Ok(Self::Open),
+            "addressed" => Ok(Self::Addressed),
+            "wont_fix" => Ok(Self::WontFix),
+            "question" => Ok(Self::Question),
+            _ => Err(format!("Unknown comment status: {value}")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct Comment {
     pub id: CommentId,
     pub review_id: ReviewId,
     pub file_path: String,
     pub line_number: Option<i64>, // None for file-level comments
+    /// Trimmed text of the commented diff line and its neighbors, used to relocate the comment
+    /// when the diff changes
+    pub line_fingerprint: Option<String>,
     pub content: String,
     pub resolved: bool,
+    /// How the comment was addressed, recorded when resolving it and cleared when reopening it
+    pub resolution_note: Option<String>,
+    pub priority: CommentPriority,
+    /// Acknowledgement of the comment, independent of whether it is resolved
+    pub status: CommentStatus,
+    /// Comment this comment replies to, `None` for the comments that start a thread
+    pub parent_id: Option<CommentId>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -51,12 +184,121 @@ impl Comment {
             review_id: review_id.to_string(),
             file_path: file_path.to_string(),
             line_number,
+            line_fingerprint: None,
             content: content.to_string(),
             resolved: false,
+            resolution_note: None,
+            priority: CommentPriority::default(),
+            status: CommentStatus::default(),
+            parent_id: None,
             created_at: time_provider.now(),
         }
     }
 
+    /// Set the priority
+    pub fn with_priority(mut self, priority: CommentPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Make the comment a reply to the thread of `parent_id`
+    pub fn with_parent_id(mut self, parent_id: Option<CommentId>) -> Self {
+        self.parent_id = parent_id;
+        self
+    }
+
+    /// Set the line fingerprint (see [`Comment::fingerprint_for_line`])
+    pub fn with_line_fingerprint(mut self, line_fingerprint: Option<String>) -> Self {
+        self.line_fingerprint = line_fingerprint;
+        self
+    }
+
+    /// Short reference to the commented location, e.g. `src/main.rs:42`, or just the path for
+    /// file-level comments
+    pub fn locator(&self) -> String {
+        match self.line_number {
+            Some(line_number) => format!("{}:{line_number}", self.file_path),
+            None => self.file_path.clone(),
+        }
+    }
+
+    /// Build the fingerprint of a line in the diff content of a file: the trimmed text of the
+    /// line and its direct neighbors without the diff origin characters.
+    /// Hunk headers are reduced to `@@` because their line ranges shift with unrelated edits.
+    pub fn fingerprint_for_line(diff_content: &str, line_index: usize) -> Option<String> {
+        let lines: Vec<&str> = diff_content.lines().collect();
+        Self::fingerprint_of_lines(&lines, line_index)
+    }
+
+    /// [`Comment::fingerprint_for_line`] for the already split lines of the diff content, to
+    /// fingerprint many lines of the same file without splitting it again for each one
+    fn fingerprint_of_lines(lines: &[&str], line_index: usize) -> Option<String> {
+        if line_index >= lines.len() {
+            return None;
+        }
+
+        let start = line_index.saturating_sub(FINGERPRINT_CONTEXT_LINES);
+        let end = (line_index + FINGERPRINT_CONTEXT_LINES + 1).min(lines.len());
+        let fingerprint = lines[start..end]
+            .iter()
+            .map(|line| Self::normalize_fingerprint_line(line))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(fingerprint)
+    }
+
+    fn normalize_fingerprint_line(line: &str) -> &str {
+        if line.starts_with("@@") {
+            return "@@";
+        }
+        match line.chars().next() {
+            Some('+' | '-' | ' ') => line[1..].trim(),
+            _ => line.trim(),
+        }
+    }
+
+    /// Find the line of this comment in the given diff.
+    ///
+    /// Searches the diff content of the comment's file for the stored fingerprint and returns
+    /// the matching line closest to the stored line number. Falls back to the stored line number
+    /// when there is no fingerprint, the file is not in the diff, or nothing matches.
+    /// Returns `None` for file-level comments.
+    pub fn relocate(&self, diff: &Diff) -> Option<i64> {
+        let line_number = self.line_number?;
+        let Some(fingerprint) = &self.line_fingerprint else {
+            return Some(line_number);
+        };
+        let Some(file) = diff.files.iter().find(|file| file.path == self.file_path) else {
+            return Some(line_number);
+        };
+
+        let lines: Vec<&str> = file.content().lines().collect();
+        (0..lines.len())
+            .filter(|index| {
+                Self::fingerprint_of_lines(&lines, *index).as_ref() == Some(fingerprint)
+            })
+            .map(|index| index as i64)
+            .min_by_key(|index| (index - line_number).abs())
+            .or(Some(line_number))
+    }
+
+    /// Update the line number, e.g. after the comment was relocated in a changed diff
+    pub async fn set_line_number(
+        &mut self,
+        pool: &SqlitePool,
+        line_number: i64,
+    ) -> color_eyre::Result<()> {
+        self.line_number = Some(line_number);
+        sqlx::query!(
+            "UPDATE comments SET line_number = ? WHERE id = ?",
+            line_number,
+            self.id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub fn is_file_comment(&self) -> bool {
         self.line_number.is_none()
     }
@@ -69,23 +311,73 @@ impl Comment {
         self.resolved
     }
 
-    /// Mark comment as resolved or unresolved
+    /// Whether the comment still needs attention, i.e. it is neither resolved nor acknowledged
+    /// with a status
+    pub fn needs_attention(&self) -> bool {
+        !self.resolved && self.status == CommentStatus::Open
+    }
+
+    /// Change the status of the comment
+    pub async fn set_status(
+        &mut self,
+        pool: &SqlitePool,
+        status: CommentStatus,
+    ) -> color_eyre::Result<()> {
+        self.status = status;
+        let status = status.to_string();
+        sqlx::query!(
+            "UPDATE comments SET status = ? WHERE id = ?",
+            status,
+            self.id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark comment as resolved or unresolved. Unresolving clears the resolution note.
+    /// Resolving a comment resolves its replies too, unresolving it keeps them resolved.
     pub async fn set_resolved(
         &mut self,
         pool: &SqlitePool,
         resolved: bool,
     ) -> color_eyre::Result<()> {
         self.resolved = resolved;
+        if !resolved {
+            self.resolution_note = None;
+        }
         sqlx::query!(
-            "UPDATE comments SET resolved = ? WHERE id = ?",
+            "UPDATE comments SET resolved = ?, resolution_note = ? WHERE id = ?",
             resolved,
+            self.resolution_note,
             self.id
         )
         .execute(pool)
         .await?;
+        if resolved {
+            sqlx::query!(
+                "UPDATE comments SET resolved = TRUE WHERE parent_id = ?",
+                self.id
+            )
+            .execute(pool)
+            .await?;
+        }
         Ok(())
     }
 
+    /// Mark comment as resolved with a note on how it was addressed. A blank note is not stored.
+    pub async fn mark_resolved_with_note(
+        &mut self,
+        pool: &SqlitePool,
+        resolution_note: Option<&str>,
+    ) -> color_eyre::Result<()> {
+        self.resolution_note = resolution_note
+            .map(str::trim)
+            .filter(|note| !note.is_empty())
+            .map(str::to_string);
+        self.set_resolved(pool, true).await
+    }
+
     /// Mark comment as resolved
     pub async fn mark_resolved(&mut self, pool: &SqlitePool) -> color_eyre::Result<()> {
         self.set_resolved(pool, true).await
@@ -98,18 +390,25 @@ impl Comment {
 
     /// Create a new comment in the database
     pub async fn create(&self, pool: &SqlitePool) -> color_eyre::Result<()> {
+        let priority = self.priority.to_string();
+        let status = self.status.to_string();
         let created_at_str = self.created_at.to_rfc3339();
         sqlx::query!(
             r#"
-            INSERT INTO comments (id, review_id, file_path, line_number, content, resolved, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO comments (id, review_id, file_path, line_number, line_fingerprint, content, resolved, resolution_note, priority, status, parent_id, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             self.id,
             self.review_id,
             self.file_path,
             self.line_number,
+            self.line_fingerprint,
             self.content,
             self.resolved,
+            self.resolution_note,
+            priority,
+            status,
+            self.parent_id,
             created_at_str
         )
         .execute(pool)
@@ -125,7 +424,7 @@ impl Comment {
     ) -> color_eyre::Result<Option<Comment>> {
         let row = sqlx::query!(
             r#"
-            SELECT id as "id!", review_id as "review_id!", file_path as "file_path!", line_number, content as "content!", resolved as "resolved!", created_at as "created_at!"
+            SELECT id as "id!", review_id as "review_id!", file_path as "file_path!", line_number, line_fingerprint, content as "content!", resolved as "resolved!", resolution_note, priority as "priority!", status as "status!", parent_id, created_at as "created_at!"
             FROM comments
             WHERE id = ?
             "#,
@@ -145,8 +444,13 @@ impl Comment {
                     review_id: row.review_id,
                     file_path: row.file_path,
                     line_number: row.line_number,
+                    line_fingerprint: row.line_fingerprint,
                     content: row.content,
                     resolved: row.resolved,
+                    resolution_note: row.resolution_note,
+                    priority: row.priority.parse().unwrap_or_default(),
+                    status: row.status.parse().unwrap_or_default(),
+                    parent_id: row.parent_id,
                     created_at,
                 }))
             }
@@ -161,7 +465,7 @@ impl Comment {
     ) -> color_eyre::Result<Vec<Comment>> {
         let rows = sqlx::query!(
             r#"
-            SELECT id as "id!", review_id as "review_id!", file_path as "file_path!", line_number, content as "content!", resolved as "resolved!", created_at as "created_at!"
+            SELECT id as "id!", review_id as "review_id!", file_path as "file_path!", line_number, line_fingerprint, content as "content!", resolved as "resolved!", resolution_note, priority as "priority!", status as "status!", parent_id, created_at as "created_at!"
             FROM comments
             WHERE review_id = ?
             ORDER BY created_at DESC
@@ -182,8 +486,13 @@ impl Comment {
                 review_id: row.review_id,
                 file_path: row.file_path,
                 line_number: row.line_number,
+                line_fingerprint: row.line_fingerprint,
                 content: row.content,
                 resolved: row.resolved,
+                resolution_note: row.resolution_note,
+                priority: row.priority.parse().unwrap_or_default(),
+                status: row.status.parse().unwrap_or_default(),
+                parent_id: row.parent_id,
                 created_at,
             });
         }
@@ -199,7 +508,7 @@ impl Comment {
     ) -> color_eyre::Result<Vec<Comment>> {
         let rows = sqlx::query!(
             r#"
-            SELECT id as "id!", review_id as "review_id!", file_path as "file_path!", line_number, content as "content!", resolved as "resolved!", created_at as "created_at!"
+            SELECT id as "id!", review_id as "review_id!", file_path as "file_path!", line_number, line_fingerprint, content as "content!", resolved as "resolved!", resolution_note, priority as "priority!", status as "status!", parent_id, created_at as "created_at!"
             FROM comments
             WHERE review_id = ? AND file_path = ?
             ORDER BY created_at DESC
@@ -221,8 +530,13 @@ impl Comment {
                 review_id: row.review_id,
                 file_path: row.file_path,
                 line_number: row.line_number,
+                line_fingerprint: row.line_fingerprint,
                 content: row.content,
                 resolved: row.resolved,
+                resolution_note: row.resolution_note,
+                priority: row.priority.parse().unwrap_or_default(),
+                status: row.status.parse().unwrap_or_default(),
+                parent_id: row.parent_id,
                 created_at,
             });
         }
@@ -239,7 +553,7 @@ impl Comment {
     ) -> color_eyre::Result<Vec<Comment>> {
         let rows = sqlx::query!(
             r#"
-            SELECT id as "id!", review_id as "review_id!", file_path as "file_path!", line_number, content as "content!", resolved as "resolved!", created_at as "created_at!"
+            SELECT id as "id!", review_id as "review_id!", file_path as "file_path!", line_number, line_fingerprint, content as "content!", resolved as "resolved!", resolution_note, priority as "priority!", status as "status!", parent_id, created_at as "created_at!"
             FROM comments
             WHERE review_id = ? AND file_path = ? AND line_number = ?
             ORDER BY created_at DESC
@@ -262,8 +576,61 @@ impl Comment {
                 review_id: row.review_id,
                 file_path: row.file_path,
                 line_number: row.line_number,
+                line_fingerprint: row.line_fingerprint,
+                content: row.content,
+                resolved: row.resolved,
+                resolution_note: row.resolution_note,
+                priority: row.priority.parse().unwrap_or_default(),
+                status: row.status.parse().unwrap_or_default(),
+                parent_id: row.parent_id,
+                created_at,
+            });
+        }
+
+        Ok(comments)
+    }
+
+    /// Search comments of all reviews using the `comments_fts` full-text index.
+    ///
+    /// The query is matched as a phrase whose last word may be a prefix, so `"fix the bu"`
+    /// finds "Please fix the bug". There is no fallback to `LIKE`: sqlx bundles SQLite with the
+    /// FTS5 module, and without it the migration creating `comments_fts` would already fail.
+    pub async fn search_fts(pool: &SqlitePool, query: &str) -> color_eyre::Result<Vec<Comment>> {
+        let Some(fts_query) = Self::fts_phrase_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT comments.id as "id!", comments.review_id as "review_id!", comments.file_path as "file_path!", comments.line_number, comments.line_fingerprint, comments.content as "content!", comments.resolved as "resolved!", comments.resolution_note, comments.priority as "priority!", comments.status as "status!", comments.parent_id, comments.created_at as "created_at!"
+            FROM comments_fts
+            JOIN comments ON comments.rowid = comments_fts.rowid
+            WHERE comments_fts MATCH ?
+            ORDER BY comments.created_at DESC
+            "#,
+            fts_query
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut comments = Vec::new();
+        for row in rows {
+            let created_at = DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| color_eyre::eyre::eyre!("Failed to parse created_at: {}", e))?
+                .with_timezone(&Utc);
+
+            comments.push(Comment {
+                id: row.id,
+                review_id: row.review_id,
+                file_path: row.file_path,
+                line_number: row.line_number,
+                line_fingerprint: row.line_fingerprint,
                 content: row.content,
                 resolved: row.resolved,
+                resolution_note: row.resolution_note,
+                priority: row.priority.parse().unwrap_or_default(),
+                status: row.status.parse().unwrap_or_default(),
+                parent_id: row.parent_id,
                 created_at,
             });
         }
@@ -271,6 +638,39 @@ impl Comment {
         Ok(comments)
     }
 
+    /// Turn user input into an FTS5 prefix phrase query, quoting it so that FTS5 operators
+    /// and punctuation in the input are matched literally
+    fn fts_phrase_query(query: &str) -> Option<String> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        Some(format!("\"{}\"*", query.replace('"', "\"\"")))
+    }
+
+    /// Count the open (unresolved) and total comments of every review that has comments
+    pub async fn counts_by_review(
+        pool: &SqlitePool,
+    ) -> color_eyre::Result<HashMap<ReviewId, (i64, i64)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                review_id,
+                SUM(CASE WHEN resolved THEN 0 ELSE 1 END) as "open!: i64",
+                COUNT(*) as "total!: i64"
+            FROM comments
+            GROUP BY review_id
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.review_id, (row.open, row.total)))
+            .collect())
+    }
+
     /// Check if a file has any comments (file-level or line-level)
     pub async fn file_has_comments(
         pool: &SqlitePool,
@@ -418,7 +818,7 @@ impl Comment {
         file_path: &str,
     ) -> color_eyre::Result<()> {
         sqlx::query!(
-            "UPDATE comments SET resolved = FALSE WHERE review_id = ? AND file_path = ?",
+            "UPDATE comments SET resolved = FALSE, resolution_note = NULL WHERE review_id = ? AND file_path = ?",
             review_id,
             file_path
         )
@@ -438,7 +838,7 @@ impl Comment {
         line_number: i64,
     ) -> color_eyre::Result<()> {
         sqlx::query!(
-                    "UPDATE comments SET resolved = FALSE WHERE review_id = ? AND file_path = ? AND line_number = ?",
+                    "UPDATE comments SET resolved = FALSE, resolution_note = NULL WHERE review_id = ? AND file_path = ? AND line_number = ?",
                     review_id,
                     file_path,
                     line_number
@@ -463,9 +863,11 @@ impl Comment {
 mod tests {
     use super::*;
 
+    use std::time::{Duration, Instant};
+
     use sqlx::SqlitePool;
 
-    use crate::models::Review;
+    use crate::models::{DiffFile, Review};
 
     async fn create_test_pool() -> SqlitePool {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
@@ -500,6 +902,50 @@ mod tests {
         assert_eq!(line_comment.line_number, Some(42));
     }
 
+    #[test]
+    fn test_comment_locator() {
+        let line_comment = Comment::new("review-1", "src/main.rs", Some(42), "Fix this");
+        assert_eq!(line_comment.locator(), "src/main.rs:42");
+
+        let file_comment = Comment::new("review-1", "src/main.rs", None, "Split this file");
+        assert_eq!(file_comment.locator(), "src/main.rs");
+    }
+
+    #[test]
+    fn test_comment_priority_round_trip() {
+        for priority in [
+            CommentPriority::Low,
+            CommentPriority::Normal,
+            CommentPriority::High,
+        ] {
+            assert_eq!(priority.to_string().parse(), Ok(priority));
+        }
+        assert!("urgent".parse::<CommentPriority>().is_err());
+        assert!(CommentPriority::High > CommentPriority::Normal);
+        assert!(CommentPriority::Normal > CommentPriority::Low);
+    }
+
+    #[test]
+    fn test_comment_status_cycle() {
+        let mut status = CommentStatus::default();
+        let mut cycled = vec![];
+        for _ in 0..4 {
+            status = status.cycle();
+            assert_eq!(status.to_string().parse(), Ok(status));
+            cycled.push(status);
+        }
+        assert_eq!(
+            cycled,
+            vec![
+                CommentStatus::Addressed,
+                CommentStatus::WontFix,
+                CommentStatus::Question,
+                CommentStatus::Open,
+            ]
+        );
+        assert!("done".parse::<CommentStatus>().is_err());
+    }
+
     #[tokio::test]
     async fn test_comment_crud_operations() {
         let pool = create_test_pool().await;
@@ -513,7 +959,8 @@ mod tests {
         file_comment.create(&pool).await.unwrap();
 
         // Create line comment
-        let line_comment = Comment::new(&review.id, "src/main.rs", Some(10), "Line comment");
+        let line_comment = Comment::new(&review.id, "src/main.rs", Some(10), "Line comment")
+            .with_priority(CommentPriority::High);
         line_comment.create(&pool).await.unwrap();
 
         // Test find_for_file (should return both comments)
@@ -528,6 +975,7 @@ mod tests {
             .unwrap();
         assert_eq!(line_comments.len(), 1);
         assert_eq!(line_comments[0].content, "Line comment");
+        assert_eq!(line_comments[0].priority, CommentPriority::High);
 
         // Test file_has_comments
         assert!(
@@ -625,4 +1073,346 @@ mod tests {
                 .unwrap()
         );
     }
+
+    fn create_fingerprint_test_diff(content: &str) -> Diff {
+        Diff::from_files(vec![DiffFile::from_content("src/main.rs", content)])
+    }
+
+    #[test]
+    fn test_fingerprint_for_line() {
+        let content = "@@ -1,3 +1,3 @@\n fn main() {\n-    old();\n+    new();\n }";
+
+        assert_eq!(
+            Comment::fingerprint_for_line(content, 3),
+            Some("old();\nnew();\n}".to_string())
+        );
+        assert_eq!(
+            Comment::fingerprint_for_line(content, 0),
+            Some("@@\nfn main() {".to_string())
+        );
+        assert_eq!(Comment::fingerprint_for_line(content, 5), None);
+    }
+
+    #[test]
+    fn test_relocate_after_lines_inserted_above() {
+        let old_content = "@@ -1,3 +1,3 @@\n fn main() {\n-    old();\n+    new();\n }";
+        let comment = Comment::new("review-123", "src/main.rs", Some(3), "Comment")
+            .with_line_fingerprint(Comment::fingerprint_for_line(old_content, 3));
+
+        let new_content =
+            "@@ -1,5 +1,7 @@\n+use std::fs;\n+\n fn main() {\n-    old();\n+    new();\n }";
+        let diff = create_fingerprint_test_diff(new_content);
+
+        assert_eq!(comment.relocate(&diff), Some(5));
+    }
+
+    #[test]
+    fn test_relocate_in_large_file() {
+        let mut content = String::from("@@ -1,20000 +1,20001 @@\n+// Added at the top");
+        for index in 0..20_000 {
+            content.push_str(&format!("\n line_{index}();"));
+        }
+        let diff = create_fingerprint_test_diff(&content);
+        let old_content = content.replacen("\n+// Added at the top", "", 1);
+        let comment = Comment::new("review-123", "src/main.rs", Some(15_000), "Comment")
+            .with_line_fingerprint(Comment::fingerprint_for_line(&old_content, 15_000));
+
+        assert_eq!(comment.relocate(&diff), Some(15_001));
+    }
+
+    #[test]
+    fn test_relocate_falls_back_to_stored_line() {
+        let diff = create_fingerprint_test_diff("@@ -1 +1 @@\n-a\n+b");
+
+        let without_fingerprint = Comment::new("review-123", "src/main.rs", Some(1), "Comment");
+        assert_eq!(without_fingerprint.relocate(&diff), Some(1));
+
+        let no_match = Comment::new("review-123", "src/main.rs", Some(1), "Comment")
+            .with_line_fingerprint(Some("something else".to_string()));
+        assert_eq!(no_match.relocate(&diff), Some(1));
+
+        let missing_file = Comment::new("review-123", "src/other.rs", Some(1), "Comment")
+            .with_line_fingerprint(Some("a".to_string()));
+        assert_eq!(missing_file.relocate(&diff), Some(1));
+
+        let file_comment = Comment::new("review-123", "src/main.rs", None, "Comment")
+            .with_line_fingerprint(Some("a".to_string()));
+        assert_eq!(file_comment.relocate(&diff), None);
+    }
+
+    #[tokio::test]
+    async fn test_counts_by_review() {
+        let pool = create_test_pool().await;
+
+        let review = Review::builder().build();
+        review.save(&pool).await.unwrap();
+        let other_review = Review::builder().build();
+        other_review.save(&pool).await.unwrap();
+        let empty_review = Review::builder().build();
+        empty_review.save(&pool).await.unwrap();
+
+        Comment::new(&review.id, "src/main.rs", None, "Open")
+            .create(&pool)
+            .await
+            .unwrap();
+        Comment::new(&review.id, "src/main.rs", Some(3), "Open")
+            .create(&pool)
+            .await
+            .unwrap();
+        let mut resolved = Comment::new(&review.id, "src/lib.rs", None, "Resolved");
+        resolved.create(&pool).await.unwrap();
+        resolved.mark_resolved(&pool).await.unwrap();
+
+        let mut other_resolved = Comment::new(&other_review.id, "src/lib.rs", None, "Resolved");
+        other_resolved.create(&pool).await.unwrap();
+        other_resolved.mark_resolved(&pool).await.unwrap();
+
+        let counts = Comment::counts_by_review(&pool).await.unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts.get(&review.id), Some(&(2, 3)));
+        assert_eq!(counts.get(&other_review.id), Some(&(0, 1)));
+        assert_eq!(counts.get(&empty_review.id), None);
+    }
+
+    #[tokio::test]
+    async fn test_set_line_number() {
+        let pool = create_test_pool().await;
+        let review = Review::builder().build();
+        review.save(&pool).await.unwrap();
+
+        let mut comment = Comment::new(&review.id, "src/main.rs", Some(3), "Comment")
+            .with_line_fingerprint(Some("fingerprint".to_string()));
+        comment.create(&pool).await.unwrap();
+        comment.set_line_number(&pool, 7).await.unwrap();
+
+        let stored = Comment::find_by_id(&pool, &comment.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.line_number, Some(7));
+        assert_eq!(stored.line_fingerprint, Some("fingerprint".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_set_status() {
+        let pool = create_test_pool().await;
+        let review = Review::builder().build();
+        review.save(&pool).await.unwrap();
+        let mut comment = Comment::new(&review.id, "src/main.rs", Some(3), "Comment");
+        comment.create(&pool).await.unwrap();
+        assert!(comment.needs_attention());
+
+        comment
+            .set_status(&pool, CommentStatus::WontFix)
+            .await
+            .unwrap();
+
+        let stored = Comment::find_by_id(&pool, &comment.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, CommentStatus::WontFix);
+        assert!(!stored.resolved);
+        assert!(!stored.needs_attention());
+    }
+
+    #[tokio::test]
+    async fn test_mark_resolved_with_note() {
+        let pool = create_test_pool().await;
+        let review = Review::builder().build();
+        review.save(&pool).await.unwrap();
+        let mut comment = Comment::new(&review.id, "src/main.rs", None, "Comment");
+        comment.create(&pool).await.unwrap();
+        let find = |id: String| {
+            let pool = pool.clone();
+            async move { Comment::find_by_id(&pool, &id).await.unwrap().unwrap() }
+        };
+
+        comment
+            .mark_resolved_with_note(&pool, Some(" Fixed in abc123 "))
+            .await
+            .unwrap();
+        let stored = find(comment.id.clone()).await;
+        assert!(stored.resolved);
+        assert_eq!(stored.resolution_note.as_deref(), Some("Fixed in abc123"));
+
+        // Reopening the comment clears the note
+        comment.mark_unresolved(&pool).await.unwrap();
+        let stored = find(comment.id.clone()).await;
+        assert!(!stored.resolved);
+        assert_eq!(stored.resolution_note, None);
+
+        // A blank note is not stored
+        comment
+            .mark_resolved_with_note(&pool, Some("  "))
+            .await
+            .unwrap();
+        assert_eq!(find(comment.id.clone()).await.resolution_note, None);
+    }
+
+    #[tokio::test]
+    async fn test_reply_is_resolved_with_its_parent() {
+        let pool = create_test_pool().await;
+        let review = Review::builder().build();
+        review.save(&pool).await.unwrap();
+        let mut parent = Comment::new(&review.id, "src/main.rs", Some(3), "Parent");
+        parent.create(&pool).await.unwrap();
+        let reply = Comment::new(&review.id, "src/main.rs", Some(3), "Reply")
+            .with_parent_id(Some(parent.id.clone()));
+        reply.create(&pool).await.unwrap();
+
+        let stored = Comment::find_by_id(&pool, &reply.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.parent_id, Some(parent.id.clone()));
+        assert!(!stored.resolved);
+
+        parent.mark_resolved(&pool).await.unwrap();
+        let stored = Comment::find_by_id(&pool, &reply.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.resolved);
+
+        // Deleting the parent deletes its replies
+        Comment::delete(&pool, &parent.id).await.unwrap();
+        assert!(
+            Comment::find_by_id(&pool, &reply.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_fts() {
+        let pool = create_test_pool().await;
+        let review = Review::builder().build();
+        review.save(&pool).await.unwrap();
+
+        let comment = Comment::new(&review.id, "src/main.rs", None, "Please fix the bug");
+        comment.create(&pool).await.unwrap();
+        Comment::new(
+            &review.id,
+            "src/lib.rs",
+            Some(1),
+            "Looks good \"to me\" OR not",
+        )
+        .create(&pool)
+        .await
+        .unwrap();
+
+        let results = Comment::search_fts(&pool, "fix the bu").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, comment.id);
+
+        assert!(
+            Comment::search_fts(&pool, "bug fix")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            Comment::search_fts(&pool, "\"to me\" OR")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(Comment::search_fts(&pool, "").await.unwrap().is_empty());
+
+        // Deleted comments are removed from the index
+        Comment::delete(&pool, &comment.id).await.unwrap();
+        assert!(Comment::search_fts(&pool, "bug").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_fts_on_many_comments() {
+        let pool = create_test_pool().await;
+        let review = Review::builder().build();
+        review.save(&pool).await.unwrap();
+
+        let words = ["refactor", "naming", "performance", "tests", "docs"];
+        for i in 0..3000 {
+            let content = format!("Comment {i} about {}", words[i % words.len()]);
+            Comment::new(&review.id, "src/main.rs", Some(i as i64), &content)
+                .create(&pool)
+                .await
+                .unwrap();
+        }
+
+        let results = Comment::search_fts(&pool, "about performance")
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 600);
+        assert!(
+            results
+                .iter()
+                .all(|comment| comment.content.ends_with("about performance"))
+        );
+    }
+    /// Fastest of a few runs of a query counting the comments that match the pattern
+    async fn fastest_count_query(pool: &SqlitePool, sql: &str, pattern: &str) -> Duration {
+        let mut fastest = Duration::MAX;
+        for _ in 0..5 {
+            let started_at = Instant::now();
+            let count: i64 = sqlx::query_scalar(sql)
+                .bind(pattern)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+            fastest = fastest.min(started_at.elapsed());
+            assert_eq!(count, 1);
+        }
+        fastest
+    }
+
+    #[tokio::test]
+    async fn test_search_fts_benchmark_against_like() {
+        let pool = create_test_pool().await;
+        let review = Review::builder().build();
+        review.save(&pool).await.unwrap();
+
+        sqlx::query(
+            r#"
+            WITH RECURSIVE numbers(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM numbers WHERE n < 50000)
+            INSERT INTO comments (id, review_id, file_path, line_number, content, created_at)
+            SELECT 'comment-' || n, ?1, 'src/main.rs', n, 'Comment ' || n || ' about the naming of things', '2025-01-01T00:00:00Z'
+            FROM numbers
+            "#,
+        )
+        .bind(&review.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        Comment::new(
+            &review.id,
+            "src/lib.rs",
+            Some(1),
+            "A needle in the haystack",
+        )
+        .create(&pool)
+        .await
+        .unwrap();
+
+        let fts = fastest_count_query(
+            &pool,
+            "SELECT COUNT(*) FROM comments_fts WHERE comments_fts MATCH ?",
+            "needle",
+        )
+        .await;
+        let like = fastest_count_query(
+            &pool,
+            "SELECT COUNT(*) FROM comments WHERE content LIKE ?",
+            "%needle%",
+        )
+        .await;
+        println!("Searching 50001 comments: FTS5 {fts:?}, LIKE {like:?}");
+
+        // The index lookup doesn't scan the content of every comment like `LIKE` does
+        assert!(fts < like, "FTS5 took {fts:?}, LIKE {like:?}");
+        assert_eq!(Comment::search_fts(&pool, "needle").await.unwrap().len(), 1);
+    }
 }
@@ -1,12 +1,325 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use crate::exclude_patterns::ExcludePatterns;
 
 /// Represents a single file in a Git diff
 #[derive(Debug, Clone, PartialEq)]
 pub struct DiffFile {
     /// Path to the file being changed
     pub path: String,
-    /// Diff content for this specific file
+    /// Hunks of the diff of this file, empty for metadata-only changes
+    hunks: Vec<Hunk>,
+    /// Flat diff content reconstructed from the hunks, or the description of a metadata change
+    content: String,
+    /// Whether the content is only a description of a metadata change (e.g. file mode or
+    /// symlink target) instead of diff lines, so there are no lines to navigate
+    metadata_only: bool,
+}
+
+impl DiffFile {
+    /// Create a file from the hunks of its diff
+    pub fn new(path: impl Into<String>, hunks: Vec<Hunk>) -> Self {
+        let content = hunks.iter().map(Hunk::text).collect();
+        Self {
+            path: path.into(),
+            hunks,
+            content,
+            metadata_only: false,
+        }
+    }
+
+    /// Create a file from flat diff content, see [`Hunk::parse_content`]
+    pub fn from_content(path: impl Into<String>, content: &str) -> Self {
+        Self::new(path, Hunk::parse_content(content))
+    }
+
+    /// Create a file whose only change is a metadata change (e.g. file mode or symlink target)
+    /// with the description of that change as content
+    pub fn metadata(path: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            hunks: vec![],
+            content: description.into(),
+            metadata_only: true,
+        }
+    }
+
+    /// Hunks of the diff of this file
+    pub fn hunks(&self) -> &[Hunk] {
+        &self.hunks
+    }
+
+    /// Flat diff content of this file as Git prints it, or the description of a metadata change
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Whether the content is only a description of a metadata change
+    pub fn metadata_only(&self) -> bool {
+        self.metadata_only
+    }
+
+    /// Hash of the diff content of this file, to detect that the file changed after it was
+    /// marked as viewed
+    pub fn content_hash(&self) -> String {
+        Self::hash_content(&self.content)
+    }
+
+    /// Git blob hash of diff content, stable across versions of this tool
+    pub fn hash_content(content: &str) -> String {
+        git2::Oid::hash_object(git2::ObjectType::Blob, content.as_bytes())
+            .map(|oid| oid.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Whether the file has diff lines to navigate. Metadata-only changes and added or deleted
+    /// empty files have none.
+    pub fn has_lines(&self) -> bool {
+        !self.hunks.is_empty()
+    }
+
+    /// Count the added and removed lines of this file
+    pub fn line_stats(&self) -> (usize, usize) {
+        self.hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .fold((0, 0), |(added, removed), line| match line.origin {
+                '+' => (added + 1, removed),
+                '-' => (added, removed + 1),
+                _ => (added, removed),
+            })
+    }
+
+    /// Line numbers in the new file for every line of the diff content. Hunk headers, removed
+    /// lines and lines outside of hunks have no line in the new file.
+    pub fn new_line_numbers(&self) -> Vec<Option<usize>> {
+        let mut next_line = None;
+        self.content
+            .lines()
+            .enumerate()
+            .map(|(line_index, line)| {
+                if self.metadata_only {
+                    return None;
+                }
+                if let Some(hunk) = DiffHunk::parse(line_index, line) {
+                    next_line = Some(hunk.new_start);
+                    return None;
+                }
+                match (line.chars().next(), next_line) {
+                    (Some('+' | ' '), Some(line_number)) => {
+                        next_line = Some(line_number + 1);
+                        Some(line_number)
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Parse the positions of the hunks of this file from their headers
+    pub fn hunk_positions(&self) -> Vec<DiffHunk> {
+        if self.metadata_only {
+            return vec![];
+        }
+        self.content
+            .lines()
+            .enumerate()
+            .filter_map(|(line_index, line)| DiffHunk::parse(line_index, line))
+            .collect()
+    }
+
+    /// Text of the hunk containing the line, from its `@@` header up to the next header or the
+    /// end of the file. Returns `None` for lines before the first hunk.
+    pub fn hunk_text(&self, line_index: usize) -> Option<String> {
+        let hunks = self.hunk_positions();
+        let position = hunks
+            .iter()
+            .rposition(|hunk| hunk.line_index <= line_index)?;
+        let start = hunks[position].line_index;
+        let end = hunks
+            .get(position + 1)
+            .map_or(usize::MAX, |hunk| hunk.line_index);
+        Some(
+            self.content
+                .lines()
+                .skip(start)
+                .take(end - start)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
+
+/// A hunk of a file diff with its lines
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hunk {
+    /// The `@@ -a,b +c,d @@` header line including its line break. Empty for the lines before
+    /// the first header of flat content.
+    pub header: String,
+    /// Lines of the hunk
+    pub lines: Vec<DiffLine>,
+}
+
+impl Hunk {
+    /// Flat text of the hunk as Git prints it, the header followed by the lines
+    pub fn text(&self) -> String {
+        let mut text = self.header.clone();
+        for line in &self.lines {
+            if line.has_origin_prefix() {
+                text.push(line.origin);
+            }
+            text.push_str(&line.content);
+        }
+        text
+    }
+
+    /// Parse flat diff content into hunks. Line numbers are counted from the hunk headers,
+    /// lines before the first header are kept in a hunk without header and line numbers.
+    pub fn parse_content(content: &str) -> Vec<Self> {
+        let mut hunks: Vec<Self> = vec![];
+        let mut next_line_numbers = None;
+
+        for line in content.split_inclusive('\n') {
+            if let Some(position) = DiffHunk::parse(0, line) {
+                next_line_numbers = Some((position.old_start, position.new_start));
+                hunks.push(Self {
+                    header: line.to_string(),
+                    lines: vec![],
+                });
+                continue;
+            }
+            if hunks.is_empty() {
+                hunks.push(Self {
+                    header: String::new(),
+                    lines: vec![],
+                });
+            }
+
+            let origin = match line.chars().next() {
+                Some(origin @ ('+' | '-' | ' ' | DiffLine::NO_NEWLINE_MARKER)) => origin,
+                _ => DiffLine::NO_ORIGIN_PREFIX,
+            };
+            let (old_lineno, new_lineno) = match (origin, next_line_numbers) {
+                (' ', Some((old, new))) => (Some(old), Some(new)),
+                ('-', Some((old, _))) => (Some(old), None),
+                ('+', Some((_, new))) => (None, Some(new)),
+                _ => (None, None),
+            };
+            if let Some((old, new)) = next_line_numbers.as_mut() {
+                *old += usize::from(old_lineno.is_some());
+                *new += usize::from(new_lineno.is_some());
+            }
+            let content = match origin {
+                '+' | '-' | ' ' => &line[1..],
+                _ => line,
+            };
+
+            if let Some(hunk) = hunks.last_mut() {
+                hunk.lines.push(DiffLine {
+                    origin,
+                    content: content.to_string(),
+                    old_lineno,
+                    new_lineno,
+                });
+            }
+        }
+
+        hunks
+    }
+}
+
+/// A line of a hunk
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffLine {
+    /// Origin of the line as Git reports it: `+` added, `-` removed, ` ` context, `\\` for the
+    /// `\ No newline at end of file` marker. Only the origins of added, removed and context
+    /// lines are printed in front of the content, the marker is its own content.
+    pub origin: char,
+    /// Content of the line without the origin, including its line break
     pub content: String,
+    /// Line number in the old file, `None` for added lines
+    pub old_lineno: Option<usize>,
+    /// Line number in the new file, `None` for removed lines
+    pub new_lineno: Option<usize>,
+}
+
+impl DiffLine {
+    /// Origin of lines of flat content without a `+`, `-` or ` ` prefix
+    pub const NO_ORIGIN_PREFIX: char = '=';
+    /// Origin of the marker after the last line of a file that doesn't end with a line break
+    pub const NO_NEWLINE_MARKER: char = '\\';
+
+    /// The `\ No newline at end of file` marker following the line it is about
+    pub fn no_newline_marker() -> Self {
+        Self {
+            origin: Self::NO_NEWLINE_MARKER,
+            content: "\\ No newline at end of file\n".to_string(),
+            old_lineno: None,
+            new_lineno: None,
+        }
+    }
+
+    /// Whether the origin is printed in front of the content
+    pub fn has_origin_prefix(&self) -> bool {
+        matches!(self.origin, '+' | '-' | ' ')
+    }
+}
+
+/// Position of a hunk in the old and new file, parsed from its `@@ -a,b +c,d @@` header
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiffHunk {
+    /// Index of the hunk header line in the diff content
+    pub line_index: usize,
+    /// First line of the hunk in the old file (1-based, 0 if the file was added)
+    pub old_start: usize,
+    /// Number of lines of the hunk in the old file
+    pub old_lines: usize,
+    /// First line of the hunk in the new file (1-based, 0 if the file was deleted)
+    pub new_start: usize,
+    /// Number of lines of the hunk in the new file
+    pub new_lines: usize,
+}
+
+impl DiffHunk {
+    /// Parse a hunk header line, returns `None` for all other lines
+    pub fn parse(line_index: usize, line: &str) -> Option<Self> {
+        let mut ranges = line.strip_prefix("@@ -")?.split_whitespace();
+        let (old_start, old_lines) = Self::parse_range(ranges.next()?)?;
+        let (new_start, new_lines) = Self::parse_range(ranges.next()?.strip_prefix('+')?)?;
+        Some(Self {
+            line_index,
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+        })
+    }
+
+    /// Parse a `start,lines` range, the number of lines is 1 when it is left out
+    fn parse_range(range: &str) -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, lines)) => Some((start.parse().ok()?, lines.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    }
+
+    /// First line of the hunk in the new file. Hunks that only remove lines are positioned
+    /// after `new_start`.
+    pub fn new_first_line(&self) -> usize {
+        if self.new_lines == 0 {
+            self.new_start + 1
+        } else {
+            self.new_start
+        }
+    }
+
+    /// Line in the new file after the last line of the hunk
+    pub fn new_end(&self) -> usize {
+        self.new_first_line() + self.new_lines
+    }
 }
 
 /// Represents a complete Git diff with structured data
@@ -14,6 +327,12 @@ pub struct DiffFile {
 pub struct Diff {
     /// List of files changed in this diff
     pub files: Arc<[DiffFile]>,
+    /// Old path => new path of the files that were renamed
+    pub renamed_paths: Arc<HashMap<String, String>>,
+    /// Paths of the files that were deleted, they are not in the target anymore
+    pub deleted_paths: Arc<HashSet<String>>,
+    /// Files left out of `files` because they match the exclude patterns of the repository
+    pub excluded_files: Arc<[DiffFile]>,
 }
 
 impl Diff {
@@ -21,6 +340,9 @@ impl Diff {
     pub fn empty() -> Self {
         Self {
             files: Arc::new([]),
+            renamed_paths: Arc::new(HashMap::new()),
+            deleted_paths: Arc::new(HashSet::new()),
+            excluded_files: Arc::new([]),
         }
     }
 
@@ -28,9 +350,57 @@ impl Diff {
     pub fn from_files(files: Vec<DiffFile>) -> Self {
         Self {
             files: files.into(),
+            renamed_paths: Arc::new(HashMap::new()),
+            deleted_paths: Arc::new(HashSet::new()),
+            excluded_files: Arc::new([]),
         }
     }
 
+    /// Set the old path => new path mapping of the renamed files
+    pub fn with_renamed_paths(mut self, renamed_paths: HashMap<String, String>) -> Self {
+        self.renamed_paths = Arc::new(renamed_paths);
+        self
+    }
+
+    /// Set the paths of the deleted files
+    pub fn with_deleted_paths(mut self, deleted_paths: HashSet<String>) -> Self {
+        self.deleted_paths = Arc::new(deleted_paths);
+        self
+    }
+
+    /// Move the files matching the exclude patterns from `files` to `excluded_files`
+    pub fn with_excluded_files(mut self, exclude_patterns: &ExcludePatterns) -> Self {
+        let (excluded_files, files): (Vec<DiffFile>, Vec<DiffFile>) = self
+            .files
+            .iter()
+            .cloned()
+            .partition(|file| exclude_patterns.is_excluded(&file.path));
+        self.files = files.into();
+        self.excluded_files = excluded_files.into();
+        self
+    }
+
+    /// Whether the file was deleted
+    pub fn is_deleted(&self, path: &str) -> bool {
+        self.deleted_paths.contains(path)
+    }
+
+    /// Old path of a renamed file
+    pub fn old_path(&self, new_path: &str) -> Option<&str> {
+        self.renamed_paths
+            .iter()
+            .find(|(_, path)| *path == new_path)
+            .map(|(old_path, _)| old_path.as_str())
+    }
+
+    /// Path of the file in this diff, following renames
+    pub fn current_path<'a>(&'a self, path: &'a str) -> &'a str {
+        self.renamed_paths
+            .get(path)
+            .map(String::as_str)
+            .unwrap_or(path)
+    }
+
     /// Check if the diff is empty (no files)
     pub fn is_empty(&self) -> bool {
         self.files.is_empty()
@@ -40,6 +410,28 @@ impl Diff {
     pub fn file_count(&self) -> usize {
         self.files.len()
     }
+
+    /// Count the added and removed lines of all files
+    pub fn line_stats(&self) -> (usize, usize) {
+        self.files.iter().map(DiffFile::line_stats).fold(
+            (0, 0),
+            |(added, removed), (file_added, file_removed)| {
+                (added + file_added, removed + file_removed)
+            },
+        )
+    }
+
+    /// Files with their added and removed lines, the most changed files (added plus removed
+    /// lines) first. Files with the same churn keep their diff order.
+    pub fn files_by_churn(&self) -> Vec<(&DiffFile, (usize, usize))> {
+        let mut files: Vec<_> = self
+            .files
+            .iter()
+            .map(|file| (file, file.line_stats()))
+            .collect();
+        files.sort_by_key(|(_, (added, removed))| std::cmp::Reverse(added + removed));
+        files
+    }
 }
 
 impl Default for Diff {
@@ -52,24 +444,254 @@ impl Default for Diff {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_diff_file_new_line_numbers() {
+        let file = DiffFile::from_content(
+            "foo.txt",
+            "diff --git a/foo.txt b/foo.txt\n@@ -3,3 +3,3 @@\n a\n-b\n+c\n d\n@@ -20 +20,2 @@\n e\n+f",
+        );
+
+        assert_eq!(
+            file.new_line_numbers(),
+            vec![
+                None,
+                None,
+                Some(3),
+                None,
+                Some(4),
+                Some(5),
+                None,
+                Some(20),
+                Some(21)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_file_content_hash() {
+        let file = DiffFile::from_content("foo.txt", "diff content");
+        let changed_file = DiffFile::from_content("foo.txt", "changed diff content");
+
+        assert_eq!(file.content_hash(), DiffFile::hash_content("diff content"));
+        assert_eq!(file.content_hash().len(), 40);
+        assert_ne!(file.content_hash(), changed_file.content_hash());
+    }
+
     #[test]
     fn test_diff_file_equality() {
-        let file1 = DiffFile {
-            path: "foo.txt".to_string(),
-            content: "diff content".to_string(),
-        };
-        let file2 = DiffFile {
-            path: "foo.txt".to_string(),
-            content: "diff content".to_string(),
-        };
-        let file3 = DiffFile {
-            path: "bar.txt".to_string(),
-            content: "other diff".to_string(),
-        };
+        let file1 = DiffFile::from_content("foo.txt", "diff content");
+        let file2 = DiffFile::from_content("foo.txt", "diff content");
+        let file3 = DiffFile::from_content("bar.txt", "other diff");
         assert_eq!(file1, file2);
         assert_ne!(file1, file3);
     }
 
+    #[test]
+    fn test_diff_file_line_stats() {
+        let file =
+            DiffFile::from_content("foo.txt", "@@ -1,3 +1,4 @@\n context\n-old\n+new\n+added");
+        assert_eq!(file.line_stats(), (2, 1));
+    }
+
+    #[test]
+    fn test_diff_file_hunk_text() {
+        let file = DiffFile::from_content(
+            "foo.txt",
+            "@@ -1,2 +1,2 @@\n a\n-b\n+c\n@@ -10,1 +10,2 @@\n x\n+y",
+        );
+
+        let first_hunk = "@@ -1,2 +1,2 @@\n a\n-b\n+c";
+        assert_eq!(file.hunk_text(0).as_deref(), Some(first_hunk));
+        assert_eq!(file.hunk_text(3).as_deref(), Some(first_hunk));
+        let last_hunk = "@@ -10,1 +10,2 @@\n x\n+y";
+        assert_eq!(file.hunk_text(4).as_deref(), Some(last_hunk));
+        assert_eq!(file.hunk_text(6).as_deref(), Some(last_hunk));
+
+        let metadata_file = DiffFile::metadata("foo.txt", "mode changed 100644 → 100755");
+        assert_eq!(metadata_file.hunk_text(0), None);
+    }
+
+    #[test]
+    fn test_diff_line_stats() {
+        let diff = Diff::from_files(vec![
+            DiffFile::from_content("foo.txt", "@@ -1,2 +1,2 @@\n-old\n+new"),
+            DiffFile::from_content("bar.txt", "@@ -0,0 +1,2 @@\n+first\n+second"),
+        ]);
+
+        assert_eq!(diff.line_stats(), (3, 1));
+        assert_eq!(Diff::empty().line_stats(), (0, 0));
+    }
+
+    #[test]
+    fn test_diff_files_by_churn() {
+        let file = |path: &str, content: &str| DiffFile::from_content(path, content);
+        let diff = Diff::from_files(vec![
+            file("small.txt", "@@ -1 +1 @@\n-old\n+new"),
+            file("large.txt", "@@ -1 +1,3 @@\n-old\n+new\n+more\n+most"),
+            file("same.txt", "@@ -0,0 +1,2 @@\n+first\n+second"),
+        ]);
+
+        let churn: Vec<_> = diff
+            .files_by_churn()
+            .into_iter()
+            .map(|(file, stats)| (file.path.as_str(), stats))
+            .collect();
+        assert_eq!(
+            churn,
+            vec![
+                ("large.txt", (3, 1)),
+                ("small.txt", (1, 1)),
+                ("same.txt", (2, 0)),
+            ]
+        );
+        assert!(Diff::empty().files_by_churn().is_empty());
+    }
+
+    #[test]
+    fn test_diff_renamed_paths() {
+        let diff = Diff::empty().with_renamed_paths(HashMap::from([(
+            "src/old.rs".to_string(),
+            "src/new.rs".to_string(),
+        )]));
+
+        assert_eq!(diff.old_path("src/new.rs"), Some("src/old.rs"));
+        assert_eq!(diff.old_path("src/old.rs"), None);
+        assert_eq!(diff.current_path("src/old.rs"), "src/new.rs");
+        assert_eq!(diff.current_path("src/other.rs"), "src/other.rs");
+    }
+
+    #[test]
+    fn test_diff_deleted_paths() {
+        let diff = Diff::empty().with_deleted_paths(HashSet::from(["src/old.rs".to_string()]));
+
+        assert!(diff.is_deleted("src/old.rs"));
+        assert!(!diff.is_deleted("src/new.rs"));
+    }
+
+    #[test]
+    fn test_hunk_parse_content() {
+        let hunks =
+            Hunk::parse_content("@@ -3,2 +3,3 @@ fn main()\n a\n-b\n+c\n+d\n@@ -20 +21 @@\n-e\n");
+
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].header, "@@ -3,2 +3,3 @@ fn main()\n");
+        assert_eq!(
+            hunks[0].lines,
+            vec![
+                DiffLine {
+                    origin: ' ',
+                    content: "a\n".to_string(),
+                    old_lineno: Some(3),
+                    new_lineno: Some(3),
+                },
+                DiffLine {
+                    origin: '-',
+                    content: "b\n".to_string(),
+                    old_lineno: Some(4),
+                    new_lineno: None,
+                },
+                DiffLine {
+                    origin: '+',
+                    content: "c\n".to_string(),
+                    old_lineno: None,
+                    new_lineno: Some(4),
+                },
+                DiffLine {
+                    origin: '+',
+                    content: "d\n".to_string(),
+                    old_lineno: None,
+                    new_lineno: Some(5),
+                },
+            ]
+        );
+        assert_eq!(hunks[1].header, "@@ -20 +21 @@\n");
+        assert_eq!(
+            hunks[1].lines,
+            vec![DiffLine {
+                origin: '-',
+                content: "e\n".to_string(),
+                old_lineno: Some(20),
+                new_lineno: None,
+            }]
+        );
+        assert!(Hunk::parse_content("").is_empty());
+    }
+
+    #[test]
+    fn test_hunk_parse_content_without_header() {
+        let hunks = Hunk::parse_content("diff --git a/foo.txt b/foo.txt\n+added");
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].header, "");
+        assert_eq!(hunks[0].lines[0].origin, DiffLine::NO_ORIGIN_PREFIX);
+        assert_eq!(
+            hunks[0].lines[0].content,
+            "diff --git a/foo.txt b/foo.txt\n"
+        );
+        assert_eq!(hunks[0].lines[1].origin, '+');
+        assert_eq!(hunks[0].lines[1].new_lineno, None);
+    }
+
+    #[test]
+    fn test_diff_file_content_is_reconstructed_from_hunks() {
+        for content in [
+            "",
+            "@@ -1,2 +1,2 @@\n a\n-b\n+c\n",
+            "@@ -1 +1 @@\n-old\n\\ No newline at end of file\n+new",
+            "diff --git a/foo.txt b/foo.txt\n@@ -3 +3 @@\n-a\n+b\n@@ -9 +9 @@\n c",
+        ] {
+            let file = DiffFile::from_content("foo.txt", content);
+            assert_eq!(file.content(), content);
+            assert_eq!(file.content_hash(), DiffFile::hash_content(content));
+        }
+
+        let metadata_file = DiffFile::metadata("foo.txt", "mode changed 100644 → 100755");
+        assert_eq!(metadata_file.content(), "mode changed 100644 → 100755");
+        assert!(metadata_file.hunks().is_empty());
+        assert!(metadata_file.metadata_only());
+        assert!(!metadata_file.has_lines());
+    }
+
+    #[test]
+    fn test_diff_file_hunk_positions() {
+        let file = DiffFile::from_content(
+            "foo.txt",
+            "@@ -1,3 +1,4 @@ fn main()\n context\n-old\n+new\n+added\n@@ -20 +21 @@\n-a\n+b",
+        );
+        assert_eq!(
+            file.hunk_positions(),
+            vec![
+                DiffHunk {
+                    line_index: 0,
+                    old_start: 1,
+                    old_lines: 3,
+                    new_start: 1,
+                    new_lines: 4,
+                },
+                DiffHunk {
+                    line_index: 5,
+                    old_start: 20,
+                    old_lines: 1,
+                    new_start: 21,
+                    new_lines: 1,
+                },
+            ]
+        );
+        assert_eq!(file.hunk_positions()[1].new_first_line(), 21);
+        assert_eq!(file.hunk_positions()[1].new_end(), 22);
+
+        let deleted_file = DiffFile::from_content("foo.txt", "@@ -1,2 +0,0 @@\n-a\n-b");
+        assert_eq!(deleted_file.hunk_positions()[0].new_start, 0);
+        assert_eq!(deleted_file.hunk_positions()[0].new_lines, 0);
+
+        let removal_only_hunk = DiffHunk::parse(0, "@@ -5,2 +4,0 @@").unwrap();
+        assert_eq!(removal_only_hunk.new_first_line(), 5);
+        assert_eq!(removal_only_hunk.new_end(), 5);
+
+        let metadata_file = DiffFile::metadata("foo.txt", "mode changed 100644 → 100755");
+        assert!(metadata_file.hunk_positions().is_empty());
+    }
+
     #[test]
     fn test_diff_empty() {
         let diff = Diff::empty();
@@ -81,14 +703,8 @@ mod tests {
     #[test]
     fn test_diff_from_files() {
         let files = vec![
-            DiffFile {
-                path: "a.txt".to_string(),
-                content: "diff a".to_string(),
-            },
-            DiffFile {
-                path: "b.txt".to_string(),
-                content: "diff b".to_string(),
-            },
+            DiffFile::from_content("a.txt", "diff a"),
+            DiffFile::from_content("b.txt", "diff b"),
         ];
         let diff = Diff::from_files(files.clone());
         assert!(!diff.is_empty());
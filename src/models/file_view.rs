@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use sqlx::{FromRow, SqlitePool};
 
@@ -6,11 +8,44 @@ use crate::{
     time_provider::{SystemTimeProvider, TimeProvider},
 };
 
+/// Whether a file of a review still needs to be reviewed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileViewStatus {
+    #[default]
+    NotViewed,
+    Viewed,
+    /// Consciously not reviewed, e.g. generated code. It doesn't need to be viewed anymore.
+    Skipped,
+}
+
+impl FileViewStatus {
+    /// Status after toggling the viewed status, a skipped file is not viewed afterwards
+    pub fn toggle_viewed(self) -> Self {
+        match self {
+            Self::NotViewed => Self::Viewed,
+            Self::Viewed | Self::Skipped => Self::NotViewed,
+        }
+    }
+
+    /// Status after toggling the skipped status, a viewed file is skipped afterwards
+    pub fn toggle_skipped(self) -> Self {
+        match self {
+            Self::NotViewed | Self::Viewed => Self::Skipped,
+            Self::Skipped => Self::NotViewed,
+        }
+    }
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct FileView {
     pub id: i64,
     pub review_id: ReviewId,
     pub file_path: String,
+    /// Hash of the diff content of the file when it was marked as viewed
+    /// (see [`crate::models::DiffFile::content_hash`])
+    pub content_hash: Option<String>,
+    /// The file is skipped instead of viewed
+    pub skipped: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -28,21 +63,51 @@ impl FileView {
             id: 0, // Will be set by database
             review_id,
             file_path,
+            content_hash: None,
+            skipped: false,
             created_at: time_provider.now(),
         }
     }
 
-    /// Mark a file as viewed for a review
+    /// Mark a file as viewed for a review, remembering the hash of its diff content so that
+    /// the file can be reset to not viewed when it changes
     pub async fn mark_as_viewed(
         pool: &SqlitePool,
         review_id: &str,
         file_path: &str,
+        content_hash: Option<&str>,
     ) -> Result<(), sqlx::Error> {
         let created_at = Utc::now().to_rfc3339();
         sqlx::query!(
             r#"
-            INSERT OR IGNORE INTO file_views (review_id, file_path, created_at)
-            VALUES (?1, ?2, ?3)
+            INSERT INTO file_views (review_id, file_path, content_hash, created_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (review_id, file_path)
+            DO UPDATE SET content_hash = excluded.content_hash, skipped = FALSE
+            "#,
+            review_id,
+            file_path,
+            content_hash,
+            created_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark a file as skipped for a review. A skipped file keeps its status when its diff
+    /// content changes, so no content hash is stored.
+    pub async fn mark_as_skipped(
+        pool: &SqlitePool,
+        review_id: &str,
+        file_path: &str,
+    ) -> Result<(), sqlx::Error> {
+        let created_at = Utc::now().to_rfc3339();
+        sqlx::query!(
+            r#"
+            INSERT INTO file_views (review_id, file_path, skipped, created_at)
+            VALUES (?1, ?2, TRUE, ?3)
+            ON CONFLICT (review_id, file_path) DO UPDATE SET content_hash = NULL, skipped = TRUE
             "#,
             review_id,
             file_path,
@@ -72,7 +137,7 @@ impl FileView {
         Ok(())
     }
 
-    /// Get all viewed file paths for a review
+    /// Get all viewed file paths for a review, skipped files are not viewed
     pub async fn get_viewed_files(
         pool: &SqlitePool,
         review_id: &str,
@@ -81,7 +146,7 @@ impl FileView {
             r#"
             SELECT file_path
             FROM file_views
-            WHERE review_id = ?1
+            WHERE review_id = ?1 AND NOT skipped
             ORDER BY created_at ASC
             "#,
             review_id
@@ -91,24 +156,99 @@ impl FileView {
         Ok(file_paths)
     }
 
-    /// Check if a file is viewed for a review
+    /// Get all skipped file paths for a review
+    pub async fn get_skipped_files(
+        pool: &SqlitePool,
+        review_id: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let file_paths = sqlx::query_scalar!(
+            r#"
+            SELECT file_path
+            FROM file_views
+            WHERE review_id = ?1 AND skipped
+            ORDER BY created_at ASC
+            "#,
+            review_id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(file_paths)
+    }
+
+    /// Number of viewed and of skipped files per review, reviews without either are missing
+    pub async fn counts_by_review(
+        pool: &SqlitePool,
+    ) -> Result<HashMap<ReviewId, (i64, i64)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                review_id,
+                SUM(CASE WHEN skipped THEN 0 ELSE 1 END) as "viewed!: i64",
+                SUM(CASE WHEN skipped THEN 1 ELSE 0 END) as "skipped!: i64"
+            FROM file_views
+            GROUP BY review_id
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.review_id, (row.viewed, row.skipped)))
+            .collect())
+    }
+
+    /// Get the content hashes of the viewed files of a review that have one
+    pub async fn get_viewed_file_content_hashes(
+        pool: &SqlitePool,
+        review_id: &str,
+    ) -> Result<HashMap<String, String>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT file_path, content_hash as "content_hash!"
+            FROM file_views
+            WHERE review_id = ?1 AND content_hash IS NOT NULL
+            "#,
+            review_id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.file_path, row.content_hash))
+            .collect())
+    }
+
+    /// Check if a file is viewed for a review, a skipped file is not viewed
     pub async fn is_file_viewed(
         pool: &SqlitePool,
         review_id: &str,
         file_path: &str,
     ) -> Result<bool, sqlx::Error> {
-        let count: i64 = sqlx::query_scalar!(
+        Ok(Self::status(pool, review_id, file_path).await? == FileViewStatus::Viewed)
+    }
+
+    /// Get the status of a file for a review
+    pub async fn status(
+        pool: &SqlitePool,
+        review_id: &str,
+        file_path: &str,
+    ) -> Result<FileViewStatus, sqlx::Error> {
+        let skipped = sqlx::query_scalar!(
             r#"
-            SELECT COUNT(*)
+            SELECT skipped as "skipped!: bool"
             FROM file_views
             WHERE review_id = ?1 AND file_path = ?2
             "#,
             review_id,
             file_path
         )
-        .fetch_one(pool)
+        .fetch_optional(pool)
         .await?;
-        Ok(count > 0)
+        Ok(match skipped {
+            None => FileViewStatus::NotViewed,
+            Some(false) => FileViewStatus::Viewed,
+            Some(true) => FileViewStatus::Skipped,
+        })
     }
 
     /// Get all file views for a review
@@ -118,7 +258,7 @@ impl FileView {
     ) -> Result<Vec<FileView>, sqlx::Error> {
         let rows = sqlx::query!(
             r#"
-            SELECT id as "id!", review_id as "review_id!", file_path as "file_path!", created_at as "created_at!"
+            SELECT id as "id!", review_id as "review_id!", file_path as "file_path!", content_hash, skipped as "skipped!: bool", created_at as "created_at!"
             FROM file_views
             WHERE review_id = ?1
             ORDER BY created_at ASC
@@ -137,6 +277,8 @@ impl FileView {
                 id: row.id,
                 review_id: row.review_id,
                 file_path: row.file_path,
+                content_hash: row.content_hash,
+                skipped: row.skipped,
                 created_at,
             });
         }
@@ -212,7 +354,7 @@ mod tests {
         let file_path = "src/main.rs";
 
         // Mark file as viewed
-        FileView::mark_as_viewed(&pool, &review.id, file_path)
+        FileView::mark_as_viewed(&pool, &review.id, file_path, None)
             .await
             .unwrap();
 
@@ -223,6 +365,30 @@ mod tests {
         assert!(is_viewed);
     }
 
+    #[tokio::test]
+    async fn test_mark_as_viewed_updates_content_hash() {
+        let pool = create_test_pool().await;
+        let review = create_test_review(&pool).await;
+
+        FileView::mark_as_viewed(&pool, &review.id, "src/main.rs", Some("old"))
+            .await
+            .unwrap();
+        FileView::mark_as_viewed(&pool, &review.id, "src/main.rs", Some("new"))
+            .await
+            .unwrap();
+        FileView::mark_as_viewed(&pool, &review.id, "src/lib.rs", None)
+            .await
+            .unwrap();
+
+        let content_hashes = FileView::get_viewed_file_content_hashes(&pool, &review.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            content_hashes,
+            HashMap::from([("src/main.rs".to_string(), "new".to_string())])
+        );
+    }
+
     #[tokio::test]
     async fn test_mark_as_viewed_duplicate() {
         let pool = create_test_pool().await;
@@ -230,10 +396,10 @@ mod tests {
         let file_path = "src/main.rs";
 
         // Mark file as viewed twice - should not fail
-        FileView::mark_as_viewed(&pool, &review.id, file_path)
+        FileView::mark_as_viewed(&pool, &review.id, file_path, None)
             .await
             .unwrap();
-        FileView::mark_as_viewed(&pool, &review.id, file_path)
+        FileView::mark_as_viewed(&pool, &review.id, file_path, None)
             .await
             .unwrap();
 
@@ -255,7 +421,7 @@ mod tests {
         let file_path = "src/main.rs";
 
         // Mark file as viewed first
-        FileView::mark_as_viewed(&pool, &review.id, file_path)
+        FileView::mark_as_viewed(&pool, &review.id, file_path, None)
             .await
             .unwrap();
         assert!(
@@ -285,7 +451,7 @@ mod tests {
 
         // Mark files as viewed
         for file_path in &file_paths {
-            FileView::mark_as_viewed(&pool, &review.id, file_path)
+            FileView::mark_as_viewed(&pool, &review.id, file_path, None)
                 .await
                 .unwrap();
         }
@@ -329,7 +495,7 @@ mod tests {
 
         // Mark files as viewed
         for file_path in &file_paths {
-            FileView::mark_as_viewed(&pool, &review.id, file_path)
+            FileView::mark_as_viewed(&pool, &review.id, file_path, None)
                 .await
                 .unwrap();
         }
@@ -354,7 +520,7 @@ mod tests {
 
         // Mark files as viewed
         for file_path in &file_paths {
-            FileView::mark_as_viewed(&pool, &review.id, file_path)
+            FileView::mark_as_viewed(&pool, &review.id, file_path, None)
                 .await
                 .unwrap();
         }
@@ -373,6 +539,104 @@ mod tests {
         assert_eq!(viewed_files.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_counts_by_review() {
+        let pool = create_test_pool().await;
+        let review1 = create_test_review(&pool).await;
+        let review2 = create_test_review(&pool).await;
+        for file_path in ["src/main.rs", "src/lib.rs"] {
+            FileView::mark_as_viewed(&pool, &review1.id, file_path, None)
+                .await
+                .unwrap();
+        }
+
+        FileView::mark_as_skipped(&pool, &review1.id, "src/generated.rs")
+            .await
+            .unwrap();
+
+        let counts = FileView::counts_by_review(&pool).await.unwrap();
+        assert_eq!(counts.get(&review1.id), Some(&(2, 1)));
+        assert_eq!(counts.get(&review2.id), None);
+    }
+
+    #[test]
+    fn test_file_view_status_toggles() {
+        let status = FileViewStatus::default();
+        assert_eq!(status, FileViewStatus::NotViewed);
+        assert_eq!(status.toggle_viewed(), FileViewStatus::Viewed);
+        assert_eq!(status.toggle_skipped(), FileViewStatus::Skipped);
+        assert_eq!(
+            FileViewStatus::Viewed.toggle_skipped(),
+            FileViewStatus::Skipped
+        );
+        assert_eq!(
+            FileViewStatus::Skipped.toggle_viewed(),
+            FileViewStatus::NotViewed
+        );
+        assert_eq!(
+            FileViewStatus::Skipped.toggle_skipped(),
+            FileViewStatus::NotViewed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mark_as_skipped() {
+        let pool = create_test_pool().await;
+        let review = create_test_review(&pool).await;
+        let file_path = "src/generated.rs";
+
+        FileView::mark_as_viewed(&pool, &review.id, file_path, Some("hash"))
+            .await
+            .unwrap();
+        FileView::mark_as_skipped(&pool, &review.id, file_path)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            FileView::status(&pool, &review.id, file_path)
+                .await
+                .unwrap(),
+            FileViewStatus::Skipped
+        );
+        assert!(
+            !FileView::is_file_viewed(&pool, &review.id, file_path)
+                .await
+                .unwrap()
+        );
+        assert!(
+            FileView::get_viewed_files(&pool, &review.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            FileView::get_skipped_files(&pool, &review.id)
+                .await
+                .unwrap(),
+            vec![file_path.to_string()]
+        );
+        // The content hash of the viewed file is dropped, a skipped file is never reset
+        assert!(
+            FileView::get_viewed_file_content_hashes(&pool, &review.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let file_views = FileView::list_for_review(&pool, &review.id).await.unwrap();
+        assert!(file_views[0].skipped);
+
+        // Viewing a skipped file again makes it viewed
+        FileView::mark_as_viewed(&pool, &review.id, file_path, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            FileView::status(&pool, &review.id, file_path)
+                .await
+                .unwrap(),
+            FileViewStatus::Viewed
+        );
+    }
+
     #[tokio::test]
     async fn test_multiple_reviews_isolated() {
         let pool = create_test_pool().await;
@@ -381,7 +645,7 @@ mod tests {
         let file_path = "src/main.rs";
 
         // Mark file as viewed for review1 only
-        FileView::mark_as_viewed(&pool, &review1.id, file_path)
+        FileView::mark_as_viewed(&pool, &review1.id, file_path, None)
             .await
             .unwrap();
 
@@ -1,9 +1,25 @@
+pub mod activity;
+pub mod checklist_item;
 pub mod comment;
 pub mod diff;
 pub mod file_view;
 pub mod review;
+pub mod review_export;
+pub mod session_state;
+pub mod settings;
 
-pub use comment::{Comment, CommentId};
-pub use diff::{Diff, DiffFile};
-pub use file_view::FileView;
-pub use review::{Review, ReviewId};
+pub use activity::{Activity, ActivityType};
+pub use checklist_item::{CHECKLIST_TEMPLATE_FILE_NAME, ChecklistItem};
+pub use comment::{Comment, CommentId, CommentPriority, CommentStatus};
+pub use diff::{Diff, DiffFile, DiffHunk, DiffLine, Hunk};
+pub use file_view::{FileView, FileViewStatus};
+pub use review::{Review, ReviewId, ReviewList, ReviewListEntry, ReviewProgress, short_sha};
+pub use review_export::{
+    CommentExportEntry, REVIEW_EXPORT_VERSION, ReviewExport, ReviewExportEntry,
+};
+pub use session_state::SessionState;
+pub use settings::DEFAULT_MAX_COMMENT_LENGTH;
+pub use settings::Density;
+pub use settings::ProgressMetric;
+pub use settings::ScrollMode;
+pub use settings::Settings;
@@ -1,14 +1,23 @@
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use sqlx::{FromRow, SqlitePool};
 use uuid::Uuid;
 
-use crate::time_provider::{SystemTimeProvider, TimeProvider};
+use crate::{
+    models::ProgressMetric,
+    time_provider::{SystemTimeProvider, TimeProvider},
+};
 
 const SHORT_SHA_LENGTH: usize = 7;
 
+/// Abbreviation of a SHA for display, SHAs shorter than that are kept as they are
+pub fn short_sha(sha: &str) -> &str {
+    sha.get(..SHORT_SHA_LENGTH).unwrap_or(sha)
+}
+
 pub type ReviewId = String;
 
-#[derive(Debug, Clone, FromRow)]
+#[derive(Debug, Clone, FromRow, Serialize)]
 pub struct Review {
     pub id: ReviewId,
     pub created_at: DateTime<Utc>,
@@ -21,6 +30,14 @@ pub struct Review {
     pub target_sha_changed: Option<String>,
     pub base_branch_exists: Option<bool>,
     pub target_branch_exists: Option<bool>,
+    /// Comma-separated names of who should look at the review, empty when nobody is assigned
+    pub assignees: String,
+    /// Archived reviews are only listed when archived reviews are included
+    pub archived: bool,
+    /// Patch file the review shows instead of the diff between two revisions
+    pub patch_path: Option<String>,
+    /// Pinned reviews are listed first and are not archived
+    pub pinned: bool,
 }
 
 impl PartialEq for Review {
@@ -29,43 +46,205 @@ impl PartialEq for Review {
     }
 }
 
+/// Progress of a review by both progress metrics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ReviewProgress {
+    pub viewed_file_count: usize,
+    /// Files that are consciously not reviewed, they don't count as files to view
+    pub skipped_file_count: usize,
+    /// Files of the diff, unknown when the diff can't be read
+    pub file_count: Option<usize>,
+    pub resolved_comment_count: usize,
+    pub comment_count: usize,
+    pub checked_checklist_item_count: usize,
+    pub checklist_item_count: usize,
+}
+
+/// Reviews of the review list with their progress, printed by `list --json`
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewList {
+    pub review_count: usize,
+    pub reviews: Vec<ReviewListEntry>,
+}
+
+/// Fields of a review and of its progress side by side, with its title
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewListEntry {
+    #[serde(flatten)]
+    pub review: Review,
+    pub title: String,
+    #[serde(flatten)]
+    pub progress: ReviewProgress,
+}
+
+impl ReviewProgress {
+    /// Done and total count of the metric, `None` when there is nothing to count
+    pub fn counts(&self, metric: ProgressMetric) -> Option<(usize, usize)> {
+        match metric {
+            ProgressMetric::ViewedFiles => self
+                .file_count
+                .map(|file_count| file_count.saturating_sub(self.skipped_file_count))
+                .filter(|file_count| *file_count > 0)
+                .map(|file_count| (self.viewed_file_count.min(file_count), file_count)),
+            ProgressMetric::ResolvedComments => (self.comment_count > 0)
+                .then_some((self.resolved_comment_count, self.comment_count)),
+        }
+    }
+
+    /// Counts of the metric like `2/3 files viewed`
+    pub fn describe(&self, metric: ProgressMetric) -> Option<String> {
+        let (done, total) = self.counts(metric)?;
+        Some(match metric {
+            ProgressMetric::ViewedFiles => format!("{done}/{total} files viewed"),
+            ProgressMetric::ResolvedComments => format!("{done}/{total} comments resolved"),
+        })
+    }
+
+    pub fn open_comment_count(&self) -> usize {
+        self.comment_count - self.resolved_comment_count
+    }
+
+    /// Completion of the checklist like `checklist 3/5`, `None` without a checklist
+    pub fn describe_checklist(&self) -> Option<String> {
+        (self.checklist_item_count > 0).then(|| {
+            format!(
+                "checklist {}/{}",
+                self.checked_checklist_item_count, self.checklist_item_count
+            )
+        })
+    }
+}
+
 impl Review {
     pub fn builder() -> ReviewBuilder {
         ReviewBuilder::new()
     }
 
-    /// Returns a human-readable title for the review in the format "base_branch -> target_branch"
+    /// Returns a human-readable title for the review in the format "base_branch -> target_branch",
+    /// or "patch <path>" for the review of a patch file
     pub fn title(&self) -> String {
+        self.display_title(false)
+    }
+
+    /// Title of the review with short or full SHAs
+    pub fn display_title(&self, full_shas: bool) -> String {
+        if let Some(patch_path) = &self.patch_path {
+            return format!("patch {patch_path}");
+        }
         let base_title = Self::format_branch_title(
             &self.base_branch,
             self.base_sha.as_ref(),
             self.base_sha_changed.as_ref(),
+            full_shas,
         );
         let target_title = Self::format_branch_title(
             &self.target_branch,
             self.target_sha.as_ref(),
             self.target_sha_changed.as_ref(),
+            full_shas,
         );
         format!("{base_title} -> {target_title}")
     }
 
+    /// Base and target of the diff as "branch (SHA)", with the short or full SHAs the diff is
+    /// loaded from. Missing branch names and SHAs are shown as unknown. A patch file goes from
+    /// the original files to the patched ones.
+    pub fn diff_direction(&self, full_shas: bool) -> (String, String) {
+        if let Some(patch_path) = &self.patch_path {
+            return ("original".to_string(), format!("patch {patch_path}"));
+        }
+        let side = |branch_name: &str, sha: Option<&String>| {
+            let branch_name = if branch_name.is_empty() {
+                "unknown"
+            } else {
+                branch_name
+            };
+            format!("{branch_name} ({})", Self::format_sha(sha, full_shas))
+        };
+        (
+            side(&self.base_branch, self.base_sha.as_ref()),
+            side(&self.target_branch, self.target_sha.as_ref()),
+        )
+    }
+
+    /// Command to show the diff of the review with the git CLI, from the SHAs if known and
+    /// limited to `pathspec`. `-M` shows renamed files as one file like the review does.
+    pub fn git_diff_command(&self, pathspec: Option<&str>) -> String {
+        let base = self.base_sha.as_deref().unwrap_or(&self.base_branch);
+        let target = self.target_sha.as_deref().unwrap_or(&self.target_branch);
+        let mut command = format!("git diff -M {} {}", shell_quote(base), shell_quote(target));
+        if let Some(pathspec) = pathspec {
+            command.push_str(&format!(" -- {}", shell_quote(pathspec)));
+        }
+        command
+    }
+
+    /// Names of who should look at the review
+    pub fn assignees(&self) -> Vec<&str> {
+        self.assignees
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    /// Whether the given name is one of the assignees, ignoring case
+    pub fn is_assigned_to(&self, name: &str) -> bool {
+        self.assignees()
+            .iter()
+            .any(|assignee| assignee.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Branches of the review that are known to no longer exist. The review can still be
+    /// opened from its stored SHAs.
+    pub fn deleted_branches(&self) -> Vec<&str> {
+        [
+            (&self.base_branch, self.base_branch_exists),
+            (&self.target_branch, self.target_branch_exists),
+        ]
+        .into_iter()
+        .filter(|(_, exists)| *exists == Some(false))
+        .map(|(branch_name, _)| branch_name.as_str())
+        .collect()
+    }
+
+    /// Normalize comma-separated assignees: names are trimmed, empty names and duplicates are
+    /// dropped and the rest is joined with ", "
+    pub fn normalize_assignees(assignees: &str) -> String {
+        let mut names: Vec<&str> = Vec::new();
+        for name in assignees.split(',').map(str::trim) {
+            if !name.is_empty()
+                && !names
+                    .iter()
+                    .any(|existing| existing.eq_ignore_ascii_case(name))
+            {
+                names.push(name);
+            }
+        }
+        names.join(", ")
+    }
+
     fn format_branch_title(
         branch_name: &str,
         original_sha: Option<&String>,
         changed_sha: Option<&String>,
+        full_shas: bool,
     ) -> String {
-        let original_short = Self::format_short_sha(original_sha);
+        let original = Self::format_sha(original_sha, full_shas);
         if let Some(changed_sha) = changed_sha {
-            let changed_short = Self::format_short_sha(Some(changed_sha));
-            format!("{branch_name} ({original_short} -> {changed_short})")
+            let changed = Self::format_sha(Some(changed_sha), full_shas);
+            format!("{branch_name} ({original} -> {changed})")
         } else {
-            format!("{branch_name} ({original_short})")
+            format!("{branch_name} ({original})")
         }
     }
 
-    fn format_short_sha(sha: Option<&String>) -> String {
-        sha.map(|value| value.chars().take(SHORT_SHA_LENGTH).collect())
-            .unwrap_or_else(|| "unknown".to_string())
+    fn format_sha(sha: Option<&String>, full_shas: bool) -> String {
+        match sha {
+            Some(sha) if full_shas => sha.clone(),
+            Some(sha) => short_sha(sha).to_string(),
+            None => "unknown".to_string(),
+        }
     }
 
     pub async fn save(&self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
@@ -73,8 +252,8 @@ impl Review {
         let updated_at = self.updated_at.to_rfc3339();
         sqlx::query!(
             r#"
-            INSERT INTO reviews (id, created_at, updated_at, base_branch, target_branch, base_sha, target_sha, base_sha_changed, target_sha_changed, base_branch_exists, target_branch_exists)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            INSERT INTO reviews (id, created_at, updated_at, base_branch, target_branch, base_sha, target_sha, base_sha_changed, target_sha_changed, base_branch_exists, target_branch_exists, assignees, archived, patch_path, pinned)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
             "#,
             self.id,
             created_at,
@@ -86,7 +265,11 @@ impl Review {
             self.base_sha_changed,
             self.target_sha_changed,
             self.base_branch_exists,
-            self.target_branch_exists
+            self.target_branch_exists,
+            self.assignees,
+            self.archived,
+            self.patch_path,
+            self.pinned
         )
         .execute(pool)
         .await?;
@@ -96,9 +279,51 @@ impl Review {
     pub async fn list_all(pool: &SqlitePool) -> Result<Vec<Review>, sqlx::Error> {
         let rows = sqlx::query!(
             r#"
-            SELECT id as "id!", created_at as "created_at!", updated_at as "updated_at!", base_branch as "base_branch!", target_branch as "target_branch!", base_sha, target_sha, base_sha_changed, target_sha_changed, base_branch_exists, target_branch_exists
+            SELECT id as "id!", created_at as "created_at!", updated_at as "updated_at!", base_branch as "base_branch!", target_branch as "target_branch!", base_sha, target_sha, base_sha_changed, target_sha_changed, base_branch_exists, target_branch_exists, assignees, archived, patch_path, pinned
+            FROM reviews
+            ORDER BY pinned DESC, created_at DESC
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut reviews = Vec::new();
+        for row in rows {
+            let created_at = DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+            let updated_at = DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+            reviews.push(Review {
+                id: row.id,
+                created_at,
+                updated_at,
+                base_branch: row.base_branch,
+                target_branch: row.target_branch,
+                base_sha: row.base_sha,
+                target_sha: row.target_sha,
+                base_sha_changed: row.base_sha_changed,
+                target_sha_changed: row.target_sha_changed,
+                base_branch_exists: row.base_branch_exists,
+                target_branch_exists: row.target_branch_exists,
+                assignees: row.assignees,
+                archived: row.archived,
+                patch_path: row.patch_path,
+                pinned: row.pinned,
+            });
+        }
+        Ok(reviews)
+    }
+
+    /// List the reviews that are not archived, newest first
+    pub async fn list_unarchived(pool: &SqlitePool) -> Result<Vec<Review>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id as "id!", created_at as "created_at!", updated_at as "updated_at!", base_branch as "base_branch!", target_branch as "target_branch!", base_sha, target_sha, base_sha_changed, target_sha_changed, base_branch_exists, target_branch_exists, assignees, archived, patch_path, pinned
             FROM reviews
-            ORDER BY created_at DESC
+            WHERE NOT archived
+            ORDER BY pinned DESC, created_at DESC
             "#
         )
         .fetch_all(pool)
@@ -124,6 +349,60 @@ impl Review {
                 target_sha_changed: row.target_sha_changed,
                 base_branch_exists: row.base_branch_exists,
                 target_branch_exists: row.target_branch_exists,
+                assignees: row.assignees,
+                archived: row.archived,
+                patch_path: row.patch_path,
+                pinned: row.pinned,
+            });
+        }
+        Ok(reviews)
+    }
+
+    /// List the reviews created at or after `since`, newest first. Archived reviews are only
+    /// listed with `include_archived`.
+    pub async fn list_recent(
+        pool: &SqlitePool,
+        since: DateTime<Utc>,
+        include_archived: bool,
+    ) -> Result<Vec<Review>, sqlx::Error> {
+        let since = since.to_rfc3339();
+        let rows = sqlx::query!(
+            r#"
+            SELECT id as "id!", created_at as "created_at!", updated_at as "updated_at!", base_branch as "base_branch!", target_branch as "target_branch!", base_sha, target_sha, base_sha_changed, target_sha_changed, base_branch_exists, target_branch_exists, assignees, archived, patch_path, pinned
+            FROM reviews
+            WHERE julianday(created_at) >= julianday(?1) AND (?2 OR NOT archived)
+            ORDER BY pinned DESC, created_at DESC
+            "#,
+            since,
+            include_archived
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut reviews = Vec::new();
+        for row in rows {
+            let created_at = DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+            let updated_at = DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+            reviews.push(Review {
+                id: row.id,
+                created_at,
+                updated_at,
+                base_branch: row.base_branch,
+                target_branch: row.target_branch,
+                base_sha: row.base_sha,
+                target_sha: row.target_sha,
+                base_sha_changed: row.base_sha_changed,
+                target_sha_changed: row.target_sha_changed,
+                base_branch_exists: row.base_branch_exists,
+                target_branch_exists: row.target_branch_exists,
+                assignees: row.assignees,
+                archived: row.archived,
+                patch_path: row.patch_path,
+                pinned: row.pinned,
             });
         }
         Ok(reviews)
@@ -132,7 +411,7 @@ impl Review {
     pub async fn find_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Review>, sqlx::Error> {
         let row = sqlx::query!(
             r#"
-            SELECT id as "id!", created_at as "created_at!", updated_at as "updated_at!", base_branch as "base_branch!", target_branch as "target_branch!", base_sha, target_sha, base_sha_changed, target_sha_changed, base_branch_exists, target_branch_exists
+            SELECT id as "id!", created_at as "created_at!", updated_at as "updated_at!", base_branch as "base_branch!", target_branch as "target_branch!", base_sha, target_sha, base_sha_changed, target_sha_changed, base_branch_exists, target_branch_exists, assignees, archived, patch_path, pinned
             FROM reviews
             WHERE id = ?1
             "#,
@@ -161,6 +440,10 @@ impl Review {
                     target_sha_changed: row.target_sha_changed,
                     base_branch_exists: row.base_branch_exists,
                     target_branch_exists: row.target_branch_exists,
+                    assignees: row.assignees,
+                    archived: row.archived,
+                    patch_path: row.patch_path,
+                    pinned: row.pinned,
                 }))
             }
             None => Ok(None),
@@ -221,6 +504,121 @@ impl Review {
         Ok(())
     }
 
+    /// Change the branches of the review together with their SHAs. The changed SHAs are reset
+    /// because they refer to the previous branches.
+    pub async fn update_branches(
+        &self,
+        pool: &SqlitePool,
+        base_branch: &str,
+        target_branch: &str,
+        base_sha: &str,
+        target_sha: &str,
+    ) -> Result<(), sqlx::Error> {
+        let updated_at = self.updated_at.to_rfc3339();
+        sqlx::query!(
+            r#"
+            UPDATE reviews
+            SET base_branch = ?2, target_branch = ?3, base_sha = ?4, target_sha = ?5,
+                base_sha_changed = NULL, target_sha_changed = NULL,
+                base_branch_exists = TRUE, target_branch_exists = TRUE, updated_at = ?6
+            WHERE id = ?1
+            "#,
+            self.id,
+            base_branch,
+            target_branch,
+            base_sha,
+            target_sha,
+            updated_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Set who should look at the review from comma-separated names, see
+    /// [`Review::normalize_assignees`]
+    pub async fn set_assignees(
+        &mut self,
+        pool: &SqlitePool,
+        assignees: &str,
+    ) -> Result<(), sqlx::Error> {
+        let assignees = Self::normalize_assignees(assignees);
+        let updated_at = self.updated_at.to_rfc3339();
+        sqlx::query!(
+            r#"
+            UPDATE reviews
+            SET assignees = ?2, updated_at = ?3
+            WHERE id = ?1
+            "#,
+            self.id,
+            assignees,
+            updated_at
+        )
+        .execute(pool)
+        .await?;
+        self.assignees = assignees;
+        Ok(())
+    }
+
+    /// Pin the review to the top of the review list or unpin it
+    pub async fn set_pinned(&mut self, pool: &SqlitePool, pinned: bool) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE reviews
+            SET pinned = ?2
+            WHERE id = ?1
+            "#,
+            self.id,
+            pinned
+        )
+        .execute(pool)
+        .await?;
+        self.pinned = pinned;
+        Ok(())
+    }
+
+    /// Number of reviews that are not archived
+    pub async fn count_unarchived(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!: i64"
+            FROM reviews
+            WHERE NOT archived
+            "#
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(count)
+    }
+
+    /// Archive the done reviews that were last updated before `before`. A review is done when
+    /// at least one of its files is viewed and none of its comments is open. Pinned reviews are
+    /// kept. Returns the number of archived reviews.
+    pub async fn archive_done_before(
+        pool: &SqlitePool,
+        before: DateTime<Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let before = before.to_rfc3339();
+        let result = sqlx::query!(
+            r#"
+            UPDATE reviews
+            SET archived = TRUE
+            WHERE NOT archived
+                AND NOT pinned
+                AND julianday(updated_at) < julianday(?1)
+                AND EXISTS (SELECT 1 FROM file_views WHERE file_views.review_id = reviews.id)
+                AND NOT EXISTS (
+                    SELECT 1 FROM comments
+                    WHERE comments.review_id = reviews.id AND NOT comments.resolved
+                )
+            "#,
+            before
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn delete(&self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
         sqlx::query!(
             r#"
@@ -235,6 +633,19 @@ impl Review {
     }
 }
 
+/// Quote an argument for a POSIX shell unless it only has characters that are safe unquoted
+fn shell_quote(argument: &str) -> String {
+    let is_safe = !argument.is_empty()
+        && argument
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || "-_./:@^~".contains(character));
+    if is_safe {
+        argument.to_string()
+    } else {
+        format!("'{}'", argument.replace('\'', "'\\''"))
+    }
+}
+
 pub struct ReviewBuilder {
     base_branch: Option<String>,
     target_branch: Option<String>,
@@ -244,6 +655,8 @@ pub struct ReviewBuilder {
     target_sha_changed: Option<String>,
     base_branch_exists: Option<bool>,
     target_branch_exists: Option<bool>,
+    assignees: String,
+    patch_path: Option<String>,
 }
 
 impl ReviewBuilder {
@@ -257,6 +670,8 @@ impl ReviewBuilder {
             target_sha_changed: None,
             base_branch_exists: None,
             target_branch_exists: None,
+            assignees: String::new(),
+            patch_path: None,
         }
     }
 
@@ -310,6 +725,16 @@ impl ReviewBuilder {
         self
     }
 
+    pub fn assignees(mut self, assignees: &str) -> Self {
+        self.assignees = Review::normalize_assignees(assignees);
+        self
+    }
+
+    pub fn patch_path(mut self, patch_path: impl Into<String>) -> Self {
+        self.patch_path = Some(patch_path.into());
+        self
+    }
+
     pub fn build(self) -> Review {
         self.build_with_time_provider(&SystemTimeProvider)
     }
@@ -331,6 +756,10 @@ impl ReviewBuilder {
             target_sha_changed: self.target_sha_changed,
             base_branch_exists: self.base_branch_exists,
             target_branch_exists: self.target_branch_exists,
+            assignees: self.assignees,
+            archived: false,
+            patch_path: self.patch_path,
+            pinned: false,
         }
     }
 }
@@ -347,7 +776,11 @@ mod tests {
 
     use sqlx::SqlitePool;
 
-    use crate::{test_utils::fixed_time, time_provider::MockTimeProvider};
+    use crate::{
+        models::{Comment, FileView},
+        test_utils::fixed_time,
+        time_provider::MockTimeProvider,
+    };
 
     async fn create_test_pool() -> SqlitePool {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
@@ -425,6 +858,142 @@ mod tests {
             changed_review.title(),
             "main (abcd123 -> 1234567) -> feature/test (efgh567 -> ijkl901)"
         );
+        assert_eq!(
+            changed_review.display_title(true),
+            "main (abcd1234 -> 123456789) -> feature/test (efgh5678 -> ijkl9012)"
+        );
+    }
+
+    #[test]
+    fn test_short_sha() {
+        assert_eq!(
+            short_sha("0123456789abcdef0123456789abcdef01234567"),
+            "0123456"
+        );
+        assert_eq!(short_sha("abc"), "abc");
+    }
+
+    #[test]
+    fn test_diff_direction() {
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("")
+            .base_sha(Some("abcd1234".to_string()))
+            .base_sha_changed(Some("123456789".to_string()))
+            .build();
+
+        assert_eq!(
+            review.diff_direction(false),
+            (
+                "main (abcd123)".to_string(),
+                "unknown (unknown)".to_string()
+            )
+        );
+        assert_eq!(review.diff_direction(true).0, "main (abcd1234)");
+    }
+
+    #[tokio::test]
+    async fn test_patch_review() {
+        let pool = create_test_pool().await;
+        let review = Review::builder()
+            .base_branch("")
+            .target_branch("")
+            .patch_path("/tmp/change.diff")
+            .build();
+        review.save(&pool).await.unwrap();
+
+        assert_eq!(review.title(), "patch /tmp/change.diff");
+        assert_eq!(
+            review.diff_direction(false),
+            ("original".to_string(), "patch /tmp/change.diff".to_string())
+        );
+        let found_review = Review::find_by_id(&pool, &review.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found_review.patch_path.as_deref(), Some("/tmp/change.diff"));
+        assert_eq!(found_review.base_sha, None);
+        assert_eq!(found_review.target_sha, None);
+    }
+
+    #[test]
+    fn test_review_progress() {
+        let progress = ReviewProgress {
+            viewed_file_count: 2,
+            skipped_file_count: 0,
+            file_count: Some(3),
+            resolved_comment_count: 1,
+            comment_count: 4,
+            checked_checklist_item_count: 3,
+            checklist_item_count: 5,
+        };
+        assert_eq!(progress.counts(ProgressMetric::ViewedFiles), Some((2, 3)));
+        assert_eq!(
+            progress.describe(ProgressMetric::ViewedFiles).as_deref(),
+            Some("2/3 files viewed")
+        );
+        assert_eq!(
+            progress.counts(ProgressMetric::ResolvedComments),
+            Some((1, 4))
+        );
+        assert_eq!(
+            progress
+                .describe(ProgressMetric::ResolvedComments)
+                .as_deref(),
+            Some("1/4 comments resolved")
+        );
+        assert_eq!(progress.open_comment_count(), 3);
+        assert_eq!(
+            progress.describe_checklist().as_deref(),
+            Some("checklist 3/5")
+        );
+
+        // Skipped files are not counted as files to view
+        let progress = ReviewProgress {
+            skipped_file_count: 1,
+            ..progress
+        };
+        assert_eq!(progress.counts(ProgressMetric::ViewedFiles), Some((2, 2)));
+        let progress = ReviewProgress {
+            viewed_file_count: 0,
+            skipped_file_count: 3,
+            ..progress
+        };
+        assert_eq!(progress.counts(ProgressMetric::ViewedFiles), None);
+
+        // Nothing to count without a readable diff or comments
+        let progress = ReviewProgress {
+            viewed_file_count: 1,
+            ..Default::default()
+        };
+        assert_eq!(progress.counts(ProgressMetric::ViewedFiles), None);
+        assert_eq!(progress.describe(ProgressMetric::ResolvedComments), None);
+        assert_eq!(progress.describe_checklist(), None);
+    }
+
+    #[test]
+    fn test_git_diff_command() {
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature/login")
+            .build();
+        assert_eq!(
+            review.git_diff_command(None),
+            "git diff -M main feature/login"
+        );
+
+        let review = Review::builder()
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
+        assert_eq!(
+            review.git_diff_command(Some("src/")),
+            "git diff -M abc123 def456 -- src/"
+        );
+        assert_eq!(
+            review.git_diff_command(Some("docs/it's *.md")),
+            "git diff -M abc123 def456 -- 'docs/it'\\''s *.md'"
+        );
     }
 
     #[tokio::test]
@@ -513,6 +1082,156 @@ mod tests {
         assert!(reviews[0].created_at > reviews[1].created_at);
     }
 
+    #[tokio::test]
+    async fn test_review_list_recent() {
+        let pool = create_test_pool().await;
+        let now = fixed_time();
+        for (base_branch, age) in [
+            ("old", chrono::Duration::days(30)),
+            ("cutoff", chrono::Duration::days(14)),
+            ("new", chrono::Duration::milliseconds(1500)),
+        ] {
+            Review::builder()
+                .base_branch(base_branch)
+                .build_with_time_provider(&MockTimeProvider::new(now - age))
+                .save(&pool)
+                .await
+                .unwrap();
+        }
+
+        let reviews = Review::list_recent(&pool, now - chrono::Duration::days(14), false)
+            .await
+            .unwrap();
+
+        let base_branches: Vec<&str> = reviews
+            .iter()
+            .map(|review| review.base_branch.as_str())
+            .collect();
+        assert_eq!(base_branches, vec!["new", "cutoff"]);
+        assert!(
+            Review::list_recent(&pool, now + chrono::Duration::seconds(1), false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_done_before() {
+        let pool = create_test_pool().await;
+        let now = fixed_time();
+        let old_time_provider = MockTimeProvider::new(now - chrono::Duration::days(60));
+        let review = |base_branch: &str, time_provider: &MockTimeProvider| {
+            Review::builder()
+                .base_branch(base_branch)
+                .build_with_time_provider(time_provider)
+        };
+        // Done: viewed files and only resolved comments
+        let done = review("done", &old_time_provider);
+        // Not done: an open comment, no viewed files, or updated recently
+        let open_comment = review("open-comment", &old_time_provider);
+        let not_viewed = review("not-viewed", &old_time_provider);
+        let recent = review("recent", &MockTimeProvider::new(now));
+        for review in [&done, &open_comment, &not_viewed, &recent] {
+            review.save(&pool).await.unwrap();
+        }
+        for review in [&done, &open_comment, &recent] {
+            FileView::mark_as_viewed(&pool, &review.id, "src/main.rs", None)
+                .await
+                .unwrap();
+        }
+        let mut resolved = Comment::new(&done.id, "src/main.rs", None, "Resolved");
+        resolved.create(&pool).await.unwrap();
+        resolved.mark_resolved(&pool).await.unwrap();
+        Comment::new(&open_comment.id, "src/main.rs", None, "Open")
+            .create(&pool)
+            .await
+            .unwrap();
+
+        let archived = Review::archive_done_before(&pool, now - chrono::Duration::days(30))
+            .await
+            .unwrap();
+
+        assert_eq!(archived, 1);
+        let base_branches = |reviews: Vec<Review>| -> Vec<String> {
+            let mut base_branches: Vec<String> = reviews
+                .into_iter()
+                .map(|review| review.base_branch)
+                .collect();
+            base_branches.sort();
+            base_branches
+        };
+        assert_eq!(
+            base_branches(Review::list_unarchived(&pool).await.unwrap()),
+            vec!["not-viewed", "open-comment", "recent"]
+        );
+        assert_eq!(Review::count_unarchived(&pool).await.unwrap(), 3);
+        assert!(
+            Review::find_by_id(&pool, &done.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .archived
+        );
+        let since = now - chrono::Duration::days(90);
+        assert_eq!(
+            Review::list_recent(&pool, since, false)
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(
+            Review::list_recent(&pool, since, true).await.unwrap().len(),
+            4
+        );
+
+        // Archived reviews are not archived again
+        let archived = Review::archive_done_before(&pool, now).await.unwrap();
+        assert_eq!(archived, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pinned_reviews_are_listed_first() {
+        let pool = create_test_pool().await;
+        let now = Utc::now();
+        let mut reviews = vec![];
+        for (days_ago, base_branch) in [(3, "oldest"), (2, "middle"), (1, "newest")] {
+            let review = Review::builder()
+                .base_branch(base_branch)
+                .build_with_time_provider(&MockTimeProvider::new(
+                    now - chrono::Duration::days(days_ago),
+                ));
+            review.save(&pool).await.unwrap();
+            reviews.push(review);
+        }
+        let base_branches = |reviews: Vec<Review>| -> Vec<String> {
+            reviews
+                .into_iter()
+                .map(|review| review.base_branch)
+                .collect()
+        };
+
+        reviews[0].set_pinned(&pool, true).await.unwrap();
+        assert_eq!(
+            base_branches(Review::list_all(&pool).await.unwrap()),
+            vec!["oldest", "newest", "middle"]
+        );
+        assert!(
+            Review::find_by_id(&pool, &reviews[0].id)
+                .await
+                .unwrap()
+                .unwrap()
+                .pinned
+        );
+
+        reviews[0].set_pinned(&pool, false).await.unwrap();
+        assert_eq!(
+            base_branches(Review::list_unarchived(&pool).await.unwrap()),
+            vec!["newest", "middle", "oldest"]
+        );
+    }
+
     #[tokio::test]
     async fn test_review_save_duplicate_id_fails() {
         let pool = create_test_pool().await;
@@ -675,6 +1394,10 @@ mod tests {
             target_sha_changed: Some("uvw321".to_string()), // Different target_sha_changed
             base_branch_exists: Some(false),                // Different base_branch_exists
             target_branch_exists: Some(true),               // Different target_branch_exists
+            assignees: "alice".to_string(),                 // Different assignees
+            archived: true,                                 // Different archived
+            patch_path: Some("change.diff".to_string()),    // Different patch_path
+            pinned: true,                                   // Different pinned
         };
 
         // Should be equal because only ID matters for equality
@@ -748,6 +1471,95 @@ mod tests {
         assert_eq!(updated.updated_at, new_time);
     }
 
+    #[tokio::test]
+    async fn test_update_branches() {
+        let pool = create_test_pool().await;
+        let mut review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature/test")
+            .base_sha_str("abcd1234")
+            .target_sha_str("efgh5678")
+            .base_sha_changed(Some("abcd9876".to_string()))
+            .build();
+        review.target_branch_exists = Some(false);
+        review.save(&pool).await.unwrap();
+
+        review
+            .update_branches(&pool, "develop", "feature/other", "1111", "2222")
+            .await
+            .unwrap();
+
+        let updated = Review::find_by_id(&pool, &review.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.base_branch, "develop");
+        assert_eq!(updated.target_branch, "feature/other");
+        assert_eq!(updated.base_sha.as_deref(), Some("1111"));
+        assert_eq!(updated.target_sha.as_deref(), Some("2222"));
+        assert_eq!(updated.base_sha_changed, None);
+        assert_eq!(updated.target_branch_exists, Some(true));
+    }
+
+    #[test]
+    fn test_assignees() {
+        let review = Review::builder()
+            .assignees(" alice, Bob,, bob ,carol ")
+            .build();
+
+        assert_eq!(review.assignees, "alice, Bob, carol");
+        assert_eq!(review.assignees(), vec!["alice", "Bob", "carol"]);
+        assert!(review.is_assigned_to("bob"));
+        assert!(review.is_assigned_to(" Alice "));
+        assert!(!review.is_assigned_to("dave"));
+
+        let unassigned = Review::builder().build();
+        assert!(unassigned.assignees().is_empty());
+        assert!(!unassigned.is_assigned_to(""));
+    }
+
+    #[test]
+    fn test_deleted_branches() {
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature")
+            .build();
+        assert!(review.deleted_branches().is_empty());
+
+        let review = Review {
+            base_branch_exists: Some(true),
+            target_branch_exists: Some(false),
+            ..review
+        };
+        assert_eq!(review.deleted_branches(), vec!["feature"]);
+
+        let review = Review {
+            base_branch_exists: Some(false),
+            ..review
+        };
+        assert_eq!(review.deleted_branches(), vec!["main", "feature"]);
+    }
+
+    #[tokio::test]
+    async fn test_set_assignees() {
+        let pool = create_test_pool().await;
+        let mut review = Review::builder().assignees("alice").build();
+        review.save(&pool).await.unwrap();
+
+        review.set_assignees(&pool, "bob, carol,").await.unwrap();
+        assert_eq!(review.assignees, "bob, carol");
+
+        let updated = Review::find_by_id(&pool, &review.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.assignees(), vec!["bob", "carol"]);
+
+        review.set_assignees(&pool, "").await.unwrap();
+        let reviews = Review::list_all(&pool).await.unwrap();
+        assert!(reviews[0].assignees().is_empty());
+    }
+
     #[test]
     fn test_review_new_with_shas() {
         let base_sha = Some("abc123".to_string());
@@ -0,0 +1,238 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    comment::{Comment, CommentPriority, CommentStatus},
+    review::Review,
+};
+
+/// Version of the JSON export format, bumped on incompatible changes
+pub const REVIEW_EXPORT_VERSION: u32 = 1;
+
+/// Reviews and their comments as JSON for moving the review state between machines.
+/// IDs are not exported because they are generated again on import.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewExport {
+    pub version: u32,
+    pub reviews: Vec<ReviewExportEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewExportEntry {
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub base_branch: String,
+    pub target_branch: String,
+    pub base_sha: Option<String>,
+    pub target_sha: Option<String>,
+    /// Missing in exports from before reviews had assignees
+    #[serde(default)]
+    pub assignees: String,
+    /// Missing in exports from before reviews of patch files
+    #[serde(default)]
+    pub patch_path: Option<String>,
+    pub comments: Vec<CommentExportEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentExportEntry {
+    pub file_path: String,
+    pub line_number: Option<i64>,
+    pub line_fingerprint: Option<String>,
+    pub content: String,
+    pub resolved: bool,
+    /// Missing in exports from before resolution notes existed
+    #[serde(default)]
+    pub resolution_note: Option<String>,
+    /// Missing in exports from before comments had priorities
+    #[serde(default)]
+    pub priority: CommentPriority,
+    /// Missing in exports from before comments had statuses
+    #[serde(default)]
+    pub status: CommentStatus,
+    /// Index of the comment this one replies to in the comments of the review, as the IDs are
+    /// not exported. Missing in exports from before comments had replies.
+    #[serde(default)]
+    pub parent_index: Option<usize>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ReviewExportEntry {
+    pub fn new(review: &Review, comments: &[Comment]) -> Self {
+        Self {
+            created_at: review.created_at,
+            updated_at: review.updated_at,
+            base_branch: review.base_branch.clone(),
+            target_branch: review.target_branch.clone(),
+            base_sha: review.base_sha.clone(),
+            target_sha: review.target_sha.clone(),
+            assignees: review.assignees.clone(),
+            patch_path: review.patch_path.clone(),
+            comments: Self::comment_entries(comments),
+        }
+    }
+
+    /// Export entries of the comments, with the replies pointing to the index of their parent
+    fn comment_entries(comments: &[Comment]) -> Vec<CommentExportEntry> {
+        let indices: HashMap<&str, usize> = comments
+            .iter()
+            .enumerate()
+            .map(|(index, comment)| (comment.id.as_str(), index))
+            .collect();
+        comments
+            .iter()
+            .map(|comment| CommentExportEntry {
+                parent_index: comment
+                    .parent_id
+                    .as_deref()
+                    .and_then(|parent_id| indices.get(parent_id).copied()),
+                ..CommentExportEntry::new(comment)
+            })
+            .collect()
+    }
+
+    /// Hash of the branches, SHAs, patch file and creation time, to recognize reviews that were
+    /// already imported. Comments and the update time are not part of it because they change
+    /// over time.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.created_at.hash(&mut hasher);
+        self.base_branch.hash(&mut hasher);
+        self.target_branch.hash(&mut hasher);
+        self.base_sha.hash(&mut hasher);
+        self.target_sha.hash(&mut hasher);
+        self.patch_path.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Build the review to store, with a fresh ID
+    pub fn to_review(&self) -> Review {
+        let mut review = Review::builder()
+            .base_branch(&self.base_branch)
+            .target_branch(&self.target_branch)
+            .base_sha(self.base_sha.clone())
+            .target_sha(self.target_sha.clone())
+            .assignees(&self.assignees)
+            .build();
+        review.patch_path = self.patch_path.clone();
+        review.created_at = self.created_at;
+        review.updated_at = self.updated_at;
+        review
+    }
+}
+
+impl CommentExportEntry {
+    pub fn new(comment: &Comment) -> Self {
+        Self {
+            file_path: comment.file_path.clone(),
+            line_number: comment.line_number,
+            line_fingerprint: comment.line_fingerprint.clone(),
+            content: comment.content.clone(),
+            resolved: comment.resolved,
+            resolution_note: comment.resolution_note.clone(),
+            priority: comment.priority,
+            status: comment.status,
+            parent_index: None,
+            created_at: comment.created_at,
+        }
+    }
+
+    /// Hash of the location, content and creation time, to recognize comments that were
+    /// already imported. The resolved state is not part of it because it changes over time.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.created_at.hash(&mut hasher);
+        self.file_path.hash(&mut hasher);
+        self.line_number.hash(&mut hasher);
+        self.content.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Build the comment to store for the given review, with a fresh ID. The parent is set by
+    /// the import, which knows the IDs the comments got.
+    pub fn to_comment(&self, review_id: &str) -> Comment {
+        let mut comment = Comment::new(review_id, &self.file_path, self.line_number, &self.content)
+            .with_line_fingerprint(self.line_fingerprint.clone())
+            .with_priority(self.priority);
+        comment.resolved = self.resolved;
+        comment.resolution_note = self.resolution_note.clone();
+        comment.status = self.status;
+        comment.created_at = self.created_at;
+        comment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_export_entry_round_trip() {
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature")
+            .base_sha_str("abc")
+            .assignees("alice, bob")
+            .build();
+        let mut comment = Comment::new(&review.id, "src/main.rs", Some(3), "Comment")
+            .with_line_fingerprint(Some("fingerprint".to_string()))
+            .with_priority(CommentPriority::High);
+        comment.resolved = true;
+        comment.resolution_note = Some("Fixed".to_string());
+        comment.status = CommentStatus::Addressed;
+
+        let entry = ReviewExportEntry::new(&review, std::slice::from_ref(&comment));
+        let imported_review = entry.to_review();
+        let imported_comment = entry.comments[0].to_comment(&imported_review.id);
+
+        assert_ne!(imported_review.id, review.id);
+        assert_eq!(imported_review.created_at, review.created_at);
+        assert_eq!(imported_review.base_sha, review.base_sha);
+        assert_eq!(imported_review.assignees(), vec!["alice", "bob"]);
+        assert_eq!(imported_comment.review_id, imported_review.id);
+        assert_eq!(imported_comment.line_fingerprint, comment.line_fingerprint);
+        assert!(imported_comment.resolved);
+        assert_eq!(imported_comment.resolution_note.as_deref(), Some("Fixed"));
+        assert_eq!(imported_comment.priority, CommentPriority::High);
+        assert_eq!(imported_comment.status, CommentStatus::Addressed);
+        assert_eq!(
+            ReviewExportEntry::new(&imported_review, &[]).content_hash(),
+            entry.content_hash()
+        );
+        assert_eq!(
+            CommentExportEntry::new(&imported_comment).content_hash(),
+            entry.comments[0].content_hash()
+        );
+    }
+
+    #[test]
+    fn test_review_export_entry_replies_point_to_parent_index() {
+        let review = Review::builder().build();
+        let reply_before_parent = Comment::new(&review.id, "src/main.rs", None, "Reply")
+            .with_parent_id(Some("parent".to_string()));
+        let mut parent = Comment::new(&review.id, "src/main.rs", None, "Parent");
+        parent.id = "parent".to_string();
+
+        let entry = ReviewExportEntry::new(&review, &[reply_before_parent, parent]);
+
+        assert_eq!(entry.comments[0].parent_index, Some(1));
+        assert_eq!(entry.comments[1].parent_index, None);
+        assert!(entry.comments[0].to_comment(&review.id).parent_id.is_none());
+    }
+
+    #[test]
+    fn test_review_export_entry_content_hash_ignores_comments() {
+        let review = Review::builder().build();
+        let comment = Comment::new(&review.id, "src/main.rs", None, "Comment");
+
+        assert_eq!(
+            ReviewExportEntry::new(&review, &[]).content_hash(),
+            ReviewExportEntry::new(&review, &[comment]).content_hash()
+        );
+    }
+}
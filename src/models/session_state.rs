@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use super::{review::ReviewId, settings::Settings};
+
+const SESSION_STATE_KEY: &str = "session_state";
+
+/// Navigation state saved on exit to resume the review that was open on the next launch.
+/// Stored as JSON in the `settings` table because there is only ever one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    pub review_id: ReviewId,
+    /// Selected file of the review details
+    pub file_path: Option<String>,
+    /// Selected line index of the file, only set when the review details were in lines mode
+    pub line_number: Option<i64>,
+}
+
+impl SessionState {
+    /// Load the saved session state. Values that can't be deserialized are treated as missing.
+    pub async fn load(pool: &SqlitePool) -> color_eyre::Result<Option<Self>> {
+        let Some(value) = Settings::get(pool, SESSION_STATE_KEY).await? else {
+            return Ok(None);
+        };
+
+        match Self::from_json(&value) {
+            Ok(session_state) => Ok(session_state),
+            Err(error) => {
+                log::warn!("Ignoring invalid session state {value:?}: {error}");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Save the session state, `None` clears it so that nothing is offered on the next launch
+    pub async fn save(pool: &SqlitePool, session_state: Option<&Self>) -> color_eyre::Result<()> {
+        Settings::set(
+            pool,
+            SESSION_STATE_KEY,
+            &serde_json::to_string(&session_state)?,
+        )
+        .await?;
+        Ok(())
+    }
+
+    fn from_json(value: &str) -> serde_json::Result<Option<Self>> {
+        serde_json::from_str(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        pool
+    }
+
+    fn session_state() -> SessionState {
+        SessionState {
+            review_id: "review-1".to_string(),
+            file_path: Some("src/main.rs".to_string()),
+            line_number: Some(3),
+        }
+    }
+
+    #[test]
+    fn test_serialization_round_trip() {
+        let json = serde_json::to_string(&Some(session_state())).unwrap();
+        assert_eq!(
+            json,
+            r#"{"review_id":"review-1","file_path":"src/main.rs","line_number":3}"#
+        );
+        assert_eq!(
+            SessionState::from_json(&json).unwrap(),
+            Some(session_state())
+        );
+        assert_eq!(SessionState::from_json("null").unwrap(), None);
+        assert!(SessionState::from_json("{").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load() {
+        let pool = create_test_pool().await;
+        assert_eq!(SessionState::load(&pool).await.unwrap(), None);
+
+        SessionState::save(&pool, Some(&session_state()))
+            .await
+            .unwrap();
+        assert_eq!(
+            SessionState::load(&pool).await.unwrap(),
+            Some(session_state())
+        );
+
+        SessionState::save(&pool, None).await.unwrap();
+        assert_eq!(SessionState::load(&pool).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_load_ignores_invalid_value() {
+        let pool = create_test_pool().await;
+        Settings::set(&pool, SESSION_STATE_KEY, "not json")
+            .await
+            .unwrap();
+
+        assert_eq!(SessionState::load(&pool).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,637 @@
+use std::{fmt, str::FromStr};
+
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+const ARCHIVE_AFTER_DAYS_KEY: &str = "archive_after_days";
+const COLLAPSE_VIEWED_LIST_KEY: &str = "collapse_viewed_list";
+const CONFIRM_QUIT_KEY: &str = "confirm_quit";
+const DEFAULT_BASE_BRANCH_KEY: &str = "default_base_branch";
+const DENSITY_KEY: &str = "density";
+const DOCK_COMMENTS_KEY: &str = "dock_comments";
+const FULL_SHAS_KEY: &str = "full_shas";
+const HIDE_DIFF_PREFIXES_KEY: &str = "hide_diff_prefixes";
+const HIDE_RESOLVED_COMMENTS_KEY: &str = "hide_resolved_comments";
+const INCLUDE_ARCHIVED_KEY: &str = "include_archived";
+const INLINE_CHANGES_KEY: &str = "inline_changes";
+const MAX_COMMENT_LENGTH_KEY: &str = "max_comment_length";
+const OPEN_REVIEW_AFTER_CREATE_KEY: &str = "open_review_after_create";
+const PROGRESS_METRIC_KEY: &str = "progress_metric";
+const RECENT_REVIEW_DAYS_KEY: &str = "recent_review_days";
+const RECENT_REVIEWS_ONLY_KEY: &str = "recent_reviews_only";
+const RELATIVE_LINE_NUMBERS_KEY: &str = "relative_line_numbers";
+const REVIEW_LIMIT_KEY: &str = "review_limit";
+const REVIEW_LIMIT_NOTIFIED_KEY: &str = "review_limit_notified";
+const SCROLL_MODE_KEY: &str = "scroll_mode";
+const SHOW_HUNK_BLAME_KEY: &str = "show_hunk_blame";
+const SHOW_REVIEW_SUMMARY_KEY: &str = "show_review_summary";
+const SHOW_WHITESPACE_KEY: &str = "show_whitespace";
+const TAB_WIDTH_KEY: &str = "tab_width";
+
+/// Number of days after their last update that done reviews are archived when it's not
+/// configured
+pub const DEFAULT_ARCHIVE_AFTER_DAYS: usize = 30;
+
+/// Number of reviews that are listed before archiving is suggested when it's not configured
+pub const DEFAULT_REVIEW_LIMIT: usize = 200;
+
+/// Maximum number of characters of a comment when it's not configured
+pub const DEFAULT_MAX_COMMENT_LENGTH: usize = 1000;
+
+/// Number of days a review counts as recent when it's not configured
+pub const DEFAULT_RECENT_REVIEW_DAYS: usize = 14;
+
+/// Number of columns between the tab stops in the diff when it's not configured
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// How the diff scrolls when the selected line moves in lines mode
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ScrollMode {
+    /// Scroll only when the selected line would leave the viewport
+    #[default]
+    EdgeFollow,
+    /// Keep the selected line vertically centered, except at the top and bottom of the file
+    Centered,
+}
+
+impl ScrollMode {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::EdgeFollow => Self::Centered,
+            Self::Centered => Self::EdgeFollow,
+        }
+    }
+}
+
+impl fmt::Display for ScrollMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EdgeFollow => write!(f, "edge_follow"),
+            Self::Centered => write!(f, "centered"),
+        }
+    }
+}
+
+impl FromStr for ScrollMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "edge_follow" => Ok(Self::EdgeFollow),
+            "centered" => Ok(Self::Centered),
+            _ => Err(format!("Unknown scroll mode: {value}")),
+        }
+    }
+}
+
+/// What the progress of a review is measured by
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ProgressMetric {
+    /// Viewed files of all files of the diff
+    #[default]
+    ViewedFiles,
+    /// Resolved comments of all comments of the review
+    ResolvedComments,
+}
+
+impl ProgressMetric {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::ViewedFiles => Self::ResolvedComments,
+            Self::ResolvedComments => Self::ViewedFiles,
+        }
+    }
+}
+
+impl fmt::Display for ProgressMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ViewedFiles => write!(f, "viewed_files"),
+            Self::ResolvedComments => write!(f, "resolved_comments"),
+        }
+    }
+}
+
+impl FromStr for ProgressMetric {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "viewed_files" => Ok(Self::ViewedFiles),
+            "resolved_comments" => Ok(Self::ResolvedComments),
+            _ => Err(format!("Unknown progress metric: {value}")),
+        }
+    }
+}
+
+/// How much blank space separates the items of the comments list and the diff gutter from the
+/// lines
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Density {
+    /// A blank line after every comment and a space after the line numbers
+    #[default]
+    Normal,
+    /// No blank lines between the comments and no space after the line numbers
+    Compact,
+}
+
+impl fmt::Display for Density {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Compact => write!(f, "compact"),
+        }
+    }
+}
+
+impl FromStr for Density {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "normal" => Ok(Self::Normal),
+            "compact" => Ok(Self::Compact),
+            _ => Err(format!("Unknown density: {value}")),
+        }
+    }
+}
+
+/// Persistent app preferences stored as key-value pairs in the `settings` table.
+///
+/// Keys that are missing from the database (or hold values that can't be parsed)
+/// fall back to the defaults, so fresh installs behave like before settings existed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// Number of days after their last update that done reviews are archived
+    pub archive_after_days: usize,
+    /// Collapse the inactive viewed files list into a count badge
+    pub collapse_viewed_list: bool,
+    /// Ask before quitting with `q` in the review list
+    pub confirm_quit: bool,
+    /// Base branch preselected when creating a review instead of main/master
+    pub default_base_branch: Option<String>,
+    /// Spacing of the comments list and the diff gutter
+    pub density: Density,
+    /// Show the comments of the review details beside the diff instead of over it
+    pub dock_comments: bool,
+    /// Show SHAs in full instead of abbreviated, copied SHAs are always full
+    pub full_shas: bool,
+    /// Hide the `+`/`-`/space origin prefixes of the diff lines, keeping their colors
+    pub hide_diff_prefixes: bool,
+    /// Hide resolved comments in the comments view
+    pub hide_resolved_comments: bool,
+    /// List the archived reviews too
+    pub include_archived: bool,
+    /// Show each removed line directly above the added line that replaces it, with the changed
+    /// part highlighted
+    pub inline_changes: bool,
+    /// Maximum number of characters of a comment
+    pub max_comment_length: usize,
+    /// Open the review details of a review right after creating it
+    pub open_review_after_create: bool,
+    /// What the progress of the reviews in the review list and their summary is measured by
+    pub progress_metric: ProgressMetric,
+    /// Number of days after their creation that reviews are listed when only recent reviews
+    /// are shown
+    pub recent_review_days: usize,
+    /// Only list the reviews created in the last `recent_review_days` days
+    pub recent_reviews_only: bool,
+    /// Number the diff lines relative to the selected line in lines mode
+    pub relative_line_numbers: bool,
+    /// Number of reviews that are not archived above which archiving is suggested
+    pub review_limit: usize,
+    /// Archiving was suggested because of the review limit, it's only suggested once
+    pub review_limit_notified: bool,
+    /// How the diff scrolls when the selected line moves
+    pub scroll_mode: ScrollMode,
+    /// Show who last touched the lines a hunk replaces next to its header. Off by default
+    /// because blaming is slow on large files.
+    pub show_hunk_blame: bool,
+    /// Show a summary of the viewed files and comments when closing the details of a review
+    pub show_review_summary: bool,
+    /// Show tabs as `→ ` and trailing spaces as `·` in the diff lines
+    pub show_whitespace: bool,
+    /// Number of columns between the tab stops the tabs of the diff lines are expanded to
+    pub tab_width: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            archive_after_days: DEFAULT_ARCHIVE_AFTER_DAYS,
+            collapse_viewed_list: false,
+            confirm_quit: false,
+            default_base_branch: None,
+            density: Density::default(),
+            dock_comments: false,
+            full_shas: false,
+            hide_diff_prefixes: false,
+            hide_resolved_comments: false,
+            include_archived: false,
+            inline_changes: false,
+            max_comment_length: DEFAULT_MAX_COMMENT_LENGTH,
+            open_review_after_create: false,
+            progress_metric: ProgressMetric::default(),
+            recent_review_days: DEFAULT_RECENT_REVIEW_DAYS,
+            recent_reviews_only: false,
+            relative_line_numbers: false,
+            review_limit: DEFAULT_REVIEW_LIMIT,
+            review_limit_notified: false,
+            scroll_mode: ScrollMode::default(),
+            show_hunk_blame: false,
+            show_review_summary: true,
+            show_whitespace: false,
+            tab_width: DEFAULT_TAB_WIDTH,
+        }
+    }
+}
+
+impl Settings {
+    /// Load all settings, using the defaults for keys that are not stored yet
+    pub async fn load(pool: &SqlitePool) -> Result<Self, sqlx::Error> {
+        let mut settings = Self::default();
+
+        if let Some(archive_after_days) = Self::get_parsed(pool, ARCHIVE_AFTER_DAYS_KEY).await?
+            && archive_after_days > 0
+        {
+            settings.archive_after_days = archive_after_days;
+        }
+        if let Some(collapse_viewed_list) = Self::get_bool(pool, COLLAPSE_VIEWED_LIST_KEY).await? {
+            settings.collapse_viewed_list = collapse_viewed_list;
+        }
+        if let Some(confirm_quit) = Self::get_bool(pool, CONFIRM_QUIT_KEY).await? {
+            settings.confirm_quit = confirm_quit;
+        }
+        if let Some(default_base_branch) = Self::get(pool, DEFAULT_BASE_BRANCH_KEY).await? {
+            // Saved empty when no default base branch is configured
+            settings.default_base_branch =
+                Some(default_base_branch).filter(|name| !name.is_empty());
+        }
+        if let Some(density) = Self::get_parsed(pool, DENSITY_KEY).await? {
+            settings.density = density;
+        }
+        if let Some(dock_comments) = Self::get_bool(pool, DOCK_COMMENTS_KEY).await? {
+            settings.dock_comments = dock_comments;
+        }
+        if let Some(full_shas) = Self::get_bool(pool, FULL_SHAS_KEY).await? {
+            settings.full_shas = full_shas;
+        }
+        if let Some(hide_diff_prefixes) = Self::get_bool(pool, HIDE_DIFF_PREFIXES_KEY).await? {
+            settings.hide_diff_prefixes = hide_diff_prefixes;
+        }
+        if let Some(hide_resolved_comments) =
+            Self::get_bool(pool, HIDE_RESOLVED_COMMENTS_KEY).await?
+        {
+            settings.hide_resolved_comments = hide_resolved_comments;
+        }
+        if let Some(include_archived) = Self::get_bool(pool, INCLUDE_ARCHIVED_KEY).await? {
+            settings.include_archived = include_archived;
+        }
+        if let Some(inline_changes) = Self::get_bool(pool, INLINE_CHANGES_KEY).await? {
+            settings.inline_changes = inline_changes;
+        }
+        if let Some(max_comment_length) = Self::get_parsed(pool, MAX_COMMENT_LENGTH_KEY).await?
+            && max_comment_length > 0
+        {
+            settings.max_comment_length = max_comment_length;
+        }
+        if let Some(open_review_after_create) =
+            Self::get_bool(pool, OPEN_REVIEW_AFTER_CREATE_KEY).await?
+        {
+            settings.open_review_after_create = open_review_after_create;
+        }
+        if let Some(progress_metric) = Self::get_parsed(pool, PROGRESS_METRIC_KEY).await? {
+            settings.progress_metric = progress_metric;
+        }
+        if let Some(recent_review_days) = Self::get_parsed(pool, RECENT_REVIEW_DAYS_KEY).await?
+            && recent_review_days > 0
+        {
+            settings.recent_review_days = recent_review_days;
+        }
+        if let Some(recent_reviews_only) = Self::get_bool(pool, RECENT_REVIEWS_ONLY_KEY).await? {
+            settings.recent_reviews_only = recent_reviews_only;
+        }
+        if let Some(relative_line_numbers) = Self::get_bool(pool, RELATIVE_LINE_NUMBERS_KEY).await?
+        {
+            settings.relative_line_numbers = relative_line_numbers;
+        }
+        if let Some(review_limit) = Self::get_parsed(pool, REVIEW_LIMIT_KEY).await?
+            && review_limit > 0
+        {
+            settings.review_limit = review_limit;
+        }
+        if let Some(review_limit_notified) = Self::get_bool(pool, REVIEW_LIMIT_NOTIFIED_KEY).await?
+        {
+            settings.review_limit_notified = review_limit_notified;
+        }
+        if let Some(scroll_mode) = Self::get_parsed(pool, SCROLL_MODE_KEY).await? {
+            settings.scroll_mode = scroll_mode;
+        }
+        if let Some(show_hunk_blame) = Self::get_bool(pool, SHOW_HUNK_BLAME_KEY).await? {
+            settings.show_hunk_blame = show_hunk_blame;
+        }
+        if let Some(show_review_summary) = Self::get_bool(pool, SHOW_REVIEW_SUMMARY_KEY).await? {
+            settings.show_review_summary = show_review_summary;
+        }
+        if let Some(show_whitespace) = Self::get_bool(pool, SHOW_WHITESPACE_KEY).await? {
+            settings.show_whitespace = show_whitespace;
+        }
+        if let Some(tab_width) = Self::get_parsed(pool, TAB_WIDTH_KEY).await?
+            && tab_width > 0
+        {
+            settings.tab_width = tab_width;
+        }
+
+        Ok(settings)
+    }
+
+    /// Persist all settings
+    pub async fn save(&self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        Self::set(
+            pool,
+            ARCHIVE_AFTER_DAYS_KEY,
+            &self.archive_after_days.to_string(),
+        )
+        .await?;
+        Self::set_bool(pool, COLLAPSE_VIEWED_LIST_KEY, self.collapse_viewed_list).await?;
+        Self::set_bool(pool, CONFIRM_QUIT_KEY, self.confirm_quit).await?;
+        Self::set(
+            pool,
+            DEFAULT_BASE_BRANCH_KEY,
+            self.default_base_branch.as_deref().unwrap_or_default(),
+        )
+        .await?;
+        Self::set(pool, DENSITY_KEY, &self.density.to_string()).await?;
+        Self::set_bool(pool, DOCK_COMMENTS_KEY, self.dock_comments).await?;
+        Self::set_bool(pool, FULL_SHAS_KEY, self.full_shas).await?;
+        Self::set_bool(pool, HIDE_DIFF_PREFIXES_KEY, self.hide_diff_prefixes).await?;
+        Self::set_bool(
+            pool,
+            HIDE_RESOLVED_COMMENTS_KEY,
+            self.hide_resolved_comments,
+        )
+        .await?;
+        Self::set_bool(pool, INCLUDE_ARCHIVED_KEY, self.include_archived).await?;
+        Self::set_bool(pool, INLINE_CHANGES_KEY, self.inline_changes).await?;
+        Self::set(
+            pool,
+            MAX_COMMENT_LENGTH_KEY,
+            &self.max_comment_length.to_string(),
+        )
+        .await?;
+        Self::set_bool(
+            pool,
+            OPEN_REVIEW_AFTER_CREATE_KEY,
+            self.open_review_after_create,
+        )
+        .await?;
+        Self::set(pool, PROGRESS_METRIC_KEY, &self.progress_metric.to_string()).await?;
+        Self::set(
+            pool,
+            RECENT_REVIEW_DAYS_KEY,
+            &self.recent_review_days.to_string(),
+        )
+        .await?;
+        Self::set_bool(pool, RECENT_REVIEWS_ONLY_KEY, self.recent_reviews_only).await?;
+        Self::set_bool(pool, RELATIVE_LINE_NUMBERS_KEY, self.relative_line_numbers).await?;
+        Self::set(pool, REVIEW_LIMIT_KEY, &self.review_limit.to_string()).await?;
+        Self::set_bool(pool, REVIEW_LIMIT_NOTIFIED_KEY, self.review_limit_notified).await?;
+        Self::set(pool, SCROLL_MODE_KEY, &self.scroll_mode.to_string()).await?;
+        Self::set_bool(pool, SHOW_HUNK_BLAME_KEY, self.show_hunk_blame).await?;
+        Self::set_bool(pool, SHOW_REVIEW_SUMMARY_KEY, self.show_review_summary).await?;
+        Self::set_bool(pool, SHOW_WHITESPACE_KEY, self.show_whitespace).await?;
+        Self::set(pool, TAB_WIDTH_KEY, &self.tab_width.to_string()).await?;
+        Ok(())
+    }
+
+    /// Get the raw value of a setting
+    pub async fn get(pool: &SqlitePool, key: &str) -> Result<Option<String>, sqlx::Error> {
+        let value = sqlx::query_scalar!(
+            r#"
+            SELECT value
+            FROM settings
+            WHERE key = ?1
+            "#,
+            key
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(value)
+    }
+
+    /// Set the raw value of a setting, replacing any existing value
+    pub async fn set(pool: &SqlitePool, key: &str, value: &str) -> Result<(), sqlx::Error> {
+        let updated_at = Utc::now().to_rfc3339();
+        sqlx::query!(
+            r#"
+            INSERT INTO settings (key, value, updated_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#,
+            key,
+            value,
+            updated_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_bool(pool: &SqlitePool, key: &str) -> Result<Option<bool>, sqlx::Error> {
+        Self::get_parsed(pool, key).await
+    }
+
+    pub async fn set_bool(pool: &SqlitePool, key: &str, value: bool) -> Result<(), sqlx::Error> {
+        Self::set(pool, key, &value.to_string()).await
+    }
+
+    pub async fn get_i64(pool: &SqlitePool, key: &str) -> Result<Option<i64>, sqlx::Error> {
+        Self::get_parsed(pool, key).await
+    }
+
+    pub async fn set_i64(pool: &SqlitePool, key: &str, value: i64) -> Result<(), sqlx::Error> {
+        Self::set(pool, key, &value.to_string()).await
+    }
+
+    /// Get a setting parsed into `T`. Values that can't be parsed are treated as missing.
+    async fn get_parsed<T: FromStr>(
+        pool: &SqlitePool,
+        key: &str,
+    ) -> Result<Option<T>, sqlx::Error> {
+        let Some(value) = Self::get(pool, key).await? else {
+            return Ok(None);
+        };
+
+        match value.parse() {
+            Ok(parsed) => Ok(Some(parsed)),
+            Err(_) => {
+                log::warn!("Ignoring invalid value {value:?} for setting {key}");
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_load_defaults_on_empty_database() {
+        let pool = create_test_pool().await;
+
+        let settings = Settings::load(&pool).await.unwrap();
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_round_trip() {
+        let pool = create_test_pool().await;
+
+        let settings = Settings {
+            archive_after_days: 60,
+            collapse_viewed_list: true,
+            confirm_quit: true,
+            default_base_branch: Some("develop".to_string()),
+            density: Density::Compact,
+            dock_comments: true,
+            full_shas: true,
+            hide_diff_prefixes: true,
+            hide_resolved_comments: true,
+            include_archived: true,
+            inline_changes: true,
+            max_comment_length: 240,
+            open_review_after_create: true,
+            progress_metric: ProgressMetric::ResolvedComments,
+            recent_review_days: 30,
+            recent_reviews_only: true,
+            relative_line_numbers: true,
+            review_limit: 50,
+            review_limit_notified: true,
+            scroll_mode: ScrollMode::Centered,
+            show_hunk_blame: true,
+            show_review_summary: false,
+            show_whitespace: true,
+            tab_width: 8,
+        };
+        settings.save(&pool).await.unwrap();
+
+        let loaded = Settings::load(&pool).await.unwrap();
+        assert_eq!(loaded, settings);
+    }
+
+    #[tokio::test]
+    async fn test_load_ignores_invalid_scroll_mode() {
+        let pool = create_test_pool().await;
+
+        Settings::set(&pool, SCROLL_MODE_KEY, "sideways")
+            .await
+            .unwrap();
+
+        let settings = Settings::load(&pool).await.unwrap();
+        assert_eq!(settings.scroll_mode, ScrollMode::EdgeFollow);
+    }
+
+    #[tokio::test]
+    async fn test_load_ignores_zero_max_comment_length() {
+        let pool = create_test_pool().await;
+
+        Settings::set(&pool, MAX_COMMENT_LENGTH_KEY, "0")
+            .await
+            .unwrap();
+
+        let settings = Settings::load(&pool).await.unwrap();
+        assert_eq!(settings.max_comment_length, DEFAULT_MAX_COMMENT_LENGTH);
+    }
+
+    #[tokio::test]
+    async fn test_load_ignores_zero_tab_width() {
+        let pool = create_test_pool().await;
+
+        Settings::set(&pool, TAB_WIDTH_KEY, "0").await.unwrap();
+
+        let settings = Settings::load(&pool).await.unwrap();
+        assert_eq!(settings.tab_width, DEFAULT_TAB_WIDTH);
+    }
+
+    #[tokio::test]
+    async fn test_load_ignores_zero_recent_review_days() {
+        let pool = create_test_pool().await;
+
+        Settings::set(&pool, RECENT_REVIEW_DAYS_KEY, "0")
+            .await
+            .unwrap();
+
+        let settings = Settings::load(&pool).await.unwrap();
+        assert_eq!(settings.recent_review_days, DEFAULT_RECENT_REVIEW_DAYS);
+    }
+
+    #[tokio::test]
+    async fn test_set_overwrites_existing_value() {
+        let pool = create_test_pool().await;
+
+        Settings::set(&pool, "theme", "dark").await.unwrap();
+        Settings::set(&pool, "theme", "light").await.unwrap();
+
+        let value = Settings::get(&pool, "theme").await.unwrap();
+        assert_eq!(value, Some("light".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_get_missing_key() {
+        let pool = create_test_pool().await;
+
+        assert_eq!(Settings::get(&pool, "missing").await.unwrap(), None);
+        assert_eq!(Settings::get_bool(&pool, "missing").await.unwrap(), None);
+        assert_eq!(Settings::get_i64(&pool, "missing").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_bool_round_trip() {
+        let pool = create_test_pool().await;
+
+        Settings::set_bool(&pool, "flag", true).await.unwrap();
+        assert_eq!(Settings::get_bool(&pool, "flag").await.unwrap(), Some(true));
+
+        Settings::set_bool(&pool, "flag", false).await.unwrap();
+        assert_eq!(
+            Settings::get_bool(&pool, "flag").await.unwrap(),
+            Some(false)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_i64_round_trip() {
+        let pool = create_test_pool().await;
+
+        Settings::set_i64(&pool, "tab_width", 8).await.unwrap();
+        assert_eq!(
+            Settings::get_i64(&pool, "tab_width").await.unwrap(),
+            Some(8)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_value_falls_back_to_default() {
+        let pool = create_test_pool().await;
+
+        Settings::set(&pool, COLLAPSE_VIEWED_LIST_KEY, "not-a-bool")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            Settings::get_bool(&pool, COLLAPSE_VIEWED_LIST_KEY)
+                .await
+                .unwrap(),
+            None
+        );
+        let settings = Settings::load(&pool).await.unwrap();
+        assert_eq!(settings, Settings::default());
+    }
+}
@@ -0,0 +1,190 @@
+//! Watcher of the refs of the repository, to offer refreshing an open review when its branches
+//! move, e.g. after a commit or a fetch in another terminal.
+
+use std::{path::Path, sync::Arc, time::Duration};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+
+use crate::{
+    event::{AppEvent, Event},
+    services::GitService,
+};
+
+/// Time without further ref changes before they are reported, so that a fetch or a rebase
+/// moving many refs is reported once
+const REF_CHANGE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Watches the refs of a repository until it is dropped and sends `AppEvent::RefsChanged` when
+/// they changed
+pub struct RefWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl RefWatcher {
+    pub fn start(
+        repo_path: &str,
+        sender: mpsc::UnboundedSender<Arc<Event>>,
+    ) -> color_eyre::Result<Self> {
+        let repository = GitService::open_repository(repo_path)?;
+        // Worktrees share the refs of the main repository
+        let git_dir = repository.commondir().canonicalize()?;
+
+        let (changes_sender, changes) = mpsc::unbounded_channel();
+        let watched_git_dir = git_dir.clone();
+        let mut watcher = notify::recommended_watcher(
+            move |result: notify::Result<notify::Event>| match result {
+                Ok(event) => {
+                    if event
+                        .paths
+                        .iter()
+                        .any(|path| is_ref_path(&watched_git_dir, path))
+                    {
+                        let _ = changes_sender.send(());
+                    }
+                }
+                Err(error) => log::warn!("Failed to watch the refs: {error}"),
+            },
+        )?;
+        // Packed refs and HEAD are in the Git directory itself, loose refs below refs/
+        watcher.watch(&git_dir, RecursiveMode::NonRecursive)?;
+        watcher.watch(&git_dir.join("refs"), RecursiveMode::Recursive)?;
+        tokio::spawn(debounce(changes, sender));
+
+        log::info!("Watching the refs in {}", git_dir.display());
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+/// Whether a changed path of the Git directory is a ref. Lock files are skipped, Git renames
+/// them to the ref once it is written.
+fn is_ref_path(git_dir: &Path, path: &Path) -> bool {
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "lock")
+    {
+        return false;
+    }
+    path.starts_with(git_dir.join("refs"))
+        || path == git_dir.join("packed-refs")
+        || path == git_dir.join("HEAD")
+}
+
+/// Send `AppEvent::RefsChanged` for each burst of changes, once no change followed for
+/// [`REF_CHANGE_DEBOUNCE`]
+async fn debounce(
+    mut changes: mpsc::UnboundedReceiver<()>,
+    sender: mpsc::UnboundedSender<Arc<Event>>,
+) {
+    while changes.recv().await.is_some() {
+        while let Ok(Some(())) = tokio::time::timeout(REF_CHANGE_DEBOUNCE, changes.recv()).await {}
+        if sender
+            .send(Event::App(AppEvent::RefsChanged).into())
+            .is_err()
+        {
+            // The app quit
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    fn create_test_repo(path: &Path) {
+        let repository = git2::Repository::init(path).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repository.index().unwrap().write_tree().unwrap();
+        let tree = repository.find_tree(tree_id).unwrap();
+        let commit_id = repository
+            .commit(None, &signature, &signature, "Initial", &tree, &[])
+            .unwrap();
+        let commit = repository.find_commit(commit_id).unwrap();
+        repository.branch("feature", &commit, true).unwrap();
+    }
+
+    fn advance_branch(path: &Path, branch: &str) {
+        let repository = git2::Repository::open(path).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repository
+            .find_branch(branch, git2::BranchType::Local)
+            .unwrap()
+            .get()
+            .peel_to_commit()
+            .unwrap();
+        let tree = parent.tree().unwrap();
+        repository
+            .commit(
+                Some(&format!("refs/heads/{branch}")),
+                &signature,
+                &signature,
+                "Next",
+                &tree,
+                &[&parent],
+            )
+            .unwrap();
+    }
+
+    fn is_refs_changed(event: &Event) -> bool {
+        matches!(event, Event::App(AppEvent::RefsChanged))
+    }
+
+    #[test]
+    fn test_is_ref_path() {
+        let git_dir = Path::new("/repo/.git");
+        assert!(is_ref_path(
+            git_dir,
+            Path::new("/repo/.git/refs/heads/main")
+        ));
+        assert!(is_ref_path(
+            git_dir,
+            Path::new("/repo/.git/refs/remotes/origin/feature/x")
+        ));
+        assert!(is_ref_path(git_dir, Path::new("/repo/.git/packed-refs")));
+        assert!(is_ref_path(git_dir, Path::new("/repo/.git/HEAD")));
+        assert!(!is_ref_path(
+            git_dir,
+            Path::new("/repo/.git/refs/heads/main.lock")
+        ));
+        assert!(!is_ref_path(git_dir, Path::new("/repo/.git/index")));
+        assert!(!is_ref_path(git_dir, Path::new("/repo/.git/ORIG_HEAD")));
+    }
+
+    #[tokio::test]
+    async fn test_debounce_reports_a_burst_of_changes_once() {
+        let (changes_sender, changes) = mpsc::unbounded_channel();
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        tokio::spawn(debounce(changes, sender));
+
+        for _ in 0..3 {
+            changes_sender.send(()).unwrap();
+        }
+
+        let event = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(is_refs_changed(&event));
+        tokio::time::sleep(REF_CHANGE_DEBOUNCE * 2).await;
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_moved_branch_is_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_repo(temp_dir.path());
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let _watcher = RefWatcher::start(temp_dir.path().to_str().unwrap(), sender).unwrap();
+
+        advance_branch(temp_dir.path(), "feature");
+
+        let event = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .expect("The moved branch should be reported")
+            .unwrap();
+        assert!(is_refs_changed(&event));
+    }
+}
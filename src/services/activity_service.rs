@@ -0,0 +1,333 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use crate::{
+    database::Database,
+    event::{AppEvent, EventHandler, ReviewId},
+    models::{Activity, ActivityType, Comment, FileViewStatus},
+    services::{ServiceContext, ServiceHandler},
+};
+
+/// Service recording the timeline of actions taken on reviews.
+/// Actions are recorded when the events confirming them are processed, so failed actions
+/// don't show up in the timeline.
+pub struct ActivityService;
+
+impl ServiceHandler for ActivityService {
+    fn handle_app_event<'a>(
+        event: &'a AppEvent,
+        context: ServiceContext<'a>,
+    ) -> Pin<Box<dyn Future<Output = color_eyre::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            match event {
+                AppEvent::ActivitiesLoad { review_id } => {
+                    Self::handle_activities_load(context.database, context.events, review_id).await;
+                }
+                _ => {
+                    if let Some(activity) = Self::activity_for_event(context.database, event).await
+                    {
+                        Self::record(context.database, &activity).await;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+impl ActivityService {
+    /// Activity to record for an event, if the event confirms an action on a review
+    async fn activity_for_event(database: &Database, event: &AppEvent) -> Option<Activity> {
+        match event {
+            AppEvent::ReviewCreated(review) => Some(Activity::new(
+                &review.id,
+                ActivityType::ReviewCreated,
+                &format!("{}..{}", review.base_branch, review.target_branch),
+            )),
+            AppEvent::ReviewUpdated(review) => Some(Activity::new(
+                &review.id,
+                ActivityType::BranchesChanged,
+                &format!("{}..{}", review.base_branch, review.target_branch),
+            )),
+            AppEvent::FileViewToggled {
+                review_id,
+                file_path,
+                status,
+            } => Some(Activity::new(
+                review_id,
+                match status {
+                    FileViewStatus::NotViewed => ActivityType::FileUnviewed,
+                    FileViewStatus::Viewed => ActivityType::FileViewed,
+                    FileViewStatus::Skipped => ActivityType::FileSkipped,
+                },
+                file_path,
+            )),
+            AppEvent::CommentCreated(comment) => Some(Activity::new(
+                &comment.review_id,
+                ActivityType::CommentAdded,
+                &Self::location(&comment.file_path, comment.line_number),
+            )),
+            AppEvent::CommentMarkedResolved { comment_id } => {
+                Self::comment_activity(database, comment_id, ActivityType::CommentResolved).await
+            }
+            AppEvent::CommentToggledResolved {
+                comment_id,
+                resolved,
+            } => {
+                let activity_type = if *resolved {
+                    ActivityType::CommentResolved
+                } else {
+                    ActivityType::CommentUnresolved
+                };
+                Self::comment_activity(database, comment_id, activity_type).await
+            }
+            AppEvent::CommentsMarkedAllResolved {
+                review_id,
+                file_path,
+                line_number,
+            } => Some(Activity::new(
+                review_id,
+                ActivityType::CommentResolved,
+                &format!("all on {}", Self::location(file_path, *line_number)),
+            )),
+            AppEvent::CommentsToggledAllResolved {
+                review_id,
+                file_path,
+                line_number,
+                resolved_count,
+                unresolved_count,
+            } => {
+                let activity_type = if *resolved_count > 0 {
+                    ActivityType::CommentResolved
+                } else if *unresolved_count > 0 {
+                    ActivityType::CommentUnresolved
+                } else {
+                    // There were no comments to toggle
+                    return None;
+                };
+                Some(Activity::new(
+                    review_id,
+                    activity_type,
+                    &format!("all on {}", Self::location(file_path, *line_number)),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Activity for a comment that is only known by its ID
+    async fn comment_activity(
+        database: &Database,
+        comment_id: &str,
+        activity_type: ActivityType,
+    ) -> Option<Activity> {
+        match Comment::find_by_id(database.pool(), comment_id).await {
+            Ok(Some(comment)) => Some(Activity::new(
+                &comment.review_id,
+                activity_type,
+                &Self::location(&comment.file_path, comment.line_number),
+            )),
+            Ok(None) => {
+                log::warn!("No comment found with ID {comment_id} to record activity for");
+                None
+            }
+            Err(error) => {
+                log::error!("Failed to load comment {comment_id} to record activity: {error}");
+                None
+            }
+        }
+    }
+
+    /// File path with the line number, if any, e.g. `src/main.rs:3`
+    fn location(file_path: &str, line_number: Option<i64>) -> String {
+        match line_number {
+            Some(line_number) => format!("{file_path}:{line_number}"),
+            None => file_path.to_string(),
+        }
+    }
+
+    /// Store the activity. Failures are only logged because the action itself succeeded.
+    async fn record(database: &Database, activity: &Activity) {
+        if let Err(error) = activity.create(database.pool()).await {
+            log::error!(
+                "Failed to record {} activity for review {}: {error}",
+                activity.activity_type,
+                activity.review_id
+            );
+        }
+    }
+
+    async fn handle_activities_load(
+        database: &Database,
+        events: &mut EventHandler,
+        review_id: &Arc<ReviewId>,
+    ) {
+        match Activity::find_for_review(database.pool(), review_id).await {
+            Ok(activities) => events.send(AppEvent::ActivitiesLoaded {
+                review_id: Arc::clone(review_id),
+                activities: activities.into(),
+            }),
+            Err(error) => events.send(AppEvent::ActivitiesLoadError {
+                review_id: Arc::clone(review_id),
+                error: Arc::from(format!("Failed to load activities: {error}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::SqlitePool;
+
+    use crate::{
+        event::Event,
+        models::{CommentPriority, Review},
+        services::CommentService,
+    };
+
+    async fn create_test_database() -> Database {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        Database::from_pool(pool)
+    }
+
+    /// Process the event like the event processor does: all services, then the events they
+    /// sent, until there are no events left
+    async fn process_events(database: &Database, events: &mut EventHandler, event: AppEvent) {
+        let mut pending = vec![event];
+        while let Some(event) = pending.pop() {
+            for handler in [
+                CommentService::handle_app_event,
+                ActivityService::handle_app_event,
+            ] {
+                handler(
+                    &event,
+                    ServiceContext {
+                        database,
+                        repo_path: ".",
+                        events,
+                    },
+                )
+                .await
+                .unwrap();
+            }
+            while let Some(sent) = events.try_recv() {
+                if let Event::App(app_event) = &*sent {
+                    pending.push(app_event.clone());
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_creating_and_resolving_a_comment_records_two_activities() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let review = Review::builder().build();
+        review.save(database.pool()).await.unwrap();
+
+        process_events(
+            &database,
+            &mut events,
+            AppEvent::CommentCreate {
+                review_id: Arc::from(review.id.as_str()),
+                file_path: Arc::from("src/main.rs"),
+                line_number: Some(3),
+                content: Arc::from("Comment"),
+                priority: CommentPriority::default(),
+                parent_id: None,
+            },
+        )
+        .await;
+        let comment = Comment::find_for_review(database.pool(), &review.id)
+            .await
+            .unwrap()
+            .remove(0);
+        process_events(
+            &database,
+            &mut events,
+            AppEvent::CommentToggleResolved {
+                comment_id: Arc::from(comment.id.as_str()),
+            },
+        )
+        .await;
+
+        let activities = Activity::find_for_review(database.pool(), &review.id)
+            .await
+            .unwrap();
+        let mut activity_types: Vec<_> = activities
+            .iter()
+            .map(|activity| activity.activity_type)
+            .collect();
+        activity_types.sort_by_key(|activity_type| activity_type.to_string());
+        assert_eq!(
+            activity_types,
+            [ActivityType::CommentAdded, ActivityType::CommentResolved]
+        );
+        assert!(
+            activities
+                .iter()
+                .all(|activity| activity.detail == "src/main.rs:3")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_toggling_all_without_comments_records_nothing() {
+        let database = create_test_database().await;
+        let review = Review::builder().build();
+        review.save(database.pool()).await.unwrap();
+
+        let activity = ActivityService::activity_for_event(
+            &database,
+            &AppEvent::CommentsToggledAllResolved {
+                review_id: Arc::from(review.id.as_str()),
+                file_path: Arc::from("src/main.rs"),
+                line_number: None,
+                resolved_count: 0,
+                unresolved_count: 0,
+            },
+        )
+        .await;
+        assert_eq!(activity, None);
+    }
+
+    #[tokio::test]
+    async fn test_handle_activities_load() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let review = Review::builder().build();
+        review.save(database.pool()).await.unwrap();
+        Activity::new(&review.id, ActivityType::FileViewed, "src/main.rs")
+            .create(database.pool())
+            .await
+            .unwrap();
+
+        let review_id: Arc<ReviewId> = Arc::from(review.id.as_str());
+        ActivityService::handle_app_event(
+            &AppEvent::ActivitiesLoad {
+                review_id: Arc::clone(&review_id),
+            },
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ActivitiesLoaded {
+                review_id: loaded_review_id,
+                activities,
+            }) => {
+                assert_eq!(loaded_review_id, &review_id);
+                assert_eq!(activities.len(), 1);
+                assert_eq!(activities[0].activity_type, ActivityType::FileViewed);
+            }
+            _ => panic!("Expected ActivitiesLoaded event, got: {event:?}"),
+        }
+    }
+}
@@ -15,15 +15,19 @@ impl BranchStatusService {
         // Get all reviews from the database
         match Review::list_all(context.database.pool()).await {
             Ok(reviews) => {
-                for mut review in reviews {
+                // Reviews of patch files have no branches
+                for mut review in reviews
+                    .into_iter()
+                    .filter(|review| review.patch_path.is_none())
+                {
                     // Check if branches still exist and if SHAs changed
                     let base_branch_exists =
-                        GitService::get_branch_sha(context.repo_path, &review.base_branch)
+                        GitService::get_branch_or_tag_sha(context.repo_path, &review.base_branch)
                             .map(|sha_opt| sha_opt.is_some())
                             .unwrap_or(false);
 
                     let target_branch_exists =
-                        GitService::get_branch_sha(context.repo_path, &review.target_branch)
+                        GitService::get_branch_or_tag_sha(context.repo_path, &review.target_branch)
                             .map(|sha_opt| sha_opt.is_some())
                             .unwrap_or(false);
 
@@ -32,8 +36,10 @@ impl BranchStatusService {
 
                     // Check if SHAs changed (only if branches exist and we have original SHAs)
                     if base_branch_exists
-                        && let Ok(Some(current_base_sha)) =
-                            GitService::get_branch_sha(context.repo_path, &review.base_branch)
+                        && let Ok(Some(current_base_sha)) = GitService::get_branch_or_tag_sha(
+                            context.repo_path,
+                            &review.base_branch,
+                        )
                         && let Some(original_base_sha) = &review.base_sha
                         && current_base_sha != *original_base_sha
                     {
@@ -41,8 +47,10 @@ impl BranchStatusService {
                     }
 
                     if target_branch_exists
-                        && let Ok(Some(current_target_sha)) =
-                            GitService::get_branch_sha(context.repo_path, &review.target_branch)
+                        && let Ok(Some(current_target_sha)) = GitService::get_branch_or_tag_sha(
+                            context.repo_path,
+                            &review.target_branch,
+                        )
                         && let Some(original_target_sha) = &review.target_sha
                         && current_target_sha != *original_target_sha
                     {
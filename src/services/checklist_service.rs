@@ -0,0 +1,257 @@
+use std::{future::Future, path::Path, pin::Pin, sync::Arc};
+
+use crate::{
+    database::Database,
+    event::{AppEvent, EventHandler, ReviewId},
+    models::{CHECKLIST_TEMPLATE_FILE_NAME, ChecklistItem},
+    services::{ServiceContext, ServiceHandler},
+};
+
+/// Service loading and changing the checklists of reviews. Every change is confirmed with
+/// `ChecklistChanged`, after which the checklist is loaded again.
+pub struct ChecklistService;
+
+impl ServiceHandler for ChecklistService {
+    fn handle_app_event<'a>(
+        event: &'a AppEvent,
+        context: ServiceContext<'a>,
+    ) -> Pin<Box<dyn Future<Output = color_eyre::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            let pool = context.database.pool();
+            let (review_id, result) = match event {
+                AppEvent::ChecklistLoad { review_id } => {
+                    Self::handle_checklist_load(context.database, context.events, review_id).await;
+                    return Ok(());
+                }
+                AppEvent::ChecklistItemCreate { review_id, text } => (
+                    review_id,
+                    ChecklistItem::new(review_id, text.trim())
+                        .create(pool)
+                        .await,
+                ),
+                AppEvent::ChecklistItemToggle { review_id, item_id } => (
+                    review_id,
+                    ChecklistItem::toggle_checked(pool, *item_id).await,
+                ),
+                AppEvent::ChecklistItemUpdate {
+                    review_id,
+                    item_id,
+                    text,
+                } => (
+                    review_id,
+                    ChecklistItem::update_text(pool, *item_id, text.trim()).await,
+                ),
+                AppEvent::ChecklistItemDelete { review_id, item_id } => {
+                    (review_id, ChecklistItem::delete(pool, *item_id).await)
+                }
+                AppEvent::ChecklistSeed { review_id } => (
+                    review_id,
+                    Self::seed_from_template(context.database, context.repo_path, review_id).await,
+                ),
+                _ => return Ok(()),
+            };
+
+            match result {
+                Ok(()) => context.events.send(AppEvent::ChecklistChanged {
+                    review_id: Arc::clone(review_id),
+                }),
+                Err(error) => context.events.send(AppEvent::ChecklistChangeError {
+                    review_id: Arc::clone(review_id),
+                    error: Arc::from(format!("Failed to change the checklist: {error}")),
+                }),
+            }
+            Ok(())
+        })
+    }
+}
+
+impl ChecklistService {
+    async fn handle_checklist_load(
+        database: &Database,
+        events: &mut EventHandler,
+        review_id: &Arc<ReviewId>,
+    ) {
+        match ChecklistItem::find_for_review(database.pool(), review_id).await {
+            Ok(items) => events.send(AppEvent::ChecklistLoaded {
+                review_id: Arc::clone(review_id),
+                items: items.into(),
+            }),
+            Err(error) => events.send(AppEvent::ChecklistLoadError {
+                review_id: Arc::clone(review_id),
+                error: Arc::from(format!("Failed to load the checklist: {error}")),
+            }),
+        }
+    }
+
+    /// Add the items of the template of the repository that the checklist doesn't have yet, so
+    /// that seeding twice doesn't duplicate them
+    async fn seed_from_template(
+        database: &Database,
+        repo_path: &str,
+        review_id: &str,
+    ) -> color_eyre::Result<()> {
+        let template = ChecklistItem::load_template(Path::new(repo_path));
+        if template.is_empty() {
+            color_eyre::eyre::bail!("no items in {CHECKLIST_TEMPLATE_FILE_NAME}");
+        }
+
+        let items = ChecklistItem::find_for_review(database.pool(), review_id).await?;
+        for text in template {
+            if !items.iter().any(|item| item.text == text) {
+                ChecklistItem::new(review_id, &text)
+                    .create(database.pool())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::SqlitePool;
+
+    use crate::{event::Event, models::Review};
+
+    async fn create_test_database() -> Database {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        Database::from_pool(pool)
+    }
+
+    async fn handle(
+        database: &Database,
+        repo_path: &str,
+        events: &mut EventHandler,
+        event: AppEvent,
+    ) {
+        ChecklistService::handle_app_event(
+            &event,
+            ServiceContext {
+                database,
+                repo_path,
+                events,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    fn assert_changed(events: &mut EventHandler) {
+        let event = events.try_recv().unwrap();
+        assert!(
+            matches!(*event, Event::App(AppEvent::ChecklistChanged { .. })),
+            "Expected ChecklistChanged event, got: {event:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_check_and_load_items() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let review = Review::builder().build();
+        review.save(database.pool()).await.unwrap();
+        let review_id: Arc<ReviewId> = Arc::from(review.id.as_str());
+
+        handle(
+            &database,
+            ".",
+            &mut events,
+            AppEvent::ChecklistItemCreate {
+                review_id: Arc::clone(&review_id),
+                text: Arc::from(" Security reviewed "),
+            },
+        )
+        .await;
+        assert_changed(&mut events);
+        let item_id = ChecklistItem::find_for_review(database.pool(), &review.id)
+            .await
+            .unwrap()[0]
+            .id;
+        handle(
+            &database,
+            ".",
+            &mut events,
+            AppEvent::ChecklistItemToggle {
+                review_id: Arc::clone(&review_id),
+                item_id,
+            },
+        )
+        .await;
+        assert_changed(&mut events);
+
+        handle(
+            &database,
+            ".",
+            &mut events,
+            AppEvent::ChecklistLoad {
+                review_id: Arc::clone(&review_id),
+            },
+        )
+        .await;
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ChecklistLoaded {
+                review_id: loaded_review_id,
+                items,
+            }) => {
+                assert_eq!(*loaded_review_id, review_id);
+                assert_eq!(items.len(), 1);
+                assert_eq!(items[0].text, "Security reviewed");
+                assert!(items[0].checked);
+            }
+            _ => panic!("Expected ChecklistLoaded event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_seed_from_template() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let review = Review::builder().build();
+        review.save(database.pool()).await.unwrap();
+        let review_id: Arc<ReviewId> = Arc::from(review.id.as_str());
+        let seed = || AppEvent::ChecklistSeed {
+            review_id: Arc::clone(&review_id),
+        };
+
+        // Without a template there is nothing to seed from
+        handle(&database, repo_path, &mut events, seed()).await;
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ChecklistChangeError { error, .. }) => {
+                assert_eq!(
+                    error.as_ref(),
+                    "Failed to change the checklist: no items in .glr-checklist"
+                );
+            }
+            _ => panic!("Expected ChecklistChangeError event, got: {event:?}"),
+        }
+
+        std::fs::write(
+            temp_dir.path().join(CHECKLIST_TEMPLATE_FILE_NAME),
+            "# Before merging\nSecurity reviewed\nTests added\n",
+        )
+        .unwrap();
+        ChecklistItem::new(&review.id, "Tests added")
+            .create(database.pool())
+            .await
+            .unwrap();
+        handle(&database, repo_path, &mut events, seed()).await;
+        assert_changed(&mut events);
+        handle(&database, repo_path, &mut events, seed()).await;
+        assert_changed(&mut events);
+
+        let texts: Vec<String> = ChecklistItem::find_for_review(database.pool(), &review.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|item| item.text)
+            .collect();
+        assert_eq!(texts, vec!["Tests added", "Security reviewed"]);
+    }
+}
@@ -3,8 +3,8 @@ use std::{future::Future, pin::Pin, sync::Arc};
 use crate::{
     database::Database,
     event::{AppEvent, EventHandler, ReviewId},
-    models::Comment,
-    services::{ServiceContext, ServiceHandler},
+    models::{Comment, CommentPriority, Review},
+    services::{GitService, ServiceContext, ServiceHandler},
 };
 
 /// Loading state for comments
@@ -57,22 +57,29 @@ impl ServiceHandler for CommentService {
                     file_path,
                     line_number,
                     content,
+                    priority,
+                    parent_id,
                 } => {
                     Self::handle_comment_create(
-                        context.database,
-                        context.events,
+                        context,
                         review_id,
                         file_path,
                         line_number,
                         content,
+                        *priority,
+                        parent_id.as_deref(),
                     )
                     .await?;
                 }
-                AppEvent::CommentMarkResolved { comment_id } => {
+                AppEvent::CommentMarkResolved {
+                    comment_id,
+                    resolution_note,
+                } => {
                     Self::handle_comment_mark_resolved(
                         context.database,
                         context.events,
                         comment_id,
+                        resolution_note.as_deref(),
                     )
                     .await?;
                 }
@@ -98,6 +105,10 @@ impl ServiceHandler for CommentService {
                     )
                     .await?;
                 }
+                AppEvent::CommentCycleStatus { comment_id } => {
+                    Self::handle_comment_cycle_status(context.database, context.events, comment_id)
+                        .await?;
+                }
                 AppEvent::CommentsToggleAllResolved {
                     review_id,
                     file_path,
@@ -183,13 +194,19 @@ impl CommentService {
 
     /// Create a new comment
     async fn handle_comment_create(
-        database: &Database,
-        events: &mut EventHandler,
+        context: ServiceContext<'_>,
         review_id: &ReviewId,
         file_path: &str,
         line_number: &Option<i64>,
         content: &str,
+        priority: CommentPriority,
+        parent_id: Option<&str>,
     ) -> color_eyre::Result<()> {
+        let ServiceContext {
+            database,
+            repo_path,
+            events,
+        } = context;
         let pool = database.pool();
 
         // Validate content
@@ -201,7 +218,17 @@ impl CommentService {
             return Ok(());
         }
 
-        let comment = Comment::new(review_id, file_path, *line_number, trimmed_content);
+        let line_fingerprint = match line_number {
+            Some(line_number) => {
+                Self::line_fingerprint(database, repo_path, review_id, file_path, *line_number)
+                    .await
+            }
+            None => None,
+        };
+        let comment = Comment::new(review_id, file_path, *line_number, trimmed_content)
+            .with_line_fingerprint(line_fingerprint)
+            .with_priority(priority)
+            .with_parent_id(parent_id.map(str::to_string));
 
         // Save comment to database
         match comment.create(pool).await {
@@ -226,6 +253,39 @@ impl CommentService {
         Ok(())
     }
 
+    /// Build the fingerprint of a line in the current diff of a review.
+    /// Returns `None` if the review, its diff or the file can't be found.
+    async fn line_fingerprint(
+        database: &Database,
+        repo_path: &str,
+        review_id: &ReviewId,
+        file_path: &str,
+        line_number: i64,
+    ) -> Option<String> {
+        let review = match Review::find_by_id(database.pool(), review_id).await {
+            Ok(review) => review?,
+            Err(error) => {
+                log::warn!("Failed to load review {review_id} for line fingerprint: {error}");
+                return None;
+            }
+        };
+        let (Some(base_sha), Some(target_sha)) = (&review.base_sha, &review.target_sha) else {
+            return None;
+        };
+        let diff = match GitService::get_diff_between_shas(repo_path, base_sha, target_sha) {
+            Ok(diff) => diff,
+            Err(error) => {
+                log::warn!(
+                    "Failed to load diff of review {review_id} for line fingerprint: {error}"
+                );
+                return None;
+            }
+        };
+
+        let file = diff.files.iter().find(|file| file.path == file_path)?;
+        Comment::fingerprint_for_line(file.content(), usize::try_from(line_number).ok()?)
+    }
+
     /// Check if a file has any comments (used for comment indicators)
     pub async fn file_has_comments(
         database: &Database,
@@ -250,6 +310,7 @@ impl CommentService {
         database: &Database,
         events: &mut EventHandler,
         comment_id: &str,
+        resolution_note: Option<&str>,
     ) -> color_eyre::Result<()> {
         let pool = database.pool();
 
@@ -259,7 +320,7 @@ impl CommentService {
             .ok_or_else(|| color_eyre::eyre::eyre!("Comment not found: {}", comment_id))?;
 
         // Mark as resolved
-        match comment.mark_resolved(pool).await {
+        match comment.mark_resolved_with_note(pool, resolution_note).await {
             Ok(()) => {
                 events.send(AppEvent::CommentMarkedResolved {
                     comment_id: comment_id.into(),
@@ -352,6 +413,37 @@ impl CommentService {
         Ok(())
     }
 
+    /// Cycle a single comment to its next status
+    async fn handle_comment_cycle_status(
+        database: &Database,
+        events: &mut EventHandler,
+        comment_id: &str,
+    ) -> color_eyre::Result<()> {
+        let pool = database.pool();
+
+        let mut comment = Comment::find_by_id(pool, comment_id)
+            .await?
+            .ok_or_else(|| color_eyre::eyre::eyre!("Comment not found: {}", comment_id))?;
+
+        let status = comment.status.cycle();
+        match comment.set_status(pool, status).await {
+            Ok(()) => {
+                events.send(AppEvent::CommentStatusChanged {
+                    comment_id: comment_id.into(),
+                    status,
+                });
+            }
+            Err(error) => {
+                events.send(AppEvent::CommentCycleStatusError {
+                    comment_id: comment_id.into(),
+                    error: Arc::from(format!("Failed to change comment status: {error}")),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Toggle resolved state for all comments in a specific target
     async fn handle_comments_toggle_all_resolved(
         database: &Database,
@@ -830,12 +922,17 @@ mod tests {
 
         // Create a file comment
         CommentService::handle_comment_create(
-            &database,
-            &mut events,
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
             &review.id,
             "src/main.rs",
             &None,
             "This is a file comment",
+            CommentPriority::default(),
+            None,
         )
         .await
         .unwrap();
@@ -885,12 +982,17 @@ mod tests {
 
         // Create a line comment
         CommentService::handle_comment_create(
-            &database,
-            &mut events,
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
             &review.id,
             "src/main.rs",
             &Some(42),
             "This is a line comment",
+            CommentPriority::High,
+            None,
         )
         .await
         .unwrap();
@@ -904,6 +1006,7 @@ mod tests {
                 assert_eq!(comment.line_number, Some(42));
                 assert_eq!(comment.content, "This is a line comment");
                 assert!(comment.is_line_comment());
+                assert_eq!(comment.priority, CommentPriority::High);
             }
             _ => panic!("Expected CommentCreated event"),
         }
@@ -941,12 +1044,17 @@ mod tests {
 
         // Try to create a comment with empty content
         CommentService::handle_comment_create(
-            &database,
-            &mut events,
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
             "review-123",
             "src/main.rs",
             &None,
             "   ", // Only whitespace
+            CommentPriority::default(),
+            None,
         )
         .await
         .unwrap();
@@ -0,0 +1,337 @@
+use std::{future::Future, pin::Pin, process::Command, sync::Arc};
+
+use crate::{
+    event::{AppEvent, EventHandler},
+    services::{GitService, ServiceContext, ServiceHandler},
+};
+
+/// Built-in git difftools that open a GUI window (see `git difftool --tool-help`).
+/// All other tools, e.g. `vimdiff` or tools with a custom `difftool.<tool>.cmd`, are run inside
+/// the terminal.
+const GUI_DIFFTOOLS: &[&str] = &[
+    "araxis",
+    "bc",
+    "bc3",
+    "bc4",
+    "codecompare",
+    "deltawalker",
+    "diffmerge",
+    "diffuse",
+    "ecmerge",
+    "examdiff",
+    "guiffy",
+    "gvimdiff",
+    "gvimdiff1",
+    "gvimdiff2",
+    "gvimdiff3",
+    "kdiff3",
+    "kompare",
+    "meld",
+    "opendiff",
+    "p4merge",
+    "smerge",
+    "tkdiff",
+    "vscode",
+    "winmerge",
+    "xxdiff",
+];
+
+/// The difftool configured in git
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difftool {
+    pub name: String,
+    /// Whether the tool opens its own window instead of taking over the terminal
+    pub gui: bool,
+}
+
+impl Difftool {
+    /// Resolve the difftool from `diff.tool`, falling back to `diff.guitool`
+    pub fn from_config(config: &git2::Config) -> Option<Self> {
+        if let Ok(name) = config.get_string("diff.tool") {
+            let gui = GUI_DIFFTOOLS.contains(&name.as_str())
+                && config.get_string(&format!("difftool.{name}.cmd")).is_err();
+            return Some(Self { name, gui });
+        }
+
+        config
+            .get_string("diff.guitool")
+            .ok()
+            .map(|name| Self { name, gui: true })
+    }
+}
+
+/// Invocation of the difftool for one file between two SHAs
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifftoolCommand {
+    pub repo_path: String,
+    pub tool: Difftool,
+    pub base_sha: Arc<str>,
+    pub target_sha: Arc<str>,
+    pub file_path: Arc<str>,
+}
+
+impl DifftoolCommand {
+    /// Build the `git difftool` command
+    pub fn command(&self) -> Command {
+        let mut command = Command::new("git");
+        command
+            .arg("-C")
+            .arg(&self.repo_path)
+            .arg("difftool")
+            .arg("--no-prompt")
+            .arg(format!("--tool={}", self.tool.name))
+            .arg(self.base_sha.as_ref())
+            .arg(self.target_sha.as_ref())
+            .arg("--")
+            .arg(self.file_path.as_ref());
+        command
+    }
+}
+
+/// Service for opening files of a review in the user's git difftool
+pub struct DifftoolService;
+
+impl ServiceHandler for DifftoolService {
+    fn handle_app_event<'a>(
+        event: &'a AppEvent,
+        context: ServiceContext<'a>,
+    ) -> Pin<Box<dyn Future<Output = color_eyre::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            match event {
+                AppEvent::DifftoolOpen {
+                    base_sha,
+                    target_sha,
+                    file_path,
+                } => {
+                    Self::handle_difftool_open(
+                        context.repo_path,
+                        base_sha,
+                        target_sha,
+                        file_path,
+                        context.events,
+                    );
+                }
+                _ => {
+                    // Event not handled by this service
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+impl DifftoolService {
+    /// Resolve the configured difftool and request running it for the file
+    fn handle_difftool_open(
+        repo_path: &str,
+        base_sha: &Arc<str>,
+        target_sha: &Arc<str>,
+        file_path: &Arc<str>,
+        events: &mut EventHandler,
+    ) {
+        let config = match GitService::open_repository(repo_path).and_then(|repo| repo.config()) {
+            Ok(config) => config,
+            Err(error) => {
+                events.send(AppEvent::DifftoolError(Arc::from(format!(
+                    "Failed to read git config: {error}"
+                ))));
+                return;
+            }
+        };
+
+        let Some(tool) = Difftool::from_config(&config) else {
+            events.send(AppEvent::DifftoolError(Arc::from(
+                "No difftool configured, set one with `git config diff.tool <tool>`",
+            )));
+            return;
+        };
+
+        events.send(AppEvent::DifftoolRun(Arc::new(DifftoolCommand {
+            repo_path: repo_path.to_string(),
+            tool,
+            base_sha: Arc::clone(base_sha),
+            target_sha: Arc::clone(target_sha),
+            file_path: Arc::clone(file_path),
+        })));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::SqlitePool;
+    use tempfile::TempDir;
+
+    use crate::{database::Database, event::Event};
+
+    async fn create_test_database() -> Database {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        Database::from_pool(pool)
+    }
+
+    fn create_test_config(temp_dir: &TempDir, entries: &[(&str, &str)]) -> git2::Config {
+        let mut config = git2::Config::open(&temp_dir.path().join("config")).unwrap();
+        for (key, value) in entries {
+            config.set_str(key, value).unwrap();
+        }
+        config
+    }
+
+    #[test]
+    fn test_difftool_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let config = create_test_config(&temp_dir, &[]);
+        assert_eq!(Difftool::from_config(&config), None);
+
+        let config = create_test_config(&temp_dir, &[("diff.tool", "vimdiff")]);
+        assert_eq!(
+            Difftool::from_config(&config),
+            Some(Difftool {
+                name: "vimdiff".to_string(),
+                gui: false,
+            })
+        );
+
+        let config = create_test_config(&temp_dir, &[("diff.tool", "meld")]);
+        assert!(Difftool::from_config(&config).unwrap().gui);
+    }
+
+    #[test]
+    fn test_difftool_from_config_custom_cmd_runs_in_terminal() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(
+            &temp_dir,
+            &[
+                ("diff.tool", "meld"),
+                ("difftool.meld.cmd", "nvim -d \"$LOCAL\" \"$REMOTE\""),
+            ],
+        );
+
+        assert!(!Difftool::from_config(&config).unwrap().gui);
+    }
+
+    #[test]
+    fn test_difftool_from_config_guitool_fallback() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir, &[("diff.guitool", "mytool")]);
+
+        assert_eq!(
+            Difftool::from_config(&config),
+            Some(Difftool {
+                name: "mytool".to_string(),
+                gui: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_difftool_command() {
+        let difftool_command = DifftoolCommand {
+            repo_path: "/repo".to_string(),
+            tool: Difftool {
+                name: "vimdiff".to_string(),
+                gui: false,
+            },
+            base_sha: Arc::from("abc"),
+            target_sha: Arc::from("def"),
+            file_path: Arc::from("src/main.rs"),
+        };
+
+        let command = difftool_command.command();
+        assert_eq!(command.get_program(), "git");
+        let args: Vec<_> = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            args,
+            [
+                "-C",
+                "/repo",
+                "difftool",
+                "--no-prompt",
+                "--tool=vimdiff",
+                "abc",
+                "def",
+                "--",
+                "src/main.rs",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_difftool_open_not_a_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+
+        DifftoolService::handle_app_event(
+            &AppEvent::DifftoolOpen {
+                base_sha: Arc::from("abc"),
+                target_sha: Arc::from("def"),
+                file_path: Arc::from("src/main.rs"),
+            },
+            ServiceContext {
+                database: &database,
+                repo_path: &repo_path,
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::DifftoolError(error)) => {
+                assert!(error.contains("Failed to read git config"));
+            }
+            _ => panic!("Expected DifftoolError event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_difftool_open_with_configured_tool() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        repo.config()
+            .unwrap()
+            .open_level(git2::ConfigLevel::Local)
+            .unwrap()
+            .set_str("diff.tool", "vimdiff")
+            .unwrap();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+
+        DifftoolService::handle_app_event(
+            &AppEvent::DifftoolOpen {
+                base_sha: Arc::from("abc"),
+                target_sha: Arc::from("def"),
+                file_path: Arc::from("src/main.rs"),
+            },
+            ServiceContext {
+                database: &database,
+                repo_path: &repo_path,
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::DifftoolRun(command)) => {
+                assert_eq!(command.tool.name, "vimdiff");
+                assert!(!command.tool.gui);
+                assert_eq!(command.base_sha.as_ref(), "abc");
+                assert_eq!(command.target_sha.as_ref(), "def");
+                assert_eq!(command.file_path.as_ref(), "src/main.rs");
+            }
+            _ => panic!("Expected DifftoolRun event, got: {event:?}"),
+        }
+    }
+}
@@ -3,7 +3,7 @@ use std::{future::Future, pin::Pin, sync::Arc};
 use crate::{
     database::Database,
     event::{AppEvent, EventHandler, ReviewId},
-    models::FileView,
+    models::{FileView, FileViewStatus},
     services::{ServiceContext, ServiceHandler},
 };
 
@@ -20,12 +20,41 @@ impl ServiceHandler for FileViewService {
                 AppEvent::FileViewToggle {
                     review_id,
                     file_path,
+                    content_hash,
                 } => {
                     Self::handle_file_view_toggle(
                         context.database,
                         context.events,
                         review_id,
                         file_path,
+                        content_hash.as_deref(),
+                        FileViewStatus::toggle_viewed,
+                    )
+                    .await?;
+                }
+                AppEvent::FileSkipToggle {
+                    review_id,
+                    file_path,
+                } => {
+                    Self::handle_file_view_toggle(
+                        context.database,
+                        context.events,
+                        review_id,
+                        file_path,
+                        None,
+                        FileViewStatus::toggle_skipped,
+                    )
+                    .await?;
+                }
+                AppEvent::FileViewsReset {
+                    review_id,
+                    file_paths,
+                } => {
+                    Self::handle_file_views_reset(
+                        context.database,
+                        context.events,
+                        review_id,
+                        file_paths,
                     )
                     .await?;
                 }
@@ -43,18 +72,19 @@ impl ServiceHandler for FileViewService {
 }
 
 impl FileViewService {
-    /// Toggle the view status of a file for a review
+    /// Toggle the viewed or skipped status of a file for a review
     async fn handle_file_view_toggle(
         database: &Database,
         events: &mut EventHandler,
         review_id: &ReviewId,
         file_path: &str,
+        content_hash: Option<&str>,
+        toggle: fn(FileViewStatus) -> FileViewStatus,
     ) -> color_eyre::Result<()> {
         let pool = database.pool();
 
-        // Check if file is currently viewed
-        let is_viewed = match FileView::is_file_viewed(pool, review_id, file_path).await {
-            Ok(viewed) => viewed,
+        let status = match FileView::status(pool, review_id, file_path).await {
+            Ok(status) => toggle(status),
             Err(error) => {
                 events.send(AppEvent::FileViewToggleError {
                     review_id: Arc::from(review_id),
@@ -65,12 +95,14 @@ impl FileViewService {
             }
         };
 
-        let result = if is_viewed {
-            // Mark as unviewed
-            FileView::mark_as_unviewed(pool, review_id, file_path).await
-        } else {
-            // Mark as viewed
-            FileView::mark_as_viewed(pool, review_id, file_path).await
+        let result = match status {
+            FileViewStatus::NotViewed => {
+                FileView::mark_as_unviewed(pool, review_id, file_path).await
+            }
+            FileViewStatus::Viewed => {
+                FileView::mark_as_viewed(pool, review_id, file_path, content_hash).await
+            }
+            FileViewStatus::Skipped => FileView::mark_as_skipped(pool, review_id, file_path).await,
         };
 
         match result {
@@ -79,7 +111,7 @@ impl FileViewService {
                 events.send(AppEvent::FileViewToggled {
                     review_id: Arc::from(review_id),
                     file_path: Arc::from(file_path),
-                    is_viewed: !is_viewed,
+                    status,
                 });
                 events.send(AppEvent::FileViewsLoad {
                     review_id: Arc::from(review_id),
@@ -97,7 +129,7 @@ impl FileViewService {
         Ok(())
     }
 
-    /// Load the viewed files for a review
+    /// Load the viewed and skipped files for a review
     async fn handle_file_views_load(
         database: &Database,
         events: &mut EventHandler,
@@ -109,11 +141,23 @@ impl FileViewService {
             review_id: Arc::from(review_id),
         });
 
-        match FileView::get_viewed_files(pool, review_id).await {
-            Ok(viewed_files) => {
+        let result = match FileView::get_viewed_files(pool, review_id).await {
+            Ok(viewed_files) => match FileView::get_skipped_files(pool, review_id).await {
+                Ok(skipped_files) => FileView::get_viewed_file_content_hashes(pool, review_id)
+                    .await
+                    .map(|content_hashes| (viewed_files, skipped_files, content_hashes)),
+                Err(error) => Err(error),
+            },
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok((viewed_files, skipped_files, content_hashes)) => {
                 events.send(AppEvent::FileViewsLoaded {
                     review_id: Arc::from(review_id),
                     viewed_files: Arc::from(viewed_files),
+                    skipped_files: Arc::from(skipped_files),
+                    content_hashes: Arc::new(content_hashes),
                 });
             }
             Err(e) => {
@@ -126,6 +170,29 @@ impl FileViewService {
 
         Ok(())
     }
+
+    /// Mark files as not viewed that changed since they were marked as viewed
+    async fn handle_file_views_reset(
+        database: &Database,
+        events: &mut EventHandler,
+        review_id: &ReviewId,
+        file_paths: &[String],
+    ) -> color_eyre::Result<()> {
+        for file_path in file_paths {
+            if let Err(error) =
+                FileView::mark_as_unviewed(database.pool(), review_id, file_path).await
+            {
+                log::warn!(
+                    "Failed to reset viewed file {file_path} for review {review_id}: {error}"
+                );
+            }
+        }
+
+        events.send(AppEvent::FileViewsLoad {
+            review_id: Arc::from(review_id),
+        });
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -171,6 +238,7 @@ mod tests {
         let event = AppEvent::FileViewToggle {
             review_id: Arc::from(review.id.as_str()),
             file_path: Arc::from(file_path),
+            content_hash: Some(Arc::from("hash")),
         };
 
         let app = App {
@@ -179,6 +247,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
         FileViewService::handle_app_event(
             &event,
@@ -191,12 +262,17 @@ mod tests {
         .await
         .unwrap();
 
-        // Verify file is now viewed
+        // Verify file is now viewed with the content hash
         assert!(
             FileView::is_file_viewed(app.database.pool(), &review.id, file_path)
                 .await
                 .unwrap()
         );
+        let content_hashes =
+            FileView::get_viewed_file_content_hashes(app.database.pool(), &review.id)
+                .await
+                .unwrap();
+        assert_eq!(content_hashes.get(file_path).unwrap(), "hash");
 
         // Check that success event was sent
         let sent_event = events.try_recv().unwrap();
@@ -204,11 +280,11 @@ mod tests {
             Event::App(AppEvent::FileViewToggled {
                 review_id,
                 file_path: sent_file_path,
-                is_viewed,
+                status,
             }) => {
                 assert_eq!(review_id.as_ref(), review.id);
                 assert_eq!(sent_file_path.as_ref(), file_path);
-                assert!(*is_viewed);
+                assert_eq!(*status, FileViewStatus::Viewed);
             }
             _ => panic!("Expected FileViewToggled event, got: {sent_event:?}"),
         }
@@ -231,7 +307,7 @@ mod tests {
         let file_path = "src/main.rs";
 
         // First mark as viewed
-        FileView::mark_as_viewed(database.pool(), &review.id, file_path)
+        FileView::mark_as_viewed(database.pool(), &review.id, file_path, None)
             .await
             .unwrap();
         assert!(
@@ -244,6 +320,7 @@ mod tests {
         let event = AppEvent::FileViewToggle {
             review_id: Arc::from(review.id.as_str()),
             file_path: Arc::from(file_path),
+            content_hash: Some(Arc::from("hash")),
         };
 
         let app = App {
@@ -252,6 +329,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
         FileViewService::handle_app_event(
             &event,
@@ -277,28 +357,92 @@ mod tests {
             Event::App(AppEvent::FileViewToggled {
                 review_id,
                 file_path: sent_file_path,
-                is_viewed,
+                status,
             }) => {
                 assert_eq!(review_id.as_ref(), review.id);
                 assert_eq!(sent_file_path.as_ref(), file_path);
-                assert!(!*is_viewed);
+                assert_eq!(*status, FileViewStatus::NotViewed);
             }
             _ => panic!("Expected FileViewToggled event, got: {sent_event:?}"),
         }
     }
 
     #[tokio::test]
-    async fn test_handle_file_views_load_success() {
+    async fn test_handle_file_view_and_skip_toggles_cycle_through_statuses() {
         let database = create_test_database().await;
         let mut events = EventHandler::new_for_test();
         let review = create_test_review(&database).await;
+        let file_path = "src/generated.rs";
+        let view_toggle = AppEvent::FileViewToggle {
+            review_id: Arc::from(review.id.as_str()),
+            file_path: Arc::from(file_path),
+            content_hash: Some(Arc::from("hash")),
+        };
+        let skip_toggle = AppEvent::FileSkipToggle {
+            review_id: Arc::from(review.id.as_str()),
+            file_path: Arc::from(file_path),
+        };
 
-        let file_paths = vec!["src/main.rs", "src/lib.rs"];
-        for file_path in &file_paths {
-            FileView::mark_as_viewed(database.pool(), &review.id, file_path)
-                .await
-                .unwrap();
+        // Not viewed -> viewed -> skipped -> not viewed
+        for (event, expected_status) in [
+            (&view_toggle, FileViewStatus::Viewed),
+            (&skip_toggle, FileViewStatus::Skipped),
+            (&skip_toggle, FileViewStatus::NotViewed),
+            (&skip_toggle, FileViewStatus::Skipped),
+            (&view_toggle, FileViewStatus::NotViewed),
+        ] {
+            FileViewService::handle_app_event(
+                event,
+                ServiceContext {
+                    database: &database,
+                    repo_path: ".",
+                    events: &mut events,
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(
+                FileView::status(database.pool(), &review.id, file_path)
+                    .await
+                    .unwrap(),
+                expected_status
+            );
+            let sent_event = events.try_recv().unwrap();
+            match &*sent_event {
+                Event::App(AppEvent::FileViewToggled { status, .. }) => {
+                    assert_eq!(*status, expected_status);
+                }
+                _ => panic!("Expected FileViewToggled event, got: {sent_event:?}"),
+            }
+            let reload_event = events.try_recv().unwrap();
+            assert!(matches!(
+                *reload_event,
+                Event::App(AppEvent::FileViewsLoad { .. })
+            ));
         }
+    }
+
+    #[tokio::test]
+    async fn test_handle_file_views_load_success() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let review = create_test_review(&database).await;
+
+        FileView::mark_as_viewed(
+            database.pool(),
+            &review.id,
+            "src/main.rs",
+            Some("hash-main"),
+        )
+        .await
+        .unwrap();
+        FileView::mark_as_viewed(database.pool(), &review.id, "src/lib.rs", None)
+            .await
+            .unwrap();
+        FileView::mark_as_skipped(database.pool(), &review.id, "src/generated.rs")
+            .await
+            .unwrap();
 
         // Load file views
         let event = AppEvent::FileViewsLoad {
@@ -311,6 +455,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
         FileViewService::handle_app_event(
             &event,
@@ -338,8 +485,16 @@ mod tests {
             Event::App(AppEvent::FileViewsLoaded {
                 review_id,
                 viewed_files,
+                skipped_files,
+                content_hashes,
             }) => {
                 assert_eq!(review_id.as_ref(), review.id);
+                assert_eq!(content_hashes.get("src/main.rs").unwrap(), "hash-main");
+                assert_eq!(
+                    skipped_files.as_ref(),
+                    &vec!["src/generated.rs".to_string()]
+                );
+                assert!(!content_hashes.contains_key("src/lib.rs"));
                 assert_eq!(viewed_files.len(), 2);
                 assert!(viewed_files.contains(&"src/main.rs".to_string()));
                 assert!(viewed_files.contains(&"src/lib.rs".to_string()));
@@ -365,6 +520,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
         FileViewService::handle_app_event(
             &event,
@@ -386,14 +544,51 @@ mod tests {
             Event::App(AppEvent::FileViewsLoaded {
                 review_id,
                 viewed_files,
+                skipped_files,
+                content_hashes,
             }) => {
                 assert_eq!(review_id.as_ref(), review.id);
                 assert_eq!(viewed_files.len(), 0);
+                assert!(skipped_files.is_empty());
+                assert!(content_hashes.is_empty());
             }
             _ => panic!("Expected FileViewsLoaded event, got: {loaded_event:?}"),
         }
     }
 
+    #[tokio::test]
+    async fn test_handle_file_views_reset() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let review = create_test_review(&database).await;
+        for file_path in ["src/main.rs", "src/lib.rs"] {
+            FileView::mark_as_viewed(database.pool(), &review.id, file_path, Some("hash"))
+                .await
+                .unwrap();
+        }
+
+        FileViewService::handle_app_event(
+            &AppEvent::FileViewsReset {
+                review_id: Arc::from(review.id.as_str()),
+                file_paths: Arc::from(vec!["src/main.rs".to_string()]),
+            },
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        let viewed_files = FileView::get_viewed_files(database.pool(), &review.id)
+            .await
+            .unwrap();
+        assert_eq!(viewed_files, vec!["src/lib.rs".to_string()]);
+        let event = events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::FileViewsLoad { .. })));
+    }
+
     #[tokio::test]
     async fn test_handle_unrelated_event() {
         let database = create_test_database().await;
@@ -408,6 +603,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
         FileViewService::handle_app_event(
             &event,
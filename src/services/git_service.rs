@@ -1,10 +1,24 @@
 use std::{
-    cell::RefCell, collections::HashMap, future::Future, path::Path, pin::Pin, rc::Rc, sync::Arc,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    future::Future,
+    path::Path,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
 };
 
 use crate::event::{AppEvent, EventHandler};
-use crate::models::{Diff, DiffFile};
+use crate::exclude_patterns::ExcludePatterns;
+use crate::models::{Diff, DiffFile, DiffHunk, DiffLine, Hunk, short_sha};
 use crate::services::{ServiceContext, ServiceHandler};
+use crate::unified_diff::read_patch_file;
+
+/// Description of a changed binary file that is not a known image
+pub const BINARY_FILE_CHANGED: &str = "binary file changed";
+
+/// Upper bound of the progress events sent while parsing a single diff
+const DIFF_PROGRESS_EVENTS: usize = 20;
 
 /// State of Git branches loading process
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -16,10 +30,20 @@ pub enum GitBranchesLoadingState {
     Loading,
     /// Branches have been successfully loaded
     Loaded(Arc<[String]>),
+    /// The repository has no commits yet, so there is nothing to review
+    NoCommits,
     /// Error occurred during loading
     Error(Arc<str>),
 }
 
+/// Review of the changes since the last release: from the most recent tag before the commit of
+/// `HEAD` to the checked out branch
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinceLastTag {
+    pub tag: String,
+    pub head_branch: String,
+}
+
 /// State of Git diff loading process with structured data
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum GitDiffLoadingState {
@@ -34,11 +58,90 @@ pub enum GitDiffLoadingState {
     Error(Arc<str>),
 }
 
+/// Summary of the diff between two branches, previewed before creating a review
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffPreview {
+    pub base_branch: Arc<str>,
+    pub target_branch: Arc<str>,
+    pub file_count: usize,
+    pub added_lines: usize,
+    pub removed_lines: usize,
+}
+
+impl DiffPreview {
+    /// Summarize the diff between the given branches
+    pub fn new(base_branch: &str, target_branch: &str, diff: &Diff) -> Self {
+        let (added_lines, removed_lines) = diff.line_stats();
+        Self {
+            base_branch: Arc::from(base_branch),
+            target_branch: Arc::from(target_branch),
+            file_count: diff.file_count(),
+            added_lines,
+            removed_lines,
+        }
+    }
+
+    /// Whether the branches have no changes between them
+    pub fn is_empty(&self) -> bool {
+        self.file_count == 0
+    }
+}
+
+/// Direction in which the context around a hunk is expanded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffContextDirection {
+    /// Lines before the hunk
+    Above,
+    /// Lines after the hunk
+    Below,
+}
+
+/// Request for unchanged lines of a file around a hunk to expand the diff context
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffContextRequest {
+    /// SHA to read the file at
+    pub sha: Arc<str>,
+    pub file_path: Arc<str>,
+    /// Index of the hunk header line in the diff content
+    pub hunk_line_index: usize,
+    pub direction: DiffContextDirection,
+    /// First line to read (1-based)
+    pub start_line: usize,
+    /// Last line to read (1-based, inclusive)
+    pub end_line: usize,
+}
+
+/// Request for the blame summaries of the hunks of a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct HunkBlameRequest {
+    /// SHA to blame the file at, the base of the diff
+    pub base_sha: Arc<str>,
+    /// Path of the file in the diff
+    pub file_path: Arc<str>,
+    /// Path of the file at the base SHA, differs from `file_path` for renamed files
+    pub old_path: Arc<str>,
+    pub hunks: Arc<[DiffHunk]>,
+}
+
 pub struct GitService;
 
 impl GitService {
+    /// Open the repository that contains the path, which can be a subdirectory of it
+    pub fn open_repository<P: AsRef<Path>>(repo_path: P) -> Result<git2::Repository, git2::Error> {
+        git2::Repository::discover(repo_path)
+    }
+
+    /// Root of the working tree of the repository that contains the path, `None` when the path
+    /// is not in a repository
+    pub fn discover_repo_root<P: AsRef<Path>>(repo_path: P) -> Option<String> {
+        let repo = Self::open_repository(repo_path).ok()?;
+        let root = repo.workdir().unwrap_or_else(|| repo.path());
+        let root = root.to_string_lossy();
+        Some(root.strip_suffix('/').unwrap_or(&root).to_string())
+    }
+
     pub fn get_branches<P: AsRef<Path>>(repo_path: P) -> color_eyre::Result<Arc<[String]>> {
-        let repo = git2::Repository::open(repo_path)?;
+        let repo = Self::open_repository(repo_path)?;
         let mut branches = Vec::new();
 
         // Get local branches
@@ -56,12 +159,19 @@ impl GitService {
         Ok(branches.into())
     }
 
+    /// Whether the repository has at least one commit. A freshly initialized repository only
+    /// has an unborn HEAD, whose branch has no SHA to review.
+    pub fn has_commits<P: AsRef<Path>>(repo_path: P) -> color_eyre::Result<bool> {
+        let repo = Self::open_repository(repo_path)?;
+        Ok(!repo.is_empty()?)
+    }
+
     /// Get the SHA of a specific branch
     pub fn get_branch_sha<PathRef: AsRef<Path>>(
         repo_path: PathRef,
         branch_name: &str,
     ) -> color_eyre::Result<Option<String>> {
-        let repo = git2::Repository::open(repo_path)?;
+        let repo = Self::open_repository(repo_path)?;
 
         // Try to find the branch reference
         match repo.find_reference(&Self::get_branch_reference_name(branch_name)) {
@@ -76,18 +186,199 @@ impl GitService {
         }
     }
 
+    /// Get the SHA of a branch, or of the tag with the name when there is no such branch. The
+    /// base of a review of the changes since the last release is a tag.
+    pub fn get_branch_or_tag_sha<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        name: &str,
+    ) -> color_eyre::Result<Option<String>> {
+        match Self::get_branch_sha(repo_path.as_ref(), name)? {
+            Some(sha) => Ok(Some(sha)),
+            None => Self::get_tag_sha(repo_path, name),
+        }
+    }
+
+    /// Get the SHA of the commit of a tag
+    pub fn get_tag_sha<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        tag_name: &str,
+    ) -> color_eyre::Result<Option<String>> {
+        let repo = Self::open_repository(repo_path)?;
+        match repo.find_reference(&format!("refs/tags/{tag_name}")) {
+            Ok(reference) => Ok(Some(reference.peel_to_commit()?.id().to_string())),
+            Err(_) => Ok(None), // Tag doesn't exist
+        }
+    }
+
+    /// Names of the tags of the repository, sorted alphabetically
+    pub fn get_tags<P: AsRef<Path>>(repo_path: P) -> color_eyre::Result<Arc<[String]>> {
+        let repo = Self::open_repository(repo_path)?;
+        let mut tags: Vec<String> = repo
+            .tag_names(None)?
+            .iter()
+            .flatten()
+            .map(String::from)
+            .collect();
+        tags.sort();
+        Ok(tags.into())
+    }
+
+    /// Most recent tag reachable from the commit, not counting the tags of the commit itself,
+    /// i.e. the release before it. `None` when no earlier commit is tagged.
+    pub fn previous_tag<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        sha: &str,
+    ) -> color_eyre::Result<Option<String>> {
+        let repo = Self::open_repository(repo_path)?;
+        let commit_id = repo.revparse_single(sha)?.peel_to_commit()?.id();
+
+        let mut tags_by_commit: HashMap<git2::Oid, Vec<String>> = HashMap::new();
+        for tag_name in repo.tag_names(None)?.iter().flatten() {
+            let Ok(reference) = repo.find_reference(&format!("refs/tags/{tag_name}")) else {
+                continue;
+            };
+            // Tags of trees or blobs can't be the base of a review
+            if let Ok(commit) = reference.peel_to_commit() {
+                tags_by_commit
+                    .entry(commit.id())
+                    .or_default()
+                    .push(tag_name.to_string());
+            }
+        }
+        if tags_by_commit.is_empty() {
+            return Ok(None);
+        }
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+        revwalk.push(commit_id)?;
+        for oid in revwalk {
+            let oid = oid?;
+            if oid == commit_id {
+                continue;
+            }
+            if let Some(tag_names) = tags_by_commit.get(&oid) {
+                return Ok(tag_names.iter().max().cloned());
+            }
+        }
+        Ok(None)
+    }
+
+    /// Changes since the last release, `None` when `HEAD` is not on a branch or no earlier
+    /// commit is tagged
+    pub fn since_last_tag<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+    ) -> color_eyre::Result<Option<SinceLastTag>> {
+        let repo = Self::open_repository(repo_path.as_ref())?;
+        let head = repo.head()?;
+        let Some(head_branch) = head.is_branch().then(|| head.shorthand()).flatten() else {
+            return Ok(None);
+        };
+        let head_sha = head.peel_to_commit()?.id().to_string();
+        Ok(
+            Self::previous_tag(repo_path, &head_sha)?.map(|tag| SinceLastTag {
+                tag,
+                head_branch: head_branch.to_string(),
+            }),
+        )
+    }
+
+    /// Resolve a revspec (branch, tag, SHA, `HEAD~2`, ...) to the SHA of its commit
+    pub fn resolve_ref<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        revspec: &str,
+    ) -> color_eyre::Result<String> {
+        let repo = Self::open_repository(repo_path)?;
+        let commit = repo.revparse_single(revspec)?.peel_to_commit()?;
+        Ok(commit.id().to_string())
+    }
+
     /// Get the full reference name for a branch
     fn get_branch_reference_name(branch_name: &str) -> String {
         format!("refs/heads/{branch_name}")
     }
 
+    /// Build the web compare URL (GitHub or GitLab shape) between two revisions from the
+    /// `origin` remote. Returns `None` if there is no `origin` remote or its URL can't be
+    /// mapped to a web URL.
+    pub fn remote_compare_url<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        base: &str,
+        target: &str,
+    ) -> color_eyre::Result<Option<String>> {
+        let repo = Self::open_repository(repo_path)?;
+        let remote = match repo.find_remote("origin") {
+            Ok(remote) => remote,
+            Err(error) if error.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        let Some(web_url) = remote.url().and_then(Self::remote_web_url) else {
+            return Ok(None);
+        };
+
+        let compare_path = if web_url.contains("gitlab") {
+            "-/compare"
+        } else {
+            "compare"
+        };
+        Ok(Some(format!("{web_url}/{compare_path}/{base}...{target}")))
+    }
+
+    /// Normalize a remote URL (SSH, scp-like, HTTPS or git protocol) to the HTTPS web URL of the
+    /// repository, e.g. `git@github.com:owner/repo.git` to `https://github.com/owner/repo`.
+    /// Returns `None` for local paths.
+    fn remote_web_url(remote_url: &str) -> Option<String> {
+        let remote_url = remote_url.trim();
+        let (host, path) = if let Some((scheme, rest)) = remote_url.split_once("://") {
+            if !matches!(
+                scheme,
+                "ssh" | "git" | "http" | "https" | "git+ssh" | "ssh+git"
+            ) {
+                return None;
+            }
+            let (authority, path) = rest.split_once('/')?;
+            // Drop user info and port
+            let host = authority.rsplit('@').next()?;
+            let host = host.split(':').next()?;
+            (host, path)
+        } else {
+            // scp-like syntax: [user@]host:path
+            let (authority, path) = remote_url.split_once(':')?;
+            if authority.contains('/') {
+                return None;
+            }
+            let host = authority.rsplit('@').next()?;
+            (host, path)
+        };
+
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        if host.is_empty() || path.is_empty() {
+            return None;
+        }
+        Some(format!("https://{host}/{path}"))
+    }
+
     /// Get the diff between two SHAs as structured data
     pub fn get_diff_between_shas<PathRef: AsRef<Path>>(
         repo_path: PathRef,
         base_sha: &str,
         target_sha: &str,
     ) -> color_eyre::Result<Diff> {
-        let repo = git2::Repository::open(repo_path)?;
+        Self::get_diff_between_shas_with_progress(repo_path, base_sha, target_sha, None, |_, _| {})
+    }
+
+    /// Get the diff between two SHAs as structured data, limited to the files matching
+    /// `pathspec` if given. `on_progress` is called with the number of parsed files and the
+    /// total number of files for every file of the diff.
+    pub fn get_diff_between_shas_with_progress<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        base_sha: &str,
+        target_sha: &str,
+        pathspec: Option<&str>,
+        mut on_progress: impl FnMut(usize, usize),
+    ) -> color_eyre::Result<Diff> {
+        let repo = Self::open_repository(repo_path)?;
 
         // Parse SHAs to git2::Oid
         let base_oid = git2::Oid::from_str(base_sha)?;
@@ -101,11 +392,172 @@ impl GitService {
         let base_tree = base_commit.tree()?;
         let target_tree = target_commit.tree()?;
 
-        // Create diff between trees
-        let diff = repo.diff_tree_to_tree(Some(&base_tree), Some(&target_tree), None)?;
+        // Create diff between trees, with renamed files as one file under the new path
+        let mut diff_options = git2::DiffOptions::new();
+        if let Some(pathspec) = pathspec {
+            diff_options.pathspec(pathspec);
+        }
+        let mut diff = repo.diff_tree_to_tree(
+            Some(&base_tree),
+            Some(&target_tree),
+            Some(&mut diff_options),
+        )?;
+        diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))?;
 
         // Parse diff into structured format
-        Self::parse_git_diff(diff)
+        Self::parse_git_diff(&repo, diff, &mut on_progress)
+    }
+
+    /// Number of files of the diff between two SHAs as reviewed: renamed files count once and
+    /// excluded files are left out. Only the trees are compared, the diff is not parsed.
+    pub fn count_diff_files<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        base_sha: &str,
+        target_sha: &str,
+    ) -> color_eyre::Result<usize> {
+        let repo = Self::open_repository(repo_path)?;
+        let base_tree = repo.find_commit(git2::Oid::from_str(base_sha)?)?.tree()?;
+        let target_tree = repo.find_commit(git2::Oid::from_str(target_sha)?)?.tree()?;
+        let mut diff = repo.diff_tree_to_tree(Some(&base_tree), Some(&target_tree), None)?;
+        diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))?;
+
+        let exclude_patterns = repo
+            .workdir()
+            .map(ExcludePatterns::load)
+            .unwrap_or_default();
+        Ok(diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().or(delta.old_file().path()))
+            .filter(|path| !exclude_patterns.is_excluded(&path.to_string_lossy()))
+            .count())
+    }
+
+    /// Summarize the diff between the current heads of two branches
+    pub fn get_diff_preview<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        base_branch: &str,
+        target_branch: &str,
+    ) -> color_eyre::Result<DiffPreview> {
+        let repo_path = repo_path.as_ref();
+        let base_sha = Self::get_branch_sha(repo_path, base_branch)?
+            .ok_or_else(|| color_eyre::eyre::eyre!("Branch {base_branch} not found"))?;
+        let target_sha = Self::get_branch_sha(repo_path, target_branch)?
+            .ok_or_else(|| color_eyre::eyre::eyre!("Branch {target_branch} not found"))?;
+        let diff = Self::get_diff_between_shas(repo_path, &base_sha, &target_sha)?;
+        Ok(DiffPreview::new(base_branch, target_branch, &diff))
+    }
+
+    /// Blame a file once at the base SHA and summarize who last touched the lines each hunk
+    /// replaces, keyed by the index of the hunk header line. Hunks that only add lines have
+    /// no summary, neither have the hunks of files that don't exist at the base SHA.
+    pub fn hunk_blame_summaries<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        base_sha: &str,
+        file_path: &str,
+        hunks: &[DiffHunk],
+    ) -> color_eyre::Result<HashMap<usize, String>> {
+        if hunks.iter().all(|hunk| hunk.old_lines == 0) {
+            return Ok(HashMap::new());
+        }
+
+        let repo = Self::open_repository(repo_path)?;
+        let base_oid = git2::Oid::from_str(base_sha)?;
+        let base_tree = repo.find_commit(base_oid)?.tree()?;
+        if base_tree.get_path(Path::new(file_path)).is_err() {
+            return Ok(HashMap::new());
+        }
+
+        let mut options = git2::BlameOptions::new();
+        options.newest_commit(base_oid);
+        let blame = repo.blame_file(Path::new(file_path), Some(&mut options))?;
+
+        let mut summaries = HashMap::new();
+        for hunk in hunks {
+            if let Some(summary) = Self::hunk_blame_summary(&repo, &blame, hunk)? {
+                summaries.insert(hunk.line_index, summary);
+            }
+        }
+        Ok(summaries)
+    }
+
+    /// One-line summary of the most recent commit that touched the old lines of a hunk:
+    /// author, short SHA, date and commit summary
+    pub fn hunk_blame_summary(
+        repo: &git2::Repository,
+        blame: &git2::Blame,
+        hunk: &DiffHunk,
+    ) -> color_eyre::Result<Option<String>> {
+        let old_lines = hunk.old_start..hunk.old_start + hunk.old_lines;
+        let mut latest_commit: Option<git2::Commit> = None;
+        for line in old_lines {
+            let Some(blame_hunk) = blame.get_line(line) else {
+                continue;
+            };
+            let commit = repo.find_commit(blame_hunk.final_commit_id())?;
+            if latest_commit
+                .as_ref()
+                .is_none_or(|latest| commit.time().seconds() > latest.time().seconds())
+            {
+                latest_commit = Some(commit);
+            }
+        }
+
+        Ok(latest_commit.map(|commit| {
+            let sha = commit.id().to_string();
+            let date = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+                .map(|time| time.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            format!(
+                "{}, {} ({date}): {}",
+                commit.author().name().unwrap_or("Unknown"),
+                short_sha(&sha),
+                commit.summary().unwrap_or_default()
+            )
+        }))
+    }
+
+    /// List the paths of all files in the tree of a SHA, sorted. Walking the whole tree is slow
+    /// on large repositories, so it is only done on request.
+    pub fn list_tree_files<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        sha: &str,
+    ) -> color_eyre::Result<Vec<String>> {
+        let repo = Self::open_repository(repo_path)?;
+        let tree = repo.find_commit(git2::Oid::from_str(sha)?)?.tree()?;
+        let mut paths = Vec::new();
+        tree.walk(git2::TreeWalkMode::PreOrder, |directory, entry| {
+            if entry.kind() == Some(git2::ObjectType::Blob)
+                && let Some(name) = entry.name()
+            {
+                paths.push(format!("{directory}{name}"));
+            }
+            git2::TreeWalkResult::Ok
+        })?;
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Read the lines `start_line..=end_line` (1-based) of a file at a SHA. Lines past the end
+    /// of the file are omitted.
+    pub fn get_file_lines<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        sha: &str,
+        file_path: &str,
+        start_line: usize,
+        end_line: usize,
+    ) -> color_eyre::Result<Vec<String>> {
+        let repo = Self::open_repository(repo_path)?;
+        let commit = repo.find_commit(git2::Oid::from_str(sha)?)?;
+        let entry = commit.tree()?.get_path(Path::new(file_path))?;
+        let content = Self::read_blob_lossy(&repo, entry.id())?;
+
+        let start_index = start_line.max(1) - 1;
+        Ok(content
+            .lines()
+            .skip(start_index)
+            .take(end_line.saturating_sub(start_index))
+            .map(String::from)
+            .collect())
     }
 
     /// Parse a `git2::Diff` into structured `DiffFile` objects.
@@ -129,43 +581,60 @@ impl GitService {
     /// The `git2::Diff` API is callback-based, meaning that it invokes user-provided
     /// closures for each file, hunk, and line in the diff. This design necessitates
     /// the use of shared state (`Rc<RefCell<HashMap>>`) to accumulate results across
-    /// multiple callbacks.
+    /// multiple callbacks. Every hunk callback starts a new `Hunk` and the line callbacks add
+    /// their `DiffLine`s with the line numbers of the old and new file to the last hunk. The
+    /// `\ No newline at end of file` markers of lines without line break are kept as lines
+    /// with the `DiffLine::NO_NEWLINE_MARKER` origin.
+    ///
+    /// ### Excluded Files
+    /// Files matching the patterns of the `.glr-ignore` file in the working directory (see
+    /// [`ExcludePatterns`]) are moved to the excluded files of the diff, so they are not
+    /// reviewed unless they are shown on purpose.
+    ///
+    /// ### Metadata Changes
+    /// Files whose only change is their mode (e.g. `chmod +x`) or a symlink target and binary
+    /// files don't have meaningful diff lines. Their content is replaced by a description of the change and
+    /// they are flagged as `metadata_only`.
     ///
     /// ### Output
     /// The function returns a `Diff` object containing a list of `DiffFile` objects,
-    /// each representing a file in the diff along with its content.
-    fn parse_git_diff(diff: git2::Diff) -> color_eyre::Result<Diff> {
+    /// each representing a file in the diff along with its hunks.
+    fn parse_git_diff(
+        repo: &git2::Repository,
+        diff: git2::Diff,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> color_eyre::Result<Diff> {
         // Use Rc and RefCell to share mutable state across closures
-        // HashMap to store file paths and their content (path => content)
-        let files_content = Rc::new(RefCell::new(HashMap::<String, String>::new()));
+        // HashMap to store file paths and their hunks (path => hunks)
+        let files_hunks = Rc::new(RefCell::new(HashMap::<String, Vec<Hunk>>::new()));
 
         // Clone references for each closure
-        let files_content_file = Rc::clone(&files_content);
-        let files_content_hunk = Rc::clone(&files_content);
-        let files_content_line = Rc::clone(&files_content);
+        let files_hunks_file = Rc::clone(&files_hunks);
+        let files_hunks_hunk = Rc::clone(&files_hunks);
+        let files_hunks_line = Rc::clone(&files_hunks);
+
+        let files_total = diff.deltas().len();
+        let mut files_done = 0;
 
         // Use foreach to collect file information
         diff.foreach(
             &mut |delta, _progress| {
+                files_done += 1;
+                on_progress(files_done, files_total);
+
                 // Extract file path from delta. If new_file and old_file are both present,
                 // new_file takes precedence because that's the state after the commits.
                 if let Some(new_file) = delta.new_file().path() {
                     let file_path = new_file.to_string_lossy().to_string();
-                    files_content_file
-                        .borrow_mut()
-                        .entry(file_path)
-                        .or_default();
+                    files_hunks_file.borrow_mut().entry(file_path).or_default();
                 } else if let Some(old_file) = delta.old_file().path() {
                     let file_path = old_file.to_string_lossy().to_string();
-                    files_content_file
-                        .borrow_mut()
-                        .entry(file_path)
-                        .or_default();
+                    files_hunks_file.borrow_mut().entry(file_path).or_default();
                 }
                 true
             },
             None, // No binary callback needed
-            Some(&mut |delta, _hunk| {
+            Some(&mut |delta, hunk| {
                 // Collect hunk headers
                 let file_path = if let Some(new_file) = delta.new_file().path() {
                     new_file.to_string_lossy().to_string()
@@ -175,10 +644,13 @@ impl GitService {
                     return true;
                 };
 
-                if let Some(content) = files_content_hunk.borrow_mut().get_mut(&file_path)
-                    && let Ok(header) = std::str::from_utf8(_hunk.header())
+                if let Some(hunks) = files_hunks_hunk.borrow_mut().get_mut(&file_path)
+                    && let Ok(header) = std::str::from_utf8(hunk.header())
                 {
-                    content.push_str(header);
+                    hunks.push(Hunk {
+                        header: header.to_string(),
+                        lines: vec![],
+                    });
                 }
                 true
             }),
@@ -192,33 +664,55 @@ impl GitService {
                     return true;
                 };
 
-                if let Some(content) = files_content_line.borrow_mut().get_mut(&file_path) {
-                    // Add line origin character
-                    match line.origin() {
-                        '+' | '-' | ' ' => content.push(line.origin()),
-                        _ => {}
+                if let Some(hunk) = files_hunks_line
+                    .borrow_mut()
+                    .get_mut(&file_path)
+                    .and_then(|hunks| hunks.last_mut())
+                {
+                    if matches!(
+                        line.origin_value(),
+                        git2::DiffLineType::ContextEOFNL
+                            | git2::DiffLineType::AddEOFNL
+                            | git2::DiffLineType::DeleteEOFNL
+                    ) {
+                        // Git reports the marker with a leading line break instead of ending
+                        // the line it is about, make the marker a line of its own
+                        if let Some(last_line) = hunk.lines.last_mut()
+                            && !last_line.content.ends_with('\n')
+                        {
+                            last_line.content.push('\n');
+                        }
+                        hunk.lines.push(DiffLine::no_newline_marker());
+                        return true;
                     }
-
-                    // Add line content
-                    match std::str::from_utf8(line.content()) {
-                        Ok(line_content) => content.push_str(line_content),
+                    let content = match std::str::from_utf8(line.content()) {
+                        Ok(line_content) => line_content.to_string(),
                         Err(error) => {
                             eprintln!("UTF-8 conversion error: {error}");
-                            content.push_str("[INVALID UTF-8]");
+                            "[INVALID UTF-8]".to_string()
                         }
-                    }
+                    };
+                    hunk.lines.push(DiffLine {
+                        origin: line.origin(),
+                        content,
+                        old_lineno: line.old_lineno().map(|lineno| lineno as usize),
+                        new_lineno: line.new_lineno().map(|lineno| lineno as usize),
+                    });
                 }
                 true
             }),
         )?;
 
+        // Describe metadata-only changes (file mode, symlink target) instead of diff lines
+        let metadata_changes = Self::collect_metadata_changes(repo, &diff, &files_hunks.borrow())?;
+
         // Convert HashMap to Vec<DiffFile>
-        let diff_files: Vec<DiffFile> = files_content
-            .borrow()
-            .iter()
-            .map(|(path, content)| DiffFile {
-                path: path.clone(),
-                content: content.clone(),
+        let diff_files: Vec<DiffFile> = files_hunks
+            .take()
+            .into_iter()
+            .map(|(path, hunks)| match metadata_changes.get(&path) {
+                Some(description) => DiffFile::metadata(path, description.clone()),
+                None => DiffFile::new(path, hunks),
             })
             .collect();
 
@@ -226,7 +720,130 @@ impl GitService {
         let mut sorted_diff_files = diff_files;
         sorted_diff_files.sort_by(|a, b| a.path.cmp(&b.path));
 
-        Ok(Diff::from_files(sorted_diff_files))
+        let exclude_patterns = repo
+            .workdir()
+            .map(ExcludePatterns::load)
+            .unwrap_or_default();
+
+        Ok(Diff::from_files(sorted_diff_files)
+            .with_renamed_paths(Self::collect_renamed_paths(&diff))
+            .with_deleted_paths(Self::collect_deleted_paths(&diff))
+            .with_excluded_files(&exclude_patterns))
+    }
+
+    /// Collect the paths of the deleted files
+    fn collect_deleted_paths(diff: &git2::Diff) -> HashSet<String> {
+        diff.deltas()
+            .filter(|delta| delta.status() == git2::Delta::Deleted)
+            .filter_map(|delta| Some(delta.old_file().path()?.to_string_lossy().to_string()))
+            .collect()
+    }
+
+    /// Collect the old path => new path mapping of renamed files
+    fn collect_renamed_paths(diff: &git2::Diff) -> HashMap<String, String> {
+        diff.deltas()
+            .filter(|delta| delta.status() == git2::Delta::Renamed)
+            .filter_map(|delta| {
+                let old_path = delta.old_file().path()?.to_string_lossy().to_string();
+                let new_path = delta.new_file().path()?.to_string_lossy().to_string();
+                Some((old_path, new_path))
+            })
+            .collect()
+    }
+
+    /// Collect descriptions of metadata-only changes (path => description).
+    ///
+    /// - Symlinks whose target changed are described as `symlink target changed: old → new`.
+    /// - Files without content changes whose mode changed are described as
+    ///   `mode changed 100644 → 100755`.
+    /// - Files renamed without content changes are described as `renamed from old/path`.
+    /// - Binary files are described as `binary file changed`, images with their dimensions and
+    ///   sizes (see [`Self::describe_binary_change`]).
+    fn collect_metadata_changes(
+        repo: &git2::Repository,
+        diff: &git2::Diff,
+        files_hunks: &HashMap<String, Vec<Hunk>>,
+    ) -> color_eyre::Result<HashMap<String, String>> {
+        let mut metadata_changes = HashMap::new();
+
+        for delta in diff.deltas() {
+            let Some(path) = delta.new_file().path().or(delta.old_file().path()) else {
+                continue;
+            };
+            let path = path.to_string_lossy().to_string();
+
+            if delta.status() == git2::Delta::Renamed
+                && files_hunks.get(&path).is_none_or(Vec::is_empty)
+                && let Some(old_path) = delta.old_file().path()
+            {
+                metadata_changes
+                    .insert(path, format!("renamed from {}", old_path.to_string_lossy()));
+                continue;
+            }
+            if delta.flags().is_binary() {
+                metadata_changes.insert(path, Self::describe_binary_change(repo, &delta));
+                continue;
+            }
+            if delta.status() != git2::Delta::Modified {
+                continue;
+            }
+            let old_mode = delta.old_file().mode();
+            let new_mode = delta.new_file().mode();
+
+            if old_mode == git2::FileMode::Link && new_mode == git2::FileMode::Link {
+                let old_target = Self::read_blob_lossy(repo, delta.old_file().id())?;
+                let new_target = Self::read_blob_lossy(repo, delta.new_file().id())?;
+                metadata_changes.insert(
+                    path,
+                    format!("symlink target changed: {old_target} → {new_target}"),
+                );
+            } else if old_mode != new_mode && files_hunks.get(&path).is_none_or(Vec::is_empty) {
+                metadata_changes.insert(
+                    path,
+                    format!(
+                        "mode changed {:o} → {:o}",
+                        u32::from(old_mode),
+                        u32::from(new_mode)
+                    ),
+                );
+            }
+        }
+
+        Ok(metadata_changes)
+    }
+
+    /// Describe the change of a binary file. With the `image-diff` feature, PNG and JPEG images
+    /// are described with their dimensions and sizes, e.g.
+    /// `image: 800x600 (45 KiB) → 1024x768 (80 KiB)`.
+    #[cfg_attr(not(feature = "image-diff"), allow(unused_variables))]
+    fn describe_binary_change(repo: &git2::Repository, delta: &git2::DiffDelta) -> String {
+        #[cfg(feature = "image-diff")]
+        if let Some(description) = Self::describe_image_change(repo, delta) {
+            return description;
+        }
+        BINARY_FILE_CHANGED.to_string()
+    }
+
+    /// Describe the change of an image from the headers of its old and new blob, `None` when
+    /// a blob can't be read or is not a PNG or JPEG image
+    #[cfg(feature = "image-diff")]
+    fn describe_image_change(repo: &git2::Repository, delta: &git2::DiffDelta) -> Option<String> {
+        // Added and deleted files have a zero id on their missing side
+        let read_blob = |file: git2::DiffFile| -> Option<Option<Vec<u8>>> {
+            if file.id().is_zero() {
+                return Some(None);
+            }
+            Some(Some(repo.find_blob(file.id()).ok()?.content().to_vec()))
+        };
+        let old = read_blob(delta.old_file())?;
+        let new = read_blob(delta.new_file())?;
+        crate::image_header::describe_change(old.as_deref(), new.as_deref())
+    }
+
+    /// Read the content of a blob as (lossy) UTF-8 string
+    fn read_blob_lossy(repo: &git2::Repository, oid: git2::Oid) -> color_eyre::Result<String> {
+        let blob = repo.find_blob(oid)?;
+        Ok(String::from_utf8_lossy(blob.content()).to_string())
     }
 
     /// Send loading event to start the actual loading process
@@ -238,21 +855,154 @@ impl GitService {
     }
 
     /// Send loading event to start the diff loading process
-    fn handle_git_diff_load(base_sha: &Arc<str>, target_sha: &Arc<str>, events: &mut EventHandler) {
+    fn handle_git_diff_load(
+        base_sha: &Arc<str>,
+        target_sha: &Arc<str>,
+        pathspec: &Option<Arc<str>>,
+        events: &mut EventHandler,
+    ) {
         events.send(AppEvent::GitDiffLoading {
             base_sha: Arc::clone(base_sha),
             target_sha: Arc::clone(target_sha),
+            pathspec: pathspec.clone(),
+        });
+        events.send(AppEvent::GitDiffLoadingState {
+            base_sha: Arc::clone(base_sha),
+            target_sha: Arc::clone(target_sha),
+            state: GitDiffLoadingState::Loading,
+        });
+    }
+
+    /// Parse the patch file of a review into its diff
+    fn handle_patch_diff_load(patch_path: &Arc<str>, events: &mut EventHandler) {
+        let state = match read_patch_file(Path::new(patch_path.as_ref())) {
+            Ok(diff) => GitDiffLoadingState::Loaded(Arc::new(diff)),
+            Err(error) => GitDiffLoadingState::Error(error.to_string().into()),
+        };
+        events.send(AppEvent::PatchDiffLoadingState {
+            patch_path: Arc::clone(patch_path),
+            state,
         });
-        events.send(AppEvent::GitDiffLoadingState(GitDiffLoadingState::Loading));
+    }
+
+    /// Build the compare URL and request copying it to the clipboard
+    fn handle_compare_url_copy(
+        repo_path: &str,
+        base: &str,
+        target: &str,
+        events: &mut EventHandler,
+    ) {
+        match Self::remote_compare_url(repo_path, base, target) {
+            Ok(Some(url)) => events.send(AppEvent::ClipboardCopy(Arc::from(url))),
+            Ok(None) => events.send(AppEvent::CompareUrlError(Arc::from(
+                "No GitHub/GitLab compatible origin remote found",
+            ))),
+            Err(error) => events.send(AppEvent::CompareUrlError(Arc::from(format!(
+                "Failed to build compare URL: {error}"
+            )))),
+        }
+    }
+
+    /// Read the requested context lines and send them back to the view
+    fn handle_diff_context_expand(
+        repo_path: &str,
+        request: &Arc<DiffContextRequest>,
+        events: &mut EventHandler,
+    ) {
+        match Self::get_file_lines(
+            repo_path,
+            &request.sha,
+            &request.file_path,
+            request.start_line,
+            request.end_line,
+        ) {
+            Ok(lines) => events.send(AppEvent::DiffContextExpanded {
+                request: Arc::clone(request),
+                lines: lines.into(),
+            }),
+            Err(error) => events.send(AppEvent::DiffContextExpandError(Arc::from(format!(
+                "Failed to expand context: {error}"
+            )))),
+        }
+    }
+
+    /// Blame the hunks of a file and send the summaries back to the view
+    fn handle_hunk_blame_load(
+        repo_path: &str,
+        request: &Arc<HunkBlameRequest>,
+        events: &mut EventHandler,
+    ) {
+        match Self::hunk_blame_summaries(
+            repo_path,
+            &request.base_sha,
+            &request.old_path,
+            &request.hunks,
+        ) {
+            Ok(summaries) => events.send(AppEvent::HunkBlameLoaded {
+                request: Arc::clone(request),
+                summaries: Arc::new(
+                    summaries
+                        .into_iter()
+                        .map(|(line_index, summary)| (line_index, Arc::from(summary)))
+                        .collect(),
+                ),
+            }),
+            Err(error) => events.send(AppEvent::HunkBlameLoadError(Arc::from(format!(
+                "Failed to blame {}: {error}",
+                request.old_path
+            )))),
+        }
+    }
+
+    /// List the files of the tree at a SHA and send them back to the view
+    fn handle_tree_files_load(repo_path: &str, sha: &Arc<str>, events: &mut EventHandler) {
+        match Self::list_tree_files(repo_path, sha) {
+            Ok(paths) => events.send(AppEvent::TreeFilesLoaded {
+                sha: Arc::clone(sha),
+                paths: paths.into(),
+            }),
+            Err(error) => events.send(AppEvent::TreeFilesLoadError(Arc::from(format!(
+                "Failed to list the files of {sha}: {error}"
+            )))),
+        }
+    }
+
+    /// Compute the diff preview of two branches and send it back to the view
+    fn handle_diff_preview_load(
+        repo_path: &str,
+        base_branch: &str,
+        target_branch: &str,
+        events: &mut EventHandler,
+    ) {
+        match Self::get_diff_preview(repo_path, base_branch, target_branch) {
+            Ok(preview) => events.send(AppEvent::DiffPreviewLoaded(Arc::new(preview))),
+            Err(error) => events.send(AppEvent::DiffPreviewError(Arc::from(format!(
+                "Failed to preview diff: {error}"
+            )))),
+        }
     }
 
     /// Actually load Git branches from repository
     async fn handle_git_branches_loading(repo_path: &str, events: &mut EventHandler) {
+        if let Ok(false) = Self::has_commits(repo_path) {
+            events.send(AppEvent::GitBranchesLoadingState(
+                GitBranchesLoadingState::NoCommits,
+            ));
+            return;
+        }
+
         match Self::get_branches(repo_path) {
             Ok(branches) => {
                 events.send(AppEvent::GitBranchesLoadingState(
                     GitBranchesLoadingState::Loaded(branches),
                 ));
+                let since_last_tag = Self::since_last_tag(repo_path).unwrap_or_else(|error| {
+                    log::warn!("Failed to find the last tag: {error}");
+                    None
+                });
+                events.send(AppEvent::GitSinceLastTagLoaded(
+                    since_last_tag.map(Arc::new),
+                ));
             }
             Err(error) => {
                 events.send(AppEvent::GitBranchesLoadingState(
@@ -262,25 +1012,50 @@ impl GitService {
         }
     }
 
+    /// Number of parsed files between two progress events, so that at most
+    /// `DIFF_PROGRESS_EVENTS` are sent for a diff
+    fn diff_progress_step(files_total: usize) -> usize {
+        files_total.div_ceil(DIFF_PROGRESS_EVENTS).max(1)
+    }
+
     /// Actually load Git diff from repository
     async fn handle_git_diff_loading(
         repo_path: &str,
         base_sha: &Arc<str>,
         target_sha: &Arc<str>,
+        pathspec: Option<&str>,
         events: &mut EventHandler,
     ) {
-        match Self::get_diff_between_shas(repo_path, base_sha, target_sha) {
-            Ok(diff) => {
-                events.send(AppEvent::GitDiffLoadingState(GitDiffLoadingState::Loaded(
-                    Arc::new(diff),
-                )));
+        let on_progress = |files_done: usize, files_total: usize| {
+            if files_done.is_multiple_of(Self::diff_progress_step(files_total))
+                || files_done == files_total
+            {
+                events.send(AppEvent::GitDiffProgress {
+                    base_sha: Arc::clone(base_sha),
+                    target_sha: Arc::clone(target_sha),
+                    files_done,
+                    files_total,
+                });
             }
+        };
+        let diff = Self::get_diff_between_shas_with_progress(
+            repo_path,
+            base_sha,
+            target_sha,
+            pathspec,
+            on_progress,
+        );
+        let state = match diff {
+            Ok(diff) => GitDiffLoadingState::Loaded(Arc::new(diff)),
             Err(error) => {
-                events.send(AppEvent::GitDiffLoadingState(GitDiffLoadingState::Error(
-                    format!("Error generating diff: {error}").into(),
-                )));
+                GitDiffLoadingState::Error(format!("Error generating diff: {error}").into())
             }
-        }
+        };
+        events.send(AppEvent::GitDiffLoadingState {
+            base_sha: Arc::clone(base_sha),
+            target_sha: Arc::clone(target_sha),
+            state,
+        });
     }
 }
 
@@ -300,21 +1075,50 @@ impl ServiceHandler for GitService {
                 AppEvent::GitDiffLoad {
                     base_sha,
                     target_sha,
+                    pathspec,
                 } => {
-                    Self::handle_git_diff_load(base_sha, target_sha, context.events);
+                    Self::handle_git_diff_load(base_sha, target_sha, pathspec, context.events);
                 }
                 AppEvent::GitDiffLoading {
                     base_sha,
                     target_sha,
+                    pathspec,
                 } => {
                     Self::handle_git_diff_loading(
                         context.repo_path,
                         base_sha,
                         target_sha,
+                        pathspec.as_deref(),
                         context.events,
                     )
                     .await;
                 }
+                AppEvent::PatchDiffLoad(patch_path) => {
+                    Self::handle_patch_diff_load(patch_path, context.events);
+                }
+                AppEvent::CompareUrlCopy { base, target } => {
+                    Self::handle_compare_url_copy(context.repo_path, base, target, context.events);
+                }
+                AppEvent::DiffContextExpand(request) => {
+                    Self::handle_diff_context_expand(context.repo_path, request, context.events);
+                }
+                AppEvent::HunkBlameLoad(request) => {
+                    Self::handle_hunk_blame_load(context.repo_path, request, context.events);
+                }
+                AppEvent::TreeFilesLoad(sha) => {
+                    Self::handle_tree_files_load(context.repo_path, sha, context.events);
+                }
+                AppEvent::DiffPreviewLoad {
+                    base_branch,
+                    target_branch,
+                } => {
+                    Self::handle_diff_preview_load(
+                        context.repo_path,
+                        base_branch,
+                        target_branch,
+                        context.events,
+                    );
+                }
                 _ => {
                     // Other events are ignored
                 }
@@ -397,6 +1201,104 @@ mod tests {
         assert_eq!(branches, Arc::from(sorted_branches));
     }
 
+    #[test]
+    fn test_get_branches_from_subdirectory() {
+        let temp_dir = create_test_git_repo().unwrap();
+        let subdirectory = temp_dir.path().join("src").join("nested");
+        fs::create_dir_all(&subdirectory).unwrap();
+
+        let branches = GitService::get_branches(&subdirectory).unwrap();
+        assert_eq!(branches, GitService::get_branches(temp_dir.path()).unwrap());
+
+        let root = GitService::discover_repo_root(&subdirectory).unwrap();
+        assert_eq!(
+            fs::canonicalize(root).unwrap(),
+            fs::canonicalize(temp_dir.path()).unwrap()
+        );
+        assert_eq!(GitService::discover_repo_root("/nonexistent/path"), None);
+    }
+
+    #[test]
+    fn test_previous_tag() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let mut commits: Vec<git2::Commit> = vec![];
+        for message in ["First", "Release", "Fix", "Feature"] {
+            let parents: Vec<&git2::Commit> = commits.last().into_iter().collect();
+            let commit_id = repo
+                .commit(
+                    Some("HEAD"),
+                    &signature,
+                    &signature,
+                    message,
+                    &tree,
+                    &parents,
+                )
+                .unwrap();
+            commits.push(repo.find_commit(commit_id).unwrap());
+        }
+        let sha = |index: usize| commits[index].id().to_string();
+
+        assert_eq!(
+            GitService::previous_tag(temp_dir.path(), &sha(3)).unwrap(),
+            None
+        );
+        assert_eq!(GitService::since_last_tag(temp_dir.path()).unwrap(), None);
+
+        repo.tag_lightweight("v0.9", commits[0].as_object(), false)
+            .unwrap();
+        repo.tag(
+            "v1.0",
+            commits[1].as_object(),
+            &signature,
+            "Release 1.0",
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            GitService::previous_tag(temp_dir.path(), &sha(3)).unwrap(),
+            Some("v1.0".to_string())
+        );
+        // The tag of the commit itself doesn't count
+        assert_eq!(
+            GitService::previous_tag(temp_dir.path(), &sha(1)).unwrap(),
+            Some("v0.9".to_string())
+        );
+        assert_eq!(
+            GitService::previous_tag(temp_dir.path(), &sha(0)).unwrap(),
+            None
+        );
+
+        let head_branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        assert_eq!(
+            GitService::since_last_tag(temp_dir.path()).unwrap(),
+            Some(SinceLastTag {
+                tag: "v1.0".to_string(),
+                head_branch: head_branch.clone(),
+            })
+        );
+        assert_eq!(
+            GitService::get_branch_or_tag_sha(temp_dir.path(), "v1.0").unwrap(),
+            Some(sha(1))
+        );
+        assert_eq!(
+            GitService::get_branch_or_tag_sha(temp_dir.path(), &head_branch).unwrap(),
+            Some(sha(3))
+        );
+        assert_eq!(
+            GitService::get_tags(temp_dir.path()).unwrap().as_ref(),
+            ["v0.9".to_string(), "v1.0".to_string()]
+        );
+
+        // A detached HEAD has no branch to review
+        repo.set_head_detached(commits[3].id()).unwrap();
+        assert_eq!(GitService::since_last_tag(temp_dir.path()).unwrap(), None);
+    }
+
     #[test]
     fn test_get_branches_nonexistent_repo() {
         let result = GitService::get_branches("/nonexistent/path");
@@ -462,12 +1364,580 @@ mod tests {
     }
 
     #[test]
-    fn test_get_diff_with_actual_changes() {
+    fn test_list_tree_files() {
         let temp_dir = TempDir::new().unwrap();
-        let repo_path = temp_dir.path();
-
-        // Initialize git repo
-        let repo = git2::Repository::init(repo_path).unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let src_tree_id = {
+            let mut builder = repo.treebuilder(None).unwrap();
+            let blob_id = repo.blob(b"fn main() {}\n").unwrap();
+            builder.insert("main.rs", blob_id, 0o100644).unwrap();
+            builder.write().unwrap()
+        };
+        let tree_id = {
+            let mut builder = repo.treebuilder(None).unwrap();
+            let blob_id = repo.blob(b"# Test\n").unwrap();
+            builder.insert("README.md", blob_id, 0o100644).unwrap();
+            builder.insert("src", src_tree_id, 0o040000).unwrap();
+            builder.write().unwrap()
+        };
+        let tree = repo.find_tree(tree_id).unwrap();
+        let sha = repo
+            .commit(None, &signature, &signature, "Commit", &tree, &[])
+            .unwrap()
+            .to_string();
+
+        let paths = GitService::list_tree_files(temp_dir.path(), &sha).unwrap();
+
+        assert_eq!(paths, vec!["README.md", "src/main.rs"]);
+        assert!(GitService::list_tree_files(temp_dir.path(), "not-a-sha").is_err());
+    }
+
+    #[test]
+    fn test_linked_worktree() {
+        let temp_dir = create_test_git_repo().unwrap();
+        let repo = git2::Repository::open(temp_dir.path()).unwrap();
+        let base_sha = repo.head().unwrap().target().unwrap().to_string();
+
+        // Check out a new branch in a linked worktree and commit on it there
+        let worktree_dir = TempDir::new().unwrap();
+        let worktree_path = worktree_dir.path().join("worktree");
+        let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        let branch = repo
+            .branch("feature/worktree", &head_commit, false)
+            .unwrap();
+        let mut options = git2::WorktreeAddOptions::new();
+        options.reference(Some(branch.get()));
+        repo.worktree("feature-worktree", &worktree_path, Some(&options))
+            .unwrap();
+        let worktree_repo = git2::Repository::open(&worktree_path).unwrap();
+        assert!(worktree_repo.is_worktree());
+        let target_oid = commit_tree_entries(
+            &worktree_repo,
+            &[
+                ("README.md", b"# Test Repository", 0o100644),
+                ("worktree.txt", b"from the worktree\n", 0o100644),
+            ],
+            Some(head_commit.id()),
+        );
+        worktree_repo
+            .reference(
+                "refs/heads/feature/worktree",
+                target_oid,
+                true,
+                "commit in worktree",
+            )
+            .unwrap();
+
+        // Branches are shared through the common git dir, from both working directories
+        let branches = GitService::get_branches(&worktree_path).unwrap();
+        assert!(branches.contains(&"develop".to_string()));
+        assert!(branches.contains(&"feature/worktree".to_string()));
+        let target_sha = target_oid.to_string();
+        for repo_path in [temp_dir.path(), worktree_path.as_path()] {
+            assert_eq!(
+                GitService::get_branch_sha(repo_path, "feature/worktree").unwrap(),
+                Some(target_sha.clone())
+            );
+        }
+
+        let diff =
+            GitService::get_diff_between_shas(&worktree_path, &base_sha, &target_sha).unwrap();
+        assert_eq!(diff.file_count(), 1);
+        assert_eq!(diff.files[0].path, "worktree.txt");
+    }
+
+    /// Commit a flat tree with the given (path, content, file mode) entries on top of `parent`
+    fn commit_tree_entries(
+        repo: &git2::Repository,
+        entries: &[(&str, &[u8], i32)],
+        parent: Option<git2::Oid>,
+    ) -> git2::Oid {
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let mut tree_builder = repo.treebuilder(None).unwrap();
+        for (path, content, mode) in entries {
+            let blob_id = repo.blob(content).unwrap();
+            tree_builder.insert(path, blob_id, *mode).unwrap();
+        }
+        let tree = repo.find_tree(tree_builder.write().unwrap()).unwrap();
+        let parents: Vec<git2::Commit> = parent
+            .map(|oid| repo.find_commit(oid).unwrap())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(None, &signature, &signature, "Commit", &tree, &parent_refs)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_get_file_lines_near_top_and_bottom() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let content: String = (1..=30).map(|line| format!("line {line}\n")).collect();
+        let sha = commit_tree_entries(&repo, &[("file.txt", content.as_bytes(), 0o100644)], None)
+            .to_string();
+
+        // Near the top of the file the start is clamped to the first line
+        let lines = GitService::get_file_lines(temp_dir.path(), &sha, "file.txt", 0, 3).unwrap();
+        assert_eq!(lines, ["line 1", "line 2", "line 3"]);
+
+        let lines = GitService::get_file_lines(temp_dir.path(), &sha, "file.txt", 10, 11).unwrap();
+        assert_eq!(lines, ["line 10", "line 11"]);
+
+        // Near the bottom of the file lines past the end are omitted
+        let lines = GitService::get_file_lines(temp_dir.path(), &sha, "file.txt", 28, 37).unwrap();
+        assert_eq!(lines, ["line 28", "line 29", "line 30"]);
+
+        let lines = GitService::get_file_lines(temp_dir.path(), &sha, "file.txt", 31, 40).unwrap();
+        assert!(lines.is_empty());
+
+        assert!(GitService::get_file_lines(temp_dir.path(), &sha, "missing.txt", 1, 3).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handle_diff_context_expand() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let sha = commit_tree_entries(&repo, &[("file.txt", b"a\nb\nc\n", 0o100644)], None);
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let mut events = EventHandler::new_for_test();
+        let request = Arc::new(DiffContextRequest {
+            sha: Arc::from(sha.to_string()),
+            file_path: Arc::from("file.txt"),
+            hunk_line_index: 0,
+            direction: DiffContextDirection::Below,
+            start_line: 2,
+            end_line: 11,
+        });
+
+        GitService::handle_diff_context_expand(&repo_path, &request, &mut events);
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::DiffContextExpanded {
+                request: expanded_request,
+                lines,
+            }) => {
+                assert_eq!(expanded_request, &request);
+                assert_eq!(lines.as_ref(), ["b", "c"]);
+            }
+            _ => panic!("Expected DiffContextExpanded event, got: {event:?}"),
+        }
+    }
+
+    #[test]
+    fn test_get_diff_mode_only_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+
+        let base = commit_tree_entries(&repo, &[("script.sh", b"echo hi\n", 0o100644)], None);
+        let target =
+            commit_tree_entries(&repo, &[("script.sh", b"echo hi\n", 0o100755)], Some(base));
+
+        let diff = GitService::get_diff_between_shas(
+            temp_dir.path(),
+            &base.to_string(),
+            &target.to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(diff.file_count(), 1);
+        assert_eq!(diff.files[0].path, "script.sh");
+        assert_eq!(diff.files[0].content(), "mode changed 100644 → 100755");
+        assert!(diff.files[0].metadata_only());
+    }
+
+    #[test]
+    fn test_get_diff_mode_and_content_change_keeps_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+
+        let base = commit_tree_entries(&repo, &[("script.sh", b"echo hi\n", 0o100644)], None);
+        let target =
+            commit_tree_entries(&repo, &[("script.sh", b"echo bye\n", 0o100755)], Some(base));
+
+        let diff = GitService::get_diff_between_shas(
+            temp_dir.path(),
+            &base.to_string(),
+            &target.to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(diff.file_count(), 1);
+        assert!(!diff.files[0].metadata_only());
+        assert!(diff.files[0].content().contains("-echo hi"));
+        assert!(diff.files[0].content().contains("+echo bye"));
+    }
+
+    #[test]
+    fn test_get_diff_symlink_target_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+
+        let base = commit_tree_entries(&repo, &[("link", b"old/target", 0o120000)], None);
+        let target = commit_tree_entries(&repo, &[("link", b"new/target", 0o120000)], Some(base));
+
+        let diff = GitService::get_diff_between_shas(
+            temp_dir.path(),
+            &base.to_string(),
+            &target.to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(diff.file_count(), 1);
+        assert_eq!(diff.files[0].path, "link");
+        assert_eq!(
+            diff.files[0].content(),
+            "symlink target changed: old/target → new/target"
+        );
+        assert!(diff.files[0].metadata_only());
+    }
+
+    #[test]
+    fn test_get_diff_renamed_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let content: String = (1..=20).map(|line| format!("line {line}\n")).collect();
+        let changed_content = content.replace("line 20", "line twenty");
+
+        let base = commit_tree_entries(
+            &repo,
+            &[
+                ("old.txt", content.as_bytes(), 0o100644),
+                ("moved.txt", b"unchanged\n", 0o100644),
+            ],
+            None,
+        );
+        let target = commit_tree_entries(
+            &repo,
+            &[
+                ("new.txt", changed_content.as_bytes(), 0o100644),
+                ("moved_to.txt", b"unchanged\n", 0o100644),
+            ],
+            Some(base),
+        );
+
+        let diff = GitService::get_diff_between_shas(
+            temp_dir.path(),
+            &base.to_string(),
+            &target.to_string(),
+        )
+        .unwrap();
+
+        let paths: Vec<_> = diff.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, ["moved_to.txt", "new.txt"]);
+        assert_eq!(diff.files[0].content(), "renamed from moved.txt");
+        assert!(diff.files[0].metadata_only());
+        assert!(diff.files[1].content().contains("-line 20"));
+        assert!(diff.files[1].content().contains("+line twenty"));
+        assert_eq!(diff.old_path("new.txt"), Some("old.txt"));
+        assert_eq!(diff.old_path("moved_to.txt"), Some("moved.txt"));
+    }
+
+    #[test]
+    fn test_get_diff_binary_file_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+
+        let base = commit_tree_entries(&repo, &[("data.bin", b"\x00\x01", 0o100644)], None);
+        let target = commit_tree_entries(&repo, &[("data.bin", b"\x00\x02", 0o100644)], Some(base));
+
+        let diff = GitService::get_diff_between_shas(
+            temp_dir.path(),
+            &base.to_string(),
+            &target.to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(diff.file_count(), 1);
+        assert_eq!(diff.files[0].content(), "binary file changed");
+        assert!(diff.files[0].metadata_only());
+    }
+
+    #[cfg(feature = "image-diff")]
+    #[test]
+    fn test_get_diff_image_change() {
+        let png = |width: u32, height: u32| -> Vec<u8> {
+            let mut data = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+            data.extend_from_slice(&width.to_be_bytes());
+            data.extend_from_slice(&height.to_be_bytes());
+            data
+        };
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+
+        let base = commit_tree_entries(&repo, &[("logo.png", &png(800, 600), 0o100644)], None);
+        let target = commit_tree_entries(
+            &repo,
+            &[
+                ("logo.png", &png(1024, 768), 0o100644),
+                ("new.png", &png(16, 16), 0o100644),
+            ],
+            Some(base),
+        );
+
+        let diff = GitService::get_diff_between_shas(
+            temp_dir.path(),
+            &base.to_string(),
+            &target.to_string(),
+        )
+        .unwrap();
+
+        let contents: Vec<_> = diff.files.iter().map(|file| file.content()).collect();
+        assert_eq!(
+            contents,
+            [
+                "image: 800x600 (24 B) → 1024x768 (24 B)",
+                "image: none → 16x16 (24 B)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_diff_deleted_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+
+        let base = commit_tree_entries(
+            &repo,
+            &[
+                ("deleted.txt", b"first\nsecond\n", 0o100644),
+                ("empty.txt", b"", 0o100644),
+                ("kept.txt", b"kept\n", 0o100644),
+            ],
+            None,
+        );
+        let target = commit_tree_entries(&repo, &[("kept.txt", b"kept\n", 0o100644)], Some(base));
+
+        let diff = GitService::get_diff_between_shas(
+            temp_dir.path(),
+            &base.to_string(),
+            &target.to_string(),
+        )
+        .unwrap();
+
+        let paths: Vec<_> = diff.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, ["deleted.txt", "empty.txt"]);
+        assert_eq!(
+            diff.files[0].content(),
+            "@@ -1,2 +0,0 @@\n-first\n-second\n"
+        );
+        // A deleted empty file has no diff lines at all
+        assert_eq!(diff.files[1].content(), "");
+        assert!(diff.is_deleted("deleted.txt"));
+        assert!(diff.is_deleted("empty.txt"));
+        assert!(!diff.is_deleted("kept.txt"));
+    }
+
+    #[test]
+    fn test_get_diff_hunks() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let content: String = (1..=20).map(|line| format!("line {line}\n")).collect();
+        let changed_content = content
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "line 18\nline 18.5\n");
+
+        let base = commit_tree_entries(&repo, &[("file.txt", content.as_bytes(), 0o100644)], None);
+        let target = commit_tree_entries(
+            &repo,
+            &[("file.txt", changed_content.as_bytes(), 0o100644)],
+            Some(base),
+        );
+
+        let diff = GitService::get_diff_between_shas(
+            temp_dir.path(),
+            &base.to_string(),
+            &target.to_string(),
+        )
+        .unwrap();
+
+        let hunks = diff.files[0].hunks();
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].header, "@@ -1,5 +1,5 @@\n");
+        assert_eq!(
+            hunks[0].lines[..3],
+            [
+                DiffLine {
+                    origin: ' ',
+                    content: "line 1\n".to_string(),
+                    old_lineno: Some(1),
+                    new_lineno: Some(1),
+                },
+                DiffLine {
+                    origin: '-',
+                    content: "line 2\n".to_string(),
+                    old_lineno: Some(2),
+                    new_lineno: None,
+                },
+                DiffLine {
+                    origin: '+',
+                    content: "line two\n".to_string(),
+                    old_lineno: None,
+                    new_lineno: Some(2),
+                },
+            ]
+        );
+        assert_eq!(hunks[1].header, "@@ -16,5 +16,6 @@ line 15\n");
+        assert_eq!(hunks[1].lines[3].content, "line 18.5\n");
+        assert_eq!(hunks[1].lines[3].new_lineno, Some(19));
+        assert_eq!(hunks[1].lines[4].old_lineno, Some(19));
+        assert_eq!(hunks[1].lines[4].new_lineno, Some(20));
+        assert_eq!(
+            diff.files[0].content(),
+            format!("{}{}", hunks[0].text(), hunks[1].text())
+        );
+        assert!(
+            diff.files[0]
+                .content()
+                .starts_with("@@ -1,5 +1,5 @@\n line 1\n-line 2\n")
+        );
+    }
+
+    #[test]
+    fn test_get_diff_trailing_newline_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let base = commit_tree_entries(
+            &repo,
+            &[
+                ("gains.txt", b"one\ntwo", 0o100644),
+                ("loses.txt", b"one\ntwo\n", 0o100644),
+            ],
+            None,
+        );
+        let target = commit_tree_entries(
+            &repo,
+            &[
+                ("gains.txt", b"one\ntwo\n", 0o100644),
+                ("loses.txt", b"one\ntwo", 0o100644),
+            ],
+            Some(base),
+        );
+
+        let diff = GitService::get_diff_between_shas(
+            temp_dir.path(),
+            &base.to_string(),
+            &target.to_string(),
+        )
+        .unwrap();
+
+        let gains = &diff.files[0];
+        assert_eq!(gains.path, "gains.txt");
+        assert_eq!(
+            gains.content(),
+            "@@ -1,2 +1,2 @@\n one\n-two\n\\ No newline at end of file\n+two\n"
+        );
+        assert_eq!(gains.hunks()[0].lines[2], DiffLine::no_newline_marker());
+        assert_eq!(gains.hunks()[0].lines[1].content, "two\n");
+        assert_eq!(gains.line_stats(), (1, 1));
+
+        let loses = &diff.files[1];
+        assert_eq!(loses.path, "loses.txt");
+        assert_eq!(
+            loses.content(),
+            "@@ -1,2 +1,2 @@\n one\n-two\n+two\n\\ No newline at end of file\n"
+        );
+        assert_eq!(loses.hunks()[0].lines[3], DiffLine::no_newline_marker());
+        // The marker has no line in the new file
+        assert_eq!(
+            loses.new_line_numbers(),
+            [None, Some(1), None, Some(2), None]
+        );
+        assert_eq!(Hunk::parse_content(loses.content()), loses.hunks());
+    }
+
+    #[test]
+    fn test_get_diff_leaves_out_excluded_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(
+            temp_dir
+                .path()
+                .join(crate::exclude_patterns::EXCLUDE_FILE_NAME),
+            "*.lock\n*.min.js\n",
+        )
+        .unwrap();
+        let base = commit_tree_entries(&repo, &[("main.rs", b"one\n", 0o100644)], None);
+        let target = commit_tree_entries(
+            &repo,
+            &[
+                ("Cargo.lock", b"lock\n", 0o100644),
+                ("main.rs", b"two\n", 0o100644),
+                ("vendor.min.js", b"lib\n", 0o100644),
+            ],
+            Some(base),
+        );
+
+        let diff = GitService::get_diff_between_shas(
+            temp_dir.path(),
+            &base.to_string(),
+            &target.to_string(),
+        )
+        .unwrap();
+
+        let paths = |files: &[DiffFile]| -> Vec<String> {
+            files.iter().map(|file| file.path.clone()).collect()
+        };
+        assert_eq!(paths(&diff.files), vec!["main.rs"]);
+        assert_eq!(diff.file_count(), 1);
+        assert_eq!(
+            paths(&diff.excluded_files),
+            vec!["Cargo.lock", "vendor.min.js"]
+        );
+    }
+
+    #[test]
+    fn test_count_diff_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(
+            temp_dir
+                .path()
+                .join(crate::exclude_patterns::EXCLUDE_FILE_NAME),
+            "*.lock\n",
+        )
+        .unwrap();
+        let base = commit_tree_entries(
+            &repo,
+            &[
+                ("old_name.rs", b"fn main() {}\n", 0o100644),
+                ("removed.rs", b"gone\n", 0o100644),
+            ],
+            None,
+        );
+        let target = commit_tree_entries(
+            &repo,
+            &[
+                ("Cargo.lock", b"lock\n", 0o100644),
+                ("added.rs", b"new\n", 0o100644),
+                ("new_name.rs", b"fn main() {}\n", 0o100644),
+            ],
+            Some(base),
+        );
+        let (base, target) = (base.to_string(), target.to_string());
+
+        // The renamed file counts once, the excluded lockfile not at all
+        assert_eq!(
+            GitService::count_diff_files(temp_dir.path(), &base, &target).unwrap(),
+            3
+        );
+        assert_eq!(
+            GitService::get_diff_between_shas(temp_dir.path(), &base, &target)
+                .unwrap()
+                .file_count(),
+            3
+        );
+        assert!(GitService::count_diff_files(temp_dir.path(), "abc123", &target).is_err());
+    }
+
+    #[test]
+    fn test_get_diff_with_actual_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        // Initialize git repo
+        let repo = git2::Repository::init(repo_path).unwrap();
         let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
 
         // Create initial commit
@@ -526,8 +1996,61 @@ mod tests {
 
         let file = &diff.files[0];
         assert_eq!(file.path, "file.txt");
-        assert!(file.content.contains("-initial content"));
-        assert!(file.content.contains("+modified content"));
+        assert!(file.content().contains("-initial content"));
+        assert!(file.content().contains("+modified content"));
+    }
+
+    #[test]
+    fn test_get_diff_preview() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let base = commit_tree_entries(&repo, &[("file.txt", b"a\nb\n", 0o100644)], None);
+        let target = commit_tree_entries(
+            &repo,
+            &[
+                ("file.txt", b"a\nc\n", 0o100644),
+                ("new.txt", b"x\ny\n", 0o100644),
+            ],
+            Some(base),
+        );
+        repo.branch("base", &repo.find_commit(base).unwrap(), false)
+            .unwrap();
+        repo.branch("target", &repo.find_commit(target).unwrap(), false)
+            .unwrap();
+
+        let preview = GitService::get_diff_preview(temp_dir.path(), "base", "target").unwrap();
+        assert_eq!(
+            preview,
+            DiffPreview {
+                base_branch: Arc::from("base"),
+                target_branch: Arc::from("target"),
+                file_count: 2,
+                added_lines: 3,
+                removed_lines: 1,
+            }
+        );
+        assert!(!preview.is_empty());
+
+        let preview = GitService::get_diff_preview(temp_dir.path(), "base", "base").unwrap();
+        assert!(preview.is_empty());
+        assert_eq!((preview.added_lines, preview.removed_lines), (0, 0));
+    }
+
+    #[test]
+    fn test_handle_diff_preview_load_missing_branch() {
+        let temp_dir = create_test_git_repo().unwrap();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let mut events = EventHandler::new_for_test();
+
+        GitService::handle_diff_preview_load(&repo_path, "develop", "missing", &mut events);
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::DiffPreviewError(error)) => {
+                assert!(error.contains("Branch missing not found"));
+            }
+            _ => panic!("Expected DiffPreviewError event, got: {event:?}"),
+        }
     }
 
     #[test]
@@ -541,6 +2064,244 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_handle_git_diff_loading_error_is_tagged_with_shas() {
+        let temp_dir = create_test_git_repo().unwrap();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let mut events = EventHandler::new_for_test();
+
+        GitService::handle_git_diff_loading(
+            &repo_path,
+            &Arc::from("invalid_sha"),
+            &Arc::from("another_invalid_sha"),
+            None,
+            &mut events,
+        )
+        .await;
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::GitDiffLoadingState {
+                base_sha,
+                target_sha,
+                state: GitDiffLoadingState::Error(_),
+            }) => {
+                assert_eq!(base_sha.as_ref(), "invalid_sha");
+                assert_eq!(target_sha.as_ref(), "another_invalid_sha");
+            }
+            _ => panic!("Expected GitDiffLoadingState error event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_git_diff_loading_sends_throttled_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let base_sha = commit_tree_entries(&repo, &[], None);
+        let paths: Vec<String> = (0..45)
+            .map(|index| format!("file_{index:02}.txt"))
+            .collect();
+        let entries: Vec<(&str, &[u8], i32)> = paths
+            .iter()
+            .map(|path| (path.as_str(), b"content\n".as_slice(), 0o100644))
+            .collect();
+        let target_sha = commit_tree_entries(&repo, &entries, Some(base_sha));
+        let base_sha: Arc<str> = Arc::from(base_sha.to_string());
+        let target_sha: Arc<str> = Arc::from(target_sha.to_string());
+        let mut events = EventHandler::new_for_test();
+
+        GitService::handle_git_diff_loading(&repo_path, &base_sha, &target_sha, None, &mut events)
+            .await;
+
+        let mut progress = vec![];
+        let mut loaded_file_count = None;
+        while let Some(event) = events.try_recv() {
+            match &*event {
+                Event::App(AppEvent::GitDiffProgress {
+                    base_sha: event_base_sha,
+                    target_sha: event_target_sha,
+                    files_done,
+                    files_total,
+                }) => {
+                    assert_eq!((event_base_sha, event_target_sha), (&base_sha, &target_sha));
+                    assert!(loaded_file_count.is_none());
+                    progress.push((*files_done, *files_total));
+                }
+                Event::App(AppEvent::GitDiffLoadingState {
+                    state: GitDiffLoadingState::Loaded(diff),
+                    ..
+                }) => loaded_file_count = Some(diff.file_count()),
+                _ => panic!("Unexpected event: {event:?}"),
+            }
+        }
+
+        // One event every 3 files to stay below `DIFF_PROGRESS_EVENTS`
+        let expected: Vec<(usize, usize)> = (1..=15).map(|step| (step * 3, 45)).collect();
+        assert_eq!(progress, expected);
+        assert_eq!(loaded_file_count, Some(45));
+    }
+
+    #[test]
+    fn test_diff_progress_step() {
+        assert_eq!(GitService::diff_progress_step(0), 1);
+        assert_eq!(GitService::diff_progress_step(5), 1);
+        assert_eq!(GitService::diff_progress_step(20), 1);
+        assert_eq!(GitService::diff_progress_step(21), 2);
+        assert_eq!(GitService::diff_progress_step(500), 25);
+    }
+
+    #[test]
+    fn test_get_diff_limited_to_pathspec() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let base_sha = commit_tree_entries(&repo, &[], None);
+        let target_sha = commit_tree_entries(
+            &repo,
+            &[
+                ("main.rs", b"fn main() {}\n", 0o100644),
+                ("notes.txt", b"notes\n", 0o100644),
+            ],
+            Some(base_sha),
+        );
+
+        let diff = GitService::get_diff_between_shas_with_progress(
+            temp_dir.path(),
+            &base_sha.to_string(),
+            &target_sha.to_string(),
+            Some("*.rs"),
+            |_, _| {},
+        )
+        .unwrap();
+
+        let paths: Vec<&str> = diff.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, vec!["main.rs"]);
+    }
+
+    #[test]
+    fn test_resolve_ref() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let first_sha = commit_tree_entries(&repo, &[], None);
+        let second_sha =
+            commit_tree_entries(&repo, &[("a.txt", b"a\n", 0o100644)], Some(first_sha));
+        let second_commit = repo.find_commit(second_sha).unwrap();
+        repo.branch("feature", &second_commit, false).unwrap();
+        repo.tag_lightweight("v1", second_commit.as_object(), false)
+            .unwrap();
+
+        let resolve = |revspec| GitService::resolve_ref(temp_dir.path(), revspec).unwrap();
+        assert_eq!(resolve("feature"), second_sha.to_string());
+        assert_eq!(resolve("v1"), second_sha.to_string());
+        assert_eq!(resolve("feature~1"), first_sha.to_string());
+        assert_eq!(
+            resolve(&second_sha.to_string()[..7]),
+            second_sha.to_string()
+        );
+        assert!(GitService::resolve_ref(temp_dir.path(), "nonexistent").is_err());
+    }
+
+    /// Commit a single file as the given author at the given time on top of HEAD
+    fn commit_file_as(
+        repo: &git2::Repository,
+        path: &str,
+        content: &str,
+        author: &str,
+        seconds: i64,
+    ) -> git2::Oid {
+        let signature =
+            git2::Signature::new(author, "author@example.com", &git2::Time::new(seconds, 0))
+                .unwrap();
+        let mut tree_builder = repo.treebuilder(None).unwrap();
+        let blob_id = repo.blob(content.as_bytes()).unwrap();
+        tree_builder.insert(path, blob_id, 0o100644).unwrap();
+        let tree = repo.find_tree(tree_builder.write().unwrap()).unwrap();
+        let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        let message = format!("Change by {author}");
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hunk_blame_summaries() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        commit_file_as(
+            &repo,
+            "a.txt",
+            "one\ntwo\nthree\nfour\n",
+            "Alice",
+            1_700_000_000,
+        );
+        let base_sha = commit_file_as(
+            &repo,
+            "a.txt",
+            "one\nTWO\nthree\nfour\n",
+            "Bob",
+            1_750_000_000,
+        );
+        let hunks = [
+            // Replaces line 1, last touched by Alice
+            DiffHunk::parse(0, "@@ -1 +1 @@").unwrap(),
+            // Replaces lines 2-3, the most recent change is Bob's
+            DiffHunk::parse(3, "@@ -2,2 +2,1 @@").unwrap(),
+            // Only adds lines, so nothing is replaced
+            DiffHunk::parse(7, "@@ -4,0 +4,2 @@").unwrap(),
+        ];
+
+        let summaries = GitService::hunk_blame_summaries(
+            temp_dir.path(),
+            &base_sha.to_string(),
+            "a.txt",
+            &hunks,
+        )
+        .unwrap();
+
+        let short_sha = &base_sha.to_string()[..7];
+        assert_eq!(summaries.len(), 2);
+        assert!(summaries[&0].starts_with("Alice, "));
+        assert!(summaries[&0].ends_with("(2023-11-14): Change by Alice"));
+        assert_eq!(
+            summaries[&3],
+            format!("Bob, {short_sha} (2025-06-15): Change by Bob")
+        );
+    }
+
+    #[test]
+    fn test_hunk_blame_summaries_of_added_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let base_sha = commit_file_as(&repo, "a.txt", "one\n", "Alice", 1_700_000_000);
+
+        // Files that don't exist at the base SHA have no blame
+        let summaries = GitService::hunk_blame_summaries(
+            temp_dir.path(),
+            &base_sha.to_string(),
+            "new.txt",
+            &[DiffHunk::parse(0, "@@ -1 +1 @@").unwrap()],
+        )
+        .unwrap();
+        assert!(summaries.is_empty());
+
+        // Hunks of added files only add lines, so the file is not even looked up
+        let summaries = GitService::hunk_blame_summaries(
+            "/nonexistent/path",
+            "sha",
+            "new.txt",
+            &[DiffHunk::parse(0, "@@ -0,0 +1,3 @@").unwrap()],
+        )
+        .unwrap();
+        assert!(summaries.is_empty());
+    }
+
     #[test]
     fn test_get_diff_nonexistent_repo() {
         let result = GitService::get_diff_between_shas("/nonexistent/path", "sha1", "sha2");
@@ -561,6 +2322,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
         // Handle GitBranchesLoad event
         GitService::handle_app_event(
@@ -607,6 +2371,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: temp_dir.path().to_string_lossy().to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
         // Handle GitBranchesLoading event
         GitService::handle_app_event(
@@ -639,10 +2406,53 @@ mod tests {
             panic!("Expected GitBranchesLoadingState::Loaded event, got: {event:?}");
         }
 
+        // The test repository has no tags
+        let event = events.try_recv().unwrap();
+        assert!(matches!(
+            *event,
+            Event::App(AppEvent::GitSinceLastTagLoaded(None))
+        ));
+
         // No more events should be pending
         assert!(!events.has_pending_events());
     }
 
+    #[test]
+    fn test_has_commits() {
+        let temp_dir = TempDir::new().unwrap();
+        git2::Repository::init(temp_dir.path()).unwrap();
+        assert!(!GitService::has_commits(temp_dir.path()).unwrap());
+        assert!(
+            GitService::get_branches(temp_dir.path())
+                .unwrap()
+                .is_empty()
+        );
+
+        let temp_dir = create_test_git_repo().unwrap();
+        assert!(GitService::has_commits(temp_dir.path()).unwrap());
+
+        assert!(GitService::has_commits("/nonexistent/path").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handle_git_branches_loading_without_commits() {
+        let temp_dir = TempDir::new().unwrap();
+        git2::Repository::init(temp_dir.path()).unwrap();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let mut events = EventHandler::new_for_test();
+
+        GitService::handle_git_branches_loading(&repo_path, &mut events).await;
+
+        let event = events.try_recv().unwrap();
+        assert!(matches!(
+            *event,
+            Event::App(AppEvent::GitBranchesLoadingState(
+                GitBranchesLoadingState::NoCommits
+            ))
+        ));
+        assert!(!events.has_pending_events());
+    }
+
     #[tokio::test]
     async fn test_handle_git_branches_loading_event_error() {
         let temp_dir = tempfile::TempDir::new().unwrap();
@@ -659,6 +2469,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: temp_dir.path().to_string_lossy().to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
         // Handle GitBranchesLoading event
         GitService::handle_app_event(
@@ -783,6 +2596,13 @@ mod tests {
             panic!("Expected GitBranchesLoadingState::Loaded event, got: {event:?}");
         }
 
+        // The test repository has no tags
+        let event = events.try_recv().unwrap();
+        assert!(matches!(
+            *event,
+            Event::App(AppEvent::GitSinceLastTagLoaded(None))
+        ));
+
         // No more events should be pending
         assert!(!events.has_pending_events());
     }
@@ -818,4 +2638,86 @@ mod tests {
         // No more events should be pending
         assert!(!events.has_pending_events());
     }
+
+    #[test]
+    fn test_remote_web_url_normalizes_ssh_and_https() {
+        let cases = [
+            (
+                "git@github.com:owner/repo.git",
+                "https://github.com/owner/repo",
+            ),
+            (
+                "git@gitlab.com:group/sub/repo.git",
+                "https://gitlab.com/group/sub/repo",
+            ),
+            (
+                "ssh://git@github.com/owner/repo.git",
+                "https://github.com/owner/repo",
+            ),
+            (
+                "ssh://git@gitlab.example.com:2222/group/repo.git",
+                "https://gitlab.example.com/group/repo",
+            ),
+            (
+                "https://github.com/owner/repo.git",
+                "https://github.com/owner/repo",
+            ),
+            (
+                "https://user@github.com/owner/repo/",
+                "https://github.com/owner/repo",
+            ),
+            (
+                "git://github.com/owner/repo",
+                "https://github.com/owner/repo",
+            ),
+        ];
+        for (remote_url, expected) in cases {
+            assert_eq!(
+                GitService::remote_web_url(remote_url).as_deref(),
+                Some(expected),
+                "{remote_url}"
+            );
+        }
+
+        assert_eq!(GitService::remote_web_url("/srv/git/repo.git"), None);
+        assert_eq!(GitService::remote_web_url("file:///srv/git/repo.git"), None);
+        assert_eq!(GitService::remote_web_url("../repo"), None);
+    }
+
+    #[test]
+    fn test_remote_compare_url() {
+        let temp_dir = create_test_git_repo().unwrap();
+        let repo = git2::Repository::open(temp_dir.path()).unwrap();
+
+        assert_eq!(
+            GitService::remote_compare_url(temp_dir.path(), "abc", "def").unwrap(),
+            None
+        );
+
+        repo.remote("origin", "git@github.com:owner/repo.git")
+            .unwrap();
+        assert_eq!(
+            GitService::remote_compare_url(temp_dir.path(), "abc", "def").unwrap(),
+            Some("https://github.com/owner/repo/compare/abc...def".to_string())
+        );
+
+        repo.remote_set_url("origin", "git@gitlab.com:group/repo.git")
+            .unwrap();
+        assert_eq!(
+            GitService::remote_compare_url(temp_dir.path(), "abc", "def").unwrap(),
+            Some("https://gitlab.com/group/repo/-/compare/abc...def".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_compare_url_copy_without_remote() {
+        let temp_dir = create_test_git_repo().unwrap();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let mut events = EventHandler::new_for_test();
+
+        GitService::handle_compare_url_copy(&repo_path, "abc", "def", &mut events);
+
+        let event = events.try_recv().unwrap();
+        assert!(matches!(&*event, Event::App(AppEvent::CompareUrlError(_))));
+    }
 }
@@ -3,24 +3,43 @@ use std::{future::Future, pin::Pin};
 use crate::database::Database;
 use crate::event::{AppEvent, EventHandler};
 
+pub mod activity_service;
 pub mod branch_status_service;
+pub mod checklist_service;
 pub mod comment_service;
+pub mod difftool_service;
 pub mod file_view_service;
 pub mod git_service;
 pub mod review_service;
+pub mod session_service;
+pub mod settings_service;
 
+pub use activity_service::ActivityService;
 pub use branch_status_service::BranchStatusService;
+pub use checklist_service::ChecklistService;
 pub use comment_service::CommentService;
 pub use comment_service::CommentsLoadParams;
 pub use comment_service::CommentsLoadingState;
+pub use difftool_service::DifftoolCommand;
+pub use difftool_service::DifftoolService;
 pub use file_view_service::FileViewService;
+pub use git_service::DiffContextDirection;
+pub use git_service::DiffContextRequest;
+pub use git_service::DiffPreview;
 pub use git_service::GitBranchesLoadingState;
 pub use git_service::GitDiffLoadingState;
 pub use git_service::GitService;
+pub use git_service::HunkBlameRequest;
+pub use git_service::SinceLastTag;
+pub use review_service::ReviewComparison;
 pub use review_service::ReviewCreateData;
+pub use review_service::ReviewImportSummary;
 pub use review_service::ReviewLoadingState;
 pub use review_service::ReviewService;
+pub use review_service::ReviewUpdateData;
 pub use review_service::ReviewsLoadingState;
+pub use session_service::SessionService;
+pub use settings_service::SettingsService;
 
 /// Context struct containing the app state that services need access to
 pub struct ServiceContext<'a> {
@@ -1,22 +1,99 @@
-use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    path::Path,
+    pin::Pin,
+    sync::Arc,
+};
 
 use crate::{
     database::Database,
     event::{AppEvent, EventHandler},
-    models::{Comment, FileView, Review, ReviewId},
+    models::{
+        ChecklistItem, Comment, CommentExportEntry, CommentId, Diff, DiffFile, FileView,
+        REVIEW_EXPORT_VERSION, Review, ReviewExport, ReviewExportEntry, ReviewId, ReviewList,
+        ReviewListEntry, ReviewProgress, Settings,
+    },
     services::git_service::GitService,
-    time_provider::TimeProvider,
+    time_provider::{SystemTimeProvider, TimeProvider},
+    unified_diff::read_patch_file,
 };
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use super::{ServiceContext, ServiceHandler};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ReviewCreateData {
     pub base_branch: String,
     pub target_branch: String,
     pub base_sha: Option<String>,
     pub target_sha: Option<String>,
+    /// Comma-separated names of who should look at the review
+    pub assignees: String,
+    /// Create the review even when there are no changes between its SHAs
+    pub allow_empty_diff: bool,
+}
+
+/// New branches and assignees for an existing review
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReviewUpdateData {
+    pub review_id: String,
+    pub base_branch: String,
+    pub target_branch: String,
+    /// Comma-separated names of who should look at the review
+    pub assignees: String,
+}
+
+/// Result of importing reviews and comments from JSON
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReviewImportSummary {
+    pub reviews_imported: usize,
+    pub reviews_skipped: usize,
+    pub comments_imported: usize,
+    pub comments_skipped: usize,
+}
+
+/// Differences between the diffs of two reviews, going from review A to review B
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReviewComparison {
+    pub review_a: Arc<Review>,
+    pub review_b: Arc<Review>,
+    /// Files only in the diff of review B
+    pub added_files: Vec<String>,
+    /// Files only in the diff of review A
+    pub removed_files: Vec<String>,
+    /// Files in both diffs whose changes differ
+    pub changed_files: Vec<String>,
+    /// Number of files with the same changes in both diffs
+    pub unchanged_file_count: usize,
+}
+
+impl ReviewComparison {
+    /// Whether the reviews are on different base or target branches. Their diffs can still be
+    /// compared, but the result mixes changes of both branch pairs.
+    pub fn different_branches(&self) -> bool {
+        self.review_a.base_branch != self.review_b.base_branch
+            || self.review_a.target_branch != self.review_b.target_branch
+    }
+
+    /// Quick review of how the changes evolved from review A to review B: the diff between
+    /// their target trees, to be limited to one file, e.g. what a rework iteration changed in
+    /// it. A file in the diff of only one review is diffed the same way, it is unchanged or
+    /// missing in the target tree of the other review. It is not saved.
+    pub fn rediff_review(&self) -> color_eyre::Result<Review> {
+        let (Some(target_sha_a), Some(target_sha_b)) =
+            (&self.review_a.target_sha, &self.review_b.target_sha)
+        else {
+            color_eyre::eyre::bail!("Only reviews of branches can be re-diffed");
+        };
+        Ok(Review::builder()
+            .base_branch(&self.review_a.target_branch)
+            .target_branch(&self.review_b.target_branch)
+            .base_sha_str(target_sha_a)
+            .target_sha_str(target_sha_b)
+            .build())
+    }
 }
 
 /// State of reviews loading process
@@ -26,8 +103,9 @@ pub enum ReviewsLoadingState {
     Init,
     /// Currently loading reviews from database
     Loading,
-    /// Reviews have been successfully loaded
-    Loaded(Arc<[Review]>),
+    /// Reviews have been successfully loaded, together with the progress of every review that
+    /// has comments, viewed files or a readable diff
+    Loaded(Arc<[Review]>, Arc<HashMap<ReviewId, ReviewProgress>>),
     /// Error occurred during loading
     Error(Arc<str>),
 }
@@ -61,24 +139,13 @@ impl ReviewService {
     pub fn new() -> Self {
         Self {}
     }
-    /// Create a new review and trigger reviews reload
-    pub async fn create_review(
-        database: &Database,
-        data: ReviewCreateData,
-        events: &mut EventHandler,
-    ) -> color_eyre::Result<Review> {
-        if data.base_branch.trim().is_empty() {
-            return Err(color_eyre::eyre::eyre!("Base branch cannot be empty"));
-        }
-        if data.target_branch.trim().is_empty() {
-            return Err(color_eyre::eyre::eyre!("Target branch cannot be empty"));
-        }
 
-        // Get SHAs from Git if not provided in the data
+    /// Get the SHAs from Git if they are not provided in the data
+    fn resolve_shas(repo_path: &str, data: ReviewCreateData) -> ReviewCreateData {
         let base_sha = if data.base_sha.is_some() {
             data.base_sha
         } else {
-            match GitService::get_branch_sha(".", &data.base_branch) {
+            match GitService::get_branch_or_tag_sha(repo_path, &data.base_branch) {
                 Ok(base) => base,
                 Err(error) => {
                     log::warn!("Failed to get Git SHAs: {error}");
@@ -89,7 +156,7 @@ impl ReviewService {
         let target_sha = if data.target_sha.is_some() {
             data.target_sha
         } else {
-            match GitService::get_branch_sha(".", &data.target_branch) {
+            match GitService::get_branch_or_tag_sha(repo_path, &data.target_branch) {
                 Ok(target) => target,
                 Err(error) => {
                     log::warn!("Failed to get Git SHAs: {error}");
@@ -98,11 +165,46 @@ impl ReviewService {
             }
         };
 
+        ReviewCreateData {
+            base_sha,
+            target_sha,
+            ..data
+        }
+    }
+
+    /// Whether there are no changes between the resolved SHAs of the data.
+    /// Unknown SHAs and Git errors don't count as empty.
+    fn has_empty_diff(repo_path: &str, data: &ReviewCreateData) -> bool {
+        let (Some(base_sha), Some(target_sha)) = (&data.base_sha, &data.target_sha) else {
+            return false;
+        };
+        base_sha == target_sha
+            || GitService::get_diff_between_shas(repo_path, base_sha, target_sha)
+                .is_ok_and(|diff| diff.file_count() == 0 && diff.excluded_files.is_empty())
+    }
+
+    /// Create a new review and trigger reviews reload
+    pub async fn create_review(
+        database: &Database,
+        repo_path: &str,
+        data: ReviewCreateData,
+        events: &mut EventHandler,
+    ) -> color_eyre::Result<Review> {
+        if data.base_branch.trim().is_empty() {
+            return Err(color_eyre::eyre::eyre!("Base branch cannot be empty"));
+        }
+        if data.target_branch.trim().is_empty() {
+            return Err(color_eyre::eyre::eyre!("Target branch cannot be empty"));
+        }
+
+        let data = Self::resolve_shas(repo_path, data);
+
         let review = Review::builder()
             .base_branch(data.base_branch.trim().to_string())
             .target_branch(data.target_branch.trim().to_string())
-            .base_sha(base_sha)
-            .target_sha(target_sha)
+            .base_sha(data.base_sha)
+            .target_sha(data.target_sha)
+            .assignees(&data.assignees)
             .build();
         review.save(database.pool()).await?;
         log::info!("Created review: {}", review.title());
@@ -113,6 +215,32 @@ impl ReviewService {
         Ok(review)
     }
 
+    /// Create the review of a patch file, e.g. the output of an external diff command. The
+    /// patch is stored by its absolute path and parsed again whenever the review is opened.
+    pub async fn create_patch_review(
+        database: &Database,
+        patch_path: &str,
+    ) -> color_eyre::Result<Review> {
+        let patch_path = std::fs::canonicalize(patch_path).map_err(|error| {
+            color_eyre::eyre::eyre!("Failed to find patch file {patch_path}: {error}")
+        })?;
+        if read_patch_file(&patch_path)?.is_empty() {
+            return Err(color_eyre::eyre::eyre!(
+                "Patch file {} contains no changes",
+                patch_path.display()
+            ));
+        }
+
+        let review = Review::builder()
+            .base_branch("")
+            .target_branch("")
+            .patch_path(patch_path.to_string_lossy())
+            .build();
+        review.save(database.pool()).await?;
+        log::info!("Created review: {}", review.title());
+        Ok(review)
+    }
+
     /// List all reviews
     pub async fn list_reviews(database: &Database) -> color_eyre::Result<Vec<Review>> {
         let reviews = Review::list_all(database.pool()).await.map_err(|error| {
@@ -122,6 +250,125 @@ impl ReviewService {
         Ok(reviews)
     }
 
+    /// List the reviews of the review list. When the settings limit it to recent reviews, only
+    /// the ones created in the last `recent_review_days` days before now are listed. Archived
+    /// reviews are only listed when the settings include them.
+    async fn list_listed_reviews(
+        database: &Database,
+        time_provider: &impl TimeProvider,
+    ) -> color_eyre::Result<Vec<Review>> {
+        let settings = Settings::load(database.pool()).await?;
+        let list_all = || async {
+            if settings.include_archived {
+                Self::list_reviews(database).await
+            } else {
+                Ok(Review::list_unarchived(database.pool()).await?)
+            }
+        };
+        if !settings.recent_reviews_only {
+            return list_all().await;
+        }
+
+        let days = chrono::Days::new(settings.recent_review_days as u64);
+        match time_provider.now().checked_sub_days(days) {
+            Some(since) => {
+                Ok(Review::list_recent(database.pool(), since, settings.include_archived).await?)
+            }
+            None => list_all().await,
+        }
+    }
+
+    /// Archive the done reviews that were not updated since `since`, see
+    /// [`Review::archive_done_before`]. Returns the number of archived reviews.
+    pub async fn archive_old_done(
+        database: &Database,
+        since: DateTime<Utc>,
+    ) -> color_eyre::Result<u64> {
+        Ok(Review::archive_done_before(database.pool(), since).await?)
+    }
+
+    /// Number of reviews that are not archived when it exceeds the review limit of the
+    /// settings, unless archiving was already suggested for it
+    async fn exceeded_review_limit(database: &Database) -> color_eyre::Result<Option<i64>> {
+        let settings = Settings::load(database.pool()).await?;
+        if settings.review_limit_notified {
+            return Ok(None);
+        }
+        let review_count = Review::count_unarchived(database.pool()).await?;
+        Ok((review_count > settings.review_limit as i64).then_some(review_count))
+    }
+
+    /// List the reviews of the review list together with their progress. The files of the
+    /// diffs are counted from the repository or the patch file, reviews without a readable diff
+    /// have no file count.
+    async fn list_reviews_with_progress(
+        database: &Database,
+        repo_path: &str,
+        time_provider: &impl TimeProvider,
+    ) -> color_eyre::Result<(Vec<Review>, HashMap<ReviewId, ReviewProgress>)> {
+        let reviews = Self::list_listed_reviews(database, time_provider).await?;
+        let comment_counts = Comment::counts_by_review(database.pool()).await?;
+        let viewed_file_counts = FileView::counts_by_review(database.pool()).await?;
+        let checklist_counts = ChecklistItem::counts_by_review(database.pool()).await?;
+
+        let mut progress = HashMap::new();
+        for review in &reviews {
+            let file_count = match (&review.patch_path, &review.base_sha, &review.target_sha) {
+                (Some(patch_path), _, _) => read_patch_file(Path::new(patch_path))
+                    .ok()
+                    .map(|diff| diff.file_count()),
+                (None, Some(base_sha), Some(target_sha)) => {
+                    GitService::count_diff_files(repo_path, base_sha, target_sha).ok()
+                }
+                _ => None,
+            };
+            let (open_comment_count, comment_count) =
+                comment_counts.get(&review.id).copied().unwrap_or_default();
+            let (viewed_file_count, skipped_file_count) = viewed_file_counts
+                .get(&review.id)
+                .copied()
+                .unwrap_or_default();
+            let (checked_checklist_item_count, checklist_item_count) = checklist_counts
+                .get(&review.id)
+                .copied()
+                .unwrap_or_default();
+            let review_progress = ReviewProgress {
+                viewed_file_count: viewed_file_count as usize,
+                skipped_file_count: skipped_file_count as usize,
+                file_count,
+                resolved_comment_count: (comment_count - open_comment_count) as usize,
+                comment_count: comment_count as usize,
+                checked_checklist_item_count: checked_checklist_item_count as usize,
+                checklist_item_count: checklist_item_count as usize,
+            };
+            if review_progress != ReviewProgress::default() {
+                progress.insert(review.id.clone(), review_progress);
+            }
+        }
+        Ok((reviews, progress))
+    }
+
+    /// The reviews of the review list with their progress, for the `list` command
+    pub async fn review_list(
+        database: &Database,
+        repo_path: &str,
+    ) -> color_eyre::Result<ReviewList> {
+        let (reviews, progress) =
+            Self::list_reviews_with_progress(database, repo_path, &SystemTimeProvider).await?;
+        let reviews: Vec<ReviewListEntry> = reviews
+            .into_iter()
+            .map(|review| ReviewListEntry {
+                title: review.title(),
+                progress: progress.get(&review.id).copied().unwrap_or_default(),
+                review,
+            })
+            .collect();
+        Ok(ReviewList {
+            review_count: reviews.len(),
+            reviews,
+        })
+    }
+
     /// Delete a review by ID and trigger reviews reload
     pub async fn delete_review_by_id(
         database: &Database,
@@ -131,6 +378,9 @@ impl ReviewService {
         match Review::find_by_id(database.pool(), review_id).await {
             Ok(Some(review)) => {
                 log::debug!("Found review to delete with ID {}", review.id);
+                // The comments, file views, activities and checklist items of the review are
+                // deleted in the same statement by the `ON DELETE CASCADE` foreign keys, which
+                // `Database::connect_options` enforces on every connection
                 review.delete(database.pool()).await?;
                 log::info!("Deleted review with ID {}", review.id);
                 events.send(AppEvent::ReviewsLoad);
@@ -147,6 +397,209 @@ impl ReviewService {
         }
     }
 
+    /// Export all reviews and their comments as JSON (see [`ReviewExport`])
+    pub async fn export_json(database: &Database) -> color_eyre::Result<serde_json::Value> {
+        let mut reviews = vec![];
+        for review in Review::list_all(database.pool()).await? {
+            let comments = Comment::find_for_review(database.pool(), &review.id).await?;
+            reviews.push(ReviewExportEntry::new(&review, &comments));
+        }
+
+        Ok(serde_json::to_value(ReviewExport {
+            version: REVIEW_EXPORT_VERSION,
+            reviews,
+        })?)
+    }
+
+    /// Recreate reviews and their comments from a JSON export with fresh IDs.
+    ///
+    /// Reviews and comments that already exist (by content hash) are skipped, so importing the
+    /// same export twice does not create duplicates. Comments of an existing review are added
+    /// to it.
+    pub async fn import_json(
+        database: &Database,
+        value: serde_json::Value,
+    ) -> color_eyre::Result<ReviewImportSummary> {
+        let export: ReviewExport = serde_json::from_value(value)
+            .map_err(|error| color_eyre::eyre::eyre!("Invalid review export: {error}"))?;
+        if export.version != REVIEW_EXPORT_VERSION {
+            return Err(color_eyre::eyre::eyre!(
+                "Unsupported review export version {} (expected {REVIEW_EXPORT_VERSION})",
+                export.version
+            ));
+        }
+
+        let mut existing_reviews: HashMap<u64, ReviewId> = Review::list_all(database.pool())
+            .await?
+            .into_iter()
+            .map(|review| {
+                (
+                    ReviewExportEntry::new(&review, &[]).content_hash(),
+                    review.id,
+                )
+            })
+            .collect();
+
+        let mut summary = ReviewImportSummary::default();
+        for entry in export.reviews {
+            let review_id = match existing_reviews.get(&entry.content_hash()) {
+                Some(review_id) => {
+                    summary.reviews_skipped += 1;
+                    review_id.clone()
+                }
+                None => {
+                    let review = entry.to_review();
+                    review.save(database.pool()).await?;
+                    existing_reviews.insert(entry.content_hash(), review.id.clone());
+                    summary.reviews_imported += 1;
+                    review.id
+                }
+            };
+
+            let mut existing_comments: HashMap<u64, CommentId> =
+                Comment::find_for_review(database.pool(), &review_id)
+                    .await?
+                    .into_iter()
+                    .map(|comment| (CommentExportEntry::new(&comment).content_hash(), comment.id))
+                    .collect();
+            // Import the thread roots first, so replies can point to their IDs
+            let mut indices: Vec<usize> = (0..entry.comments.len()).collect();
+            indices.sort_by_key(|&index| entry.comments[index].parent_index.is_some());
+            let mut comment_ids: HashMap<usize, CommentId> = HashMap::new();
+            for index in indices {
+                let comment_entry = &entry.comments[index];
+                if let Some(comment_id) = existing_comments.get(&comment_entry.content_hash()) {
+                    comment_ids.insert(index, comment_id.clone());
+                    summary.comments_skipped += 1;
+                    continue;
+                }
+                let comment = comment_entry.to_comment(&review_id).with_parent_id(
+                    comment_entry
+                        .parent_index
+                        .and_then(|parent_index| comment_ids.get(&parent_index).cloned()),
+                );
+                comment.create(database.pool()).await?;
+                existing_comments.insert(comment_entry.content_hash(), comment.id.clone());
+                comment_ids.insert(index, comment.id);
+                summary.comments_imported += 1;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Compare the diffs of two reviews at their SHAs
+    pub fn compare<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        review_a: &Review,
+        review_b: &Review,
+    ) -> color_eyre::Result<ReviewComparison> {
+        let diff_a = Self::review_diff(repo_path.as_ref(), review_a)?;
+        let diff_b = Self::review_diff(repo_path.as_ref(), review_b)?;
+        Ok(Self::compare_diffs(review_a, &diff_a, review_b, &diff_b))
+    }
+
+    /// Build a review between two revspecs for a quick one-off review. It is not saved.
+    pub fn quick_review<PathRef: AsRef<Path>>(
+        repo_path: PathRef,
+        base: &str,
+        target: &str,
+    ) -> color_eyre::Result<Review> {
+        let base_sha = GitService::resolve_ref(repo_path.as_ref(), base)
+            .map_err(|error| color_eyre::eyre::eyre!("Failed to resolve base {base}: {error}"))?;
+        let target_sha = GitService::resolve_ref(repo_path.as_ref(), target).map_err(|error| {
+            color_eyre::eyre::eyre!("Failed to resolve target {target}: {error}")
+        })?;
+
+        Ok(Review::builder()
+            .base_branch(base)
+            .target_branch(target)
+            .base_sha(Some(base_sha))
+            .target_sha(Some(target_sha))
+            .build())
+    }
+
+    fn review_diff(repo_path: &Path, review: &Review) -> color_eyre::Result<Diff> {
+        if let Some(patch_path) = &review.patch_path {
+            return read_patch_file(Path::new(patch_path));
+        }
+        let (Some(base_sha), Some(target_sha)) = (&review.base_sha, &review.target_sha) else {
+            color_eyre::eyre::bail!("Review {} has no SHAs to compare", review.title());
+        };
+        GitService::get_diff_between_shas(repo_path, base_sha, target_sha)
+    }
+
+    /// Compare the file lists of two diffs, files are compared by path and content hash
+    fn compare_diffs(
+        review_a: &Review,
+        diff_a: &Diff,
+        review_b: &Review,
+        diff_b: &Diff,
+    ) -> ReviewComparison {
+        let hashes_a: BTreeMap<&str, String> = diff_a
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.content_hash()))
+            .collect();
+        let hashes_b: BTreeMap<&str, String> = diff_b
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.content_hash()))
+            .collect();
+
+        let mut comparison = ReviewComparison {
+            review_a: Arc::new(review_a.clone()),
+            review_b: Arc::new(review_b.clone()),
+            added_files: vec![],
+            removed_files: vec![],
+            changed_files: vec![],
+            unchanged_file_count: 0,
+        };
+        for (path, hash_b) in &hashes_b {
+            match hashes_a.get(path) {
+                None => comparison.added_files.push(path.to_string()),
+                Some(hash_a) if hash_a != hash_b => comparison.changed_files.push(path.to_string()),
+                Some(_) => comparison.unchanged_file_count += 1,
+            }
+        }
+        comparison.removed_files = hashes_a
+            .keys()
+            .filter(|path| !hashes_b.contains_key(*path))
+            .map(|path| path.to_string())
+            .collect();
+        comparison
+    }
+
+    /// Load both reviews and compare their diffs
+    async fn handle_review_compare_load(
+        review_a_id: &str,
+        review_b_id: &str,
+        context: ServiceContext<'_>,
+    ) {
+        let result = async {
+            let review_a = Review::find_by_id(context.database.pool(), review_a_id)
+                .await?
+                .ok_or_else(|| color_eyre::eyre::eyre!("Review {review_a_id} not found"))?;
+            let review_b = Review::find_by_id(context.database.pool(), review_b_id)
+                .await?
+                .ok_or_else(|| color_eyre::eyre::eyre!("Review {review_b_id} not found"))?;
+            Self::compare(context.repo_path, &review_a, &review_b)
+        }
+        .await;
+
+        match result {
+            Ok(comparison) => context
+                .events
+                .send(AppEvent::ReviewCompareLoaded(Arc::new(comparison))),
+            Err(error) => {
+                log::error!("Failed to compare reviews {review_a_id} and {review_b_id}: {error}");
+                context.events.send(AppEvent::ReviewCompareLoadError(
+                    format!("Error comparing reviews: {error}").into(),
+                ));
+            }
+        }
+    }
+
     /// Send loading event to start the actual loading process
     fn handle_reviews_load(events: &mut EventHandler) {
         events.send(AppEvent::ReviewsLoading);
@@ -154,12 +607,25 @@ impl ReviewService {
     }
 
     /// Actually load reviews from database
-    async fn handle_reviews_loading(database: &Database, events: &mut EventHandler) {
-        match Self::list_reviews(database).await {
-            Ok(reviews) => {
+    async fn handle_reviews_loading(
+        database: &Database,
+        repo_path: &str,
+        events: &mut EventHandler,
+    ) {
+        match Self::list_reviews_with_progress(database, repo_path, &SystemTimeProvider).await {
+            Ok((mut reviews, progress)) => {
+                Self::check_branches_exist(repo_path, &mut reviews);
                 events.send(AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
                     reviews.into(),
+                    Arc::new(progress),
                 )));
+                match Self::exceeded_review_limit(database).await {
+                    Ok(Some(review_count)) => {
+                        events.send(AppEvent::ReviewLimitExceeded(review_count));
+                    }
+                    Ok(None) => {}
+                    Err(error) => log::warn!("Failed to check the review limit: {error}"),
+                }
             }
             Err(error) => {
                 events.send(AppEvent::ReviewsLoadingState(ReviewsLoadingState::Error(
@@ -169,23 +635,103 @@ impl ReviewService {
         }
     }
 
+    /// Set whether the branches of the reviews still exist, looking up the local branches and
+    /// tags once for all reviews. Without a readable repository the stored branch status is kept.
+    fn check_branches_exist(repo_path: &str, reviews: &mut [Review]) {
+        let branches_and_tags = GitService::get_branches(repo_path)
+            .and_then(|branches| Ok((branches, GitService::get_tags(repo_path)?)));
+        let (branches, tags) = match branches_and_tags {
+            Ok(branches_and_tags) => branches_and_tags,
+            Err(error) => {
+                log::warn!("Failed to check the branches of the reviews: {error}");
+                return;
+            }
+        };
+        let exists = |branch_name: &str| {
+            branches.iter().any(|branch| branch == branch_name)
+                || tags.iter().any(|tag| tag == branch_name)
+        };
+        // Reviews of patch files have no branches
+        for review in reviews
+            .iter_mut()
+            .filter(|review| review.patch_path.is_none())
+        {
+            review.base_branch_exists = Some(exists(&review.base_branch));
+            review.target_branch_exists = Some(exists(&review.target_branch));
+        }
+    }
+
     /// Handle review creation submission
-    async fn handle_review_create_submit(
-        data: &ReviewCreateData,
-        database: &Database,
-        events: &mut EventHandler,
-    ) {
-        match Self::create_review(database, data.clone(), events).await {
+    async fn handle_review_create_submit(data: &ReviewCreateData, context: ServiceContext<'_>) {
+        let data = Self::resolve_shas(context.repo_path, data.clone());
+        if !data.allow_empty_diff && Self::has_empty_diff(context.repo_path, &data) {
+            context
+                .events
+                .send(AppEvent::ReviewCreateEmptyConfirm(Arc::new(
+                    ReviewCreateData {
+                        allow_empty_diff: true,
+                        ..data
+                    },
+                )));
+            return;
+        }
+
+        match Self::create_review(context.database, context.repo_path, data, context.events).await {
             Ok(review) => {
-                events.send(AppEvent::ReviewCreated(review));
+                context
+                    .events
+                    .send(AppEvent::ReviewCreated(Arc::new(review)));
             }
             Err(error) => {
                 log::error!("Failed to create review: {error}");
                 // For now, we'll still close the dialog even on error
                 // In the future, we might want to show an error message
-                events.send(AppEvent::ReviewCreatedError(error.to_string().into()));
+                context
+                    .events
+                    .send(AppEvent::ReviewCreatedError(error.to_string().into()));
+            }
+        }
+    }
+
+    /// Archive the done reviews that were not updated in the last `archive_after_days` days
+    /// and reload the reviews without them
+    async fn handle_reviews_archive(
+        database: &Database,
+        time_provider: &impl TimeProvider,
+        events: &mut EventHandler,
+    ) {
+        let archived = async {
+            let settings = Settings::load(database.pool()).await?;
+            let days = chrono::Days::new(settings.archive_after_days as u64);
+            match time_provider.now().checked_sub_days(days) {
+                Some(since) => Self::archive_old_done(database, since).await,
+                None => Ok(0),
             }
+        };
+        match archived.await {
+            Ok(count) => log::info!("Archived {count} done reviews"),
+            Err(error) => log::error!("Failed to archive done reviews: {error}"),
         }
+        events.send(AppEvent::ReviewsLoad);
+    }
+
+    /// Pin the review to the top of the review list or unpin it and reload the reviews
+    async fn handle_review_pin_toggle(
+        review_id: &str,
+        database: &Database,
+        events: &mut EventHandler,
+    ) {
+        let toggled = async {
+            if let Some(mut review) = Review::find_by_id(database.pool(), review_id).await? {
+                let pinned = !review.pinned;
+                review.set_pinned(database.pool(), pinned).await?;
+            }
+            Ok::<(), sqlx::Error>(())
+        };
+        if let Err(error) = toggled.await {
+            log::error!("Failed to pin review: {error}");
+        }
+        events.send(AppEvent::ReviewsLoad);
     }
 
     /// Handle review deletion
@@ -253,7 +799,7 @@ impl ReviewService {
         let mut did_update = false;
 
         if refresh_base {
-            match GitService::get_branch_sha(context.repo_path, &review.base_branch) {
+            match GitService::get_branch_or_tag_sha(context.repo_path, &review.base_branch) {
                 Ok(Some(base_sha)) => {
                     updated_review.base_sha = Some(base_sha);
                     updated_review.base_sha_changed = None;
@@ -272,7 +818,7 @@ impl ReviewService {
         }
 
         if refresh_target {
-            match GitService::get_branch_sha(context.repo_path, &review.target_branch) {
+            match GitService::get_branch_or_tag_sha(context.repo_path, &review.target_branch) {
                 Ok(Some(target_sha)) => {
                     updated_review.target_sha = Some(target_sha);
                     updated_review.target_sha_changed = None;
@@ -310,7 +856,7 @@ impl ReviewService {
             _ => None,
         };
 
-        updated_review.updated_at = crate::time_provider::SystemTimeProvider.now();
+        updated_review.updated_at = SystemTimeProvider.now();
         if let Err(error) = updated_review
             .update_shas(
                 context.database.pool(),
@@ -325,6 +871,10 @@ impl ReviewService {
             return;
         }
 
+        if let Some(new_diff) = &new_diff {
+            Self::relocate_comments(context.database, &review.id, new_diff).await;
+        }
+
         if let (Some(old_diff), Some(new_diff)) = (old_diff, new_diff)
             && let Ok(viewed_files) =
                 FileView::get_viewed_files(context.database.pool(), &review.id).await
@@ -333,12 +883,12 @@ impl ReviewService {
             let old_map: HashMap<String, String> = old_diff
                 .files
                 .iter()
-                .map(|file| (file.path.clone(), file.content.clone()))
+                .map(|file| (file.path.clone(), file.content().to_string()))
                 .collect();
             let new_map: HashMap<String, String> = new_diff
                 .files
                 .iter()
-                .map(|file| (file.path.clone(), file.content.clone()))
+                .map(|file| (file.path.clone(), file.content().to_string()))
                 .collect();
 
             for file_path in viewed_files {
@@ -364,51 +914,154 @@ impl ReviewService {
             .send(AppEvent::ReviewLoad(Arc::from(review.id)));
     }
 
-    /// Handle creating a new review from current branch heads.
-    async fn handle_review_duplicate(review_id: &str, context: ServiceContext<'_>) {
-        let review = match Review::find_by_id(context.database.pool(), review_id).await {
-            Ok(Some(review)) => review,
-            Ok(None) => {
-                log::warn!("No review found with ID: {review_id}");
-                return;
+    /// Handle changing the branches of a review.
+    ///
+    /// Resolves the SHAs of the new branches, stores them and moves the line comments to their
+    /// lines in the new diff. Comments on files that are not part of the new diff are kept.
+    async fn handle_review_update(data: &ReviewUpdateData, context: ServiceContext<'_>) {
+        match Self::update_review(data, context.database, context.repo_path).await {
+            Ok(review) => {
+                context
+                    .events
+                    .send(AppEvent::ReviewUpdated(Arc::new(review)));
+                context.events.send(AppEvent::ReviewsLoad);
+                context
+                    .events
+                    .send(AppEvent::ReviewLoad(Arc::from(data.review_id.as_str())));
             }
             Err(error) => {
-                log::error!("Error loading review by ID: {error}");
-                return;
+                log::error!("Failed to update review {}: {error}", data.review_id);
+                context
+                    .events
+                    .send(AppEvent::ReviewUpdateError(error.to_string().into()));
             }
-        };
+        }
+    }
 
-        let base_sha = match GitService::get_branch_sha(context.repo_path, &review.base_branch) {
-            Ok(Some(base_sha)) => base_sha,
-            Ok(None) => {
-                log::warn!("No base branch SHA found for review {}", review.id);
-                return;
-            }
+    async fn update_review(
+        data: &ReviewUpdateData,
+        database: &Database,
+        repo_path: &str,
+    ) -> color_eyre::Result<Review> {
+        let base_branch = data.base_branch.trim();
+        let target_branch = data.target_branch.trim();
+        if base_branch.is_empty() {
+            return Err(color_eyre::eyre::eyre!("Base branch cannot be empty"));
+        }
+        if target_branch.is_empty() {
+            return Err(color_eyre::eyre::eyre!("Target branch cannot be empty"));
+        }
+
+        let mut review = Review::find_by_id(database.pool(), &data.review_id)
+            .await?
+            .ok_or_else(|| color_eyre::eyre::eyre!("Review {} not found", data.review_id))?;
+        if review.patch_path.is_some() {
+            return Err(color_eyre::eyre::eyre!(
+                "The branches of a review of a patch file can't be changed"
+            ));
+        }
+
+        let base_sha = GitService::get_branch_or_tag_sha(repo_path, base_branch)?
+            .ok_or_else(|| color_eyre::eyre::eyre!("Branch {base_branch} not found"))?;
+        let target_sha = GitService::get_branch_or_tag_sha(repo_path, target_branch)?
+            .ok_or_else(|| color_eyre::eyre::eyre!("Branch {target_branch} not found"))?;
+
+        review.updated_at = SystemTimeProvider.now();
+        review
+            .update_branches(
+                database.pool(),
+                base_branch,
+                target_branch,
+                &base_sha,
+                &target_sha,
+            )
+            .await?;
+
+        review
+            .set_assignees(database.pool(), &data.assignees)
+            .await?;
+
+        if let Ok(diff) = GitService::get_diff_between_shas(repo_path, &base_sha, &target_sha) {
+            Self::relocate_comments(database, &review.id, &diff).await;
+        }
+
+        review.base_branch = base_branch.to_string();
+        review.target_branch = target_branch.to_string();
+        review.base_sha = Some(base_sha);
+        review.target_sha = Some(target_sha);
+        review.base_sha_changed = None;
+        review.target_sha_changed = None;
+        review.base_branch_exists = Some(true);
+        review.target_branch_exists = Some(true);
+        Ok(review)
+    }
+
+    /// Move line comments of a review to the lines matching their fingerprints in the new diff.
+    async fn relocate_comments(database: &Database, review_id: &str, diff: &Diff) {
+        let comments = match Comment::find_for_review(database.pool(), review_id).await {
+            Ok(comments) => comments,
             Err(error) => {
-                log::warn!(
-                    "Failed to load base branch SHA for review {}: {error}",
-                    review.id
-                );
+                log::warn!("Failed to load comments for review {review_id}: {error}");
                 return;
             }
         };
 
-        let target_sha = match GitService::get_branch_sha(context.repo_path, &review.target_branch)
-        {
-            Ok(Some(target_sha)) => target_sha,
+        for mut comment in comments {
+            if let Some(line_number) = comment.relocate(diff)
+                && comment.line_number != Some(line_number)
+                && let Err(error) = comment.set_line_number(database.pool(), line_number).await
+            {
+                log::warn!("Failed to relocate comment {}: {error}", comment.id);
+            }
+        }
+    }
+
+    /// Handle creating a new review from current branch heads.
+    async fn handle_review_duplicate(review_id: &str, context: ServiceContext<'_>) {
+        let review = match Review::find_by_id(context.database.pool(), review_id).await {
+            Ok(Some(review)) => review,
             Ok(None) => {
-                log::warn!("No target branch SHA found for review {}", review.id);
+                log::warn!("No review found with ID: {review_id}");
                 return;
             }
             Err(error) => {
-                log::warn!(
-                    "Failed to load target branch SHA for review {}: {error}",
-                    review.id
-                );
+                log::error!("Error loading review by ID: {error}");
                 return;
             }
         };
 
+        let base_sha =
+            match GitService::get_branch_or_tag_sha(context.repo_path, &review.base_branch) {
+                Ok(Some(base_sha)) => base_sha,
+                Ok(None) => {
+                    log::warn!("No base branch SHA found for review {}", review.id);
+                    return;
+                }
+                Err(error) => {
+                    log::warn!(
+                        "Failed to load base branch SHA for review {}: {error}",
+                        review.id
+                    );
+                    return;
+                }
+            };
+
+        let target_sha =
+            match GitService::get_branch_or_tag_sha(context.repo_path, &review.target_branch) {
+                Ok(Some(target_sha)) => target_sha,
+                Ok(None) => {
+                    log::warn!("No target branch SHA found for review {}", review.id);
+                    return;
+                }
+                Err(error) => {
+                    log::warn!(
+                        "Failed to load target branch SHA for review {}: {error}",
+                        review.id
+                    );
+                    return;
+                }
+            };
+
         let old_diff = match (review.base_sha.as_deref(), review.target_sha.as_deref()) {
             (Some(base_sha), Some(target_sha)) => {
                 GitService::get_diff_between_shas(context.repo_path, base_sha, target_sha).ok()
@@ -436,22 +1089,41 @@ impl ReviewService {
         }
 
         match Comment::find_for_review(context.database.pool(), &review.id).await {
-            Ok(comments) => {
+            Ok(mut comments) => {
+                // Copy the thread roots first, so replies can point to their copies
+                comments.sort_by_key(|comment| comment.parent_id.is_some());
+                let mut copied_ids: HashMap<CommentId, CommentId> = HashMap::new();
                 for comment in comments {
+                    let line_number = match &new_diff {
+                        Some(new_diff) => comment.relocate(new_diff),
+                        None => comment.line_number,
+                    };
                     let new_comment = Comment {
                         id: Uuid::new_v4().to_string(),
                         review_id: new_review.id.clone(),
                         file_path: comment.file_path,
-                        line_number: comment.line_number,
+                        line_number,
+                        line_fingerprint: comment.line_fingerprint,
                         content: comment.content,
                         resolved: comment.resolved,
+                        resolution_note: comment.resolution_note,
+                        priority: comment.priority,
+                        status: comment.status,
+                        parent_id: comment
+                            .parent_id
+                            .and_then(|parent_id| copied_ids.get(&parent_id).cloned()),
                         created_at: comment.created_at,
                     };
-                    if let Err(error) = new_comment.create(context.database.pool()).await {
-                        log::warn!(
-                            "Failed to copy comment to review {}: {error}",
-                            new_review.id
-                        );
+                    match new_comment.create(context.database.pool()).await {
+                        Ok(()) => {
+                            copied_ids.insert(comment.id, new_comment.id);
+                        }
+                        Err(error) => {
+                            log::warn!(
+                                "Failed to copy comment to review {}: {error}",
+                                new_review.id
+                            );
+                        }
                     }
                 }
             }
@@ -477,12 +1149,12 @@ impl ReviewService {
                 let old_map: HashMap<String, String> = old_diff
                     .files
                     .iter()
-                    .map(|file| (file.path.clone(), file.content.clone()))
+                    .map(|file| (file.path.clone(), file.content().to_string()))
                     .collect();
                 let new_map: HashMap<String, String> = new_diff
                     .files
                     .iter()
-                    .map(|file| (file.path.clone(), file.content.clone()))
+                    .map(|file| (file.path.clone(), file.content().to_string()))
                     .collect();
 
                 for file_path in viewed_files {
@@ -493,6 +1165,7 @@ impl ReviewService {
                             context.database.pool(),
                             &new_review.id,
                             &file_path,
+                            Some(&DiffFile::hash_content(new_content)),
                         )
                         .await
                     {
@@ -524,14 +1197,31 @@ impl ServiceHandler for ReviewService {
             match event {
                 AppEvent::ReviewsLoad => Self::handle_reviews_load(context.events),
                 AppEvent::ReviewsLoading => {
-                    Self::handle_reviews_loading(context.database, context.events).await
+                    Self::handle_reviews_loading(
+                        context.database,
+                        context.repo_path,
+                        context.events,
+                    )
+                    .await
                 }
                 AppEvent::ReviewCreateSubmit(data) => {
-                    Self::handle_review_create_submit(data, context.database, context.events).await
+                    Self::handle_review_create_submit(data, context).await
                 }
                 AppEvent::ReviewDelete(review_id) => {
                     Self::handle_review_delete(review_id, context.database, context.events).await
                 }
+                AppEvent::ReviewPinToggle(review_id) => {
+                    Self::handle_review_pin_toggle(review_id, context.database, context.events)
+                        .await
+                }
+                AppEvent::ReviewsArchive => {
+                    Self::handle_reviews_archive(
+                        context.database,
+                        &SystemTimeProvider,
+                        context.events,
+                    )
+                    .await
+                }
                 AppEvent::ReviewLoad(review_id) => {
                     Self::handle_review_load(review_id, context.database, context.events).await
                 }
@@ -546,6 +1236,11 @@ impl ServiceHandler for ReviewService {
                 AppEvent::ReviewDuplicate { review_id } => {
                     Self::handle_review_duplicate(review_id, context).await
                 }
+                AppEvent::ReviewUpdate(data) => Self::handle_review_update(data, context).await,
+                AppEvent::ReviewCompareLoad {
+                    review_a_id,
+                    review_b_id,
+                } => Self::handle_review_compare_load(review_a_id, review_b_id, context).await,
                 _ => {
                     // Other events are not handled by ReviewService
                 }
@@ -567,6 +1262,9 @@ mod tests {
     use crate::{
         app::App,
         event::{AppEvent, Event, EventHandler, ReviewId},
+        models::{Activity, ActivityType},
+        test_utils::fixed_time,
+        time_provider::MockTimeProvider,
     };
 
     async fn create_test_database() -> Database {
@@ -663,9 +1361,11 @@ mod tests {
             target_branch: "feature/test".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
 
-        ReviewService::create_review(&database, data, &mut events)
+        ReviewService::create_review(&database, ".", data, &mut events)
             .await
             .unwrap();
 
@@ -690,9 +1390,11 @@ mod tests {
             target_branch: "feature/test".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
 
-        match ReviewService::create_review(&database, data, &mut events).await {
+        match ReviewService::create_review(&database, ".", data, &mut events).await {
             Ok(_) => panic!("Expected error for empty base branch"),
             Err(e) => {
                 assert_eq!(e.to_string(), "Base branch cannot be empty");
@@ -712,9 +1414,11 @@ mod tests {
             target_branch: "".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
 
-        match ReviewService::create_review(&database, data, &mut events).await {
+        match ReviewService::create_review(&database, ".", data, &mut events).await {
             Ok(_) => panic!("Expected error for empty target branch"),
             Err(e) => {
                 assert_eq!(e.to_string(), "Target branch cannot be empty");
@@ -734,9 +1438,11 @@ mod tests {
             target_branch: "  feature/test  ".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
 
-        ReviewService::create_review(&database, data, &mut events)
+        ReviewService::create_review(&database, ".", data, &mut events)
             .await
             .unwrap();
 
@@ -761,6 +1467,126 @@ mod tests {
         assert_eq!(reviews.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_list_listed_reviews_limited_to_recent_reviews() {
+        let database = create_test_database().await;
+        let now = fixed_time();
+        for (target_branch, age_in_days) in [("feature/old", 20), ("feature/recent", 3)] {
+            Review::builder()
+                .target_branch(target_branch)
+                .build_with_time_provider(&MockTimeProvider::new(
+                    now - chrono::Duration::days(age_in_days),
+                ))
+                .save(database.pool())
+                .await
+                .unwrap();
+        }
+        let time_provider = MockTimeProvider::new(now);
+
+        let reviews = ReviewService::list_listed_reviews(&database, &time_provider)
+            .await
+            .unwrap();
+        assert_eq!(reviews.len(), 2);
+
+        let settings = Settings {
+            recent_reviews_only: true,
+            recent_review_days: 7,
+            ..Settings::default()
+        };
+        settings.save(database.pool()).await.unwrap();
+        let reviews = ReviewService::list_listed_reviews(&database, &time_provider)
+            .await
+            .unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].target_branch, "feature/recent");
+    }
+
+    #[tokio::test]
+    async fn test_archive_old_done_excludes_reviews_from_list() {
+        let database = create_test_database().await;
+        let now = fixed_time();
+        let mut review_ids = vec![];
+        for (target_branch, age_in_days) in [("feature/old", 40), ("feature/recent", 3)] {
+            let review = Review::builder()
+                .target_branch(target_branch)
+                .build_with_time_provider(&MockTimeProvider::new(
+                    now - chrono::Duration::days(age_in_days),
+                ));
+            review.save(database.pool()).await.unwrap();
+            FileView::mark_as_viewed(database.pool(), &review.id, "src/main.rs", None)
+                .await
+                .unwrap();
+            review_ids.push(review.id);
+        }
+        let time_provider = MockTimeProvider::new(now);
+
+        let archived = ReviewService::archive_old_done(&database, now - chrono::Duration::days(30))
+            .await
+            .unwrap();
+        assert_eq!(archived, 1);
+
+        let reviews = ReviewService::list_listed_reviews(&database, &time_provider)
+            .await
+            .unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].target_branch, "feature/recent");
+
+        let settings = Settings {
+            include_archived: true,
+            ..Settings::default()
+        };
+        settings.save(database.pool()).await.unwrap();
+        let reviews = ReviewService::list_listed_reviews(&database, &time_provider)
+            .await
+            .unwrap();
+        assert_eq!(reviews.len(), 2);
+        assert!(reviews.iter().any(|review| review.archived));
+    }
+
+    #[tokio::test]
+    async fn test_handle_reviews_loading_suggests_archiving_above_review_limit() {
+        let database = create_test_database().await;
+        let settings = Settings {
+            review_limit: 1,
+            ..Settings::default()
+        };
+        settings.save(database.pool()).await.unwrap();
+        for target_branch in ["feature/a", "feature/b"] {
+            Review::builder()
+                .target_branch(target_branch)
+                .build()
+                .save(database.pool())
+                .await
+                .unwrap();
+        }
+        let mut events = EventHandler::new_for_test();
+
+        ReviewService::handle_reviews_loading(&database, ".", &mut events).await;
+
+        let event = events.try_recv().unwrap();
+        assert!(matches!(
+            *event,
+            Event::App(AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+                ..
+            )))
+        ));
+        let event = events.try_recv().unwrap();
+        assert!(matches!(
+            *event,
+            Event::App(AppEvent::ReviewLimitExceeded(2))
+        ));
+
+        // Archiving is only suggested once
+        let settings = Settings {
+            review_limit_notified: true,
+            ..settings
+        };
+        settings.save(database.pool()).await.unwrap();
+        ReviewService::handle_reviews_loading(&database, ".", &mut events).await;
+        events.try_recv().unwrap();
+        assert!(events.try_recv().is_none());
+    }
+
     #[tokio::test]
     async fn test_list_reviews_with_data() {
         let database = create_test_database().await;
@@ -772,18 +1598,22 @@ mod tests {
             target_branch: "feature/review-1".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
         let data2 = ReviewCreateData {
             base_branch: "main".to_string(),
             target_branch: "feature/review-2".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
 
-        ReviewService::create_review(&database, data1, &mut events)
+        ReviewService::create_review(&database, ".", data1, &mut events)
             .await
             .unwrap();
-        ReviewService::create_review(&database, data2, &mut events)
+        ReviewService::create_review(&database, ".", data2, &mut events)
             .await
             .unwrap();
 
@@ -806,18 +1636,22 @@ mod tests {
             target_branch: "feature/review-1".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
         let data2 = ReviewCreateData {
             base_branch: "main".to_string(),
             target_branch: "feature/review-2".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
 
-        ReviewService::create_review(&database, data1, &mut events)
+        ReviewService::create_review(&database, ".", data1, &mut events)
             .await
             .unwrap();
-        ReviewService::create_review(&database, data2, &mut events)
+        ReviewService::create_review(&database, ".", data2, &mut events)
             .await
             .unwrap();
 
@@ -840,6 +1674,56 @@ mod tests {
         assert_eq!(updated_reviews[0].target_branch, "feature/review-1");
     }
 
+    #[tokio::test]
+    async fn test_delete_review_by_id_deletes_comments_and_file_views() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let review = Review::builder().build();
+        review.save(database.pool()).await.unwrap();
+        let other_review = Review::builder().build();
+        other_review.save(database.pool()).await.unwrap();
+
+        for review_id in [&review.id, &other_review.id] {
+            Comment::new(review_id, "file.txt", Some(1), "Comment")
+                .create(database.pool())
+                .await
+                .unwrap();
+            FileView::mark_as_viewed(database.pool(), review_id, "file.txt", None)
+                .await
+                .unwrap();
+            Activity::new(review_id, ActivityType::FileViewed, "file.txt")
+                .create(database.pool())
+                .await
+                .unwrap();
+        }
+
+        ReviewService::delete_review_by_id(&database, &review.id, &mut events)
+            .await
+            .unwrap();
+
+        let comments = Comment::find_for_review(database.pool(), &review.id)
+            .await
+            .unwrap();
+        assert!(comments.is_empty());
+        let viewed_files = FileView::get_viewed_files(database.pool(), &review.id)
+            .await
+            .unwrap();
+        assert!(viewed_files.is_empty());
+        let activities = Activity::find_for_review(database.pool(), &review.id)
+            .await
+            .unwrap();
+        assert!(activities.is_empty());
+
+        let other_comments = Comment::find_for_review(database.pool(), &other_review.id)
+            .await
+            .unwrap();
+        assert_eq!(other_comments.len(), 1);
+        let other_viewed_files = FileView::get_viewed_files(database.pool(), &other_review.id)
+            .await
+            .unwrap();
+        assert_eq!(other_viewed_files, vec!["file.txt".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_delete_review_by_invalid_id() {
         let database = create_test_database().await;
@@ -851,8 +1735,10 @@ mod tests {
             target_branch: "feature/review-1".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
-        ReviewService::create_review(&database, data, &mut events)
+        ReviewService::create_review(&database, ".", data, &mut events)
             .await
             .unwrap();
         // Receive the event that was sent by create_review
@@ -875,58 +1761,330 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_handle_app_event_reviews_load() {
+    async fn test_handle_app_event_reviews_load() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let app = App {
+            running: true,
+            events: EventHandler::new_for_test(),
+            database,
+            view_stack: vec![],
+            repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
+        };
+
+        ReviewService::handle_app_event(
+            &AppEvent::ReviewsLoad,
+            ServiceContext {
+                database: &app.database,
+                repo_path: &app.repo_path,
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        // Should have sent a ReviewsLoading event
+        assert!(events.has_pending_events());
+        let event = events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ReviewsLoading)));
+    }
+
+    #[tokio::test]
+    async fn test_export_import_json_round_trip() {
+        let source_database = create_test_database().await;
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature")
+            .base_sha_str("abc")
+            .target_sha_str("def")
+            .build();
+        review.save(source_database.pool()).await.unwrap();
+        let mut comment = Comment::new(&review.id, "src/main.rs", Some(2), "Line comment")
+            .with_line_fingerprint(Some("fingerprint".to_string()));
+        comment.resolved = true;
+        comment.create(source_database.pool()).await.unwrap();
+        let mut file_comment = Comment::new(&review.id, "src/lib.rs", None, "File comment");
+        file_comment.created_at = comment.created_at - chrono::Duration::minutes(1);
+        file_comment.create(source_database.pool()).await.unwrap();
+        let mut reply = Comment::new(&review.id, "src/main.rs", Some(2), "Reply")
+            .with_parent_id(Some(comment.id.clone()));
+        reply.created_at = comment.created_at + chrono::Duration::minutes(1);
+        reply.create(source_database.pool()).await.unwrap();
+
+        let export = ReviewService::export_json(&source_database).await.unwrap();
+        let target_database = create_test_database().await;
+        let summary = ReviewService::import_json(&target_database, export.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            summary,
+            ReviewImportSummary {
+                reviews_imported: 1,
+                reviews_skipped: 0,
+                comments_imported: 3,
+                comments_skipped: 0,
+            }
+        );
+        let imported_reviews = Review::list_all(target_database.pool()).await.unwrap();
+        assert_eq!(imported_reviews.len(), 1);
+        assert_ne!(imported_reviews[0].id, review.id);
+        let imported_comments =
+            Comment::find_for_review(target_database.pool(), &imported_reviews[0].id)
+                .await
+                .unwrap();
+        assert_eq!(imported_comments.len(), 3);
+        // The reply is still in the thread of the imported line comment
+        let imported_line_comment = imported_comments
+            .iter()
+            .find(|comment| comment.content == "Line comment")
+            .unwrap();
+        let imported_reply = imported_comments
+            .iter()
+            .find(|comment| comment.content == "Reply")
+            .unwrap();
+        assert_eq!(
+            imported_reply.parent_id.as_ref(),
+            Some(&imported_line_comment.id)
+        );
+        assert_eq!(
+            ReviewService::export_json(&target_database).await.unwrap(),
+            export
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_json_skips_existing_entries() {
+        let database = create_test_database().await;
+        let review = Review::builder().build();
+        review.save(database.pool()).await.unwrap();
+        Comment::new(&review.id, "src/main.rs", None, "Comment")
+            .create(database.pool())
+            .await
+            .unwrap();
+        let mut export = ReviewService::export_json(&database).await.unwrap();
+        export["reviews"][0]["comments"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({
+                "file_path": "src/lib.rs",
+                "line_number": null,
+                "line_fingerprint": null,
+                "content": "New comment",
+                "resolved": false,
+                "created_at": "2025-01-01T00:00:00Z",
+            }));
+
+        let summary = ReviewService::import_json(&database, export).await.unwrap();
+
+        assert_eq!(
+            summary,
+            ReviewImportSummary {
+                reviews_imported: 0,
+                reviews_skipped: 1,
+                comments_imported: 1,
+                comments_skipped: 1,
+            }
+        );
+        assert_eq!(Review::list_all(database.pool()).await.unwrap().len(), 1);
+        assert_eq!(
+            Comment::find_for_review(database.pool(), &review.id)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_json_invalid_schema() {
+        let database = create_test_database().await;
+
+        let error = ReviewService::import_json(&database, serde_json::json!({ "reviews": 1 }))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Invalid review export"));
+
+        let error = ReviewService::import_json(
+            &database,
+            serde_json::json!({ "version": 99, "reviews": [] }),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Unsupported review export version")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_review_refresh_base_updates_sha() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let (temp_dir, _base_sha, _target_sha, _new_target_sha) = create_refresh_test_repo();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let base_sha = GitService::get_branch_sha(repo_path.as_str(), "base").unwrap();
+
+        let review = Review::builder()
+            .base_branch("base")
+            .target_branch("target")
+            .base_sha(Some("old-base".to_string()))
+            .target_sha(Some("old-target".to_string()))
+            .base_sha_changed(Some("changed-base".to_string()))
+            .target_sha_changed(Some("changed-target".to_string()))
+            .build();
+        review.save(database.pool()).await.unwrap();
+
+        ReviewService::handle_app_event(
+            &AppEvent::ReviewRefresh {
+                review_id: Arc::from(review.id.clone()),
+                refresh_base: true,
+                refresh_target: false,
+            },
+            ServiceContext {
+                database: &database,
+                repo_path: repo_path.as_str(),
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        let updated = Review::find_by_id(database.pool(), &review.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.base_sha, base_sha);
+        assert!(updated.base_sha_changed.is_none());
+        assert_eq!(updated.target_sha, Some("old-target".to_string()));
+        assert_eq!(
+            updated.target_sha_changed,
+            Some("changed-target".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_review_update_changes_branches_and_shas() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let (temp_dir, initial_sha, _target_sha, new_target_sha) = create_refresh_test_repo();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature")
+            .base_sha(Some("old-base".to_string()))
+            .target_sha(Some("old-target".to_string()))
+            .target_sha_changed(Some("changed-target".to_string()))
+            .build();
+        review.save(database.pool()).await.unwrap();
+
+        ReviewService::handle_app_event(
+            &AppEvent::ReviewUpdate(Arc::new(ReviewUpdateData {
+                review_id: review.id.clone(),
+                base_branch: "base".to_string(),
+                target_branch: " target ".to_string(),
+                assignees: "bob, carol".to_string(),
+            })),
+            ServiceContext {
+                database: &database,
+                repo_path: repo_path.as_str(),
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        let updated = Review::find_by_id(database.pool(), &review.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.base_branch, "base");
+        assert_eq!(updated.target_branch, "target");
+        assert_eq!(updated.base_sha, Some(initial_sha));
+        assert_eq!(updated.target_sha, Some(new_target_sha));
+        assert!(updated.target_sha_changed.is_none());
+        assert_eq!(updated.assignees(), vec!["bob", "carol"]);
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewUpdated(review)) => {
+                assert_eq!(review.target_branch, updated.target_branch);
+                assert_eq!(review.assignees, updated.assignees);
+                assert_eq!(review.target_sha, updated.target_sha);
+            }
+            _ => panic!("Expected ReviewUpdated event, got: {event:?}"),
+        }
+        let event = events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ReviewsLoad)));
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewLoad(review_id)) => {
+                assert_eq!(review_id.as_ref(), review.id);
+            }
+            _ => panic!("Expected ReviewLoad event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_review_update_unknown_branch() {
         let database = create_test_database().await;
         let mut events = EventHandler::new_for_test();
-        let app = App {
-            running: true,
-            events: EventHandler::new_for_test(),
-            database,
-            view_stack: vec![],
-            repo_path: ".".to_string(),
-        };
+        let (temp_dir, _base_sha, _target_sha, _new_target_sha) = create_refresh_test_repo();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+
+        let review = Review::builder()
+            .base_branch("base")
+            .target_branch("target")
+            .build();
+        review.save(database.pool()).await.unwrap();
 
         ReviewService::handle_app_event(
-            &AppEvent::ReviewsLoad,
+            &AppEvent::ReviewUpdate(Arc::new(ReviewUpdateData {
+                review_id: review.id.clone(),
+                base_branch: "base".to_string(),
+                target_branch: "missing".to_string(),
+                assignees: String::new(),
+            })),
             ServiceContext {
-                database: &app.database,
-                repo_path: &app.repo_path,
+                database: &database,
+                repo_path: repo_path.as_str(),
                 events: &mut events,
             },
         )
         .await
         .unwrap();
 
-        // Should have sent a ReviewsLoading event
-        assert!(events.has_pending_events());
         let event = events.try_recv().unwrap();
-        assert!(matches!(*event, Event::App(AppEvent::ReviewsLoading)));
+        assert!(matches!(*event, Event::App(AppEvent::ReviewUpdateError(_))));
+        let unchanged = Review::find_by_id(database.pool(), &review.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(unchanged.target_branch, "target");
     }
 
     #[tokio::test]
-    async fn test_handle_review_refresh_base_updates_sha() {
+    async fn test_handle_review_update_patch_review() {
         let database = create_test_database().await;
         let mut events = EventHandler::new_for_test();
         let (temp_dir, _base_sha, _target_sha, _new_target_sha) = create_refresh_test_repo();
         let repo_path = temp_dir.path().to_string_lossy().to_string();
-        let base_sha = GitService::get_branch_sha(repo_path.as_str(), "base").unwrap();
 
-        let review = Review::builder()
-            .base_branch("base")
-            .target_branch("target")
-            .base_sha(Some("old-base".to_string()))
-            .target_sha(Some("old-target".to_string()))
-            .base_sha_changed(Some("changed-base".to_string()))
-            .target_sha_changed(Some("changed-target".to_string()))
-            .build();
+        let review = Review::builder().patch_path("/tmp/change.diff").build();
         review.save(database.pool()).await.unwrap();
 
         ReviewService::handle_app_event(
-            &AppEvent::ReviewRefresh {
-                review_id: Arc::from(review.id.clone()),
-                refresh_base: true,
-                refresh_target: false,
-            },
+            &AppEvent::ReviewUpdate(Arc::new(ReviewUpdateData {
+                review_id: review.id.clone(),
+                base_branch: "base".to_string(),
+                target_branch: "target".to_string(),
+                assignees: String::new(),
+            })),
             ServiceContext {
                 database: &database,
                 repo_path: repo_path.as_str(),
@@ -936,17 +2094,19 @@ mod tests {
         .await
         .unwrap();
 
-        let updated = Review::find_by_id(database.pool(), &review.id)
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewUpdateError(error)) => assert_eq!(
+                error.as_ref(),
+                "The branches of a review of a patch file can't be changed"
+            ),
+            _ => panic!("Expected ReviewUpdateError event, got: {event:?}"),
+        }
+        let unchanged = Review::find_by_id(database.pool(), &review.id)
             .await
             .unwrap()
             .unwrap();
-        assert_eq!(updated.base_sha, base_sha);
-        assert!(updated.base_sha_changed.is_none());
-        assert_eq!(updated.target_sha, Some("old-target".to_string()));
-        assert_eq!(
-            updated.target_sha_changed,
-            Some("changed-target".to_string())
-        );
+        assert!(unchanged.base_sha.is_none());
     }
 
     #[tokio::test]
@@ -1009,7 +2169,7 @@ mod tests {
             .build();
         review.save(database.pool()).await.unwrap();
 
-        FileView::mark_as_viewed(database.pool(), &review.id, "file.txt")
+        FileView::mark_as_viewed(database.pool(), &review.id, "file.txt", None)
             .await
             .unwrap();
 
@@ -1053,7 +2213,7 @@ mod tests {
         comment.resolved = true;
         comment.create(database.pool()).await.unwrap();
 
-        FileView::mark_as_viewed(database.pool(), &review.id, "file.txt")
+        FileView::mark_as_viewed(database.pool(), &review.id, "file.txt", None)
             .await
             .unwrap();
 
@@ -1114,7 +2274,7 @@ mod tests {
             .build();
         review.save(database.pool()).await.unwrap();
 
-        FileView::mark_as_viewed(database.pool(), &review.id, "file.txt")
+        FileView::mark_as_viewed(database.pool(), &review.id, "file.txt", None)
             .await
             .unwrap();
 
@@ -1159,6 +2319,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
 
         ReviewService::handle_app_event(
@@ -1175,16 +2338,166 @@ mod tests {
         // Should have sent a ReviewsLoadingState event with the review
         assert!(events.has_pending_events());
         let event = events.try_recv().unwrap();
-        if let Event::App(AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(ref reviews))) =
-            *event
+        if let Event::App(AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+            ref reviews,
+            ref progress,
+        ))) = *event
         {
             assert_eq!(reviews.len(), 1);
+            assert!(progress.is_empty());
             assert_eq!(reviews[0].base_branch, "default");
         } else {
             panic!("Expected ReviewsLoadingState event with reviews");
         }
     }
 
+    #[tokio::test]
+    async fn test_list_reviews_with_progress() {
+        let database = create_test_database().await;
+        let (temp_dir, initial_sha, target_sha, _) = create_refresh_test_repo();
+        let review = Review::builder()
+            .base_sha_str(&initial_sha)
+            .target_sha_str(&target_sha)
+            .build();
+        review.save(database.pool()).await.unwrap();
+        FileView::mark_as_viewed(database.pool(), &review.id, "file.txt", None)
+            .await
+            .unwrap();
+        for (content, resolved) in [("Open", false), ("Done", true), ("Also done", true)] {
+            let mut comment = Comment::test_comment(&review.id, "file.txt", None, content);
+            comment.create(database.pool()).await.unwrap();
+            if resolved {
+                comment.set_resolved(database.pool(), true).await.unwrap();
+            }
+        }
+        ChecklistItem::new(&review.id, "Tests added")
+            .create(database.pool())
+            .await
+            .unwrap();
+
+        let (reviews, progress) = ReviewService::list_reviews_with_progress(
+            &database,
+            temp_dir.path().to_str().unwrap(),
+            &SystemTimeProvider,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(
+            progress[&review.id],
+            ReviewProgress {
+                viewed_file_count: 1,
+                skipped_file_count: 0,
+                file_count: Some(1),
+                resolved_comment_count: 2,
+                comment_count: 3,
+                checked_checklist_item_count: 0,
+                checklist_item_count: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_list_serializes_to_json() {
+        let database = create_test_database().await;
+        let (temp_dir, initial_sha, target_sha, _) = create_refresh_test_repo();
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature")
+            .base_sha_str(&initial_sha)
+            .target_sha_str(&target_sha)
+            .build();
+        review.save(database.pool()).await.unwrap();
+        let comment = Comment::test_comment(&review.id, "file.txt", None, "Open");
+        comment.create(database.pool()).await.unwrap();
+        Review::builder()
+            .base_branch("main")
+            .target_branch("other")
+            .build()
+            .save(database.pool())
+            .await
+            .unwrap();
+
+        let review_list = ReviewService::review_list(&database, temp_dir.path().to_str().unwrap())
+            .await
+            .unwrap();
+        let json = serde_json::to_string_pretty(&review_list).unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["review_count"], 2);
+        let reviews = value["reviews"].as_array().unwrap();
+        assert_eq!(reviews.len(), 2);
+        let entry = reviews
+            .iter()
+            .find(|entry| entry["id"] == review.id.as_str())
+            .unwrap();
+        assert_eq!(entry["base_branch"], "main");
+        assert_eq!(entry["target_branch"], "feature");
+        assert_eq!(entry["target_sha"], target_sha.as_str());
+        assert_eq!(entry["file_count"], 1);
+        assert_eq!(entry["viewed_file_count"], 0);
+        assert_eq!(entry["comment_count"], 1);
+        assert_eq!(entry["resolved_comment_count"], 0);
+        assert!(entry["title"].as_str().unwrap().starts_with("main ("));
+        // Reviews without a readable diff have no file count
+        let other = reviews
+            .iter()
+            .find(|entry| entry["target_branch"] == "other")
+            .unwrap();
+        assert_eq!(other["file_count"], serde_json::Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_create_patch_review() {
+        let database = create_test_database().await;
+        let temp_dir = TempDir::new().unwrap();
+        let patch_path = temp_dir.path().join("change.diff");
+        fs::write(
+            &patch_path,
+            "--- a/a.txt\n+++ b/a.txt\n@@ -1 +1 @@\n-a\n+b\n--- a/b.txt\n+++ b/b.txt\n@@ -1 +1 @@\n-c\n+d\n",
+        )
+        .unwrap();
+
+        let review = ReviewService::create_patch_review(&database, patch_path.to_str().unwrap())
+            .await
+            .unwrap();
+
+        let patch_path = fs::canonicalize(&patch_path).unwrap();
+        assert_eq!(
+            review.patch_path.as_deref(),
+            Some(patch_path.to_str().unwrap())
+        );
+        assert_eq!(review.base_sha, None);
+        assert_eq!(review.target_sha, None);
+        FileView::mark_as_viewed(database.pool(), &review.id, "b.txt", None)
+            .await
+            .unwrap();
+        // The files of the patch are counted from the patch file, not from the repository
+        let (_, progress) = ReviewService::list_reviews_with_progress(
+            &database,
+            "/nonexistent",
+            &SystemTimeProvider,
+        )
+        .await
+        .unwrap();
+        assert_eq!(progress[&review.id].file_count, Some(2));
+        assert_eq!(progress[&review.id].viewed_file_count, 1);
+
+        let empty_patch_path = temp_dir.path().join("empty.diff");
+        fs::write(&empty_patch_path, "Nothing changed\n").unwrap();
+        let error =
+            ReviewService::create_patch_review(&database, empty_patch_path.to_str().unwrap())
+                .await
+                .unwrap_err();
+        assert!(error.to_string().contains("contains no changes"));
+        assert!(
+            ReviewService::create_patch_review(&database, "/nonexistent/change.diff")
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn test_handle_app_event_reviews_loading_empty() {
         let database = create_test_database().await;
@@ -1195,6 +2508,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
 
         ReviewService::handle_app_event(
@@ -1211,8 +2527,10 @@ mod tests {
         // Should have sent a ReviewsLoadingState event with empty list
         assert!(events.has_pending_events());
         let event = events.try_recv().unwrap();
-        if let Event::App(AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(ref reviews))) =
-            *event
+        if let Event::App(AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+            ref reviews,
+            _,
+        ))) = *event
         {
             assert_eq!(reviews.len(), 0);
         } else {
@@ -1230,6 +2548,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
 
         // Test that other events are ignored
@@ -1258,6 +2579,8 @@ mod tests {
             target_branch: "feature/created".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
 
         let app = App {
@@ -1266,6 +2589,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
 
         ReviewService::handle_app_event(
@@ -1296,6 +2622,124 @@ mod tests {
         assert_eq!(reviews[0].target_branch, "feature/created");
     }
 
+    fn empty_diff_data(allow_empty_diff: bool) -> ReviewCreateData {
+        ReviewCreateData {
+            base_branch: "main".to_string(),
+            target_branch: "feature/empty".to_string(),
+            base_sha: Some("abc123".to_string()),
+            target_sha: Some("abc123".to_string()),
+            assignees: String::new(),
+            allow_empty_diff,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_review_create_submit_empty_diff_asks_for_confirmation() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+
+        ReviewService::handle_review_create_submit(
+            &empty_diff_data(false),
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
+        )
+        .await;
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewCreateEmptyConfirm(data)) => {
+                assert_eq!(**data, empty_diff_data(true));
+            }
+            _ => panic!("Expected ReviewCreateEmptyConfirm event, got: {event:?}"),
+        }
+        assert!(!events.has_pending_events());
+        assert_eq!(Review::list_all(database.pool()).await.unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_handle_review_create_submit_empty_diff_allowed() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+
+        ReviewService::handle_review_create_submit(
+            &empty_diff_data(true),
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
+        )
+        .await;
+
+        let event = events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ReviewsLoad)));
+        let event = events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ReviewCreated(_))));
+        let reviews = Review::list_all(database.pool()).await.unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].target_branch, "feature/empty");
+    }
+
+    #[tokio::test]
+    async fn test_handle_review_create_submit_resolves_shas_in_repo_path() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let (temp_dir, initial_sha, _, new_target_sha) = create_refresh_test_repo();
+        let data = ReviewCreateData {
+            base_branch: "base".to_string(),
+            target_branch: "target".to_string(),
+            base_sha: None,
+            target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
+        };
+
+        ReviewService::handle_review_create_submit(
+            &data,
+            ServiceContext {
+                database: &database,
+                repo_path: temp_dir.path().to_str().unwrap(),
+                events: &mut events,
+            },
+        )
+        .await;
+
+        let reviews = Review::list_all(database.pool()).await.unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].base_sha, Some(initial_sha));
+        assert_eq!(reviews[0].target_sha, Some(new_target_sha));
+    }
+
+    #[test]
+    fn test_check_branches_exist() {
+        let (temp_dir, _, _, _) = create_refresh_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let mut reviews = vec![
+            Review::builder()
+                .base_branch("base")
+                .target_branch("target")
+                .build(),
+            Review::builder()
+                .base_branch("base")
+                .target_branch("deleted")
+                .build(),
+        ];
+
+        ReviewService::check_branches_exist(repo_path, &mut reviews);
+
+        assert!(reviews[0].deleted_branches().is_empty());
+        assert_eq!(reviews[1].deleted_branches(), vec!["deleted"]);
+        assert_eq!(reviews[1].base_branch_exists, Some(true));
+
+        // Without a repository the stored status is kept
+        let not_a_repo = TempDir::new().unwrap();
+        ReviewService::check_branches_exist(not_a_repo.path().to_str().unwrap(), &mut reviews);
+        assert_eq!(reviews[1].deleted_branches(), vec!["deleted"]);
+    }
+
     #[tokio::test]
     async fn test_handle_app_event_review_create_submit_empty_branches() {
         let database = create_test_database().await;
@@ -1306,6 +2750,8 @@ mod tests {
             target_branch: "feature/test".to_string(),
             base_sha: None,
             target_sha: None,
+            assignees: String::new(),
+            allow_empty_diff: false,
         };
 
         let app = App {
@@ -1314,6 +2760,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
 
         // Test empty branches submission
@@ -1357,6 +2806,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
 
         // Create two reviews
@@ -1413,6 +2865,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
 
         // Create a review but try to delete with non-existent ID
@@ -1461,6 +2916,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
 
         ReviewService::handle_app_event(
@@ -1494,6 +2952,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         };
 
         // Create a test review
@@ -1610,4 +3071,230 @@ mod tests {
             _ => panic!("Expected ReviewLoadingState::Error event, got: {event2:?}"),
         }
     }
+
+    fn comparison_diff(files: &[(&str, &str)]) -> Diff {
+        Diff::from_files(
+            files
+                .iter()
+                .map(|(path, content)| DiffFile::from_content(*path, content))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_compare_diffs_with_overlapping_files() {
+        let review_a = Review::builder().build();
+        let review_b = Review::builder().build();
+        let diff_a = comparison_diff(&[
+            ("src/main.rs", "+one"),
+            ("src/lib.rs", "+same"),
+            ("src/old.rs", "+old"),
+        ]);
+        let diff_b = comparison_diff(&[
+            ("src/main.rs", "+one\n+two"),
+            ("src/lib.rs", "+same"),
+            ("src/new.rs", "+new"),
+            ("README.md", "+docs"),
+        ]);
+
+        let comparison = ReviewService::compare_diffs(&review_a, &diff_a, &review_b, &diff_b);
+
+        assert_eq!(comparison.added_files, ["README.md", "src/new.rs"]);
+        assert_eq!(comparison.removed_files, ["src/old.rs"]);
+        assert_eq!(comparison.changed_files, ["src/main.rs"]);
+        assert_eq!(comparison.unchanged_file_count, 1);
+        assert!(!comparison.different_branches());
+    }
+
+    #[test]
+    fn test_compare_diffs_with_disjoint_files() {
+        let review_a = Review::builder().target_branch("feature").build();
+        let review_b = Review::builder().target_branch("other-feature").build();
+        let diff_a = comparison_diff(&[("a.rs", "+a"), ("b.rs", "+b")]);
+        let diff_b = comparison_diff(&[("c.rs", "+c")]);
+
+        let comparison = ReviewService::compare_diffs(&review_a, &diff_a, &review_b, &diff_b);
+
+        assert_eq!(comparison.added_files, ["c.rs"]);
+        assert_eq!(comparison.removed_files, ["a.rs", "b.rs"]);
+        assert!(comparison.changed_files.is_empty());
+        assert_eq!(comparison.unchanged_file_count, 0);
+        assert!(comparison.different_branches());
+    }
+
+    #[tokio::test]
+    async fn test_handle_review_compare_load() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let (temp_dir, initial_sha, target_sha, _) = create_refresh_test_repo();
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let review_a = Review::builder()
+            .base_sha_str(&initial_sha)
+            .target_sha_str(&initial_sha)
+            .build();
+        let review_b = Review::builder()
+            .base_sha_str(&initial_sha)
+            .target_sha_str(&target_sha)
+            .build();
+        review_a.save(database.pool()).await.unwrap();
+        review_b.save(database.pool()).await.unwrap();
+
+        ReviewService::handle_app_event(
+            &AppEvent::ReviewCompareLoad {
+                review_a_id: Arc::from(review_a.id.as_str()),
+                review_b_id: Arc::from(review_b.id.as_str()),
+            },
+            ServiceContext {
+                database: &database,
+                repo_path: &repo_path,
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewCompareLoaded(comparison)) => {
+                assert_eq!(comparison.review_a.id, review_a.id);
+                assert_eq!(comparison.review_b.id, review_b.id);
+                assert_eq!(comparison.added_files, ["file.txt"]);
+                assert!(comparison.removed_files.is_empty());
+            }
+            _ => panic!("Expected ReviewCompareLoaded event, got: {event:?}"),
+        }
+    }
+
+    #[test]
+    fn test_rediff_review_of_a_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let commit = |files: &[(&str, Option<&str>)]| {
+            let mut index = repo.index().unwrap();
+            for (path, content) in files {
+                match content {
+                    Some(content) => {
+                        fs::write(temp_dir.path().join(path), content).unwrap();
+                        index.add_path(Path::new(path)).unwrap();
+                    }
+                    None => index.remove_path(Path::new(path)).unwrap(),
+                }
+            }
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+            let parents: Vec<&git2::Commit> = parent.iter().collect();
+            repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "Commit",
+                &tree,
+                &parents,
+            )
+            .unwrap()
+            .to_string()
+        };
+        let base_sha = commit(&[("main.rs", Some("a\n"))]);
+        let first_sha = commit(&[("main.rs", Some("a\nb\n")), ("old.rs", Some("old\n"))]);
+        let second_sha = commit(&[
+            ("main.rs", Some("a\nc\n")),
+            ("old.rs", None),
+            ("new.rs", Some("new\n")),
+        ]);
+        let review = |target_sha: &str| {
+            Review::builder()
+                .base_branch("main")
+                .target_branch("feature")
+                .base_sha_str(&base_sha)
+                .target_sha_str(target_sha)
+                .build()
+        };
+        let comparison =
+            ReviewService::compare(temp_dir.path(), &review(&first_sha), &review(&second_sha))
+                .unwrap();
+        assert_eq!(comparison.changed_files, ["main.rs"]);
+        assert_eq!(comparison.added_files, ["new.rs"]);
+        assert_eq!(comparison.removed_files, ["old.rs"]);
+
+        let rediff_review = comparison.rediff_review().unwrap();
+        assert_eq!(rediff_review.base_sha.as_deref(), Some(first_sha.as_str()));
+        assert_eq!(
+            rediff_review.target_sha.as_deref(),
+            Some(second_sha.as_str())
+        );
+        let file_rediff = |path: &str| {
+            GitService::get_diff_between_shas_with_progress(
+                temp_dir.path(),
+                rediff_review.base_sha.as_deref().unwrap(),
+                rediff_review.target_sha.as_deref().unwrap(),
+                Some(path),
+                |_, _| {},
+            )
+            .unwrap()
+        };
+
+        // Only the change of the latest iteration is left
+        let diff = file_rediff("main.rs");
+        assert_eq!(diff.file_count(), 1);
+        assert_eq!(diff.files[0].line_stats(), (1, 1));
+        assert!(diff.files[0].content().contains("-b\n+c\n"));
+        // Files in the diff of only one review are added or deleted
+        assert_eq!(
+            file_rediff("new.rs").files[0].content(),
+            "@@ -0,0 +1 @@\n+new\n"
+        );
+        let diff = file_rediff("old.rs");
+        assert!(diff.is_deleted("old.rs"));
+        assert_eq!(diff.files[0].content(), "@@ -1 +0,0 @@\n-old\n");
+    }
+
+    #[test]
+    fn test_rediff_review_without_target_shas() {
+        let patch_review = Review::builder().patch_path("/tmp/change.patch").build();
+        let review = Review::builder().target_sha_str("2222222").build();
+        let comparison = ReviewComparison {
+            review_a: Arc::new(patch_review),
+            review_b: Arc::new(review),
+            added_files: vec![],
+            removed_files: vec![],
+            changed_files: vec![],
+            unchanged_file_count: 0,
+        };
+
+        assert!(comparison.rediff_review().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handle_review_compare_load_without_shas() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let review_a = Review::builder().build();
+        let review_b = Review::builder().build();
+        review_a.save(database.pool()).await.unwrap();
+        review_b.save(database.pool()).await.unwrap();
+
+        ReviewService::handle_app_event(
+            &AppEvent::ReviewCompareLoad {
+                review_a_id: Arc::from(review_a.id.as_str()),
+                review_b_id: Arc::from(review_b.id.as_str()),
+            },
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewCompareLoadError(error)) => {
+                assert!(error.contains("has no SHAs to compare"));
+            }
+            _ => panic!("Expected ReviewCompareLoadError event, got: {event:?}"),
+        }
+    }
 }
@@ -0,0 +1,143 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use crate::{
+    database::Database,
+    event::{AppEvent, EventHandler},
+    models::{Review, SessionState},
+    services::{ServiceContext, ServiceHandler},
+};
+
+/// Service for loading the navigation state saved on the last exit
+pub struct SessionService;
+
+impl ServiceHandler for SessionService {
+    fn handle_app_event<'a>(
+        event: &'a AppEvent,
+        context: ServiceContext<'a>,
+    ) -> Pin<Box<dyn Future<Output = color_eyre::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            match event {
+                AppEvent::SessionLoad => {
+                    Self::handle_session_load(context.database, context.events).await;
+                }
+                _ => {
+                    // Event not handled by this service
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+impl SessionService {
+    /// Load the saved session state. Nothing is offered when there is none or when its review
+    /// was deleted in the meantime.
+    async fn handle_session_load(database: &Database, events: &mut EventHandler) {
+        let session_state = match SessionState::load(database.pool()).await {
+            Ok(Some(session_state)) => session_state,
+            Ok(None) => return,
+            Err(error) => {
+                log::error!("Failed to load the session state: {error}");
+                return;
+            }
+        };
+
+        match Review::find_by_id(database.pool(), &session_state.review_id).await {
+            Ok(Some(review)) => events.send(AppEvent::SessionLoaded {
+                session_state: Arc::new(session_state),
+                review: Arc::new(review),
+            }),
+            Ok(None) => log::info!(
+                "Review {} of the session state no longer exists",
+                session_state.review_id
+            ),
+            Err(error) => log::error!("Failed to load the review of the session state: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::SqlitePool;
+
+    use crate::event::Event;
+
+    async fn create_test_database() -> Database {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        Database::from_pool(pool)
+    }
+
+    async fn session_load(database: &Database, events: &mut EventHandler) {
+        SessionService::handle_app_event(
+            &AppEvent::SessionLoad,
+            ServiceContext {
+                database,
+                repo_path: ".",
+                events,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_session_load_without_saved_state() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+
+        session_load(&database, &mut events).await;
+
+        assert!(!events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_session_load_with_saved_state() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let review = Review::builder().build();
+        review.save(database.pool()).await.unwrap();
+        let session_state = SessionState {
+            review_id: review.id.clone(),
+            file_path: Some("src/main.rs".to_string()),
+            line_number: None,
+        };
+        SessionState::save(database.pool(), Some(&session_state))
+            .await
+            .unwrap();
+
+        session_load(&database, &mut events).await;
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SessionLoaded {
+                session_state: loaded_session_state,
+                review: loaded_review,
+            }) => {
+                assert_eq!(loaded_session_state.as_ref(), &session_state);
+                assert_eq!(loaded_review.id, review.id);
+            }
+            _ => panic!("Expected SessionLoaded event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_load_with_deleted_review() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let session_state = SessionState {
+            review_id: "deleted-review".to_string(),
+            file_path: None,
+            line_number: None,
+        };
+        SessionState::save(database.pool(), Some(&session_state))
+            .await
+            .unwrap();
+
+        session_load(&database, &mut events).await;
+
+        assert!(!events.has_pending_events());
+    }
+}
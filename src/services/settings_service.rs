@@ -0,0 +1,140 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use crate::{
+    database::Database,
+    event::{AppEvent, EventHandler},
+    models::Settings,
+    services::{ServiceContext, ServiceHandler},
+};
+
+/// Service for loading and persisting the app settings
+pub struct SettingsService;
+
+impl ServiceHandler for SettingsService {
+    fn handle_app_event<'a>(
+        event: &'a AppEvent,
+        context: ServiceContext<'a>,
+    ) -> Pin<Box<dyn Future<Output = color_eyre::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            match event {
+                AppEvent::SettingsLoad => {
+                    Self::handle_settings_load(context.database, context.events).await;
+                }
+                AppEvent::SettingsSave(settings) => {
+                    Self::handle_settings_save(context.database, context.events, settings).await;
+                }
+                _ => {
+                    // Event not handled by this service
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+impl SettingsService {
+    /// Load the settings from the database
+    async fn handle_settings_load(database: &Database, events: &mut EventHandler) {
+        match Settings::load(database.pool()).await {
+            Ok(settings) => events.send(AppEvent::SettingsLoaded(Arc::new(settings))),
+            Err(error) => events.send(AppEvent::SettingsLoadError(Arc::from(format!(
+                "Failed to load settings: {error}"
+            )))),
+        }
+    }
+
+    /// Persist the settings to the database
+    async fn handle_settings_save(
+        database: &Database,
+        events: &mut EventHandler,
+        settings: &Settings,
+    ) {
+        if let Err(error) = settings.save(database.pool()).await {
+            events.send(AppEvent::SettingsSaveError(Arc::from(format!(
+                "Failed to save settings: {error}"
+            ))));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::SqlitePool;
+
+    use crate::event::Event;
+
+    async fn create_test_database() -> Database {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        Database::from_pool(pool)
+    }
+
+    #[tokio::test]
+    async fn test_settings_load_defaults() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+
+        SettingsService::handle_app_event(
+            &AppEvent::SettingsLoad,
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SettingsLoaded(settings)) => {
+                assert_eq!(**settings, Settings::default());
+            }
+            _ => panic!("Expected SettingsLoaded event, got: {event:?}"),
+        }
+        assert!(!events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_settings_save_and_reload() {
+        let database = create_test_database().await;
+        let mut events = EventHandler::new_for_test();
+        let settings = Settings {
+            collapse_viewed_list: true,
+            ..Default::default()
+        };
+
+        SettingsService::handle_app_event(
+            &AppEvent::SettingsSave(Arc::new(settings.clone())),
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!events.has_pending_events());
+
+        SettingsService::handle_app_event(
+            &AppEvent::SettingsLoad,
+            ServiceContext {
+                database: &database,
+                repo_path: ".",
+                events: &mut events,
+            },
+        )
+        .await
+        .unwrap();
+
+        let event = events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SettingsLoaded(loaded)) => {
+                assert_eq!(**loaded, settings);
+            }
+            _ => panic!("Expected SettingsLoaded event, got: {event:?}"),
+        }
+    }
+}
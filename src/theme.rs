@@ -0,0 +1,137 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier},
+    widgets::BorderType,
+};
+
+/// Marker for the selected diff line when colors are disabled
+const NO_COLOR_LINE_SELECTION_INDICATOR: &str = ">";
+
+/// Visual theme of the UI
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Theme {
+    /// Render without colors for limited terminals and color-vision deficiency.
+    /// Distinctions that are otherwise only made by color get text markers, bold borders
+    /// and reversed highlights instead.
+    pub no_color: bool,
+}
+
+impl Theme {
+    pub fn new(no_color: bool) -> Self {
+        Self { no_color }
+    }
+
+    /// Resolve the theme from the `--no-color` flag and the `NO_COLOR` environment variable
+    /// (see https://no-color.org)
+    pub fn from_flag_and_env(no_color_flag: bool) -> Self {
+        let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self::new(no_color_flag || no_color_env)
+    }
+
+    /// Border type of a pane, active panes get bold borders when colors are disabled
+    pub fn border_type(&self, is_active: bool) -> BorderType {
+        if self.no_color && is_active {
+            BorderType::Thick
+        } else {
+            BorderType::Plain
+        }
+    }
+
+    /// Separator between the comment indicator and the text of a diff line
+    pub fn diff_line_separator(&self, is_selected: bool) -> &'static str {
+        if self.no_color && is_selected {
+            NO_COLOR_LINE_SELECTION_INDICATOR
+        } else {
+            " "
+        }
+    }
+
+    /// Turn the rendered buffer monochrome when colors are disabled.
+    /// Highlighted cells (any background besides the black dialog background) are reversed so
+    /// that selections stay visible.
+    pub fn apply(&self, area: Rect, buf: &mut Buffer) {
+        if !self.no_color {
+            return;
+        }
+
+        for position in area.positions() {
+            let Some(cell) = buf.cell_mut(position) else {
+                continue;
+            };
+            if !matches!(cell.bg, Color::Reset | Color::Black) {
+                cell.modifier.insert(Modifier::REVERSED);
+            }
+            cell.fg = Color::Reset;
+            cell.bg = Color::Reset;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_apply_without_no_color_keeps_colors() {
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(
+            0,
+            0,
+            "ab",
+            Style::default().fg(Color::Green).bg(Color::Blue),
+        );
+
+        Theme::default().apply(area, &mut buf);
+
+        assert_eq!(buf[(0, 0)].fg, Color::Green);
+        assert_eq!(buf[(0, 0)].bg, Color::Blue);
+        assert!(!buf[(0, 0)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_apply_no_color() {
+        let area = Rect::new(0, 0, 3, 1);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 0, "a", Style::default().fg(Color::Black).bg(Color::Blue));
+        buf.set_string(
+            1,
+            0,
+            "b",
+            Style::default().fg(Color::White).bg(Color::Black),
+        );
+        buf.set_string(
+            2,
+            0,
+            "c",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        );
+
+        Theme::new(true).apply(area, &mut buf);
+
+        for x in 0..3 {
+            assert_eq!(buf[(x, 0)].fg, Color::Reset);
+            assert_eq!(buf[(x, 0)].bg, Color::Reset);
+        }
+        assert!(buf[(0, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(!buf[(1, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(buf[(2, 0)].modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_border_type() {
+        assert_eq!(Theme::default().border_type(true), BorderType::Plain);
+        assert_eq!(Theme::new(true).border_type(true), BorderType::Thick);
+        assert_eq!(Theme::new(true).border_type(false), BorderType::Plain);
+    }
+
+    #[test]
+    fn test_diff_line_separator() {
+        assert_eq!(Theme::default().diff_line_separator(true), " ");
+        assert_eq!(Theme::new(true).diff_line_separator(true), ">");
+        assert_eq!(Theme::new(true).diff_line_separator(false), " ");
+    }
+}
@@ -1,7 +1,16 @@
-use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Paragraph, Widget, Wrap},
+};
 
 use crate::app::App;
 
+/// Smallest terminal size the views can be laid out in
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 10;
+
 impl Widget for &App {
     /// Renders the user interface widgets.
     ///
@@ -10,8 +19,81 @@ impl Widget for &App {
     // - https://docs.rs/ratatui/latest/ratatui/widgets/index.html
     // - https://github.com/ratatui/ratatui/tree/master/examples
     fn render(self, area: Rect, buf: &mut Buffer) {
-        for view in self.view_stack.iter() {
-            view.render(self, area, buf);
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            render_terminal_too_small(area, buf);
+        } else {
+            for view in self.view_stack.iter() {
+                view.render(self, area, buf);
+            }
         }
+        self.theme.apply(area, buf);
+    }
+}
+
+/// Explain why nothing is shown instead of rendering the views into areas that are too small
+fn render_terminal_too_small(area: Rect, buf: &mut Buffer) {
+    Paragraph::new(format!(
+        "Terminal too small (need at least {MIN_WIDTH}x{MIN_HEIGHT}, got {}x{})",
+        area.width, area.height
+    ))
+    .style(Style::default().fg(Color::Yellow))
+    .wrap(Wrap { trim: true })
+    .render(area, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use insta::assert_snapshot;
+    use ratatui::{Terminal, backend::TestBackend};
+    use sqlx::SqlitePool;
+
+    use crate::{database::Database, event::EventHandler, views::MainView};
+
+    async fn create_test_app() -> App {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        App {
+            running: true,
+            events: EventHandler::new_for_test(),
+            database: Database::from_pool(pool),
+            view_stack: vec![Box::new(MainView::new())],
+            repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
+        }
+    }
+
+    fn render_app(app: &App, width: u16, height: u16) -> TestBackend {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(app, frame.area()))
+            .unwrap();
+        terminal.backend().clone()
+    }
+
+    #[tokio::test]
+    async fn test_render_terminal_too_small() {
+        let app = create_test_app().await;
+
+        assert_snapshot!(render_app(&app, 30, 5));
+    }
+
+    #[tokio::test]
+    async fn test_render_minimum_size_renders_views() {
+        let app = create_test_app().await;
+
+        let backend = render_app(&app, MIN_WIDTH, MIN_HEIGHT);
+        let content: String = backend
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(!content.contains("Terminal too small"));
+        assert!(!content.trim().is_empty());
     }
 }
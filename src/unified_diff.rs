@@ -0,0 +1,385 @@
+//! Parser of unified diffs as written by `git diff`, `diff -u` or other tools, to review patch
+//! files without reproducing their changes with git2.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use crate::{
+    models::{Diff, DiffFile, DiffHunk},
+    services::git_service::BINARY_FILE_CHANGED,
+};
+
+/// Path of the missing side of an added or deleted file
+const DEV_NULL: &str = "/dev/null";
+
+/// Changes of one file while its section of the unified diff is parsed
+#[derive(Debug, Default)]
+struct FileSection {
+    old_path: Option<String>,
+    new_path: Option<String>,
+    /// Hunk headers and lines as they appear in the diff
+    content: String,
+    is_binary: bool,
+    /// Old and new mode of a file whose mode changed
+    mode_change: Option<(String, String)>,
+}
+
+impl FileSection {
+    /// Path of the file in the reviewed state, the old path for deleted files
+    fn path(&self) -> Option<&str> {
+        self.new_path.as_deref().or(self.old_path.as_deref())
+    }
+
+    fn into_diff_file(self) -> Option<DiffFile> {
+        let path = self.path()?.to_string();
+        if !self.content.is_empty() {
+            return Some(DiffFile::from_content(path, &self.content));
+        }
+        let description = if self.is_binary {
+            BINARY_FILE_CHANGED.to_string()
+        } else if let Some((old_mode, new_mode)) = &self.mode_change {
+            format!("mode changed {old_mode} → {new_mode}")
+        } else if self.old_path.is_none() {
+            "new empty file".to_string()
+        } else if self.new_path.is_none() {
+            "deleted empty file".to_string()
+        } else if let (Some(old_path), Some(_)) = (&self.old_path, &self.new_path)
+            && old_path != &path
+        {
+            format!("renamed from {old_path}")
+        } else {
+            return None;
+        };
+        Some(DiffFile::metadata(path, description))
+    }
+}
+
+/// Parse the text of a unified diff into a [`Diff`], files sorted by path like the diffs of the
+/// repository. Git extended headers (`diff --git`, renames, modes, binary files) are understood
+/// but not required. Anything that is neither a header nor part of a hunk, e.g. the commit
+/// message of a patch, is skipped.
+pub fn parse_unified_diff(text: &str) -> Diff {
+    let mut sections: Vec<FileSection> = vec![];
+    // Old and new lines left in the current hunk, so that removed lines starting with `--`
+    // are not taken for file headers
+    let mut hunk_remaining: (usize, usize) = (0, 0);
+
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+        if hunk_remaining != (0, 0)
+            && let Some(section) = sections.last_mut()
+        {
+            let (old, new) = &mut hunk_remaining;
+            let is_hunk_line = match trimmed.chars().next() {
+                Some(' ') | None => {
+                    *old = old.saturating_sub(1);
+                    *new = new.saturating_sub(1);
+                    true
+                }
+                Some('-') => {
+                    *old = old.saturating_sub(1);
+                    true
+                }
+                Some('+') => {
+                    *new = new.saturating_sub(1);
+                    true
+                }
+                Some('\\') => true,
+                _ => false,
+            };
+            if is_hunk_line {
+                // Empty context lines lose their space in some tools
+                if trimmed.is_empty() {
+                    section.content.push(' ');
+                }
+                section.content.push_str(line);
+                continue;
+            }
+            hunk_remaining = (0, 0);
+        }
+
+        if let Some(paths) = trimmed.strip_prefix("diff --git ") {
+            let (old_path, new_path) = split_git_header_paths(paths);
+            sections.push(FileSection {
+                old_path,
+                new_path,
+                ..Default::default()
+            });
+        } else if let Some(path) = trimmed.strip_prefix("--- ") {
+            // A plain unified diff starts a file with its old path
+            let has_git_header = sections
+                .last()
+                .is_some_and(|section| section.content.is_empty() && !section.is_binary);
+            if !has_git_header {
+                sections.push(FileSection::default());
+            }
+            if let Some(section) = sections.last_mut() {
+                section.old_path = header_path(path);
+            }
+        } else if let Some(path) = trimmed.strip_prefix("+++ ") {
+            if let Some(section) = sections.last_mut() {
+                section.new_path = header_path(path);
+            }
+        } else if let Some(path) = trimmed.strip_prefix("rename from ") {
+            if let Some(section) = sections.last_mut() {
+                section.old_path = Some(path.to_string());
+            }
+        } else if let Some(path) = trimmed.strip_prefix("rename to ") {
+            if let Some(section) = sections.last_mut() {
+                section.new_path = Some(path.to_string());
+            }
+        } else if let Some(mode) = trimmed.strip_prefix("old mode ") {
+            if let Some(section) = sections.last_mut() {
+                section.mode_change = Some((mode.to_string(), String::new()));
+            }
+        } else if let Some(mode) = trimmed.strip_prefix("new mode ") {
+            if let Some((_, new_mode)) = sections
+                .last_mut()
+                .and_then(|section| section.mode_change.as_mut())
+            {
+                *new_mode = mode.to_string();
+            }
+        } else if trimmed.starts_with("new file mode ") {
+            if let Some(section) = sections.last_mut() {
+                section.old_path = None;
+            }
+        } else if trimmed.starts_with("deleted file mode ") {
+            if let Some(section) = sections.last_mut() {
+                section.new_path = None;
+            }
+        } else if trimmed.starts_with("Binary files ") || trimmed == "GIT binary patch" {
+            if let Some(section) = sections.last_mut() {
+                section.is_binary = true;
+            }
+        } else if let Some(position) = DiffHunk::parse(0, trimmed)
+            && let Some(section) = sections.last_mut()
+        {
+            hunk_remaining = (position.old_lines, position.new_lines);
+            section.content.push_str(line);
+        } else if trimmed.starts_with('\\')
+            && let Some(section) = sections.last_mut()
+            && !section.content.is_empty()
+        {
+            // The no newline marker follows the last line of a hunk
+            section.content.push_str(line);
+        }
+    }
+
+    let renamed_paths: HashMap<String, String> = sections
+        .iter()
+        .filter_map(|section| match (&section.old_path, &section.new_path) {
+            (Some(old_path), Some(new_path)) if old_path != new_path => {
+                Some((old_path.clone(), new_path.clone()))
+            }
+            _ => None,
+        })
+        .collect();
+    let deleted_paths: HashSet<String> = sections
+        .iter()
+        .filter(|section| section.new_path.is_none())
+        .filter_map(|section| section.old_path.clone())
+        .collect();
+    let mut files: Vec<DiffFile> = sections
+        .into_iter()
+        .filter_map(FileSection::into_diff_file)
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Diff::from_files(files)
+        .with_renamed_paths(renamed_paths)
+        .with_deleted_paths(deleted_paths)
+}
+
+/// Read and parse a patch file
+pub fn read_patch_file(path: &Path) -> color_eyre::Result<Diff> {
+    let text = std::fs::read_to_string(path).map_err(|error| {
+        color_eyre::eyre::eyre!("Failed to read patch file {}: {error}", path.display())
+    })?;
+    Ok(parse_unified_diff(&text))
+}
+
+/// Path of a `---`/`+++` header without the `a/`/`b/` prefix and a trailing timestamp, `None`
+/// for the missing side of an added or deleted file
+fn header_path(header: &str) -> Option<String> {
+    let path = header.split('\t').next().unwrap_or(header).trim_end();
+    if path == DEV_NULL {
+        return None;
+    }
+    Some(strip_side_prefix(path).to_string())
+}
+
+/// Paths of a `diff --git a/old b/new` header. They are split in the middle when both halves
+/// are the same path, which is exact for a file that was not renamed even when its path
+/// contains ` b/`. Otherwise they are split at the first ` b/`, the rename and `---`/`+++`
+/// headers correct them.
+fn split_git_header_paths(paths: &str) -> (Option<String>, Option<String>) {
+    // In `a/<path> b/<path>` the separator follows the prefix and one of the paths
+    let middle = (paths.len() + "a/".len()).saturating_sub(" b/".len()) / 2;
+    if let (Some(old_path), Some(" b/"), Some(new_path)) = (
+        paths.get(..middle),
+        paths.get(middle..middle + " b/".len()),
+        paths.get(middle + " b/".len()..),
+    ) && old_path.strip_prefix("a/") == Some(new_path)
+    {
+        return (Some(new_path.to_string()), Some(new_path.to_string()));
+    }
+
+    let Some((old_path, new_path)) = paths.split_once(" b/") else {
+        return (None, None);
+    };
+    let old_path = strip_side_prefix(old_path).to_string();
+    (Some(old_path), Some(new_path.to_string()))
+}
+
+fn strip_side_prefix(path: &str) -> &str {
+    path.strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MULTI_FILE_DIFF: &str = "\
+From 1234 Mon Sep 17 00:00:00 2001
+Subject: [PATCH] Change things
+
+---
+diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    println!(\"old\");
++    println!(\"new\");
+ }
+@@ -10,2 +10,3 @@ fn helper() {
+ -- not a header
++added
+ end
+diff --git a/docs/new.md b/docs/new.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/docs/new.md
+@@ -0,0 +1 @@
++# New
+\\ No newline at end of file
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+diff --git a/before.rs b/after.rs
+similarity index 100%
+rename from before.rs
+rename to after.rs
+diff --git a/logo.png b/logo.png
+Binary files a/logo.png and b/logo.png differ
+diff --git a/run.sh b/run.sh
+old mode 100644
+new mode 100755
+--
+2.40.0
+";
+
+    #[test]
+    fn test_parse_multi_file_git_diff() {
+        let diff = parse_unified_diff(MULTI_FILE_DIFF);
+
+        let paths: Vec<&str> = diff.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "after.rs",
+                "docs/new.md",
+                "logo.png",
+                "old.txt",
+                "run.sh",
+                "src/main.rs"
+            ]
+        );
+
+        let main = &diff.files[5];
+        assert_eq!(
+            main.content(),
+            "@@ -1,3 +1,3 @@\n fn main() {\n-    println!(\"old\");\n+    println!(\"new\");\n }\n\
+             @@ -10,2 +10,3 @@ fn helper() {\n -- not a header\n+added\n end\n"
+        );
+        assert_eq!(main.line_stats(), (2, 1));
+        assert_eq!(main.hunks()[1].lines[0].new_lineno, Some(10));
+
+        assert_eq!(
+            diff.files[1].content(),
+            "@@ -0,0 +1 @@\n+# New\n\\ No newline at end of file\n"
+        );
+        assert_eq!(diff.files[3].content(), "@@ -1 +0,0 @@\n-gone\n");
+        assert!(diff.is_deleted("old.txt"));
+        assert_eq!(diff.files[0].content(), "renamed from before.rs");
+        assert_eq!(diff.old_path("after.rs"), Some("before.rs"));
+        assert_eq!(diff.files[2].content(), BINARY_FILE_CHANGED);
+        assert_eq!(diff.files[4].content(), "mode changed 100644 → 100755");
+    }
+
+    #[test]
+    fn test_parse_plain_unified_diff() {
+        let diff = parse_unified_diff(
+            "--- a.txt\t2024-01-01 10:00:00\n+++ a.txt\t2024-01-02 10:00:00\n@@ -1 +1 @@\n-a\n+b\n\
+             --- dir/b.txt\n+++ dir/b.txt\n@@ -1,2 +1,2 @@\n-x\n+y\n\n",
+        );
+
+        assert_eq!(diff.file_count(), 2);
+        assert_eq!(diff.files[0].path, "a.txt");
+        assert_eq!(diff.files[0].content(), "@@ -1 +1 @@\n-a\n+b\n");
+        assert_eq!(diff.files[1].path, "dir/b.txt");
+        // The empty context line gets its space back
+        assert_eq!(diff.files[1].content(), "@@ -1,2 +1,2 @@\n-x\n+y\n \n");
+    }
+
+    #[test]
+    fn test_parse_empty_added_and_deleted_files() {
+        let diff = parse_unified_diff(
+            "diff --git a/empty.txt b/empty.txt\nnew file mode 100644\nindex 0000000..e69de29\n\
+             diff --git a/gone.txt b/gone.txt\ndeleted file mode 100644\nindex e69de29..0000000\n",
+        );
+
+        assert_eq!(diff.file_count(), 2);
+        assert_eq!(diff.files[0].path, "empty.txt");
+        assert!(diff.files[0].metadata_only());
+        assert_eq!(diff.files[0].content(), "new empty file");
+        assert_eq!(diff.files[1].path, "gone.txt");
+        assert_eq!(diff.files[1].content(), "deleted empty file");
+        assert!(diff.is_deleted("gone.txt"));
+    }
+
+    #[test]
+    fn test_split_git_header_paths() {
+        assert_eq!(
+            split_git_header_paths("a/x b/y b/x b/y"),
+            (Some("x b/y".to_string()), Some("x b/y".to_string()))
+        );
+        assert_eq!(
+            split_git_header_paths("a/src/main.rs b/src/main.rs"),
+            (
+                Some("src/main.rs".to_string()),
+                Some("src/main.rs".to_string())
+            )
+        );
+        // Renamed files are split at the first separator, their rename headers follow
+        assert_eq!(
+            split_git_header_paths("a/before.rs b/after.rs"),
+            (Some("before.rs".to_string()), Some("after.rs".to_string()))
+        );
+        assert_eq!(split_git_header_paths("no paths"), (None, None));
+    }
+
+    #[test]
+    fn test_parse_no_diff() {
+        assert!(parse_unified_diff("Just some text\n").is_empty());
+    }
+}
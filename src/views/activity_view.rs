@@ -0,0 +1,385 @@
+#[cfg(test)]
+use std::any::Any;
+
+use std::sync::Arc;
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, BorderType, Clear, List, ListItem, ListState, Paragraph, Widget},
+};
+
+use crate::{
+    app::App,
+    event::{AppEvent, ReviewId},
+    models::Activity,
+    views::{KeyBinding, ViewHandler, ViewType, centered_rectangle},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActivitiesState {
+    Loading,
+    Loaded(Arc<[Activity]>),
+    Error(Arc<str>),
+}
+
+/// Popup with the timeline of actions taken on a review, newest first
+pub struct ActivityView {
+    pub review_id: Arc<ReviewId>,
+    pub activities: ActivitiesState,
+    pub list_state: ListState,
+}
+
+impl ActivityView {
+    pub fn new(review_id: Arc<ReviewId>) -> Self {
+        Self {
+            review_id,
+            activities: ActivitiesState::Loading,
+            list_state: ListState::default(),
+        }
+    }
+
+    fn activity_count(&self) -> usize {
+        match &self.activities {
+            ActivitiesState::Loaded(activities) => activities.len(),
+            ActivitiesState::Loading | ActivitiesState::Error(_) => 0,
+        }
+    }
+
+    fn select_next(&mut self) {
+        let count = self.activity_count();
+        if count == 0 {
+            return;
+        }
+
+        let selected = self.list_state.selected().unwrap_or(0);
+        self.list_state.select(Some((selected + 1).min(count - 1)));
+    }
+
+    fn select_previous(&mut self) {
+        if self.activity_count() == 0 {
+            return;
+        }
+
+        let selected = self.list_state.selected().unwrap_or(0);
+        self.list_state.select(Some(selected.saturating_sub(1)));
+    }
+}
+
+impl ViewHandler for ActivityView {
+    fn view_type(&self) -> ViewType {
+        ViewType::Activity
+    }
+
+    fn handle_key_events(&mut self, app: &mut App, key_event: &KeyEvent) -> color_eyre::Result<()> {
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                app.events.send(AppEvent::ViewClose);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.select_next();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_app_events(&mut self, _app: &mut App, event: &AppEvent) {
+        match event {
+            AppEvent::ActivitiesLoaded {
+                review_id,
+                activities,
+            } if *review_id == self.review_id => {
+                self.list_state
+                    .select((!activities.is_empty()).then_some(0));
+                self.activities = ActivitiesState::Loaded(Arc::clone(activities));
+            }
+            AppEvent::ActivitiesLoadError { review_id, error } if *review_id == self.review_id => {
+                self.list_state.select(None);
+                self.activities = ActivitiesState::Error(Arc::clone(error));
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self, _app: &App, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rectangle(80, 80, area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::bordered()
+            .title("Activity")
+            .border_type(BorderType::Rounded)
+            .style(Style::default().bg(Color::Black));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner);
+
+        match &self.activities {
+            ActivitiesState::Loading => {
+                Paragraph::new("Loading activity...")
+                    .style(Style::default().fg(Color::Gray))
+                    .render(chunks[0], buf);
+            }
+            ActivitiesState::Error(error) => {
+                Paragraph::new(error.as_ref())
+                    .style(Style::default().fg(Color::Red))
+                    .render(chunks[0], buf);
+            }
+            ActivitiesState::Loaded(activities) if activities.is_empty() => {
+                Paragraph::new("No activity yet")
+                    .style(Style::default().fg(Color::Gray))
+                    .render(chunks[0], buf);
+            }
+            ActivitiesState::Loaded(activities) => {
+                let items: Vec<ListItem> = activities
+                    .iter()
+                    .map(|activity| {
+                        ListItem::new(format!(
+                            "{}  {:<18} {}",
+                            activity.created_at.format("%Y-%m-%d %H:%M:%S"),
+                            activity.activity_type.label(),
+                            activity.detail
+                        ))
+                        .style(Style::default().fg(Color::White))
+                    })
+                    .collect();
+
+                let list = List::new(items)
+                    .highlight_style(Style::default().bg(Color::Blue).fg(Color::White))
+                    .highlight_symbol("► ");
+
+                let mut list_state = self.list_state;
+                ratatui::widgets::StatefulWidget::render(list, chunks[0], buf, &mut list_state);
+            }
+        }
+
+        Paragraph::new("Use ↑/↓ or j/k to scroll, Esc to close")
+            .style(Style::default().fg(Color::Gray))
+            .render(chunks[1], buf);
+    }
+
+    #[cfg(test)]
+    fn debug_state(&self) -> String {
+        format!(
+            "ActivityView(review_id: {}, selected: {:?}, activities: {})",
+            self.review_id,
+            self.list_state.selected(),
+            self.activity_count()
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_keybindings(&self) -> Arc<[KeyBinding]> {
+        Arc::new([
+            KeyBinding {
+                key: "↑ / k".to_string(),
+                description: "Previous activity".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('k'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "↓ / j".to_string(),
+                description: "Next activity".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('j'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "Esc / q".to_string(),
+                description: "Close".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Esc,
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+        ])
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+    use insta::assert_snapshot;
+    use sqlx::SqlitePool;
+
+    use crate::{
+        database::Database,
+        event::{Event, EventHandler},
+        models::ActivityType,
+        test_utils::{fixed_time, render_app_to_terminal_backend},
+        time_provider::MockTimeProvider,
+    };
+
+    async fn create_test_app() -> App {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        App {
+            running: true,
+            events: EventHandler::new_for_test(),
+            database: Database::from_pool(pool),
+            view_stack: vec![],
+            repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
+        }
+    }
+
+    fn test_activities() -> Arc<[Activity]> {
+        Arc::new([
+            Activity::new_with_time_provider(
+                "review-1",
+                ActivityType::CommentResolved,
+                "src/main.rs:3",
+                &MockTimeProvider::new(fixed_time() + Duration::minutes(2)),
+            ),
+            Activity::new_with_time_provider(
+                "review-1",
+                ActivityType::CommentAdded,
+                "src/main.rs:3",
+                &MockTimeProvider::new(fixed_time() + Duration::minutes(1)),
+            ),
+            Activity::new_with_time_provider(
+                "review-1",
+                ActivityType::ReviewCreated,
+                "main..feature",
+                &MockTimeProvider::new(fixed_time()),
+            ),
+        ])
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[tokio::test]
+    async fn test_activities_loaded_for_other_review_is_ignored() {
+        let mut app = create_test_app().await;
+        let mut view = ActivityView::new(Arc::from("review-1"));
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ActivitiesLoaded {
+                review_id: Arc::from("review-2"),
+                activities: test_activities(),
+            },
+        );
+        assert_eq!(view.activities, ActivitiesState::Loading);
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ActivitiesLoaded {
+                review_id: Arc::from("review-1"),
+                activities: test_activities(),
+            },
+        );
+        assert_eq!(view.activity_count(), 3);
+        assert_eq!(view.list_state.selected(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_navigation_stops_at_the_ends() {
+        let mut app = create_test_app().await;
+        let mut view = ActivityView::new(Arc::from("review-1"));
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ActivitiesLoaded {
+                review_id: Arc::from("review-1"),
+                activities: test_activities(),
+            },
+        );
+
+        view.handle_key_events(&mut app, &key(KeyCode::Char('k')))
+            .unwrap();
+        assert_eq!(view.list_state.selected(), Some(0));
+        for _ in 0..3 {
+            view.handle_key_events(&mut app, &key(KeyCode::Char('j')))
+                .unwrap();
+        }
+        assert_eq!(view.list_state.selected(), Some(2));
+        view.handle_key_events(&mut app, &key(KeyCode::Up)).unwrap();
+        assert_eq!(view.list_state.selected(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_esc_closes_view() {
+        let mut app = create_test_app().await;
+        let mut view = ActivityView::new(Arc::from("review-1"));
+
+        view.handle_key_events(&mut app, &key(KeyCode::Esc))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ViewClose)));
+    }
+
+    #[tokio::test]
+    async fn test_render_activities() {
+        let mut app = create_test_app().await;
+        let mut view = ActivityView::new(Arc::from("review-1"));
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ActivitiesLoaded {
+                review_id: Arc::from("review-1"),
+                activities: test_activities(),
+            },
+        );
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_render_no_activity() {
+        let mut app = create_test_app().await;
+        let mut view = ActivityView::new(Arc::from("review-1"));
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ActivitiesLoaded {
+                review_id: Arc::from("review-1"),
+                activities: Arc::new([]),
+            },
+        );
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+}
@@ -0,0 +1,640 @@
+#[cfg(test)]
+use std::any::Any;
+
+use std::sync::Arc;
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, BorderType, Clear, List, ListItem, ListState, Paragraph, Widget},
+};
+
+use crate::{
+    app::App,
+    event::{AppEvent, ReviewId},
+    models::{CHECKLIST_TEMPLATE_FILE_NAME, ChecklistItem},
+    views::{KeyBinding, ViewHandler, ViewType, centered_rectangle},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChecklistState {
+    Loading,
+    Loaded(Arc<[ChecklistItem]>),
+    Error(Arc<str>),
+}
+
+/// Text of a checklist item while it is typed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChecklistInput {
+    /// Item whose text is changed, `None` for a new item
+    pub item_id: Option<i64>,
+    pub text: String,
+}
+
+/// Popup with the checklist of a review, whose items are added, checked and edited in place
+pub struct ChecklistView {
+    pub review_id: Arc<ReviewId>,
+    pub items: ChecklistState,
+    pub list_state: ListState,
+    /// Item being added or edited, `None` while navigating the items
+    pub input: Option<ChecklistInput>,
+    /// Message shown at the bottom of the popup until the next key press
+    pub notice: Option<Arc<str>>,
+}
+
+impl ChecklistView {
+    pub fn new(review_id: Arc<ReviewId>) -> Self {
+        Self {
+            review_id,
+            items: ChecklistState::Loading,
+            list_state: ListState::default(),
+            input: None,
+            notice: None,
+        }
+    }
+
+    fn loaded_items(&self) -> &[ChecklistItem] {
+        match &self.items {
+            ChecklistState::Loaded(items) => items,
+            ChecklistState::Loading | ChecklistState::Error(_) => &[],
+        }
+    }
+
+    fn selected_item(&self) -> Option<&ChecklistItem> {
+        self.loaded_items().get(self.list_state.selected()?)
+    }
+
+    fn select_next(&mut self) {
+        let count = self.loaded_items().len();
+        if count == 0 {
+            return;
+        }
+
+        let selected = self.list_state.selected().unwrap_or(0);
+        self.list_state.select(Some((selected + 1).min(count - 1)));
+    }
+
+    fn select_previous(&mut self) {
+        if self.loaded_items().is_empty() {
+            return;
+        }
+
+        let selected = self.list_state.selected().unwrap_or(0);
+        self.list_state.select(Some(selected.saturating_sub(1)));
+    }
+
+    fn toggle_selected_item(&self, app: &mut App) {
+        if let Some(item) = self.selected_item() {
+            app.events.send(AppEvent::ChecklistItemToggle {
+                review_id: Arc::clone(&self.review_id),
+                item_id: item.id,
+            });
+        }
+    }
+
+    fn delete_selected_item(&self, app: &mut App) {
+        if let Some(item) = self.selected_item() {
+            app.events.send(AppEvent::ChecklistItemDelete {
+                review_id: Arc::clone(&self.review_id),
+                item_id: item.id,
+            });
+        }
+    }
+
+    fn edit_selected_item(&mut self) {
+        if let Some(item) = self.selected_item() {
+            self.input = Some(ChecklistInput {
+                item_id: Some(item.id),
+                text: item.text.clone(),
+            });
+        }
+    }
+
+    /// Add the typed item or change the text of the edited one. Empty texts are discarded.
+    fn submit_input(&mut self, app: &mut App) {
+        let Some(input) = self.input.take() else {
+            return;
+        };
+        if input.text.trim().is_empty() {
+            return;
+        }
+
+        let review_id = Arc::clone(&self.review_id);
+        let text = Arc::from(input.text.as_str());
+        app.events.send(match input.item_id {
+            Some(item_id) => AppEvent::ChecklistItemUpdate {
+                review_id,
+                item_id,
+                text,
+            },
+            None => AppEvent::ChecklistItemCreate { review_id, text },
+        });
+    }
+
+    fn handle_input_key_events(&mut self, app: &mut App, key_event: &KeyEvent) {
+        let Some(input) = &mut self.input else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Esc => self.input = None,
+            KeyCode::Enter => self.submit_input(app),
+            KeyCode::Backspace => {
+                input.text.pop();
+            }
+            KeyCode::Char(char) => input.text.push(char),
+            _ => {}
+        }
+    }
+
+    /// Completion of the checklist like `3/5`
+    fn completion(&self) -> Option<String> {
+        let items = self.loaded_items();
+        (!items.is_empty()).then(|| {
+            let checked = items.iter().filter(|item| item.checked).count();
+            format!("{checked}/{}", items.len())
+        })
+    }
+
+    fn render_input(input: &ChecklistInput, area: Rect, buf: &mut Buffer) {
+        let title = if input.item_id.is_some() {
+            "Edit item (Enter to save, Esc to cancel)"
+        } else {
+            "New item (Enter to add, Esc to cancel)"
+        };
+        Paragraph::new(format!("{}█", input.text))
+            .block(
+                Block::bordered()
+                    .title(title)
+                    .border_style(Style::default().fg(Color::Yellow)),
+            )
+            .render(area, buf);
+    }
+}
+
+impl ViewHandler for ChecklistView {
+    fn view_type(&self) -> ViewType {
+        ViewType::Checklist
+    }
+
+    fn handle_key_events(&mut self, app: &mut App, key_event: &KeyEvent) -> color_eyre::Result<()> {
+        self.notice = None;
+        if self.input.is_some() {
+            self.handle_input_key_events(app, key_event);
+            return Ok(());
+        }
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                app.events.send(AppEvent::ViewClose);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.select_next();
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => self.toggle_selected_item(app),
+            KeyCode::Char('a') => {
+                self.input = Some(ChecklistInput {
+                    item_id: None,
+                    text: String::new(),
+                });
+            }
+            KeyCode::Char('e') => self.edit_selected_item(),
+            KeyCode::Char('d') => self.delete_selected_item(app),
+            KeyCode::Char('t') => app.events.send(AppEvent::ChecklistSeed {
+                review_id: Arc::clone(&self.review_id),
+            }),
+            KeyCode::Char('?') => app.events.send(AppEvent::HelpOpen(self.get_keybindings())),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_app_events(&mut self, app: &mut App, event: &AppEvent) {
+        match event {
+            AppEvent::ChecklistLoaded { review_id, items } if *review_id == self.review_id => {
+                // Keep the selection where it was when the checklist is loaded after a change
+                let selected = self
+                    .list_state
+                    .selected()
+                    .unwrap_or(0)
+                    .min(items.len().saturating_sub(1));
+                self.list_state
+                    .select((!items.is_empty()).then_some(selected));
+                self.items = ChecklistState::Loaded(Arc::clone(items));
+            }
+            AppEvent::ChecklistLoadError { review_id, error } if *review_id == self.review_id => {
+                self.list_state.select(None);
+                self.items = ChecklistState::Error(Arc::clone(error));
+            }
+            AppEvent::ChecklistChanged { review_id } if *review_id == self.review_id => {
+                app.events.send(AppEvent::ChecklistLoad {
+                    review_id: Arc::clone(review_id),
+                });
+            }
+            AppEvent::ChecklistChangeError { review_id, error } if *review_id == self.review_id => {
+                self.notice = Some(Arc::clone(error));
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self, _app: &App, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rectangle(80, 80, area);
+
+        Clear.render(popup_area, buf);
+
+        let title = match self.completion() {
+            Some(completion) => format!("Checklist {completion}"),
+            None => "Checklist".to_string(),
+        };
+        let mut block = Block::bordered()
+            .title(title)
+            .border_type(BorderType::Rounded)
+            .style(Style::default().bg(Color::Black));
+        if let Some(notice) = &self.notice {
+            block = block.title_bottom(
+                Line::from(format!(" {notice} ")).style(Style::default().fg(Color::Yellow)),
+            );
+        }
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let input_height = if self.input.is_some() { 3 } else { 0 };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(input_height),
+                Constraint::Length(2),
+            ])
+            .split(inner);
+
+        match &self.items {
+            ChecklistState::Loading => {
+                Paragraph::new("Loading checklist...")
+                    .style(Style::default().fg(Color::Gray))
+                    .render(chunks[0], buf);
+            }
+            ChecklistState::Error(error) => {
+                Paragraph::new(error.as_ref())
+                    .style(Style::default().fg(Color::Red))
+                    .render(chunks[0], buf);
+            }
+            ChecklistState::Loaded(items) if items.is_empty() => {
+                Paragraph::new(format!(
+                    "No checklist items - Press 'a' to add one or 't' to add the items of {CHECKLIST_TEMPLATE_FILE_NAME}"
+                ))
+                .style(Style::default().fg(Color::Yellow))
+                .render(chunks[0], buf);
+            }
+            ChecklistState::Loaded(items) => {
+                let items: Vec<ListItem> = items
+                    .iter()
+                    .map(|item| {
+                        let (checkbox, color) = if item.checked {
+                            ("[x]", Color::Green)
+                        } else {
+                            ("[ ]", Color::White)
+                        };
+                        ListItem::new(format!("{checkbox} {}", item.text))
+                            .style(Style::default().fg(color))
+                    })
+                    .collect();
+
+                let list = List::new(items)
+                    .highlight_style(Style::default().bg(Color::Blue).fg(Color::White))
+                    .highlight_symbol("► ");
+
+                let mut list_state = self.list_state;
+                ratatui::widgets::StatefulWidget::render(list, chunks[0], buf, &mut list_state);
+            }
+        }
+
+        if let Some(input) = &self.input {
+            Self::render_input(input, chunks[1], buf);
+        }
+
+        Paragraph::new(
+            "Use ↑/↓ or j/k to select, Space to check, a to add, e to edit, d to delete, Esc to close",
+        )
+        .style(Style::default().fg(Color::Gray))
+        .render(chunks[2], buf);
+    }
+
+    #[cfg(test)]
+    fn debug_state(&self) -> String {
+        format!(
+            "ChecklistView(review_id: {}, selected: {:?}, items: {}, input: {:?})",
+            self.review_id,
+            self.list_state.selected(),
+            self.loaded_items().len(),
+            self.input
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_keybindings(&self) -> Arc<[KeyBinding]> {
+        let key_binding = |key: &str, description: &str, code: KeyCode| KeyBinding {
+            key: key.to_string(),
+            description: description.to_string(),
+            key_event: KeyEvent {
+                code,
+                modifiers: KeyModifiers::empty(),
+                kind: KeyEventKind::Press,
+                state: KeyEventState::empty(),
+            },
+        };
+        Arc::new([
+            key_binding("↑ / k", "Previous item", KeyCode::Char('k')),
+            key_binding("↓ / j", "Next item", KeyCode::Char('j')),
+            key_binding("Space / Enter", "Check or uncheck item", KeyCode::Char(' ')),
+            key_binding("a", "Add item", KeyCode::Char('a')),
+            key_binding("e", "Edit item", KeyCode::Char('e')),
+            key_binding("d", "Delete item", KeyCode::Char('d')),
+            key_binding(
+                "t",
+                "Add the items of the checklist template",
+                KeyCode::Char('t'),
+            ),
+            key_binding("Esc / q", "Close", KeyCode::Esc),
+        ])
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use insta::assert_snapshot;
+    use sqlx::SqlitePool;
+
+    use crate::{
+        database::Database,
+        event::{Event, EventHandler},
+        event_handler::EventProcessor,
+        models::Review,
+        test_utils::{fixed_time, render_app_to_terminal_backend},
+        time_provider::MockTimeProvider,
+    };
+
+    async fn create_test_app() -> App {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        App {
+            running: true,
+            events: EventHandler::new_for_test(),
+            database: Database::from_pool(pool),
+            view_stack: vec![],
+            repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
+        }
+    }
+
+    fn test_items() -> Arc<[ChecklistItem]> {
+        let time_provider = MockTimeProvider::new(fixed_time());
+        let mut tests_added =
+            ChecklistItem::new_with_time_provider("review-1", "Tests added", &time_provider);
+        tests_added.id = 2;
+        tests_added.checked = true;
+        let mut security_reviewed =
+            ChecklistItem::new_with_time_provider("review-1", "Security reviewed", &time_provider);
+        security_reviewed.id = 1;
+        Arc::new([security_reviewed, tests_added])
+    }
+
+    fn load(view: &mut ChecklistView, app: &mut App, items: Arc<[ChecklistItem]>) {
+        view.handle_app_events(
+            app,
+            &AppEvent::ChecklistLoaded {
+                review_id: Arc::from("review-1"),
+                items,
+            },
+        );
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(view: &mut ChecklistView, app: &mut App, text: &str) {
+        for char in text.chars() {
+            view.handle_key_events(app, &key(KeyCode::Char(char)))
+                .unwrap();
+        }
+    }
+
+    async fn process_events(app: &mut App) {
+        while let Some(event) = app.events.try_recv() {
+            EventProcessor::process_event(app, event).await.unwrap();
+        }
+    }
+
+    fn checklist_view(app: &App) -> &ChecklistView {
+        app.view_stack
+            .last()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ChecklistView>()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_checklist_loaded_for_other_review_is_ignored() {
+        let mut app = create_test_app().await;
+        let mut view = ChecklistView::new(Arc::from("review-1"));
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ChecklistLoaded {
+                review_id: Arc::from("review-2"),
+                items: test_items(),
+            },
+        );
+        assert_eq!(view.items, ChecklistState::Loading);
+
+        load(&mut view, &mut app, test_items());
+        assert_eq!(view.loaded_items().len(), 2);
+        assert_eq!(view.list_state.selected(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_keys_change_the_selected_item() {
+        let mut app = create_test_app().await;
+        let mut view = ChecklistView::new(Arc::from("review-1"));
+        load(&mut view, &mut app, test_items());
+
+        view.handle_key_events(&mut app, &key(KeyCode::Char('j')))
+            .unwrap();
+        view.handle_key_events(&mut app, &key(KeyCode::Char(' ')))
+            .unwrap();
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ChecklistItemToggle { review_id, item_id }) => {
+                assert_eq!(review_id.as_ref(), "review-1");
+                assert_eq!(*item_id, 2);
+            }
+            _ => panic!("Expected ChecklistItemToggle event, got: {event:?}"),
+        }
+
+        view.handle_key_events(&mut app, &key(KeyCode::Char('e')))
+            .unwrap();
+        // Keys are typed into the item while it is edited
+        view.handle_key_events(&mut app, &key(KeyCode::Char('d')))
+            .unwrap();
+        view.handle_key_events(&mut app, &key(KeyCode::Backspace))
+            .unwrap();
+        type_text(&mut view, &mut app, " and docs");
+        view.handle_key_events(&mut app, &key(KeyCode::Enter))
+            .unwrap();
+        assert_eq!(view.input, None);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ChecklistItemUpdate { item_id, text, .. }) => {
+                assert_eq!(*item_id, 2);
+                assert_eq!(text.as_ref(), "Tests added and docs");
+            }
+            _ => panic!("Expected ChecklistItemUpdate event, got: {event:?}"),
+        }
+
+        view.handle_key_events(&mut app, &key(KeyCode::Char('d')))
+            .unwrap();
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(
+            *event,
+            Event::App(AppEvent::ChecklistItemDelete { item_id: 2, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_empty_item_is_not_added() {
+        let mut app = create_test_app().await;
+        let mut view = ChecklistView::new(Arc::from("review-1"));
+        load(&mut view, &mut app, Arc::new([]));
+
+        view.handle_key_events(&mut app, &key(KeyCode::Char('a')))
+            .unwrap();
+        type_text(&mut view, &mut app, "  ");
+        view.handle_key_events(&mut app, &key(KeyCode::Enter))
+            .unwrap();
+
+        assert_eq!(view.input, None);
+        assert!(app.events.try_recv().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_add_and_check_items_are_persisted() {
+        let mut app = create_test_app().await;
+        let review = Review::builder().build();
+        review.save(app.database.pool()).await.unwrap();
+        app.events.send(AppEvent::ChecklistOpen {
+            review_id: Arc::from(review.id.as_str()),
+        });
+        process_events(&mut app).await;
+
+        for text in ["Security reviewed", "Tests added"] {
+            app.handle_key_events(&key(KeyCode::Char('a'))).unwrap();
+            for char in text.chars() {
+                app.handle_key_events(&key(KeyCode::Char(char))).unwrap();
+            }
+            app.handle_key_events(&key(KeyCode::Enter)).unwrap();
+            process_events(&mut app).await;
+        }
+        app.handle_key_events(&key(KeyCode::Char('j'))).unwrap();
+        app.handle_key_events(&key(KeyCode::Char(' '))).unwrap();
+        process_events(&mut app).await;
+
+        let view = checklist_view(&app);
+        assert_eq!(view.completion().as_deref(), Some("1/2"));
+        // The selection stays on the checked item when the checklist is loaded again
+        assert_eq!(view.list_state.selected(), Some(1));
+        let items = ChecklistItem::find_for_review(app.database.pool(), &review.id)
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].text, "Security reviewed");
+        assert!(!items[0].checked);
+        assert_eq!(items[1].text, "Tests added");
+        assert!(items[1].checked);
+    }
+
+    #[tokio::test]
+    async fn test_change_error_is_shown_as_notice() {
+        let mut app = create_test_app().await;
+        let mut view = ChecklistView::new(Arc::from("review-1"));
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ChecklistChangeError {
+                review_id: Arc::from("review-1"),
+                error: Arc::from("Failed to change the checklist: no items in .glr-checklist"),
+            },
+        );
+        assert_eq!(
+            view.notice.as_deref(),
+            Some("Failed to change the checklist: no items in .glr-checklist")
+        );
+
+        view.handle_key_events(&mut app, &key(KeyCode::Char('j')))
+            .unwrap();
+        assert_eq!(view.notice, None);
+    }
+
+    #[tokio::test]
+    async fn test_esc_closes_view() {
+        let mut app = create_test_app().await;
+        let mut view = ChecklistView::new(Arc::from("review-1"));
+
+        view.handle_key_events(&mut app, &key(KeyCode::Esc))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ViewClose)));
+    }
+
+    #[tokio::test]
+    async fn test_render_checklist() {
+        let mut app = create_test_app().await;
+        let mut view = ChecklistView::new(Arc::from("review-1"));
+        load(&mut view, &mut app, test_items());
+        view.handle_key_events(&mut app, &key(KeyCode::Char('a')))
+            .unwrap();
+        type_text(&mut view, &mut app, "Docs updated");
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_render_empty_checklist() {
+        let mut app = create_test_app().await;
+        let mut view = ChecklistView::new(Arc::from("review-1"));
+        load(&mut view, &mut app, Arc::new([]));
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+}
@@ -1,7 +1,10 @@
 #[cfg(test)]
 use std::any::Any;
 
-use std::sync::Arc;
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
 
 use ratatui::{
     buffer::Buffer,
@@ -17,12 +20,17 @@ use ratatui::{
 use crate::{
     app::App,
     event::AppEvent,
-    models::Comment,
+    models::{
+        Comment, CommentId, CommentPriority, CommentStatus, DEFAULT_MAX_COMMENT_LENGTH, Density,
+    },
     services::{CommentsLoadParams, CommentsLoadingState},
-    views::{KeyBinding, ViewHandler, ViewType},
+    views::{KeyBinding, ViewHandler, ViewType, comment_priority_style, comment_status_color},
 };
 
-#[derive(Debug, Clone)]
+/// Number of characters of the answered comment shown while typing a reply
+const REPLY_CONTEXT_LENGTH: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CommentTarget {
     File {
         review_id: String,
@@ -87,16 +95,17 @@ impl CommentTarget {
         matches!(self, CommentTarget::Line { .. })
     }
 
-    pub fn display_title(&self) -> String {
-        match self {
-            CommentTarget::File { file_path, .. } => {
-                format!("Comments for {file_path}")
+    /// Title of the comments, with the old path of a renamed or copied file before its path
+    pub fn display_title(&self, old_file_path: Option<&str>) -> String {
+        let file_path = match old_file_path {
+            Some(old_file_path) if old_file_path != self.file_path() => {
+                format!("{old_file_path} → {}", self.file_path())
             }
-            CommentTarget::Line {
-                file_path,
-                line_number,
-                ..
-            } => {
+            _ => self.file_path().to_string(),
+        };
+        match self {
+            CommentTarget::File { .. } => format!("Comments for {file_path}"),
+            CommentTarget::Line { line_number, .. } => {
                 format!("Comments for {file_path}:{line_number}")
             }
         }
@@ -120,8 +129,39 @@ pub struct CommentsView {
     comments: Arc<Vec<Comment>>,
     /// Current focus state (input field or comments list)
     focus_state: FocusState,
-    /// Currently selected comment index (for navigation)
+    /// Currently selected comment index into the visible comments (for navigation)
     selected_comment_index: Option<usize>,
+    /// Comment that was selected last, to select it again after reloads and focus switches
+    /// even when its index changed
+    selected_comment_id: Option<CommentId>,
+    /// Whether resolved comments are listed
+    show_resolved: bool,
+    /// Whether the input field shrinks to a single line while the comments list is focused
+    collapse_input: bool,
+    /// Maximum number of characters of a new comment
+    max_comment_length: usize,
+    /// Whether the last typed character was dropped because of the maximum comment length,
+    /// shown until the next key press
+    limit_reached: bool,
+    /// Comment that is resolved with the note typed into the input field
+    resolving_comment_id: Option<CommentId>,
+    /// Comment whose thread the reply typed into the input field is added to
+    replying_to_comment_id: Option<CommentId>,
+    /// New comment text that was in the input field before typing the resolution note or reply
+    comment_draft: String,
+    /// Whether the comments of a file target are grouped under a header per line, with the
+    /// file comments first
+    group_by_line: bool,
+    /// Priority of the new comment, cycled with Ctrl+P
+    priority: CommentPriority,
+    /// Sorted paths of the files of the review that have comments, to move a file target to
+    /// the next or previous of them
+    comment_files: Arc<[String]>,
+    /// Whether the comments are docked beside the diff of the review details instead of being
+    /// a view of their own
+    docked: bool,
+    /// Path of the file before it was renamed or copied, shown in the title
+    old_file_path: Option<String>,
 }
 
 impl CommentsView {
@@ -136,6 +176,19 @@ impl CommentsView {
             comments: Arc::new(vec![]),
             focus_state: FocusState::Input,
             selected_comment_index: None,
+            selected_comment_id: None,
+            show_resolved: true,
+            collapse_input: false,
+            max_comment_length: DEFAULT_MAX_COMMENT_LENGTH,
+            limit_reached: false,
+            resolving_comment_id: None,
+            replying_to_comment_id: None,
+            comment_draft: String::new(),
+            group_by_line: true,
+            priority: CommentPriority::default(),
+            comment_files: Arc::new([]),
+            docked: false,
+            old_file_path: None,
         }
     }
 
@@ -151,6 +204,67 @@ impl CommentsView {
             comments: Arc::new(vec![]),
             focus_state: FocusState::Input,
             selected_comment_index: None,
+            selected_comment_id: None,
+            show_resolved: true,
+            collapse_input: false,
+            max_comment_length: DEFAULT_MAX_COMMENT_LENGTH,
+            limit_reached: false,
+            resolving_comment_id: None,
+            replying_to_comment_id: None,
+            comment_draft: String::new(),
+            group_by_line: true,
+            priority: CommentPriority::default(),
+            comment_files: Arc::new([]),
+            docked: false,
+            old_file_path: None,
+        }
+    }
+
+    /// Set whether resolved comments are listed
+    pub fn with_show_resolved(mut self, show_resolved: bool) -> Self {
+        self.show_resolved = show_resolved;
+        self
+    }
+
+    /// Set the maximum number of characters of a new comment
+    pub fn with_max_comment_length(mut self, max_comment_length: usize) -> Self {
+        self.max_comment_length = max_comment_length;
+        self
+    }
+
+    /// Set whether the comments are docked beside the diff of the review details
+    pub fn with_docked(mut self, docked: bool) -> Self {
+        self.docked = docked;
+        self
+    }
+
+    /// Set the path of the file before it was renamed or copied
+    pub fn with_old_file_path(mut self, old_file_path: Option<String>) -> Self {
+        self.old_file_path = old_file_path;
+        self
+    }
+
+    /// Restore the unsubmitted comment text of a previous comments view of the same target
+    pub fn with_comment_drafts(mut self, comment_drafts: &HashMap<CommentTarget, String>) -> Self {
+        if let Some(draft) = comment_drafts.get(&self.target) {
+            self.input_text = draft.clone();
+        }
+        self
+    }
+
+    /// Remember the unsubmitted comment text for the next comments view of the same target.
+    /// While a resolution note or reply is typed, the comment text is the stashed draft.
+    fn save_comment_draft(&self, app: &mut App) {
+        let draft = if self.is_comment_draft_stashed() {
+            &self.comment_draft
+        } else {
+            &self.input_text
+        };
+        if draft.trim().is_empty() {
+            app.comment_drafts.remove(&self.target);
+        } else {
+            app.comment_drafts
+                .insert(self.target.clone(), draft.clone());
         }
     }
 
@@ -161,6 +275,23 @@ impl CommentsView {
 
     fn handle_enter(&mut self, app: &mut App) {
         if self.focus_state != FocusState::Input {
+            self.go_to_selected_comment_line(app);
+            return;
+        }
+
+        if let Some(comment_id) = self.resolving_comment_id.take() {
+            let note = self.input_text.trim();
+            app.events.send(AppEvent::CommentMarkResolved {
+                comment_id: comment_id.into(),
+                resolution_note: (!note.is_empty()).then(|| Arc::from(note)),
+            });
+            self.input_text = std::mem::take(&mut self.comment_draft);
+            self.switch_focus_to_comments();
+            return;
+        }
+
+        if self.replying_to_comment_id.is_some() {
+            self.send_reply(app);
             return;
         }
 
@@ -176,6 +307,8 @@ impl CommentsView {
                         file_path: file_path.clone().into(),
                         line_number: None,
                         content: self.input_text.trim().to_string().into(),
+                        priority: self.priority,
+                        parent_id: None,
                     });
                 }
                 CommentTarget::Line {
@@ -188,18 +321,23 @@ impl CommentsView {
                         file_path: file_path.clone().into(),
                         line_number: Some(*line_number),
                         content: self.input_text.trim().to_string().into(),
+                        priority: self.priority,
+                        parent_id: None,
                     });
                 }
             }
 
-            // Clear the input
+            // Clear the input and start the next comment with the default priority
             self.input_text.clear();
+            self.priority = CommentPriority::default();
         }
     }
 
     /// Switch focus between input field and comments list
     fn handle_tab(&mut self) {
-        if self.focus_state == FocusState::Input {
+        if self.is_comment_draft_stashed() {
+            self.restore_comment_draft();
+        } else if self.focus_state == FocusState::Input {
             self.switch_focus_to_comments();
         } else {
             self.switch_focus_to_input();
@@ -214,23 +352,198 @@ impl CommentsView {
         self.input_text.pop();
     }
 
+    /// Close the view, docked comments are closed by the review details they are docked in
     fn close(&self, app: &mut App) {
-        app.events.send(AppEvent::ViewClose);
+        app.events.send(if self.docked {
+            AppEvent::CommentsDockedClose
+        } else {
+            AppEvent::ViewClose
+        });
+    }
+
+    /// Cancel typing a resolution note or reply while it is typed, otherwise close the view
+    fn handle_esc(&mut self, app: &mut App) {
+        if self.is_comment_draft_stashed() {
+            self.restore_comment_draft();
+        } else {
+            self.close(app);
+        }
+    }
+
+    /// Close the comments and jump to the line of the selected comment in the diff of the
+    /// review details below
+    fn go_to_selected_comment_line(&self, app: &mut App) {
+        let Some(comment) = self.get_selected_comment() else {
+            return;
+        };
+        let Some(line_number) = comment.line_number else {
+            return;
+        };
+
+        self.close(app);
+        app.events.send(AppEvent::ReviewDiffJump {
+            review_id: self.target.review_id().into(),
+            file_path: self.target.file_path().into(),
+            line_number,
+        });
+    }
+
+    /// Copy the locator of the selected comment, e.g. to reference it in a commit message
+    fn copy_selected_comment_locator(&self, app: &mut App) {
+        if let Some(comment) = self.get_selected_comment() {
+            app.events
+                .send(AppEvent::ClipboardCopy(Arc::from(comment.locator())));
+        }
+    }
+
+    /// Move a file target to the next (or previous) file of the review that has comments and
+    /// load its comments. Line targets stay on their line.
+    fn switch_comment_file(&mut self, app: &mut App, next: bool) {
+        let CommentTarget::File {
+            review_id,
+            file_path,
+        } = &self.target
+        else {
+            return;
+        };
+        let comment_file = if next {
+            self.comment_files.iter().find(|path| *path > file_path)
+        } else {
+            self.comment_files
+                .iter()
+                .rev()
+                .find(|path| *path < file_path)
+        };
+        let Some(comment_file) = comment_file else {
+            return;
+        };
+
+        self.target = CommentTarget::File {
+            review_id: review_id.clone(),
+            file_path: comment_file.clone(),
+        };
+        self.old_file_path = None;
+        self.input_text = app
+            .comment_drafts
+            .get(&self.target)
+            .cloned()
+            .unwrap_or_default();
+        self.loading_state = CommentsLoadingState::Init;
+        self.comments = Arc::new(vec![]);
+        self.selected_comment_index = None;
+        self.selected_comment_id = None;
+        self.request_comments_reload(app);
+    }
+
+    /// Whether the listed comments are grouped by line
+    fn is_grouped_by_line(&self) -> bool {
+        self.group_by_line && self.target.is_file_target()
+    }
+
+    /// Toggle grouping the comments by line, keeping the selected comment selected
+    fn toggle_group_by_line(&mut self) {
+        self.remember_selected_comment();
+        self.group_by_line = !self.group_by_line;
+        if let Some(index) = self.remembered_comment_index() {
+            self.selected_comment_index = Some(index);
+        }
+    }
+
+    /// Start typing a resolution note for the selected comment in the input field.
+    /// Already resolved comments are ignored, `r` reopens them.
+    fn start_resolution_note(&mut self) {
+        let Some(comment) = self.get_selected_comment() else {
+            return;
+        };
+        if comment.resolved {
+            return;
+        }
+
+        self.resolving_comment_id = Some(comment.id.clone());
+        self.comment_draft = std::mem::take(&mut self.input_text);
+        self.switch_focus_to_input();
+    }
+
+    /// Start typing a reply to the selected comment in the input field. Replies are added to
+    /// the thread of the comment, so replying to a reply answers its parent.
+    fn start_reply(&mut self) {
+        let Some(comment) = self.get_selected_comment() else {
+            return;
+        };
+
+        self.replying_to_comment_id = Some(comment.parent_id.clone().unwrap_or(comment.id.clone()));
+        self.comment_draft = std::mem::take(&mut self.input_text);
+        self.switch_focus_to_input();
+    }
+
+    /// Comment the typed reply is added to, if it is still loaded
+    fn replying_to_comment(&self) -> Option<&Comment> {
+        let comment_id = self.replying_to_comment_id.as_ref()?;
+        self.comments
+            .iter()
+            .find(|comment| &comment.id == comment_id)
+    }
+
+    /// Add the typed reply on the line of the comment it answers. An empty reply is ignored.
+    fn send_reply(&mut self, app: &mut App) {
+        if self.input_text.trim().is_empty() {
+            return;
+        }
+        let Some(parent) = self.replying_to_comment() else {
+            self.restore_comment_draft();
+            return;
+        };
+
+        app.events.send(AppEvent::CommentCreate {
+            review_id: parent.review_id.clone().into(),
+            file_path: parent.file_path.clone().into(),
+            line_number: parent.line_number,
+            content: self.input_text.trim().to_string().into(),
+            priority: self.priority,
+            parent_id: Some(parent.id.clone().into()),
+        });
+        self.priority = CommentPriority::default();
+        self.restore_comment_draft();
+    }
+
+    /// Whether the new comment text is stashed while a resolution note or reply is typed
+    fn is_comment_draft_stashed(&self) -> bool {
+        self.resolving_comment_id.is_some() || self.replying_to_comment_id.is_some()
+    }
+
+    /// Stop typing the resolution note or reply and restore the new comment text
+    fn restore_comment_draft(&mut self) {
+        self.resolving_comment_id = None;
+        self.replying_to_comment_id = None;
+        self.input_text = std::mem::take(&mut self.comment_draft);
+        self.switch_focus_to_comments();
     }
 
     fn handle_char(&mut self, char: char, app: &mut App) {
         // Only handle character input when focused on input field
         if self.focus_state == FocusState::Input {
-            // Limit input length to prevent very long comments
-            if self.input_text.len() < 1000 {
+            if self.input_text.chars().count() < self.max_comment_length {
                 self.input_text.push(char);
+            } else {
+                self.limit_reached = true;
             }
         } else {
             match char {
                 'j' => self.move_selection_down(),
                 'k' => self.move_selection_up(),
                 'r' => self.handle_toggle_selected_comment(app),
+                'n' => self.start_resolution_note(),
+                'a' => self.start_reply(),
+                's' => self.cycle_selected_comment_status(app),
+                'x' => self.resolve_selected_comment_and_close(app),
                 'R' => self.handle_toggle_all_comments(app),
+                't' => self.toggle_show_resolved(app),
+                'c' => self.collapse_input = !self.collapse_input,
+                'i' => self.switch_focus_to_input(),
+                'g' => self.toggle_group_by_line(),
+                'y' => self.copy_selected_comment_locator(app),
+                ']' => self.switch_comment_file(app, true),
+                '[' => self.switch_comment_file(app, false),
                 '?' => self.help(app),
                 _ => {
                     // Ignore other characters when not focused on input
@@ -241,19 +554,33 @@ impl CommentsView {
 
     fn switch_focus_to_comments(&mut self) {
         self.focus_state = FocusState::CommentsList;
-        // Select the first comment if available
-        if !self.comments.is_empty() {
-            self.selected_comment_index = Some(0);
-        } else {
-            self.selected_comment_index = None;
-        }
+        // Select the previously selected comment, otherwise the first one if available
+        self.selected_comment_index = self
+            .remembered_comment_index()
+            .or_else(|| (!self.visible_comments().is_empty()).then_some(0));
     }
 
     fn switch_focus_to_input(&mut self) {
+        self.remember_selected_comment();
         self.focus_state = FocusState::Input;
         self.selected_comment_index = None;
     }
 
+    /// Remember the ID of the selected comment, if any
+    fn remember_selected_comment(&mut self) {
+        if let Some(comment) = self.get_selected_comment() {
+            self.selected_comment_id = Some(comment.id.clone());
+        }
+    }
+
+    /// Index of the remembered comment in the visible comments
+    fn remembered_comment_index(&self) -> Option<usize> {
+        let comment_id = self.selected_comment_id.as_ref()?;
+        self.visible_comments()
+            .iter()
+            .position(|comment| &comment.id == comment_id)
+    }
+
     fn move_selection_up(&mut self) {
         if self.focus_state != FocusState::CommentsList {
             return;
@@ -271,18 +598,84 @@ impl CommentsView {
             return;
         }
 
+        let visible_count = self.visible_comments().len();
         if let Some(current_index) = self.selected_comment_index {
-            if current_index < self.comments.len().saturating_sub(1) {
+            if current_index < visible_count.saturating_sub(1) {
                 self.selected_comment_index = Some(current_index + 1);
             }
-        } else if !self.comments.is_empty() {
+        } else if visible_count > 0 {
             self.selected_comment_index = Some(0);
         }
     }
 
     fn get_selected_comment(&self) -> Option<&Comment> {
         self.selected_comment_index
-            .and_then(|index| self.comments.get(index))
+            .and_then(|index| self.visible_comments().get(index).copied())
+    }
+
+    /// Comments that are listed with the current resolved filter, in the order of their groups
+    /// when they are grouped by line. Replies follow the comment they answer, replies whose
+    /// comment is hidden are listed on their own.
+    fn visible_comments(&self) -> Vec<&Comment> {
+        let mut comments: Vec<&Comment> = self
+            .comments
+            .iter()
+            .filter(|comment| self.show_resolved || !comment.resolved)
+            .collect();
+        if self.is_grouped_by_line() {
+            // File comments (without a line) first, the order within a group is kept
+            comments.sort_by_key(|comment| comment.line_number);
+        }
+
+        let comment_ids: HashSet<&str> =
+            comments.iter().map(|comment| comment.id.as_str()).collect();
+        let is_listed_reply = |comment: &Comment| {
+            comment
+                .parent_id
+                .as_deref()
+                .is_some_and(|parent_id| comment_ids.contains(parent_id))
+        };
+        let mut threads = Vec::with_capacity(comments.len());
+        for comment in comments.iter().filter(|comment| !is_listed_reply(comment)) {
+            threads.push(*comment);
+            // Comments are loaded newest first, replies read as a conversation oldest first
+            threads.extend(
+                comments
+                    .iter()
+                    .rev()
+                    .filter(|reply| reply.parent_id.as_ref() == Some(&comment.id)),
+            );
+        }
+        threads
+    }
+
+    /// Number of resolved comments hidden by the resolved filter
+    fn hidden_resolved_count(&self) -> usize {
+        self.comments.len() - self.visible_comments().len()
+    }
+
+    /// Keep the selection within the visible comments while the comments list is focused
+    fn clamp_selection(&mut self) {
+        if self.focus_state != FocusState::CommentsList {
+            return;
+        }
+
+        let visible_count = self.visible_comments().len();
+        self.selected_comment_index = match self.selected_comment_index {
+            _ if visible_count == 0 => None,
+            Some(current_index) => Some(current_index.min(visible_count - 1)),
+            None => Some(0),
+        };
+    }
+
+    /// Toggle whether resolved comments are listed and persist the preference
+    fn toggle_show_resolved(&mut self, app: &mut App) {
+        self.show_resolved = !self.show_resolved;
+        self.clamp_selection();
+
+        app.settings.hide_resolved_comments = !self.show_resolved;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
     }
 
     fn handle_toggle_selected_comment(&self, app: &mut App) {
@@ -293,6 +686,32 @@ impl CommentsView {
         }
     }
 
+    /// Cycle the status of the selected comment, e.g. to acknowledge it without resolving it
+    fn cycle_selected_comment_status(&self, app: &mut App) {
+        if let Some(comment) = self.get_selected_comment() {
+            app.events.send(AppEvent::CommentCycleStatus {
+                comment_id: comment.id.clone().into(),
+            });
+        }
+    }
+
+    /// Resolve the selected comment and close the comments in one step. An already resolved
+    /// comment stays resolved. The resolve is sent first, so it is stored before the review
+    /// details below reload their comments.
+    fn resolve_selected_comment_and_close(&self, app: &mut App) {
+        let Some(comment) = self.get_selected_comment() else {
+            return;
+        };
+
+        if !comment.resolved {
+            app.events.send(AppEvent::CommentMarkResolved {
+                comment_id: comment.id.clone().into(),
+                resolution_note: None,
+            });
+        }
+        self.close(app);
+    }
+
     fn handle_toggle_all_comments(&self, app: &mut App) {
         app.events.send(AppEvent::CommentsToggleAllResolved {
             review_id: self.target.review_id().into(),
@@ -307,12 +726,15 @@ impl ViewHandler for CommentsView {
         ViewType::Comments
     }
 
-    fn render(&self, _app: &App, area: Rect, buf: &mut Buffer) {
+    fn render(&self, app: &App, area: Rect, buf: &mut Buffer) {
         // Clear the background to make this a proper modal
         Clear.render(area, buf);
 
         let block = Block::default()
-            .title(format!(" {} ", self.target.display_title()))
+            .title(format!(
+                " {} ",
+                self.target.display_title(self.old_file_path.as_deref())
+            ))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Blue))
             .style(Style::default().bg(Color::Black));
@@ -321,29 +743,39 @@ impl ViewHandler for CommentsView {
         block.render(area, buf);
 
         // Split into input area (top) and comments list (bottom)
+        let input_height = if self.is_input_collapsed() { 1 } else { 3 };
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Input field
-                Constraint::Min(1),    // Comments list
+                Constraint::Length(input_height), // Input field
+                Constraint::Min(1),               // Comments list
             ])
             .split(inner_area);
 
-        self.render_input_field(layout[0], buf);
-        self.render_comments_list(layout[1], buf);
+        if self.is_input_collapsed() {
+            self.render_collapsed_input_field(layout[0], buf);
+        } else {
+            self.render_input_field(layout[0], buf);
+        }
+        self.render_comments_list(layout[1], buf, app.settings.density);
     }
 
     fn handle_key_events(&mut self, app: &mut App, key_event: &KeyEvent) -> color_eyre::Result<()> {
+        self.limit_reached = false;
         match key_event.code {
+            KeyCode::Char('p') if key_event.modifiers == KeyModifiers::CONTROL => {
+                self.priority = self.priority.cycle();
+            }
             KeyCode::Tab => self.handle_tab(),
             KeyCode::Up => self.move_selection_up(),
             KeyCode::Down => self.move_selection_down(),
             KeyCode::Char(c) => self.handle_char(c, app),
             KeyCode::Backspace => self.handle_backspace(),
             KeyCode::Enter => self.handle_enter(app),
-            KeyCode::Esc => self.close(app),
+            KeyCode::Esc => self.handle_esc(app),
             _ => {}
         }
+        self.save_comment_draft(app);
         Ok(())
     }
 
@@ -384,6 +816,10 @@ impl ViewHandler for CommentsView {
                 // Reload comments when all comments' resolved state is toggled
                 self.request_comments_reload(app);
             }
+            AppEvent::CommentStatusChanged { .. } | AppEvent::CommentCycleStatusError { .. } => {
+                // Reload comments when a comment's status is changed
+                self.request_comments_reload(app);
+            }
             AppEvent::CommentToggleResolvedError { .. } => {
                 // Could show error message in UI, for now just reload
                 self.request_comments_reload(app);
@@ -420,6 +856,16 @@ impl ViewHandler for CommentsView {
                     state: KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "Ctrl+P".to_string(),
+                description: "Cycle the priority of the new comment".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('p'),
+                    modifiers: KeyModifiers::CONTROL,
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
             KeyBinding {
                 key: "k/↑".to_string(),
                 description: "Navigate up (when in comments list)".to_string(),
@@ -450,6 +896,36 @@ impl ViewHandler for CommentsView {
                     state: KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "n".to_string(),
+                description: "Resolve with a note (when in comments list)".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('n'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "a".to_string(),
+                description: "Reply to the comment (when in comments list)".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('a'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "x".to_string(),
+                description: "Resolve and close (when in comments list)".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('x'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
             KeyBinding {
                 key: "R".to_string(),
                 description: "Toggle all resolved (when in comments list)".to_string(),
@@ -460,6 +936,89 @@ impl ViewHandler for CommentsView {
                     state: KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "t".to_string(),
+                description: "Show/hide resolved (when in comments list)".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('t'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "c".to_string(),
+                description: "Collapse/expand input (when in comments list)".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('c'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "i".to_string(),
+                description: "Start typing a comment (when in comments list)".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('i'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "g".to_string(),
+                description: "Group by line (when in comments list of a file)".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('g'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "y".to_string(),
+                description: "Copy the location of the comment (when in comments list)".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('y'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "s".to_string(),
+                description:
+                    "Cycle the status: addressed, won't fix, question (when in comments list)"
+                        .to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('s'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "] / [".to_string(),
+                description: "Next / previous file with comments (file comments, in comments list)"
+                    .to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char(']'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "Enter".to_string(),
+                description: "Go to the line in the diff (when in comments list)".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Enter,
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
             KeyBinding {
                 key: "Esc".to_string(),
                 description: "Close comments".to_string(),
@@ -476,7 +1035,7 @@ impl ViewHandler for CommentsView {
     #[cfg(test)]
     fn debug_state(&self) -> String {
         format!(
-            "target: {:?}, input_text: {:?}, loading_state: {:?}, comments_count: {}, focus_state: {:?}, selected_comment_index: {:?}",
+            "target: {:?}, input_text: {:?}, loading_state: {:?}, comments_count: {}, focus_state: {:?}, selected_comment_index: {:?}, show_resolved: {}",
             self.target,
             self.input_text,
             match &self.loading_state {
@@ -488,6 +1047,7 @@ impl ViewHandler for CommentsView {
             self.comments.len(),
             self.focus_state,
             self.selected_comment_index,
+            self.show_resolved,
         )
     }
 
@@ -503,6 +1063,23 @@ impl ViewHandler for CommentsView {
 }
 
 impl CommentsView {
+    /// The input is only collapsed while reading comments, typing always shows it in full
+    fn is_input_collapsed(&self) -> bool {
+        self.collapse_input && self.focus_state == FocusState::CommentsList
+    }
+
+    fn render_collapsed_input_field(&self, area: Rect, buf: &mut Buffer) {
+        let text = if self.input_text.is_empty() {
+            "New Comment (press i to type)".to_string()
+        } else {
+            format!("New Comment: {}", self.input_text)
+        };
+
+        Paragraph::new(text)
+            .style(Style::default().fg(Color::Gray))
+            .render(area, buf);
+    }
+
     fn render_input_field(&self, area: Rect, buf: &mut Buffer) {
         let is_focused = self.focus_state == FocusState::Input;
         let border_color = if is_focused {
@@ -510,16 +1087,60 @@ impl CommentsView {
         } else {
             Color::Gray
         };
-        let title = if is_focused {
-            " New Comment (focused) "
+        let title = if self.resolving_comment_id.is_some() {
+            Line::from(" Resolution Note (Enter to resolve, Esc to cancel) ")
+        } else if self.replying_to_comment_id.is_some() {
+            let parent_content = self
+                .replying_to_comment()
+                .map(|comment| comment.content.lines().next().unwrap_or_default())
+                .unwrap_or_default();
+            let parent_content = match parent_content.char_indices().nth(REPLY_CONTEXT_LENGTH) {
+                Some((index, _)) => format!("{}…", &parent_content[..index]),
+                None => parent_content.to_string(),
+            };
+            Line::from(format!(
+                " Reply to \"{parent_content}\" (Enter to send, Esc to cancel) "
+            ))
+        } else {
+            let mut title = vec![Span::raw(if is_focused {
+                " New Comment (focused) "
+            } else {
+                " New Comment "
+            })];
+            if self.priority != CommentPriority::Normal {
+                title.push(Span::styled(
+                    format!("[{} priority] ", self.priority),
+                    comment_priority_style(self.priority),
+                ));
+            }
+            Line::from(title)
+        };
+
+        let length = self.input_text.chars().count();
+        let counter_color = if self.limit_reached || length >= self.max_comment_length {
+            Color::Red
+        } else if length * 10 >= self.max_comment_length * 9 {
+            Color::Yellow
         } else {
-            " New Comment "
+            Color::Gray
         };
+        let counter = Line::styled(
+            format!(" ({length}/{}) ", self.max_comment_length),
+            Style::default().fg(counter_color),
+        )
+        .right_aligned();
 
-        let input_block = Block::default()
+        let mut input_block = Block::default()
             .title(title)
+            .title(counter)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color));
+        if self.limit_reached {
+            input_block = input_block.title_bottom(Line::styled(
+                " Maximum comment length reached ",
+                Style::default().fg(Color::Red),
+            ));
+        }
 
         let input_content = Paragraph::new(self.input_text.as_str())
             .block(input_block)
@@ -528,7 +1149,7 @@ impl CommentsView {
         input_content.render(area, buf);
     }
 
-    fn render_comments_list(&self, area: Rect, buf: &mut Buffer) {
+    fn render_comments_list(&self, area: Rect, buf: &mut Buffer, density: Density) {
         match &self.loading_state {
             CommentsLoadingState::Init => {
                 let loading_text = Paragraph::new("Initializing comments...")
@@ -564,26 +1185,41 @@ impl CommentsView {
                 error_text.render(area, buf);
             }
             CommentsLoadingState::Loaded(_) => {
-                self.render_comments_list_loaded(area, buf);
+                self.render_comments_list_loaded(area, buf, density);
             }
         }
     }
 
-    fn render_comments_list_loaded(&self, area: Rect, buf: &mut Buffer) {
+    fn render_comments_list_loaded(&self, area: Rect, buf: &mut Buffer, density: Density) {
         let is_focused = self.focus_state == FocusState::CommentsList;
         let border_color = if is_focused {
             Color::Green
         } else {
             Color::Gray
         };
+        let visible_comments = self.visible_comments();
+        let hidden_resolved_count = self.hidden_resolved_count();
+        let count = if hidden_resolved_count > 0 {
+            format!(
+                "{}, {hidden_resolved_count} resolved hidden",
+                visible_comments.len()
+            )
+        } else {
+            visible_comments.len().to_string()
+        };
         let title = if is_focused {
-            format!(" Comments ({}) (focused) ", self.comments.len())
+            format!(" Comments ({count}) (focused) ")
         } else {
-            format!(" Comments ({}) ", self.comments.len())
+            format!(" Comments ({count}) ")
         };
 
-        if self.comments.is_empty() {
-            let empty_text = Paragraph::new("No comments yet. Add one above!")
+        if visible_comments.is_empty() {
+            let empty_message = if hidden_resolved_count > 0 {
+                "No open comments. Press t to show resolved ones."
+            } else {
+                "No comments yet. Add one above!"
+            };
+            let empty_text = Paragraph::new(empty_message)
                 .style(Style::default().fg(Color::Gray))
                 .block(
                     Block::default()
@@ -595,13 +1231,31 @@ impl CommentsView {
             return;
         }
 
-        // Create list items for comments
-        let comment_items: Vec<ListItem> = self
-            .comments
-            .iter()
-            .enumerate()
-            .map(|(index, comment)| self.render_comment_item(index, comment))
-            .collect();
+        // Create list items for comments, with a header before every group when they are
+        // grouped by line. Only file comments need no headers.
+        let show_group_headers = self.is_grouped_by_line()
+            && visible_comments
+                .iter()
+                .any(|comment| comment.line_number.is_some());
+        let mut comment_items: Vec<ListItem> = vec![];
+        let mut selected_item_index = None;
+        let mut current_group = None;
+        let mut current_thread: Option<&CommentId> = None;
+        for (index, comment) in visible_comments.into_iter().enumerate() {
+            let is_reply =
+                comment.parent_id.is_some() && comment.parent_id.as_ref() == current_thread;
+            if !is_reply {
+                current_thread = Some(&comment.id);
+            }
+            if show_group_headers && !is_reply && current_group != Some(comment.line_number) {
+                current_group = Some(comment.line_number);
+                comment_items.push(Self::render_group_header(comment.line_number));
+            }
+            if self.selected_comment_index == Some(index) {
+                selected_item_index = Some(comment_items.len());
+            }
+            comment_items.push(self.render_comment_item(comment, is_reply, density));
+        }
 
         let comments_list = List::new(comment_items)
             .block(
@@ -621,13 +1275,34 @@ impl CommentsView {
         // Create list state and set selected index if focused
         let mut list_state = ListState::default();
         if is_focused {
-            list_state.select(self.selected_comment_index);
+            list_state.select(selected_item_index);
         }
 
         StatefulWidget::render(comments_list, area, buf, &mut list_state);
     }
 
-    fn render_comment_item(&self, _index: usize, comment: &Comment) -> ListItem<'_> {
+    /// Header of a group of comments on the same line, or of the file comments
+    fn render_group_header(line_number: Option<i64>) -> ListItem<'static> {
+        let title = match line_number {
+            Some(line_number) => format!("Line {line_number}"),
+            None => "File".to_string(),
+        };
+        ListItem::new(Line::styled(
+            format!("── {title} ──"),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+    }
+
+    /// Comment with its status, time and priority, indented below its parent when it is a reply.
+    /// It's followed by a blank line unless the density is compact.
+    fn render_comment_item(
+        &self,
+        comment: &Comment,
+        is_reply: bool,
+        density: Density,
+    ) -> ListItem<'_> {
         // Format the comment with timestamp and content
         let timestamp = comment.created_at.format("%Y-%m-%d %H:%M:%S");
 
@@ -637,16 +1312,22 @@ impl CommentsView {
             &format!("LINE {}", comment.line_number.unwrap_or(0))
         };
 
-        // Show resolved status
+        // Show resolved status, colored by the status of unresolved comments
         let resolved_indicator = if comment.resolved { "[✓]" } else { "[ ]" };
         let resolved_color = if comment.resolved {
             Color::Green
         } else {
-            Color::Gray
+            comment_status_color(comment.status)
         };
 
-        let content = vec![
+        let (header_indent, indent) = if is_reply {
+            ("  ↳ ", "    ")
+        } else {
+            ("", "")
+        };
+        let mut content = vec![
             Line::from(vec![
+                Span::raw(header_indent),
                 Span::styled(
                     format!("{resolved_indicator} [{comment_type}] "),
                     Style::default()
@@ -654,17 +1335,42 @@ impl CommentsView {
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(timestamp.to_string(), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    match comment.priority {
+                        CommentPriority::Normal => String::new(),
+                        priority => format!(" [{priority}]"),
+                    },
+                    comment_priority_style(comment.priority),
+                ),
+                Span::styled(
+                    match comment.status {
+                        CommentStatus::Open => String::new(),
+                        status => format!(" [{}]", status.label()),
+                    },
+                    Style::default().fg(comment_status_color(comment.status)),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw(indent),
+                Span::styled(
+                    comment.content.clone(),
+                    if comment.resolved {
+                        Style::default().fg(Color::Gray)
+                    } else {
+                        Style::default().fg(Color::White)
+                    },
+                ),
             ]),
-            Line::from(Span::styled(
-                comment.content.clone(),
-                if comment.resolved {
-                    Style::default().fg(Color::Gray)
-                } else {
-                    Style::default().fg(Color::White)
-                },
-            )),
-            Line::from(""), // Empty line for spacing
         ];
+        if let Some(resolution_note) = &comment.resolution_note {
+            content.push(Line::from(Span::styled(
+                format!("{indent}↳ {resolution_note}"),
+                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+            )));
+        }
+        if density == Density::Normal {
+            content.push(Line::from("")); // Empty line for spacing
+        }
 
         ListItem::new(content)
     }
@@ -674,6 +1380,16 @@ impl CommentsView {
         params: &CommentsLoadParams,
         state: &CommentsLoadingState,
     ) {
+        if let CommentsLoadingState::Loaded(comments) = state
+            && params.review_id.as_ref() == self.target.review_id()
+            && params.file_path.is_none()
+        {
+            // Comments of the whole review, to know which files have comments
+            let comment_files: BTreeSet<&String> =
+                comments.iter().map(|comment| &comment.file_path).collect();
+            self.comment_files = comment_files.into_iter().cloned().collect();
+            return;
+        }
         if !self.target.comments_load_params().equals(params) {
             // If the params don't match our target, ignore this state
             return;
@@ -682,22 +1398,14 @@ impl CommentsView {
         self.loading_state = state.clone();
 
         if let CommentsLoadingState::Loaded(comments) = state {
+            self.remember_selected_comment();
             self.comments = comments.clone();
-            // Reset selection if comments changed
-            if self.focus_state == FocusState::CommentsList {
-                if !self.comments.is_empty() {
-                    // Keep selection in bounds
-                    if let Some(current_index) = self.selected_comment_index {
-                        if current_index >= self.comments.len() {
-                            self.selected_comment_index =
-                                Some(self.comments.len().saturating_sub(1));
-                        }
-                    } else {
-                        self.selected_comment_index = Some(0);
-                    }
-                } else {
-                    self.selected_comment_index = None;
+            // Keep the selected comment selected, otherwise keep the selection in bounds
+            match self.remembered_comment_index() {
+                Some(index) if self.focus_state == FocusState::CommentsList => {
+                    self.selected_comment_index = Some(index);
                 }
+                _ => self.clamp_selection(),
             }
         }
     }
@@ -733,9 +1441,16 @@ impl CommentsView {
 mod tests {
     use super::*;
 
+    use insta::assert_snapshot;
     use sqlx::SqlitePool;
 
-    use crate::{app::App, database::Database, models::Comment};
+    use crate::{
+        app::App,
+        database::Database,
+        models::Comment,
+        test_utils::{fixed_time, render_app_to_terminal_backend},
+        time_provider::MockTimeProvider,
+    };
 
     async fn create_test_app() -> App {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
@@ -749,6 +1464,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         }
     }
 
@@ -783,14 +1501,44 @@ mod tests {
             review_id: "review-123".to_string(),
             file_path: "src/main.rs".to_string(),
         };
-        assert_eq!(file_target.display_title(), "Comments for src/main.rs");
+        assert_eq!(file_target.display_title(None), "Comments for src/main.rs");
 
         let line_target = CommentTarget::Line {
             review_id: "review-123".to_string(),
             file_path: "src/main.rs".to_string(),
             line_number: 42,
         };
-        assert_eq!(line_target.display_title(), "Comments for src/main.rs:42");
+        assert_eq!(
+            line_target.display_title(None),
+            "Comments for src/main.rs:42"
+        );
+    }
+
+    #[test]
+    fn test_comment_target_display_title_for_renamed_file() {
+        let file_target = CommentTarget::File {
+            review_id: "review-123".to_string(),
+            file_path: "src/new.rs".to_string(),
+        };
+        assert_eq!(
+            file_target.display_title(Some("src/old.rs")),
+            "Comments for src/old.rs → src/new.rs"
+        );
+        // An old path that is the same as the path is not repeated
+        assert_eq!(
+            file_target.display_title(Some("src/new.rs")),
+            "Comments for src/new.rs"
+        );
+
+        let line_target = CommentTarget::Line {
+            review_id: "review-123".to_string(),
+            file_path: "src/new.rs".to_string(),
+            line_number: 42,
+        };
+        assert_eq!(
+            line_target.display_title(Some("src/old.rs")),
+            "Comments for src/old.rs → src/new.rs:42"
+        );
     }
 
     #[tokio::test]
@@ -815,6 +1563,51 @@ mod tests {
         assert_eq!(view.input_text, "H");
     }
 
+    fn type_text(view: &mut CommentsView, app: &mut App, text: &str) {
+        for char in text.chars() {
+            let key_event = KeyEvent::new(KeyCode::Char(char), KeyModifiers::empty());
+            view.handle_key_events(app, &key_event).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_input_stops_at_max_comment_length() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string())
+                .with_max_comment_length(5);
+        let mut app = create_test_app().await;
+
+        type_text(&mut view, &mut app, "Grüße");
+        assert_eq!(view.input_text, "Grüße");
+        assert!(!view.limit_reached);
+
+        type_text(&mut view, &mut app, "!");
+        assert_eq!(view.input_text, "Grüße");
+        assert!(view.limit_reached);
+
+        // The notice is cleared with the next key press
+        let key_event = KeyEvent::new(KeyCode::Backspace, KeyModifiers::empty());
+        view.handle_key_events(&mut app, &key_event).unwrap();
+        assert_eq!(view.input_text, "Grüß");
+        assert!(!view.limit_reached);
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_render_max_comment_length_reached() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string())
+                .with_max_comment_length(10);
+        let mut app = create_test_app().await;
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![]));
+        type_text(&mut view, &mut app, "Rename this!");
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
     #[tokio::test]
     async fn test_comments_view_enter_creates_comment() {
         let mut view =
@@ -839,16 +1632,140 @@ mod tests {
                 file_path,
                 line_number,
                 content,
+                priority,
+                parent_id,
             }) => {
+                assert_eq!(*parent_id, None);
                 assert_eq!(review_id.to_string(), "review-123");
                 assert_eq!(file_path.to_string(), "src/main.rs");
                 assert_eq!(*line_number, None);
                 assert_eq!(content.to_string(), "This is a test comment");
+                assert_eq!(*priority, CommentPriority::Normal);
             }
             _ => panic!("Expected CommentCreate event"),
         }
     }
 
+    #[tokio::test]
+    async fn test_comments_view_ctrl_p_cycles_priority() {
+        let mut view =
+            CommentsView::new_for_line("review-123".to_string(), "src/main.rs".to_string(), 3);
+        let mut app = create_test_app().await;
+        let ctrl_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+
+        view.handle_key_events(&mut app, &ctrl_p).unwrap();
+        assert_eq!(view.priority, CommentPriority::High);
+        view.handle_key_events(&mut app, &ctrl_p).unwrap();
+        assert_eq!(view.priority, CommentPriority::Low);
+        view.handle_key_events(&mut app, &ctrl_p).unwrap();
+        assert_eq!(view.priority, CommentPriority::Normal);
+        // The shortcut is not typed into the input
+        assert_eq!(view.input_text, "");
+
+        view.handle_key_events(&mut app, &ctrl_p).unwrap();
+        type_text(&mut view, &mut app, "Urgent");
+        view.handle_enter(&mut app);
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(
+            &*event,
+            crate::event::Event::App(AppEvent::CommentCreate {
+                priority: CommentPriority::High,
+                ..
+            })
+        ));
+        // The next comment starts with the default priority again
+        assert_eq!(view.priority, CommentPriority::Normal);
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_render_priorities() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        let comment = |content, priority| {
+            Comment::new_with_time_provider(
+                "review-123",
+                "src/main.rs",
+                None,
+                content,
+                &MockTimeProvider::new(fixed_time()),
+            )
+            .with_priority(priority)
+        };
+        let comments = vec![
+            comment("Urgent", CommentPriority::High),
+            comment("Whenever", CommentPriority::Low),
+        ];
+        view.handle_app_events(&mut app, &loaded_comments_event(comments));
+        view.priority = CommentPriority::High;
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_render_statuses() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        let comments = [
+            ("Fixed", CommentStatus::Addressed),
+            ("Out of scope", CommentStatus::WontFix),
+            ("Why?", CommentStatus::Question),
+        ]
+        .into_iter()
+        .map(|(content, status)| {
+            let mut comment = Comment::new_with_time_provider(
+                "review-123",
+                "src/main.rs",
+                None,
+                content,
+                &MockTimeProvider::new(fixed_time()),
+            );
+            comment.status = status;
+            comment
+        })
+        .collect();
+        view.handle_app_events(&mut app, &loaded_comments_event(comments));
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_render_compact_density() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        app.settings.density = Density::Compact;
+        let comments = ["First", "Second", "Third"]
+            .into_iter()
+            .map(|content| {
+                Comment::new_with_time_provider(
+                    "review-123",
+                    "src/main.rs",
+                    None,
+                    content,
+                    &MockTimeProvider::new(fixed_time()),
+                )
+            })
+            .collect();
+        view.handle_app_events(&mut app, &loaded_comments_event(comments));
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
     #[tokio::test]
     async fn test_comments_view_enter_with_empty_input() {
         let mut view =
@@ -883,6 +1800,77 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_comments_view_restores_draft_after_close() {
+        let mut app = create_test_app().await;
+        let open_view = |app: &App| {
+            CommentsView::new_for_line("review-123".to_string(), "src/main.rs".to_string(), 7)
+                .with_comment_drafts(&app.comment_drafts)
+        };
+        let mut view = open_view(&app);
+        for char in "Half done".chars() {
+            view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char(char)))
+                .unwrap();
+        }
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Esc))
+            .unwrap();
+
+        let mut view = open_view(&app);
+        assert_eq!(view.input_text, "Half done");
+        // Other targets don't get the draft
+        let other_view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string())
+                .with_comment_drafts(&app.comment_drafts);
+        assert_eq!(other_view.input_text, "");
+
+        // Submitting the comment clears the draft
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Enter))
+            .unwrap();
+        assert!(app.comment_drafts.is_empty());
+        assert_eq!(open_view(&app).input_text, "");
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_switches_to_next_file_with_comments() {
+        let mut app = create_test_app().await;
+        let mut view = CommentsView::new_for_file("review-123".to_string(), "src/b.rs".to_string());
+        let review_comments = vec![
+            Comment::test_comment("review-123", "src/c.rs", Some(3), "Line comment"),
+            Comment::test_comment("review-123", "src/a.rs", None, "File comment"),
+            Comment::test_comment("review-123", "src/c.rs", None, "Another one"),
+        ];
+        view.handle_comments_loading_state(
+            &CommentsLoadParams {
+                review_id: Arc::from("review-123"),
+                file_path: Arc::from(None),
+                line_number: Arc::from(None),
+            },
+            &CommentsLoadingState::Loaded(Arc::new(review_comments)),
+        );
+        // The comments of the whole review are not listed
+        assert!(view.comments.is_empty());
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Tab))
+            .unwrap();
+        // Returns the file of the target and the file whose comments are loaded, if any
+        let mut press = |view: &mut CommentsView, key: char| {
+            view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char(key)))
+                .unwrap();
+            let mut loaded_file = None;
+            while let Some(event) = app.events.try_recv() {
+                if let crate::event::Event::App(AppEvent::CommentsLoad(params)) = &*event {
+                    loaded_file = params.file_path.as_ref().clone();
+                }
+            }
+            (view.target.file_path().to_string(), loaded_file)
+        };
+
+        let loaded = |path: &str| (path.to_string(), Some(path.to_string()));
+        assert_eq!(press(&mut view, ']'), loaded("src/c.rs"));
+        assert_eq!(press(&mut view, ']'), ("src/c.rs".to_string(), None));
+        assert_eq!(press(&mut view, '['), loaded("src/a.rs"));
+        assert_eq!(press(&mut view, '['), ("src/a.rs".to_string(), None));
+    }
+
     #[test]
     fn test_comments_view_debug_state() {
         let view =
@@ -899,21 +1887,49 @@ mod tests {
         let view = CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
 
         let keybindings = view.get_keybindings();
-        assert_eq!(keybindings.len(), 7);
+        assert_eq!(keybindings.len(), 19);
         assert_eq!(keybindings[0].key, "Tab");
         assert_eq!(keybindings[0].description, "Switch focus");
         assert_eq!(keybindings[1].key, "Enter");
         assert_eq!(keybindings[1].description, "Add comment");
-        assert_eq!(keybindings[2].key, "k/↑");
-        assert!(keybindings[2].description.contains("Navigate up"));
-        assert_eq!(keybindings[3].key, "j/↓");
-        assert!(keybindings[3].description.contains("Navigate down"));
-        assert_eq!(keybindings[4].key, "r");
-        assert!(keybindings[4].description.contains("Toggle resolved"));
-        assert_eq!(keybindings[5].key, "R");
-        assert!(keybindings[5].description.contains("Toggle all resolved"));
-        assert_eq!(keybindings[6].key, "Esc");
-        assert_eq!(keybindings[6].description, "Close comments");
+        assert_eq!(keybindings[2].key, "Ctrl+P");
+        assert!(keybindings[2].description.contains("priority"));
+        assert_eq!(keybindings[3].key, "k/↑");
+        assert!(keybindings[3].description.contains("Navigate up"));
+        assert_eq!(keybindings[4].key, "j/↓");
+        assert!(keybindings[4].description.contains("Navigate down"));
+        assert_eq!(keybindings[5].key, "r");
+        assert!(keybindings[5].description.contains("Toggle resolved"));
+        assert_eq!(keybindings[6].key, "n");
+        assert!(keybindings[6].description.contains("Resolve with a note"));
+        assert_eq!(keybindings[7].key, "a");
+        assert!(keybindings[7].description.contains("Reply"));
+        assert_eq!(keybindings[8].key, "x");
+        assert!(keybindings[8].description.contains("Resolve and close"));
+        assert_eq!(keybindings[9].key, "R");
+        assert!(keybindings[9].description.contains("Toggle all resolved"));
+        assert_eq!(keybindings[10].key, "t");
+        assert!(keybindings[10].description.contains("Show/hide resolved"));
+        assert_eq!(keybindings[11].key, "c");
+        assert!(
+            keybindings[11]
+                .description
+                .contains("Collapse/expand input")
+        );
+        assert_eq!(keybindings[12].key, "i");
+        assert!(keybindings[12].description.contains("Start typing"));
+        assert_eq!(keybindings[13].key, "g");
+        assert!(keybindings[13].description.contains("Group by line"));
+        assert_eq!(keybindings[14].key, "y");
+        assert!(keybindings[14].description.contains("Copy the location"));
+        assert_eq!(keybindings[15].key, "s");
+        assert!(keybindings[15].description.contains("Cycle the status"));
+        assert_eq!(keybindings[16].key, "] / [");
+        assert!(keybindings[16].description.contains("file with comments"));
+        assert_eq!(keybindings[17].key, "Enter");
+        assert!(keybindings[17].description.contains("Go to the line"));
+        assert_eq!(keybindings[18].key, "Esc");
+        assert_eq!(keybindings[18].description, "Close comments");
     }
 
     #[tokio::test]
@@ -961,4 +1977,607 @@ mod tests {
         assert_eq!(view.comments.len(), 1);
         assert_eq!(view.comments[0].content, "Test comment");
     }
+
+    fn loaded_comments_event(comments: Vec<Comment>) -> AppEvent {
+        AppEvent::CommentsLoadingState {
+            params: CommentsLoadParams {
+                review_id: Arc::from("review-123"),
+                file_path: Arc::from(Some("src/main.rs".to_string())),
+                line_number: Arc::from(None),
+            },
+            state: CommentsLoadingState::Loaded(Arc::new(comments)),
+        }
+    }
+
+    fn resolved(mut comment: Comment) -> Comment {
+        comment.resolved = true;
+        comment
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_toggle_show_resolved() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+
+        let open_comment = Comment::test_comment("review-123", "src/main.rs", None, "Open");
+        let resolved_comment = resolved(Comment::test_comment(
+            "review-123",
+            "src/main.rs",
+            None,
+            "Done",
+        ));
+        view.handle_app_events(
+            &mut app,
+            &loaded_comments_event(vec![resolved_comment, open_comment.clone()]),
+        );
+
+        view.handle_tab();
+        view.move_selection_down();
+        assert_eq!(view.selected_comment_index, Some(1));
+
+        // Hiding resolved comments re-clamps the selection and persists the preference
+        view.handle_char('t', &mut app);
+        assert!(!view.show_resolved);
+        assert_eq!(view.selected_comment_index, Some(0));
+        assert_eq!(view.visible_comments().len(), 1);
+        assert_eq!(view.hidden_resolved_count(), 1);
+        assert_eq!(view.get_selected_comment().unwrap().id, open_comment.id);
+        assert!(app.settings.hide_resolved_comments);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            crate::event::Event::App(AppEvent::SettingsSave(settings)) => {
+                assert!(settings.hide_resolved_comments);
+            }
+            _ => panic!("Expected SettingsSave event"),
+        }
+
+        view.handle_char('t', &mut app);
+        assert!(view.show_resolved);
+        assert_eq!(view.visible_comments().len(), 2);
+        assert!(!app.settings.hide_resolved_comments);
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_resolving_last_visible_comment_clears_selection() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string())
+                .with_show_resolved(false);
+        let mut app = create_test_app().await;
+
+        let comment = Comment::test_comment("review-123", "src/main.rs", None, "Open");
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![comment.clone()]));
+        view.handle_tab();
+        assert_eq!(view.selected_comment_index, Some(0));
+
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![resolved(comment)]));
+        assert_eq!(view.selected_comment_index, None);
+        assert!(view.get_selected_comment().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_resolve_with_note() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        let comment = Comment::test_comment("review-123", "src/main.rs", None, "Open");
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![comment.clone()]));
+        for char in "draft".chars() {
+            view.handle_char(char, &mut app);
+        }
+        view.handle_tab();
+
+        view.handle_char('n', &mut app);
+        assert_eq!(view.focus_state, FocusState::Input);
+        assert_eq!(view.input_text, "");
+        for char in "Fixed".chars() {
+            view.handle_char(char, &mut app);
+        }
+        view.handle_enter(&mut app);
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            crate::event::Event::App(AppEvent::CommentMarkResolved {
+                comment_id,
+                resolution_note,
+            }) => {
+                assert_eq!(comment_id.as_ref(), comment.id);
+                assert_eq!(resolution_note.as_deref(), Some("Fixed"));
+            }
+            _ => panic!("Expected CommentMarkResolved event, got: {event:?}"),
+        }
+        // The new comment draft is back and the comments list is focused again
+        assert_eq!(view.input_text, "draft");
+        assert_eq!(view.focus_state, FocusState::CommentsList);
+        assert_eq!(view.resolving_comment_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_resolve_without_note_and_cancel() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        let comment = Comment::test_comment("review-123", "src/main.rs", None, "Open");
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![comment]));
+        view.handle_tab();
+
+        // Esc cancels the note instead of closing the view
+        view.handle_char('n', &mut app);
+        view.handle_char('x', &mut app);
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()),
+        )
+        .unwrap();
+        assert_eq!(view.focus_state, FocusState::CommentsList);
+        assert_eq!(view.input_text, "");
+        assert!(!app.events.has_pending_events());
+
+        // Enter without a note resolves the comment without one
+        view.handle_char('n', &mut app);
+        view.handle_enter(&mut app);
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(
+            &*event,
+            crate::event::Event::App(AppEvent::CommentMarkResolved {
+                resolution_note: None,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_resolve_and_close() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        let comment = Comment::test_comment("review-123", "src/main.rs", None, "Open");
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![comment.clone()]));
+
+        // Nothing happens without a selected comment
+        view.handle_char('x', &mut app);
+        assert!(!app.events.has_pending_events());
+
+        view.handle_tab();
+        view.handle_char('x', &mut app);
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            crate::event::Event::App(AppEvent::CommentMarkResolved {
+                comment_id,
+                resolution_note: None,
+            }) => assert_eq!(comment_id.as_ref(), comment.id),
+            _ => panic!("Expected CommentMarkResolved event, got: {event:?}"),
+        }
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(
+            &*event,
+            crate::event::Event::App(AppEvent::ViewClose)
+        ));
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_resolve_and_close_resolved_comment() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        let comment = Comment::test_comment("review-123", "src/main.rs", None, "Done");
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![resolved(comment)]));
+        view.handle_tab();
+
+        // The resolved comment is not reopened, the comments are only closed
+        view.handle_char('x', &mut app);
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(
+            &*event,
+            crate::event::Event::App(AppEvent::ViewClose)
+        ));
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_cycles_the_status_of_the_selected_comment() {
+        let mut app = create_test_app().await;
+        let mut review = crate::models::Review::builder().build();
+        review.id = "review-123".to_string();
+        review.save(app.database.pool()).await.unwrap();
+        let comment = Comment::test_comment("review-123", "src/main.rs", None, "Why?");
+        comment.create(app.database.pool()).await.unwrap();
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![comment]));
+        view.handle_tab();
+        app.view_stack.push(Box::new(view));
+
+        let mut statuses = vec![];
+        for _ in 0..4 {
+            app.handle_key_events(&KeyEvent::from(KeyCode::Char('s')))
+                .unwrap();
+            while let Some(event) = app.events.try_recv() {
+                crate::event_handler::EventProcessor::process_event(&mut app, event)
+                    .await
+                    .unwrap();
+            }
+            let view = app.view_stack[0]
+                .as_any()
+                .downcast_ref::<CommentsView>()
+                .unwrap();
+            statuses.push(view.get_selected_comment().unwrap().status);
+        }
+
+        assert_eq!(
+            statuses,
+            vec![
+                CommentStatus::Addressed,
+                CommentStatus::WontFix,
+                CommentStatus::Question,
+                CommentStatus::Open,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_render_resolution_note() {
+        let mut app = create_test_app().await;
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut comment = resolved(Comment::new_with_time_provider(
+            "review-123",
+            "src/main.rs",
+            None,
+            "Please rename this",
+            &MockTimeProvider::new(fixed_time()),
+        ));
+        comment.resolution_note = Some("Renamed in the next commit".to_string());
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![comment]));
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_reply_to_comment() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        let parent = Comment::test_comment("review-123", "src/main.rs", Some(7), "Why?");
+        let reply = Comment::test_comment("review-123", "src/main.rs", Some(7), "Because")
+            .with_parent_id(Some(parent.id.clone()));
+        view.handle_app_events(
+            &mut app,
+            &loaded_comments_event(vec![reply.clone(), parent.clone()]),
+        );
+        for char in "draft".chars() {
+            view.handle_char(char, &mut app);
+        }
+        view.handle_tab();
+
+        // Replying to a reply answers the comment of the thread
+        view.move_selection_down();
+        assert_eq!(view.get_selected_comment(), Some(&reply));
+        view.handle_char('a', &mut app);
+        assert_eq!(view.focus_state, FocusState::Input);
+        assert_eq!(view.input_text, "");
+        for char in "Ok".chars() {
+            view.handle_char(char, &mut app);
+        }
+        view.handle_enter(&mut app);
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            crate::event::Event::App(AppEvent::CommentCreate {
+                line_number,
+                content,
+                parent_id,
+                ..
+            }) => {
+                assert_eq!(*line_number, Some(7));
+                assert_eq!(content.as_ref(), "Ok");
+                assert_eq!(parent_id.as_deref(), Some(parent.id.as_str()));
+            }
+            _ => panic!("Expected CommentCreate event, got: {event:?}"),
+        }
+        // The new comment draft is back and the comments list is focused again
+        assert_eq!(view.input_text, "draft");
+        assert_eq!(view.focus_state, FocusState::CommentsList);
+        assert_eq!(view.replying_to_comment_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_render_reply_under_its_parent() {
+        let mut app = create_test_app().await;
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let comment = |line_number, content| {
+            Comment::new_with_time_provider(
+                "review-123",
+                "src/main.rs",
+                line_number,
+                content,
+                &MockTimeProvider::new(fixed_time()),
+            )
+        };
+        let parent = comment(Some(12), "Why is this needed?");
+        let reply = comment(Some(12), "For the migration").with_parent_id(Some(parent.id.clone()));
+        // Newest first, as they are loaded
+        view.handle_app_events(
+            &mut app,
+            &loaded_comments_event(vec![
+                comment(Some(12), "Typo"),
+                reply,
+                comment(None, "Split this file"),
+                parent,
+            ]),
+        );
+        view.handle_tab();
+        view.handle_char('j', &mut app);
+        view.handle_char('j', &mut app);
+        view.handle_char('a', &mut app);
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    fn grouped_test_comments() -> Vec<Comment> {
+        let comment = |line_number, content| {
+            Comment::new_with_time_provider(
+                "review-123",
+                "src/main.rs",
+                line_number,
+                content,
+                &MockTimeProvider::new(fixed_time()),
+            )
+        };
+        // Newest first, as they are loaded
+        vec![
+            comment(Some(40), "Extract this"),
+            comment(None, "Split this file"),
+            comment(Some(12), "Typo"),
+            comment(Some(40), "Add a test"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_groups_file_comments_by_line() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        view.handle_app_events(&mut app, &loaded_comments_event(grouped_test_comments()));
+        let contents = |view: &CommentsView| {
+            view.visible_comments()
+                .iter()
+                .map(|comment| comment.content.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            contents(&view),
+            ["Split this file", "Typo", "Extract this", "Add a test"]
+        );
+
+        // Ungrouping keeps the selected comment selected
+        view.handle_tab();
+        view.move_selection_down();
+        view.handle_char('g', &mut app);
+        assert_eq!(
+            contents(&view),
+            ["Extract this", "Split this file", "Typo", "Add a test"]
+        );
+        assert_eq!(view.get_selected_comment().unwrap().content, "Typo");
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_enter_goes_to_the_line_of_the_comment() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        view.handle_app_events(&mut app, &loaded_comments_event(grouped_test_comments()));
+        view.handle_tab();
+
+        // File comments have no line to go to
+        view.handle_enter(&mut app);
+        assert!(!app.events.has_pending_events());
+
+        view.move_selection_down();
+        view.handle_enter(&mut app);
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(
+            &*event,
+            crate::event::Event::App(AppEvent::ViewClose)
+        ));
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            crate::event::Event::App(AppEvent::ReviewDiffJump {
+                review_id,
+                file_path,
+                line_number,
+            }) => {
+                assert_eq!(review_id.as_ref(), "review-123");
+                assert_eq!(file_path.as_ref(), "src/main.rs");
+                assert_eq!(*line_number, 12);
+            }
+            _ => panic!("Expected ReviewDiffJump event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_copies_the_location_of_the_selected_comment() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        view.handle_app_events(&mut app, &loaded_comments_event(grouped_test_comments()));
+
+        // Nothing is copied while typing
+        view.handle_char('y', &mut app);
+        assert!(!app.events.has_pending_events());
+
+        view.handle_tab();
+        view.handle_char('y', &mut app);
+        view.move_selection_down();
+        view.handle_char('y', &mut app);
+
+        let mut copied = vec![];
+        while let Some(event) = app.events.try_recv() {
+            match &*event {
+                crate::event::Event::App(AppEvent::ClipboardCopy(text)) => {
+                    copied.push(text.to_string());
+                }
+                _ => panic!("Expected ClipboardCopy event, got: {event:?}"),
+            }
+        }
+        assert_eq!(copied, vec!["src/main.rs", "src/main.rs:12"]);
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_render_grouped_by_line() {
+        let mut app = create_test_app().await;
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        view.handle_app_events(&mut app, &loaded_comments_event(grouped_test_comments()));
+        view.handle_tab();
+        view.move_selection_down();
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_selection_is_kept_by_id_across_reloads() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+        let newer = Comment::test_comment("review-123", "src/main.rs", None, "Newer");
+        let older = Comment::test_comment("review-123", "src/main.rs", None, "Older");
+        view.handle_app_events(
+            &mut app,
+            &loaded_comments_event(vec![newer.clone(), older.clone()]),
+        );
+        view.handle_tab();
+        view.move_selection_down();
+        assert_eq!(view.get_selected_comment().unwrap().id, older.id);
+
+        // Adding a comment from the input reloads the comments with the new one first
+        view.handle_tab();
+        let added = Comment::test_comment("review-123", "src/main.rs", None, "Added");
+        view.handle_app_events(
+            &mut app,
+            &loaded_comments_event(vec![added.clone(), newer.clone(), older.clone()]),
+        );
+        view.handle_tab();
+        assert_eq!(view.selected_comment_index, Some(2));
+        assert_eq!(view.get_selected_comment().unwrap().id, older.id);
+
+        // A reload while the comments list is focused keeps the selection as well
+        let another = Comment::test_comment("review-123", "src/main.rs", None, "Another");
+        view.handle_app_events(
+            &mut app,
+            &loaded_comments_event(vec![another, added, newer, older.clone()]),
+        );
+        assert_eq!(view.selected_comment_index, Some(3));
+
+        // The selection is clamped when the selected comment is gone
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![older.clone()]));
+        assert_eq!(view.selected_comment_index, Some(0));
+        view.handle_app_events(
+            &mut app,
+            &loaded_comments_event(vec![Comment::test_comment(
+                "review-123",
+                "src/main.rs",
+                None,
+                "Other",
+            )]),
+        );
+        assert_eq!(view.selected_comment_index, Some(0));
+    }
+
+    /// Comments view focused on its comments list with the input collapsed or not
+    async fn create_app_with_comments_list_focused(collapse_input: bool) -> App {
+        let mut app = create_test_app().await;
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let comment = Comment::new_with_time_provider(
+            "review-123",
+            "src/main.rs",
+            None,
+            "Looks good",
+            &MockTimeProvider::new(fixed_time()),
+        );
+        view.handle_app_events(&mut app, &loaded_comments_event(vec![comment]));
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(KeyCode::Tab, KeyModifiers::empty()),
+        )
+        .unwrap();
+        if collapse_input {
+            view.handle_key_events(
+                &mut app,
+                &KeyEvent::new(KeyCode::Char('c'), KeyModifiers::empty()),
+            )
+            .unwrap();
+        }
+
+        App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        }
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_render_expanded_input() {
+        let app = create_app_with_comments_list_focused(false).await;
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_render_collapsed_input() {
+        let app = create_app_with_comments_list_focused(true).await;
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_comments_view_collapsed_input_expands_when_typing() {
+        let mut view =
+            CommentsView::new_for_file("review-123".to_string(), "src/main.rs".to_string());
+        let mut app = create_test_app().await;
+
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(KeyCode::Tab, KeyModifiers::empty()),
+        )
+        .unwrap();
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(KeyCode::Char('c'), KeyModifiers::empty()),
+        )
+        .unwrap();
+        assert!(view.is_input_collapsed());
+
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(KeyCode::Char('i'), KeyModifiers::empty()),
+        )
+        .unwrap();
+        assert_eq!(view.focus_state, FocusState::Input);
+        assert!(!view.is_input_collapsed());
+
+        // The mode is kept, so reading the comments again collapses the input
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(KeyCode::Tab, KeyModifiers::empty()),
+        )
+        .unwrap();
+        assert!(view.is_input_collapsed());
+    }
 }
@@ -148,6 +148,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         }
     }
 
@@ -202,6 +202,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         }
     }
 
@@ -227,7 +230,7 @@ mod tests {
         let main_view = MainView::new();
         let keybindings = main_view.get_keybindings();
         let view = HelpModalView::new(Arc::clone(&keybindings));
-        assert_eq!(view.keybindings.len(), 6);
+        assert_eq!(view.keybindings.len(), 16);
         assert_eq!(view.list_state.selected(), Some(0));
     }
 
@@ -236,7 +239,7 @@ mod tests {
         let review_create_view = ReviewCreateView::default();
         let keybindings = review_create_view.get_keybindings();
         let view = HelpModalView::new(Arc::clone(&keybindings));
-        assert_eq!(view.keybindings.len(), 4);
+        assert_eq!(view.keybindings.len(), 7);
         assert_eq!(view.list_state.selected(), Some(0));
     }
 
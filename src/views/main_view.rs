@@ -1,30 +1,42 @@
 #[cfg(test)]
 use std::any::Any;
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style, Stylize},
+    text::{Line, Span},
     widgets::{Block, List, ListItem, Paragraph, Widget},
 };
 
 use crate::{
     app::App,
     event::AppEvent,
-    models::Review,
-    services::ReviewsLoadingState,
-    views::{KeyBinding, ViewHandler, ViewType},
+    models::{ProgressMetric, Review, ReviewId, ReviewProgress},
+    services::{ReviewUpdateData, ReviewsLoadingState},
+    views::{KeyBinding, ViewHandler, ViewType, toggle_full_shas},
 };
 
 const REVIEW_SELECTION_INDICATOR: &str = super::SELECTION_INDICATOR;
 
 pub struct MainView {
     selected_review_index: Option<usize>,
+    /// Listed reviews, the loaded reviews filtered by the assignee filter
     reviews: Arc<[Review]>,
+    /// All loaded reviews
+    loaded_reviews: Arc<[Review]>,
+    /// Only list the reviews assigned to this name
+    assignee_filter: Option<String>,
+    /// Progress per review, reviews without anything to count are missing
+    progress: Arc<HashMap<ReviewId, ReviewProgress>>,
     reviews_loading_state: ReviewsLoadingState,
+    /// Review marked as the first review of a comparison
+    compare_review_id: Option<ReviewId>,
+    /// Review that stays selected when the next reload moves it, e.g. after pinning it
+    followed_review_id: Option<ReviewId>,
 }
 
 impl Default for MainView {
@@ -40,15 +52,29 @@ impl ViewHandler for MainView {
 
     fn handle_key_events(&mut self, app: &mut App, key_event: &KeyEvent) -> color_eyre::Result<()> {
         match key_event.code {
+            KeyCode::Char('q') if app.settings.confirm_quit => {
+                app.events.send(AppEvent::QuitConfirm)
+            }
             KeyCode::Char('q') => app.events.send(AppEvent::Quit),
+            // Ctrl+C always quits right away, even when quitting is confirmed
             KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
                 app.events.send(AppEvent::Quit)
             }
             KeyCode::Char('n') => self.create_review(app),
+            KeyCode::Char('C') => app.events.send(AppEvent::QuickCompareOpen),
             KeyCode::Char('j') | KeyCode::Down => self.select_next_review(),
             KeyCode::Char('k') | KeyCode::Up => self.select_previous_review(),
             KeyCode::Char('d') => self.delete_selected_review(app),
             KeyCode::Char('r') => self.open_review_refresh(app),
+            KeyCode::Char('e') => self.open_review_edit(app),
+            KeyCode::Char('c') => self.compare_selected_review(app),
+            KeyCode::Char('p') => self.toggle_selected_review_pinned(app),
+            KeyCode::Char('a') => self.cycle_assignee_filter(),
+            KeyCode::Char('t') => self.toggle_recent_reviews_only(app),
+            KeyCode::Char('i') => self.toggle_include_archived(app),
+            KeyCode::Char('m') => self.toggle_progress_metric(app),
+            KeyCode::Char('S') => toggle_full_shas(app),
+            KeyCode::Char('A') => app.events.send(AppEvent::ReviewsArchiveConfirm),
             KeyCode::Char('o') | KeyCode::Char(' ') | KeyCode::Enter => {
                 self.open_review_details(app)
             }
@@ -58,7 +84,7 @@ impl ViewHandler for MainView {
         Ok(())
     }
 
-    fn render(&self, _app: &App, area: Rect, buf: &mut Buffer) {
+    fn render(&self, app: &App, area: Rect, buf: &mut Buffer) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(3), Constraint::Min(0)])
@@ -72,26 +98,67 @@ impl ViewHandler for MainView {
         let reviews: Vec<ListItem> = match &self.reviews_loading_state {
             ReviewsLoadingState::Init => self.render_reviews_init(),
             ReviewsLoadingState::Loading => self.render_reviews_loading(),
-            ReviewsLoadingState::Loaded(_reviews) => self.render_reviews_loaded(),
+            ReviewsLoadingState::Loaded(..) => {
+                self.render_reviews_loaded(app.settings.progress_metric, app.settings.full_shas)
+            }
             ReviewsLoadingState::Error(error) => self.render_reviews_error(error),
         };
 
+        let mut title = "Reviews".to_string();
+        if app.settings.recent_reviews_only {
+            title.push_str(&format!(
+                " of the last {} days",
+                app.settings.recent_review_days
+            ));
+        }
+        if let Some(assignee) = &self.assignee_filter {
+            title.push_str(&format!(" assigned to {assignee}"));
+        }
+        if app.settings.include_archived {
+            title.push_str(" including archived");
+        }
         let reviews_list = List::new(reviews)
-            .block(Block::bordered().title("Reviews"))
+            .block(Block::bordered().title(title))
             .style(Style::default().fg(Color::White));
 
         reviews_list.render(chunks[1], buf);
     }
 
-    fn handle_app_events(&mut self, _app: &mut App, event: &AppEvent) {
+    fn handle_app_events(&mut self, app: &mut App, event: &AppEvent) {
         match event {
             AppEvent::ReviewsLoadingState(state) => {
                 self.reviews_loading_state = state.clone();
-                if let ReviewsLoadingState::Loaded(reviews) = state {
-                    self.reviews = Arc::clone(reviews);
-                    self.update_selection_after_reviews_change();
+                if let ReviewsLoadingState::Loaded(reviews, progress) = state {
+                    self.loaded_reviews = Arc::clone(reviews);
+                    self.progress = Arc::clone(progress);
+                    self.apply_assignee_filter();
+                    if let Some(followed_review_id) = self.followed_review_id.take()
+                        && let Some(index) = self
+                            .reviews
+                            .iter()
+                            .position(|review| review.id == followed_review_id)
+                    {
+                        self.selected_review_index = Some(index);
+                    }
+                    if let Some(compare_review_id) = &self.compare_review_id
+                        && !self
+                            .reviews
+                            .iter()
+                            .any(|review| review.id == *compare_review_id)
+                    {
+                        self.compare_review_id = None;
+                    }
                 }
             }
+            AppEvent::CommentCreated(_)
+            | AppEvent::CommentMarkedResolved { .. }
+            | AppEvent::CommentToggledResolved { .. }
+            | AppEvent::CommentsMarkedAllResolved { .. }
+            | AppEvent::CommentsToggledAllResolved { .. }
+            | AppEvent::ChecklistChanged { .. } => {
+                // Reload the reviews to keep the comment count and checklist badges up to date
+                app.events.send(AppEvent::ReviewsLoad);
+            }
             _ => {
                 // Ignore other events
             }
@@ -125,6 +192,16 @@ impl ViewHandler for MainView {
                     state: KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "C".to_string(),
+                description: "Quick compare two branches without a review".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('C'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
             KeyBinding {
                 key: "Up / Down / k / j".to_string(),
                 description: "Navigate review selection".to_string(),
@@ -165,6 +242,96 @@ impl ViewHandler for MainView {
                     state: KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "e".to_string(),
+                description: "Change review branches and assignees".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('e'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "c".to_string(),
+                description: "Mark review / compare with marked".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('c'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "p".to_string(),
+                description: "Pin review to the top / unpin it".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('p'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "a".to_string(),
+                description: "Filter reviews by assignee".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('a'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "t".to_string(),
+                description: "Toggle showing only recent reviews".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('t'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "i".to_string(),
+                description: "Toggle showing archived reviews".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('i'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "m".to_string(),
+                description: "Toggle progress by viewed files / resolved comments".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('m'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "S".to_string(),
+                description: "Toggle short / full SHAs".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('S'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "A".to_string(),
+                description: "Archive old done reviews".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('A'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
         ])
     }
 
@@ -179,7 +346,12 @@ impl MainView {
         Self {
             selected_review_index: None,
             reviews: Arc::new([]),
+            loaded_reviews: Arc::new([]),
+            assignee_filter: None,
+            progress: Arc::new(HashMap::new()),
             reviews_loading_state: ReviewsLoadingState::Init,
+            compare_review_id: None,
+            followed_review_id: None,
         }
     }
 
@@ -198,7 +370,79 @@ impl MainView {
         }
     }
 
+    /// List the loaded reviews assigned to the filtered name, or all of them without a filter
+    fn apply_assignee_filter(&mut self) {
+        self.reviews = match &self.assignee_filter {
+            Some(assignee) => self
+                .loaded_reviews
+                .iter()
+                .filter(|review| review.is_assigned_to(assignee))
+                .cloned()
+                .collect(),
+            None => Arc::clone(&self.loaded_reviews),
+        };
+        self.update_selection_after_reviews_change();
+    }
+
+    /// Names of all assignees of the loaded reviews, sorted case-insensitively
+    fn assignee_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in self
+            .loaded_reviews
+            .iter()
+            .flat_map(|review| review.assignees())
+        {
+            if !names
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(name))
+            {
+                names.push(name.to_string());
+            }
+        }
+        names.sort_by_key(|name| name.to_lowercase());
+        names
+    }
+
+    /// Filter the reviews by the next assignee, after the last one the filter is removed
+    pub fn cycle_assignee_filter(&mut self) {
+        let names = self.assignee_names();
+        let next_index = match &self.assignee_filter {
+            Some(assignee) => names
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(assignee))
+                .map_or(0, |index| index + 1),
+            None => 0,
+        };
+        self.assignee_filter = names.get(next_index).cloned();
+        self.selected_review_index = None;
+        self.apply_assignee_filter();
+    }
+
+    /// Toggle listing only the reviews created in the last `recent_review_days` days and
+    /// reload the reviews with it
+    pub fn toggle_recent_reviews_only(&mut self, app: &mut App) {
+        app.settings.recent_reviews_only = !app.settings.recent_reviews_only;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
+        app.events.send(AppEvent::ReviewsLoad);
+    }
+
     /// Open the review creation view
+    /// Toggle listing the archived reviews too and reload the reviews with it
+    pub fn toggle_include_archived(&mut self, app: &mut App) {
+        app.settings.include_archived = !app.settings.include_archived;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
+        app.events.send(AppEvent::ReviewsLoad);
+    }
+
+    /// Toggle measuring the progress of the reviews by viewed files or by resolved comments
+    pub fn toggle_progress_metric(&mut self, app: &mut App) {
+        app.settings.progress_metric = app.settings.progress_metric.toggle();
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
+    }
+
     pub fn create_review(&mut self, app: &mut App) {
         app.events.send(AppEvent::ReviewCreateOpen);
     }
@@ -269,6 +513,57 @@ impl MainView {
         }
     }
 
+    /// Open the branch selection for the currently selected review. Reviews of patch files have
+    /// no branches to change.
+    pub fn open_review_edit(&self, app: &mut App) {
+        if let Some(index) = self.selected_review_index
+            && index < self.reviews.len()
+            && self.reviews[index].patch_path.is_none()
+        {
+            let review = &self.reviews[index];
+            app.events
+                .send(AppEvent::ReviewEditOpen(Arc::new(ReviewUpdateData {
+                    review_id: review.id.clone(),
+                    base_branch: review.base_branch.clone(),
+                    target_branch: review.target_branch.clone(),
+                    assignees: review.assignees.clone(),
+                })));
+        }
+    }
+
+    /// Pin the selected review to the top of the list or unpin it, it stays selected
+    pub fn toggle_selected_review_pinned(&mut self, app: &mut App) {
+        let Some(review) = self
+            .selected_review_index
+            .and_then(|index| self.reviews.get(index))
+        else {
+            return;
+        };
+        self.followed_review_id = Some(review.id.clone());
+        app.events
+            .send(AppEvent::ReviewPinToggle(Arc::from(review.id.as_str())));
+    }
+
+    /// Mark the selected review for a comparison, or compare the marked review with the
+    /// selected one. Pressing it on the marked review unmarks it.
+    pub fn compare_selected_review(&mut self, app: &mut App) {
+        let Some(review) = self
+            .selected_review_index
+            .and_then(|index| self.reviews.get(index))
+        else {
+            return;
+        };
+
+        match self.compare_review_id.take() {
+            None => self.compare_review_id = Some(review.id.clone()),
+            Some(compare_review_id) if compare_review_id == review.id => {}
+            Some(compare_review_id) => app.events.send(AppEvent::ReviewCompareOpen {
+                review_a_id: Arc::from(compare_review_id),
+                review_b_id: Arc::from(review.id.as_str()),
+            }),
+        }
+    }
+
     fn render_reviews_init(&self) -> Vec<ListItem<'_>> {
         vec![ListItem::new("Initializing...").style(Style::default().fg(Color::Gray))]
     }
@@ -277,7 +572,11 @@ impl MainView {
         vec![ListItem::new("Loading reviews...").style(Style::default().fg(Color::Yellow))]
     }
 
-    fn render_reviews_loaded(&self) -> Vec<ListItem<'_>> {
+    fn render_reviews_loaded(
+        &self,
+        progress_metric: ProgressMetric,
+        full_shas: bool,
+    ) -> Vec<ListItem<'_>> {
         if self.reviews.is_empty() {
             vec![
                 ListItem::new("No reviews found - Press 'n' to create a new review")
@@ -289,13 +588,19 @@ impl MainView {
                 .enumerate()
                 .map(|(index, review)| {
                     let is_selected = Some(index) == self.selected_review_index;
-                    self.render_review_list_item(review, is_selected)
+                    self.render_review_list_item(review, is_selected, progress_metric, full_shas)
                 })
                 .collect()
         }
     }
 
-    fn render_review_list_item(&self, review: &Review, is_selected: bool) -> ListItem<'_> {
+    fn render_review_list_item(
+        &self,
+        review: &Review,
+        is_selected: bool,
+        progress_metric: ProgressMetric,
+        full_shas: bool,
+    ) -> ListItem<'_> {
         let style = if is_selected {
             Style::default().bg(Color::Blue).fg(Color::Black)
         } else {
@@ -306,13 +611,51 @@ impl MainView {
         } else {
             " "
         };
-        let content = format!(
-            "{} {} ({})",
+        let pin = if review.pinned { "📌 " } else { "" };
+        let mut content = format!(
+            "{} {pin}{} ({})",
             prefix,
-            review.title(),
+            review.display_title(full_shas),
             review.created_at.format("%Y-%m-%d %H:%M")
         );
-        ListItem::new(content).style(style)
+        let assignees = review.assignees();
+        if !assignees.is_empty() {
+            content.push_str(&format!(" [@{}]", assignees.join(", @")));
+        }
+        if let Some(progress) = self.progress.get(&review.id) {
+            if let Some(description) = progress.describe(progress_metric) {
+                content.push_str(&format!(" [{description}]"));
+            }
+            // The open comments are part of the progress when it's measured by comments
+            if progress_metric == ProgressMetric::ViewedFiles && progress.comment_count > 0 {
+                content.push_str(&format!(
+                    " [{} open / {}]",
+                    progress.open_comment_count(),
+                    progress.comment_count
+                ));
+            }
+            if let Some(checklist) = progress.describe_checklist() {
+                content.push_str(&format!(" [{checklist}]"));
+            }
+        }
+        if self.compare_review_id.as_ref() == Some(&review.id) {
+            content.push_str(" [compare]");
+        }
+        if review.archived {
+            content.push_str(" [archived]");
+        }
+        let mut line = vec![Span::raw(content)];
+        let deleted_branches = review.deleted_branches();
+        if !deleted_branches.is_empty() {
+            line.push(Span::styled(
+                format!(
+                    " ⚠ deleted: {} (stored SHAs are used)",
+                    deleted_branches.join(", ")
+                ),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        ListItem::new(Line::from(line)).style(style)
     }
 
     fn render_reviews_error(&self, error: &str) -> Vec<ListItem<'_>> {
@@ -345,6 +688,7 @@ mod tests {
     use crate::{
         database::Database,
         event::{AppEvent, Event},
+        event_handler::EventProcessor,
         models::Review,
         services::review_service::ReviewCreateData,
         test_utils::{fixed_time, render_app_to_terminal_backend},
@@ -381,6 +725,9 @@ mod tests {
             database,
             view_stack: vec![Box::new(MainView::new())],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         }
     }
 
@@ -456,6 +803,21 @@ mod tests {
         assert!(app.running);
     }
 
+    #[tokio::test]
+    async fn test_main_view_handle_quick_compare_key() {
+        let mut app = create_test_app_with_reviews().await;
+        let mut view = MainView::new();
+
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(KeyCode::Char('C'), KeyModifiers::NONE),
+        )
+        .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::QuickCompareOpen)));
+    }
+
     #[tokio::test]
     async fn test_main_view_handle_unknown_key() {
         let mut app = create_test_app_with_reviews().await;
@@ -600,6 +962,7 @@ mod tests {
         let reviews = Review::list_all(app.database.pool()).await.unwrap();
         app.handle_app_events(&AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
             reviews.into(),
+            Default::default(),
         )));
         assert_snapshot!(render_app_to_terminal_backend(app))
     }
@@ -609,7 +972,8 @@ mod tests {
         let mut app = create_test_app_with_reviews().await;
         // Create a MainView with Loaded state but no reviews
         let mut main_view = MainView::new();
-        main_view.reviews_loading_state = ReviewsLoadingState::Loaded(Arc::new([]));
+        main_view.reviews_loading_state =
+            ReviewsLoadingState::Loaded(Arc::new([]), Default::default());
         app.view_stack = vec![Box::new(main_view)];
         assert_snapshot!(render_app_to_terminal_backend(app))
     }
@@ -630,7 +994,8 @@ mod tests {
         // Create a MainView with first review selected
         let mut main_view = MainView::new();
         let reviews = Review::list_all(app.database.pool()).await.unwrap();
-        main_view.reviews_loading_state = ReviewsLoadingState::Loaded(reviews.clone().into());
+        main_view.reviews_loading_state =
+            ReviewsLoadingState::Loaded(reviews.clone().into(), Default::default());
         main_view.reviews = reviews.into();
         main_view.selected_review_index = Some(0);
         app.view_stack = vec![Box::new(main_view)];
@@ -638,6 +1003,373 @@ mod tests {
         assert_snapshot!(render_app_to_terminal_backend(app))
     }
 
+    #[tokio::test]
+    async fn test_main_view_render_with_comment_counts() {
+        let mut app = create_test_app_with_reviews().await;
+        let reviews = Review::list_all(app.database.pool()).await.unwrap();
+        let progress = HashMap::from([(
+            reviews[0].id.clone(),
+            ReviewProgress {
+                resolved_comment_count: 2,
+                comment_count: 5,
+                ..Default::default()
+            },
+        )]);
+        app.handle_app_events(&AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+            reviews.into(),
+            Arc::new(progress),
+        )));
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    /// App with a review whose progress differs between the progress metrics
+    async fn create_progress_test_app(progress_metric: ProgressMetric) -> App {
+        let mut app = create_test_app_with_reviews().await;
+        app.settings.progress_metric = progress_metric;
+        let reviews = Review::list_all(app.database.pool()).await.unwrap();
+        let progress = HashMap::from([(
+            reviews[0].id.clone(),
+            ReviewProgress {
+                viewed_file_count: 2,
+                skipped_file_count: 0,
+                file_count: Some(3),
+                resolved_comment_count: 1,
+                comment_count: 4,
+                checked_checklist_item_count: 3,
+                checklist_item_count: 5,
+            },
+        )]);
+        app.handle_app_events(&AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+            reviews.into(),
+            Arc::new(progress),
+        )));
+        app
+    }
+
+    #[tokio::test]
+    async fn test_main_view_render_progress_by_viewed_files() {
+        let app = create_progress_test_app(ProgressMetric::ViewedFiles).await;
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_main_view_render_progress_by_resolved_comments() {
+        let app = create_progress_test_app(ProgressMetric::ResolvedComments).await;
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_main_view_toggle_progress_metric() {
+        let mut app = create_progress_test_app(ProgressMetric::ViewedFiles).await;
+
+        app.handle_key_events(&KeyEvent::from(KeyCode::Char('m')))
+            .unwrap();
+
+        assert_eq!(
+            app.settings.progress_metric,
+            ProgressMetric::ResolvedComments
+        );
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::SettingsSave(_))));
+    }
+
+    #[tokio::test]
+    async fn test_main_view_toggle_full_shas() {
+        let mut app = create_progress_test_app(ProgressMetric::ViewedFiles).await;
+
+        app.handle_key_events(&KeyEvent::from(KeyCode::Char('S')))
+            .unwrap();
+
+        assert!(app.settings.full_shas);
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::SettingsSave(_))));
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    fn loaded_reviews_with_assignees() -> AppEvent {
+        let reviews = vec![
+            Review::builder()
+                .target_branch("feature/a")
+                .assignees("alice, Bob")
+                .build_with_time_provider(&MockTimeProvider::new(fixed_time())),
+            Review::builder()
+                .target_branch("feature/b")
+                .assignees("bob")
+                .build_with_time_provider(&MockTimeProvider::new(fixed_time())),
+            Review::builder()
+                .target_branch("feature/c")
+                .build_with_time_provider(&MockTimeProvider::new(fixed_time())),
+        ];
+        AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+            reviews.into(),
+            Arc::new(HashMap::new()),
+        ))
+    }
+
+    fn listed_target_branches(view: &MainView) -> Vec<&str> {
+        view.reviews
+            .iter()
+            .map(|review| review.target_branch.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_main_view_filter_by_assignee() {
+        let mut app = create_test_app_with_reviews().await;
+        let mut view = MainView::new();
+        view.handle_app_events(&mut app, &loaded_reviews_with_assignees());
+        let filter_key = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+
+        view.handle_key_events(&mut app, &filter_key).unwrap();
+        assert_eq!(view.assignee_filter.as_deref(), Some("alice"));
+        assert_eq!(listed_target_branches(&view), vec!["feature/a"]);
+        assert_eq!(view.selected_review_index, Some(0));
+
+        // Names are matched case-insensitively
+        view.handle_key_events(&mut app, &filter_key).unwrap();
+        assert_eq!(view.assignee_filter.as_deref(), Some("Bob"));
+        assert_eq!(
+            listed_target_branches(&view),
+            vec!["feature/a", "feature/b"]
+        );
+
+        // The filter is kept when the reviews are reloaded
+        view.handle_app_events(&mut app, &loaded_reviews_with_assignees());
+        assert_eq!(
+            listed_target_branches(&view),
+            vec!["feature/a", "feature/b"]
+        );
+
+        view.handle_key_events(&mut app, &filter_key).unwrap();
+        assert_eq!(view.assignee_filter, None);
+        assert_eq!(view.reviews.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_main_view_render_filtered_by_assignee() {
+        let mut app = create_test_app_with_reviews().await;
+        app.handle_app_events(&loaded_reviews_with_assignees());
+        app.view_stack[0]
+            .as_any_mut()
+            .downcast_mut::<MainView>()
+            .unwrap()
+            .cycle_assignee_filter();
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_main_view_compare_key_marks_and_compares_reviews() {
+        let mut app = create_test_app_with_reviews().await;
+        let mut view = MainView::new();
+        let reviews = Review::list_all(app.database.pool()).await.unwrap();
+        view.reviews = reviews.clone().into();
+        view.selected_review_index = Some(0);
+        let compare_key = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE);
+
+        // Pressing it twice on the same review unmarks it again
+        view.handle_key_events(&mut app, &compare_key).unwrap();
+        assert_eq!(view.compare_review_id, Some(reviews[0].id.clone()));
+        view.handle_key_events(&mut app, &compare_key).unwrap();
+        assert_eq!(view.compare_review_id, None);
+        assert!(!app.events.has_pending_events());
+
+        view.handle_key_events(&mut app, &compare_key).unwrap();
+        view.select_next_review();
+        view.handle_key_events(&mut app, &compare_key).unwrap();
+
+        assert_eq!(view.compare_review_id, None);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewCompareOpen {
+                review_a_id,
+                review_b_id,
+            }) => {
+                assert_eq!(review_a_id.as_ref(), reviews[0].id);
+                assert_eq!(review_b_id.as_ref(), reviews[1].id);
+            }
+            _ => panic!("Expected ReviewCompareOpen event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_main_view_render_with_review_marked_for_comparison() {
+        let mut app = create_test_app_with_reviews().await;
+        let reviews = Review::list_all(app.database.pool()).await.unwrap();
+        app.handle_app_events(&AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+            reviews.into(),
+            Arc::new(HashMap::new()),
+        )));
+        app.handle_key_events(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE))
+            .unwrap();
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_main_view_toggle_recent_reviews_only() {
+        let mut app = create_test_app_with_reviews().await;
+        let mut view = MainView::new();
+        let toggle_key = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE);
+
+        view.handle_key_events(&mut app, &toggle_key).unwrap();
+
+        assert!(app.settings.recent_reviews_only);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SettingsSave(settings)) => {
+                assert!(settings.recent_reviews_only);
+            }
+            _ => panic!("Expected SettingsSave event, got: {event:?}"),
+        }
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ReviewsLoad)));
+
+        view.handle_key_events(&mut app, &toggle_key).unwrap();
+        assert!(!app.settings.recent_reviews_only);
+    }
+
+    #[tokio::test]
+    async fn test_main_view_render_recent_reviews_only() {
+        let mut app = create_test_app_with_reviews().await;
+        app.settings.recent_reviews_only = true;
+        app.handle_app_events(&loaded_reviews_with_assignees());
+        app.view_stack[0]
+            .as_any_mut()
+            .downcast_mut::<MainView>()
+            .unwrap()
+            .cycle_assignee_filter();
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_main_view_toggle_include_archived() {
+        let mut app = create_test_app_with_reviews().await;
+        let mut view = MainView::new();
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('i')))
+            .unwrap();
+
+        assert!(app.settings.include_archived);
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::SettingsSave(_))));
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ReviewsLoad)));
+    }
+
+    #[tokio::test]
+    async fn test_main_view_pin_review() {
+        let mut app = create_test_app_with_reviews().await;
+        let process_events = async |app: &mut App| {
+            while let Some(event) = app.events.try_recv() {
+                EventProcessor::process_event(app, event).await.unwrap();
+            }
+        };
+        let list = |app: &App| -> (Vec<String>, Option<usize>) {
+            let view = app.view_stack[0]
+                .as_any()
+                .downcast_ref::<MainView>()
+                .unwrap();
+            let base_branches = view
+                .reviews
+                .iter()
+                .map(|review| {
+                    let pin = if review.pinned { "📌 " } else { "" };
+                    format!("{pin}{}", review.base_branch)
+                })
+                .collect();
+            (base_branches, view.selected_review_index)
+        };
+        app.events.send(AppEvent::ReviewsLoad);
+        process_events(&mut app).await;
+        assert_eq!(list(&app), (vec!["dev".into(), "main".into()], Some(0)));
+
+        // The older review moves to the top and stays selected
+        app.handle_key_events(&KeyEvent::from(KeyCode::Char('j')))
+            .unwrap();
+        app.handle_key_events(&KeyEvent::from(KeyCode::Char('p')))
+            .unwrap();
+        process_events(&mut app).await;
+        assert_eq!(list(&app), (vec!["📌 main".into(), "dev".into()], Some(0)));
+
+        app.handle_key_events(&KeyEvent::from(KeyCode::Char('p')))
+            .unwrap();
+        process_events(&mut app).await;
+        assert_eq!(list(&app), (vec!["dev".into(), "main".into()], Some(1)));
+    }
+
+    #[tokio::test]
+    async fn test_main_view_render_pinned_review() {
+        let mut app = create_test_app_with_reviews().await;
+        let reviews = vec![
+            Review {
+                pinned: true,
+                ..Review::builder()
+                    .target_branch("feature/active")
+                    .build_with_time_provider(&MockTimeProvider::new(fixed_time()))
+            },
+            Review::builder()
+                .target_branch("feature/a")
+                .build_with_time_provider(&MockTimeProvider::new(fixed_time())),
+        ];
+        app.handle_app_events(&AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+            reviews.into(),
+            Arc::new(HashMap::new()),
+        )));
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_main_view_render_including_archived() {
+        let mut app = create_test_app_with_reviews().await;
+        app.settings.include_archived = true;
+        let reviews = vec![
+            Review::builder()
+                .target_branch("feature/a")
+                .build_with_time_provider(&MockTimeProvider::new(fixed_time())),
+            Review {
+                archived: true,
+                ..Review::builder()
+                    .target_branch("feature/old")
+                    .build_with_time_provider(&MockTimeProvider::new(fixed_time()))
+            },
+        ];
+        app.handle_app_events(&AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+            reviews.into(),
+            Arc::new(HashMap::new()),
+        )));
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_main_view_render_with_deleted_branch() {
+        let mut app = create_test_app_with_reviews().await;
+        let reviews = vec![
+            Review::builder()
+                .target_branch("feature/deleted")
+                .base_branch_exists(Some(true))
+                .target_branch_exists(Some(false))
+                .build_with_time_provider(&MockTimeProvider::new(fixed_time())),
+            Review::builder()
+                .target_branch("feature/kept")
+                .base_branch_exists(Some(true))
+                .target_branch_exists(Some(true))
+                .build_with_time_provider(&MockTimeProvider::new(fixed_time())),
+        ];
+        app.handle_app_events(&AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+            reviews.into(),
+            Arc::new(HashMap::new()),
+        )));
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
     #[tokio::test]
     async fn test_main_view_handle_delete_key_with_selection() {
         let mut app = create_test_app_with_reviews().await;
@@ -727,7 +1459,10 @@ mod tests {
 
         view.handle_app_events(
             &mut app,
-            &AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(reviews.into())),
+            &AppEvent::ReviewsLoadingState(ReviewsLoadingState::Loaded(
+                reviews.into(),
+                Default::default(),
+            )),
         );
 
         assert_eq!(view.selected_review_index, Some(0));
@@ -766,6 +1501,8 @@ mod tests {
                 target_branch: "feature/new-review".to_string(),
                 base_sha: None,
                 target_sha: None,
+                assignees: String::new(),
+                allow_empty_diff: false,
             })),
         );
 
@@ -773,6 +1510,23 @@ mod tests {
         assert_eq!(view.selected_review_index, None);
     }
 
+    #[tokio::test]
+    async fn test_main_view_handle_app_events_comment_toggled_resolved_reloads_reviews() {
+        let mut app = create_test_app_with_reviews().await;
+        let mut view = MainView::new();
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::CommentToggledResolved {
+                comment_id: Arc::from("comment-id"),
+                resolved: true,
+            },
+        );
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ReviewsLoad)));
+    }
+
     #[tokio::test]
     async fn test_main_view_update_selection_after_reviews_change_empty() {
         let mut view = MainView::new();
@@ -897,6 +1651,65 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_main_view_handle_review_edit_key_with_selection() {
+        let mut app = create_test_app_with_reviews().await;
+        let mut view = MainView::new();
+
+        let reviews = Review::list_all(app.database.pool()).await.unwrap();
+        view.reviews = reviews.into();
+        view.selected_review_index = Some(0);
+
+        let key_event = KeyEvent {
+            code: KeyCode::Char('e'),
+            modifiers: KeyModifiers::empty(),
+            kind: KeyEventKind::Press,
+            state: KeyEventState::empty(),
+        };
+
+        view.handle_key_events(&mut app, &key_event).unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewEditOpen(data)) => {
+                assert_eq!(data.review_id, view.reviews[0].id);
+                assert_eq!(data.base_branch, view.reviews[0].base_branch);
+                assert_eq!(data.target_branch, view.reviews[0].target_branch);
+            }
+            _ => panic!("Expected ReviewEditOpen event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_main_view_handle_review_edit_key_patch_review() {
+        let mut app = create_test_app_with_reviews().await;
+        let mut view = MainView::new();
+        view.reviews = vec![Review::builder().patch_path("/tmp/change.diff").build()].into();
+        view.selected_review_index = Some(0);
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('e')))
+            .unwrap();
+
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_main_view_handle_review_edit_key_no_selection() {
+        let mut app = create_test_app_with_reviews().await;
+        let mut view = MainView::new();
+
+        let key_event = KeyEvent {
+            code: KeyCode::Char('e'),
+            modifiers: KeyModifiers::empty(),
+            kind: KeyEventKind::Press,
+            state: KeyEventState::empty(),
+        };
+
+        view.handle_key_events(&mut app, &key_event).unwrap();
+
+        assert!(!app.events.has_pending_events());
+    }
+
     #[tokio::test]
     async fn test_main_view_handle_open_review_details_key_no_selection() {
         let mut app = create_test_app_with_reviews().await;
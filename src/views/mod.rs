@@ -7,30 +7,62 @@ use ratatui::{
     buffer::Buffer,
     crossterm::event::KeyEvent,
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
 };
 
-use crate::{app::App, event::AppEvent};
+use crate::{
+    app::App,
+    event::AppEvent,
+    models::{CommentPriority, CommentStatus, SessionState},
+};
 
 pub use help_modal::KeyBinding;
 
+pub mod activity_view;
+pub mod checklist_view;
 pub mod comments_view;
 pub mod confirmation_dialog;
 pub mod help_modal;
 pub mod main_view;
+pub mod review_compare_view;
 pub mod review_create_view;
 pub mod review_details_view;
 pub mod review_refresh_dialog;
+pub mod review_summary_view;
 
-pub use comments_view::CommentsView;
+pub use activity_view::ActivityView;
+pub use checklist_view::ChecklistView;
+pub use comments_view::{CommentTarget, CommentsView};
 pub use confirmation_dialog::ConfirmationDialogView;
 pub use help_modal::HelpModalView;
 pub use main_view::MainView;
+pub use review_compare_view::ReviewCompareView;
 pub use review_create_view::ReviewCreateView;
 pub use review_details_view::ReviewDetailsView;
 pub use review_refresh_dialog::{ReviewRefreshDialogView, ReviewRefreshOptions};
+pub use review_summary_view::{ReviewSummary, ReviewSummaryView};
 
 const SELECTION_INDICATOR: &str = ">";
 
+/// Measurements of the last render that key handling needs, because the real size of a view
+/// region is only known while rendering. Views keep it in a `Cell` to update it from `render`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RenderMetrics {
+    /// Inner area of the scrollable content, without borders
+    pub content_area: Rect,
+}
+
+impl RenderMetrics {
+    /// Number of content lines that fit into the content area. Before the first render the
+    /// area is unknown and the default is used.
+    pub fn content_height(&self, default: usize) -> usize {
+        match self.content_area.height {
+            0 => default,
+            height => usize::from(height),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ViewType {
     Main,
@@ -40,6 +72,10 @@ pub enum ViewType {
     ReviewDetails,
     ReviewRefreshDialog,
     Comments,
+    Activity,
+    Checklist,
+    ReviewCompare,
+    ReviewSummary,
 }
 
 pub trait ViewHandler {
@@ -53,6 +89,15 @@ pub trait ViewHandler {
     }
     /// Get the keybindings for this view to display in help modal
     fn get_keybindings(&self) -> Arc<[KeyBinding]>;
+    /// Navigation state to resume on the next launch, for views that show a review
+    fn session_state(&self) -> Option<SessionState> {
+        None
+    }
+    /// Show the comments beside the content of this view instead of over it, for views that
+    /// support it. Returns the comments view when it is not docked.
+    fn dock_comments(&mut self, comments_view: CommentsView) -> Option<CommentsView> {
+        Some(comments_view)
+    }
 
     /// Get a debug representation of the view's state for testing purposes.
     /// This is only available in test builds.
@@ -101,6 +146,33 @@ pub fn centered_rectangle(percent_x: u16, percent_y: u16, rectangle: Rect) -> Re
         .split(popup_layout[1])[1]
 }
 
+/// Style of the comment indicators and labels of a comment priority. Normal comments keep the
+/// style of their surroundings.
+fn comment_priority_style(priority: CommentPriority) -> Style {
+    match priority {
+        CommentPriority::Low => Style::default().fg(Color::DarkGray),
+        CommentPriority::Normal => Style::default(),
+        CommentPriority::High => Style::default().fg(Color::Red),
+    }
+}
+
+/// Color of the indicator of an unresolved comment by its status
+fn comment_status_color(status: CommentStatus) -> Color {
+    match status {
+        CommentStatus::Open => Color::Gray,
+        CommentStatus::Addressed => Color::Green,
+        CommentStatus::WontFix => Color::DarkGray,
+        CommentStatus::Question => Color::Cyan,
+    }
+}
+
+/// Toggle showing the SHAs of the reviews in full or abbreviated, in every view
+fn toggle_full_shas(app: &mut App) {
+    app.settings.full_shas = !app.settings.full_shas;
+    app.events
+        .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
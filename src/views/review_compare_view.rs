@@ -0,0 +1,519 @@
+#[cfg(test)]
+use std::any::Any;
+
+use std::sync::Arc;
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+
+use crate::{
+    app::App,
+    event::{AppEvent, ReviewId},
+    services::ReviewComparison,
+    views::{KeyBinding, ViewHandler, ViewType, centered_rectangle, toggle_full_shas},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewComparisonState {
+    Loading,
+    Loaded(Arc<ReviewComparison>),
+    Error(Arc<str>),
+}
+
+/// Popup with the files that were added, removed or changed between the diffs of two reviews.
+/// The selected file can be re-diffed to see how its changes evolved from review A to review B.
+pub struct ReviewCompareView {
+    pub review_a_id: Arc<ReviewId>,
+    pub review_b_id: Arc<ReviewId>,
+    pub comparison: ReviewComparisonState,
+    /// Index of the selected file in the added, removed and changed files
+    pub selected_file_index: usize,
+    /// Why the selected file can't be re-diffed, cleared by the next key
+    notice: Option<Arc<str>>,
+}
+
+impl ReviewCompareView {
+    pub fn new(review_a_id: Arc<ReviewId>, review_b_id: Arc<ReviewId>) -> Self {
+        Self {
+            review_a_id,
+            review_b_id,
+            comparison: ReviewComparisonState::Loading,
+            selected_file_index: 0,
+            notice: None,
+        }
+    }
+
+    /// Added, removed and changed files with their prefix and color, in the order they are listed
+    fn files(comparison: &ReviewComparison) -> Vec<(&'static str, &String, Color)> {
+        let files = [
+            ("+", &comparison.added_files, Color::Green),
+            ("-", &comparison.removed_files, Color::Red),
+            ("~", &comparison.changed_files, Color::Yellow),
+        ];
+        files
+            .into_iter()
+            .flat_map(|(prefix, paths, color)| paths.iter().map(move |path| (prefix, path, color)))
+            .collect()
+    }
+
+    /// Lines above the files: the reviews and the summary of the comparison
+    fn header_lines(comparison: &ReviewComparison, full_shas: bool) -> Vec<Line<'_>> {
+        let mut lines = vec![
+            Line::from(format!(
+                "A: {}",
+                comparison.review_a.display_title(full_shas)
+            )),
+            Line::from(format!(
+                "B: {}",
+                comparison.review_b.display_title(full_shas)
+            )),
+        ];
+        if comparison.different_branches() {
+            lines.push(Line::styled(
+                "The reviews are on different branches",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        lines.push(Line::from(format!(
+            "{} added, {} removed, {} changed, {} unchanged files",
+            comparison.added_files.len(),
+            comparison.removed_files.len(),
+            comparison.changed_files.len(),
+            comparison.unchanged_file_count
+        )));
+        lines.push(Line::from(""));
+        lines
+    }
+
+    fn comparison_lines<'a>(
+        &self,
+        comparison: &'a ReviewComparison,
+        full_shas: bool,
+    ) -> Vec<Line<'a>> {
+        let mut lines = Self::header_lines(comparison, full_shas);
+        lines.extend(Self::files(comparison).into_iter().enumerate().map(
+            |(index, (prefix, path, color))| {
+                let mut style = Style::default().fg(color);
+                if index == self.selected_file_index {
+                    style = style.bg(Color::DarkGray).add_modifier(Modifier::BOLD);
+                }
+                Line::styled(format!("{prefix} {path}"), style)
+            },
+        ));
+        lines
+    }
+
+    fn selected_file(&self) -> Option<(&ReviewComparison, &String)> {
+        let ReviewComparisonState::Loaded(comparison) = &self.comparison else {
+            return None;
+        };
+        let (_, path, _) = Self::files(comparison)
+            .into_iter()
+            .nth(self.selected_file_index)?;
+        Some((comparison, path))
+    }
+
+    fn file_count(&self) -> usize {
+        match &self.comparison {
+            ReviewComparisonState::Loaded(comparison) => Self::files(comparison).len(),
+            ReviewComparisonState::Loading | ReviewComparisonState::Error(_) => 0,
+        }
+    }
+
+    fn select_next_file(&mut self) {
+        let file_count = self.file_count();
+        if file_count == 0 {
+            return;
+        }
+
+        self.selected_file_index = (self.selected_file_index + 1).min(file_count - 1);
+    }
+
+    fn select_previous_file(&mut self) {
+        self.selected_file_index = self.selected_file_index.saturating_sub(1);
+    }
+
+    /// Open the diff of the selected file between the target trees of both reviews
+    fn open_file_rediff(&mut self, app: &mut App) {
+        let Some((comparison, path)) = self.selected_file() else {
+            return;
+        };
+        match comparison.rediff_review() {
+            Ok(review) => app.events.send(AppEvent::QuickReviewOpen {
+                review: Arc::new(review),
+                pathspec: Some(Arc::from(path.as_str())),
+            }),
+            Err(error) => self.notice = Some(error.to_string().into()),
+        }
+    }
+}
+
+impl ViewHandler for ReviewCompareView {
+    fn view_type(&self) -> ViewType {
+        ViewType::ReviewCompare
+    }
+
+    fn handle_key_events(&mut self, app: &mut App, key_event: &KeyEvent) -> color_eyre::Result<()> {
+        self.notice = None;
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                app.events.send(AppEvent::ViewClose);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select_previous_file();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.select_next_file();
+            }
+            KeyCode::Enter => {
+                self.open_file_rediff(app);
+            }
+            KeyCode::Char('S') => toggle_full_shas(app),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_app_events(&mut self, _app: &mut App, event: &AppEvent) {
+        match event {
+            AppEvent::ReviewCompareLoaded(comparison)
+                if *comparison.review_a.id == *self.review_a_id
+                    && *comparison.review_b.id == *self.review_b_id =>
+            {
+                self.selected_file_index = 0;
+                self.comparison = ReviewComparisonState::Loaded(Arc::clone(comparison));
+            }
+            AppEvent::ReviewCompareLoadError(error) => {
+                self.selected_file_index = 0;
+                self.comparison = ReviewComparisonState::Error(Arc::clone(error));
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self, app: &App, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rectangle(80, 80, area);
+
+        Clear.render(popup_area, buf);
+
+        let mut block = Block::bordered()
+            .title("Compare Reviews")
+            .border_type(BorderType::Rounded)
+            .style(Style::default().bg(Color::Black));
+        if let Some(notice) = &self.notice {
+            block = block.title_bottom(
+                Line::from(format!(" {notice} ")).style(Style::default().fg(Color::Yellow)),
+            );
+        }
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner);
+
+        match &self.comparison {
+            ReviewComparisonState::Loading => {
+                Paragraph::new("Comparing reviews...")
+                    .style(Style::default().fg(Color::Gray))
+                    .render(chunks[0], buf);
+            }
+            ReviewComparisonState::Error(error) => {
+                Paragraph::new(error.as_ref())
+                    .style(Style::default().fg(Color::Red))
+                    .render(chunks[0], buf);
+            }
+            ReviewComparisonState::Loaded(comparison) => {
+                // Scroll just enough to keep the selected file visible
+                let selected_line = Self::header_lines(comparison, app.settings.full_shas).len()
+                    + self.selected_file_index;
+                let scroll_offset = (selected_line + 1).saturating_sub(chunks[0].height.into());
+                Paragraph::new(self.comparison_lines(comparison, app.settings.full_shas))
+                    .style(Style::default().fg(Color::White))
+                    .scroll((scroll_offset as u16, 0))
+                    .render(chunks[0], buf);
+            }
+        }
+
+        Paragraph::new(
+            "Use ↑/↓ or j/k to select a file, Enter to see how it changed, Esc to close",
+        )
+        .style(Style::default().fg(Color::Gray))
+        .render(chunks[1], buf);
+    }
+
+    #[cfg(test)]
+    fn debug_state(&self) -> String {
+        format!(
+            "ReviewCompareView(review_a_id: {}, review_b_id: {}, selected_file_index: {})",
+            self.review_a_id, self.review_b_id, self.selected_file_index
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_keybindings(&self) -> Arc<[KeyBinding]> {
+        Arc::new([
+            KeyBinding {
+                key: "↑ / k".to_string(),
+                description: "Select the previous file".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('k'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "↓ / j".to_string(),
+                description: "Select the next file".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('j'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "Enter".to_string(),
+                description: "Show how the changes of the file evolved from A to B".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Enter,
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "S".to_string(),
+                description: "Toggle short / full SHAs".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('S'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "Esc / q".to_string(),
+                description: "Close".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Esc,
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+        ])
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use insta::assert_snapshot;
+    use sqlx::SqlitePool;
+
+    use crate::{
+        database::Database,
+        event::{Event, EventHandler},
+        models::Review,
+        test_utils::{fixed_time, render_app_to_terminal_backend},
+        time_provider::MockTimeProvider,
+    };
+
+    async fn create_test_app() -> App {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        App {
+            running: true,
+            events: EventHandler::new_for_test(),
+            database: Database::from_pool(pool),
+            view_stack: vec![],
+            repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
+        }
+    }
+
+    fn review(id: &str, target_branch: &str, target_sha: &str) -> Review {
+        Review {
+            id: id.to_string(),
+            ..Review::builder()
+                .base_branch("main")
+                .target_branch(target_branch)
+                .base_sha_str("1111111111")
+                .target_sha_str(target_sha)
+                .build_with_time_provider(&MockTimeProvider::new(fixed_time()))
+        }
+    }
+
+    fn test_comparison(target_branch_b: &str) -> Arc<ReviewComparison> {
+        Arc::new(ReviewComparison {
+            review_a: Arc::new(review("review-a", "feature", "2222222222")),
+            review_b: Arc::new(review("review-b", target_branch_b, "3333333333")),
+            added_files: vec!["src/new.rs".to_string()],
+            removed_files: vec!["src/old.rs".to_string()],
+            changed_files: vec!["src/main.rs".to_string(), "src/lib.rs".to_string()],
+            unchanged_file_count: 2,
+        })
+    }
+
+    fn loaded_view(app: &mut App, comparison: Arc<ReviewComparison>) -> ReviewCompareView {
+        let mut view = ReviewCompareView::new(Arc::from("review-a"), Arc::from("review-b"));
+        view.handle_app_events(app, &AppEvent::ReviewCompareLoaded(comparison));
+        view
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[tokio::test]
+    async fn test_comparison_of_other_reviews_is_ignored() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewCompareView::new(Arc::from("review-a"), Arc::from("review-c"));
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ReviewCompareLoaded(test_comparison("feature")),
+        );
+
+        assert_eq!(view.comparison, ReviewComparisonState::Loading);
+    }
+
+    #[tokio::test]
+    async fn test_selection_stops_at_the_ends() {
+        let mut app = create_test_app().await;
+        let mut view = loaded_view(&mut app, test_comparison("feature"));
+
+        view.handle_key_events(&mut app, &key(KeyCode::Char('k')))
+            .unwrap();
+        assert_eq!(view.selected_file_index, 0);
+        for _ in 0..20 {
+            view.handle_key_events(&mut app, &key(KeyCode::Char('j')))
+                .unwrap();
+        }
+        // 1 added, 1 removed and 2 changed files
+        assert_eq!(view.selected_file_index, 3);
+    }
+
+    #[tokio::test]
+    async fn test_enter_opens_the_rediff_of_the_selected_file() {
+        let mut app = create_test_app().await;
+        let mut view = loaded_view(&mut app, test_comparison("feature"));
+
+        view.handle_key_events(&mut app, &key(KeyCode::Char('j')))
+            .unwrap();
+        view.handle_key_events(&mut app, &key(KeyCode::Enter))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::QuickReviewOpen { review, pathspec }) => {
+                assert_eq!(review.base_sha.as_deref(), Some("2222222222"));
+                assert_eq!(review.target_sha.as_deref(), Some("3333333333"));
+                assert_eq!(pathspec.as_deref(), Some("src/old.rs"));
+            }
+            _ => panic!("Expected QuickReviewOpen event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enter_shows_why_a_patch_review_cant_be_rediffed() {
+        let mut app = create_test_app().await;
+        let comparison = test_comparison("feature");
+        let mut review_b = (*comparison.review_b).clone();
+        review_b.target_sha = None;
+        review_b.patch_path = Some("/tmp/change.patch".to_string());
+        let mut view = loaded_view(
+            &mut app,
+            Arc::new(ReviewComparison {
+                review_b: Arc::new(review_b),
+                ..(*comparison).clone()
+            }),
+        );
+
+        view.handle_key_events(&mut app, &key(KeyCode::Enter))
+            .unwrap();
+
+        assert!(!app.events.has_pending_events());
+        assert_eq!(
+            view.notice.as_deref(),
+            Some("Only reviews of branches can be re-diffed")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_esc_closes_view() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewCompareView::new(Arc::from("review-a"), Arc::from("review-b"));
+
+        view.handle_key_events(&mut app, &key(KeyCode::Esc))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ViewClose)));
+    }
+
+    #[tokio::test]
+    async fn test_render_comparison() {
+        let mut app = create_test_app().await;
+        let view = loaded_view(&mut app, test_comparison("feature"));
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_render_comparison_of_different_branches() {
+        let mut app = create_test_app().await;
+        let view = loaded_view(&mut app, test_comparison("other-feature"));
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_render_error() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewCompareView::new(Arc::from("review-a"), Arc::from("review-b"));
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ReviewCompareLoadError(
+                "Error comparing reviews: Review main -> feature has no SHAs to compare".into(),
+            ),
+        );
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+}
@@ -8,13 +8,17 @@ use ratatui::{
     crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
-    widgets::{Block, BorderType, Clear, List, ListItem, Paragraph, Widget},
+    text::Line,
+    widgets::{Block, BorderType, Clear, List, ListItem, Paragraph, Widget, Wrap},
 };
 
 use crate::{
     app::App,
     event::AppEvent,
-    services::{GitBranchesLoadingState, ReviewCreateData},
+    services::{
+        DiffPreview, GitBranchesLoadingState, ReviewCreateData, ReviewService, ReviewUpdateData,
+        SinceLastTag,
+    },
     views::{KeyBinding, ViewHandler, ViewType, centered_rectangle},
 };
 
@@ -24,6 +28,28 @@ pub struct ReviewCreateView {
     pub base_branch_index: usize,
     pub target_branch_index: usize,
     pub current_field: InputField,
+    /// Review whose branches are changed instead of creating a new review
+    pub edited_review: Option<ReviewUpdateData>,
+    /// The selected branches are opened as a quick review instead of creating a review
+    pub quick_compare: bool,
+    /// Diff stats of the selected branches, cleared when the selection changes
+    pub diff_preview: Option<DiffPreviewState>,
+    /// Comma-separated names of who should look at the review
+    pub assignees: String,
+    /// The branches are reloaded on request, the loaded ones stay shown until then
+    pub refreshing: bool,
+    /// Message shown at the bottom of the popup until the next key press
+    pub notice: Option<Arc<str>>,
+    /// Tag and branch of the changes since the last tag, `None` disables reviewing them
+    pub since_last_tag: Option<Arc<SinceLastTag>>,
+}
+
+/// State of the diff preview of the selected branches
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffPreviewState {
+    Loading,
+    Loaded(Arc<DiffPreview>),
+    Error(Arc<str>),
 }
 
 #[derive(Default, PartialEq, Debug)]
@@ -31,6 +57,7 @@ pub enum InputField {
     #[default]
     BaseBranch,
     TargetBranch,
+    Assignees,
 }
 
 impl ViewHandler for ReviewCreateView {
@@ -39,12 +66,28 @@ impl ViewHandler for ReviewCreateView {
     }
 
     fn handle_key_events(&mut self, app: &mut App, key_event: &KeyEvent) -> color_eyre::Result<()> {
+        self.notice = None;
         match key_event.code {
-            KeyCode::Esc => self.close_view(app),
+            KeyCode::Esc => {
+                if self.diff_preview.is_some() {
+                    self.diff_preview = None;
+                } else {
+                    self.close_view(app);
+                }
+            }
             KeyCode::Tab => self.review_selection_switch(),
+            KeyCode::Enter => self.submit_review(app),
+            KeyCode::Char(char) if self.current_field == InputField::Assignees => {
+                self.assignees.push(char);
+            }
+            KeyCode::Backspace if self.current_field == InputField::Assignees => {
+                self.assignees.pop();
+            }
             KeyCode::Up | KeyCode::Char('k') => self.review_selection_up(),
             KeyCode::Down | KeyCode::Char('j') => self.review_selection_down(),
-            KeyCode::Enter => self.submit_review(app),
+            KeyCode::Char('p') => self.preview_diff(app),
+            KeyCode::Char('r') => self.refresh_branches(app),
+            KeyCode::Char('l') => self.submit_since_last_tag(app),
             KeyCode::Char('?') => app.events.send(AppEvent::HelpOpen(self.get_keybindings())),
             _ => {}
         }
@@ -53,10 +96,29 @@ impl ViewHandler for ReviewCreateView {
 
     fn handle_app_events(&mut self, app: &mut App, event: &AppEvent) {
         match event {
-            AppEvent::ReviewCreated(_review) => self.close_view(app),
+            AppEvent::ReviewCreated(review) => {
+                self.close_view(app);
+                // Sent after closing, so that the details are opened once the dialog is closed
+                if app.settings.open_review_after_create {
+                    app.events
+                        .send(AppEvent::ReviewDetailsOpen(Arc::from(review.id.as_str())));
+                }
+            }
             AppEvent::ReviewCreatedError(_error) => self.close_view(app),
-            AppEvent::GitBranchesLoadingState(state) => {
-                self.handle_git_branches_loading_state(state)
+            AppEvent::ReviewUpdated(_review) => self.close_view(app),
+            AppEvent::ReviewUpdateError(_error) => self.close_view(app),
+            AppEvent::GitBranchesLoadingState(state) => self.handle_git_branches_loading_state(
+                state,
+                app.settings.default_base_branch.as_deref(),
+            ),
+            AppEvent::GitSinceLastTagLoaded(since_last_tag) => {
+                self.since_last_tag = since_last_tag.clone();
+            }
+            AppEvent::DiffPreviewLoaded(preview) => self.handle_diff_preview_loaded(preview),
+            AppEvent::DiffPreviewError(error)
+                if self.diff_preview == Some(DiffPreviewState::Loading) =>
+            {
+                self.diff_preview = Some(DiffPreviewState::Error(Arc::clone(error)));
             }
             _ => (),
         }
@@ -67,10 +129,22 @@ impl ViewHandler for ReviewCreateView {
 
         Clear.render(popup_area, buf);
 
-        let block = Block::bordered()
-            .title("Create New Review - Select Branches")
+        let title = if self.edited_review.is_some() {
+            "Edit Review - Select Branches"
+        } else if self.quick_compare {
+            "Quick Compare - Select Branches"
+        } else {
+            "Create New Review - Select Branches"
+        };
+        let mut block = Block::bordered()
+            .title(title)
             .border_type(BorderType::Rounded)
             .style(Style::default().bg(Color::Black));
+        if let Some(notice) = &self.notice {
+            block = block.title_bottom(
+                Line::from(format!(" {notice} ")).style(Style::default().fg(Color::Yellow)),
+            );
+        }
 
         let inner = block.inner(popup_area);
         block.render(popup_area, buf);
@@ -88,6 +162,22 @@ impl ViewHandler for ReviewCreateView {
                 loading.render(inner, buf);
                 return;
             }
+            GitBranchesLoadingState::NoCommits => {
+                let onboarding = Paragraph::new(vec![
+                    Line::from("This repository has no commits yet."),
+                    Line::from(""),
+                    Line::from(
+                        "A review compares the commits of two branches, so at least one commit is needed.",
+                    ),
+                    Line::from("Create the first commit, e.g. with:"),
+                    Line::from(""),
+                    Line::from("  git add . && git commit -m \"Initial commit\""),
+                ])
+                .style(Style::default().fg(Color::Yellow))
+                .wrap(Wrap { trim: false });
+                onboarding.render(inner, buf);
+                return;
+            }
             GitBranchesLoadingState::Error(error) => {
                 let error_paragraph =
                     Paragraph::new(error.as_ref()).style(Style::default().fg(Color::Red));
@@ -105,10 +195,37 @@ impl ViewHandler for ReviewCreateView {
             }
         }
 
+        // Keep a line above the help text free for the diff preview
+        let (lists_area, preview_area) = if self.diff_preview.is_some() {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                ])
+                .split(inner);
+            (rows[0], Some(rows[1]))
+        } else {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(inner);
+            (rows[0], None)
+        };
+
+        // Nobody is assigned to a quick compare
+        let assignees_height = if self.quick_compare { 0 } else { 3 };
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(assignees_height)])
+            .split(lists_area);
+        let (lists_area, assignees_area) = (rows[0], rows[1]);
+
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(inner);
+            .split(lists_area);
 
         // Get branches from loaded state
         let branches = match &self.branches_state {
@@ -180,6 +297,14 @@ impl ViewHandler for ReviewCreateView {
         );
         target_branch_list.render(chunks[1], buf);
 
+        if !self.quick_compare {
+            self.render_assignees_input(assignees_area, buf);
+        }
+
+        if let (Some(preview_area), Some(diff_preview)) = (preview_area, &self.diff_preview) {
+            Self::render_diff_preview(diff_preview, preview_area, buf);
+        }
+
         // Help text at the bottom
         let help_area = Rect {
             x: popup_area.x + 1,
@@ -187,8 +312,22 @@ impl ViewHandler for ReviewCreateView {
             width: popup_area.width - 2,
             height: 1,
         };
-        let help = Paragraph::new("↑↓: Navigate, Tab: Switch lists, Enter: Create, Esc: Cancel")
-            .style(Style::default().fg(Color::Gray));
+        let since_last_tag = self
+            .since_last_tag
+            .as_ref()
+            .map(|since_last_tag| format!(", l: Since {}", since_last_tag.tag))
+            .unwrap_or_default();
+        let submit = if self.edited_review.is_some() {
+            "Save"
+        } else if self.quick_compare {
+            "Compare"
+        } else {
+            "Create"
+        };
+        let help_text = format!(
+            "↑↓: Navigate, Tab: Switch fields, p: Preview, r: Refresh{since_last_tag}, Enter: {submit}, Esc: Cancel"
+        );
+        let help = Paragraph::new(help_text).style(Style::default().fg(Color::Gray));
         help.render(help_area, buf);
     }
 
@@ -207,6 +346,12 @@ impl ViewHandler for ReviewCreateView {
                     self.current_field
                 )
             }
+            GitBranchesLoadingState::NoCommits => {
+                format!(
+                    "ReviewCreateView(state: NoCommits, current_field: {:?})",
+                    self.current_field
+                )
+            }
             GitBranchesLoadingState::Error(error) => {
                 format!(
                     "ReviewCreateView(state: Error({}), current_field: {:?})",
@@ -252,7 +397,7 @@ impl ViewHandler for ReviewCreateView {
             },
             KeyBinding {
                 key: "Tab".to_string(),
-                description: "Switch between input fields".to_string(),
+                description: "Switch between branch lists and assignees".to_string(),
                 key_event: KeyEvent {
                     code: KeyCode::Tab,
                     modifiers: KeyModifiers::empty(),
@@ -260,9 +405,44 @@ impl ViewHandler for ReviewCreateView {
                     state: KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "p".to_string(),
+                description: "Preview diff stats of selected branches".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('p'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "r".to_string(),
+                description: "Refresh branches".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('r'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "l".to_string(),
+                description: "Review the changes since the last tag".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('l'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
             KeyBinding {
                 key: "Enter".to_string(),
-                description: "Create review".to_string(),
+                description: if self.quick_compare {
+                    "Compare branches"
+                } else {
+                    "Create review"
+                }
+                .to_string(),
                 key_event: KeyEvent {
                     code: KeyCode::Enter,
                     modifiers: KeyModifiers::empty(),
@@ -272,7 +452,7 @@ impl ViewHandler for ReviewCreateView {
             },
             KeyBinding {
                 key: "Esc".to_string(),
-                description: "Cancel and close popup".to_string(),
+                description: "Cancel preview or close popup".to_string(),
                 key_event: KeyEvent {
                     code: KeyCode::Esc,
                     modifiers: KeyModifiers::empty(),
@@ -290,62 +470,214 @@ impl ViewHandler for ReviewCreateView {
 }
 
 impl ReviewCreateView {
+    /// View for changing the branches and assignees of an existing review, preselecting its
+    /// current branches
+    pub fn for_review(review: &ReviewUpdateData) -> Self {
+        Self {
+            edited_review: Some(review.clone()),
+            assignees: review.assignees.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// View for picking two branches whose diff is opened as a quick review, without saving
+    /// anything
+    pub fn for_quick_compare() -> Self {
+        Self {
+            quick_compare: true,
+            ..Default::default()
+        }
+    }
+
     fn close_view(&mut self, app: &mut App) {
         self.base_branch_index = 0;
         self.target_branch_index = 0;
         self.current_field = InputField::BaseBranch;
+        self.diff_preview = None;
+        self.assignees.clear();
         app.events.send(AppEvent::ViewClose);
     }
 
-    fn submit_review(&self, app: &mut App) {
-        if let GitBranchesLoadingState::Loaded(ref branches) = self.branches_state {
-            if branches.is_empty() {
-                log::warn!("No branches available to create a review");
-                return;
+    /// Selected base and target branch, if the branches are loaded
+    fn selected_branches(&self) -> Option<(String, String)> {
+        let GitBranchesLoadingState::Loaded(ref branches) = self.branches_state else {
+            return None;
+        };
+        if branches.is_empty() {
+            log::warn!("No branches available to create a review");
+            return None;
+        }
+        let base_branch = match branches.get(self.base_branch_index) {
+            Some(branch) => branch.clone(),
+            None => {
+                // This should never happen, but handle gracefully
+                log::error!(
+                    "Base branch index {} out of bounds for branches: {:?}",
+                    self.base_branch_index,
+                    branches
+                );
+                return None;
             }
-            let base_branch = match branches.get(self.base_branch_index) {
-                Some(branch) => branch.clone(),
-                None => {
-                    // This should never happen, but handle gracefully
-                    log::error!(
-                        "Base branch index {} out of bounds for branches: {:?}",
-                        self.base_branch_index,
-                        branches
-                    );
-                    return;
-                }
-            };
-            let target_branch = match branches.get(self.target_branch_index) {
-                Some(branch) => branch.clone(),
-                None => {
-                    // This should never happen, but handle gracefully
-                    log::error!(
-                        "Target branch index {} out of bounds for branches: {:?}",
-                        self.target_branch_index,
-                        branches
-                    );
-                    return;
-                }
-            };
+        };
+        let target_branch = match branches.get(self.target_branch_index) {
+            Some(branch) => branch.clone(),
+            None => {
+                // This should never happen, but handle gracefully
+                log::error!(
+                    "Target branch index {} out of bounds for branches: {:?}",
+                    self.target_branch_index,
+                    branches
+                );
+                return None;
+            }
+        };
+        Some((base_branch, target_branch))
+    }
 
+    fn submit_review(&mut self, app: &mut App) {
+        let Some((base_branch, target_branch)) = self.selected_branches() else {
+            return;
+        };
+        self.submit_branches(app, base_branch, target_branch);
+    }
+
+    /// Submit the changes from the last tag to the checked out branch, if there is a tag
+    fn submit_since_last_tag(&mut self, app: &mut App) {
+        if !matches!(self.branches_state, GitBranchesLoadingState::Loaded(_)) {
+            return;
+        }
+        let Some(since_last_tag) = &self.since_last_tag else {
+            self.notice = Some(Arc::from("No tag before HEAD to review the changes since"));
+            return;
+        };
+        self.submit_branches(
+            app,
+            since_last_tag.tag.clone(),
+            since_last_tag.head_branch.clone(),
+        );
+    }
+
+    fn submit_branches(&mut self, app: &mut App, base_branch: String, target_branch: String) {
+        if self.quick_compare {
+            self.submit_quick_compare(app, &base_branch, &target_branch);
+            return;
+        }
+        if let Some(edited_review) = &self.edited_review {
             app.events
-                .send(AppEvent::ReviewCreateSubmit(Arc::new(ReviewCreateData {
+                .send(AppEvent::ReviewUpdate(Arc::new(ReviewUpdateData {
+                    review_id: edited_review.review_id.clone(),
                     base_branch,
                     target_branch,
-                    base_sha: None,
-                    target_sha: None,
+                    assignees: self.assignees.clone(),
                 })));
+            return;
+        }
+
+        app.events
+            .send(AppEvent::ReviewCreateSubmit(Arc::new(ReviewCreateData {
+                base_branch,
+                target_branch,
+                base_sha: None,
+                target_sha: None,
+                assignees: self.assignees.clone(),
+                allow_empty_diff: false,
+            })));
+    }
+
+    /// Open the diff of the branches as a quick review. It is opened after closing this view,
+    /// so that closing the quick review returns to the review list.
+    fn submit_quick_compare(&mut self, app: &mut App, base_branch: &str, target_branch: &str) {
+        match ReviewService::quick_review(&app.repo_path, base_branch, target_branch) {
+            Ok(review) => {
+                self.close_view(app);
+                app.events.send(AppEvent::QuickReviewOpen {
+                    review: Arc::new(review),
+                    pathspec: None,
+                });
+            }
+            Err(error) => self.notice = Some(Arc::from(error.to_string())),
+        }
+    }
+
+    /// Request the diff stats of the selected branches without creating anything
+    fn preview_diff(&mut self, app: &mut App) {
+        let Some((base_branch, target_branch)) = self.selected_branches() else {
+            return;
+        };
+
+        self.diff_preview = Some(DiffPreviewState::Loading);
+        app.events.send(AppEvent::DiffPreviewLoad {
+            base_branch: Arc::from(base_branch),
+            target_branch: Arc::from(target_branch),
+        });
+    }
+
+    /// Show the loaded preview unless it was cancelled or the selection changed meanwhile
+    fn handle_diff_preview_loaded(&mut self, preview: &Arc<DiffPreview>) {
+        if self.diff_preview != Some(DiffPreviewState::Loading) {
+            return;
+        }
+        let Some((base_branch, target_branch)) = self.selected_branches() else {
+            return;
+        };
+        if *preview.base_branch == *base_branch && *preview.target_branch == *target_branch {
+            self.diff_preview = Some(DiffPreviewState::Loaded(Arc::clone(preview)));
         }
     }
 
+    fn render_diff_preview(diff_preview: &DiffPreviewState, area: Rect, buf: &mut Buffer) {
+        let (text, color) = match diff_preview {
+            DiffPreviewState::Loading => ("Preview: Computing diff...".to_string(), Color::Yellow),
+            DiffPreviewState::Loaded(preview) if preview.is_empty() => (
+                format!(
+                    "Preview: No changes between {} and {}",
+                    preview.base_branch, preview.target_branch
+                ),
+                Color::Yellow,
+            ),
+            DiffPreviewState::Loaded(preview) => (
+                format!(
+                    "Preview: {} file{} changed, +{} -{}",
+                    preview.file_count,
+                    if preview.file_count == 1 { "" } else { "s" },
+                    preview.added_lines,
+                    preview.removed_lines
+                ),
+                Color::Green,
+            ),
+            DiffPreviewState::Error(error) => (error.to_string(), Color::Red),
+        };
+        Paragraph::new(text)
+            .style(Style::default().fg(color))
+            .render(area, buf);
+    }
+
     fn review_selection_switch(&mut self) {
         self.current_field = match self.current_field {
             InputField::BaseBranch => InputField::TargetBranch,
-            InputField::TargetBranch => InputField::BaseBranch,
+            InputField::TargetBranch if self.quick_compare => InputField::BaseBranch,
+            InputField::TargetBranch => InputField::Assignees,
+            InputField::Assignees => InputField::BaseBranch,
         };
     }
 
+    fn render_assignees_input(&self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.current_field == InputField::Assignees {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Paragraph::new(self.assignees.as_str())
+            .block(
+                Block::bordered()
+                    .title("Assignees (comma-separated)")
+                    .border_style(border_style),
+            )
+            .render(area, buf);
+    }
+
     fn review_selection_up(&mut self) {
+        self.diff_preview = None;
         if let GitBranchesLoadingState::Loaded(ref _branches) = self.branches_state {
             match self.current_field {
                 InputField::BaseBranch => {
@@ -358,11 +690,13 @@ impl ReviewCreateView {
                         self.target_branch_index -= 1;
                     }
                 }
+                InputField::Assignees => {}
             }
         }
     }
 
     fn review_selection_down(&mut self) {
+        self.diff_preview = None;
         if let GitBranchesLoadingState::Loaded(ref branches) = self.branches_state {
             match self.current_field {
                 InputField::BaseBranch => {
@@ -375,18 +709,84 @@ impl ReviewCreateView {
                         self.target_branch_index += 1;
                     }
                 }
+                InputField::Assignees => {}
             }
         }
     }
 
-    fn handle_git_branches_loading_state(&mut self, state: &GitBranchesLoadingState) {
+    /// Reload the branches, e.g. after creating a branch in another terminal
+    fn refresh_branches(&mut self, app: &mut App) {
+        if matches!(self.branches_state, GitBranchesLoadingState::Loading) {
+            return;
+        }
+        self.refreshing = true;
+        app.events.send(AppEvent::GitBranchesLoad);
+    }
+
+    /// Select the branches with the given names, the selection of a branch that doesn't exist
+    /// anymore stays in the bounds of the list
+    fn select_branches(&mut self, base_branch: &str, target_branch: &str) {
+        let GitBranchesLoadingState::Loaded(ref branches) = self.branches_state else {
+            return;
+        };
+        let last_index = branches.len().saturating_sub(1);
+        self.base_branch_index = branches
+            .iter()
+            .position(|branch| branch == base_branch)
+            .unwrap_or(self.base_branch_index.min(last_index));
+        self.target_branch_index = branches
+            .iter()
+            .position(|branch| branch == target_branch)
+            .unwrap_or(self.target_branch_index.min(last_index));
+    }
+
+    /// Show the loaded branches, preselecting the configured default base branch if it exists
+    fn handle_git_branches_loading_state(
+        &mut self,
+        state: &GitBranchesLoadingState,
+        default_base_branch: Option<&str>,
+    ) {
+        if self.refreshing {
+            // Keep showing the loaded branches until the refreshed ones arrive
+            if matches!(state, GitBranchesLoadingState::Loading) {
+                return;
+            }
+            self.refreshing = false;
+            let selection = self.selected_branches();
+            self.branches_state = state.clone();
+            if let Some((base_branch, target_branch)) = selection {
+                self.select_branches(&base_branch, &target_branch);
+            }
+            if matches!(self.branches_state, GitBranchesLoadingState::Loaded(_)) {
+                // The stats of the previous branch heads are outdated
+                self.diff_preview = None;
+                self.notice = Some(Arc::from("Branches refreshed"));
+            }
+            return;
+        }
+
         self.branches_state = state.clone();
 
-        // Set default selection to main/master if available and we just loaded
-        if let GitBranchesLoadingState::Loaded(ref branches) = self.branches_state
-            && let Some(main_index) = branches.iter().position(|b| b == "main" || b == "master")
+        let GitBranchesLoadingState::Loaded(ref branches) = self.branches_state else {
+            return;
+        };
+
+        // Preselect the current branches of an edited review
+        if let Some(edited_review) = self.edited_review.clone() {
+            self.select_branches(&edited_review.base_branch, &edited_review.target_branch);
+            return;
+        }
+
+        let default_base_index =
+            default_base_branch.and_then(|name| branches.iter().position(|b| b == name));
+        if let (Some(name), None) = (default_base_branch, default_base_index) {
+            self.notice = Some(Arc::from(format!("Default base branch {name} not found")));
+        }
+        // Otherwise set default selection to main/master if available and we just loaded
+        if let Some(index) = default_base_index
+            .or_else(|| branches.iter().position(|b| b == "main" || b == "master"))
         {
-            self.base_branch_index = main_index;
+            self.base_branch_index = index;
         }
     }
 }
@@ -402,6 +802,7 @@ mod tests {
     use crate::{
         database::Database,
         event::{AppEvent, Event, EventHandler},
+        event_handler::EventProcessor,
         test_utils::render_app_to_terminal_backend,
     };
 
@@ -417,6 +818,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         }
     }
 
@@ -444,6 +848,13 @@ mod tests {
             base_branch_index: 1,
             target_branch_index: 1,
             current_field: InputField::BaseBranch,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: String::new(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
         };
 
         let key_event_up = KeyEvent {
@@ -477,6 +888,13 @@ mod tests {
             base_branch_index: 0,
             target_branch_index: 0,
             current_field: InputField::BaseBranch,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: String::new(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
         };
 
         let key_event = KeyEvent {
@@ -489,6 +907,9 @@ mod tests {
         view.handle_key_events(&mut app, &key_event).unwrap();
         assert_eq!(view.current_field, InputField::TargetBranch);
 
+        view.handle_key_events(&mut app, &key_event).unwrap();
+        assert_eq!(view.current_field, InputField::Assignees);
+
         view.handle_key_events(&mut app, &key_event).unwrap();
         assert_eq!(view.current_field, InputField::BaseBranch);
     }
@@ -503,6 +924,13 @@ mod tests {
             base_branch_index: 0,
             target_branch_index: 0,
             current_field: InputField::BaseBranch,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: String::new(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
         };
 
         let key_event = KeyEvent {
@@ -527,6 +955,13 @@ mod tests {
             base_branch_index: 1,
             target_branch_index: 1,
             current_field: InputField::BaseBranch,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: String::new(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
         };
 
         let key_event = KeyEvent {
@@ -551,6 +986,13 @@ mod tests {
             base_branch_index: 1,
             target_branch_index: 1,
             current_field: InputField::TargetBranch,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: String::new(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
         };
         assert!(!app.events.has_pending_events());
 
@@ -590,6 +1032,13 @@ mod tests {
             base_branch_index: 0,
             target_branch_index: 2,
             current_field: InputField::BaseBranch,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: String::new(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
         };
         assert!(!app.events.has_pending_events());
 
@@ -613,6 +1062,57 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_review_create_view_edit_assignees() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewCreateView::for_review(&ReviewUpdateData {
+            review_id: "review-1".to_string(),
+            base_branch: "main".to_string(),
+            target_branch: "develop".to_string(),
+            assignees: "alice".to_string(),
+        });
+        view.handle_git_branches_loading_state(
+            &GitBranchesLoadingState::Loaded(
+                vec!["main".to_string(), "develop".to_string()].into(),
+            ),
+            None,
+        );
+
+        view.handle_key_events(&mut app, &KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE))
+            .unwrap();
+        view.handle_key_events(&mut app, &KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(view.current_field, InputField::Assignees);
+
+        // Navigation keys are typed into the assignees
+        for char in ", jk".chars() {
+            view.handle_key_events(
+                &mut app,
+                &KeyEvent::new(KeyCode::Char(char), KeyModifiers::NONE),
+            )
+            .unwrap();
+        }
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE),
+        )
+        .unwrap();
+        assert_eq!(view.assignees, "alice, j");
+        assert_eq!(view.target_branch_index, 1);
+
+        view.handle_key_events(&mut app, &KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewUpdate(data)) => {
+                assert_eq!(data.review_id, "review-1");
+                assert_eq!(data.target_branch, "develop");
+                assert_eq!(data.assignees, "alice, j");
+            }
+            _ => panic!("Expected ReviewUpdate event, got: {event:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_review_create_view_handle_enter_empty() {
         let mut app = create_test_app().await;
@@ -641,6 +1141,13 @@ mod tests {
             base_branch_index: 1,
             target_branch_index: 0,
             current_field: InputField::BaseBranch,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: String::new(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
         };
         let initial_index = view.base_branch_index;
 
@@ -657,6 +1164,335 @@ mod tests {
         assert_eq!(view.base_branch_index, initial_index);
     }
 
+    fn loaded_branches() -> AppEvent {
+        AppEvent::GitBranchesLoadingState(GitBranchesLoadingState::Loaded(
+            vec![
+                "develop".to_string(),
+                "feature".to_string(),
+                "main".to_string(),
+            ]
+            .into(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_preselects_main() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewCreateView::default();
+
+        view.handle_app_events(&mut app, &loaded_branches());
+
+        assert_eq!(view.base_branch_index, 2);
+        assert_eq!(view.notice, None);
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_preselects_default_base_branch() {
+        let mut app = create_test_app().await;
+        app.settings.default_base_branch = Some("develop".to_string());
+        let mut view = ReviewCreateView::default();
+
+        view.handle_app_events(&mut app, &loaded_branches());
+
+        assert_eq!(view.base_branch_index, 0);
+        assert_eq!(view.notice, None);
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_default_base_branch_not_found() {
+        let mut app = create_test_app().await;
+        app.settings.default_base_branch = Some("trunk".to_string());
+        let mut view = ReviewCreateView::default();
+
+        view.handle_app_events(&mut app, &loaded_branches());
+
+        // Falls back to main
+        assert_eq!(view.base_branch_index, 2);
+        assert_eq!(
+            view.notice.as_deref(),
+            Some("Default base branch trunk not found")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_refresh_branches() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit_id = repo
+            .commit(None, &signature, &signature, "Initial", &tree, &[])
+            .unwrap();
+        let commit = repo.find_commit(commit_id).unwrap();
+        repo.branch("main", &commit, true).unwrap();
+        repo.branch("feature", &commit, true).unwrap();
+
+        let view = ReviewCreateView {
+            target_branch_index: 0,
+            ..create_preview_test_view()
+        };
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            repo_path: temp_dir.path().to_string_lossy().to_string(),
+            ..create_test_app().await
+        };
+        let view = |app: &App| -> (Arc<[String]>, usize, usize, Option<Arc<str>>) {
+            let view = app.view_stack[0]
+                .as_any()
+                .downcast_ref::<ReviewCreateView>()
+                .unwrap();
+            let GitBranchesLoadingState::Loaded(ref branches) = view.branches_state else {
+                panic!("Expected loaded branches, got: {:?}", view.branches_state);
+            };
+            (
+                Arc::clone(branches),
+                view.base_branch_index,
+                view.target_branch_index,
+                view.notice.clone(),
+            )
+        };
+
+        // A branch created in another terminal after opening the view
+        repo.branch("bugfix", &commit, true).unwrap();
+        app.handle_key_events(&KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE))
+            .unwrap();
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::GitBranchesLoad)));
+        EventProcessor::process_event(&mut app, event)
+            .await
+            .unwrap();
+        while let Some(event) = app.events.try_recv() {
+            EventProcessor::process_event(&mut app, event)
+                .await
+                .unwrap();
+            // The loaded branches stay shown while refreshing
+            view(&app);
+        }
+
+        let (branches, base_branch_index, target_branch_index, notice) = view(&app);
+        assert_eq!(
+            branches.as_ref(),
+            [
+                "bugfix".to_string(),
+                "feature".to_string(),
+                "main".to_string()
+            ]
+        );
+        // The selected branches stay selected
+        assert_eq!(branches[base_branch_index], "main");
+        assert_eq!(branches[target_branch_index], "main");
+        assert_eq!(notice.as_deref(), Some("Branches refreshed"));
+
+        // The notice is dismissed with the next key press
+        app.handle_key_events(&KeyEvent::new(KeyCode::Down, KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(view(&app).3, None);
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_since_last_tag() {
+        let mut app = create_test_app().await;
+        let mut view = create_preview_test_view();
+
+        // Without a tag the option is disabled
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('l')))
+            .unwrap();
+        assert!(!app.events.has_pending_events());
+        assert!(view.notice.is_some());
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::GitSinceLastTagLoaded(Some(Arc::new(SinceLastTag {
+                tag: "v1.0".to_string(),
+                head_branch: "feature".to_string(),
+            }))),
+        );
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('l')))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewCreateSubmit(data)) => {
+                assert_eq!(data.base_branch, "v1.0");
+                assert_eq!(data.target_branch, "feature");
+            }
+            _ => panic!("Expected ReviewCreateSubmit event, got: {event:?}"),
+        }
+    }
+
+    fn create_preview_test_view() -> ReviewCreateView {
+        ReviewCreateView {
+            branches_state: GitBranchesLoadingState::Loaded(
+                vec!["main".to_string(), "feature".to_string()].into(),
+            ),
+            base_branch_index: 0,
+            target_branch_index: 1,
+            current_field: InputField::BaseBranch,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: String::new(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
+        }
+    }
+
+    fn create_test_diff_preview(base_branch: &str, target_branch: &str) -> Arc<DiffPreview> {
+        Arc::new(DiffPreview {
+            base_branch: Arc::from(base_branch),
+            target_branch: Arc::from(target_branch),
+            file_count: 3,
+            added_lines: 12,
+            removed_lines: 4,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_preview_diff() {
+        let mut app = create_test_app().await;
+        let mut view = create_preview_test_view();
+
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::empty(),
+                kind: KeyEventKind::Press,
+                state: KeyEventState::empty(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(view.diff_preview, Some(DiffPreviewState::Loading));
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::DiffPreviewLoad {
+                base_branch,
+                target_branch,
+            }) => {
+                assert_eq!(base_branch.as_ref(), "main");
+                assert_eq!(target_branch.as_ref(), "feature");
+            }
+            _ => panic!("Expected DiffPreviewLoad event, got: {event:?}"),
+        }
+
+        let preview = create_test_diff_preview("main", "feature");
+        view.handle_app_events(&mut app, &AppEvent::DiffPreviewLoaded(Arc::clone(&preview)));
+        assert_eq!(view.diff_preview, Some(DiffPreviewState::Loaded(preview)));
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_preview_diff_ignores_stale_preview() {
+        let mut app = create_test_app().await;
+        let mut view = create_preview_test_view();
+        view.diff_preview = Some(DiffPreviewState::Loading);
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::DiffPreviewLoaded(create_test_diff_preview("feature", "main")),
+        );
+        assert_eq!(view.diff_preview, Some(DiffPreviewState::Loading));
+
+        // Changing the selection drops the preview
+        view.review_selection_up();
+        assert_eq!(view.diff_preview, None);
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::DiffPreviewLoaded(create_test_diff_preview("main", "feature")),
+        );
+        assert_eq!(view.diff_preview, None);
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_esc_cancels_preview() {
+        let mut app = create_test_app().await;
+        let mut view = create_preview_test_view();
+        view.diff_preview = Some(DiffPreviewState::Loaded(create_test_diff_preview(
+            "main", "feature",
+        )));
+
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent {
+                code: KeyCode::Esc,
+                modifiers: KeyModifiers::empty(),
+                kind: KeyEventKind::Press,
+                state: KeyEventState::empty(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(view.diff_preview, None);
+        assert_eq!(view.target_branch_index, 1);
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_render_diff_preview() {
+        let mut view = create_preview_test_view();
+        view.diff_preview = Some(DiffPreviewState::Loaded(create_test_diff_preview(
+            "main", "feature",
+        )));
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_render_diff_preview_no_changes() {
+        let mut view = create_preview_test_view();
+        view.diff_preview = Some(DiffPreviewState::Loaded(Arc::new(DiffPreview {
+            base_branch: Arc::from("main"),
+            target_branch: Arc::from("feature"),
+            file_count: 0,
+            added_lines: 0,
+            removed_lines: 0,
+        })));
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_render_no_commits() {
+        let view = ReviewCreateView {
+            branches_state: GitBranchesLoadingState::NoCommits,
+            ..Default::default()
+        };
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_no_commits_prevents_submit() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewCreateView::default();
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::GitBranchesLoadingState(GitBranchesLoadingState::NoCommits),
+        );
+
+        for code in [KeyCode::Enter, KeyCode::Char('p')] {
+            view.handle_key_events(&mut app, &KeyEvent::new(code, KeyModifiers::empty()))
+                .unwrap();
+        }
+
+        assert!(!app.events.has_pending_events());
+    }
+
     #[tokio::test]
     async fn test_review_create_view_render_default() {
         let view = ReviewCreateView::default();
@@ -668,6 +1504,23 @@ mod tests {
         assert_snapshot!(render_app_to_terminal_backend(app))
     }
 
+    #[tokio::test]
+    async fn test_review_create_view_render_quick_compare() {
+        let view = ReviewCreateView {
+            branches_state: GitBranchesLoadingState::Loaded(
+                vec!["main".to_string(), "feature/new-feature".to_string()].into(),
+            ),
+            target_branch_index: 1,
+            ..ReviewCreateView::for_quick_compare()
+        };
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
     #[tokio::test]
     async fn test_review_create_view_render_with_branches() {
         let view = ReviewCreateView {
@@ -682,6 +1535,38 @@ mod tests {
             base_branch_index: 0,
             target_branch_index: 2,
             current_field: InputField::BaseBranch,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: String::new(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
+        };
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_create_view_render_assignees() {
+        let view = ReviewCreateView {
+            branches_state: GitBranchesLoadingState::Loaded(
+                vec!["main".to_string(), "develop".to_string()].into(),
+            ),
+            base_branch_index: 0,
+            target_branch_index: 1,
+            current_field: InputField::Assignees,
+            edited_review: None,
+            quick_compare: false,
+            diff_preview: None,
+            assignees: "alice, bob".to_string(),
+            refreshing: false,
+            notice: None,
+            since_last_tag: None,
         };
         let app = App {
             view_stack: vec![Box::new(view)],
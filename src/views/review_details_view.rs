@@ -2,15 +2,18 @@
 use std::any::Any;
 
 use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
     collections::{HashMap, HashSet},
     fmt,
+    ops::Range,
     sync::Arc,
 };
 
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{KeyCode, KeyEvent},
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Widget},
@@ -19,9 +22,20 @@ use ratatui::{
 use crate::{
     app::App,
     event::AppEvent,
-    models::{Diff, DiffFile, Review},
-    services::{CommentsLoadParams, CommentsLoadingState, GitDiffLoadingState, ReviewLoadingState},
-    views::{KeyBinding, ViewHandler, ViewType},
+    models::{
+        CommentPriority, Density, Diff, DiffFile, DiffHunk, DiffLine, Review, ScrollMode,
+        SessionState, Settings, short_sha,
+    },
+    services::{
+        CommentsLoadParams, CommentsLoadingState, DiffContextDirection, DiffContextRequest,
+        GitDiffLoadingState, GitService, HunkBlameRequest, ReviewLoadingState,
+    },
+    theme::Theme,
+    views::{
+        CommentsView, KeyBinding, RenderMetrics, ReviewRefreshOptions, ReviewSummary, ViewHandler,
+        ViewType, centered_rectangle, comment_priority_style, toggle_full_shas,
+    },
+    word_diff,
 };
 
 const FILE_SELECTION_INDICATOR: &str = super::SELECTION_INDICATOR;
@@ -29,6 +43,17 @@ const FILE_COMMENT_INDICATOR: &str = "●";
 const LINE_COMMENT_INDICATOR: &str = "■";
 const FILE_AND_LINE_COMMENT_INDICATOR: &str = "#";
 const RESOLVED_COMMENT_INDICATOR: &str = "_";
+const DELETED_FILE_COMMENT_INDICATOR: &str = "✗";
+const OVERVIEW_VIEWED_INDICATOR: &str = "✓";
+const SKIPPED_FILE_INDICATOR: &str = "⊘";
+const HIDDEN_LINES_INDICATOR: &str = "⋯";
+const INLINE_CHANGE_CONNECTOR: &str = "└";
+const PATH_ELLIPSIS: &str = "...";
+
+/// Number of unchanged lines loaded per context expansion
+const CONTEXT_EXPAND_STEP: usize = 10;
+/// Number of most changed files listed in the churn summary
+const CHURN_SUMMARY_FILE_COUNT: usize = 5;
 
 #[derive(Debug, Clone)]
 pub enum NavigationMode {
@@ -42,6 +67,82 @@ pub enum FileListType {
     Viewed,
 }
 
+/// A file (and optionally a line) to jump to as soon as the diff is loaded
+#[derive(Debug, Clone, PartialEq)]
+struct FileJump {
+    file_path: Arc<str>,
+    line_number: Option<i64>,
+}
+
+/// Unchanged lines of a file expanded around a hunk
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ContextExpansion {
+    /// Lines shown before the hunk header
+    above: Vec<String>,
+    /// Lines shown after the last line of the hunk
+    below: Vec<String>,
+    /// Whether expanding below the hunk reached the end of the file
+    end_of_file: bool,
+}
+
+/// A row of the rendered diff content
+#[derive(Debug, Clone, PartialEq)]
+enum DiffRow<'a> {
+    /// Separator with the path of the file whose diff follows (all files mode)
+    FileHeader(&'a str),
+    /// Line of the diff content with its index
+    Line(usize, &'a str),
+    /// Expanded unchanged line of the file
+    Context(&'a str),
+    /// Marker for the number of unchanged lines hidden before a hunk
+    Hidden(usize),
+}
+
+/// A removed or added line paired with the line it is changed into or from, in the inline
+/// changes mode
+#[derive(Debug, Clone, PartialEq)]
+struct InlineChange {
+    /// Byte range of the changed part in the raw diff line
+    changed: Range<usize>,
+    /// Whether this is the added line, shown below the removed one with a connector
+    is_added: bool,
+}
+
+/// Part of a diff line as displayed
+#[derive(Debug, Clone, PartialEq)]
+struct DisplaySegment {
+    text: String,
+    /// Marker of a tab or trailing spaces
+    is_whitespace: bool,
+    /// Changed part of a line paired in the inline changes mode
+    is_changed: bool,
+}
+
+/// Everything the visible diff lines are built from. The cached lines of the last render are
+/// reused as long as the key stays the same.
+#[derive(Debug, Clone, PartialEq)]
+struct DiffRenderKey {
+    selected_file_path: String,
+    active_file_list: FileListType,
+    scroll_offset: usize,
+    selected_line_index: usize,
+    lines_mode: bool,
+    additions_only: bool,
+    all_files: bool,
+    area: Rect,
+    /// See `ReviewDetailsView::comments_version`
+    comments_version: u64,
+    /// See `ReviewDetailsView::content_version`
+    content_version: u64,
+    relative_line_numbers: bool,
+    hide_diff_prefixes: bool,
+    show_hunk_blame: bool,
+    show_whitespace: bool,
+    inline_changes: bool,
+    tab_width: usize,
+    density: Density,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 /// Represents the type of comments a file has
 pub enum CommentIndicator {
@@ -50,6 +151,8 @@ pub enum CommentIndicator {
     LineComment,
     FileAndLineComment,
     ResolvedComment,
+    /// Open comments on a file that was deleted
+    DeletedFileComment,
 }
 
 impl fmt::Display for CommentIndicator {
@@ -60,6 +163,7 @@ impl fmt::Display for CommentIndicator {
             CommentIndicator::LineComment => f.write_str(LINE_COMMENT_INDICATOR),
             CommentIndicator::FileAndLineComment => f.write_str(FILE_AND_LINE_COMMENT_INDICATOR),
             CommentIndicator::ResolvedComment => f.write_str(RESOLVED_COMMENT_INDICATOR),
+            CommentIndicator::DeletedFileComment => f.write_str(DELETED_FILE_COMMENT_INDICATOR),
         }
     }
 }
@@ -71,6 +175,8 @@ pub struct ReviewDetailsView {
     review: Option<Arc<Review>>,
     /// Current state of the git diff loading
     diff_state: GitDiffLoadingState,
+    /// Parsed and total files of the diff while it is loading
+    diff_progress: Option<(usize, usize)>,
     /// Current git diff if loaded
     diff: Arc<Diff>,
     /// Current scroll offset for the diff content
@@ -85,6 +191,10 @@ pub struct ReviewDetailsView {
     active_file_list: FileListType,
     /// List of viewed file paths for the current review
     viewed_files: Arc<Vec<String>>,
+    /// Files that are consciously not reviewed. They are listed with the viewed files.
+    skipped_files: Arc<Vec<String>>,
+    /// Diff content hashes of the viewed files at the time they were marked as viewed
+    viewed_file_content_hashes: Arc<HashMap<String, String>>,
     /// Files that have comments (file comments only, for comment indicators)
     files_with_file_comments: Arc<Vec<String>>,
     /// Files that have file and line comments (for comment indicators)
@@ -95,9 +205,83 @@ pub struct ReviewDetailsView {
     files_with_only_resolved_comments: Arc<Vec<String>>,
     /// Map of file paths to line numbers with only resolved comments
     lines_with_only_resolved_comments: Arc<HashMap<String, Vec<i64>>>,
+    /// File jump that is deferred until the diff is loaded
+    pending_file_jump: Option<FileJump>,
+    /// Whether the selected file was toggled from the file list, it is then not followed to
+    /// the other list once the file views are reloaded
+    toggled_from_file_list: bool,
+    /// Whether the one-line-per-file overview is shown instead of the file lists and diff
+    show_overview: bool,
+    /// Index of the selected file in the overview (in diff order)
+    overview_selected_index: usize,
+    /// Whether the panel with the total churn and the most changed files is shown over the diff
+    show_churn_summary: bool,
+    /// Message shown at the bottom of the view until the next key press
+    notice: Option<Arc<str>>,
+    /// Context expanded around hunks, keyed by file path and hunk header line index
+    context_expansions: HashMap<(String, usize), ContextExpansion>,
+    /// Number of comments per file path, to flag comments on files that are not in the diff
+    comment_counts_by_file: Arc<HashMap<String, usize>>,
+    /// Highest priority of the unresolved comments per file path, to color the comment
+    /// indicators
+    comment_priorities_by_file: Arc<HashMap<String, CommentPriority>>,
+    /// How the diff scrolls when the selected line moves
+    scroll_mode: ScrollMode,
+    /// Whether only the added lines (and hunk headers) of the diff are shown
+    additions_only: bool,
+    /// Whether the review is a quick review that is not saved, which disables everything
+    /// that is stored for a review (file views, comments, activity)
+    quick_review: bool,
+    /// Pathspec the diff is limited to
+    pathspec: Option<Arc<str>>,
+    /// Blame summaries of the hunks keyed by file path and hunk header line index, loaded for
+    /// the selected file while the hunk blame is shown. An empty map marks a pending load.
+    hunk_blames: HashMap<String, Arc<HashMap<usize, Arc<str>>>>,
+    /// Whether the diffs of all files of the active file list are shown in one scrollable
+    /// content, separated by file headers. The selected file follows the selected line and the
+    /// scroll offset counts the lines of all files.
+    all_files: bool,
+    /// Whether the files of the target SHA that are not in the diff are listed after the
+    /// changed files, so that file comments can be added to them
+    show_unchanged_files: bool,
+    /// Whether the files left out of the diff by the exclude patterns are listed after the
+    /// changed files
+    show_excluded_files: bool,
+    /// SHA and paths of all files of its tree, listed when the unchanged files are shown
+    tree_files: Option<(Arc<str>, Arc<[String]>)>,
+    /// Files of `tree_files` that are not in the diff, as entries without diff lines
+    unchanged_files: Arc<[DiffFile]>,
+    /// Number of comments added to the review since the view was opened, for the summary
+    /// shown on close
+    comments_added: usize,
+    /// Number of unresolved comments of the review from the last comments load
+    unresolved_comment_count: usize,
+    /// Number of all comments of the review from the last comments load
+    comment_count: usize,
+    /// Size of the diff content from the last render, for scrolling while handling keys
+    render_metrics: Cell<RenderMetrics>,
+    /// Incremented when the comment indicators of the diff lines change
+    comments_version: u64,
+    /// Incremented when the diff, the viewed or unchanged files, the expanded context or the
+    /// hunk blames change
+    content_version: u64,
+    /// Styled visible diff lines of the last render, so that they are only rebuilt when one of
+    /// their inputs changed
+    diff_lines_cache: RefCell<Option<(DiffRenderKey, Arc<[Line<'static>]>)>>,
+    /// Comments shown beside the diff instead of over it, they get the key events while open
+    docked_comments: Option<CommentsView>,
 }
 
-const CONTENT_HEIGHT: usize = 15; // Default content height for scrolling
+/// Content height for scrolling before the diff content is rendered for the first time
+const DEFAULT_CONTENT_HEIGHT: usize = 15;
+
+/// Content of the unchanged files, which have no diff lines
+const UNCHANGED_FILE_CONTENT: &str = "No changes in this file, only file comments can be added";
+
+const QUICK_REVIEW_NOTICE: &str = "Not available for a quick review, it is not saved";
+const PATCH_REVIEW_NOTICE: &str = "Not available for a review of a patch file, it has no branches";
+
+const REVIEW_COMPLETE_NOTICE: &str = "Review complete, all files are viewed";
 
 impl ReviewDetailsView {
     pub fn new(review: Review) -> Self {
@@ -106,6 +290,7 @@ impl ReviewDetailsView {
             review_state: ReviewLoadingState::Loaded(review_arc.clone()),
             review: Some(review_arc.clone()),
             diff_state: GitDiffLoadingState::Init,
+            diff_progress: None,
             diff: Arc::new(Diff::default()),
             scroll_offset: 0,
             selected_file_index: 0,
@@ -113,11 +298,40 @@ impl ReviewDetailsView {
             navigation_mode: NavigationMode::Files,
             active_file_list: FileListType::NotViewed,
             viewed_files: Arc::new(vec![]),
+            skipped_files: Arc::new(vec![]),
+            viewed_file_content_hashes: Arc::new(HashMap::new()),
             files_with_file_comments: Arc::new(vec![]),
             files_with_file_and_or_line_comments: Arc::new(vec![]),
             lines_with_comments: Arc::new(HashMap::new()),
             files_with_only_resolved_comments: Arc::new(vec![]),
             lines_with_only_resolved_comments: Arc::new(HashMap::new()),
+            pending_file_jump: None,
+            toggled_from_file_list: false,
+            show_overview: false,
+            overview_selected_index: 0,
+            show_churn_summary: false,
+            notice: None,
+            context_expansions: HashMap::new(),
+            comment_counts_by_file: Arc::new(HashMap::new()),
+            comment_priorities_by_file: Arc::new(HashMap::new()),
+            scroll_mode: ScrollMode::default(),
+            additions_only: false,
+            quick_review: false,
+            pathspec: None,
+            hunk_blames: HashMap::new(),
+            all_files: false,
+            show_unchanged_files: false,
+            show_excluded_files: false,
+            tree_files: None,
+            unchanged_files: Arc::new([]),
+            comments_added: 0,
+            unresolved_comment_count: 0,
+            comment_count: 0,
+            render_metrics: Cell::default(),
+            comments_version: 0,
+            content_version: 0,
+            diff_lines_cache: RefCell::default(),
+            docked_comments: None,
         }
     }
 
@@ -126,6 +340,7 @@ impl ReviewDetailsView {
             review_state: ReviewLoadingState::Loading,
             review: None,
             diff_state: GitDiffLoadingState::Init,
+            diff_progress: None,
             diff: Arc::new(Diff::default()),
             scroll_offset: 0,
             selected_file_index: 0,
@@ -133,11 +348,64 @@ impl ReviewDetailsView {
             navigation_mode: NavigationMode::Files,
             active_file_list: FileListType::NotViewed,
             viewed_files: Arc::new(vec![]),
+            skipped_files: Arc::new(vec![]),
+            viewed_file_content_hashes: Arc::new(HashMap::new()),
             files_with_file_comments: Arc::new(vec![]),
             files_with_file_and_or_line_comments: Arc::new(vec![]),
             lines_with_comments: Arc::new(HashMap::new()),
             files_with_only_resolved_comments: Arc::new(vec![]),
             lines_with_only_resolved_comments: Arc::new(HashMap::new()),
+            pending_file_jump: None,
+            toggled_from_file_list: false,
+            show_overview: false,
+            overview_selected_index: 0,
+            show_churn_summary: false,
+            notice: None,
+            context_expansions: HashMap::new(),
+            comment_counts_by_file: Arc::new(HashMap::new()),
+            comment_priorities_by_file: Arc::new(HashMap::new()),
+            scroll_mode: ScrollMode::default(),
+            additions_only: false,
+            quick_review: false,
+            pathspec: None,
+            hunk_blames: HashMap::new(),
+            all_files: false,
+            show_unchanged_files: false,
+            show_excluded_files: false,
+            tree_files: None,
+            unchanged_files: Arc::new([]),
+            comments_added: 0,
+            unresolved_comment_count: 0,
+            comment_count: 0,
+            render_metrics: Cell::default(),
+            comments_version: 0,
+            content_version: 0,
+            diff_lines_cache: RefCell::default(),
+            docked_comments: None,
+        }
+    }
+
+    /// Use the scroll mode from the settings
+    pub fn with_scroll_mode(mut self, scroll_mode: ScrollMode) -> Self {
+        self.scroll_mode = scroll_mode;
+        self
+    }
+
+    /// Show a quick review that is not saved, limited to the files matching `pathspec`
+    pub fn with_quick_review(mut self, pathspec: Option<Arc<str>>) -> Self {
+        self.quick_review = true;
+        self.pathspec = pathspec;
+        self
+    }
+
+    /// Create a loading view that jumps to the given file (and line) once the diff is loaded
+    pub fn new_loading_with_file_jump(file_path: Arc<str>, line_number: Option<i64>) -> Self {
+        Self {
+            pending_file_jump: Some(FileJump {
+                file_path,
+                line_number,
+            }),
+            ..Self::new_loading()
         }
     }
 }
@@ -147,15 +415,20 @@ impl ViewHandler for ReviewDetailsView {
         ViewType::ReviewDetails
     }
 
-    fn render(&self, _app: &App, area: Rect, buf: &mut Buffer) {
+    fn render(&self, app: &App, area: Rect, buf: &mut Buffer) {
         // Clear the background to make this a proper full-screen modal
         Clear.render(area, buf);
 
-        let block = Block::default()
+        let mut block = Block::default()
             .title(" Review Details ")
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Blue))
             .style(Style::default().bg(Color::Black));
+        if let Some(notice) = &self.notice {
+            block = block.title_bottom(
+                Line::from(format!(" {notice} ")).style(Style::default().fg(Color::Yellow)),
+            );
+        }
 
         let inner_area = block.inner(area);
         block.render(area, buf);
@@ -167,55 +440,204 @@ impl ViewHandler for ReviewDetailsView {
             ReviewLoadingState::NotFound(review_id) => {
                 self.render_not_found(review_id, inner_area, buf)
             }
-            ReviewLoadingState::Loaded(_review) => self.render_loaded(inner_area, buf),
+            ReviewLoadingState::Loaded(_review) => self.render_loaded(app, inner_area, buf),
         }
     }
 
     fn handle_key_events(&mut self, app: &mut App, key_event: &KeyEvent) -> color_eyre::Result<()> {
+        self.notice = None;
+
+        if let Some(docked_comments) = &mut self.docked_comments {
+            return docked_comments.handle_key_events(app, key_event);
+        }
+        if self.show_overview {
+            return self.handle_overview_key_events(app, key_event);
+        }
+        if self.show_churn_summary {
+            match key_event.code {
+                KeyCode::Char('s') | KeyCode::Esc => self.show_churn_summary = false,
+                KeyCode::Char('?') => self.help(app),
+                _ => {}
+            }
+            return Ok(());
+        }
+
         match key_event.code {
+            KeyCode::Char(' ' | 'X' | 'c' | 'R' | 'r' | 'a' | 'C') if self.quick_review => {
+                self.notice = Some(Arc::from(QUICK_REVIEW_NOTICE));
+            }
+            KeyCode::Char('y' | 'D' | 'r' | 'd' | 'e' | 'E' | 'b') if self.is_patch_review() => {
+                self.notice = Some(Arc::from(PATCH_REVIEW_NOTICE));
+            }
             KeyCode::Up | KeyCode::Char('k') => self.go_up(),
             KeyCode::Down | KeyCode::Char('j') => self.go_down(),
             KeyCode::Left | KeyCode::Char('h') => self.switch_file_list_left(),
             KeyCode::Right | KeyCode::Char('l') => self.switch_file_list_right(),
             KeyCode::Enter => self.toggle_navigation_mode(),
+            KeyCode::Tab => self.cycle_focus(true),
+            KeyCode::BackTab => self.cycle_focus(false),
             KeyCode::Char(' ') => self.toggle_file_view_status(app),
+            KeyCode::Char('X') => self.toggle_file_skip_status(app),
             KeyCode::Char('c') => self.open_comments(app),
+            KeyCode::Char('R') => self.toggle_line_comments_resolved(app),
             KeyCode::Char('r') => self.open_refresh_chooser(app),
+            KeyCode::Char('v') => self.toggle_collapse_viewed_list(app),
+            KeyCode::Char('n') => self.toggle_relative_line_numbers(app),
+            KeyCode::Char('z') => self.toggle_scroll_mode(app),
+            KeyCode::Char('p') => self.toggle_diff_prefixes(app),
+            KeyCode::Char('+') => self.toggle_additions_only(),
+            KeyCode::Char('b') => self.toggle_hunk_blame(app),
+            KeyCode::Char('w') => self.toggle_whitespace(app),
+            KeyCode::Char('i') => self.toggle_inline_changes(app),
+            KeyCode::Char('S') => toggle_full_shas(app),
+            KeyCode::Char('u') => self.toggle_all_files(),
+            KeyCode::Char('t') => self.toggle_unchanged_files(app),
+            KeyCode::Char('x') => self.toggle_excluded_files(),
+            KeyCode::Char('d') => self.open_difftool(app),
+            KeyCode::Char('D') => self.copy_git_diff_command(app),
+            KeyCode::Char('y') => self.copy_compare_url(app),
+            KeyCode::Char('Y') => self.copy_selected_hunk(app),
+            KeyCode::Char('e') => self.expand_context(app, DiffContextDirection::Above),
+            KeyCode::Char('E') => self.expand_context(app, DiffContextDirection::Below),
+            KeyCode::Char('o') => self.open_overview(),
+            KeyCode::Char('s') => self.show_churn_summary = true,
+            KeyCode::Char('f') => self.jump_to_first_not_viewed_file(),
+            KeyCode::Char('}') => self.jump_by_churn(true),
+            KeyCode::Char('{') => self.jump_by_churn(false),
+            KeyCode::Char('a') => self.open_activity(app),
+            KeyCode::Char('C') => self.open_checklist(app),
             KeyCode::Esc => self.handle_esc(app),
             KeyCode::Char('?') => self.help(app),
             _ => {}
         }
+        self.load_hunk_blame(app);
         Ok(())
     }
 
     fn handle_app_events(&mut self, app: &mut App, event: &AppEvent) {
+        if let Some(docked_comments) = &mut self.docked_comments {
+            docked_comments.handle_app_events(app, event);
+        }
         match event {
+            AppEvent::CommentsDockedClose => self.docked_comments = None,
             AppEvent::ReviewLoadingState(review_loading_state) => {
                 self.handle_review_loading_state(app, review_loading_state);
             }
-            AppEvent::GitDiffLoadingState(diff_loading_state) => {
-                self.handle_git_diff_loading_state(diff_loading_state);
+            #[cfg(feature = "watch-refs")]
+            AppEvent::ActiveReviewRefsChanged => self.handle_refs_changed(app),
+            AppEvent::GitDiffLoadingState {
+                base_sha,
+                target_sha,
+                state,
+            } => {
+                if !self.relevant_git_diff_loading_state(base_sha, target_sha) {
+                    // Ignore diffs of a previous review or of SHAs that were refreshed since
+                    return;
+                }
+                self.handle_git_diff_loading_state(state);
+                self.load_tree_files(app);
+                self.reset_changed_viewed_files(app);
+                self.load_hunk_blame(app);
+            }
+            AppEvent::PatchDiffLoadingState { patch_path, state }
+                if self
+                    .review
+                    .as_ref()
+                    .is_some_and(|review| review.patch_path.as_deref() == Some(patch_path)) =>
+            {
+                self.handle_git_diff_loading_state(state);
+                self.reset_changed_viewed_files(app);
+            }
+            AppEvent::ReviewDiffJump {
+                review_id,
+                file_path,
+                line_number,
+            } if self
+                .review
+                .as_ref()
+                .is_some_and(|review| review.id == review_id.as_ref()) =>
+            {
+                self.jump_to_diff_line(file_path, *line_number);
+            }
+            AppEvent::HunkBlameLoaded { request, summaries } => {
+                self.handle_hunk_blame_loaded(request, summaries);
+            }
+            AppEvent::HunkBlameLoadError(error) | AppEvent::TreeFilesLoadError(error) => {
+                self.notice = Some(Arc::clone(error));
+            }
+            AppEvent::TreeFilesLoaded { sha, paths } => {
+                self.handle_tree_files_loaded(sha, paths);
+            }
+            AppEvent::GitDiffProgress {
+                base_sha,
+                target_sha,
+                files_done,
+                files_total,
+            } if self.relevant_git_diff_loading_state(base_sha, target_sha) => {
+                self.diff_progress = Some((*files_done, *files_total));
             }
             AppEvent::FileViewsLoaded {
                 review_id,
                 viewed_files,
+                skipped_files,
+                content_hashes,
             } => {
-                self.handle_file_views_loaded(review_id, viewed_files);
+                self.handle_file_views_loaded(
+                    review_id,
+                    viewed_files,
+                    skipped_files,
+                    content_hashes,
+                );
+                self.reset_changed_viewed_files(app);
             }
             AppEvent::FileViewToggled {
                 review_id: _,
                 file_path: _,
-                is_viewed: _,
+                status: _,
             } => {
                 // File view status changed, file views will be reloaded automatically
             }
             AppEvent::CommentsLoadingState { params, state } => {
                 self.handle_comments_loading_state(params, state);
             }
-            AppEvent::CommentCreated(_) => {
+            AppEvent::CommentCreated(comment) => {
+                if self
+                    .review
+                    .as_ref()
+                    .is_some_and(|review| review.id == comment.review_id)
+                {
+                    self.comments_added += 1;
+                }
                 // Reload comment metadata when a comment is created
                 self.reload_comments(app);
             }
+            AppEvent::CommentsToggledAllResolved { review_id, .. }
+                if self
+                    .review
+                    .as_ref()
+                    .is_some_and(|review| review.id == review_id.as_ref()) =>
+            {
+                self.reload_comments(app);
+            }
+            AppEvent::CommentsToggleAllResolvedError { error, .. } => {
+                self.notice = Some(Arc::clone(error));
+            }
+            AppEvent::DiffContextExpanded { request, lines } => {
+                self.handle_diff_context_expanded(request, lines);
+            }
+            AppEvent::DifftoolError(error)
+            | AppEvent::CompareUrlError(error)
+            | AppEvent::DiffContextExpandError(error) => {
+                self.notice = Some(Arc::clone(error));
+            }
+            AppEvent::ClipboardCopy(text) => {
+                let line_count = text.lines().count();
+                self.notice = Some(Arc::from(if line_count > 1 {
+                    format!("Copied {line_count} lines")
+                } else {
+                    format!("Copied {}", self.display_shas(text, app.settings.full_shas))
+                }));
+            }
             _ => {
                 // Other events are not handled by this view
             }
@@ -266,7 +688,7 @@ impl ViewHandler for ReviewDetailsView {
             },
             KeyBinding {
                 key: "Space".to_string(),
-                description: "Toggle file view status".to_string(),
+                description: "Toggle file view status (also from its lines)".to_string(),
                 key_event: KeyEvent {
                     code: KeyCode::Char(' '),
                     modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
@@ -274,6 +696,16 @@ impl ViewHandler for ReviewDetailsView {
                     state: ratatui::crossterm::event::KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "X".to_string(),
+                description: "Toggle skipping file without reviewing it".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('X'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
             KeyBinding {
                 key: "Enter".to_string(),
                 description: "Toggle navigation mode".to_string(),
@@ -284,6 +716,16 @@ impl ViewHandler for ReviewDetailsView {
                     state: ratatui::crossterm::event::KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "Tab / Shift+Tab".to_string(),
+                description: "Cycle not viewed, viewed files and content".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Tab,
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
             KeyBinding {
                 key: "Esc".to_string(),
                 description: "Go back / Switch to Files mode".to_string(),
@@ -304,6 +746,16 @@ impl ViewHandler for ReviewDetailsView {
                     state: ratatui::crossterm::event::KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "R".to_string(),
+                description: "Toggle resolved comments of the line".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('R'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
             KeyBinding {
                 key: "r".to_string(),
                 description: "Refresh review SHAs".to_string(),
@@ -314,6 +766,236 @@ impl ViewHandler for ReviewDetailsView {
                     state: ratatui::crossterm::event::KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "v".to_string(),
+                description: "Toggle collapsing viewed files".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('v'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "n".to_string(),
+                description: "Toggle relative line numbers".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('n'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "z".to_string(),
+                description: "Toggle centering the selected line".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('z'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "p".to_string(),
+                description: "Toggle diff +/- prefixes".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('p'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "+".to_string(),
+                description: "Toggle showing only added lines".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('+'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "b".to_string(),
+                description: "Toggle hunk blame".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('b'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "w".to_string(),
+                description: "Toggle showing whitespace".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('w'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "i".to_string(),
+                description: "Toggle showing changed lines inline, old above new".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('i'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "S".to_string(),
+                description: "Toggle short / full SHAs".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('S'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "u".to_string(),
+                description: "Toggle showing all files in one diff".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('u'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "t".to_string(),
+                description: "Toggle listing unchanged files".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('t'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "x".to_string(),
+                description: "Toggle listing excluded files".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('x'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "o".to_string(),
+                description: "Toggle files overview".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('o'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "f".to_string(),
+                description: "Go to the first not viewed file".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('f'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "} / {".to_string(),
+                description: "Next / previous most changed file".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('}'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "s".to_string(),
+                description: "Toggle churn summary".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('s'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "d".to_string(),
+                description: "Open file in difftool".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('d'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "D".to_string(),
+                description: "Copy git diff command".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('D'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "y".to_string(),
+                description: "Copy compare URL".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('y'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "Y".to_string(),
+                description: "Copy hunk of selected line".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('Y'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "e / E".to_string(),
+                description: "Expand context above / below hunk".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('e'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "a".to_string(),
+                description: "Show activity log".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('a'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
+            KeyBinding {
+                key: "C".to_string(),
+                description: "Show review checklist".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('C'),
+                    modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                    kind: ratatui::crossterm::event::KeyEventKind::Press,
+                    state: ratatui::crossterm::event::KeyEventState::empty(),
+                },
+            },
             KeyBinding {
                 key: "?".to_string(),
                 description: "Help".to_string(),
@@ -327,6 +1009,29 @@ impl ViewHandler for ReviewDetailsView {
         ])
     }
 
+    /// Comments are docked while the file lists and diff are shown
+    fn dock_comments(&mut self, comments_view: CommentsView) -> Option<CommentsView> {
+        if !matches!(self.diff_state, GitDiffLoadingState::Loaded(_)) || self.show_overview {
+            return Some(comments_view);
+        }
+        self.docked_comments = Some(comments_view.with_docked(true));
+        None
+    }
+
+    fn session_state(&self) -> Option<SessionState> {
+        if self.quick_review {
+            // A quick review is not saved, so it can't be resumed
+            return None;
+        }
+        let review = self.review.as_ref()?;
+        Some(SessionState {
+            review_id: review.id.clone(),
+            file_path: self.get_selected_file().map(|file| file.path.clone()),
+            line_number: matches!(self.navigation_mode, NavigationMode::Lines)
+                .then_some(self.selected_line_index as i64),
+        })
+    }
+
     #[cfg(test)]
     fn debug_state(&self) -> String {
         format!(
@@ -362,7 +1067,14 @@ impl ReviewDetailsView {
         app.events.send(AppEvent::HelpOpen(self.get_keybindings()));
     }
 
-    fn open_refresh_chooser(&self, app: &mut App) {
+    /// Reviews of patch files have neither branches nor SHAs for the git based actions
+    fn is_patch_review(&self) -> bool {
+        self.review
+            .as_ref()
+            .is_some_and(|review| review.patch_path.is_some())
+    }
+
+    fn open_refresh_chooser(&self, app: &mut App) {
         let Some(review) = &self.review else {
             return;
         };
@@ -378,6 +1090,73 @@ impl ReviewDetailsView {
         });
     }
 
+    /// Open the activity log of the review
+    fn open_activity(&self, app: &mut App) {
+        let Some(review) = &self.review else {
+            return;
+        };
+
+        app.events.send(AppEvent::ActivityOpen {
+            review_id: Arc::from(review.id.as_str()),
+        });
+    }
+
+    /// Open the checklist of the review
+    fn open_checklist(&self, app: &mut App) {
+        let Some(review) = &self.review else {
+            return;
+        };
+
+        app.events.send(AppEvent::ChecklistOpen {
+            review_id: Arc::from(review.id.as_str()),
+        });
+    }
+
+    /// Handle key events while the files overview is shown
+    fn handle_overview_key_events(
+        &mut self,
+        app: &mut App,
+        key_event: &KeyEvent,
+    ) -> color_eyre::Result<()> {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.overview_selected_index = self.overview_selected_index.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j')
+                if self.overview_selected_index < self.diff.file_count().saturating_sub(1) =>
+            {
+                self.overview_selected_index += 1;
+            }
+            KeyCode::Enter => self.open_overview_selection(),
+            KeyCode::Char('o') | KeyCode::Esc => self.show_overview = false,
+            KeyCode::Char('?') => self.help(app),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Show the files overview with the currently selected file preselected
+    fn open_overview(&mut self) {
+        let selected_path = self.get_selected_file().map(|file| file.path.clone());
+        self.overview_selected_index = selected_path
+            .and_then(|path| self.diff.files.iter().position(|file| file.path == path))
+            .unwrap_or(0);
+        self.show_overview = true;
+    }
+
+    /// Close the files overview and jump into the diff of the selected file
+    fn open_overview_selection(&mut self) {
+        if let Some(file) = self.diff.files.get(self.overview_selected_index) {
+            let file_jump = FileJump {
+                file_path: file.path.as_str().into(),
+                line_number: None,
+            };
+            self.navigation_mode = NavigationMode::Files;
+            self.jump_to_file(&file_jump);
+        }
+        self.show_overview = false;
+    }
+
     /// Navigate to the previous line in the respective navigation mode
     fn go_up(&mut self) {
         match self.navigation_mode {
@@ -385,13 +1164,19 @@ impl ReviewDetailsView {
                 if self.selected_file_index > 0 {
                     self.selected_file_index -= 1;
                     self.selected_line_index = 0;
-                    self.scroll_offset = 0;
+                    self.scroll_to_selected_file();
                 }
             }
             NavigationMode::Lines => {
-                if self.selected_line_index > 0 {
-                    self.selected_line_index -= 1;
-                    self.update_scroll_to_follow_selected_line(CONTENT_HEIGHT);
+                if let Some(line_index) = self.previous_visible_line_index() {
+                    self.selected_line_index = line_index;
+                    self.update_scroll_to_follow_selected_line();
+                } else if let Some(file_index) = self.adjacent_navigable_file_index(false) {
+                    // Continue on the last line of the previous file in all files mode
+                    self.selected_file_index = file_index;
+                    self.selected_line_index = self.get_current_file_lines().saturating_sub(1);
+                    self.select_visible_line();
+                    self.update_scroll_to_follow_selected_line();
                 }
             }
         }
@@ -405,14 +1190,19 @@ impl ReviewDetailsView {
                 if self.selected_file_index < current_files.len().saturating_sub(1) {
                     self.selected_file_index += 1;
                     self.selected_line_index = 0;
-                    self.scroll_offset = 0;
+                    self.scroll_to_selected_file();
                 }
             }
             NavigationMode::Lines => {
-                let current_file_lines = self.get_current_file_lines();
-                if self.selected_line_index < current_file_lines.saturating_sub(1) {
-                    self.selected_line_index += 1;
-                    self.update_scroll_to_follow_selected_line(CONTENT_HEIGHT);
+                if let Some(line_index) = self.next_visible_line_index() {
+                    self.selected_line_index = line_index;
+                    self.update_scroll_to_follow_selected_line();
+                } else if let Some(file_index) = self.adjacent_navigable_file_index(true) {
+                    // Continue on the first line of the next file in all files mode
+                    self.selected_file_index = file_index;
+                    self.selected_line_index = 0;
+                    self.select_visible_line();
+                    self.update_scroll_to_follow_selected_line();
                 }
             }
         }
@@ -422,10 +1212,12 @@ impl ReviewDetailsView {
     fn toggle_navigation_mode(&mut self) {
         match self.navigation_mode {
             NavigationMode::Files => {
-                let current_files = self.get_current_file_list();
-                if !current_files.is_empty() {
+                // Metadata-only changes (mode, symlink target) and empty files have no lines to
+                // navigate
+                if self.get_selected_file().is_some_and(DiffFile::has_lines) {
                     self.navigation_mode = NavigationMode::Lines;
                     self.selected_line_index = 0;
+                    self.select_visible_line();
                 }
             }
             NavigationMode::Lines => {
@@ -434,6 +1226,249 @@ impl ReviewDetailsView {
         }
     }
 
+    /// Move the focus forward (or backward) through the not viewed files, the viewed files and
+    /// the diff content of the selected file. The content is skipped when the file has no lines.
+    fn cycle_focus(&mut self, forward: bool) {
+        let focus = match (&self.navigation_mode, &self.active_file_list) {
+            (NavigationMode::Files, FileListType::NotViewed) => 0,
+            (NavigationMode::Files, FileListType::Viewed) => 1,
+            (NavigationMode::Lines, _) => 2,
+        };
+        let step = if forward { 1 } else { 2 };
+        let mut next = (focus + step) % 3;
+        if next == 2 && !self.get_selected_file().is_some_and(DiffFile::has_lines) {
+            next = (next + step) % 3;
+        }
+        match next {
+            0 => {
+                self.navigation_mode = NavigationMode::Files;
+                self.switch_file_list_left();
+            }
+            1 => {
+                self.navigation_mode = NavigationMode::Files;
+                self.switch_file_list_right();
+            }
+            _ => self.toggle_navigation_mode(),
+        }
+    }
+
+    /// Toggle showing only the added lines and hunk headers of the diff
+    fn toggle_additions_only(&mut self) {
+        self.additions_only = !self.additions_only;
+        if matches!(self.navigation_mode, NavigationMode::Lines) {
+            self.select_visible_line();
+            self.update_scroll_to_follow_selected_line();
+        }
+    }
+
+    /// Toggle showing the diffs of all files of the active file list in one scrollable content
+    fn toggle_all_files(&mut self) {
+        self.all_files = !self.all_files;
+        match self.navigation_mode {
+            NavigationMode::Files => self.scroll_to_selected_file(),
+            NavigationMode::Lines => self.update_scroll_to_follow_selected_line(),
+        }
+    }
+
+    /// Toggle listing the files of the target SHA that are not in the diff. Their paths are
+    /// listed from the tree the first time, which can take a while on large repositories.
+    fn toggle_unchanged_files(&mut self, app: &mut App) {
+        self.show_unchanged_files = !self.show_unchanged_files;
+        self.content_version += 1;
+        if self.show_unchanged_files {
+            self.load_tree_files(app);
+        } else {
+            let file_count = self.get_current_file_list().len();
+            self.selected_file_index = self.selected_file_index.min(file_count.saturating_sub(1));
+        }
+    }
+
+    /// Toggle listing the files that match the exclude patterns of the repository
+    fn toggle_excluded_files(&mut self) {
+        self.show_excluded_files = !self.show_excluded_files;
+        self.content_version += 1;
+        if !self.show_excluded_files {
+            let file_count = self.get_current_file_list().len();
+            self.selected_file_index = self.selected_file_index.min(file_count.saturating_sub(1));
+        }
+    }
+
+    /// Request the files of the target SHA while the unchanged files are shown, unless they
+    /// are already listed for it
+    fn load_tree_files(&self, app: &mut App) {
+        let Some(target_sha) = self
+            .review
+            .as_ref()
+            .and_then(|review| review.target_sha.as_deref())
+        else {
+            return;
+        };
+        let is_loaded = self
+            .tree_files
+            .as_ref()
+            .is_some_and(|(sha, _)| sha.as_ref() == target_sha);
+        if self.show_unchanged_files && !is_loaded {
+            app.events
+                .send(AppEvent::TreeFilesLoad(Arc::from(target_sha)));
+        }
+    }
+
+    /// Keep the files of the target SHA to list the ones that are not in the diff
+    fn handle_tree_files_loaded(&mut self, sha: &Arc<str>, paths: &Arc<[String]>) {
+        let is_target_sha = self
+            .review
+            .as_ref()
+            .is_some_and(|review| review.target_sha.as_deref() == Some(sha.as_ref()));
+        if !is_target_sha {
+            return;
+        }
+
+        self.tree_files = Some((Arc::clone(sha), Arc::clone(paths)));
+        self.update_unchanged_files();
+    }
+
+    /// Derive the unchanged files from the files of the target SHA and the files of the diff
+    fn update_unchanged_files(&mut self) {
+        let Some((_, tree_files)) = &self.tree_files else {
+            return;
+        };
+        self.unchanged_files = tree_files
+            .iter()
+            .filter(|path| {
+                !self
+                    .diff
+                    .files
+                    .iter()
+                    .chain(self.diff.excluded_files.iter())
+                    .any(|file| &file.path == *path)
+            })
+            .map(|path| DiffFile::metadata(path.clone(), UNCHANGED_FILE_CONTENT))
+            .collect();
+        self.content_version += 1;
+    }
+
+    /// Whether the file is one of the unchanged files, which are not part of the diff
+    fn is_unchanged_file(&self, diff_file: &DiffFile) -> bool {
+        self.show_unchanged_files
+            && !self
+                .diff
+                .files
+                .iter()
+                .chain(self.diff.excluded_files.iter())
+                .any(|file| file.path == diff_file.path)
+    }
+
+    /// Index of the closest file before or after the selected one whose lines can be navigated,
+    /// only in all files mode where the line navigation continues across files
+    fn adjacent_navigable_file_index(&self, forward: bool) -> Option<usize> {
+        if !self.all_files {
+            return None;
+        }
+        let files = self.get_current_file_list();
+        let is_navigable = |index: &usize| files[*index].has_lines();
+        if forward {
+            (self.selected_file_index + 1..files.len()).find(is_navigable)
+        } else {
+            (0..self.selected_file_index.min(files.len())).rfind(is_navigable)
+        }
+    }
+
+    /// Scroll line of the first diff line of the file at `file_index` in the active file list.
+    /// In all files mode the lines of the previous files and a header line per file come
+    /// before it, otherwise only the selected file is shown and it starts at 0.
+    fn file_line_offset(&self, file_index: usize) -> usize {
+        if !self.all_files {
+            return 0;
+        }
+        self.get_current_file_list()
+            .iter()
+            .take(file_index)
+            .map(|file| file.content().lines().count() + 1)
+            .sum::<usize>()
+            + 1
+    }
+
+    /// Scroll line of the selected line
+    fn selected_scroll_line(&self) -> usize {
+        self.file_line_offset(self.selected_file_index) + self.selected_line_index
+    }
+
+    /// Number of scroll lines of the shown diff content
+    fn scroll_line_count(&self) -> usize {
+        if !self.all_files {
+            return self.get_current_file_lines();
+        }
+        self.get_current_file_list()
+            .iter()
+            .map(|file| file.content().lines().count() + 1)
+            .sum()
+    }
+
+    /// Scroll to the start of the selected file (its header in all files mode)
+    fn scroll_to_selected_file(&mut self) {
+        self.scroll_offset = self
+            .file_line_offset(self.selected_file_index)
+            .saturating_sub(1);
+    }
+
+    /// Whether a line of the diff content of the file is shown with the current filter
+    fn is_line_visible(&self, file: &DiffFile, line: &str) -> bool {
+        !self.additions_only
+            || file.metadata_only()
+            || line.starts_with('+')
+            || line.starts_with("@@")
+    }
+
+    /// Index of the closest shown line before the selected line
+    fn previous_visible_line_index(&self) -> Option<usize> {
+        if !self.additions_only {
+            return self.selected_line_index.checked_sub(1);
+        }
+        let file = self.get_selected_file()?;
+        file.content()
+            .lines()
+            .enumerate()
+            .take(self.selected_line_index)
+            .filter(|(_, line)| self.is_line_visible(file, line))
+            .map(|(line_index, _)| line_index)
+            .last()
+    }
+
+    /// Index of the closest shown line after the selected line
+    fn next_visible_line_index(&self) -> Option<usize> {
+        if !self.additions_only {
+            let next = self.selected_line_index + 1;
+            return (next < self.get_current_file_lines()).then_some(next);
+        }
+        let file = self.get_selected_file()?;
+        file.content()
+            .lines()
+            .enumerate()
+            .skip(self.selected_line_index + 1)
+            .find(|(_, line)| self.is_line_visible(file, line))
+            .map(|(line_index, _)| line_index)
+    }
+
+    /// Move the selection to the closest shown line if the selected line is filtered out,
+    /// preferring the lines below
+    fn select_visible_line(&mut self) {
+        let Some(file) = self.get_selected_file() else {
+            return;
+        };
+        let is_selected_line_visible = file
+            .content()
+            .lines()
+            .nth(self.selected_line_index)
+            .is_some_and(|line| self.is_line_visible(file, line));
+        if !is_selected_line_visible
+            && let Some(line_index) = self
+                .next_visible_line_index()
+                .or_else(|| self.previous_visible_line_index())
+        {
+            self.selected_line_index = line_index;
+        }
+    }
+
     /// Handle the Escape key based on the current navigation mode
     /// If in Lines mode, switch to Files mode.
     /// If already in Files mode, close the view.
@@ -446,10 +1481,45 @@ impl ReviewDetailsView {
             NavigationMode::Files => {
                 // Close the view when already in Files mode
                 app.events.send(AppEvent::ViewClose);
+                if app.settings.show_review_summary
+                    && let Some(summary) = self.review_summary(app.settings.full_shas)
+                {
+                    app.events
+                        .send(AppEvent::ReviewSummaryOpen(Arc::new(summary)));
+                }
             }
         }
     }
 
+    /// Summary of the viewed files and comments of the review, shown when the view is closed.
+    /// There is none for a quick review, which stores neither.
+    fn review_summary(&self, full_shas: bool) -> Option<ReviewSummary> {
+        if self.quick_review {
+            return None;
+        }
+        let review = self.review.as_ref()?;
+
+        Some(ReviewSummary {
+            review_title: review.display_title(full_shas),
+            viewed_file_count: self
+                .diff
+                .files
+                .iter()
+                .filter(|file| self.viewed_files.contains(&file.path))
+                .count(),
+            // Skipped files are not counted as files to view
+            file_count: self
+                .diff
+                .files
+                .iter()
+                .filter(|file| !self.is_file_skipped(&file.path))
+                .count(),
+            comments_added: self.comments_added,
+            unresolved_comment_count: self.unresolved_comment_count,
+            comment_count: self.comment_count,
+        })
+    }
+
     /// Handle review loading state changes
     fn handle_review_loading_state(&mut self, app: &mut App, loading_state: &ReviewLoadingState) {
         self.review_state = loading_state.clone();
@@ -459,11 +1529,18 @@ impl ReviewDetailsView {
         if let ReviewLoadingState::Loaded(review) = loading_state {
             self.review = Some(review.clone());
 
-            // Request git diff if SHAs are available
-            if let (Some(base_sha), Some(target_sha)) = (&review.base_sha, &review.target_sha) {
+            // Request the diff of the patch file or the git diff if SHAs are available
+            if let Some(patch_path) = &review.patch_path {
+                self.diff_state = GitDiffLoadingState::Loading;
+                app.events
+                    .send(AppEvent::PatchDiffLoad(Arc::from(patch_path.as_str())));
+            } else if let (Some(base_sha), Some(target_sha)) =
+                (&review.base_sha, &review.target_sha)
+            {
                 app.events.send(AppEvent::GitDiffLoad {
                     base_sha: base_sha.clone().into(),
                     target_sha: target_sha.clone().into(),
+                    pathspec: self.pathspec.clone(),
                 });
             } else {
                 self.diff_state = GitDiffLoadingState::Error(
@@ -471,6 +1548,11 @@ impl ReviewDetailsView {
                 );
             }
 
+            if self.quick_review {
+                // Nothing is stored for a quick review
+                return;
+            }
+
             // Load file views for this review
             app.events.send(AppEvent::FileViewsLoad {
                 review_id: review.id.clone().into(),
@@ -492,14 +1574,24 @@ impl ReviewDetailsView {
         self.selected_line_index = 0;
         self.navigation_mode = NavigationMode::Files;
         self.active_file_list = FileListType::NotViewed;
+        self.overview_selected_index = 0;
         self.viewed_files = Arc::new(vec![]);
+        self.skipped_files = Arc::new(vec![]);
+        self.viewed_file_content_hashes = Arc::new(HashMap::new());
         self.files_with_file_comments = Arc::new(vec![]);
         self.lines_with_comments = Arc::new(HashMap::new());
+        self.comment_counts_by_file = Arc::new(HashMap::new());
+        self.comment_priorities_by_file = Arc::new(HashMap::new());
+        self.context_expansions.clear();
+        self.hunk_blames.clear();
+        self.content_version += 1;
+        self.comments_version += 1;
     }
 
     /// Handle git diff loading state changes
     fn handle_git_diff_loading_state(&mut self, loading_state: &GitDiffLoadingState) {
         self.diff_state = loading_state.clone();
+        self.diff_progress = None;
 
         // Use structured diff data when loaded
         if let GitDiffLoadingState::Loaded(diff) = loading_state {
@@ -507,39 +1599,273 @@ impl ReviewDetailsView {
             self.selected_file_index = 0;
             self.selected_line_index = 0;
             self.navigation_mode = NavigationMode::Files;
+            self.context_expansions.clear();
+            self.content_version += 1;
+            self.update_unchanged_files();
+
+            if let Some(file_jump) = self.pending_file_jump.take() {
+                self.jump_to_file(&file_jump);
+            }
+        }
+    }
+
+    /// Check if the diff loading state is for the SHAs of the current review
+    fn relevant_git_diff_loading_state(&self, base_sha: &str, target_sha: &str) -> bool {
+        self.review.as_ref().is_some_and(|review| {
+            review.base_sha.as_deref() == Some(base_sha)
+                && review.target_sha.as_deref() == Some(target_sha)
+        })
+    }
+
+    /// Select the file of the jump in the list it belongs to and, if a line is given,
+    /// switch to Lines mode with that line selected
+    fn jump_to_file(&mut self, file_jump: &FileJump) {
+        let file_path = file_jump.file_path.as_ref();
+        if !self.diff.files.iter().any(|file| file.path == file_path) {
+            log::warn!("File {file_path} not found in diff, cannot jump to it");
+            return;
+        }
+
+        self.active_file_list = if self.is_file_done(file_path) {
+            FileListType::Viewed
+        } else {
+            FileListType::NotViewed
+        };
+        self.selected_file_index = self
+            .get_current_file_list()
+            .iter()
+            .position(|file| file.path == file_path)
+            .unwrap_or(0);
+        self.scroll_to_selected_file();
+
+        if let Some(line_number) = file_jump.line_number
+            && self.get_selected_file().is_some_and(DiffFile::has_lines)
+        {
+            let max_line_index = self.get_current_file_lines().saturating_sub(1);
+            self.selected_line_index = (line_number.max(0) as usize).min(max_line_index);
+            self.select_visible_line();
+            self.navigation_mode = NavigationMode::Lines;
+            self.update_scroll_to_follow_selected_line();
+        }
+    }
+
+    /// Jump to a line of a file, deferred until the diff is loaded
+    fn jump_to_diff_line(&mut self, file_path: &Arc<str>, line_number: i64) {
+        let file_jump = FileJump {
+            file_path: Arc::clone(file_path),
+            line_number: Some(line_number),
+        };
+        if matches!(self.diff_state, GitDiffLoadingState::Loaded(_)) {
+            self.show_overview = false;
+            self.show_churn_summary = false;
+            self.jump_to_file(&file_jump);
+        } else {
+            self.pending_file_jump = Some(file_jump);
         }
     }
 
     /// Get the number of lines in the currently selected file
     fn get_current_file_lines(&self) -> usize {
         if let Some(file) = self.get_selected_file() {
-            file.content.lines().count()
+            file.content().lines().count()
         } else {
             0
         }
     }
 
-    /// Update scroll offset to ensure selected line is visible
-    fn update_scroll_to_follow_selected_line(&mut self, content_height: usize) {
-        if content_height == 0 {
+    /// Context expanded around a hunk of a file
+    fn context_expansion(&self, file_path: &str, hunk: &DiffHunk) -> Option<&ContextExpansion> {
+        self.context_expansions
+            .get(&(file_path.to_string(), hunk.line_index))
+    }
+
+    /// Unchanged lines (first line, count) that are hidden between the hunk at `position` and
+    /// the previous hunk (or the start of the file), taking expanded context into account
+    fn hidden_lines_before_hunk(
+        &self,
+        file_path: &str,
+        hunks: &[DiffHunk],
+        position: usize,
+    ) -> (usize, usize) {
+        let first_hidden = match position.checked_sub(1).map(|previous| &hunks[previous]) {
+            Some(previous) => {
+                previous.new_end()
+                    + self
+                        .context_expansion(file_path, previous)
+                        .map_or(0, |expansion| expansion.below.len())
+            }
+            None => 1,
+        };
+        let hunk = &hunks[position];
+        let first_visible = hunk.new_first_line()
+            - self
+                .context_expansion(file_path, hunk)
+                .map_or(0, |expansion| expansion.above.len());
+        (first_hidden, first_visible.saturating_sub(first_hidden))
+    }
+
+    /// Request more unchanged lines above or below the hunk of the selected line
+    fn expand_context(&self, app: &mut App, direction: DiffContextDirection) {
+        if !matches!(self.navigation_mode, NavigationMode::Lines) {
+            return;
+        }
+        let (Some(file), Some(target_sha)) = (
+            self.get_selected_file(),
+            self.review
+                .as_ref()
+                .and_then(|review| review.target_sha.as_deref()),
+        ) else {
+            return;
+        };
+        let hunks = file.hunk_positions();
+        let Some(position) = hunks
+            .iter()
+            .rposition(|hunk| hunk.line_index <= self.selected_line_index)
+        else {
+            return;
+        };
+
+        let (start_line, end_line) = match direction {
+            DiffContextDirection::Above => {
+                let (first_hidden, hidden_count) =
+                    self.hidden_lines_before_hunk(&file.path, &hunks, position);
+                if hidden_count == 0 {
+                    return;
+                }
+                let end_line = first_hidden + hidden_count - 1;
+                (
+                    (end_line + 1)
+                        .saturating_sub(CONTEXT_EXPAND_STEP)
+                        .max(first_hidden),
+                    end_line,
+                )
+            }
+            DiffContextDirection::Below => {
+                let hunk = &hunks[position];
+                let expansion = self.context_expansion(&file.path, hunk);
+                // Deleted files have no lines to expand
+                if hunk.new_start == 0 || expansion.is_some_and(|expansion| expansion.end_of_file) {
+                    return;
+                }
+                let start_line =
+                    hunk.new_end() + expansion.map_or(0, |expansion| expansion.below.len());
+                let mut end_line = start_line + CONTEXT_EXPAND_STEP - 1;
+                // Between hunks only the lines that are still hidden can be expanded
+                if position + 1 < hunks.len() {
+                    let (first_hidden, hidden_count) =
+                        self.hidden_lines_before_hunk(&file.path, &hunks, position + 1);
+                    if hidden_count == 0 {
+                        return;
+                    }
+                    end_line = end_line.min(first_hidden + hidden_count - 1);
+                }
+                (start_line, end_line)
+            }
+        };
+
+        app.events
+            .send(AppEvent::DiffContextExpand(Arc::new(DiffContextRequest {
+                sha: Arc::from(target_sha),
+                file_path: Arc::from(file.path.as_str()),
+                hunk_line_index: hunks[position].line_index,
+                direction,
+                start_line,
+                end_line,
+            })));
+    }
+
+    /// Add loaded context lines to the expansion of their hunk. Responses that don't continue
+    /// the current expansion (e.g. for an outdated diff or duplicate requests) are ignored.
+    fn handle_diff_context_expanded(&mut self, request: &DiffContextRequest, lines: &[String]) {
+        let target_sha = self
+            .review
+            .as_ref()
+            .and_then(|review| review.target_sha.as_deref());
+        if target_sha != Some(request.sha.as_ref()) {
+            return;
+        }
+        let Some(file) = self
+            .diff
+            .files
+            .iter()
+            .find(|file| file.path == request.file_path.as_ref())
+        else {
+            return;
+        };
+        let hunks = file.hunk_positions();
+        let Some(hunk) = hunks
+            .iter()
+            .find(|hunk| hunk.line_index == request.hunk_line_index)
+        else {
+            return;
+        };
+
+        let expansion = self.context_expansion(&file.path, hunk);
+        let continues_expansion = match request.direction {
+            DiffContextDirection::Above => {
+                let first_visible =
+                    hunk.new_first_line() - expansion.map_or(0, |expansion| expansion.above.len());
+                request.end_line + 1 == first_visible
+            }
+            DiffContextDirection::Below => {
+                request.start_line
+                    == hunk.new_end() + expansion.map_or(0, |expansion| expansion.below.len())
+            }
+        };
+        if !continues_expansion {
             return;
         }
 
-        // If selected line is above the viewport, scroll up
-        if self.selected_line_index < self.scroll_offset {
-            self.scroll_offset = self.selected_line_index;
+        let expansion = self
+            .context_expansions
+            .entry((file.path.clone(), hunk.line_index))
+            .or_default();
+        match request.direction {
+            DiffContextDirection::Above => {
+                expansion.above.splice(0..0, lines.iter().cloned());
+            }
+            DiffContextDirection::Below => {
+                expansion.below.extend(lines.iter().cloned());
+                expansion.end_of_file = lines.len() < request.end_line + 1 - request.start_line;
+            }
         }
+        self.content_version += 1;
+    }
+
+    /// Number of diff rows shown in the content area from the last render
+    fn content_height(&self) -> usize {
+        self.render_metrics
+            .get()
+            .content_height(DEFAULT_CONTENT_HEIGHT)
+    }
+
+    /// Update scroll offset to ensure selected line is visible, following the scroll mode
+    fn update_scroll_to_follow_selected_line(&mut self) {
+        let content_height = self.content_height();
+
+        let selected_line = self.selected_scroll_line();
+        match self.scroll_mode {
+            ScrollMode::EdgeFollow => {
+                // If selected line is above the viewport, scroll up
+                if selected_line < self.scroll_offset {
+                    self.scroll_offset = selected_line;
+                }
 
-        // If selected line is below the viewport, scroll down
-        let viewport_bottom = self.scroll_offset + content_height.saturating_sub(1);
-        if self.selected_line_index > viewport_bottom {
-            self.scroll_offset = self
-                .selected_line_index
-                .saturating_sub(content_height.saturating_sub(1));
+                // If selected line is below the viewport, scroll down
+                let viewport_bottom = self.scroll_offset + content_height.saturating_sub(1);
+                if selected_line > viewport_bottom {
+                    self.scroll_offset =
+                        selected_line.saturating_sub(content_height.saturating_sub(1));
+                }
+            }
+            ScrollMode::Centered => {
+                // Near the top of the file the offset is clamped to 0
+                self.scroll_offset = selected_line.saturating_sub(content_height / 2);
+            }
         }
 
         // Ensure scroll offset doesn't exceed bounds
-        let current_file_lines = self.get_current_file_lines();
+        let current_file_lines = self.scroll_line_count();
         let max_offset = if current_file_lines > content_height {
             current_file_lines.saturating_sub(content_height)
         } else {
@@ -560,6 +1886,56 @@ impl ReviewDetailsView {
         }
     }
 
+    /// Select the first file of the not viewed list. When all files are viewed, the first
+    /// file of the viewed list is selected and the review is reported complete.
+    fn jump_to_first_not_viewed_file(&mut self) {
+        self.active_file_list = if self.get_file_list(&FileListType::NotViewed).is_empty() {
+            if !self.diff.is_empty() {
+                self.notice = Some(Arc::from(REVIEW_COMPLETE_NOTICE));
+            }
+            FileListType::Viewed
+        } else {
+            FileListType::NotViewed
+        };
+        self.navigation_mode = NavigationMode::Files;
+        self.selected_file_index = 0;
+        self.selected_line_index = 0;
+        self.scroll_offset = 0;
+    }
+
+    /// Select the next (or previous) file of the active list ordered by churn, from the most to
+    /// the least added and removed lines. Files with the same churn are ordered by path.
+    fn jump_by_churn(&mut self, next: bool) {
+        let mut files: Vec<(usize, &DiffFile)> = self
+            .get_current_file_list()
+            .into_iter()
+            .enumerate()
+            .collect();
+        files.sort_by_key(|(_, file)| {
+            let (added, removed) = file.line_stats();
+            (std::cmp::Reverse(added + removed), file.path.as_str())
+        });
+        let Some(position) = files
+            .iter()
+            .position(|(index, _)| *index == self.selected_file_index)
+        else {
+            return;
+        };
+        let target = if next {
+            files.get(position + 1)
+        } else {
+            position
+                .checked_sub(1)
+                .and_then(|position| files.get(position))
+        };
+        if let Some(&(file_index, _)) = target {
+            self.navigation_mode = NavigationMode::Files;
+            self.selected_file_index = file_index;
+            self.selected_line_index = 0;
+            self.scroll_to_selected_file();
+        }
+    }
+
     /// Switch to the right file list (viewed files)
     fn switch_file_list_right(&mut self) {
         if matches!(self.navigation_mode, NavigationMode::Files)
@@ -572,48 +1948,479 @@ impl ReviewDetailsView {
         }
     }
 
-    /// Toggle the view status of the currently selected file
-    fn toggle_file_view_status(&mut self, app: &mut App) {
-        if let Some(review) = &self.review {
-            let current_files = self.get_current_file_list();
-            if let Some(file) = current_files.get(self.selected_file_index) {
-                app.events.send(AppEvent::FileViewToggle {
-                    review_id: review.id.clone().into(),
-                    file_path: file.path.clone().into(),
-                });
-            }
-        }
+    /// Toggle whether the viewed files list collapses into a count badge while inactive
+    fn toggle_collapse_viewed_list(&self, app: &mut App) {
+        app.settings.collapse_viewed_list = !app.settings.collapse_viewed_list;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
     }
 
-    /// Open comments view for the current context (file or line)
-    fn open_comments(&mut self, app: &mut App) {
-        if let Some(review) = &self.review {
-            let current_files = self.get_current_file_list();
-            if let Some(file) = current_files.get(self.selected_file_index) {
-                let line_number = match self.navigation_mode {
-                    NavigationMode::Files => None, // File-level comments
-                    NavigationMode::Lines => Some(self.selected_line_index as i64), // Line-level comments
-                };
+    /// Toggle between absolute and relative line numbers in the diff gutter
+    fn toggle_relative_line_numbers(&self, app: &mut App) {
+        app.settings.relative_line_numbers = !app.settings.relative_line_numbers;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
+    }
 
-                app.events.send(AppEvent::CommentsOpen {
-                    review_id: review.id.clone().into(),
-                    file_path: file.path.clone().into(),
-                    line_number,
-                });
-            }
-        }
+    /// Toggle showing the `+`/`-`/space origin prefixes of the diff lines
+    fn toggle_diff_prefixes(&self, app: &mut App) {
+        app.settings.hide_diff_prefixes = !app.settings.hide_diff_prefixes;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
     }
 
-    /// Handle file views loaded event
-    fn handle_file_views_loaded(&mut self, _review_id: &str, viewed_files: &Arc<Vec<String>>) {
-        self.viewed_files = viewed_files.clone();
-        // Reset selection when file views change
-        self.selected_file_index = 0;
-        self.selected_line_index = 0;
-        self.scroll_offset = 0;
+    /// Toggle showing who last touched the lines each hunk replaces and persist the preference
+    fn toggle_hunk_blame(&self, app: &mut App) {
+        app.settings.show_hunk_blame = !app.settings.show_hunk_blame;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
     }
 
-    /// Handle comments loaded. This updates the files with comments and lines with comments
+    /// Toggle showing tabs and trailing spaces in the diff lines
+    fn toggle_whitespace(&self, app: &mut App) {
+        app.settings.show_whitespace = !app.settings.show_whitespace;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
+    }
+
+    /// Toggle showing the removed lines of a change paired with their added lines
+    fn toggle_inline_changes(&self, app: &mut App) {
+        app.settings.inline_changes = !app.settings.inline_changes;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
+    }
+
+    /// Request the hunk blame of the selected file while it is shown and not loaded yet.
+    /// Blaming is slow, so only the files that are looked at are blamed, once each.
+    fn load_hunk_blame(&mut self, app: &mut App) {
+        if !app.settings.show_hunk_blame {
+            return;
+        }
+        let Some(base_sha) = self
+            .review
+            .as_ref()
+            .and_then(|review| review.base_sha.clone())
+        else {
+            return;
+        };
+        let Some(file) = self.get_selected_file() else {
+            return;
+        };
+        if file.metadata_only() || self.hunk_blames.contains_key(&file.path) {
+            return;
+        }
+
+        let request = HunkBlameRequest {
+            base_sha: Arc::from(base_sha),
+            file_path: Arc::from(file.path.as_str()),
+            old_path: Arc::from(self.diff.old_path(&file.path).unwrap_or(&file.path)),
+            hunks: file.hunk_positions().into(),
+        };
+        self.hunk_blames
+            .insert(file.path.clone(), Arc::new(HashMap::new()));
+        self.content_version += 1;
+        app.events.send(AppEvent::HunkBlameLoad(Arc::new(request)));
+    }
+
+    /// Store the blame summaries of a file, unless they are for a previous base SHA
+    fn handle_hunk_blame_loaded(
+        &mut self,
+        request: &HunkBlameRequest,
+        summaries: &Arc<HashMap<usize, Arc<str>>>,
+    ) {
+        let is_current_base = self
+            .review
+            .as_ref()
+            .and_then(|review| review.base_sha.as_deref())
+            == Some(request.base_sha.as_ref());
+        if is_current_base {
+            self.hunk_blames
+                .insert(request.file_path.to_string(), Arc::clone(summaries));
+            self.content_version += 1;
+        }
+    }
+
+    /// Switch between scrolling at the viewport edges and keeping the selected line centered
+    fn toggle_scroll_mode(&mut self, app: &mut App) {
+        self.scroll_mode = self.scroll_mode.toggle();
+        if matches!(self.navigation_mode, NavigationMode::Lines) {
+            self.update_scroll_to_follow_selected_line();
+        }
+        app.settings.scroll_mode = self.scroll_mode;
+        app.events
+            .send(AppEvent::SettingsSave(Arc::new(app.settings.clone())));
+    }
+
+    /// Open the selected file in the user's git difftool
+    fn open_difftool(&self, app: &mut App) {
+        let Some(review) = &self.review else {
+            return;
+        };
+        let (Some(base_sha), Some(target_sha)) = (&review.base_sha, &review.target_sha) else {
+            return;
+        };
+        let current_files = self.get_current_file_list();
+        let Some(file) = current_files.get(self.selected_file_index) else {
+            return;
+        };
+
+        app.events.send(AppEvent::DifftoolOpen {
+            base_sha: base_sha.as_str().into(),
+            target_sha: target_sha.as_str().into(),
+            file_path: file.path.as_str().into(),
+        });
+    }
+
+    /// Copy the GitHub/GitLab compare URL of the review, using the SHAs if known
+    fn copy_compare_url(&self, app: &mut App) {
+        let Some(review) = &self.review else {
+            return;
+        };
+
+        app.events.send(AppEvent::CompareUrlCopy {
+            base: review
+                .base_sha
+                .as_deref()
+                .unwrap_or(&review.base_branch)
+                .into(),
+            target: review
+                .target_sha
+                .as_deref()
+                .unwrap_or(&review.target_branch)
+                .into(),
+        });
+    }
+
+    /// Text with the SHAs of the review abbreviated unless full SHAs are shown. Copied text keeps
+    /// the full SHAs, only what is displayed of it is shortened.
+    fn display_shas(&self, text: &str, full_shas: bool) -> String {
+        let Some(review) = self.review.as_ref().filter(|_| !full_shas) else {
+            return text.to_string();
+        };
+        [&review.base_sha, &review.target_sha]
+            .into_iter()
+            .flatten()
+            .fold(text.to_string(), |text, sha| {
+                text.replace(sha, short_sha(sha))
+            })
+    }
+
+    /// Copy the git CLI command that shows the diff of the review
+    fn copy_git_diff_command(&self, app: &mut App) {
+        let Some(review) = &self.review else {
+            return;
+        };
+
+        app.events.send(AppEvent::ClipboardCopy(Arc::from(
+            review.git_diff_command(self.pathspec.as_deref()),
+        )));
+    }
+
+    /// Copy the hunk containing the selected line (Lines mode only)
+    fn copy_selected_hunk(&self, app: &mut App) {
+        if !matches!(self.navigation_mode, NavigationMode::Lines) {
+            return;
+        }
+        if let Some(hunk_text) = self
+            .get_selected_file()
+            .and_then(|file| file.hunk_text(self.selected_line_index))
+        {
+            app.events
+                .send(AppEvent::ClipboardCopy(Arc::from(hunk_text)));
+        }
+    }
+
+    /// Whether the viewed files list is currently rendered as a collapsed count badge
+    fn is_viewed_list_collapsed(&self, settings: &Settings) -> bool {
+        settings.collapse_viewed_list && self.active_file_list != FileListType::Viewed
+    }
+
+    /// Whether the file doesn't need to be reviewed anymore because it is viewed or skipped
+    fn is_file_done(&self, file_path: &str) -> bool {
+        self.viewed_files.iter().any(|path| path == file_path) || self.is_file_skipped(file_path)
+    }
+
+    fn is_file_skipped(&self, file_path: &str) -> bool {
+        self.skipped_files.iter().any(|path| path == file_path)
+    }
+
+    /// Toggle the skipped status of the currently selected file
+    fn toggle_file_skip_status(&mut self, app: &mut App) {
+        let Some(review) = &self.review else {
+            return;
+        };
+        if let Some(file) = self.get_current_file_list().get(self.selected_file_index) {
+            app.events.send(AppEvent::FileSkipToggle {
+                review_id: review.id.clone().into(),
+                file_path: file.path.clone().into(),
+            });
+            self.toggled_from_file_list = matches!(self.navigation_mode, NavigationMode::Files);
+        }
+    }
+
+    /// Toggle the view status of the currently selected file
+    fn toggle_file_view_status(&mut self, app: &mut App) {
+        if let Some(review) = &self.review {
+            let current_files = self.get_current_file_list();
+            if let Some(file) = current_files.get(self.selected_file_index) {
+                app.events.send(AppEvent::FileViewToggle {
+                    review_id: review.id.clone().into(),
+                    file_path: file.path.clone().into(),
+                    content_hash: Some(file.content_hash().into()),
+                });
+                self.toggled_from_file_list = matches!(self.navigation_mode, NavigationMode::Files);
+            }
+        }
+    }
+
+    /// Open comments view for the current context (file or line)
+    fn open_comments(&mut self, app: &mut App) {
+        if let Some(review) = &self.review {
+            let current_files = self.get_current_file_list();
+            if let Some(file) = current_files.get(self.selected_file_index) {
+                let line_number = match self.navigation_mode {
+                    NavigationMode::Files => None, // File-level comments
+                    NavigationMode::Lines => Some(self.selected_line_index as i64), // Line-level comments
+                };
+
+                // Line comments on an outdated diff could end up on the wrong lines
+                if line_number.is_some()
+                    && let Some(options) =
+                        Self::outdated_diff_refresh_options(review, &app.repo_path)
+                {
+                    app.events.send(AppEvent::CommentsOpenOutdatedDiff {
+                        review_id: review.id.clone().into(),
+                        file_path: file.path.clone().into(),
+                        line_number,
+                        options,
+                    });
+                    return;
+                }
+
+                app.events.send(AppEvent::CommentsOpen {
+                    review_id: review.id.clone().into(),
+                    file_path: file.path.clone().into(),
+                    line_number,
+                    old_file_path: self.diff.old_path(&file.path).map(Arc::from),
+                });
+            }
+        }
+    }
+
+    /// Resolve the comments of the selected line without opening them, or unresolve them when
+    /// most of them are resolved already
+    fn toggle_line_comments_resolved(&mut self, app: &mut App) {
+        if !matches!(self.navigation_mode, NavigationMode::Lines) {
+            return;
+        }
+        let Some(review) = &self.review else {
+            return;
+        };
+        let Some(file) = self
+            .get_current_file_list()
+            .get(self.selected_file_index)
+            .copied()
+        else {
+            return;
+        };
+        let line_number = self.selected_line_index as i64;
+        let has_comments = self
+            .lines_with_comments
+            .get(&file.path)
+            .is_some_and(|lines| lines.contains(&line_number));
+        if !has_comments {
+            self.notice = Some(Arc::from("No comments on this line"));
+            return;
+        }
+
+        app.events.send(AppEvent::CommentsToggleAllResolved {
+            review_id: review.id.clone().into(),
+            file_path: file.path.clone().into(),
+            line_number: Some(line_number),
+        });
+    }
+
+    /// Offer to refresh the review when the refs changed and one of its branches moved to a head
+    /// that wasn't offered yet. The moved heads are kept in the review like the branch status
+    /// check does, so that the title shows them and `r` refreshes to them.
+    #[cfg(feature = "watch-refs")]
+    fn handle_refs_changed(&mut self, app: &mut App) {
+        if self.quick_review || self.is_patch_review() {
+            return;
+        }
+        let Some(review) = &self.review else {
+            return;
+        };
+        let base_sha_changed =
+            Self::moved_branch_head(&review.base_sha, &review.base_branch, &app.repo_path);
+        let target_sha_changed =
+            Self::moved_branch_head(&review.target_sha, &review.target_branch, &app.repo_path);
+        if base_sha_changed == review.base_sha_changed
+            && target_sha_changed == review.target_sha_changed
+        {
+            return;
+        }
+
+        let options = ReviewRefreshOptions {
+            can_refresh_base: base_sha_changed.is_some(),
+            can_refresh_target: target_sha_changed.is_some(),
+            can_duplicate: true,
+        };
+        let review = Arc::new(Review {
+            base_sha_changed,
+            target_sha_changed,
+            ..review.as_ref().clone()
+        });
+        let review_id = Arc::from(review.id.as_str());
+        self.review = Some(review);
+        if !options.can_refresh_base && !options.can_refresh_target {
+            return;
+        }
+
+        self.notice = Some(Arc::from("The branches of the review moved"));
+        app.events
+            .send(AppEvent::ReviewRefreshOpen { review_id, options });
+    }
+
+    /// Current head of a branch if it moved away from the SHA the diff was loaded from, `None`
+    /// if it didn't move or its head can't be determined
+    fn moved_branch_head(
+        reviewed_sha: &Option<String>,
+        branch: &str,
+        repo_path: &str,
+    ) -> Option<String> {
+        let reviewed_sha = reviewed_sha.as_ref()?;
+        match GitService::get_branch_sha(repo_path, branch) {
+            Ok(head_sha) => head_sha.filter(|head_sha| head_sha != reviewed_sha),
+            Err(error) => {
+                log::warn!("Failed to check head of branch {branch}: {error}");
+                None
+            }
+        }
+    }
+
+    /// Compare the SHAs the diff was loaded from with the current branch heads.
+    /// Returns the refresh options if a branch moved, `None` if the diff is up to date
+    /// or the branch heads can't be determined.
+    fn outdated_diff_refresh_options(
+        review: &Review,
+        repo_path: &str,
+    ) -> Option<ReviewRefreshOptions> {
+        let is_outdated = |reviewed_sha: &Option<String>, branch: &str| {
+            Self::moved_branch_head(reviewed_sha, branch, repo_path).is_some()
+        };
+
+        let base_outdated = is_outdated(&review.base_sha, &review.base_branch);
+        let target_outdated = is_outdated(&review.target_sha, &review.target_branch);
+        if !base_outdated && !target_outdated {
+            return None;
+        }
+
+        Some(ReviewRefreshOptions {
+            can_refresh_base: base_outdated,
+            can_refresh_target: target_outdated,
+            can_duplicate: true,
+        })
+    }
+
+    /// Handle file views loaded event
+    fn handle_file_views_loaded(
+        &mut self,
+        _review_id: &str,
+        viewed_files: &Arc<Vec<String>>,
+        skipped_files: &Arc<Vec<String>>,
+        content_hashes: &Arc<HashMap<String, String>>,
+    ) {
+        // A file that moves to the other list is followed to stay on it, e.g. after toggling it
+        // from its lines or after jumping to it before its file views were loaded. A file
+        // toggled from the file list is left to continue with the first file of the list.
+        let toggled_from_file_list = std::mem::take(&mut self.toggled_from_file_list);
+        let selected_line = (!toggled_from_file_list)
+            .then(|| self.get_selected_file())
+            .flatten()
+            .map(|file| FileJump {
+                file_path: Arc::from(file.path.as_str()),
+                line_number: match self.navigation_mode {
+                    NavigationMode::Lines => Some(self.selected_line_index as i64),
+                    NavigationMode::Files => None,
+                },
+            });
+
+        self.viewed_files = viewed_files.clone();
+        self.skipped_files = skipped_files.clone();
+        self.viewed_file_content_hashes = content_hashes.clone();
+        self.content_version += 1;
+        // Reset selection when file views change
+        self.selected_file_index = 0;
+        self.selected_line_index = 0;
+        self.scroll_offset = 0;
+
+        if let Some(file_jump) = selected_line {
+            self.jump_to_file(&file_jump);
+        }
+    }
+
+    /// Number of comments on files that are not part of the loaded diff, e.g. after the
+    /// branches of the review were changed
+    fn comments_outside_diff_count(&self) -> usize {
+        if !matches!(self.diff_state, GitDiffLoadingState::Loaded(_)) {
+            return 0;
+        }
+
+        self.comment_counts_by_file
+            .iter()
+            .filter(|(file_path, _)| {
+                let file_path = self.diff.current_path(file_path);
+                !self.diff.files.iter().any(|file| file.path == file_path)
+            })
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Viewed files whose diff content changed since they were marked as viewed.
+    /// Files viewed without a stored content hash are never considered changed.
+    fn changed_viewed_files(&self) -> Vec<String> {
+        self.viewed_files
+            .iter()
+            .filter(|file_path| {
+                let Some(content_hash) = self.viewed_file_content_hashes.get(*file_path) else {
+                    return false;
+                };
+                self.diff
+                    .files
+                    .iter()
+                    .find(|file| &file.path == *file_path)
+                    .is_some_and(|file| file.content_hash() != *content_hash)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Move viewed files that changed since they were marked as viewed back to not viewed
+    fn reset_changed_viewed_files(&mut self, app: &mut App) {
+        let Some(review) = &self.review else {
+            return;
+        };
+        let changed_files = self.changed_viewed_files();
+        if changed_files.is_empty() {
+            return;
+        }
+
+        self.viewed_files = Arc::new(
+            self.viewed_files
+                .iter()
+                .filter(|file_path| !changed_files.contains(file_path))
+                .cloned()
+                .collect(),
+        );
+        self.content_version += 1;
+        app.events.send(AppEvent::FileViewsReset {
+            review_id: review.id.clone().into(),
+            file_paths: changed_files.into(),
+        });
+    }
+
+    /// Handle comments loaded. This updates the files with comments and lines with comments
     /// so that the comment indicators are up to date.
     fn handle_comments_loading_state(
         &mut self,
@@ -626,8 +2433,12 @@ impl ReviewDetailsView {
         };
 
         if let CommentsLoadingState::Loaded(comments) = state {
-            // Separate unresolved and resolved comments
-            let unresolved_comments: Vec<_> = comments.iter().filter(|c| !c.resolved).collect();
+            self.unresolved_comment_count = comments.iter().filter(|c| !c.resolved).count();
+            // Comments acknowledged with a status are indicated like resolved ones, only the
+            // open ones still need attention
+            let unresolved_comments: Vec<_> =
+                comments.iter().filter(|c| c.needs_attention()).collect();
+            self.comment_count = comments.len();
             let _resolved_comments: Vec<_> = comments.iter().filter(|c| c.resolved).collect();
 
             // Track files with unresolved file-level comments
@@ -669,6 +2480,24 @@ impl ReviewDetailsView {
                 },
             ));
 
+            self.comment_counts_by_file = Arc::new(comments.iter().fold(
+                HashMap::new(),
+                |mut acc: HashMap<String, usize>, comment| {
+                    *acc.entry(comment.file_path.clone()).or_default() += 1;
+                    acc
+                },
+            ));
+
+            self.comment_priorities_by_file = Arc::new(unresolved_comments.iter().fold(
+                HashMap::new(),
+                |mut acc: HashMap<String, CommentPriority>, comment| {
+                    acc.entry(comment.file_path.clone())
+                        .and_modify(|priority| *priority = (*priority).max(comment.priority))
+                        .or_insert(comment.priority);
+                    acc
+                },
+            ));
+
             // Track files that only have resolved comments
             let all_files_with_comments: HashSet<String> =
                 comments.iter().map(|c| c.file_path.clone()).collect();
@@ -727,6 +2556,7 @@ impl ReviewDetailsView {
                     })
                     .collect::<HashMap<String, Vec<i64>>>(),
             );
+            self.comments_version += 1;
         };
     }
 
@@ -758,19 +2588,31 @@ impl ReviewDetailsView {
 
     /// Get the current file list based on the active file list type
     fn get_current_file_list(&self) -> Vec<&DiffFile> {
-        match self.active_file_list {
-            FileListType::NotViewed => self
-                .diff
-                .files
-                .iter()
-                .filter(|file| !self.viewed_files.contains(&file.path))
-                .collect(),
-            FileListType::Viewed => self
-                .diff
-                .files
-                .iter()
-                .filter(|file| self.viewed_files.contains(&file.path))
-                .collect(),
+        self.get_file_list(&self.active_file_list)
+    }
+
+    /// Get the files of the given file list
+    fn get_file_list(&self, list_type: &FileListType) -> Vec<&DiffFile> {
+        let unchanged_files: &[DiffFile] = if self.show_unchanged_files {
+            &self.unchanged_files
+        } else {
+            &[]
+        };
+        let excluded_files: &[DiffFile] = if self.show_excluded_files {
+            &self.diff.excluded_files
+        } else {
+            &[]
+        };
+        let is_done = |file: &&DiffFile| self.is_file_done(&file.path);
+        let files = self
+            .diff
+            .files
+            .iter()
+            .chain(excluded_files)
+            .chain(unchanged_files);
+        match list_type {
+            FileListType::NotViewed => files.filter(|file| !is_done(file)).collect(),
+            FileListType::Viewed => files.filter(is_done).collect(),
         }
     }
 
@@ -808,7 +2650,7 @@ impl ReviewDetailsView {
         error_text.render(area, buf);
     }
 
-    fn render_loaded(&self, area: Rect, buf: &mut Buffer) {
+    fn render_loaded(&self, app: &App, area: Rect, buf: &mut Buffer) {
         let review = self.review.as_ref().expect("Review should be loaded");
 
         let layout = Layout::default()
@@ -825,18 +2667,49 @@ impl ReviewDetailsView {
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Gray));
 
-        let title = review.title().clone();
-        let title_content = Paragraph::new(title.as_str())
+        let comments_outside_diff = self.comments_outside_diff_count();
+        let title_block = if comments_outside_diff > 0 {
+            title_block.title(
+                Line::from(format!(
+                    " ⚠ {comments_outside_diff} comment(s) on files not in the diff "
+                ))
+                .style(Style::default().fg(Color::Yellow))
+                .right_aligned(),
+            )
+        } else {
+            title_block
+        };
+
+        // The diff goes from the base (removed lines) to the target (added lines)
+        let (base, target) = review.diff_direction(app.settings.full_shas);
+        let mut title = vec![
+            Span::styled(format!("- {base}"), Style::default().fg(Color::Red)),
+            Span::raw(" → "),
+            Span::styled(format!("+ {target}"), Style::default().fg(Color::Green)),
+        ];
+        if review.base_sha_changed.is_some() || review.target_sha_changed.is_some() {
+            title.push(Span::styled(
+                " [branch moved, r to refresh]",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if self.quick_review {
+            title.push(Span::raw(" [quick review, not saved]"));
+        }
+        if let Some(pathspec) = &self.pathspec {
+            title.push(Span::raw(format!(" [path: {pathspec}]")));
+        }
+        let title_content = Paragraph::new(Line::from(title))
             .block(title_block)
             .style(Style::default().fg(Color::White));
 
         title_content.render(layout[0], buf);
 
-        self.render_loaded_diff_state(layout[1], buf);
+        self.render_loaded_diff_state(app, layout[1], buf);
     }
 
     /// Render the diff content based on the current diff state
-    fn render_loaded_diff_state(&self, area: Rect, buf: &mut Buffer) {
+    fn render_loaded_diff_state(&self, app: &App, area: Rect, buf: &mut Buffer) {
         match &self.diff_state {
             GitDiffLoadingState::Init => {
                 // Show loading state for diff
@@ -846,13 +2719,27 @@ impl ReviewDetailsView {
                 loading_text.render(area, buf);
             }
             GitDiffLoadingState::Loading => {
-                // Show loading state for diff
-                let loading_text = Paragraph::new("Loading diff...")
+                // Show loading state for diff, with the parsing progress once it is known
+                let text = match self.diff_progress {
+                    Some((files_done, files_total)) => {
+                        format!("Parsing diff: {files_done}/{files_total} files")
+                    }
+                    None => "Loading diff...".to_string(),
+                };
+                let loading_text = Paragraph::new(text)
                     .style(Style::default().fg(Color::Yellow))
                     .block(Block::default().borders(Borders::ALL));
                 loading_text.render(area, buf);
             }
-            GitDiffLoadingState::Loaded(_diff) => self.render_loaded_diff_state_loaded(area, buf),
+            GitDiffLoadingState::Loaded(_diff) if self.show_overview => {
+                self.render_overview(&app.theme, area, buf)
+            }
+            GitDiffLoadingState::Loaded(_diff) => {
+                self.render_loaded_diff_state_loaded(app, area, buf);
+                if self.show_churn_summary {
+                    self.render_churn_summary(area, buf);
+                }
+            }
             GitDiffLoadingState::Error(error) => {
                 // Show error state for diff
                 let error_text = Paragraph::new(format!("Diff error: {error}"))
@@ -864,70 +2751,235 @@ impl ReviewDetailsView {
     }
 
     /// Render the loaded diff content (file lists, file content) when the diff is fully loaded
-    fn render_loaded_diff_state_loaded(&self, area: Rect, buf: &mut Buffer) {
-        // Split content area into files lists (20%) and diff content (80%)
-        let content_layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
+    fn render_loaded_diff_state_loaded(&self, app: &App, area: Rect, buf: &mut Buffer) {
+        // Split content area into files lists (20%) and diff content (80%), the docked
+        // comments take a part of the diff content
+        let constraints: &[Constraint] = if self.docked_comments.is_some() {
+            &[
+                Constraint::Percentage(20), // Files lists
+                Constraint::Percentage(45), // Diff content
+                Constraint::Percentage(35), // Docked comments
+            ]
+        } else {
+            &[
                 Constraint::Percentage(20), // Files lists
                 Constraint::Percentage(80), // Diff content
-            ])
+            ]
+        };
+        let content_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(constraints)
             .split(area);
 
         // Render both file lists
-        self.render_file_lists(content_layout[0], buf);
+        self.render_file_lists(app, content_layout[0], buf);
 
         // Render diff content
-        self.render_diff_content(content_layout[1], buf);
-    }
-
-    /// Render both file lists (not viewed and viewed) side by side
-    fn render_file_lists(&self, area: Rect, buf: &mut Buffer) {
-        // Split the file lists area into two equal parts vertically
-        let lists_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(50), // Not viewed files
-                Constraint::Percentage(50), // Viewed files
-            ])
-            .split(area);
-
-        // Render not viewed files list
-        self.render_single_file_list(lists_layout[0], buf, FileListType::NotViewed, "Not Viewed");
+        self.render_diff_content(app, content_layout[1], buf);
 
-        // Render viewed files list
-        self.render_single_file_list(lists_layout[1], buf, FileListType::Viewed, "Viewed");
+        if let Some(docked_comments) = &self.docked_comments {
+            docked_comments.render(app, content_layout[2], buf);
+        }
     }
 
-    /// Render a single file list (either not viewed or viewed)
-    fn render_single_file_list(
+    /// Render the overview with one line per file: comment indicator, viewed status, path and
+    /// line stats
+    fn render_overview(&self, theme: &Theme, area: Rect, buf: &mut Buffer) {
+        let path_width = self
+            .diff
+            .files
+            .iter()
+            .map(|file| file.path.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let (mut total_added, mut total_removed) = (0, 0);
+        let items: Vec<ListItem> = self
+            .diff
+            .files
+            .iter()
+            .enumerate()
+            .map(|(index, diff_file)| {
+                let (added, removed) = diff_file.line_stats();
+                total_added += added;
+                total_removed += removed;
+
+                let is_selected = index == self.overview_selected_index;
+                let prefix = if is_selected {
+                    FILE_SELECTION_INDICATOR
+                } else {
+                    " "
+                };
+                let viewed = if self.viewed_files.contains(&diff_file.path) {
+                    OVERVIEW_VIEWED_INDICATOR
+                } else if self.is_file_skipped(&diff_file.path) {
+                    SKIPPED_FILE_INDICATOR
+                } else {
+                    " "
+                };
+                let content = format!(
+                    "{prefix}{} {viewed} {:<path_width$}  +{added} -{removed}",
+                    self.comment_indicator(diff_file),
+                    diff_file.path,
+                );
+                let style = if is_selected {
+                    Style::default().bg(Color::Blue).fg(Color::Black)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                ListItem::new(content).style(style)
+            })
+            .collect();
+
+        let title = format!(
+            " Overview [ACTIVE] ({} files, +{total_added} -{total_removed}) ",
+            self.diff.file_count()
+        );
+        let overview = List::new(items).block(
+            Block::bordered()
+                .title(title)
+                .border_type(theme.border_type(true))
+                .border_style(Style::default().fg(Color::Blue)),
+        );
+
+        overview.render(area, buf);
+    }
+
+    /// Render the total churn of the diff and its most changed files in a panel over the diff
+    fn render_churn_summary(&self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rectangle(60, 50, area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::bordered()
+            .title(" Churn Summary ")
+            .title_bottom(" Press s or Esc to close ")
+            .border_style(Style::default().fg(Color::Blue))
+            .style(Style::default().bg(Color::Black));
+
+        let lines = if self.diff.is_empty() {
+            vec![Line::from("No changes")]
+        } else {
+            let (added, removed) = self.diff.line_stats();
+            let mut lines = vec![
+                Line::from(vec![
+                    Span::raw(format!("{} files changed, ", self.diff.file_count())),
+                    Span::styled(format!("+{added}"), Style::default().fg(Color::Green)),
+                    Span::raw(" "),
+                    Span::styled(format!("-{removed}"), Style::default().fg(Color::Red)),
+                ]),
+                Line::from(""),
+                Line::styled("Most changed files:", Style::default().fg(Color::Gray)),
+            ];
+            let most_changed = self.diff.files_by_churn();
+            let most_changed = &most_changed[..most_changed.len().min(CHURN_SUMMARY_FILE_COUNT)];
+            let stats_width = most_changed
+                .iter()
+                .map(|(_, (added, removed))| format!("+{added} -{removed}").len())
+                .max()
+                .unwrap_or(0);
+            lines.extend(most_changed.iter().map(|(file, (added, removed))| {
+                Line::from(format!(
+                    "  {:<stats_width$}  {}",
+                    format!("+{added} -{removed}"),
+                    file.path
+                ))
+            }));
+            lines
+        };
+
+        Paragraph::new(lines)
+            .block(block)
+            .style(Style::default().fg(Color::White))
+            .render(popup_area, buf);
+    }
+
+    /// Render both file lists (not viewed and viewed) side by side
+    fn render_file_lists(&self, app: &App, area: Rect, buf: &mut Buffer) {
+        let viewed_list_collapsed = self.is_viewed_list_collapsed(&app.settings);
+
+        // Split the file lists area into two equal parts vertically, unless the viewed list is
+        // collapsed, in which case the not viewed list gets all but a single line
+        let constraints = if viewed_list_collapsed {
+            [
+                Constraint::Min(0),    // Not viewed files
+                Constraint::Length(1), // Collapsed viewed files badge
+            ]
+        } else {
+            [
+                Constraint::Percentage(50), // Not viewed files
+                Constraint::Percentage(50), // Viewed files
+            ]
+        };
+        let lists_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(area);
+
+        // Hint at the excluded files while they are not listed
+        let excluded_file_count = self.diff.excluded_files.len();
+        let not_viewed_title = if self.show_excluded_files || excluded_file_count == 0 {
+            "Not Viewed".to_string()
+        } else {
+            format!("Not Viewed ({excluded_file_count} excluded)")
+        };
+
+        // Render not viewed files list
+        self.render_single_file_list(
+            &app.theme,
+            lists_layout[0],
+            buf,
+            FileListType::NotViewed,
+            &not_viewed_title,
+        );
+
+        // Render viewed files list
+        if viewed_list_collapsed {
+            self.render_collapsed_file_list(lists_layout[1], buf, FileListType::Viewed, "Viewed");
+        } else {
+            self.render_single_file_list(
+                &app.theme,
+                lists_layout[1],
+                buf,
+                FileListType::Viewed,
+                "Viewed",
+            );
+        }
+    }
+
+    /// Render a file list collapsed into a single line with the number of files in it
+    fn render_collapsed_file_list(
         &self,
         area: Rect,
         buf: &mut Buffer,
         list_type: FileListType,
         title: &str,
     ) {
-        // Get the files for this list type
-        let files: Vec<&DiffFile> = match list_type {
-            FileListType::NotViewed => self
-                .diff
-                .files
-                .iter()
-                .filter(|file| !self.viewed_files.contains(&file.path))
-                .collect(),
-            FileListType::Viewed => self
-                .diff
-                .files
-                .iter()
-                .filter(|file| self.viewed_files.contains(&file.path))
-                .collect(),
-        };
+        let file_count = self.get_file_list(&list_type).len();
+        let badge = Paragraph::new(format!(" {title} ({file_count}) — press → to expand"))
+            .style(Style::default().fg(Color::Gray));
+        badge.render(area, buf);
+    }
+
+    /// Render a single file list (either not viewed or viewed)
+    fn render_single_file_list(
+        &self,
+        theme: &Theme,
+        area: Rect,
+        buf: &mut Buffer,
+        list_type: FileListType,
+        title: &str,
+    ) {
+        let files = self.get_file_list(&list_type);
+        // Width inside the borders
+        let width = usize::from(area.width.saturating_sub(2));
 
         // Create list items
         let files_lines: Vec<ListItem> = files
             .iter()
             .enumerate()
-            .map(|(index, diff_file)| self.render_file_line_for_list(index, diff_file, &list_type))
+            .map(|(index, diff_file)| {
+                self.render_file_line_for_list(index, diff_file, &list_type, width)
+            })
             .collect();
 
         // Determine if this list is active
@@ -948,6 +3000,7 @@ impl ReviewDetailsView {
             .block(
                 Block::bordered()
                     .title(list_title)
+                    .border_type(theme.border_type(is_active))
                     .border_style(border_color),
             )
             .style(Style::default().fg(Color::White));
@@ -960,14 +3013,24 @@ impl ReviewDetailsView {
         index: usize,
         diff_file: &DiffFile,
         list_type: &FileListType,
+        width: usize,
     ) -> ListItem<'_> {
         let is_selected = index == self.selected_file_index && self.active_file_list == *list_type;
         let is_files_mode = matches!(self.navigation_mode, NavigationMode::Files);
 
+        let is_unchanged = self.is_unchanged_file(diff_file);
+        let is_excluded = self
+            .diff
+            .excluded_files
+            .iter()
+            .any(|file| file.path == diff_file.path);
+
         let style = if is_selected && is_files_mode {
             Style::default().bg(Color::Blue).fg(Color::Black)
         } else if is_selected {
             Style::default().fg(Color::Yellow)
+        } else if is_unchanged || is_excluded {
+            Style::default().fg(Color::Gray)
         } else {
             Style::default().fg(Color::White)
         };
@@ -976,28 +3039,106 @@ impl ReviewDetailsView {
         } else {
             " "
         };
+        // Changed files are marked while the unchanged files are listed as well
+        let change_marker = match (self.show_unchanged_files, is_unchanged) {
+            (false, _) => "",
+            (true, false) => "~ ",
+            (true, true) => "  ",
+        };
 
-        let content = format!(
-            "{}{} {}",
-            prefix,
-            self.comment_indicator(diff_file),
-            diff_file.path.clone()
-        );
+        let skip_marker = if self.is_file_skipped(&diff_file.path) {
+            format!("{SKIPPED_FILE_INDICATOR} ")
+        } else {
+            String::new()
+        };
+
+        let indicator = self.comment_indicator(diff_file).to_string();
+        let prefix_width = prefix.chars().count()
+            + indicator.chars().count()
+            + 1
+            + change_marker.chars().count()
+            + skip_marker.chars().count();
+        let path = Self::truncate_path_middle(&diff_file.path, width.saturating_sub(prefix_width));
+
+        let content = Line::from(vec![
+            Span::raw(prefix),
+            Span::styled(
+                indicator,
+                comment_priority_style(self.comment_priority(diff_file)),
+            ),
+            Span::raw(format!(" {change_marker}{skip_marker}{path}")),
+        ]);
         ListItem::new(content).style(style)
     }
 
+    /// Highest priority of the unresolved comments of a diff file
+    fn comment_priority(&self, diff_file: &DiffFile) -> CommentPriority {
+        self.comment_paths(diff_file)
+            .filter_map(|path| self.comment_priorities_by_file.get(path))
+            .max()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Shorten a path that is wider than `max_width` in the middle, like `src/.../main_view.rs`,
+    /// so that the file name stays visible. As many leading directories are kept as fit. A file
+    /// name that doesn't fit on its own keeps its end.
+    fn truncate_path_middle(path: &str, max_width: usize) -> Cow<'_, str> {
+        if path.chars().count() <= max_width {
+            return Cow::Borrowed(path);
+        }
+
+        let file_name = path
+            .rsplit_once('/')
+            .map_or(path, |(_, file_name)| file_name);
+        let tail = format!("{PATH_ELLIPSIS}/{file_name}");
+        let tail_width = tail.chars().count();
+        if file_name == path || tail_width > max_width {
+            let file_name_width = file_name.chars().count();
+            let kept = max_width.saturating_sub(PATH_ELLIPSIS.len());
+            let end: String = file_name
+                .chars()
+                .skip(file_name_width.saturating_sub(kept))
+                .collect();
+            return Cow::Owned(format!("{PATH_ELLIPSIS}{end}"));
+        }
+
+        let leading_end = path
+            .match_indices('/')
+            .map(|(index, _)| index + 1)
+            .take_while(|&end| path[..end].chars().count() + tail_width <= max_width)
+            .last()
+            .unwrap_or(0);
+        Cow::Owned(format!("{}{tail}", &path[..leading_end]))
+    }
+
+    /// Paths the comments of a diff file are stored under: its path and, if the file was
+    /// renamed, its old path. The comments themselves are not moved to the new path.
+    fn comment_paths<'a>(&'a self, diff_file: &'a DiffFile) -> impl Iterator<Item = &'a str> {
+        std::iter::once(diff_file.path.as_str()).chain(self.diff.old_path(&diff_file.path))
+    }
+
     /// Get the comment indicator for a diff file based on its comment status
     ///
     /// Use different indicator for file comments and line comments and files that have both.
-    /// Files with only resolved comments show the resolved indicator.
+    /// Files with only resolved comments show the resolved indicator. Open comments on deleted
+    /// files have their own indicator, they are about code that is gone from the target.
     fn comment_indicator(&self, diff_file: &DiffFile) -> CommentIndicator {
+        let has_comment_in = |files: &[String]| {
+            self.comment_paths(diff_file)
+                .any(|path| files.iter().any(|file| file == path))
+        };
+
         // Check if file has any unresolved comments
-        if self
-            .files_with_file_and_or_line_comments
-            .contains(&diff_file.path)
+        if has_comment_in(&self.files_with_file_and_or_line_comments)
+            && self.diff.is_deleted(&diff_file.path)
         {
-            let has_line_comment = self.lines_with_comments.contains_key(&diff_file.path);
-            let has_file_comment = self.files_with_file_comments.contains(&diff_file.path);
+            CommentIndicator::DeletedFileComment
+        } else if has_comment_in(&self.files_with_file_and_or_line_comments) {
+            let has_line_comment = self
+                .comment_paths(diff_file)
+                .any(|path| self.lines_with_comments.contains_key(path));
+            let has_file_comment = has_comment_in(&self.files_with_file_comments);
 
             if has_file_comment && !has_line_comment {
                 CommentIndicator::FileComment
@@ -1006,10 +3147,7 @@ impl ReviewDetailsView {
             } else {
                 CommentIndicator::FileAndLineComment
             }
-        } else if self
-            .files_with_only_resolved_comments
-            .contains(&diff_file.path)
-        {
+        } else if has_comment_in(&self.files_with_only_resolved_comments) {
             // File has only resolved comments
             CommentIndicator::ResolvedComment
         } else {
@@ -1018,7 +3156,8 @@ impl ReviewDetailsView {
     }
 
     /// Render the diff content panel
-    fn render_diff_content(&self, area: Rect, buf: &mut Buffer) {
+    fn render_diff_content(&self, app: &App, area: Rect, buf: &mut Buffer) {
+        let theme = &app.theme;
         // Show empty state when no files are available
         if self.diff.is_empty() {
             let empty_text = Paragraph::new("No diff to display")
@@ -1034,9 +3173,7 @@ impl ReviewDetailsView {
             return;
         }
 
-        let content_text = if let Some(file) = self.get_selected_file() {
-            &file.content
-        } else {
+        let Some(file) = self.get_selected_file() else {
             // Show error when no files are available
             let error_text = Paragraph::new("Error: No files available")
                 .style(Style::default().fg(Color::Red))
@@ -1050,103 +3187,57 @@ impl ReviewDetailsView {
             return;
         };
 
-        // Split content into lines and apply scrolling with highlighting
-        let content_lines: Vec<&str> = content_text.lines().collect();
-        let content_height = area.height.saturating_sub(2) as usize; // Account for borders
-
-        // Calculate the visible lines based on scroll offset
-        let start_line = self.scroll_offset;
-        let end_line = (start_line + content_height).min(content_lines.len());
-        let visible_lines = if start_line < content_lines.len() {
-            &content_lines[start_line..end_line]
+        // In all files mode the diffs of all files of the active list follow each other,
+        // otherwise only the selected file is shown
+        let files = if self.all_files {
+            self.get_current_file_list()
         } else {
-            &[]
+            vec![file]
         };
-
-        // Create styled lines with highlighting for selected line
-        let styled_lines: Vec<Line> = visible_lines
-            .iter()
-            .enumerate()
-            .map(|(idx, line_text)| {
-                let absolute_line_idx = start_line + idx;
-                let is_selected_line = absolute_line_idx == self.selected_line_index;
-                let is_lines_mode = matches!(self.navigation_mode, NavigationMode::Lines);
-
-                // Check if this line has comments
-                let has_comments = self
-                    .get_selected_file()
-                    .map(|file| {
-                        self.lines_with_comments
-                            .get(&file.path)
-                            .map(|lines| lines.contains(&(absolute_line_idx as i64)))
-                            .unwrap_or(false)
-                    })
-                    .unwrap_or(false);
-
-                // Check if the line has only resolved comments
-                let has_only_resolved_comments = self
-                    .get_selected_file()
-                    .map(|file| {
-                        self.lines_with_only_resolved_comments
-                            .get(&file.path)
-                            .map(|lines| lines.contains(&(absolute_line_idx as i64)))
-                            .unwrap_or(false)
-                    })
-                    .unwrap_or(false);
-
-                // Add comment indicator based on comment status
-                let comment_prefix = if has_comments {
-                    LINE_COMMENT_INDICATOR
-                } else if has_only_resolved_comments {
-                    RESOLVED_COMMENT_INDICATOR
-                } else {
-                    " "
-                };
-                let display_text = format!("{comment_prefix} {line_text}");
-
-                if is_selected_line && is_lines_mode {
-                    // Highlight selected line in lines mode
-                    Line::from(Span::styled(
-                        display_text,
-                        Style::default()
-                            .fg(Color::Black)
-                            .bg(Color::White)
-                            .add_modifier(Modifier::BOLD),
-                    ))
-                } else {
-                    // Regular styling for other lines with diff colors
-                    let style = match line_text.chars().next() {
-                        Some('+') => Style::default().fg(Color::Green),
-                        Some('-') => Style::default().fg(Color::Red),
-                        Some('@') => Style::default()
-                            .fg(Color::Cyan)
-                            .add_modifier(Modifier::BOLD),
-                        _ => Style::default().fg(Color::White),
-                    };
-                    Line::from(Span::styled(display_text, style))
-                }
-            })
-            .collect();
+        // Account for borders
+        let content_area = area.inner(Margin::new(1, 1));
+        self.render_metrics.set(RenderMetrics { content_area });
+        let styled_lines = self.cached_diff_lines(app, area, file, &files);
 
         // Show file info and navigation mode in title
-        let total_lines = content_lines.len();
-        let current_file_name = self
-            .get_selected_file()
-            .map(|f| f.path.as_str())
-            .unwrap_or("Unknown");
+        let total_lines = file.content().lines().count();
+        let current_file_name = if self.diff.is_deleted(&file.path) {
+            format!("{} [DELETED]", file.path)
+        } else {
+            file.path.clone()
+        };
 
+        let filter = if self.additions_only {
+            " [ADDITIONS ONLY]"
+        } else {
+            ""
+        };
+        let filter = if self.all_files {
+            format!(
+                "{filter} [ALL FILES {}/{}]",
+                self.selected_file_index + 1,
+                files.len()
+            )
+        } else {
+            filter.to_string()
+        };
         let title_text = match self.navigation_mode {
-            NavigationMode::Files => format!(" {current_file_name} ({total_lines} lines) "),
+            NavigationMode::Files => {
+                format!(" {current_file_name}{filter} ({total_lines} lines) ")
+            }
             NavigationMode::Lines => {
                 let line_num = self.selected_line_index + 1;
-                format!(" {current_file_name} [ACTIVE] (line {line_num}/{total_lines}) ")
+                format!(" {current_file_name} [ACTIVE]{filter} (line {line_num}/{total_lines}) ")
             }
         };
 
-        let content = Paragraph::new(styled_lines).block(
+        let content = Paragraph::new(styled_lines.to_vec()).block(
             Block::default()
                 .title(title_text)
                 .borders(Borders::ALL)
+                .border_type(
+                    theme.border_type(matches!(self.navigation_mode, NavigationMode::Lines)),
+                )
                 .border_style(Style::default().fg(
                     if matches!(self.navigation_mode, NavigationMode::Lines) {
                         Color::Blue
@@ -1158,6 +3249,534 @@ impl ReviewDetailsView {
 
         content.render(area, buf);
     }
+
+    /// Visible diff lines of the last render when nothing they are built from changed since,
+    /// otherwise the newly built lines
+    fn cached_diff_lines(
+        &self,
+        app: &App,
+        area: Rect,
+        file: &DiffFile,
+        files: &[&DiffFile],
+    ) -> Arc<[Line<'static>]> {
+        let key = DiffRenderKey {
+            selected_file_path: file.path.clone(),
+            active_file_list: self.active_file_list.clone(),
+            scroll_offset: self.scroll_offset,
+            selected_line_index: self.selected_line_index,
+            lines_mode: matches!(self.navigation_mode, NavigationMode::Lines),
+            additions_only: self.additions_only,
+            all_files: self.all_files,
+            area,
+            comments_version: self.comments_version,
+            content_version: self.content_version,
+            relative_line_numbers: app.settings.relative_line_numbers,
+            hide_diff_prefixes: app.settings.hide_diff_prefixes,
+            show_hunk_blame: app.settings.show_hunk_blame,
+            show_whitespace: app.settings.show_whitespace,
+            inline_changes: app.settings.inline_changes,
+            tab_width: app.settings.tab_width,
+            density: app.settings.density,
+        };
+        let mut cache = self.diff_lines_cache.borrow_mut();
+        if let Some((cached_key, lines)) = cache.as_ref()
+            && *cached_key == key
+        {
+            return Arc::clone(lines);
+        }
+        let lines: Arc<[Line<'static>]> = self.build_diff_lines(app, area, file, files).into();
+        *cache = Some((key, Arc::clone(&lines)));
+        lines
+    }
+
+    /// Build the visible diff lines with the selected line highlighted
+    fn build_diff_lines(
+        &self,
+        app: &App,
+        area: Rect,
+        file: &DiffFile,
+        files: &[&DiffFile],
+    ) -> Vec<Line<'static>> {
+        let theme = &app.theme;
+        // Split content into rows (file headers, lines, expanded context, hidden lines markers)
+        // and apply scrolling with highlighting. In all files mode the rows of all files of the
+        // active list follow each other, otherwise only the selected file is shown.
+        let mut rows = vec![];
+        // Paired lines of every file by line index, see `pair_changed_lines`
+        let mut inline_changes = vec![];
+        // Scroll line of the first diff line of every file, see `file_line_offset`
+        let mut line_offsets = vec![];
+        let mut next_line_offset = usize::from(self.all_files);
+        for (file_position, diff_file) in files.iter().enumerate() {
+            if self.all_files {
+                rows.push((file_position, DiffRow::FileHeader(&diff_file.path)));
+            }
+            line_offsets.push(next_line_offset);
+            next_line_offset += diff_file.content().lines().count() + 1;
+            let mut file_rows = self.diff_rows(diff_file);
+            let mut file_inline_changes = HashMap::new();
+            if app.settings.inline_changes {
+                (file_rows, file_inline_changes) = Self::pair_changed_lines(file_rows);
+            }
+            inline_changes.push(file_inline_changes);
+            rows.extend(file_rows.into_iter().map(|row| (file_position, row)));
+        }
+        let row_scroll_line = |(file_position, row): &(usize, DiffRow)| match row {
+            DiffRow::FileHeader(_) => Some(line_offsets[*file_position] - 1),
+            DiffRow::Line(line_index, _) => Some(line_offsets[*file_position] + line_index),
+            DiffRow::Context(_) | DiffRow::Hidden(_) => None,
+        };
+        let content_height = area.height.saturating_sub(2) as usize; // Account for borders
+
+        // Start at the row of the scrolled to line. Expanded context rows can push the selected
+        // line below the viewport, so scroll further in that case.
+        let selected_scroll_line = self.selected_scroll_line();
+        let selected_row = rows.iter().position(|row| {
+            matches!(row.1, DiffRow::Line(..)) && row_scroll_line(row) == Some(selected_scroll_line)
+        });
+        // The scrolled to line can be filtered out, start at the next shown line then
+        let mut start_row = if self.scroll_offset == 0 {
+            0
+        } else {
+            rows.iter()
+                .position(|row| row_scroll_line(row).is_some_and(|line| line >= self.scroll_offset))
+                .unwrap_or(rows.len())
+        };
+        if let Some(selected_row) = selected_row
+            && content_height > 0
+            && selected_row >= start_row + content_height
+        {
+            start_row = selected_row + 1 - content_height;
+        }
+        let end_row = (start_row + content_height).min(rows.len());
+        let visible_rows = rows.get(start_row..end_row).unwrap_or_default();
+
+        // Line number gutter, wide enough for absolute and relative numbers so that toggling
+        // does not shift the content
+        let line_numbers: Vec<Vec<Option<usize>>> =
+            files.iter().map(|file| file.new_line_numbers()).collect();
+        let gutter_width = line_numbers
+            .iter()
+            .flatten()
+            .flatten()
+            .max()
+            .copied()
+            .unwrap_or(0)
+            .max(self.scroll_line_count())
+            .to_string()
+            .len();
+        // The compact density drops the space between the line numbers and the lines
+        let gutter_padding = match app.settings.density {
+            Density::Normal => " ",
+            Density::Compact => "",
+        };
+        let empty_gutter = " ".repeat(gutter_width + gutter_padding.len());
+        // Expanded context lines have no origin prefix, pad them to align with the diff lines
+        let context_prefix = if app.settings.hide_diff_prefixes {
+            ""
+        } else {
+            " "
+        };
+
+        // Create styled lines with highlighting for selected line
+        let mut styled_lines: Vec<Line> = visible_rows
+            .iter()
+            .map(|(file_position, row)| match row {
+                DiffRow::FileHeader(path) => {
+                    let header = if self.diff.is_deleted(path) {
+                        format!("── {path} [DELETED] ")
+                    } else {
+                        format!("── {path} ")
+                    };
+                    let fill = (area.width.saturating_sub(2) as usize)
+                        .saturating_sub(header.chars().count());
+                    Line::from(Span::styled(
+                        format!("{header}{}", "─".repeat(fill)),
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                }
+                DiffRow::Line(line_index, line_text) => {
+                    let line_number = self
+                        .gutter_line_number(
+                            &app.settings,
+                            &line_numbers[*file_position],
+                            line_offsets[*file_position],
+                            *line_index,
+                        )
+                        .map(|number| number.to_string())
+                        .unwrap_or_default();
+                    let gutter = format!("{line_number:>gutter_width$}{gutter_padding}");
+                    self.render_diff_line(
+                        theme,
+                        &app.settings,
+                        files[*file_position],
+                        *line_index,
+                        line_text,
+                        gutter,
+                        inline_changes[*file_position].get(line_index),
+                    )
+                }
+                DiffRow::Context(line_text) => Line::from(Span::styled(
+                    format!(
+                        "{empty_gutter}  {context_prefix}{}",
+                        Self::expand_tabs(line_text, app.settings.tab_width)
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                )),
+                DiffRow::Hidden(count) => Line::from(Span::styled(
+                    format!(
+                        "{empty_gutter}  {HIDDEN_LINES_INDICATOR} {count} hidden lines (e/E to expand)"
+                    ),
+                    Style::default().fg(Color::Cyan),
+                )),
+            })
+            .collect();
+
+        // Added or deleted empty files have no diff lines at all
+        if !self.all_files && file.content().is_empty() {
+            styled_lines.push(Line::styled(
+                "Empty file, there are no lines to show",
+                Style::default().fg(Color::Gray),
+            ));
+        }
+
+        styled_lines
+    }
+
+    /// Number shown in the gutter of a diff line: the line in the new file or, with relative
+    /// line numbers in lines mode, the distance to the selected line. `line_offset` is the
+    /// scroll line of the first line of the file (see `file_line_offset`).
+    fn gutter_line_number(
+        &self,
+        settings: &Settings,
+        line_numbers: &[Option<usize>],
+        line_offset: usize,
+        line_index: usize,
+    ) -> Option<usize> {
+        if settings.relative_line_numbers && matches!(self.navigation_mode, NavigationMode::Lines) {
+            return Some((line_offset + line_index).abs_diff(self.selected_scroll_line()));
+        }
+        line_numbers.get(line_index).copied().flatten()
+    }
+
+    /// Render a line of the diff content with its line number gutter, comment indicator and
+    /// diff colors. The changed part of a paired line is highlighted and an added one gets a
+    /// connector to the removed line above.
+    #[allow(clippy::too_many_arguments)]
+    fn render_diff_line(
+        &self,
+        theme: &Theme,
+        settings: &Settings,
+        file: &DiffFile,
+        line_index: usize,
+        line_text: &str,
+        gutter: String,
+        inline_change: Option<&InlineChange>,
+    ) -> Line<'static> {
+        let is_selected_line = line_index == self.selected_line_index
+            && self
+                .get_selected_file()
+                .is_some_and(|selected_file| selected_file.path == file.path);
+        let is_lines_mode = matches!(self.navigation_mode, NavigationMode::Lines);
+
+        // Check if this line has comments
+        let has_comments = self.comment_paths(file).any(|path| {
+            self.lines_with_comments
+                .get(path)
+                .is_some_and(|lines| lines.contains(&(line_index as i64)))
+        });
+
+        // Check if the line has only resolved comments
+        let has_only_resolved_comments = self.comment_paths(file).any(|path| {
+            self.lines_with_only_resolved_comments
+                .get(path)
+                .is_some_and(|lines| lines.contains(&(line_index as i64)))
+        });
+
+        // Add comment indicator based on comment status
+        let comment_prefix = if has_comments {
+            LINE_COMMENT_INDICATOR
+        } else if has_only_resolved_comments {
+            RESOLVED_COMMENT_INDICATOR
+        } else {
+            " "
+        };
+        let separator = match theme.diff_line_separator(is_selected_line && is_lines_mode) {
+            " " if inline_change.is_some_and(|change| change.is_added) => INLINE_CHANGE_CONNECTOR,
+            separator => separator,
+        };
+        let display_segments = Self::display_line_segments(
+            line_text,
+            settings,
+            inline_change.map(|change| &change.changed),
+        );
+        let display_spans = |style: Style| {
+            let mut spans = vec![Span::styled(format!("{comment_prefix}{separator}"), style)];
+            spans.extend(display_segments.iter().map(|segment| {
+                let mut segment_style = style;
+                if segment.is_whitespace {
+                    segment_style = segment_style.add_modifier(Modifier::DIM);
+                }
+                if segment.is_changed {
+                    segment_style = segment_style.add_modifier(Modifier::REVERSED);
+                }
+                Span::styled(segment.text.clone(), segment_style)
+            }));
+            spans
+        };
+        // Who last touched the lines of the hunk, next to its header
+        let hunk_blame = settings
+            .show_hunk_blame
+            .then(|| self.hunk_blames.get(&file.path)?.get(&line_index))
+            .flatten()
+            .map(|summary| format!("  {summary}"))
+            .unwrap_or_default();
+
+        if is_selected_line && is_lines_mode {
+            // Highlight selected line in lines mode
+            let style = Style::default()
+                .fg(Color::Black)
+                .bg(Color::White)
+                .add_modifier(Modifier::BOLD);
+            let mut spans = vec![Span::styled(gutter, style)];
+            spans.extend(display_spans(style));
+            spans.push(Span::styled(hunk_blame, style));
+            Line::from(spans)
+        } else {
+            // Regular styling for other lines with diff colors
+            let style = match line_text.chars().next() {
+                Some('+') => Style::default().fg(Color::Green),
+                Some('-') => Style::default().fg(Color::Red),
+                Some('@') => Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+                Some(DiffLine::NO_NEWLINE_MARKER) => {
+                    Style::default().fg(Color::Gray).add_modifier(Modifier::DIM)
+                }
+                _ => Style::default().fg(Color::White),
+            };
+            let mut spans = vec![Span::styled(gutter, Style::default().fg(Color::DarkGray))];
+            spans.extend(display_spans(style));
+            spans.push(Span::styled(
+                hunk_blame,
+                Style::default().fg(Color::DarkGray),
+            ));
+            Line::from(spans)
+        }
+    }
+
+    /// Text of a diff line as displayed. The raw line (used for the colors and copying) keeps
+    /// its origin prefix, hunk headers and metadata lines are never stripped.
+    fn display_line_text(line_text: &str, hide_prefix: bool) -> &str {
+        if hide_prefix {
+            line_text.strip_prefix(['+', '-', ' ']).unwrap_or(line_text)
+        } else {
+            line_text
+        }
+    }
+
+    /// Segments of a diff line as displayed, flagged when they are whitespace markers or in the
+    /// `changed` byte range of the raw line. Tabs are expanded to the next tab stop; with
+    /// `show_whitespace` they start with `→` and trailing spaces are shown as `·` in the content
+    /// of the diff lines. The raw line is not changed, so comments and copying are not affected.
+    fn display_line_segments(
+        line_text: &str,
+        settings: &Settings,
+        changed: Option<&Range<usize>>,
+    ) -> Vec<DisplaySegment> {
+        let display_text = Self::display_line_text(line_text, settings.hide_diff_prefixes);
+        let is_diff_line = line_text.starts_with(['+', '-', ' ']);
+        let (prefix, content) = if is_diff_line {
+            display_text.split_at(display_text.len() + 1 - line_text.len())
+        } else {
+            ("", display_text)
+        };
+        let mark_whitespace = settings.show_whitespace && is_diff_line;
+        let trailing_spaces_start = if mark_whitespace {
+            content.trim_end_matches(' ').len()
+        } else {
+            content.len()
+        };
+
+        // Offset of the content in the raw line, to match the changed range
+        let content_offset = line_text.len() - content.len();
+
+        let mut segments = vec![DisplaySegment {
+            text: prefix.to_string(),
+            is_whitespace: false,
+            is_changed: false,
+        }];
+        let mut column = 0;
+        for (index, char) in content.char_indices() {
+            let (text, is_whitespace) = match char {
+                '\t' => {
+                    let padding = " ".repeat(Self::tab_columns(column, settings.tab_width) - 1);
+                    let marker = if mark_whitespace { '→' } else { ' ' };
+                    (format!("{marker}{padding}"), mark_whitespace)
+                }
+                ' ' if index >= trailing_spaces_start => ("·".to_string(), true),
+                _ => (char.to_string(), false),
+            };
+            let is_changed =
+                changed.is_some_and(|changed| changed.contains(&(content_offset + index)));
+            column += text.chars().count();
+            match segments.last_mut() {
+                Some(last)
+                    if last.is_whitespace == is_whitespace && last.is_changed == is_changed =>
+                {
+                    last.text.push_str(&text);
+                }
+                _ => segments.push(DisplaySegment {
+                    text,
+                    is_whitespace,
+                    is_changed,
+                }),
+            }
+        }
+        segments
+    }
+
+    /// Pair the removed lines of a change with the added lines that replace them, in the inline
+    /// changes mode: each removed line is followed by its added line, with the changed parts of
+    /// both. Changes with different numbers of removed and added lines or with lines that have
+    /// nothing in common can't be paired unambiguously, they keep their rows.
+    fn pair_changed_lines(
+        rows: Vec<DiffRow<'_>>,
+    ) -> (Vec<DiffRow<'_>>, HashMap<usize, InlineChange>) {
+        let line_count = |rows: &[DiffRow], origin: char| {
+            rows.iter()
+                .take_while(|row| matches!(row, DiffRow::Line(_, text) if text.starts_with(origin)))
+                .count()
+        };
+        let mut paired_rows = Vec::with_capacity(rows.len());
+        let mut inline_changes = HashMap::new();
+        let mut index = 0;
+        while index < rows.len() {
+            let removed_count = line_count(&rows[index..], '-');
+            if removed_count == 0 {
+                paired_rows.push(rows[index].clone());
+                index += 1;
+                continue;
+            }
+            let added_count = line_count(&rows[index + removed_count..], '+');
+            let (removed, added) =
+                rows[index..index + removed_count + added_count].split_at(removed_count);
+            let pairs: Option<Vec<_>> = (removed_count == added_count)
+                .then(|| {
+                    removed
+                        .iter()
+                        .zip(added)
+                        .map(|pair| match pair {
+                            (DiffRow::Line(_, old), DiffRow::Line(_, new)) => {
+                                let (old_changed, new_changed) =
+                                    word_diff::changed_ranges(&old[1..], &new[1..])?;
+                                Some((pair, old_changed, new_changed))
+                            }
+                            _ => None,
+                        })
+                        .collect()
+                })
+                .flatten();
+            match pairs {
+                Some(pairs) => {
+                    for ((old_row, new_row), old_changed, new_changed) in pairs {
+                        for (row, changed, is_added) in
+                            [(old_row, old_changed, false), (new_row, new_changed, true)]
+                        {
+                            if let DiffRow::Line(line_index, _) = row {
+                                // Shift the ranges past the origin prefix of the raw lines
+                                inline_changes.insert(
+                                    *line_index,
+                                    InlineChange {
+                                        changed: changed.start + 1..changed.end + 1,
+                                        is_added,
+                                    },
+                                );
+                            }
+                            paired_rows.push(row.clone());
+                        }
+                    }
+                }
+                None => {
+                    paired_rows.extend(removed.iter().chain(added).cloned());
+                }
+            }
+            index += removed_count + added_count;
+        }
+        (paired_rows, inline_changes)
+    }
+
+    /// Number of columns a tab at the given column takes up to the next tab stop
+    fn tab_columns(column: usize, tab_width: usize) -> usize {
+        let tab_width = tab_width.max(1);
+        tab_width - column % tab_width
+    }
+
+    /// Expand the tabs of a text to spaces up to the next tab stops, for display only
+    fn expand_tabs(text: &str, tab_width: usize) -> String {
+        let mut expanded = String::with_capacity(text.len());
+        let mut column = 0;
+        for char in text.chars() {
+            if char == '\t' {
+                let columns = Self::tab_columns(column, tab_width);
+                expanded.push_str(&" ".repeat(columns));
+                column += columns;
+            } else {
+                expanded.push(char);
+                column += 1;
+            }
+        }
+        expanded
+    }
+
+    /// Rows of the diff content of a file: the diff lines, context expanded around the hunks and
+    /// markers for the unchanged lines that are still hidden before a hunk
+    fn diff_rows<'a>(&'a self, file: &'a DiffFile) -> Vec<DiffRow<'a>> {
+        if self.additions_only {
+            // Expanded context and hidden lines markers are left out like the unchanged lines
+            return file
+                .content()
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| self.is_line_visible(file, line))
+                .map(|(line_index, line)| DiffRow::Line(line_index, line))
+                .collect();
+        }
+
+        let hunks = file.hunk_positions();
+        let mut next_hunk = hunks.iter().enumerate().peekable();
+        let mut rows = vec![];
+
+        for (line_index, line) in file.content().lines().enumerate() {
+            if let Some((position, hunk)) =
+                next_hunk.next_if(|(_, hunk)| hunk.line_index == line_index)
+            {
+                if let Some(previous) = position.checked_sub(1)
+                    && let Some(expansion) = self.context_expansion(&file.path, &hunks[previous])
+                {
+                    rows.extend(expansion.below.iter().map(|line| DiffRow::Context(line)));
+                }
+                let (_, hidden_count) = self.hidden_lines_before_hunk(&file.path, &hunks, position);
+                if hidden_count > 0 {
+                    rows.push(DiffRow::Hidden(hidden_count));
+                }
+                if let Some(expansion) = self.context_expansion(&file.path, hunk) {
+                    rows.extend(expansion.above.iter().map(|line| DiffRow::Context(line)));
+                }
+            }
+            rows.push(DiffRow::Line(line_index, line));
+        }
+
+        if let Some(last) = hunks.last()
+            && let Some(expansion) = self.context_expansion(&file.path, last)
+        {
+            rows.extend(expansion.below.iter().map(|line| DiffRow::Context(line)));
+        }
+
+        rows
+    }
 }
 
 #[cfg(test)]
@@ -1171,9 +3790,11 @@ mod tests {
         app::App,
         database::Database,
         event::{Event, EventHandler},
-        models::{Comment, Diff, DiffFile, Review},
+        exclude_patterns::ExcludePatterns,
+        models::{Comment, CommentStatus, Diff, DiffFile, Review},
         services::{CommentsLoadParams, CommentsLoadingState},
-        test_utils::render_app_to_terminal_backend,
+        test_utils::{fixed_time, render_app_to_terminal_backend, render_view_to_terminal_backend},
+        time_provider::MockTimeProvider,
     };
 
     async fn create_test_app() -> App {
@@ -1188,6 +3809,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         }
     }
 
@@ -1243,7 +3867,7 @@ mod tests {
         let view = ReviewDetailsView::new(review);
 
         let keybindings = view.get_keybindings();
-        assert_eq!(keybindings.len(), 10);
+        assert_eq!(keybindings.len(), 36);
         assert_eq!(keybindings[0].key, "↑/k");
         assert_eq!(keybindings[0].description, "Scroll up");
         assert_eq!(keybindings[1].key, "↓/j");
@@ -1253,27 +3877,118 @@ mod tests {
         assert_eq!(keybindings[3].key, "→/l");
         assert_eq!(keybindings[3].description, "Switch to viewed files");
         assert_eq!(keybindings[4].key, "Space");
-        assert_eq!(keybindings[4].description, "Toggle file view status");
-        assert_eq!(keybindings[5].key, "Enter");
-        assert_eq!(keybindings[5].description, "Toggle navigation mode");
-        assert_eq!(keybindings[6].key, "Esc");
-        assert_eq!(keybindings[6].description, "Go back / Switch to Files mode");
-        assert_eq!(keybindings[7].key, "c");
-        assert_eq!(keybindings[7].description, "Open comments");
-        assert_eq!(keybindings[8].key, "r");
-        assert_eq!(keybindings[8].description, "Refresh review SHAs");
-        assert_eq!(keybindings[9].key, "?");
-        assert_eq!(keybindings[9].description, "Help");
-    }
-
-    #[tokio::test]
-    async fn test_review_details_view_line_navigation_keys() {
-        let review = Review::builder()
-            .base_sha_str("abc123")
-            .target_sha_str("def456")
-            .build();
-        let mut view = ReviewDetailsView::new(review);
-        let mut app = create_test_app().await;
+        assert_eq!(
+            keybindings[4].description,
+            "Toggle file view status (also from its lines)"
+        );
+        assert_eq!(keybindings[5].key, "X");
+        assert_eq!(
+            keybindings[5].description,
+            "Toggle skipping file without reviewing it"
+        );
+        assert_eq!(keybindings[6].key, "Enter");
+        assert_eq!(keybindings[6].description, "Toggle navigation mode");
+        assert_eq!(keybindings[7].key, "Tab / Shift+Tab");
+        assert_eq!(
+            keybindings[7].description,
+            "Cycle not viewed, viewed files and content"
+        );
+        assert_eq!(keybindings[8].key, "Esc");
+        assert_eq!(keybindings[8].description, "Go back / Switch to Files mode");
+        assert_eq!(keybindings[9].key, "c");
+        assert_eq!(keybindings[9].description, "Open comments");
+        assert_eq!(keybindings[10].key, "R");
+        assert_eq!(
+            keybindings[10].description,
+            "Toggle resolved comments of the line"
+        );
+        assert_eq!(keybindings[11].key, "r");
+        assert_eq!(keybindings[11].description, "Refresh review SHAs");
+        assert_eq!(keybindings[12].key, "v");
+        assert_eq!(
+            keybindings[12].description,
+            "Toggle collapsing viewed files"
+        );
+        assert_eq!(keybindings[13].key, "n");
+        assert_eq!(keybindings[13].description, "Toggle relative line numbers");
+        assert_eq!(keybindings[14].key, "z");
+        assert_eq!(
+            keybindings[14].description,
+            "Toggle centering the selected line"
+        );
+        assert_eq!(keybindings[15].key, "p");
+        assert_eq!(keybindings[15].description, "Toggle diff +/- prefixes");
+        assert_eq!(keybindings[16].key, "+");
+        assert_eq!(
+            keybindings[16].description,
+            "Toggle showing only added lines"
+        );
+        assert_eq!(keybindings[17].key, "b");
+        assert_eq!(keybindings[17].description, "Toggle hunk blame");
+        assert_eq!(keybindings[18].key, "w");
+        assert_eq!(keybindings[18].description, "Toggle showing whitespace");
+        assert_eq!(keybindings[19].key, "i");
+        assert_eq!(
+            keybindings[19].description,
+            "Toggle showing changed lines inline, old above new"
+        );
+        assert_eq!(keybindings[20].key, "S");
+        assert_eq!(keybindings[20].description, "Toggle short / full SHAs");
+        assert_eq!(keybindings[21].key, "u");
+        assert_eq!(
+            keybindings[21].description,
+            "Toggle showing all files in one diff"
+        );
+        assert_eq!(keybindings[22].key, "t");
+        assert_eq!(
+            keybindings[22].description,
+            "Toggle listing unchanged files"
+        );
+        assert_eq!(keybindings[23].key, "x");
+        assert_eq!(keybindings[23].description, "Toggle listing excluded files");
+        assert_eq!(keybindings[24].key, "o");
+        assert_eq!(keybindings[24].description, "Toggle files overview");
+        assert_eq!(keybindings[25].key, "f");
+        assert_eq!(
+            keybindings[25].description,
+            "Go to the first not viewed file"
+        );
+        assert_eq!(keybindings[26].key, "} / {");
+        assert_eq!(
+            keybindings[26].description,
+            "Next / previous most changed file"
+        );
+        assert_eq!(keybindings[27].key, "s");
+        assert_eq!(keybindings[27].description, "Toggle churn summary");
+        assert_eq!(keybindings[28].key, "d");
+        assert_eq!(keybindings[28].description, "Open file in difftool");
+        assert_eq!(keybindings[29].key, "D");
+        assert_eq!(keybindings[29].description, "Copy git diff command");
+        assert_eq!(keybindings[30].key, "y");
+        assert_eq!(keybindings[30].description, "Copy compare URL");
+        assert_eq!(keybindings[31].key, "Y");
+        assert_eq!(keybindings[31].description, "Copy hunk of selected line");
+        assert_eq!(keybindings[32].key, "e / E");
+        assert_eq!(
+            keybindings[32].description,
+            "Expand context above / below hunk"
+        );
+        assert_eq!(keybindings[33].key, "a");
+        assert_eq!(keybindings[33].description, "Show activity log");
+        assert_eq!(keybindings[34].key, "C");
+        assert_eq!(keybindings[34].description, "Show review checklist");
+        assert_eq!(keybindings[35].key, "?");
+        assert_eq!(keybindings[35].description, "Help");
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_line_navigation_keys() {
+        let review = Review::builder()
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
+        let mut view = ReviewDetailsView::new(review);
+        let mut app = create_test_app().await;
         view.navigation_mode = NavigationMode::Lines;
 
         // Initial current line should be 0
@@ -1395,6 +4110,48 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_review_details_view_handles_activity_key() {
+        let review = Review::builder().build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        let mut app = create_test_app().await;
+
+        let key_event = KeyEvent::new(
+            KeyCode::Char('a'),
+            ratatui::crossterm::event::KeyModifiers::NONE,
+        );
+        view.handle_key_events(&mut app, &key_event).unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ActivityOpen { review_id }) => {
+                assert_eq!(review_id.as_ref(), review.id);
+            }
+            _ => panic!("Expected ActivityOpen event"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_handles_checklist_key() {
+        let review = Review::builder().build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        let mut app = create_test_app().await;
+
+        let key_event = KeyEvent::new(
+            KeyCode::Char('C'),
+            ratatui::crossterm::event::KeyModifiers::NONE,
+        );
+        view.handle_key_events(&mut app, &key_event).unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ChecklistOpen { review_id }) => {
+                assert_eq!(review_id.as_ref(), review.id);
+            }
+            _ => panic!("Expected ChecklistOpen event"),
+        }
+    }
+
     #[tokio::test]
     async fn test_review_details_view_handles_review_loading_state_loaded_event() {
         let mut view = ReviewDetailsView::new_loading();
@@ -1521,6 +4278,155 @@ mod tests {
         assert_eq!(view.selected_line_index, 1);
     }
 
+    fn create_long_file_view(scroll_mode: ScrollMode) -> ReviewDetailsView {
+        let content: String = (1..=100).map(|line| format!("+line {line}\n")).collect();
+        let mut view =
+            ReviewDetailsView::new(Review::builder().build()).with_scroll_mode(scroll_mode);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(
+            Diff::from_files(vec![DiffFile::from_content("long.txt", &content)]),
+        )));
+        view.navigation_mode = NavigationMode::Lines;
+        view
+    }
+
+    #[test]
+    fn test_update_scroll_centered() {
+        let mut view = create_long_file_view(ScrollMode::Centered);
+
+        // In the middle of the file the selected line stays centered
+        view.selected_line_index = 50;
+        view.update_scroll_to_follow_selected_line();
+        assert_eq!(view.scroll_offset, 50 - DEFAULT_CONTENT_HEIGHT / 2);
+        view.go_down();
+        assert_eq!(view.scroll_offset, 51 - DEFAULT_CONTENT_HEIGHT / 2);
+
+        // Near the top the offset is clamped to the start of the file
+        view.selected_line_index = 3;
+        view.update_scroll_to_follow_selected_line();
+        assert_eq!(view.scroll_offset, 0);
+
+        // Near the bottom the offset is clamped to the end of the file
+        view.selected_line_index = 98;
+        view.update_scroll_to_follow_selected_line();
+        assert_eq!(view.scroll_offset, 100 - DEFAULT_CONTENT_HEIGHT);
+    }
+
+    #[test]
+    fn test_update_scroll_edge_follow() {
+        let mut view = create_long_file_view(ScrollMode::EdgeFollow);
+
+        view.selected_line_index = 50;
+        view.update_scroll_to_follow_selected_line();
+        assert_eq!(view.scroll_offset, 50 - (DEFAULT_CONTENT_HEIGHT - 1));
+
+        // Moving within the viewport does not scroll
+        view.go_up();
+        assert_eq!(view.scroll_offset, 50 - (DEFAULT_CONTENT_HEIGHT - 1));
+    }
+
+    #[test]
+    fn test_update_scroll_uses_rendered_content_height() {
+        let mut view = create_long_file_view(ScrollMode::Centered);
+        view.render_metrics.set(RenderMetrics {
+            content_area: Rect::new(0, 0, 80, 30),
+        });
+
+        view.selected_line_index = 50;
+        view.update_scroll_to_follow_selected_line();
+        assert_eq!(view.scroll_offset, 50 - 15);
+
+        view.scroll_mode = ScrollMode::EdgeFollow;
+        view.selected_line_index = 98;
+        view.update_scroll_to_follow_selected_line();
+        assert_eq!(view.scroll_offset, 98 - 29);
+        // The whole viewport of 30 lines can be used without scrolling
+        for _ in 0..29 {
+            view.go_up();
+        }
+        assert_eq!(view.selected_line_index, 69);
+        assert_eq!(view.scroll_offset, 98 - 29);
+    }
+
+    #[tokio::test]
+    async fn test_render_updates_render_metrics() {
+        let view = create_long_file_view(ScrollMode::Centered);
+        assert_eq!(view.content_height(), DEFAULT_CONTENT_HEIGHT);
+        let app = create_test_app().await;
+
+        render_view_to_terminal_backend(&app, |app, area, buf| view.render(app, area, buf));
+
+        let content_area = view.render_metrics.get().content_area;
+        assert!(content_area.height > 0);
+        assert_eq!(view.content_height(), usize::from(content_area.height));
+    }
+
+    #[tokio::test]
+    async fn test_render_reuses_cached_diff_lines() {
+        let mut view = create_long_file_view(ScrollMode::EdgeFollow);
+        let review_id = view.review.as_ref().unwrap().id.clone();
+        let app = create_test_app().await;
+        let cached_lines = |view: &ReviewDetailsView| {
+            render_view_to_terminal_backend(&app, |app, area, buf| view.render(app, area, buf));
+            let cache = view.diff_lines_cache.borrow();
+            Arc::clone(&cache.as_ref().unwrap().1)
+        };
+
+        // Rendering the same state again reuses the lines
+        let lines = cached_lines(&view);
+        assert!(Arc::ptr_eq(&lines, &cached_lines(&view)));
+
+        // Moving the selection rebuilds them
+        view.go_down();
+        let moved_lines = cached_lines(&view);
+        assert!(!Arc::ptr_eq(&lines, &moved_lines));
+
+        // Reloaded comments rebuild them for the new comment indicators
+        let params = CommentsLoadParams {
+            review_id: Arc::from(review_id.clone()),
+            file_path: Arc::new(None),
+            line_number: Arc::new(None),
+        };
+        let comments = vec![Comment::test_comment(
+            &review_id,
+            "long.txt",
+            Some(2),
+            "Line comment",
+        )];
+        view.handle_comments_loading_state(
+            &params,
+            &CommentsLoadingState::Loaded(Arc::new(comments)),
+        );
+        assert!(!Arc::ptr_eq(&moved_lines, &cached_lines(&view)));
+    }
+
+    #[tokio::test]
+    async fn test_toggle_scroll_mode() {
+        let mut app = create_test_app().await;
+        let mut view = create_long_file_view(ScrollMode::EdgeFollow);
+        view.selected_line_index = 50;
+        view.update_scroll_to_follow_selected_line();
+
+        let key_event = KeyEvent::new(
+            KeyCode::Char('z'),
+            ratatui::crossterm::event::KeyModifiers::NONE,
+        );
+        view.handle_key_events(&mut app, &key_event).unwrap();
+
+        assert_eq!(view.scroll_mode, ScrollMode::Centered);
+        assert_eq!(view.scroll_offset, 50 - DEFAULT_CONTENT_HEIGHT / 2);
+        assert_eq!(app.settings.scroll_mode, ScrollMode::Centered);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SettingsSave(settings)) => {
+                assert_eq!(settings.scroll_mode, ScrollMode::Centered);
+            }
+            _ => panic!("Expected SettingsSave event, got: {event:?}"),
+        }
+
+        view.handle_key_events(&mut app, &key_event).unwrap();
+        assert_eq!(view.scroll_mode, ScrollMode::EdgeFollow);
+    }
+
     #[tokio::test]
     async fn test_review_details_view_scroll_up_bounds() {
         let review = Review::builder().build();
@@ -1614,6 +4520,7 @@ mod tests {
         let review = Review::builder()
             .base_branch("develop")
             .base_sha_str("asdf1234")
+            .target_sha_str("qwer5678")
             .build();
         let view = ReviewDetailsView::new(review);
 
@@ -1622,7 +4529,46 @@ mod tests {
             ..create_test_app().await
         };
         // Simulate diff loading state
-        app.handle_app_events(&AppEvent::GitDiffLoadingState(GitDiffLoadingState::Loading));
+        app.handle_app_events(&AppEvent::GitDiffLoadingState {
+            base_sha: Arc::from("asdf1234"),
+            target_sha: Arc::from("qwer5678"),
+            state: GitDiffLoadingState::Loading,
+        });
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_loaded_state_diff_progress() {
+        let review = Review::builder()
+            .base_branch("develop")
+            .base_sha_str("asdf1234")
+            .target_sha_str("qwer5678")
+            .build();
+        let view = ReviewDetailsView::new(review);
+
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        app.handle_app_events(&AppEvent::GitDiffLoadingState {
+            base_sha: Arc::from("asdf1234"),
+            target_sha: Arc::from("qwer5678"),
+            state: GitDiffLoadingState::Loading,
+        });
+        app.handle_app_events(&AppEvent::GitDiffProgress {
+            base_sha: Arc::from("asdf1234"),
+            target_sha: Arc::from("qwer5678"),
+            files_done: 120,
+            files_total: 500,
+        });
+        // Progress of another diff is ignored
+        app.handle_app_events(&AppEvent::GitDiffProgress {
+            base_sha: Arc::from("asdf1234"),
+            target_sha: Arc::from("other"),
+            files_done: 3,
+            files_total: 4,
+        });
 
         assert_snapshot!(render_app_to_terminal_backend(app))
     }
@@ -1632,6 +4578,7 @@ mod tests {
         let review = Review::builder()
             .base_branch("feature")
             .base_sha_str("jkl09876")
+            .target_sha_str("zxcv5432")
             .build();
         let view = ReviewDetailsView::new(review);
 
@@ -1641,16 +4588,22 @@ mod tests {
         };
 
         // Simulate diff error state
-        app.handle_app_events(&AppEvent::GitDiffLoadingState(GitDiffLoadingState::Error(
-            "Repository not found".into(),
-        )));
+        app.handle_app_events(&AppEvent::GitDiffLoadingState {
+            base_sha: Arc::from("jkl09876"),
+            target_sha: Arc::from("zxcv5432"),
+            state: GitDiffLoadingState::Error("Repository not found".into()),
+        });
 
         assert_snapshot!(render_app_to_terminal_backend(app))
     }
 
     #[tokio::test]
     async fn test_review_details_view_render_loaded_state_diff_loaded_no_files() {
-        let review = Review::builder().base_branch("main").build();
+        let review = Review::builder()
+            .base_branch("main")
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
         let view = ReviewDetailsView::new(review);
 
         let files = vec![];
@@ -1660,16 +4613,22 @@ mod tests {
             ..create_test_app().await
         };
 
-        app.handle_app_events(&AppEvent::GitDiffLoadingState(GitDiffLoadingState::Loaded(
-            Arc::new(Diff::from_files(files)),
-        )));
+        app.handle_app_events(&AppEvent::GitDiffLoadingState {
+            base_sha: Arc::from("abc123"),
+            target_sha: Arc::from("def456"),
+            state: GitDiffLoadingState::Loaded(Arc::new(Diff::from_files(files))),
+        });
 
         assert_snapshot!(render_app_to_terminal_backend(app))
     }
 
     #[tokio::test]
     async fn test_review_details_view_render_loaded_state_diff_loaded_with_files() {
-        let review = Review::builder().base_branch("main").build();
+        let review = Review::builder()
+            .base_branch("main")
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
         let view = ReviewDetailsView::new(review);
 
         // Simulate diff content being loaded
@@ -1679,60 +4638,2366 @@ mod tests {
  This is a test file
 -Old line to remove
 +New line to add"#;
-        let files = vec![DiffFile {
-            path: "test_file.txt".to_string(),
-            content: diff_content.to_string(),
-        }];
+        let files = vec![DiffFile::from_content("test_file.txt", diff_content)];
 
         let mut app = App {
             view_stack: vec![Box::new(view)],
             ..create_test_app().await
         };
 
-        app.handle_app_events(&AppEvent::GitDiffLoadingState(GitDiffLoadingState::Loaded(
-            Arc::new(Diff::from_files(files)),
-        )));
+        app.handle_app_events(&AppEvent::GitDiffLoadingState {
+            base_sha: Arc::from("abc123"),
+            target_sha: Arc::from("def456"),
+            state: GitDiffLoadingState::Loaded(Arc::new(Diff::from_files(files))),
+        });
 
         assert_snapshot!(render_app_to_terminal_backend(app))
     }
 
+    #[test]
+    fn test_truncate_path_middle() {
+        let path = "src/views/components/lists/main_view.rs";
+
+        assert_eq!(ReviewDetailsView::truncate_path_middle(path, 100), path);
+        assert_eq!(
+            ReviewDetailsView::truncate_path_middle(path, 30),
+            "src/views/.../main_view.rs"
+        );
+        assert_eq!(
+            ReviewDetailsView::truncate_path_middle(path, 20),
+            "src/.../main_view.rs"
+        );
+        assert_eq!(
+            ReviewDetailsView::truncate_path_middle(path, 16),
+            ".../main_view.rs"
+        );
+        // The file name alone doesn't fit, its end is kept
+        assert_eq!(
+            ReviewDetailsView::truncate_path_middle(path, 10),
+            "...view.rs"
+        );
+        assert_eq!(
+            ReviewDetailsView::truncate_path_middle("a_very_long_file_name.rs", 12),
+            "...e_name.rs"
+        );
+    }
+
     #[tokio::test]
-    async fn test_review_details_view_open_comments_file_level() {
+    async fn test_review_details_view_render_comment_priority_colors() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        let comments = vec![
+            Comment::test_comment(&review.id, "src/main.rs", None, "Normal"),
+            Comment::test_comment(&review.id, "src/main.rs", Some(2), "Urgent")
+                .with_priority(CommentPriority::High),
+            Comment::test_comment(&review.id, "src/lib.rs", None, "Whenever")
+                .with_priority(CommentPriority::Low),
+        ];
+        view.handle_comments_loading_state(
+            &view.comments_load_params().unwrap(),
+            &CommentsLoadingState::Loaded(Arc::new(comments)),
+        );
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        let backend = render_app_to_terminal_backend(app);
+        let buffer = backend.buffer();
+
+        // The comment indicator is two columns in front of the path in the file list
+        let indicator_color = |path: &str| {
+            let (x, y) = (0..buffer.area.height)
+                .find_map(|y| {
+                    let row: String = (0..40).map(|x| buffer[(x, y)].symbol()).collect();
+                    row.find(path)
+                        .map(|index| (row[..index].chars().count(), y))
+                })
+                .unwrap();
+            buffer[(x as u16 - 2, y)].fg
+        };
+        assert_eq!(indicator_color("src/main.rs"), Color::Red);
+        assert_eq!(indicator_color("src/lib.rs"), Color::DarkGray);
+        assert_eq!(indicator_color("README.md"), Color::White);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_renders_no_newline_marker_dimly() {
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(
+            Diff::from_files(vec![DiffFile::from_content(
+                "file.txt",
+                "@@ -1 +1 @@\n-two\n+two\n\\ No newline at end of file\n",
+            )]),
+        )));
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        let backend = render_app_to_terminal_backend(app);
+        let buffer = backend.buffer();
+
+        let style_of = |text: &str| {
+            (0..buffer.area.height)
+                .find_map(|y| {
+                    let row: String = (0..buffer.area.width)
+                        .map(|x| buffer[(x, y)].symbol())
+                        .collect();
+                    row.find(text)
+                        .map(|index| buffer[(row[..index].chars().count() as u16, y)].style())
+                })
+                .unwrap()
+        };
+        assert!(
+            style_of("\\ No newline")
+                .add_modifier
+                .contains(Modifier::DIM)
+        );
+        assert!(!style_of("+two").add_modifier.contains(Modifier::DIM));
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_docks_comments() {
+        let review = Review::builder().build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        app.settings.dock_comments = true;
+        let docked_comments = |app: &App| {
+            app.view_stack[0]
+                .as_any()
+                .downcast_ref::<ReviewDetailsView>()
+                .unwrap()
+                .docked_comments
+                .is_some()
+        };
+
+        crate::event_handler::EventProcessor::process_event(
+            &mut app,
+            Event::App(AppEvent::CommentsOpen {
+                review_id: review.id.clone().into(),
+                file_path: Arc::from("src/main.rs"),
+                line_number: None,
+                old_file_path: None,
+            })
+            .into(),
+        )
+        .await
+        .unwrap();
+        // The comments are docked instead of pushed as a view of their own
+        assert_eq!(app.view_stack.len(), 1);
+        assert!(docked_comments(&app));
+
+        // The docked comments get the key events and close themselves
+        app.handle_key_events(&KeyEvent::from(KeyCode::Esc))
+            .unwrap();
+        let mut closed = false;
+        while let Some(event) = app.events.try_recv() {
+            if let Event::App(app_event) = &*event {
+                closed |= matches!(app_event, AppEvent::CommentsDockedClose);
+                app.handle_app_events(app_event);
+            }
+        }
+        assert!(closed);
+        assert!(!docked_comments(&app));
+        assert_eq!(app.view_stack.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_docked_comments() {
         let review = Review::builder().base_branch("main").build();
         let mut view = ReviewDetailsView::new(review.clone());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        let comments_view =
+            CommentsView::new_for_file(review.id.clone(), "src/main.rs".to_string());
+        assert!(view.dock_comments(comments_view).is_none());
+        let mut app = create_test_app().await;
+        let comment = Comment::new_with_time_provider(
+            &review.id,
+            "src/main.rs",
+            None,
+            "Rename this file",
+            &MockTimeProvider::new(fixed_time()),
+        );
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::CommentsLoadingState {
+                params: CommentsLoadParams {
+                    review_id: Arc::from(review.id.as_str()),
+                    file_path: Arc::new(Some("src/main.rs".to_string())),
+                    line_number: Arc::new(None),
+                },
+                state: CommentsLoadingState::Loaded(Arc::new(vec![comment])),
+            },
+        );
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_jump_to_first_not_viewed_file() {
         let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.viewed_files = Arc::new(vec!["src/main.rs".to_string()]);
+        // A restored selection in the viewed list, in the diff of the file
+        view.active_file_list = FileListType::Viewed;
+        view.navigation_mode = NavigationMode::Lines;
+        view.selected_line_index = 2;
 
-        // Set up a diff with files
-        let files = vec![DiffFile {
-            path: "src/main.rs".to_string(),
-            content: "line1\nline2\nline3".to_string(),
-        }];
-        let diff = Arc::new(Diff::from_files(files));
-        view.diff = diff;
-        view.navigation_mode = NavigationMode::Files;
-        view.selected_file_index = 0;
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('f')))
+            .unwrap();
 
-        // Initial state should have no pending events
-        assert!(!app.events.has_pending_events());
+        assert_eq!(view.active_file_list, FileListType::NotViewed);
+        assert!(matches!(view.navigation_mode, NavigationMode::Files));
+        assert_eq!(view.selected_file_index, 0);
+        assert_eq!(view.selected_line_index, 0);
+        assert_eq!(view.get_selected_file().unwrap().path, "src/lib.rs");
+        assert_eq!(view.notice, None);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_jump_to_first_not_viewed_file_when_complete() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.viewed_files = Arc::new(vec![
+            "README.md".to_string(),
+            "src/lib.rs".to_string(),
+            "src/main.rs".to_string(),
+        ]);
+        view.selected_file_index = 2;
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('f')))
+            .unwrap();
+
+        assert_eq!(view.active_file_list, FileListType::Viewed);
+        assert_eq!(view.selected_file_index, 0);
+        assert_eq!(view.get_selected_file().unwrap().path, "src/main.rs");
+        assert_eq!(view.notice.as_deref(), Some(REVIEW_COMPLETE_NOTICE));
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_jump_by_churn() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(
+            Diff::from_files(vec![
+                DiffFile::from_content("a.txt", "@@ -1 +1 @@\n-old\n+new"),
+                DiffFile::from_content("b.txt", "@@ -1,2 +1,2 @@\n-a\n-b\n+c\n+d"),
+                DiffFile::from_content("c.txt", "@@ -0,0 +1,2 @@\n+first\n+second"),
+                DiffFile::from_content("d.txt", "@@ -0,0 +1,6 @@\n+1\n+2\n+3\n+4\n+5\n+6"),
+            ]),
+        )));
+        let mut press = |view: &mut ReviewDetailsView, key: char| {
+            view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char(key)))
+                .unwrap();
+            view.get_selected_file().unwrap().path.clone()
+        };
+
+        // Ordered by churn: d.txt (6), b.txt (4), a.txt (2) and c.txt (2) by path
+        assert_eq!(press(&mut view, '}'), "c.txt");
+        assert_eq!(press(&mut view, '}'), "c.txt");
+        assert_eq!(press(&mut view, '{'), "a.txt");
+        assert_eq!(press(&mut view, '{'), "b.txt");
+        assert_eq!(press(&mut view, '{'), "d.txt");
+        assert_eq!(press(&mut view, '{'), "d.txt");
+
+        // Only the files of the active list are visited
+        view.viewed_files = Arc::new(vec!["b.txt".to_string()]);
+        view.selected_file_index = 2;
+        assert_eq!(press(&mut view, '}'), "a.txt");
+        assert_eq!(press(&mut view, '}'), "c.txt");
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_churn_summary_toggle() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('s')))
+            .unwrap();
+        assert!(view.show_churn_summary);
+
+        // Other keys don't move the selection below the panel
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('j')))
+            .unwrap();
+        assert_eq!(view.selected_file_index, 0);
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Esc))
+            .unwrap();
+        assert!(!view.show_churn_summary);
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_churn_summary() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review);
+        let file = |path: &str, content: &str| DiffFile::from_content(path, content);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(
+            Diff::from_files(vec![
+                file("README.md", "@@ -1 +1 @@\n-old\n+new"),
+                file("src/app.rs", "@@ -1,3 +1,4 @@\n-a\n-b\n+c\n+d\n+e\n+f"),
+                file("src/lib.rs", "@@ -0,0 +1 @@\n+mod app;"),
+                file(
+                    "src/main.rs",
+                    "@@ -1,10 +1,2 @@\n-1\n-2\n-3\n-4\n-5\n-6\n-7\n-8\n+new",
+                ),
+                file(
+                    "Cargo.toml",
+                    "@@ -1 +1,2 @@\n+[dependencies]\n+git2 = \"0.20\"",
+                ),
+                file("docs/usage.md", "@@ -1,3 +1 @@\n-old\n-docs\n-here"),
+            ]),
+        )));
+        view.show_churn_summary = true;
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_churn_summary_empty_diff() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(Diff::default())));
+        view.show_churn_summary = true;
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_long_path_keeps_file_name() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review);
+        let path = "src/very/deeply/nested/directories/of/the/project/main_view.rs";
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(
+            Diff::from_files(vec![DiffFile::from_content(
+                path,
+                "@@ -1 +1 @@\n-old\n+new",
+            )]),
+        )));
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        let backend = render_app_to_terminal_backend(app);
+
+        let rendered: String = backend
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(rendered.contains("src/very/.../main_view.rs"));
+        assert_snapshot!(backend)
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_viewed_list_collapsed() {
+        let review = Review::builder()
+            .base_branch("main")
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
+        let review_id = review.id.clone();
+        let view = ReviewDetailsView::new(review);
+
+        let files = vec![
+            DiffFile::from_content("src/main.rs", "@@ -1 +1 @@\n-old\n+new"),
+            DiffFile::from_content("src/lib.rs", "@@ -1 +1 @@\n-old\n+new"),
+            DiffFile::from_content("README.md", "@@ -1 +1 @@\n-old\n+new"),
+        ];
+
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        app.settings.collapse_viewed_list = true;
+
+        app.handle_app_events(&AppEvent::GitDiffLoadingState {
+            base_sha: Arc::from("abc123"),
+            target_sha: Arc::from("def456"),
+            state: GitDiffLoadingState::Loaded(Arc::new(Diff::from_files(files))),
+        });
+        app.handle_app_events(&AppEvent::FileViewsLoaded {
+            review_id: review_id.into(),
+            viewed_files: Arc::new(vec!["src/lib.rs".to_string(), "README.md".to_string()]),
+            skipped_files: Arc::new(vec![]),
+            content_hashes: Default::default(),
+        });
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_viewed_list_collapse_follows_active_list() {
+        let mut app = create_test_app().await;
+        let review = Review::builder().build();
+        let mut view = ReviewDetailsView::new(review);
+        assert!(!view.is_viewed_list_collapsed(&app.settings));
+
+        view.toggle_collapse_viewed_list(&mut app);
+        assert!(view.is_viewed_list_collapsed(&app.settings));
+
+        // The changed setting is persisted
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SettingsSave(settings)) => {
+                assert!(settings.collapse_viewed_list);
+            }
+            _ => panic!("Expected SettingsSave event, got: {event:?}"),
+        }
+
+        // Switching to the viewed list expands it
+        view.switch_file_list_right();
+        assert!(!view.is_viewed_list_collapsed(&app.settings));
+
+        // Switching back collapses it again
+        view.switch_file_list_left();
+        assert!(view.is_viewed_list_collapsed(&app.settings));
+
+        view.toggle_collapse_viewed_list(&mut app);
+        assert!(!view.is_viewed_list_collapsed(&app.settings));
+    }
+
+    fn create_jump_test_diff() -> Arc<Diff> {
+        Arc::new(Diff::from_files(vec![
+            DiffFile::from_content("src/main.rs", "@@ -1 +1 @@\n-old\n+new"),
+            DiffFile::from_content(
+                "src/lib.rs",
+                "@@ -1,2 +1,3 @@\n context\n-old\n+new\n+added",
+            ),
+            DiffFile::from_content("README.md", "@@ -1 +1 @@\n-old\n+new"),
+        ]))
+    }
+
+    /// View of the jump test diff with the hunk blame enabled in the app settings
+    async fn create_hunk_blame_test_view() -> (App, ReviewDetailsView) {
+        let mut app = create_test_app().await;
+        app.settings.show_hunk_blame = true;
+        let review = Review::builder()
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::GitDiffLoadingState {
+                base_sha: Arc::from("abc123"),
+                target_sha: Arc::from("def456"),
+                state: GitDiffLoadingState::Loaded(create_jump_test_diff()),
+            },
+        );
+        (app, view)
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_loads_hunk_blame_once_per_file() {
+        let (mut app, mut view) = create_hunk_blame_test_view().await;
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::HunkBlameLoad(request)) => {
+                assert_eq!(request.base_sha.as_ref(), "abc123");
+                assert_eq!(request.file_path.as_ref(), "src/main.rs");
+                assert_eq!(request.old_path.as_ref(), "src/main.rs");
+                assert_eq!(request.hunks.len(), 1);
+            }
+            _ => panic!("Expected HunkBlameLoad event, got: {event:?}"),
+        }
+
+        // Moving within the same file does not blame it again, a new file is blamed
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Enter))
+            .unwrap();
+        assert!(!app.events.has_pending_events());
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Esc))
+            .unwrap();
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('j')))
+            .unwrap();
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(
+            &*event,
+            Event::App(AppEvent::HunkBlameLoad(request)) if request.file_path.as_ref() == "src/lib.rs"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_hunk_blame_is_opt_in() {
+        let (mut app, mut view) = create_hunk_blame_test_view().await;
+        app.events.try_recv().unwrap();
+
+        // Turning it off persists the setting and stops blaming
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('b')))
+            .unwrap();
+        assert!(!app.settings.show_hunk_blame);
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(&*event, Event::App(AppEvent::SettingsSave(_))));
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('j')))
+            .unwrap();
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_hunk_blame() {
+        let (mut app, mut view) = create_hunk_blame_test_view().await;
+        let request = match &*app.events.try_recv().unwrap() {
+            Event::App(AppEvent::HunkBlameLoad(request)) => Arc::clone(request),
+            event => panic!("Expected HunkBlameLoad event, got: {event:?}"),
+        };
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::HunkBlameLoaded {
+                request,
+                summaries: Arc::new(HashMap::from([(
+                    0,
+                    Arc::from("Alice, 1234567 (2025-01-01): Add the old line"),
+                )])),
+            },
+        );
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    fn create_context_test_view() -> ReviewDetailsView {
+        let review = Review::builder().target_sha_str("target").build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(
+            Diff::from_files(vec![DiffFile::from_content(
+                "src/main.rs",
+                "@@ -5,3 +5,3 @@\n a\n-b\n+B\n c\n@@ -20,2 +20,2 @@\n x\n-y\n+Y",
+            )]),
+        )));
+        view.navigation_mode = NavigationMode::Lines;
+        view
+    }
+
+    async fn expand_context_request(
+        view: &ReviewDetailsView,
+        direction: DiffContextDirection,
+    ) -> Option<Arc<DiffContextRequest>> {
+        let mut app = create_test_app().await;
+        view.expand_context(&mut app, direction);
+        let event = app.events.try_recv()?;
+        match &*event {
+            Event::App(AppEvent::DiffContextExpand(request)) => Some(Arc::clone(request)),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_expand_context_requests() {
+        let mut view = create_context_test_view();
+
+        // Above the first hunk, lines 1-4 are hidden
+        let request = expand_context_request(&view, DiffContextDirection::Above)
+            .await
+            .unwrap();
+        assert_eq!(request.sha.as_ref(), "target");
+        assert_eq!(request.file_path.as_ref(), "src/main.rs");
+        assert_eq!(request.hunk_line_index, 0);
+        assert_eq!((request.start_line, request.end_line), (1, 4));
+
+        // Below the first hunk, the expansion is bounded by the next hunk
+        let request = expand_context_request(&view, DiffContextDirection::Below)
+            .await
+            .unwrap();
+        assert_eq!((request.start_line, request.end_line), (8, 17));
+
+        // Above the second hunk, the closest hidden lines are expanded first
+        view.selected_line_index = 6;
+        let request = expand_context_request(&view, DiffContextDirection::Above)
+            .await
+            .unwrap();
+        assert_eq!(request.hunk_line_index, 5);
+        assert_eq!((request.start_line, request.end_line), (10, 19));
+
+        // Below the last hunk, the expansion is bounded by the end of the file only
+        let request = expand_context_request(&view, DiffContextDirection::Below)
+            .await
+            .unwrap();
+        assert_eq!((request.start_line, request.end_line), (22, 31));
+
+        // Nothing is expanded in Files mode
+        view.navigation_mode = NavigationMode::Files;
+        assert!(
+            expand_context_request(&view, DiffContextDirection::Above)
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_handle_diff_context_expanded() {
+        let mut view = create_context_test_view();
+        let request = |direction, hunk_line_index, start_line, end_line| DiffContextRequest {
+            sha: Arc::from("target"),
+            file_path: Arc::from("src/main.rs"),
+            hunk_line_index,
+            direction,
+            start_line,
+            end_line,
+        };
+        let lines = |range: std::ops::RangeInclusive<usize>| -> Vec<String> {
+            range.map(|line| format!("line {line}")).collect()
+        };
+
+        view.handle_diff_context_expanded(
+            &request(DiffContextDirection::Above, 0, 1, 4),
+            &lines(1..=4),
+        );
+        // A duplicate response doesn't continue the expansion and is ignored
+        view.handle_diff_context_expanded(
+            &request(DiffContextDirection::Above, 0, 1, 4),
+            &lines(1..=4),
+        );
+        view.handle_diff_context_expanded(
+            &request(DiffContextDirection::Below, 0, 8, 17),
+            &lines(8..=17),
+        );
+        // A response for another target SHA is ignored
+        view.handle_diff_context_expanded(
+            &DiffContextRequest {
+                sha: Arc::from("other"),
+                ..request(DiffContextDirection::Below, 5, 22, 31)
+            },
+            &lines(22..=23),
+        );
+        // Fewer lines than requested below the last hunk means the end of the file is reached
+        view.handle_diff_context_expanded(
+            &request(DiffContextDirection::Below, 5, 22, 31),
+            &lines(22..=23),
+        );
+
+        let file = view.get_selected_file().unwrap().clone();
+        let rows = view.diff_rows(&file);
+        assert_eq!(rows.len(), 9 + 4 + 10 + 1 + 2);
+        assert_eq!(rows[0], DiffRow::Context("line 1"));
+        assert_eq!(rows[4], DiffRow::Line(0, "@@ -5,3 +5,3 @@"));
+        assert_eq!(rows[9], DiffRow::Context("line 8"));
+        assert_eq!(rows[19], DiffRow::Hidden(2));
+        assert_eq!(rows[20], DiffRow::Line(5, "@@ -20,2 +20,2 @@"));
+        assert_eq!(rows[24], DiffRow::Context("line 22"));
+
+        view.selected_line_index = 6;
+        assert!(
+            expand_context_request(&view, DiffContextDirection::Below)
+                .await
+                .is_none()
+        );
+        let request = expand_context_request(&view, DiffContextDirection::Above)
+            .await
+            .unwrap();
+        assert_eq!((request.start_line, request.end_line), (18, 19));
+
+        // Reloading the diff drops the expansions
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::clone(&view.diff)));
+        assert!(view.context_expansions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_expanded_context() {
+        let mut view = create_context_test_view();
+        view.handle_diff_context_expanded(
+            &DiffContextRequest {
+                sha: Arc::from("target"),
+                file_path: Arc::from("src/main.rs"),
+                hunk_line_index: 5,
+                direction: DiffContextDirection::Above,
+                start_line: 18,
+                end_line: 19,
+            },
+            &["fn unchanged() {".to_string(), "}".to_string()],
+        );
+        view.selected_line_index = 6;
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[test]
+    fn test_review_details_view_metadata_only_file_disables_line_navigation() {
+        let review = Review::builder().build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(
+            Diff::from_files(vec![DiffFile::metadata(
+                "script.sh",
+                "mode changed 100644 → 100755",
+            )]),
+        )));
+
+        view.toggle_navigation_mode();
+        assert!(matches!(view.navigation_mode, NavigationMode::Files));
+
+        view.jump_to_file(&FileJump {
+            file_path: "script.sh".into(),
+            line_number: Some(0),
+        });
+        assert!(matches!(view.navigation_mode, NavigationMode::Files));
+    }
+
+    /// View of the jump test diff with the unchanged files of its target SHA listed
+    async fn create_unchanged_files_test_view() -> (App, ReviewDetailsView) {
+        let mut app = create_test_app().await;
+        let review = Review::builder()
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::GitDiffLoadingState {
+                base_sha: Arc::from("abc123"),
+                target_sha: Arc::from("def456"),
+                state: GitDiffLoadingState::Loaded(create_jump_test_diff()),
+            },
+        );
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('t')))
+            .unwrap();
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::TreeFilesLoad(sha)) => assert_eq!(sha.as_ref(), "def456"),
+            _ => panic!("Expected TreeFilesLoad event, got: {event:?}"),
+        }
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::TreeFilesLoaded {
+                sha: Arc::from("def456"),
+                paths: Arc::from(vec![
+                    "Cargo.toml".to_string(),
+                    "README.md".to_string(),
+                    "src/lib.rs".to_string(),
+                    "src/main.rs".to_string(),
+                    "src/util.rs".to_string(),
+                ]),
+            },
+        );
+        (app, view)
+    }
+
+    fn listed_paths(view: &ReviewDetailsView) -> Vec<&str> {
+        view.get_current_file_list()
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_lists_unchanged_files() {
+        let (mut app, mut view) = create_unchanged_files_test_view().await;
+
+        // The changed files come first, followed by the unchanged files of the target SHA
+        assert_eq!(
+            listed_paths(&view),
+            vec![
+                "src/main.rs",
+                "src/lib.rs",
+                "README.md",
+                "Cargo.toml",
+                "src/util.rs"
+            ]
+        );
+        view.selected_file_index = 4;
+        let file = view.get_selected_file().unwrap();
+        assert!(view.is_unchanged_file(file));
+        assert!(!file.has_lines());
+
+        // Unchanged files can't be opened in lines mode, but file comments can be added
+        view.toggle_navigation_mode();
+        assert!(matches!(view.navigation_mode, NavigationMode::Files));
+
+        // The files are only listed once, hiding them keeps the selection in the list
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('t')))
+            .unwrap();
+        assert_eq!(
+            listed_paths(&view),
+            vec!["src/main.rs", "src/lib.rs", "README.md"]
+        );
+        assert_eq!(view.selected_file_index, 2);
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('t')))
+            .unwrap();
+        assert!(!app.events.has_pending_events());
+        assert_eq!(listed_paths(&view).len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_toggles_excluded_files() {
+        let mut app = create_test_app().await;
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review);
+        let diff = Arc::unwrap_or_clone(create_jump_test_diff())
+            .with_excluded_files(&ExcludePatterns::parse("*.md\nsrc/lib.rs"));
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(diff)));
+
+        // Excluded files are not listed by default
+        assert_eq!(listed_paths(&view), vec!["src/main.rs"]);
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('x')))
+            .unwrap();
+        assert_eq!(
+            listed_paths(&view),
+            vec!["src/main.rs", "src/lib.rs", "README.md"]
+        );
+
+        // Hiding them again keeps the selection in the list
+        view.selected_file_index = 2;
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('x')))
+            .unwrap();
+        assert_eq!(listed_paths(&view), vec!["src/main.rs"]);
+        assert_eq!(view.selected_file_index, 0);
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_ignores_tree_files_of_other_sha() {
+        let mut app = create_test_app().await;
+        let review = Review::builder().target_sha_str("def456").build();
+        let mut view = ReviewDetailsView::new(review);
+        view.show_unchanged_files = true;
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::TreeFilesLoaded {
+                sha: Arc::from("0123456"),
+                paths: Arc::from(vec!["Cargo.toml".to_string()]),
+            },
+        );
+
+        assert!(view.get_current_file_list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_unchanged_files() {
+        let (app, view) = create_unchanged_files_test_view().await;
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..app
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_overview() {
+        let review = Review::builder().base_branch("main").build();
+        let review_id = review.id.clone();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.handle_file_views_loaded(
+            &review_id,
+            &Arc::new(vec!["README.md".to_string()]),
+            &Arc::new(vec![]),
+            &Default::default(),
+        );
+        view.files_with_file_and_or_line_comments = Arc::new(vec!["src/lib.rs".to_string()]);
+        view.files_with_file_comments = Arc::new(vec!["src/lib.rs".to_string()]);
+        view.open_overview();
+        view.overview_selected_index = 1;
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_lines_mode_absolute_line_numbers() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.navigation_mode = NavigationMode::Lines;
+        view.selected_line_index = 2;
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_lines_mode_relative_line_numbers() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.navigation_mode = NavigationMode::Lines;
+        view.selected_line_index = 2;
+
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        app.settings.relative_line_numbers = true;
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[test]
+    fn test_gutter_line_number() {
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        view.selected_line_index = 1;
+        let line_numbers = [None, Some(10), Some(11), None];
+        let relative = Settings {
+            relative_line_numbers: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            view.gutter_line_number(&Settings::default(), &line_numbers, 0, 2),
+            Some(11)
+        );
+        assert_eq!(
+            view.gutter_line_number(&Settings::default(), &line_numbers, 0, 3),
+            None
+        );
+        // Relative line numbers only apply in lines mode
+        assert_eq!(
+            view.gutter_line_number(&relative, &line_numbers, 0, 3),
+            None
+        );
+
+        view.navigation_mode = NavigationMode::Lines;
+        assert_eq!(
+            view.gutter_line_number(&relative, &line_numbers, 0, 0),
+            Some(1)
+        );
+        assert_eq!(
+            view.gutter_line_number(&relative, &line_numbers, 0, 1),
+            Some(0)
+        );
+        assert_eq!(
+            view.gutter_line_number(&relative, &line_numbers, 0, 3),
+            Some(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_toggle_relative_line_numbers() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        assert!(!app.settings.relative_line_numbers);
+
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+                kind: ratatui::crossterm::event::KeyEventKind::Press,
+                state: ratatui::crossterm::event::KeyEventState::empty(),
+            },
+        )
+        .unwrap();
+
+        assert!(app.settings.relative_line_numbers);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SettingsSave(settings)) => {
+                assert!(settings.relative_line_numbers);
+            }
+            _ => panic!("Expected SettingsSave event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_toggle_diff_prefixes() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        assert!(!app.settings.hide_diff_prefixes);
+
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(
+                KeyCode::Char('p'),
+                ratatui::crossterm::event::KeyModifiers::NONE,
+            ),
+        )
+        .unwrap();
+
+        assert!(app.settings.hide_diff_prefixes);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SettingsSave(settings)) => {
+                assert!(settings.hide_diff_prefixes);
+            }
+            _ => panic!("Expected SettingsSave event, got: {event:?}"),
+        }
+    }
+
+    #[test]
+    fn test_display_line_text() {
+        assert_eq!(
+            ReviewDetailsView::display_line_text("+added", false),
+            "+added"
+        );
+        assert_eq!(
+            ReviewDetailsView::display_line_text("+added", true),
+            "added"
+        );
+        assert_eq!(
+            ReviewDetailsView::display_line_text("-removed", true),
+            "removed"
+        );
+        assert_eq!(
+            ReviewDetailsView::display_line_text(" context", true),
+            "context"
+        );
+        assert_eq!(
+            ReviewDetailsView::display_line_text("@@ -1,2 +1,2 @@", true),
+            "@@ -1,2 +1,2 @@"
+        );
+        assert_eq!(
+            ReviewDetailsView::display_line_text("renamed from old.rs", true),
+            "renamed from old.rs"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_lines_mode_hidden_diff_prefixes() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.navigation_mode = NavigationMode::Lines;
+        view.selected_line_index = 2;
+
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        app.settings.hide_diff_prefixes = true;
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_toggle_whitespace() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        assert!(!app.settings.show_whitespace);
+
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(
+                KeyCode::Char('w'),
+                ratatui::crossterm::event::KeyModifiers::NONE,
+            ),
+        )
+        .unwrap();
+
+        assert!(app.settings.show_whitespace);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SettingsSave(settings)) => {
+                assert!(settings.show_whitespace);
+            }
+            _ => panic!("Expected SettingsSave event, got: {event:?}"),
+        }
+    }
+
+    #[test]
+    fn test_display_line_segments() {
+        let whitespace = Settings {
+            show_whitespace: true,
+            ..Default::default()
+        };
+        let segments = |line_text: &str, settings: &Settings| {
+            ReviewDetailsView::display_line_segments(line_text, settings, None)
+        };
+        let segment = |text: &str, is_whitespace| DisplaySegment {
+            text: text.to_string(),
+            is_whitespace,
+            is_changed: false,
+        };
+
+        assert_eq!(
+            segments("+\tlet a = 1;  ", &Settings::default()),
+            vec![segment("+    let a = 1;  ", false)]
+        );
+        assert_eq!(
+            segments("+\tlet a = 1;  ", &whitespace),
+            vec![
+                segment("+", false),
+                segment("→   ", true),
+                segment("let a = 1;", false),
+                segment("··", true),
+            ]
+        );
+        assert_eq!(
+            segments(
+                " \t\tx",
+                &Settings {
+                    hide_diff_prefixes: true,
+                    ..whitespace.clone()
+                }
+            ),
+            vec![
+                segment("", false),
+                segment("→   →   ", true),
+                segment("x", false)
+            ]
+        );
+        // A context line that is empty apart from its origin prefix has no trailing spaces
+        assert_eq!(segments(" ", &whitespace), vec![segment(" ", false)]);
+        assert_eq!(
+            segments("@@ -1 +1 @@ fn main()  ", &whitespace),
+            vec![segment("@@ -1 +1 @@ fn main()  ", false)]
+        );
+    }
+
+    #[test]
+    fn test_display_line_segments_tab_width() {
+        let segments = |line_text, tab_width| {
+            ReviewDetailsView::display_line_segments(
+                line_text,
+                &Settings {
+                    tab_width,
+                    ..Default::default()
+                },
+                None,
+            )
+            .into_iter()
+            .map(|segment| segment.text)
+            .collect::<String>()
+        };
+
+        // Tabs are expanded to the next tab stop of the content, not of the whole line
+        assert_eq!(segments("+\tx", 2), "+  x");
+        assert_eq!(segments("+a\tb\tc", 2), "+a b c");
+        assert_eq!(segments("+\tx", 8), "+        x");
+        assert_eq!(segments("+abc\tx", 8), "+abc     x");
+        assert_eq!(segments("-\t\tx", 8), "-                x");
+    }
+
+    #[test]
+    fn test_expand_tabs() {
+        assert_eq!(ReviewDetailsView::expand_tabs("\tx", 2), "  x");
+        assert_eq!(ReviewDetailsView::expand_tabs("ab\tc\td", 2), "ab  c d");
+        assert_eq!(ReviewDetailsView::expand_tabs("\tx", 8), "        x");
+        assert_eq!(ReviewDetailsView::expand_tabs("abc\tx", 8), "abc     x");
+        assert_eq!(ReviewDetailsView::expand_tabs("no tabs", 8), "no tabs");
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_whitespace() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(
+            Diff::from_files(vec![DiffFile::from_content(
+                "src/main.rs",
+                "@@ -1,2 +1,2 @@\n fn main() {\n-    let a = 1;  \n+\tlet a = 1;\n \t \n }",
+            )]),
+        )));
+        view.navigation_mode = NavigationMode::Lines;
+
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        app.settings.show_whitespace = true;
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    /// Diff with a change of two lines that are paired and one whose lines can't be paired
+    fn create_inline_changes_test_file() -> DiffFile {
+        DiffFile::from_content(
+            "src/main.rs",
+            "@@ -1,4 +1,4 @@\n fn main() {\n-    let total = 1;\n-    println!(\"{total}\");\n\
+             +    let total = 42;\n+    println!(\"total: {total}\");\n }\n\
+             @@ -10,1 +10,2 @@\n-    old();\n+    new_a();\n+    new_b();",
+        )
+    }
+
+    #[test]
+    fn test_review_details_view_pair_changed_lines() {
+        let view = ReviewDetailsView::new(Review::builder().build());
+        let file = create_inline_changes_test_file();
+
+        let (rows, inline_changes) = ReviewDetailsView::pair_changed_lines(view.diff_rows(&file));
+
+        let line_indices: Vec<usize> = rows
+            .iter()
+            .filter_map(|row| match row {
+                DiffRow::Line(line_index, _) => Some(*line_index),
+                _ => None,
+            })
+            .collect();
+        // Every removed line is followed by its added line, the second change is kept as is
+        assert_eq!(line_indices, vec![0, 1, 2, 4, 3, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(
+            inline_changes.get(&2),
+            Some(&InlineChange {
+                changed: 17..18,
+                is_added: false
+            })
+        );
+        assert_eq!(
+            inline_changes.get(&4),
+            Some(&InlineChange {
+                changed: 17..19,
+                is_added: true
+            })
+        );
+        assert_eq!(
+            inline_changes
+                .get(&5)
+                .map(|change| &file.content().lines().nth(5).unwrap()[change.changed.clone()]),
+            Some("total: ")
+        );
+        assert!(!inline_changes.contains_key(&8));
+        assert_eq!(inline_changes.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_inline_changes() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(
+            Diff::from_files(vec![create_inline_changes_test_file()]),
+        )));
+
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        app.settings.inline_changes = true;
+
+        let backend = render_app_to_terminal_backend(app);
+        // Only the changed words are highlighted
+        let highlighted: String = backend
+            .buffer()
+            .content()
+            .iter()
+            .filter(|cell| cell.modifier.contains(Modifier::REVERSED))
+            .map(|cell| cell.symbol())
+            .collect();
+        assert_eq!(highlighted, "142total: ");
+        assert_snapshot!(backend);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_toggle_inline_changes() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('i')))
+            .unwrap();
+
+        assert!(app.settings.inline_changes);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::SettingsSave(settings)) => assert!(settings.inline_changes),
+            _ => panic!("Expected SettingsSave event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_lines_mode_no_color() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.navigation_mode = NavigationMode::Lines;
+        view.selected_line_index = 1;
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            theme: Theme::new(true),
+            ..create_test_app().await
+        };
+
+        let backend = render_app_to_terminal_backend(app);
+        assert!(
+            backend
+                .buffer()
+                .content()
+                .iter()
+                .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+        );
+        assert_snapshot!(backend)
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_resets_changed_viewed_files() {
+        let mut app = create_test_app().await;
+        let review = Review::builder()
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
+        let review_id = review.id.clone();
+        let mut view = ReviewDetailsView::new(review);
+        let diff = create_jump_test_diff();
+        let unchanged_hash = diff.files[2].content_hash();
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::FileViewsLoaded {
+                review_id: review_id.clone().into(),
+                viewed_files: Arc::new(vec!["src/lib.rs".to_string(), "README.md".to_string()]),
+                skipped_files: Arc::new(vec![]),
+                content_hashes: Arc::new(HashMap::from([
+                    ("src/lib.rs".to_string(), "outdated-hash".to_string()),
+                    ("README.md".to_string(), unchanged_hash),
+                ])),
+            },
+        );
+        // Nothing is compared before the diff is loaded
+        assert!(!app.events.has_pending_events());
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::GitDiffLoadingState {
+                base_sha: Arc::from("abc123"),
+                target_sha: Arc::from("def456"),
+                state: GitDiffLoadingState::Loaded(diff),
+            },
+        );
+
+        assert_eq!(view.viewed_files.as_ref(), &vec!["README.md".to_string()]);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::FileViewsReset {
+                review_id: reset_review_id,
+                file_paths,
+            }) => {
+                assert_eq!(reset_review_id.as_ref(), review_id);
+                assert_eq!(file_paths.as_ref(), ["src/lib.rs".to_string()]);
+            }
+            _ => panic!("Expected FileViewsReset event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_toggle_file_view_sends_content_hash() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        let diff = create_jump_test_diff();
+        let expected_hash = diff.files[0].content_hash();
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(diff));
+
+        view.toggle_file_view_status(&mut app);
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::FileViewToggle { content_hash, .. }) => {
+                assert_eq!(content_hash.as_deref(), Some(expected_hash.as_str()));
+            }
+            _ => panic!("Expected FileViewToggle event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_toggle_file_skip() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.selected_file_index = 1;
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('X')))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::FileSkipToggle { file_path, .. }) => {
+                assert_eq!(file_path.as_ref(), "src/lib.rs");
+            }
+            _ => panic!("Expected FileSkipToggle event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_toggle_file_view_in_lines_mode() {
+        let mut app = create_test_app().await;
+        let review = Review::builder().build();
+        let review_id = review.id.clone();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.selected_file_index = 1;
+        view.toggle_navigation_mode();
+        view.go_down();
+        view.go_down();
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char(' ')))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::FileViewToggle { file_path, .. }) => {
+                assert_eq!(file_path.as_ref(), "src/lib.rs");
+            }
+            _ => panic!("Expected FileViewToggle event, got: {event:?}"),
+        }
+
+        // The reloaded file views move the file to the viewed list, which is followed
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::FileViewsLoaded {
+                review_id: review_id.into(),
+                viewed_files: Arc::new(vec!["src/lib.rs".to_string()]),
+                skipped_files: Arc::new(vec![]),
+                content_hashes: Default::default(),
+            },
+        );
+        assert!(matches!(view.navigation_mode, NavigationMode::Lines));
+        assert_eq!(view.active_file_list, FileListType::Viewed);
+        assert_eq!(view.get_selected_file().unwrap().path, "src/lib.rs");
+        assert_eq!(view.selected_line_index, 2);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_toggle_file_view_in_files_mode() {
+        let mut app = create_test_app().await;
+        let review = Review::builder().build();
+        let review_id = review.id.clone();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.selected_file_index = 1;
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char(' ')))
+            .unwrap();
+
+        // The toggled file is not followed to the viewed list
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::FileViewsLoaded {
+                review_id: review_id.into(),
+                viewed_files: Arc::new(vec!["src/lib.rs".to_string()]),
+                skipped_files: Arc::new(vec![]),
+                content_hashes: Default::default(),
+            },
+        );
+        assert!(matches!(view.navigation_mode, NavigationMode::Files));
+        assert_eq!(view.active_file_list, FileListType::NotViewed);
+        assert_eq!(view.selected_file_index, 0);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_file_jump_before_file_views_loaded() {
+        let mut app = create_test_app().await;
+        let review = Review::builder().build();
+        let review_id = review.id.clone();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.jump_to_file(&FileJump {
+            file_path: "src/lib.rs".into(),
+            line_number: None,
+        });
+        assert!(matches!(view.navigation_mode, NavigationMode::Files));
+        assert_eq!(view.get_selected_file().unwrap().path, "src/lib.rs");
+
+        // The file views arrive after the diff and move the file to the viewed list
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::FileViewsLoaded {
+                review_id: review_id.into(),
+                viewed_files: Arc::new(vec!["src/lib.rs".to_string()]),
+                skipped_files: Arc::new(vec![]),
+                content_hashes: Default::default(),
+            },
+        );
+        assert!(matches!(view.navigation_mode, NavigationMode::Files));
+        assert_eq!(view.active_file_list, FileListType::Viewed);
+        assert_eq!(view.get_selected_file().unwrap().path, "src/lib.rs");
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_tab_cycles_focus() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.viewed_files = Arc::new(vec!["README.md".to_string()]);
+        let focus = |view: &ReviewDetailsView| {
+            (
+                view.active_file_list.clone(),
+                matches!(view.navigation_mode, NavigationMode::Lines),
+            )
+        };
+        let tab = KeyEvent::from(KeyCode::Tab);
+        let back_tab = KeyEvent::from(KeyCode::BackTab);
+
+        view.handle_key_events(&mut app, &tab).unwrap();
+        assert_eq!(focus(&view), (FileListType::Viewed, false));
+        view.handle_key_events(&mut app, &tab).unwrap();
+        assert_eq!(focus(&view), (FileListType::Viewed, true));
+        assert_eq!(view.get_selected_file().unwrap().path, "README.md");
+        view.handle_key_events(&mut app, &tab).unwrap();
+        assert_eq!(focus(&view), (FileListType::NotViewed, false));
+
+        view.handle_key_events(&mut app, &back_tab).unwrap();
+        assert_eq!(focus(&view), (FileListType::NotViewed, true));
+        view.handle_key_events(&mut app, &back_tab).unwrap();
+        assert_eq!(focus(&view), (FileListType::Viewed, false));
+        view.handle_key_events(&mut app, &back_tab).unwrap();
+        assert_eq!(focus(&view), (FileListType::NotViewed, false));
+    }
+
+    #[test]
+    fn test_comments_outside_diff_count() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        let params = CommentsLoadParams {
+            review_id: Arc::from(review.id.clone()),
+            file_path: Arc::new(None),
+            line_number: Arc::new(None),
+        };
+        let comments = vec![
+            Comment::test_comment(&review.id, "src/main.rs", None, "Still in the diff"),
+            Comment::test_comment(&review.id, "src/removed.rs", None, "File comment"),
+            Comment::test_comment(&review.id, "src/removed.rs", Some(3), "Line comment"),
+        ];
+        view.handle_comments_loading_state(
+            &params,
+            &CommentsLoadingState::Loaded(Arc::new(comments)),
+        );
+
+        // The diff is not loaded yet
+        assert_eq!(view.comments_outside_diff_count(), 0);
+
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        assert_eq!(view.comments_outside_diff_count(), 2);
+    }
+
+    /// A view of a review whose diff is loaded, with `src/main.rs` viewed, one resolved and
+    /// two unresolved comments and one comment added since it was opened
+    async fn create_review_summary_test_view(app: &mut App) -> ReviewDetailsView {
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature")
+            .build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.viewed_files = Arc::new(vec![
+            "src/main.rs".to_string(),
+            "src/removed.rs".to_string(),
+        ]);
+
+        let mut resolved = Comment::test_comment(&review.id, "src/lib.rs", None, "Resolved");
+        resolved.resolved = true;
+        let comments = vec![
+            Comment::test_comment(&review.id, "src/main.rs", None, "Unresolved"),
+            Comment::test_comment(&review.id, "src/lib.rs", Some(3), "Unresolved"),
+            resolved,
+        ];
+        view.handle_comments_loading_state(
+            &view.comments_load_params().unwrap(),
+            &CommentsLoadingState::Loaded(Arc::new(comments)),
+        );
+
+        let comment = Comment::test_comment(&review.id, "src/main.rs", None, "New comment");
+        view.handle_app_events(app, &AppEvent::CommentCreated(Arc::new(comment)));
+        // A comment of another review is not counted
+        let other_comment = Comment::test_comment("other-review", "src/main.rs", None, "Other");
+        view.handle_app_events(app, &AppEvent::CommentCreated(Arc::new(other_comment)));
+        while app.events.try_recv().is_some() {}
+
+        view
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_shows_summary_on_close() {
+        let mut app = create_test_app().await;
+        let mut view = create_review_summary_test_view(&mut app).await;
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Esc))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ViewClose)));
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewSummaryOpen(summary)) => {
+                assert_eq!(
+                    **summary,
+                    ReviewSummary {
+                        review_title: "main (unknown) -> feature (unknown)".to_string(),
+                        viewed_file_count: 1,
+                        file_count: 3,
+                        comments_added: 1,
+                        unresolved_comment_count: 2,
+                        comment_count: 3,
+                    }
+                );
+            }
+            _ => panic!("Expected ReviewSummaryOpen event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_skips_summary_when_disabled() {
+        let mut app = create_test_app().await;
+        app.settings.show_review_summary = false;
+        let mut view = create_review_summary_test_view(&mut app).await;
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Esc))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ViewClose)));
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_quick_review_has_no_summary() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build()).with_quick_review(None);
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Esc))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ViewClose)));
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[test]
+    fn test_comments_on_old_path_of_renamed_file() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        let params = CommentsLoadParams {
+            review_id: Arc::from(review.id.clone()),
+            file_path: Arc::new(None),
+            line_number: Arc::new(None),
+        };
+        let comments = vec![Comment::test_comment(
+            &review.id,
+            "src/old.rs",
+            Some(1),
+            "Line comment",
+        )];
+        view.handle_comments_loading_state(
+            &params,
+            &CommentsLoadingState::Loaded(Arc::new(comments)),
+        );
+        let renamed_file = DiffFile::from_content("src/new.rs", "@@ -1,1 +1,1 @@\n-old\n+new");
+        let diff = Diff::from_files(vec![renamed_file.clone()]).with_renamed_paths(HashMap::from(
+            [("src/old.rs".to_string(), "src/new.rs".to_string())],
+        ));
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(diff)));
+
+        assert_eq!(
+            view.comment_indicator(&renamed_file),
+            CommentIndicator::LineComment
+        );
+        let line = view.render_diff_line(
+            &Theme::default(),
+            &Settings::default(),
+            &renamed_file,
+            1,
+            "-old",
+            String::new(),
+            None,
+        );
+        assert!(line.to_string().starts_with(LINE_COMMENT_INDICATOR));
+        assert_eq!(view.comments_outside_diff_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_comments_outside_diff() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.comment_counts_by_file = Arc::new(HashMap::from([("src/removed.rs".to_string(), 2)]));
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_overview_navigation_and_selection() {
+        let mut app = create_test_app().await;
+        let review = Review::builder().build();
+        let review_id = review.id.clone();
+        let mut view = ReviewDetailsView::new(review);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.handle_file_views_loaded(
+            &review_id,
+            &Arc::new(vec!["README.md".to_string()]),
+            &Arc::new(vec![]),
+            &Default::default(),
+        );
+
+        let key = |code| KeyEvent {
+            code,
+            modifiers: ratatui::crossterm::event::KeyModifiers::empty(),
+            kind: ratatui::crossterm::event::KeyEventKind::Press,
+            state: ratatui::crossterm::event::KeyEventState::empty(),
+        };
+
+        view.handle_key_events(&mut app, &key(KeyCode::Char('o')))
+            .unwrap();
+        assert!(view.show_overview);
+        assert_eq!(view.overview_selected_index, 0);
+
+        // Navigation is bounded by the number of files in the diff
+        for _ in 0..5 {
+            view.handle_key_events(&mut app, &key(KeyCode::Down))
+                .unwrap();
+        }
+        assert_eq!(view.overview_selected_index, 2);
+        view.handle_key_events(&mut app, &key(KeyCode::Char('k')))
+            .unwrap();
+        assert_eq!(view.overview_selected_index, 1);
+
+        // File navigation keys don't affect the file lists while the overview is shown
+        assert_eq!(view.selected_file_index, 0);
+
+        view.handle_key_events(&mut app, &key(KeyCode::Enter))
+            .unwrap();
+        assert!(!view.show_overview);
+        assert_eq!(view.get_selected_file().unwrap().path, "src/lib.rs");
+
+        // Reopening the overview preselects the current file and Esc closes it again
+        view.handle_key_events(&mut app, &key(KeyCode::Char('l')))
+            .unwrap();
+        view.handle_key_events(&mut app, &key(KeyCode::Char('o')))
+            .unwrap();
+        assert_eq!(view.overview_selected_index, 2);
+        view.handle_key_events(&mut app, &key(KeyCode::Esc))
+            .unwrap();
+        assert!(!view.show_overview);
+        assert!(!app.events.has_pending_events());
+    }
+
+    fn create_additions_only_test_view() -> ReviewDetailsView {
+        let mut view = ReviewDetailsView::new(Review::builder().base_branch("main").build());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        // src/lib.rs: hunk header, context line, removed line and two added lines
+        view.selected_file_index = 1;
+        view.navigation_mode = NavigationMode::Lines;
+        view
+    }
+
+    #[test]
+    fn test_review_details_view_additions_only_navigation() {
+        let mut view = create_additions_only_test_view();
+        view.selected_line_index = 1;
+
+        // The selected context line is filtered out, so the next added line is selected
+        view.toggle_additions_only();
+        assert!(view.additions_only);
+        assert_eq!(view.selected_line_index, 3);
+
+        view.go_down();
+        assert_eq!(view.selected_line_index, 4);
+        view.go_down();
+        assert_eq!(view.selected_line_index, 4);
+        view.go_up();
+        assert_eq!(view.selected_line_index, 3);
+        view.go_up();
+        assert_eq!(view.selected_line_index, 0);
+        view.go_up();
+        assert_eq!(view.selected_line_index, 0);
+
+        view.toggle_additions_only();
+        view.go_down();
+        assert_eq!(view.selected_line_index, 1);
+    }
+
+    #[test]
+    fn test_review_details_view_additions_only_rows_keep_line_indices() {
+        let mut view = create_additions_only_test_view();
+        view.additions_only = true;
+        let file = view.get_selected_file().unwrap();
+
+        assert_eq!(
+            view.diff_rows(file),
+            vec![
+                DiffRow::Line(0, "@@ -1,2 +1,3 @@"),
+                DiffRow::Line(3, "+new"),
+                DiffRow::Line(4, "+added"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_additions_only() {
+        let mut view = create_additions_only_test_view();
+        view.selected_line_index = 3;
+        view.lines_with_comments = Arc::new(HashMap::from([("src/lib.rs".to_string(), vec![4])]));
+        view.toggle_additions_only();
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    fn create_all_files_test_view() -> ReviewDetailsView {
+        let mut view = ReviewDetailsView::new(Review::builder().base_branch("main").build());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.toggle_all_files();
+        view
+    }
+
+    #[test]
+    fn test_review_details_view_all_files_navigation_crosses_files() {
+        let mut view = create_all_files_test_view();
+        // Header of src/main.rs, its 3 lines and the header of src/lib.rs come before line 0
+        assert_eq!(view.file_line_offset(1), 5);
+        view.go_down();
+        assert_eq!((view.selected_file_index, view.scroll_offset), (1, 4));
+
+        view.toggle_navigation_mode();
+        view.selected_line_index = 4;
+        view.go_down();
+        assert_eq!((view.selected_file_index, view.selected_line_index), (2, 0));
+        assert_eq!(view.selected_scroll_line(), 11);
+        view.go_up();
+        assert_eq!((view.selected_file_index, view.selected_line_index), (1, 4));
+        view.selected_line_index = 0;
+        view.go_up();
+        assert_eq!((view.selected_file_index, view.selected_line_index), (0, 2));
+
+        // Without the all files mode the navigation stops at the end of the file
+        view.toggle_all_files();
+        view.go_up();
+        view.go_up();
+        view.go_up();
+        assert_eq!((view.selected_file_index, view.selected_line_index), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_all_files_comments_use_file_lines() {
+        let mut app = create_test_app().await;
+        let mut view = create_all_files_test_view();
+        view.toggle_navigation_mode();
+        for _ in 0..4 {
+            view.go_down();
+        }
+
+        view.open_comments(&mut app);
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::CommentsOpen {
+                file_path,
+                line_number,
+                ..
+            }) => {
+                assert_eq!(file_path.as_ref(), "src/lib.rs");
+                assert_eq!(*line_number, Some(1));
+            }
+            _ => panic!("Expected CommentsOpen event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_all_files() {
+        let mut view = create_all_files_test_view();
+        view.toggle_navigation_mode();
+        for _ in 0..4 {
+            view.go_down();
+        }
+        view.lines_with_comments = Arc::new(HashMap::from([
+            ("src/main.rs".to_string(), vec![1]),
+            ("src/lib.rs".to_string(), vec![1]),
+        ]));
+
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_diff_direction() {
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature")
+            .base_sha_str("abc1234567")
+            .target_sha_str("def4567890")
+            .target_sha_changed(Some("9876543210".to_string()))
+            .build();
+        let app = App {
+            view_stack: vec![Box::new(ReviewDetailsView::new(review))],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[test]
+    fn test_review_details_view_session_state() {
+        let review = Review::builder().build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        view.selected_file_index = 1;
+
+        assert_eq!(
+            view.session_state(),
+            Some(SessionState {
+                review_id: review.id.clone(),
+                file_path: Some("src/lib.rs".to_string()),
+                line_number: None,
+            })
+        );
+
+        view.navigation_mode = NavigationMode::Lines;
+        view.selected_line_index = 2;
+        assert_eq!(view.session_state().unwrap().line_number, Some(2));
+
+        // Nothing to resume while the review is still loading
+        assert_eq!(ReviewDetailsView::new_loading().session_state(), None);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_quick_review_only_loads_the_diff() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new_loading().with_quick_review(Some(Arc::from("src/")));
+        let review = Review::builder()
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ReviewLoadingState(ReviewLoadingState::Loaded(Arc::new(review))),
+        );
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::GitDiffLoad {
+                base_sha,
+                target_sha,
+                pathspec,
+            }) => {
+                assert_eq!(base_sha.as_ref(), "abc123");
+                assert_eq!(target_sha.as_ref(), "def456");
+                assert_eq!(pathspec.as_deref(), Some("src/"));
+            }
+            _ => panic!("Expected GitDiffLoad event, got: {event:?}"),
+        }
+        // No file views or comments are loaded for a quick review
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_loads_the_diff_of_a_patch_review() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new_loading();
+        let review = Review::builder().patch_path("/tmp/change.diff").build();
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ReviewLoadingState(ReviewLoadingState::Loaded(Arc::new(review))),
+        );
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::PatchDiffLoad(patch_path)) => {
+                assert_eq!(patch_path.as_ref(), "/tmp/change.diff");
+            }
+            _ => panic!("Expected PatchDiffLoad event, got: {event:?}"),
+        }
+        assert_eq!(view.diff_state, GitDiffLoadingState::Loading);
+
+        let diff = Arc::new(crate::unified_diff::parse_unified_diff(
+            "--- a/a.txt\n+++ b/a.txt\n@@ -1 +1 @@\n-a\n+b\n",
+        ));
+        // The diff of another patch file is ignored
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::PatchDiffLoadingState {
+                patch_path: Arc::from("/tmp/other.diff"),
+                state: GitDiffLoadingState::Loaded(Arc::clone(&diff)),
+            },
+        );
+        assert_eq!(view.diff_state, GitDiffLoadingState::Loading);
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::PatchDiffLoadingState {
+                patch_path: Arc::from("/tmp/change.diff"),
+                state: GitDiffLoadingState::Loaded(diff),
+            },
+        );
+        assert_eq!(view.diff.files.len(), 1);
+        assert_eq!(view.diff.files[0].path, "a.txt");
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_quick_review_disables_stored_actions() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewDetailsView::new(Review::builder().build()).with_quick_review(None);
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+
+        for code in [' ', 'X', 'c', 'r', 'a', 'C'] {
+            view.handle_key_events(
+                &mut app,
+                &KeyEvent::new(
+                    KeyCode::Char(code),
+                    ratatui::crossterm::event::KeyModifiers::NONE,
+                ),
+            )
+            .unwrap();
+
+            assert!(!app.events.has_pending_events());
+            assert_eq!(view.notice.as_deref(), Some(QUICK_REVIEW_NOTICE));
+        }
+        assert_eq!(view.session_state(), None);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_patch_review_disables_git_actions() {
+        let mut app = create_test_app().await;
+        let mut view =
+            ReviewDetailsView::new(Review::builder().patch_path("/tmp/change.diff").build());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        let show_hunk_blame = app.settings.show_hunk_blame;
+
+        for code in ['y', 'D', 'r', 'd', 'e', 'E', 'b'] {
+            view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char(code)))
+                .unwrap();
+
+            assert!(!app.events.has_pending_events());
+            assert_eq!(view.notice.as_deref(), Some(PATCH_REVIEW_NOTICE));
+        }
+        assert_eq!(app.settings.show_hunk_blame, show_hunk_blame);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_quick_review() {
+        let review = Review::builder()
+            .base_branch("HEAD~2")
+            .target_branch("HEAD")
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
+        let view = ReviewDetailsView::new(review).with_quick_review(Some(Arc::from("src/")));
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        app.handle_app_events(&AppEvent::GitDiffLoadingState {
+            base_sha: Arc::from("abc123"),
+            target_sha: Arc::from("def456"),
+            state: GitDiffLoadingState::Loaded(create_jump_test_diff()),
+        });
+
+        assert_snapshot!(render_app_to_terminal_backend(app))
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_ignores_stale_diff_loads() {
+        let mut app = create_test_app().await;
+        let old_review = Review::builder()
+            .base_sha_str("old-base")
+            .target_sha_str("old-target")
+            .build();
+        let review = Review::builder()
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
+        let mut view = ReviewDetailsView::new_loading();
+        let stale_diff = Arc::new(Diff::from_files(vec![DiffFile::from_content(
+            "stale.rs",
+            "@@ -1 +1 @@\n-old\n+new",
+        )]));
+
+        // Both reviews request their diff before either diff is loaded
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ReviewLoadingState(ReviewLoadingState::Loaded(Arc::new(old_review))),
+        );
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ReviewLoadingState(ReviewLoadingState::Loaded(Arc::new(review))),
+        );
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::GitDiffLoadingState {
+                base_sha: Arc::from("abc123"),
+                target_sha: Arc::from("def456"),
+                state: GitDiffLoadingState::Loaded(create_jump_test_diff()),
+            },
+        );
+        // The diff of the previous review finishes last and must not replace the current one
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::GitDiffLoadingState {
+                base_sha: Arc::from("old-base"),
+                target_sha: Arc::from("old-target"),
+                state: GitDiffLoadingState::Loaded(stale_diff),
+            },
+        );
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::GitDiffLoadingState {
+                base_sha: Arc::from("old-base"),
+                target_sha: Arc::from("old-target"),
+                state: GitDiffLoadingState::Error("Stale error".into()),
+            },
+        );
+
+        assert!(matches!(view.diff_state, GitDiffLoadingState::Loaded(_)));
+        assert_eq!(view.diff, create_jump_test_diff());
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_file_jump_deferred_until_diff_loaded() {
+        let review = Review::builder()
+            .base_sha_str("abc123")
+            .target_sha_str("def456")
+            .build();
+        let review_id = review.id.clone();
+        let view = ReviewDetailsView::new_loading_with_file_jump("src/lib.rs".into(), Some(3));
+
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+
+        app.handle_app_events(&AppEvent::ReviewLoadingState(ReviewLoadingState::Loaded(
+            Arc::new(review),
+        )));
+        app.handle_app_events(&AppEvent::FileViewsLoaded {
+            review_id: review_id.into(),
+            viewed_files: Arc::new(vec!["README.md".to_string()]),
+            skipped_files: Arc::new(vec![]),
+            content_hashes: Default::default(),
+        });
+
+        // The jump is still pending while the diff is not loaded
+        {
+            let view = app.view_stack[0]
+                .as_any()
+                .downcast_ref::<ReviewDetailsView>()
+                .unwrap();
+            assert_eq!(
+                view.pending_file_jump,
+                Some(FileJump {
+                    file_path: "src/lib.rs".into(),
+                    line_number: Some(3),
+                })
+            );
+            assert_eq!(view.selected_file_index, 0);
+        }
+
+        app.handle_app_events(&AppEvent::GitDiffLoadingState {
+            base_sha: Arc::from("abc123"),
+            target_sha: Arc::from("def456"),
+            state: GitDiffLoadingState::Loaded(create_jump_test_diff()),
+        });
+
+        let view = app.view_stack[0]
+            .as_any()
+            .downcast_ref::<ReviewDetailsView>()
+            .unwrap();
+        assert_eq!(view.pending_file_jump, None);
+        assert_eq!(view.active_file_list, FileListType::NotViewed);
+        assert_eq!(view.selected_file_index, 1);
+        assert_eq!(view.selected_line_index, 3);
+        assert!(matches!(view.navigation_mode, NavigationMode::Lines));
+        assert_eq!(view.get_selected_file().unwrap().path, "src/lib.rs");
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_review_diff_jump() {
+        let review = Review::builder().build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        let mut app = create_test_app().await;
+        let jump = |review_id: &str| AppEvent::ReviewDiffJump {
+            review_id: Arc::from(review_id),
+            file_path: Arc::from("src/lib.rs"),
+            line_number: 3,
+        };
+
+        // Deferred until the diff is loaded
+        view.handle_app_events(&mut app, &jump(&review.id));
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+        assert_eq!(view.selected_file_index, 1);
+        assert_eq!(view.selected_line_index, 3);
+        assert!(matches!(view.navigation_mode, NavigationMode::Lines));
+
+        // Jumps of other reviews are ignored
+        view.navigation_mode = NavigationMode::Files;
+        view.selected_file_index = 0;
+        view.handle_app_events(&mut app, &jump("other-review"));
+        assert_eq!(view.selected_file_index, 0);
+
+        view.handle_app_events(&mut app, &jump(&review.id));
+        assert_eq!(view.selected_file_index, 1);
+    }
+
+    #[test]
+    fn test_review_details_view_file_jump_to_viewed_file_without_line() {
+        let mut view = ReviewDetailsView::new_loading_with_file_jump("README.md".into(), None);
+        view.viewed_files = Arc::new(vec!["README.md".to_string()]);
+
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+
+        assert_eq!(view.active_file_list, FileListType::Viewed);
+        assert_eq!(view.selected_file_index, 0);
+        assert_eq!(view.selected_line_index, 0);
+        assert!(matches!(view.navigation_mode, NavigationMode::Files));
+        assert_eq!(view.get_selected_file().unwrap().path, "README.md");
+    }
+
+    #[test]
+    fn test_review_details_view_file_jump_unknown_file() {
+        let mut view = ReviewDetailsView::new_loading_with_file_jump("missing.rs".into(), Some(1));
+
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(create_jump_test_diff()));
+
+        assert_eq!(view.pending_file_jump, None);
+        assert_eq!(view.active_file_list, FileListType::NotViewed);
+        assert_eq!(view.selected_file_index, 0);
+        assert!(matches!(view.navigation_mode, NavigationMode::Files));
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_open_comments_file_level() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        let mut app = create_test_app().await;
+
+        // Set up a diff with files
+        let files = vec![DiffFile::from_content("src/main.rs", "line1\nline2\nline3")];
+        let diff = Arc::new(Diff::from_files(files));
+        view.diff = diff;
+        view.navigation_mode = NavigationMode::Files;
+        view.selected_file_index = 0;
+
+        // Initial state should have no pending events
+        assert!(!app.events.has_pending_events());
+
+        // Call open_comments (should open file-level comments)
+        view.open_comments(&mut app);
+
+        // Should have sent CommentsOpen event for file-level comments
+        assert!(app.events.has_pending_events());
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::CommentsOpen {
+                review_id,
+                file_path,
+                line_number,
+                ..
+            }) => {
+                assert_eq!(review_id.as_ref(), review.id);
+                assert_eq!(file_path.as_ref(), "src/main.rs");
+                assert_eq!(*line_number, None); // File-level comments
+            }
+            _ => panic!("Expected CommentsOpen event, got: {event:?}"),
+        }
+    }
+
+    fn create_deleted_files_test_view() -> ReviewDetailsView {
+        let mut view = ReviewDetailsView::new(Review::builder().base_branch("main").build());
+        let diff = Diff::from_files(vec![
+            DiffFile::from_content("src/empty.rs", ""),
+            DiffFile::from_content("src/old.rs", "@@ -1,2 +0,0 @@\n-fn old() {\n-}"),
+        ])
+        .with_deleted_paths(HashSet::from([
+            "src/empty.rs".to_string(),
+            "src/old.rs".to_string(),
+        ]));
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(diff)));
+        view
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_open_comments_on_deleted_files() {
+        let mut app = create_test_app().await;
+        let mut view = create_deleted_files_test_view();
+        let comments_open = |app: &mut App| {
+            let event = app.events.try_recv().unwrap();
+            match &*event {
+                Event::App(AppEvent::CommentsOpen {
+                    file_path,
+                    line_number,
+                    ..
+                }) => (file_path.to_string(), *line_number),
+                _ => panic!("Expected CommentsOpen event, got: {event:?}"),
+            }
+        };
+
+        // An empty deleted file has no lines, only file comments
+        view.toggle_navigation_mode();
+        assert!(matches!(view.navigation_mode, NavigationMode::Files));
+        view.open_comments(&mut app);
+        assert_eq!(comments_open(&mut app), ("src/empty.rs".to_string(), None));
+
+        // The removed lines of a deleted file can be commented
+        view.go_down();
+        view.toggle_navigation_mode();
+        view.go_down();
+        view.open_comments(&mut app);
+        assert_eq!(comments_open(&mut app), ("src/old.rs".to_string(), Some(1)));
+    }
+
+    #[test]
+    fn test_comment_indicator_deleted_file() {
+        let mut view = create_deleted_files_test_view();
+        view.files_with_file_and_or_line_comments = Arc::new(vec!["src/old.rs".to_string()]);
+        view.lines_with_comments = Arc::new(HashMap::from([("src/old.rs".to_string(), vec![1])]));
+        view.files_with_only_resolved_comments = Arc::new(vec!["src/empty.rs".to_string()]);
+
+        assert_eq!(
+            view.comment_indicator(&view.diff.files[1]),
+            CommentIndicator::DeletedFileComment
+        );
+        assert_eq!(
+            view.comment_indicator(&view.diff.files[0]),
+            CommentIndicator::ResolvedComment
+        );
+    }
 
-        // Call open_comments (should open file-level comments)
-        view.open_comments(&mut app);
+    #[tokio::test]
+    async fn test_review_details_view_render_deleted_empty_file() {
+        let mut view = create_deleted_files_test_view();
+        view.files_with_file_and_or_line_comments = Arc::new(vec!["src/empty.rs".to_string()]);
+        view.files_with_file_comments = Arc::new(vec!["src/empty.rs".to_string()]);
+        let app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
 
-        // Should have sent CommentsOpen event for file-level comments
-        assert!(app.events.has_pending_events());
-        let event = app.events.try_recv().unwrap();
-        match &*event {
-            Event::App(AppEvent::CommentsOpen {
-                review_id,
-                file_path,
-                line_number,
-            }) => {
-                assert_eq!(review_id.as_ref(), review.id);
-                assert_eq!(file_path.as_ref(), "src/main.rs");
-                assert_eq!(*line_number, None); // File-level comments
-            }
-            _ => panic!("Expected CommentsOpen event, got: {event:?}"),
-        }
+        assert_snapshot!(render_app_to_terminal_backend(app))
     }
 
     #[tokio::test]
@@ -1742,10 +7007,10 @@ mod tests {
         let mut app = create_test_app().await;
 
         // Set up a diff with files
-        let files = vec![DiffFile {
-            path: "src/lib.rs".to_string(),
-            content: "line1\nline2\nline3\nline4\nline5".to_string(),
-        }];
+        let files = vec![DiffFile::from_content(
+            "src/lib.rs",
+            "line1\nline2\nline3\nline4\nline5",
+        )];
         let diff = Arc::new(Diff::from_files(files));
         view.diff = diff;
         view.navigation_mode = NavigationMode::Lines; // Switch to Lines mode
@@ -1766,6 +7031,7 @@ mod tests {
                 review_id,
                 file_path,
                 line_number,
+                ..
             }) => {
                 assert_eq!(review_id.as_ref(), review.id);
                 assert_eq!(file_path.as_ref(), "src/lib.rs");
@@ -1775,6 +7041,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_review_details_view_acknowledged_comments_do_not_need_attention() {
+        let review = Review::builder().build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        let mut acknowledged = Comment::test_comment(&review.id, "src/lib.rs", Some(1), "Later");
+        acknowledged.status = CommentStatus::WontFix;
+        let open = Comment::test_comment(&review.id, "src/main.rs", Some(1), "Fix this");
+
+        view.handle_comments_loading_state(
+            &CommentsLoadParams {
+                review_id: Arc::from(review.id.as_str()),
+                file_path: None.into(),
+                line_number: None.into(),
+            },
+            &CommentsLoadingState::Loaded(Arc::new(vec![acknowledged, open])),
+        );
+
+        // The acknowledged comment is indicated like a resolved one but still counts as unresolved
+        assert_eq!(
+            view.files_with_only_resolved_comments.as_slice(),
+            ["src/lib.rs".to_string()]
+        );
+        assert_eq!(
+            view.files_with_file_and_or_line_comments.as_slice(),
+            ["src/main.rs".to_string()]
+        );
+        assert_eq!(view.unresolved_comment_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_resolves_line_comments_from_the_diff() {
+        let review = Review::builder().base_branch("main").build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        view.diff = Arc::new(Diff::from_files(vec![DiffFile::from_content(
+            "src/lib.rs",
+            "line1\nline2\nline3",
+        )]));
+        view.navigation_mode = NavigationMode::Lines;
+        view.selected_line_index = 1;
+        let mut app = App {
+            view_stack: vec![Box::new(view)],
+            ..create_test_app().await
+        };
+        review.save(app.database.pool()).await.unwrap();
+        for content in ["First", "Second"] {
+            Comment::test_comment(&review.id, "src/lib.rs", Some(1), content)
+                .create(app.database.pool())
+                .await
+                .unwrap();
+        }
+        let process_events = async |app: &mut App| {
+            while let Some(event) = app.events.try_recv() {
+                crate::event_handler::EventProcessor::process_event(app, event)
+                    .await
+                    .unwrap();
+            }
+        };
+        let resolved_only_lines = |app: &App| {
+            app.view_stack[0]
+                .as_any()
+                .downcast_ref::<ReviewDetailsView>()
+                .unwrap()
+                .lines_with_only_resolved_comments
+                .get("src/lib.rs")
+                .cloned()
+        };
+
+        app.events.send(AppEvent::CommentsLoad(CommentsLoadParams {
+            review_id: Arc::from(review.id.as_str()),
+            file_path: None.into(),
+            line_number: None.into(),
+        }));
+        process_events(&mut app).await;
+        assert_eq!(resolved_only_lines(&app), None);
+
+        app.handle_key_events(&KeyEvent::from(KeyCode::Char('R')))
+            .unwrap();
+        process_events(&mut app).await;
+
+        // The comments of the line are resolved without opening them
+        assert_eq!(resolved_only_lines(&app), Some(vec![1]));
+        assert_eq!(app.view_stack.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_review_details_view_open_comments_no_review() {
         let mut view = ReviewDetailsView::new_loading();
@@ -1813,10 +7163,7 @@ mod tests {
         let mut app = create_test_app().await;
 
         // Set up a diff with one file
-        let files = vec![DiffFile {
-            path: "src/main.rs".to_string(),
-            content: "line1\nline2".to_string(),
-        }];
+        let files = vec![DiffFile::from_content("src/main.rs", "line1\nline2")];
         let diff = Arc::new(Diff::from_files(files));
         view.diff = diff;
         view.selected_file_index = 5; // Out of bounds index
@@ -1835,10 +7182,7 @@ mod tests {
         let mut app = create_test_app().await;
 
         // Set up a diff with files
-        let files = vec![DiffFile {
-            path: "src/test.rs".to_string(),
-            content: "test content".to_string(),
-        }];
+        let files = vec![DiffFile::from_content("src/test.rs", "test content")];
         let diff = Arc::new(Diff::from_files(files));
         view.diff = diff;
         view.selected_file_index = 0;
@@ -1862,6 +7206,7 @@ mod tests {
                 review_id,
                 file_path,
                 line_number,
+                ..
             }) => {
                 assert_eq!(review_id.as_ref(), review.id);
                 assert_eq!(file_path.as_ref(), "src/test.rs");
@@ -1876,10 +7221,7 @@ mod tests {
         let review = Review::builder().build();
         let view = ReviewDetailsView::new(review);
 
-        let diff_file = DiffFile {
-            path: "src/main.rs".to_string(),
-            content: "test content".to_string(),
-        };
+        let diff_file = DiffFile::from_content("src/main.rs", "test content");
 
         // No comments set up
         let indicator = view.comment_indicator(&diff_file);
@@ -1891,10 +7233,7 @@ mod tests {
         let review = Review::builder().build();
         let mut view = ReviewDetailsView::new(review);
 
-        let diff_file = DiffFile {
-            path: "src/main.rs".to_string(),
-            content: "test content".to_string(),
-        };
+        let diff_file = DiffFile::from_content("src/main.rs", "test content");
 
         // Set up file with file comments only
         view.files_with_file_comments = Arc::new(vec!["src/main.rs".to_string()]);
@@ -1910,10 +7249,7 @@ mod tests {
         let review = Review::builder().build();
         let mut view = ReviewDetailsView::new(review);
 
-        let diff_file = DiffFile {
-            path: "src/main.rs".to_string(),
-            content: "test content".to_string(),
-        };
+        let diff_file = DiffFile::from_content("src/main.rs", "test content");
 
         // Set up file with line comments only
         view.files_with_file_and_or_line_comments = Arc::new(vec!["src/main.rs".to_string()]);
@@ -1931,10 +7267,7 @@ mod tests {
         let review = Review::builder().build();
         let mut view = ReviewDetailsView::new(review);
 
-        let diff_file = DiffFile {
-            path: "src/main.rs".to_string(),
-            content: "test content".to_string(),
-        };
+        let diff_file = DiffFile::from_content("src/main.rs", "test content");
 
         // Set up file with both file and line comments
         view.files_with_file_comments = Arc::new(vec!["src/main.rs".to_string()]);
@@ -2244,4 +7577,430 @@ mod tests {
             _ => panic!("Expected CommentsLoad event, got: {event:?}"),
         }
     }
+
+    /// Create a repository with `base` and `target` branches on the same commit.
+    /// Returns the SHA of that commit.
+    fn create_outdated_diff_test_repo(repo_path: &std::path::Path) -> String {
+        let repo = git2::Repository::init(repo_path).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit_id = repo
+            .commit(None, &signature, &signature, "Initial", &tree, &[])
+            .unwrap();
+        let commit = repo.find_commit(commit_id).unwrap();
+        repo.branch("base", &commit, true).unwrap();
+        repo.branch("target", &commit, true).unwrap();
+        commit_id.to_string()
+    }
+
+    fn advance_branch(repo_path: &std::path::Path, branch: &str) {
+        let repo = git2::Repository::open(repo_path).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo
+            .find_branch(branch, git2::BranchType::Local)
+            .unwrap()
+            .get()
+            .peel_to_commit()
+            .unwrap();
+        let tree = parent.tree().unwrap();
+        repo.commit(
+            Some(&format!("refs/heads/{branch}")),
+            &signature,
+            &signature,
+            "Next",
+            &tree,
+            &[&parent],
+        )
+        .unwrap();
+    }
+
+    #[cfg(feature = "watch-refs")]
+    #[tokio::test]
+    async fn test_review_details_view_refs_changed_offers_refresh() {
+        let mut app = create_test_app().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let sha = create_outdated_diff_test_repo(temp_dir.path());
+        app.repo_path = temp_dir.path().to_string_lossy().to_string();
+        let review = Review::builder()
+            .base_branch("base")
+            .target_branch("target")
+            .base_sha_str(&sha)
+            .target_sha_str(&sha)
+            .build();
+        let review_id = review.id.clone();
+        let mut view = ReviewDetailsView::new(review);
+
+        // Refs that changed without moving the branches of the review are ignored
+        view.handle_app_events(&mut app, &AppEvent::ActiveReviewRefsChanged);
+        assert!(!app.events.has_pending_events());
+
+        advance_branch(temp_dir.path(), "target");
+        view.handle_app_events(&mut app, &AppEvent::ActiveReviewRefsChanged);
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ReviewRefreshOpen {
+                review_id: refresh_review_id,
+                options,
+            }) => {
+                assert_eq!(refresh_review_id.as_ref(), review_id);
+                assert!(!options.can_refresh_base);
+                assert!(options.can_refresh_target);
+            }
+            _ => panic!("Expected ReviewRefreshOpen event, got: {event:?}"),
+        }
+        assert_eq!(
+            view.notice.as_deref(),
+            Some("The branches of the review moved")
+        );
+        assert!(view.review.as_ref().unwrap().target_sha_changed.is_some());
+
+        // The same moved head is offered once
+        view.handle_app_events(&mut app, &AppEvent::ActiveReviewRefsChanged);
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[cfg(feature = "watch-refs")]
+    #[tokio::test]
+    async fn test_review_details_view_refs_changed_ignores_patch_review() {
+        let mut app = create_test_app().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let sha = create_outdated_diff_test_repo(temp_dir.path());
+        app.repo_path = temp_dir.path().to_string_lossy().to_string();
+        let review = Review::builder()
+            .base_branch("base")
+            .target_branch("target")
+            .base_sha_str(&sha)
+            .target_sha_str(&sha)
+            .patch_path("/tmp/change.diff")
+            .build();
+        let mut view = ReviewDetailsView::new(review);
+
+        advance_branch(temp_dir.path(), "target");
+        view.handle_app_events(&mut app, &AppEvent::ActiveReviewRefsChanged);
+
+        assert!(!app.events.has_pending_events());
+        assert!(view.notice.is_none());
+    }
+
+    #[test]
+    fn test_review_details_view_outdated_diff_detection() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let sha = create_outdated_diff_test_repo(temp_dir.path());
+        let repo_path = temp_dir.path().to_string_lossy().to_string();
+        let review = Review::builder()
+            .base_branch("base")
+            .target_branch("target")
+            .base_sha_str(&sha)
+            .target_sha_str(&sha)
+            .build();
+
+        assert!(ReviewDetailsView::outdated_diff_refresh_options(&review, &repo_path).is_none());
+
+        advance_branch(temp_dir.path(), "target");
+
+        let options =
+            ReviewDetailsView::outdated_diff_refresh_options(&review, &repo_path).unwrap();
+        assert!(!options.can_refresh_base);
+        assert!(options.can_refresh_target);
+        assert!(options.can_duplicate);
+
+        // Reviews without SHAs or with deleted branches can't be checked
+        let review_without_shas = Review::builder()
+            .base_branch("base")
+            .target_branch("target")
+            .build();
+        assert!(
+            ReviewDetailsView::outdated_diff_refresh_options(&review_without_shas, &repo_path)
+                .is_none()
+        );
+        let review_with_missing_branch = Review::builder()
+            .base_branch("base")
+            .target_branch("deleted")
+            .base_sha_str(&sha)
+            .target_sha_str(&sha)
+            .build();
+        assert!(
+            ReviewDetailsView::outdated_diff_refresh_options(
+                &review_with_missing_branch,
+                &repo_path
+            )
+            .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_open_comments_on_outdated_diff() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let sha = create_outdated_diff_test_repo(temp_dir.path());
+        advance_branch(temp_dir.path(), "base");
+
+        let review = Review::builder()
+            .base_branch("base")
+            .target_branch("target")
+            .base_sha_str(&sha)
+            .target_sha_str(&sha)
+            .build();
+        let mut view = ReviewDetailsView::new(review.clone());
+        view.diff = Arc::new(Diff::from_files(vec![DiffFile::from_content(
+            "src/main.rs",
+            "line1\nline2\nline3",
+        )]));
+        let mut app = create_test_app().await;
+        app.repo_path = temp_dir.path().to_string_lossy().to_string();
+
+        // Line comments ask to refresh first
+        view.navigation_mode = NavigationMode::Lines;
+        view.selected_line_index = 1;
+        view.open_comments(&mut app);
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::CommentsOpenOutdatedDiff {
+                review_id,
+                file_path,
+                line_number,
+                options,
+            }) => {
+                assert_eq!(review_id.as_ref(), review.id);
+                assert_eq!(file_path.as_ref(), "src/main.rs");
+                assert_eq!(*line_number, Some(1));
+                assert!(options.can_refresh_base);
+                assert!(!options.can_refresh_target);
+            }
+            _ => panic!("Expected CommentsOpenOutdatedDiff event, got: {event:?}"),
+        }
+
+        // File comments don't depend on line positions
+        view.navigation_mode = NavigationMode::Files;
+        view.open_comments(&mut app);
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(
+            &*event,
+            Event::App(AppEvent::CommentsOpen {
+                line_number: None,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_open_difftool() {
+        let review = Review::builder()
+            .base_sha_str("abc")
+            .target_sha_str("def")
+            .build();
+        let mut view = ReviewDetailsView::new(review);
+        view.diff = create_jump_test_diff();
+        view.selected_file_index = 1;
+        let mut app = create_test_app().await;
+
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(
+                KeyCode::Char('d'),
+                ratatui::crossterm::event::KeyModifiers::empty(),
+            ),
+        )
+        .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::DifftoolOpen {
+                base_sha,
+                target_sha,
+                file_path,
+            }) => {
+                assert_eq!(base_sha.as_ref(), "abc");
+                assert_eq!(target_sha.as_ref(), "def");
+                assert_eq!(file_path.as_ref(), "src/lib.rs");
+            }
+            _ => panic!("Expected DifftoolOpen event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_difftool_error_notice() {
+        let review = Review::builder().build();
+        let mut view = ReviewDetailsView::new(review);
+        let mut app = create_test_app().await;
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::DifftoolError("No difftool configured".into()),
+        );
+        assert_eq!(view.notice.as_deref(), Some("No difftool configured"));
+
+        // The notice is dismissed with the next key press
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(
+                KeyCode::Char('j'),
+                ratatui::crossterm::event::KeyModifiers::empty(),
+            ),
+        )
+        .unwrap();
+        assert_eq!(view.notice, None);
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_copy_compare_url() {
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature")
+            .target_sha_str("def")
+            .build();
+        let mut view = ReviewDetailsView::new(review);
+        let mut app = create_test_app().await;
+
+        view.handle_key_events(
+            &mut app,
+            &KeyEvent::new(
+                KeyCode::Char('y'),
+                ratatui::crossterm::event::KeyModifiers::empty(),
+            ),
+        )
+        .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::CompareUrlCopy { base, target }) => {
+                assert_eq!(base.as_ref(), "main");
+                assert_eq!(target.as_ref(), "def");
+            }
+            _ => panic!("Expected CompareUrlCopy event, got: {event:?}"),
+        }
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ClipboardCopy("https://github.com/owner/repo/compare/main...def".into()),
+        );
+        assert_eq!(
+            view.notice.as_deref(),
+            Some("Copied https://github.com/owner/repo/compare/main...def")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_copy_compare_url_of_full_shas() {
+        let base_sha = "0123456789abcdef0123456789abcdef01234567";
+        let target_sha = "89abcdef0123456789abcdef0123456789abcdef";
+        let review = Review::builder()
+            .base_sha_str(base_sha)
+            .target_sha_str(target_sha)
+            .build();
+        let mut view = ReviewDetailsView::new(review);
+        let mut app = create_test_app().await;
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('y')))
+            .unwrap();
+
+        // The full SHAs are copied
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::CompareUrlCopy { base, target }) => {
+                assert_eq!(base.as_ref(), base_sha);
+                assert_eq!(target.as_ref(), target_sha);
+            }
+            _ => panic!("Expected CompareUrlCopy event, got: {event:?}"),
+        }
+
+        // But shown abbreviated unless full SHAs are shown
+        let copied: Arc<str> =
+            format!("https://github.com/owner/repo/compare/{base_sha}...{target_sha}").into();
+        view.handle_app_events(&mut app, &AppEvent::ClipboardCopy(Arc::clone(&copied)));
+        assert_eq!(
+            view.notice.as_deref(),
+            Some("Copied https://github.com/owner/repo/compare/0123456...89abcde")
+        );
+
+        app.settings.full_shas = true;
+        view.handle_app_events(&mut app, &AppEvent::ClipboardCopy(Arc::clone(&copied)));
+        assert_eq!(view.notice, Some(format!("Copied {copied}").into()));
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_render_full_shas() {
+        let review = Review::builder()
+            .base_branch("main")
+            .target_branch("feature")
+            .base_sha_str("0123456789abcdef0123456789abcdef01234567")
+            .target_sha_str("89abcdef0123456789abcdef0123456789abcdef")
+            .build();
+        let mut app = App {
+            view_stack: vec![Box::new(ReviewDetailsView::new(review))],
+            ..create_test_app().await
+        };
+
+        app.handle_key_events(&KeyEvent::from(KeyCode::Char('S')))
+            .unwrap();
+
+        assert!(app.settings.full_shas);
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_review_details_view_copy_git_diff_command() {
+        let review = Review::builder()
+            .base_sha_str("abc")
+            .target_sha_str("def")
+            .build();
+        let mut view = ReviewDetailsView::new(review).with_quick_review(Some(Arc::from("src/")));
+        let mut app = create_test_app().await;
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('D')))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::ClipboardCopy(text)) => {
+                assert_eq!(text.as_ref(), "git diff -M abc def -- src/");
+            }
+            _ => panic!("Expected ClipboardCopy event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_copy_selected_hunk() {
+        let mut view = ReviewDetailsView::new(Review::builder().build());
+        view.handle_git_diff_loading_state(&GitDiffLoadingState::Loaded(Arc::new(
+            Diff::from_files(vec![DiffFile::from_content(
+                "src/main.rs",
+                "@@ -1,2 +1,2 @@\n a\n-b\n+c\n@@ -10,1 +10,2 @@\n x\n+y",
+            )]),
+        )));
+        let mut app = create_test_app().await;
+        let key_event = KeyEvent::new(
+            KeyCode::Char('Y'),
+            ratatui::crossterm::event::KeyModifiers::empty(),
+        );
+
+        // Nothing is copied in Files mode
+        view.handle_key_events(&mut app, &key_event).unwrap();
+        assert!(!app.events.has_pending_events());
+
+        view.navigation_mode = NavigationMode::Lines;
+        for (selected_line_index, expected_hunk) in [
+            (2, "@@ -1,2 +1,2 @@\n a\n-b\n+c"),
+            (6, "@@ -10,1 +10,2 @@\n x\n+y"),
+        ] {
+            view.selected_line_index = selected_line_index;
+            view.handle_key_events(&mut app, &key_event).unwrap();
+
+            let event = app.events.try_recv().unwrap();
+            match &*event {
+                Event::App(AppEvent::ClipboardCopy(text)) => {
+                    assert!(text.starts_with("@@"));
+                    assert_eq!(text.as_ref(), expected_hunk);
+                }
+                _ => panic!("Expected ClipboardCopy event, got: {event:?}"),
+            }
+        }
+
+        view.handle_app_events(
+            &mut app,
+            &AppEvent::ClipboardCopy("@@ -10,1 +10,2 @@\n x\n+y".into()),
+        );
+        assert_eq!(view.notice.as_deref(), Some("Copied 3 lines"));
+    }
 }
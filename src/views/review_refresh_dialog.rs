@@ -30,6 +30,7 @@ enum RefreshAction {
     Target,
     Both,
     Duplicate,
+    CommentAnyway,
 }
 
 impl RefreshAction {
@@ -39,6 +40,7 @@ impl RefreshAction {
             RefreshAction::Target => "Refresh target SHA",
             RefreshAction::Both => "Refresh both SHAs",
             RefreshAction::Duplicate => "Duplicate review from current heads",
+            RefreshAction::CommentAnyway => "Comment anyway",
         }
     }
 
@@ -48,15 +50,24 @@ impl RefreshAction {
             RefreshAction::Target => 't',
             RefreshAction::Both => 'a',
             RefreshAction::Duplicate => 'd',
+            RefreshAction::CommentAnyway => 'c',
         }
     }
 }
 
+/// Comment that was about to be added when the review diff turned out to be outdated
+#[derive(Debug, Clone)]
+struct PendingComment {
+    file_path: Arc<str>,
+    line_number: Option<i64>,
+}
+
 pub struct ReviewRefreshDialogView {
     review_id: Arc<str>,
     options: ReviewRefreshOptions,
     actions: Arc<[RefreshAction]>,
     list_state: ListState,
+    pending_comment: Option<PendingComment>,
 }
 
 impl ReviewRefreshDialogView {
@@ -73,6 +84,7 @@ impl ReviewRefreshDialogView {
             RefreshAction::Target => options.can_refresh_target,
             RefreshAction::Both => options.can_refresh_base && options.can_refresh_target,
             RefreshAction::Duplicate => options.can_duplicate,
+            RefreshAction::CommentAnyway => false,
         }) {
             list_state.select(Some(selected));
         }
@@ -82,7 +94,27 @@ impl ReviewRefreshDialogView {
             options,
             actions,
             list_state,
+            pending_comment: None,
+        }
+    }
+
+    /// Show the dialog as a warning that the diff is outdated before commenting on it,
+    /// offering to open the comments anyway
+    pub fn with_pending_comment(mut self, file_path: Arc<str>, line_number: Option<i64>) -> Self {
+        self.actions = self
+            .actions
+            .iter()
+            .copied()
+            .chain([RefreshAction::CommentAnyway])
+            .collect();
+        if self.list_state.selected().is_none() {
+            self.list_state.select(Some(self.actions.len() - 1));
         }
+        self.pending_comment = Some(PendingComment {
+            file_path,
+            line_number,
+        });
+        self
     }
 
     fn select_next(&mut self) {
@@ -143,6 +175,7 @@ impl ReviewRefreshDialogView {
             RefreshAction::Target => self.options.can_refresh_target,
             RefreshAction::Both => self.options.can_refresh_base && self.options.can_refresh_target,
             RefreshAction::Duplicate => self.options.can_duplicate,
+            RefreshAction::CommentAnyway => self.pending_comment.is_some(),
         }
     }
 
@@ -168,6 +201,20 @@ impl ReviewRefreshDialogView {
                     review_id: Arc::clone(&self.review_id),
                 });
             }
+            RefreshAction::CommentAnyway => {
+                if let Some(pending_comment) = &self.pending_comment {
+                    // Close this dialog first, so that the comments view ends up on top
+                    app.events.send(AppEvent::ViewClose);
+                    app.events.send(AppEvent::CommentsOpen {
+                        review_id: Arc::clone(&self.review_id),
+                        file_path: Arc::clone(&pending_comment.file_path),
+                        line_number: pending_comment.line_number,
+                        // The diff is reloaded after a refresh, the title shows the current path
+                        old_file_path: None,
+                    });
+                }
+                return;
+            }
         }
         app.events.send(AppEvent::ViewClose);
     }
@@ -180,25 +227,20 @@ impl ViewHandler for ReviewRefreshDialogView {
 
     fn handle_key_events(&mut self, app: &mut App, key_event: &KeyEvent) -> color_eyre::Result<()> {
         match key_event.code {
-            KeyCode::Char('b') => {
-                if self.is_action_enabled(RefreshAction::Base) {
-                    self.trigger_action(app, RefreshAction::Base);
-                }
+            KeyCode::Char('b') if self.is_action_enabled(RefreshAction::Base) => {
+                self.trigger_action(app, RefreshAction::Base);
             }
-            KeyCode::Char('t') => {
-                if self.is_action_enabled(RefreshAction::Target) {
-                    self.trigger_action(app, RefreshAction::Target);
-                }
+            KeyCode::Char('t') if self.is_action_enabled(RefreshAction::Target) => {
+                self.trigger_action(app, RefreshAction::Target);
             }
-            KeyCode::Char('a') => {
-                if self.is_action_enabled(RefreshAction::Both) {
-                    self.trigger_action(app, RefreshAction::Both);
-                }
+            KeyCode::Char('a') if self.is_action_enabled(RefreshAction::Both) => {
+                self.trigger_action(app, RefreshAction::Both);
             }
-            KeyCode::Char('d') => {
-                if self.is_action_enabled(RefreshAction::Duplicate) {
-                    self.trigger_action(app, RefreshAction::Duplicate);
-                }
+            KeyCode::Char('d') if self.is_action_enabled(RefreshAction::Duplicate) => {
+                self.trigger_action(app, RefreshAction::Duplicate);
+            }
+            KeyCode::Char('c') if self.is_action_enabled(RefreshAction::CommentAnyway) => {
+                self.trigger_action(app, RefreshAction::CommentAnyway);
             }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.select_previous();
@@ -224,8 +266,13 @@ impl ViewHandler for ReviewRefreshDialogView {
         let popup_area = centered_rectangle(70, 40, area);
         Clear.render(popup_area, buf);
 
+        let title = if self.pending_comment.is_some() {
+            "Outdated Diff"
+        } else {
+            "Refresh Review"
+        };
         let block = Block::bordered()
-            .title("Refresh Review")
+            .title(title)
             .border_type(BorderType::Rounded)
             .style(Style::default().bg(Color::Black));
 
@@ -235,12 +282,24 @@ impl ViewHandler for ReviewRefreshDialogView {
         if self.options.can_refresh_base
             || self.options.can_refresh_target
             || self.options.can_duplicate
+            || self.pending_comment.is_some()
         {
+            let warning_height = if self.pending_comment.is_some() { 1 } else { 0 };
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Min(1), Constraint::Length(2)])
+                .constraints([
+                    Constraint::Length(warning_height),
+                    Constraint::Min(1),
+                    Constraint::Length(2),
+                ])
                 .split(inner);
 
+            if self.pending_comment.is_some() {
+                Paragraph::new("Diff outdated: line comments may land on wrong lines")
+                    .style(Style::default().fg(Color::Yellow))
+                    .render(chunks[0], buf);
+            }
+
             let items: Vec<ListItem> = self
                 .actions
                 .iter()
@@ -253,9 +312,9 @@ impl ViewHandler for ReviewRefreshDialogView {
                             RefreshAction::Base | RefreshAction::Target => {
                                 format!("{} (N/A because of up-to-date SHA)", action.label())
                             }
-                            RefreshAction::Both | RefreshAction::Duplicate => {
-                                action.label().to_string()
-                            }
+                            RefreshAction::Both
+                            | RefreshAction::Duplicate
+                            | RefreshAction::CommentAnyway => action.label().to_string(),
                         }
                     };
                     let key_label = if enabled {
@@ -277,12 +336,12 @@ impl ViewHandler for ReviewRefreshDialogView {
                 .highlight_symbol("► ");
 
             let mut list_state = self.list_state;
-            ratatui::widgets::StatefulWidget::render(list, chunks[0], buf, &mut list_state);
+            ratatui::widgets::StatefulWidget::render(list, chunks[1], buf, &mut list_state);
 
             let help_text =
                 Paragraph::new("Use ↑/↓ or j/k to navigate, Enter to select, Esc to cancel")
                     .style(Style::default().fg(Color::Gray));
-            help_text.render(chunks[1], buf);
+            help_text.render(chunks[2], buf);
         } else {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -373,6 +432,16 @@ impl ViewHandler for ReviewRefreshDialogView {
                     state: KeyEventState::empty(),
                 },
             },
+            KeyBinding {
+                key: "c".to_string(),
+                description: "Comment anyway (outdated diff)".to_string(),
+                key_event: KeyEvent {
+                    code: KeyCode::Char('c'),
+                    modifiers: KeyModifiers::empty(),
+                    kind: KeyEventKind::Press,
+                    state: KeyEventState::empty(),
+                },
+            },
             KeyBinding {
                 key: "Esc".to_string(),
                 description: "Cancel".to_string(),
@@ -435,6 +504,9 @@ mod tests {
             database,
             view_stack: vec![],
             repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
         }
     }
 
@@ -625,4 +697,84 @@ mod tests {
         });
         assert_snapshot!(backend);
     }
+
+    #[tokio::test]
+    async fn test_review_refresh_dialog_view_comment_anyway() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewRefreshDialogView::new(
+            Arc::from("review-1"),
+            ReviewRefreshOptions {
+                can_refresh_base: false,
+                can_refresh_target: true,
+                can_duplicate: true,
+            },
+        )
+        .with_pending_comment(Arc::from("src/main.rs"), Some(3));
+
+        let key_event = KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers: KeyModifiers::empty(),
+            kind: KeyEventKind::Press,
+            state: KeyEventState::empty(),
+        };
+        view.handle_key_events(&mut app, &key_event).unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ViewClose)));
+        let event = app.events.try_recv().unwrap();
+        match &*event {
+            Event::App(AppEvent::CommentsOpen {
+                review_id,
+                file_path,
+                line_number,
+                ..
+            }) => {
+                assert_eq!(review_id.as_ref(), "review-1");
+                assert_eq!(file_path.as_ref(), "src/main.rs");
+                assert_eq!(*line_number, Some(3));
+            }
+            _ => panic!("Expected CommentsOpen event, got: {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_refresh_dialog_view_comment_anyway_disabled_without_pending_comment() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewRefreshDialogView::new(
+            Arc::from("review-1"),
+            ReviewRefreshOptions {
+                can_refresh_base: true,
+                can_refresh_target: true,
+                can_duplicate: true,
+            },
+        );
+
+        let key_event = KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers: KeyModifiers::empty(),
+            kind: KeyEventKind::Press,
+            state: KeyEventState::empty(),
+        };
+        view.handle_key_events(&mut app, &key_event).unwrap();
+
+        assert!(!app.events.has_pending_events());
+    }
+
+    #[tokio::test]
+    async fn test_review_refresh_dialog_view_render_outdated_diff() {
+        let app = create_test_app().await;
+        let view = ReviewRefreshDialogView::new(
+            Arc::from("review-1"),
+            ReviewRefreshOptions {
+                can_refresh_base: false,
+                can_refresh_target: true,
+                can_duplicate: true,
+            },
+        )
+        .with_pending_comment(Arc::from("src/main.rs"), Some(3));
+        let backend = render_view_to_terminal_backend(&app, |app, area, buf| {
+            view.render(app, area, buf);
+        });
+        assert_snapshot!(backend);
+    }
 }
@@ -0,0 +1,241 @@
+#[cfg(test)]
+use std::any::Any;
+
+use std::sync::Arc;
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+
+use crate::{
+    app::App,
+    event::AppEvent,
+    models::ProgressMetric,
+    views::{KeyBinding, ViewHandler, ViewType, centered_rectangle},
+};
+
+/// Progress of a review when its details are closed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewSummary {
+    pub review_title: String,
+    /// Files of the diff that are marked as viewed
+    pub viewed_file_count: usize,
+    pub file_count: usize,
+    /// Comments added since the review details were opened
+    pub comments_added: usize,
+    pub unresolved_comment_count: usize,
+    pub comment_count: usize,
+}
+
+impl ReviewSummary {
+    /// Lines of the summary, starting with the progress by the given metric
+    fn lines(&self, progress_metric: ProgressMetric) -> Vec<Line<'_>> {
+        let outstanding = Style::default().fg(Color::Yellow);
+        let done = Style::default().fg(Color::Green);
+
+        let files_style = if self.viewed_file_count < self.file_count {
+            outstanding
+        } else {
+            done
+        };
+        let comments_style = if self.unresolved_comment_count > 0 {
+            outstanding
+        } else {
+            done
+        };
+        let files_line = Line::styled(
+            format!(
+                "{} of {} files viewed",
+                self.viewed_file_count, self.file_count
+            ),
+            files_style,
+        );
+        let comments_added_line = Line::from(format!("{} comment(s) added", self.comments_added));
+        match progress_metric {
+            ProgressMetric::ViewedFiles => vec![
+                Line::from(self.review_title.as_str()),
+                Line::from(""),
+                files_line,
+                comments_added_line,
+                Line::styled(
+                    format!("{} unresolved comment(s)", self.unresolved_comment_count),
+                    comments_style,
+                ),
+            ],
+            ProgressMetric::ResolvedComments => vec![
+                Line::from(self.review_title.as_str()),
+                Line::from(""),
+                Line::styled(
+                    format!(
+                        "{} of {} comments resolved",
+                        self.comment_count - self.unresolved_comment_count,
+                        self.comment_count
+                    ),
+                    comments_style,
+                ),
+                comments_added_line,
+                files_line,
+            ],
+        }
+    }
+}
+
+/// Popup with the summary of a review after closing its details, closed by any key
+pub struct ReviewSummaryView {
+    pub summary: Arc<ReviewSummary>,
+}
+
+impl ReviewSummaryView {
+    pub fn new(summary: Arc<ReviewSummary>) -> Self {
+        Self { summary }
+    }
+}
+
+impl ViewHandler for ReviewSummaryView {
+    fn view_type(&self) -> ViewType {
+        ViewType::ReviewSummary
+    }
+
+    fn handle_key_events(
+        &mut self,
+        app: &mut App,
+        _key_event: &KeyEvent,
+    ) -> color_eyre::Result<()> {
+        app.events.send(AppEvent::ViewClose);
+        Ok(())
+    }
+
+    fn render(&self, app: &App, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rectangle(50, 40, area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::bordered()
+            .title("Review Summary")
+            .border_type(BorderType::Rounded)
+            .style(Style::default().bg(Color::Black));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        Paragraph::new(self.summary.lines(app.settings.progress_metric))
+            .style(Style::default().fg(Color::White))
+            .render(chunks[0], buf);
+
+        Paragraph::new("Press any key to close")
+            .style(Style::default().fg(Color::Gray))
+            .render(chunks[1], buf);
+    }
+
+    #[cfg(test)]
+    fn debug_state(&self) -> String {
+        format!("ReviewSummaryView(summary: {:?})", self.summary)
+    }
+
+    #[cfg(test)]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_keybindings(&self) -> Arc<[KeyBinding]> {
+        Arc::new([KeyBinding {
+            key: "Any key".to_string(),
+            description: "Close".to_string(),
+            key_event: KeyEvent {
+                code: KeyCode::Esc,
+                modifiers: KeyModifiers::empty(),
+                kind: KeyEventKind::Press,
+                state: KeyEventState::empty(),
+            },
+        }])
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use insta::assert_snapshot;
+    use sqlx::SqlitePool;
+
+    use crate::{
+        database::Database,
+        event::{Event, EventHandler},
+        test_utils::render_app_to_terminal_backend,
+    };
+
+    async fn create_test_app() -> App {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        App {
+            running: true,
+            events: EventHandler::new_for_test(),
+            database: Database::from_pool(pool),
+            view_stack: vec![],
+            repo_path: ".".to_string(),
+            settings: Default::default(),
+            theme: Default::default(),
+            comment_drafts: Default::default(),
+        }
+    }
+
+    fn summary() -> Arc<ReviewSummary> {
+        Arc::new(ReviewSummary {
+            review_title: "main -> feature".to_string(),
+            viewed_file_count: 2,
+            file_count: 3,
+            comments_added: 1,
+            unresolved_comment_count: 4,
+            comment_count: 6,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_any_key_closes_view() {
+        let mut app = create_test_app().await;
+        let mut view = ReviewSummaryView::new(summary());
+
+        view.handle_key_events(&mut app, &KeyEvent::from(KeyCode::Char('x')))
+            .unwrap();
+
+        let event = app.events.try_recv().unwrap();
+        assert!(matches!(*event, Event::App(AppEvent::ViewClose)));
+    }
+
+    #[tokio::test]
+    async fn test_render_summary() {
+        let app = App {
+            view_stack: vec![Box::new(ReviewSummaryView::new(summary()))],
+            ..create_test_app().await
+        };
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+
+    #[tokio::test]
+    async fn test_render_summary_by_resolved_comments() {
+        let mut app = App {
+            view_stack: vec![Box::new(ReviewSummaryView::new(summary()))],
+            ..create_test_app().await
+        };
+        app.settings.progress_metric = ProgressMetric::ResolvedComments;
+
+        assert_snapshot!(render_app_to_terminal_backend(app));
+    }
+}
@@ -0,0 +1,106 @@
+//! Word level differences between a removed and an added line, to highlight what changed when
+//! they are shown paired.
+
+use std::ops::Range;
+
+/// Words (runs of alphanumeric characters and `_`) and the single other characters of a text,
+/// with their byte offsets
+fn tokens(text: &str) -> Vec<(usize, &str)> {
+    let mut tokens = vec![];
+    let mut word_start = None;
+    for (index, char) in text.char_indices() {
+        let is_word = char.is_alphanumeric() || char == '_';
+        if is_word {
+            word_start.get_or_insert(index);
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            tokens.push((start, &text[start..index]));
+        }
+        tokens.push((index, &text[index..index + char.len_utf8()]));
+    }
+    if let Some(start) = word_start {
+        tokens.push((start, &text[start..]));
+    }
+    tokens
+}
+
+/// Byte ranges of the changed parts of an old and a new text: everything between the tokens
+/// they start and end with in common. `None` when they have nothing in common at either end,
+/// they are too different to be shown as a change of each other then.
+pub fn changed_ranges(old: &str, new: &str) -> Option<(Range<usize>, Range<usize>)> {
+    let old_tokens = tokens(old);
+    let new_tokens = tokens(new);
+    let common_prefix = old_tokens
+        .iter()
+        .zip(&new_tokens)
+        .take_while(|((_, old), (_, new))| old == new)
+        .count();
+    let common_suffix = old_tokens
+        .iter()
+        .rev()
+        .zip(new_tokens.iter().rev())
+        .take(old_tokens.len().min(new_tokens.len()) - common_prefix)
+        .take_while(|((_, old), (_, new))| old == new)
+        .count();
+    let is_changed_entirely = common_prefix == 0 && common_suffix == 0;
+    if is_changed_entirely && !old_tokens.is_empty() && !new_tokens.is_empty() {
+        return None;
+    }
+
+    let changed_range = |tokens: &[(usize, &str)], text: &str| {
+        let start = tokens
+            .get(common_prefix)
+            .map_or(text.len(), |(start, _)| *start);
+        let end = match tokens.len().checked_sub(common_suffix + 1) {
+            Some(last) if last >= common_prefix => tokens[last].0 + tokens[last].1.len(),
+            _ => start,
+        };
+        start..end
+    };
+    Some((
+        changed_range(&old_tokens, old),
+        changed_range(&new_tokens, new),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(
+            tokens("let a_b = f(1);"),
+            vec![
+                (0, "let"),
+                (3, " "),
+                (4, "a_b"),
+                (7, " "),
+                (8, "="),
+                (9, " "),
+                (10, "f"),
+                (11, "("),
+                (12, "1"),
+                (13, ")"),
+                (14, ";")
+            ]
+        );
+        assert_eq!(tokens("größe"), vec![(0, "größe")]);
+    }
+
+    #[test]
+    fn test_changed_ranges() {
+        // Only the changed word is highlighted
+        assert_eq!(
+            changed_ranges("let total = 1;", "let total = 42;"),
+            Some((12..13, 12..14))
+        );
+        // Added and removed words leave an empty range on the other side
+        assert_eq!(changed_ranges("call(a)", "call(a, b)"), Some((6..6, 6..9)));
+        assert_eq!(changed_ranges("same", "same"), Some((4..4, 4..4)));
+        assert_eq!(changed_ranges("", "fn main() {}"), Some((0..0, 0..12)));
+        // Lines without anything in common at either end are not a change of each other
+        assert_eq!(changed_ranges("fn main() {", "use std::io;"), None);
+    }
+}